@@ -2,7 +2,10 @@ use std::{error::Error, fmt};
 
 use intern::Lookup;
 
-use crate::{text_with_carats::text_with_carats, SourceFileName, Span, WithSpan};
+use crate::{
+    text_with_carats::{line_and_column, text_with_carats},
+    SourceFileName, Span, WithSpan,
+};
 
 /// A source, which consists of a filename, and an optional span
 /// indicating the subset of the file which corresponds to the
@@ -40,12 +43,26 @@ pub struct EmbeddedLocation {
     pub span: Span,
 }
 
+/// Prints e.g. `path/to/file.ts:12:5`, followed by the surrounding lines of the
+/// file, with the span underlined. Lines and columns are relative to the start of
+/// the file, not to the iso literal (if any) that the span is relative to.
 impl std::fmt::Display for EmbeddedLocation {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        let (file_path, read_out_text) = self.text_source.read_to_string();
-        let text_with_carats = text_with_carats(&read_out_text, self.span);
+        let file_path = self.text_source.path.lookup();
+        let span = self.span_in_file();
+        // Empty spans are (for now) used for locations that are not known precisely,
+        // so pointing at them would be misleading.
+        if span.is_empty() {
+            return write!(f, "{}", file_path);
+        }
+        let Ok(file_contents) = std::fs::read_to_string(file_path) else {
+            return write!(f, "{}", file_path);
+        };
+
+        let (line, column) = line_and_column(&file_contents, span.start);
+        let text_with_carats = text_with_carats(&file_contents, span);
 
-        write!(f, "{}\n{}", file_path, text_with_carats)
+        write!(f, "{}:{}:{}\n{}", file_path, line, column, text_with_carats)
     }
 }
 
@@ -73,6 +90,15 @@ impl EmbeddedLocation {
     pub fn new(text_source: TextSource, span: Span) -> Self {
         EmbeddedLocation { text_source, span }
     }
+
+    /// The span relative to the start of the file, e.g. for editors, which do not
+    /// know about the iso literal that a span is relative to.
+    pub fn span_in_file(&self) -> Span {
+        match self.text_source.span {
+            Some(text_source_span) => self.span.with_offset(text_source_span.start),
+            None => self.span,
+        }
+    }
 }
 
 impl fmt::Display for Location {
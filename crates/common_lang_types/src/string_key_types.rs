@@ -90,6 +90,9 @@ string_key_conversion!(from: LinkedFieldAlias, to: FieldNameOrAlias);
 string_key_conversion!(from: SelectableFieldName, to: FieldNameOrAlias);
 
 string_key_newtype!(FilePath);
+// The name of a package declared in the config, by which the artifacts of the
+// client fields declared in that package are namespaced
+string_key_newtype!(ArtifactNamespace);
 string_key_newtype!(ConstExportName);
 
 // Operations
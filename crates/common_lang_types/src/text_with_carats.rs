@@ -13,14 +13,15 @@ static LINE_COUNT_BUFFER: usize = 3;
 
 /// For a given string and span, return a string with
 /// the span underlined with carats and LINE_COUNT_BUFFER previous and following
-/// lines.
+/// lines. Each line of source text is prefixed with its (1-based) line number.
 pub(crate) fn text_with_carats(text: &str, span: Span) -> String {
     // Major hack alert
     if span.is_empty() {
         return "".to_string();
     }
 
-    let mut output_lines = vec![];
+    // Source lines have a line number, carat lines do not
+    let mut output_lines: Vec<(Option<usize>, String)> = vec![];
     let mut cur_index = 0;
 
     // index of the line (in output_lines) of **source text** in which the span starts
@@ -29,8 +30,8 @@ pub(crate) fn text_with_carats(text: &str, span: Span) -> String {
     let mut last_line_with_span = 0;
 
     let mut span_state = SpanState::Before;
-    for line_content in text.split("\n").into_iter() {
-        output_lines.push(line_content.to_string());
+    for (line_index, line_content) in text.split("\n").enumerate() {
+        output_lines.push((Some(line_index + 1), line_content.to_string()));
 
         let start_of_line = cur_index;
 
@@ -86,7 +87,7 @@ pub(crate) fn text_with_carats(text: &str, span: Span) -> String {
                     carats.push(' ');
                 }
 
-                output_lines.push(carats);
+                output_lines.push((None, carats));
             }
         }
     }
@@ -96,7 +97,40 @@ pub(crate) fn text_with_carats(text: &str, span: Span) -> String {
     // - the carat line containing the end of the span and LINE_COUNT_BUFFER later lines
     // - everything in between
 
-    output_lines[(first_line_with_span.saturating_sub(LINE_COUNT_BUFFER))
-        ..(std::cmp::min(last_line_with_span + LINE_COUNT_BUFFER, output_lines.len()))]
+    let output_lines = &output_lines[(first_line_with_span.saturating_sub(LINE_COUNT_BUFFER))
+        ..(std::cmp::min(last_line_with_span + LINE_COUNT_BUFFER, output_lines.len()))];
+
+    let gutter_width = output_lines
+        .iter()
+        .filter_map(|(line_number, _)| *line_number)
+        .max()
+        .unwrap_or(0)
+        .to_string()
+        .len();
+    output_lines
+        .iter()
+        .map(|(line_number, line_content)| {
+            let line_number = line_number
+                .map(|line_number| line_number.to_string())
+                .unwrap_or_default();
+            format!("{line_number:>gutter_width$} | {line_content}")
+                .trim_end()
+                .to_string()
+        })
+        .collect::<Vec<_>>()
         .join("\n")
 }
+
+/// The (1-based) line and column of the character at offset, e.g. for printing
+/// `path/to/file.ts:12:5`. Columns are counted in characters, not bytes.
+pub(crate) fn line_and_column(text: &str, offset: u32) -> (usize, usize) {
+    let offset = std::cmp::min(offset as usize, text.len());
+    let preceding_text = text.get(..offset).unwrap_or(text);
+    let line = preceding_text.matches('\n').count() + 1;
+    let start_of_line = preceding_text
+        .rfind('\n')
+        .map(|index| index + 1)
+        .unwrap_or(0);
+    let column = preceding_text[start_of_line..].chars().count() + 1;
+    (line, column)
+}
@@ -4,12 +4,13 @@ use crate::{GraphQLDirective, TypeAnnotation};
 
 use super::{write_arguments, write_directives, ConstantValue};
 use common_lang_types::{
-    DescriptionValue, DirectiveName, EnumLiteralValue, GraphQLInterfaceTypeName,
-    GraphQLObjectTypeName, GraphQLScalarTypeName, GraphQLUnionTypeName, InputTypeName,
-    InputValueName, SelectableFieldName, UnvalidatedTypeName, WithLocation, WithSpan,
+    DescriptionValue, DirectiveName, EnumLiteralValue, GraphQLEnumTypeName,
+    GraphQLInputObjectTypeName, GraphQLInterfaceTypeName, GraphQLObjectTypeName,
+    GraphQLScalarTypeName, GraphQLUnionTypeName, InputTypeName, InputValueName,
+    SelectableFieldName, UnvalidatedTypeName, WithLocation, WithSpan,
 };
 use intern::{string_key::Intern, Lookup};
-use strum::EnumString;
+use strum::{Display, EnumString};
 
 // also Schema
 #[derive(Clone, Eq, PartialEq, Ord, PartialOrd, Debug)]
@@ -97,11 +98,11 @@ pub enum GraphQLTypeSystemExtensionOrDefinition {
 #[derive(Clone, Eq, PartialEq, Ord, PartialOrd, Debug)]
 pub enum GraphQLTypeSystemExtension {
     ObjectTypeExtension(GraphQLObjectTypeExtension),
-    // ScalarTypeExtension
-    // InterfaceTypeExtension
-    // UnionTypeExtension
-    // EnumTypeExtension
-    // InputObjectTypeExtension
+    ScalarTypeExtension(GraphQLScalarTypeExtension),
+    InterfaceTypeExtension(GraphQLInterfaceTypeExtension),
+    UnionTypeExtension(GraphQLUnionTypeExtension),
+    EnumTypeExtension(GraphQLEnumTypeExtension),
+    InputObjectTypeExtension(GraphQLInputObjectTypeExtension),
     // SchemaExtension
 }
 
@@ -111,6 +112,36 @@ impl From<GraphQLObjectTypeExtension> for GraphQLTypeSystemExtension {
     }
 }
 
+impl From<GraphQLScalarTypeExtension> for GraphQLTypeSystemExtension {
+    fn from(scalar_type_extension: GraphQLScalarTypeExtension) -> Self {
+        Self::ScalarTypeExtension(scalar_type_extension)
+    }
+}
+
+impl From<GraphQLInterfaceTypeExtension> for GraphQLTypeSystemExtension {
+    fn from(interface_type_extension: GraphQLInterfaceTypeExtension) -> Self {
+        Self::InterfaceTypeExtension(interface_type_extension)
+    }
+}
+
+impl From<GraphQLUnionTypeExtension> for GraphQLTypeSystemExtension {
+    fn from(union_type_extension: GraphQLUnionTypeExtension) -> Self {
+        Self::UnionTypeExtension(union_type_extension)
+    }
+}
+
+impl From<GraphQLEnumTypeExtension> for GraphQLTypeSystemExtension {
+    fn from(enum_type_extension: GraphQLEnumTypeExtension) -> Self {
+        Self::EnumTypeExtension(enum_type_extension)
+    }
+}
+
+impl From<GraphQLInputObjectTypeExtension> for GraphQLTypeSystemExtension {
+    fn from(input_object_type_extension: GraphQLInputObjectTypeExtension) -> Self {
+        Self::InputObjectTypeExtension(input_object_type_extension)
+    }
+}
+
 #[derive(Clone, Eq, PartialEq, Ord, PartialOrd, Debug)]
 pub struct GraphQLObjectTypeDefinition {
     pub description: Option<WithSpan<DescriptionValue>>,
@@ -128,6 +159,41 @@ pub struct GraphQLObjectTypeExtension {
     pub fields: Vec<WithLocation<GraphQLFieldDefinition>>,
 }
 
+#[derive(Clone, Eq, PartialEq, Ord, PartialOrd, Debug)]
+pub struct GraphQLScalarTypeExtension {
+    pub name: WithLocation<GraphQLScalarTypeName>,
+    pub directives: Vec<GraphQLDirective<ConstantValue>>,
+}
+
+#[derive(Clone, Eq, PartialEq, Ord, PartialOrd, Debug)]
+pub struct GraphQLInterfaceTypeExtension {
+    pub name: WithLocation<GraphQLInterfaceTypeName>,
+    pub interfaces: Vec<WithLocation<GraphQLInterfaceTypeName>>,
+    pub directives: Vec<GraphQLDirective<ConstantValue>>,
+    pub fields: Vec<WithLocation<GraphQLFieldDefinition>>,
+}
+
+#[derive(Clone, Eq, PartialEq, Ord, PartialOrd, Debug)]
+pub struct GraphQLUnionTypeExtension {
+    pub name: WithLocation<GraphQLUnionTypeName>,
+    pub directives: Vec<GraphQLDirective<ConstantValue>>,
+    pub union_member_types: Vec<WithLocation<GraphQLObjectTypeName>>,
+}
+
+#[derive(Clone, Eq, PartialEq, Ord, PartialOrd, Debug)]
+pub struct GraphQLEnumTypeExtension {
+    pub name: WithLocation<GraphQLEnumTypeName>,
+    pub directives: Vec<GraphQLDirective<ConstantValue>>,
+    pub enum_value_definitions: Vec<WithLocation<GraphQLEnumValueDefinition>>,
+}
+
+#[derive(Clone, Eq, PartialEq, Ord, PartialOrd, Debug)]
+pub struct GraphQLInputObjectTypeExtension {
+    pub name: WithLocation<GraphQLInputObjectTypeName>,
+    pub directives: Vec<GraphQLDirective<ConstantValue>>,
+    pub fields: Vec<WithLocation<GraphQLInputValueDefinition>>,
+}
+
 #[derive(Clone, Eq, PartialEq, Ord, PartialOrd, Debug)]
 pub struct GraphQLScalarTypeDefinition {
     pub description: Option<WithSpan<DescriptionValue>>,
@@ -162,7 +228,7 @@ pub struct GraphQLSchemaDefinition {
 }
 
 #[allow(unused)]
-#[derive(Clone, Copy, Eq, PartialEq, Ord, PartialOrd, Debug, EnumString)]
+#[derive(Clone, Copy, Eq, PartialEq, Ord, PartialOrd, Debug, EnumString, Display)]
 #[strum(serialize_all = "SCREAMING_SNAKE_CASE")]
 pub enum DirectiveLocation {
     Query,
@@ -244,8 +310,13 @@ pub struct GraphQLFieldDefinition {
 
 impl fmt::Display for GraphQLFieldDefinition {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        write!(f, "{}", self.name)?;
-        write_arguments(f, &self.arguments)?;
+        write!(f, "{}", self.name.item)?;
+        let arguments = self
+            .arguments
+            .iter()
+            .map(|argument| &argument.item)
+            .collect::<Vec<_>>();
+        write_arguments(f, &arguments)?;
         write!(f, ": {}", self.type_)?;
         write_directives(f, &self.directives)?;
         Ok(())
@@ -266,9 +337,9 @@ pub struct GraphQLInputValueDefinition {
 
 impl fmt::Display for GraphQLInputValueDefinition {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        write!(f, "{}: {}", self.name, self.type_)?;
+        write!(f, "{}: {}", self.name.item, self.type_)?;
         if let Some(v) = &self.default_value {
-            write!(f, " = {}", v)?;
+            write!(f, " = {}", v.item)?;
         }
 
         write_directives(f, &self.directives)?;
@@ -1,11 +1,13 @@
 mod directive;
 mod graphql_sdl;
+mod print_sdl;
 mod type_annotation;
 mod value;
 mod write;
 
 pub use directive::*;
 pub use graphql_sdl::*;
+pub use print_sdl::*;
 pub use type_annotation::*;
 pub use value::*;
 pub use write::*;
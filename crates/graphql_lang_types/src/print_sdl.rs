@@ -0,0 +1,267 @@
+use std::fmt::Write;
+
+use common_lang_types::{DescriptionValue, WithLocation, WithSpan};
+use intern::Lookup;
+
+use crate::{
+    GraphQLDirective, GraphQLEnumValueDefinition, GraphQLFieldDefinition,
+    GraphQLInputValueDefinition, GraphQLTypeSystemDefinition, GraphQLTypeSystemDocument,
+    GraphQLTypeSystemExtension, GraphQLTypeSystemExtensionDocument,
+    GraphQLTypeSystemExtensionOrDefinition, ValueType,
+};
+
+static INDENT: &str = "  ";
+
+/// Prints a type system document as SDL, e.g. to write a schema that was read from
+/// an introspection result to disk. Definitions are printed in order, separated by
+/// blank lines. Comments and formatting of the original SDL (if any) are not kept.
+pub fn print_type_system_document(document: &GraphQLTypeSystemDocument) -> String {
+    document
+        .iter()
+        .map(|definition| print_type_system_definition(&definition.item))
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// Prints a type system extension document (e.g. a schema extension) as SDL, in the
+/// same way as print_type_system_document.
+pub fn print_type_system_extension_document(
+    document: &GraphQLTypeSystemExtensionDocument,
+) -> String {
+    document
+        .0
+        .iter()
+        .map(
+            |extension_or_definition| match &extension_or_definition.item {
+                GraphQLTypeSystemExtensionOrDefinition::Definition(definition) => {
+                    print_type_system_definition(definition)
+                }
+                GraphQLTypeSystemExtensionOrDefinition::Extension(extension) => {
+                    print_type_system_extension(extension)
+                }
+            },
+        )
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+fn print_type_system_definition(definition: &GraphQLTypeSystemDefinition) -> String {
+    let mut s = String::new();
+    match definition {
+        GraphQLTypeSystemDefinition::ObjectTypeDefinition(object) => {
+            write_description(&mut s, &object.description, "");
+            write!(s, "type {}", object.name.item).unwrap();
+            write_implemented_interfaces(&mut s, &object.interfaces);
+            write_applied_directives(&mut s, &object.directives);
+            write_field_definitions(&mut s, &object.fields);
+        }
+        GraphQLTypeSystemDefinition::InterfaceTypeDefinition(interface) => {
+            write_description(&mut s, &interface.description, "");
+            write!(s, "interface {}", interface.name.item).unwrap();
+            write_implemented_interfaces(&mut s, &interface.interfaces);
+            write_applied_directives(&mut s, &interface.directives);
+            write_field_definitions(&mut s, &interface.fields);
+        }
+        GraphQLTypeSystemDefinition::ScalarTypeDefinition(scalar) => {
+            write_description(&mut s, &scalar.description, "");
+            write!(s, "scalar {}", scalar.name.item).unwrap();
+            write_applied_directives(&mut s, &scalar.directives);
+            s.push('\n');
+        }
+        GraphQLTypeSystemDefinition::UnionTypeDefinition(union) => {
+            write_description(&mut s, &union.description, "");
+            write!(s, "union {}", union.name.item).unwrap();
+            write_applied_directives(&mut s, &union.directives);
+            write_union_member_types(&mut s, &union.union_member_types);
+            s.push('\n');
+        }
+        GraphQLTypeSystemDefinition::EnumDefinition(enum_definition) => {
+            write_description(&mut s, &enum_definition.description, "");
+            write!(s, "enum {}", enum_definition.name.item).unwrap();
+            write_applied_directives(&mut s, &enum_definition.directives);
+            write_enum_value_definitions(&mut s, &enum_definition.enum_value_definitions);
+        }
+        GraphQLTypeSystemDefinition::InputObjectTypeDefinition(input_object) => {
+            write_description(&mut s, &input_object.description, "");
+            write!(s, "input {}", input_object.name.item).unwrap();
+            write_applied_directives(&mut s, &input_object.directives);
+            write_input_value_definitions(&mut s, &input_object.fields);
+        }
+        GraphQLTypeSystemDefinition::DirectiveDefinition(directive) => {
+            write_description(&mut s, &directive.description, "");
+            write!(s, "directive @{}", directive.name.item).unwrap();
+            write_argument_definitions(&mut s, &directive.arguments);
+            if directive.repeatable.is_some() {
+                s.push_str(" repeatable");
+            }
+            let locations = directive
+                .locations
+                .iter()
+                .map(|location| location.item.to_string())
+                .collect::<Vec<_>>();
+            writeln!(s, " on {}", locations.join(" | ")).unwrap();
+        }
+        GraphQLTypeSystemDefinition::SchemaDefinition(schema) => {
+            write_description(&mut s, &schema.description, "");
+            s.push_str("schema");
+            write_applied_directives(&mut s, &schema.directives);
+            s.push_str(" {\n");
+            for (operation, root_type) in [
+                ("query", &schema.query),
+                ("mutation", &schema.mutation),
+                ("subscription", &schema.subscription),
+            ] {
+                if let Some(root_type) = root_type {
+                    writeln!(s, "{INDENT}{operation}: {}", root_type.item).unwrap();
+                }
+            }
+            s.push_str("}\n");
+        }
+    }
+    s
+}
+
+fn print_type_system_extension(extension: &GraphQLTypeSystemExtension) -> String {
+    let mut s = String::new();
+    match extension {
+        GraphQLTypeSystemExtension::ObjectTypeExtension(object) => {
+            write!(s, "extend type {}", object.name.item).unwrap();
+            write_implemented_interfaces(&mut s, &object.interfaces);
+            write_applied_directives(&mut s, &object.directives);
+            write_field_definitions(&mut s, &object.fields);
+        }
+        GraphQLTypeSystemExtension::InterfaceTypeExtension(interface) => {
+            write!(s, "extend interface {}", interface.name.item).unwrap();
+            write_implemented_interfaces(&mut s, &interface.interfaces);
+            write_applied_directives(&mut s, &interface.directives);
+            write_field_definitions(&mut s, &interface.fields);
+        }
+        GraphQLTypeSystemExtension::ScalarTypeExtension(scalar) => {
+            write!(s, "extend scalar {}", scalar.name.item).unwrap();
+            write_applied_directives(&mut s, &scalar.directives);
+            s.push('\n');
+        }
+        GraphQLTypeSystemExtension::UnionTypeExtension(union) => {
+            write!(s, "extend union {}", union.name.item).unwrap();
+            write_applied_directives(&mut s, &union.directives);
+            write_union_member_types(&mut s, &union.union_member_types);
+            s.push('\n');
+        }
+        GraphQLTypeSystemExtension::EnumTypeExtension(enum_extension) => {
+            write!(s, "extend enum {}", enum_extension.name.item).unwrap();
+            write_applied_directives(&mut s, &enum_extension.directives);
+            write_enum_value_definitions(&mut s, &enum_extension.enum_value_definitions);
+        }
+        GraphQLTypeSystemExtension::InputObjectTypeExtension(input_object) => {
+            write!(s, "extend input {}", input_object.name.item).unwrap();
+            write_applied_directives(&mut s, &input_object.directives);
+            write_input_value_definitions(&mut s, &input_object.fields);
+        }
+    }
+    s
+}
+
+/// Descriptions are printed as block strings, so that they can span several lines.
+fn write_description(
+    s: &mut String,
+    description: &Option<WithSpan<DescriptionValue>>,
+    indent: &str,
+) {
+    if let Some(description) = description {
+        let description = description.item.lookup().replace("\"\"\"", "\\\"\"\"");
+        writeln!(s, "{indent}\"\"\"").unwrap();
+        for line in description.lines() {
+            writeln!(s, "{indent}{line}").unwrap();
+        }
+        writeln!(s, "{indent}\"\"\"").unwrap();
+    }
+}
+
+fn write_implemented_interfaces<T: std::fmt::Display>(
+    s: &mut String,
+    interfaces: &[WithLocation<T>],
+) {
+    if !interfaces.is_empty() {
+        let interfaces = interfaces
+            .iter()
+            .map(|interface| interface.item.to_string())
+            .collect::<Vec<_>>();
+        write!(s, " implements {}", interfaces.join(" & ")).unwrap();
+    }
+}
+
+fn write_union_member_types<T: std::fmt::Display>(s: &mut String, members: &[WithLocation<T>]) {
+    if !members.is_empty() {
+        let members = members
+            .iter()
+            .map(|member| member.item.to_string())
+            .collect::<Vec<_>>();
+        write!(s, " = {}", members.join(" | ")).unwrap();
+    }
+}
+
+fn write_applied_directives<T: ValueType>(s: &mut String, directives: &[GraphQLDirective<T>]) {
+    for directive in directives {
+        write!(s, " {}", directive).unwrap();
+    }
+}
+
+fn write_argument_definitions(
+    s: &mut String,
+    arguments: &[WithLocation<GraphQLInputValueDefinition>],
+) {
+    if !arguments.is_empty() {
+        let arguments = arguments
+            .iter()
+            .map(|argument| argument.item.to_string())
+            .collect::<Vec<_>>();
+        write!(s, "({})", arguments.join(", ")).unwrap();
+    }
+}
+
+fn write_field_definitions(s: &mut String, fields: &[WithLocation<GraphQLFieldDefinition>]) {
+    if fields.is_empty() {
+        s.push('\n');
+        return;
+    }
+    s.push_str(" {\n");
+    for field in fields {
+        write_description(s, &field.item.description, INDENT);
+        writeln!(s, "{INDENT}{}", field.item).unwrap();
+    }
+    s.push_str("}\n");
+}
+
+fn write_input_value_definitions(
+    s: &mut String,
+    input_values: &[WithLocation<GraphQLInputValueDefinition>],
+) {
+    if input_values.is_empty() {
+        s.push('\n');
+        return;
+    }
+    s.push_str(" {\n");
+    for input_value in input_values {
+        write_description(s, &input_value.item.description, INDENT);
+        writeln!(s, "{INDENT}{}", input_value.item).unwrap();
+    }
+    s.push_str("}\n");
+}
+
+fn write_enum_value_definitions(
+    s: &mut String,
+    enum_values: &[WithLocation<GraphQLEnumValueDefinition>],
+) {
+    if enum_values.is_empty() {
+        s.push('\n');
+        return;
+    }
+    s.push_str(" {\n");
+    for enum_value in enum_values {
+        write_description(s, &enum_value.item.description, INDENT);
+        write!(s, "{INDENT}{}", enum_value.item.value.item).unwrap();
+        write_applied_directives(s, &enum_value.item.directives);
+        s.push('\n');
+    }
+    s.push_str("}\n");
+}
@@ -66,6 +66,18 @@ impl<TValue> TypeAnnotation<TValue> {
             },
         }
     }
+
+    /// Whether this is a list type, i.e. of the form [X] or [X]!
+    pub fn is_list(&self) -> bool {
+        match self {
+            TypeAnnotation::Named(_) => false,
+            TypeAnnotation::List(_) => true,
+            TypeAnnotation::NonNull(non_null) => match non_null.as_ref() {
+                NonNullTypeAnnotation::Named(_) => false,
+                NonNullTypeAnnotation::List(_) => true,
+            },
+        }
+    }
 }
 
 impl<TValue: fmt::Display> fmt::Display for TypeAnnotation<TValue> {
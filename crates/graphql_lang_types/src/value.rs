@@ -62,7 +62,7 @@ impl fmt::Display for ConstantValue {
                 "[{}]",
                 value
                     .iter()
-                    .map(|item| item.to_string())
+                    .map(|item| item.item.to_string())
                     .collect::<Vec<String>>()
                     .join(", ")
             )),
@@ -139,6 +139,6 @@ impl<TName, TValue: ValueType> NameValuePair<TName, TValue> {
 
 impl<TName: fmt::Display, TValue: ValueType> fmt::Display for NameValuePair<TName, TValue> {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        f.write_fmt(format_args!("{}: {}", self.name, self.value))
+        f.write_fmt(format_args!("{}: {}", self.name.item, self.value.item))
     }
 }
@@ -0,0 +1,509 @@
+use std::str::FromStr;
+
+use common_lang_types::{
+    DescriptionValue, EmbeddedLocation, Location, Span, TextSource, WithEmbeddedLocation,
+    WithLocation, WithSpan,
+};
+use graphql_lang_types::{
+    ConstantValue, DirectiveLocation, GraphQLDirective, GraphQLDirectiveDefinition,
+    GraphQLEnumDefinition, GraphQLEnumValueDefinition, GraphQLFieldDefinition,
+    GraphQLInputObjectTypeDefinition, GraphQLInputValueDefinition, GraphQLInterfaceTypeDefinition,
+    GraphQLObjectTypeDefinition, GraphQLScalarTypeDefinition, GraphQLSchemaDefinition,
+    GraphQLTypeSystemDefinition, GraphQLTypeSystemDocument, GraphQLUnionTypeDefinition,
+    ListTypeAnnotation, NameValuePair, NamedTypeAnnotation, NonNullTypeAnnotation, TypeAnnotation,
+};
+use intern::string_key::{Intern, StringKey};
+use serde::Deserialize;
+
+use crate::{parse_constant_value_text, ParseResult, SchemaParseError};
+
+/// Types that every GraphQL schema has, and which are thus already known to the
+/// compiler. Types whose names start with __ are skipped as well.
+const BUILT_IN_SCALARS: [&str; 5] = ["ID", "String", "Int", "Float", "Boolean"];
+
+/// Directives that are defined by the GraphQL spec, and which are thus already known
+/// to the compiler.
+const BUILT_IN_DIRECTIVES: [&str; 5] = ["skip", "include", "deprecated", "specifiedBy", "oneOf"];
+
+#[derive(Deserialize)]
+struct IntrospectionResult {
+    data: Option<IntrospectionData>,
+    #[serde(rename = "__schema")]
+    schema: Option<IntrospectionSchema>,
+}
+
+#[derive(Deserialize)]
+struct IntrospectionData {
+    #[serde(rename = "__schema")]
+    schema: IntrospectionSchema,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct IntrospectionSchema {
+    description: Option<String>,
+    query_type: Option<IntrospectionNamedTypeRef>,
+    mutation_type: Option<IntrospectionNamedTypeRef>,
+    subscription_type: Option<IntrospectionNamedTypeRef>,
+    types: Vec<IntrospectionType>,
+    #[serde(default)]
+    directives: Vec<IntrospectionDirective>,
+}
+
+#[derive(Deserialize)]
+struct IntrospectionNamedTypeRef {
+    name: String,
+}
+
+#[derive(Deserialize, Clone, Copy)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+enum IntrospectionTypeKind {
+    Scalar,
+    Object,
+    Interface,
+    Union,
+    Enum,
+    InputObject,
+    List,
+    NonNull,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct IntrospectionType {
+    kind: IntrospectionTypeKind,
+    name: String,
+    description: Option<String>,
+    fields: Option<Vec<IntrospectionField>>,
+    input_fields: Option<Vec<IntrospectionInputValue>>,
+    interfaces: Option<Vec<IntrospectionNamedTypeRef>>,
+    enum_values: Option<Vec<IntrospectionEnumValue>>,
+    possible_types: Option<Vec<IntrospectionNamedTypeRef>>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct IntrospectionField {
+    name: String,
+    description: Option<String>,
+    #[serde(default)]
+    args: Vec<IntrospectionInputValue>,
+    #[serde(rename = "type")]
+    type_: IntrospectionTypeRef,
+    #[serde(default)]
+    is_deprecated: bool,
+    deprecation_reason: Option<String>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct IntrospectionInputValue {
+    name: String,
+    description: Option<String>,
+    #[serde(rename = "type")]
+    type_: IntrospectionTypeRef,
+    /// The default value, printed as a GraphQL value, e.g. `"[1, 2]"`
+    default_value: Option<String>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct IntrospectionEnumValue {
+    name: String,
+    description: Option<String>,
+    #[serde(default)]
+    is_deprecated: bool,
+    deprecation_reason: Option<String>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct IntrospectionDirective {
+    name: String,
+    description: Option<String>,
+    locations: Vec<String>,
+    #[serde(default)]
+    args: Vec<IntrospectionInputValue>,
+    #[serde(default)]
+    is_repeatable: bool,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct IntrospectionTypeRef {
+    kind: IntrospectionTypeKind,
+    name: Option<String>,
+    of_type: Option<Box<IntrospectionTypeRef>>,
+}
+
+/// Parses the result of the standard introspection query (i.e. JSON with the shape
+/// `{ "data": { "__schema": ... } }`, or just `{ "__schema": ... }`), as printed by
+/// e.g. graphql-codegen or Apollo, into the same document that parse_schema returns
+/// for the equivalent SDL.
+///
+/// Introspection results contain no locations, so errors in definitions point to
+/// the file as a whole. Built-in scalars and directives and introspection types
+/// are skipped.
+pub fn parse_introspection_json(
+    source: &str,
+    text_source: TextSource,
+) -> ParseResult<GraphQLTypeSystemDocument> {
+    let introspection_result: IntrospectionResult =
+        serde_json::from_str(source).map_err(|error| {
+            let offset = offset_of_line_and_column(source, error.line(), error.column());
+            WithSpan::new(
+                SchemaParseError::InvalidIntrospectionJson {
+                    message: error.to_string(),
+                },
+                Span::new(offset, offset),
+            )
+        })?;
+    let schema = introspection_result
+        .data
+        .map(|data| data.schema)
+        .or(introspection_result.schema)
+        .ok_or_else(|| {
+            WithSpan::new(
+                SchemaParseError::InvalidIntrospectionJson {
+                    message: "Expected the result to contain __schema".to_string(),
+                },
+                Span::todo_generated(),
+            )
+        })?;
+
+    let converter = IntrospectionConverter { text_source };
+    let mut definitions = vec![converter.with_location(converter.schema_definition(&schema))];
+
+    for type_ in schema.types.iter() {
+        if type_.name.starts_with("__") || BUILT_IN_SCALARS.contains(&type_.name.as_str()) {
+            continue;
+        }
+        definitions.push(converter.with_location(converter.type_definition(type_)?));
+    }
+
+    for directive in schema.directives.iter() {
+        if BUILT_IN_DIRECTIVES.contains(&directive.name.as_str()) {
+            continue;
+        }
+        definitions.push(converter.with_location(
+            GraphQLTypeSystemDefinition::DirectiveDefinition(
+                converter.directive_definition(directive)?,
+            ),
+        ));
+    }
+
+    Ok(GraphQLTypeSystemDocument(definitions))
+}
+
+struct IntrospectionConverter {
+    text_source: TextSource,
+}
+
+impl IntrospectionConverter {
+    fn location(&self) -> Location {
+        Location::new(self.text_source, Span::todo_generated())
+    }
+
+    fn with_location<T>(&self, item: T) -> WithLocation<T> {
+        WithLocation::new(item, self.location())
+    }
+
+    fn name<T: From<StringKey>>(&self, name: &str) -> WithLocation<T> {
+        self.with_location(name.intern().into())
+    }
+
+    fn description(&self, description: &Option<String>) -> Option<WithSpan<DescriptionValue>> {
+        description
+            .as_ref()
+            .map(|description| WithSpan::new(description.intern().into(), Span::todo_generated()))
+    }
+
+    fn schema_definition(&self, schema: &IntrospectionSchema) -> GraphQLTypeSystemDefinition {
+        let root_type = |root_type: &Option<IntrospectionNamedTypeRef>| {
+            root_type
+                .as_ref()
+                .map(|root_type| self.name(&root_type.name))
+        };
+        GraphQLTypeSystemDefinition::SchemaDefinition(GraphQLSchemaDefinition {
+            description: self.description(&schema.description),
+            query: root_type(&schema.query_type),
+            subscription: root_type(&schema.subscription_type),
+            mutation: root_type(&schema.mutation_type),
+            directives: vec![],
+        })
+    }
+
+    fn type_definition(
+        &self,
+        type_: &IntrospectionType,
+    ) -> ParseResult<GraphQLTypeSystemDefinition> {
+        let description = self.description(&type_.description);
+        let name = &type_.name;
+        let interfaces = || {
+            type_
+                .interfaces
+                .iter()
+                .flatten()
+                .map(|interface| self.name(&interface.name))
+                .collect()
+        };
+        let fields = || {
+            type_
+                .fields
+                .iter()
+                .flatten()
+                .map(|field| self.field_definition(field))
+                .collect::<ParseResult<Vec<_>>>()
+        };
+        let input_fields = || {
+            type_
+                .input_fields
+                .iter()
+                .flatten()
+                .map(|input_field| self.input_value_definition(input_field))
+                .collect::<ParseResult<Vec<_>>>()
+        };
+
+        Ok(match type_.kind {
+            IntrospectionTypeKind::Scalar => GraphQLScalarTypeDefinition {
+                description,
+                name: self.name(name),
+                directives: vec![],
+            }
+            .into(),
+            IntrospectionTypeKind::Object => GraphQLObjectTypeDefinition {
+                description,
+                name: self.name(name),
+                interfaces: interfaces(),
+                directives: vec![],
+                fields: fields()?,
+            }
+            .into(),
+            IntrospectionTypeKind::Interface => GraphQLInterfaceTypeDefinition {
+                description,
+                name: self.name(name),
+                interfaces: interfaces(),
+                directives: vec![],
+                fields: fields()?,
+            }
+            .into(),
+            IntrospectionTypeKind::Union => GraphQLUnionTypeDefinition {
+                description,
+                name: self.name(name),
+                directives: vec![],
+                union_member_types: type_
+                    .possible_types
+                    .iter()
+                    .flatten()
+                    .map(|possible_type| self.name(&possible_type.name))
+                    .collect(),
+            }
+            .into(),
+            IntrospectionTypeKind::Enum => GraphQLEnumDefinition {
+                description,
+                name: self.name(name),
+                directives: vec![],
+                enum_value_definitions: type_
+                    .enum_values
+                    .iter()
+                    .flatten()
+                    .map(|enum_value| {
+                        self.with_location(GraphQLEnumValueDefinition {
+                            description: self.description(&enum_value.description),
+                            value: self.name(&enum_value.name),
+                            directives: self.deprecated_directive(
+                                enum_value.is_deprecated,
+                                &enum_value.deprecation_reason,
+                            ),
+                        })
+                    })
+                    .collect(),
+            }
+            .into(),
+            IntrospectionTypeKind::InputObject => GraphQLInputObjectTypeDefinition {
+                description,
+                name: self.name(name),
+                directives: vec![],
+                fields: input_fields()?,
+            }
+            .into(),
+            IntrospectionTypeKind::List | IntrospectionTypeKind::NonNull => {
+                return Err(self.invalid_type_reference(name))
+            }
+        })
+    }
+
+    fn field_definition(
+        &self,
+        field: &IntrospectionField,
+    ) -> ParseResult<WithLocation<GraphQLFieldDefinition>> {
+        Ok(self.with_location(GraphQLFieldDefinition {
+            description: self.description(&field.description),
+            name: self.name(&field.name),
+            type_: self.type_annotation(&field.type_, &field.name)?,
+            arguments: field
+                .args
+                .iter()
+                .map(|argument| self.input_value_definition(argument))
+                .collect::<ParseResult<_>>()?,
+            directives: self.deprecated_directive(field.is_deprecated, &field.deprecation_reason),
+        }))
+    }
+
+    fn input_value_definition(
+        &self,
+        input_value: &IntrospectionInputValue,
+    ) -> ParseResult<WithLocation<GraphQLInputValueDefinition>> {
+        let default_value = input_value
+            .default_value
+            .as_ref()
+            .map(|default_value| {
+                // The spans of default values are relative to the default value, not to
+                // the introspection result, so they are discarded.
+                parse_constant_value_text(default_value, self.text_source)
+                    .map(|constant_value| self.with_location(constant_value.item))
+                    .map_err(|_| {
+                        WithSpan::new(
+                            SchemaParseError::InvalidIntrospectionDefaultValue {
+                                name: input_value.name.clone(),
+                                default_value: default_value.clone(),
+                            },
+                            Span::todo_generated(),
+                        )
+                    })
+            })
+            .transpose()?;
+
+        Ok(self.with_location(GraphQLInputValueDefinition {
+            description: self.description(&input_value.description),
+            name: self.name(&input_value.name),
+            type_: self.type_annotation(&input_value.type_, &input_value.name)?,
+            default_value,
+            directives: vec![],
+        }))
+    }
+
+    fn directive_definition(
+        &self,
+        directive: &IntrospectionDirective,
+    ) -> ParseResult<GraphQLDirectiveDefinition> {
+        let locations = directive
+            .locations
+            .iter()
+            .map(|location| {
+                DirectiveLocation::from_str(location)
+                    .map(|location| WithSpan::new(location, Span::todo_generated()))
+                    .map_err(|_| {
+                        WithSpan::new(
+                            SchemaParseError::ExpectedDirectiveLocation {
+                                text: location.clone(),
+                            },
+                            Span::todo_generated(),
+                        )
+                    })
+            })
+            .collect::<ParseResult<_>>()?;
+
+        Ok(GraphQLDirectiveDefinition {
+            description: self.description(&directive.description),
+            name: self.name(&directive.name),
+            arguments: directive
+                .args
+                .iter()
+                .map(|argument| self.input_value_definition(argument))
+                .collect::<ParseResult<_>>()?,
+            repeatable: directive
+                .is_repeatable
+                .then(|| WithSpan::new((), Span::todo_generated())),
+            locations,
+        })
+    }
+
+    fn type_annotation<T: From<StringKey>>(
+        &self,
+        type_ref: &IntrospectionTypeRef,
+        field_name: &str,
+    ) -> ParseResult<TypeAnnotation<T>> {
+        match type_ref.kind {
+            IntrospectionTypeKind::NonNull => {
+                let inner = type_ref
+                    .of_type
+                    .as_ref()
+                    .ok_or_else(|| self.invalid_type_reference(field_name))?;
+                Ok(TypeAnnotation::NonNull(Box::new(
+                    match self.type_annotation(inner, field_name)? {
+                        TypeAnnotation::Named(named) => NonNullTypeAnnotation::Named(named),
+                        TypeAnnotation::List(list) => NonNullTypeAnnotation::List(*list),
+                        TypeAnnotation::NonNull(_) => {
+                            return Err(self.invalid_type_reference(field_name))
+                        }
+                    },
+                )))
+            }
+            IntrospectionTypeKind::List => {
+                let inner = type_ref
+                    .of_type
+                    .as_ref()
+                    .ok_or_else(|| self.invalid_type_reference(field_name))?;
+                Ok(TypeAnnotation::List(Box::new(ListTypeAnnotation(
+                    self.type_annotation(inner, field_name)?,
+                ))))
+            }
+            _ => {
+                let name = type_ref
+                    .name
+                    .as_ref()
+                    .ok_or_else(|| self.invalid_type_reference(field_name))?;
+                Ok(TypeAnnotation::Named(NamedTypeAnnotation(WithSpan::new(
+                    name.intern().into(),
+                    Span::todo_generated(),
+                ))))
+            }
+        }
+    }
+
+    /// The @deprecated directive, if the field or enum value is deprecated, as
+    /// introspection results do not contain the directives that are applied in the
+    /// schema.
+    fn deprecated_directive(
+        &self,
+        is_deprecated: bool,
+        deprecation_reason: &Option<String>,
+    ) -> Vec<GraphQLDirective<ConstantValue>> {
+        if !is_deprecated {
+            return vec![];
+        }
+        vec![GraphQLDirective {
+            name: WithEmbeddedLocation::new(
+                "deprecated".intern().into(),
+                EmbeddedLocation::new(self.text_source, Span::todo_generated()),
+            ),
+            arguments: deprecation_reason
+                .iter()
+                .map(|reason| NameValuePair {
+                    name: self.name("reason"),
+                    value: self.with_location(ConstantValue::String(reason.intern().into())),
+                })
+                .collect(),
+        }]
+    }
+
+    fn invalid_type_reference(&self, name: &str) -> WithSpan<SchemaParseError> {
+        WithSpan::new(
+            SchemaParseError::InvalidIntrospectionTypeReference {
+                name: name.to_string(),
+            },
+            Span::todo_generated(),
+        )
+    }
+}
+
+/// The byte offset of a (1-based) line and column, as reported by serde_json.
+fn offset_of_line_and_column(source: &str, line: usize, column: usize) -> u32 {
+    let line_start: usize = source
+        .split_inclusive('\n')
+        .take(line.saturating_sub(1))
+        .map(str::len)
+        .sum();
+    (line_start + column.saturating_sub(1)).min(source.len()) as u32
+}
@@ -1,8 +1,10 @@
 pub mod description;
+mod introspection;
 mod parse_schema;
 mod peekable_lexer;
 pub mod schema_parse_error;
 
+pub use introspection::*;
 pub use parse_schema::*;
 pub use peekable_lexer::*;
 pub use schema_parse_error::*;
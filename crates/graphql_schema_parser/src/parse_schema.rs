@@ -12,14 +12,15 @@ use intern::{
 
 use graphql_lang_types::{
     ConstantValue, DirectiveLocation, GraphQLDirective, GraphQLDirectiveDefinition,
-    GraphQLEnumDefinition, GraphQLEnumValueDefinition, GraphQLFieldDefinition,
-    GraphQLInputObjectTypeDefinition, GraphQLInputValueDefinition, GraphQLInterfaceTypeDefinition,
+    GraphQLEnumDefinition, GraphQLEnumTypeExtension, GraphQLEnumValueDefinition,
+    GraphQLFieldDefinition, GraphQLInputObjectTypeDefinition, GraphQLInputObjectTypeExtension,
+    GraphQLInputValueDefinition, GraphQLInterfaceTypeDefinition, GraphQLInterfaceTypeExtension,
     GraphQLObjectTypeDefinition, GraphQLObjectTypeExtension, GraphQLScalarTypeDefinition,
-    GraphQLSchemaDefinition, GraphQLTypeSystemDefinition, GraphQLTypeSystemDocument,
-    GraphQLTypeSystemExtension, GraphQLTypeSystemExtensionDocument,
-    GraphQLTypeSystemExtensionOrDefinition, GraphQLUnionTypeDefinition, ListTypeAnnotation,
-    NameValuePair, NamedTypeAnnotation, NonNullTypeAnnotation, RootOperationKind, TypeAnnotation,
-    ValueType,
+    GraphQLScalarTypeExtension, GraphQLSchemaDefinition, GraphQLTypeSystemDefinition,
+    GraphQLTypeSystemDocument, GraphQLTypeSystemExtension, GraphQLTypeSystemExtensionDocument,
+    GraphQLTypeSystemExtensionOrDefinition, GraphQLUnionTypeDefinition, GraphQLUnionTypeExtension,
+    ListTypeAnnotation, NameValuePair, NamedTypeAnnotation, NonNullTypeAnnotation,
+    RootOperationKind, TypeAnnotation, ValueType,
 };
 
 use crate::ParseResult;
@@ -50,6 +51,70 @@ fn parse_type_system_document(
     Ok(GraphQLTypeSystemDocument(type_system_definitions))
 }
 
+/// Parses a schema like parse_schema, but instead of stopping at the first error,
+/// skips to the next top-level definition after each error, so that every error in
+/// the schema is reported at once.
+pub fn parse_schema_with_error_recovery(
+    source: &str,
+    text_source: TextSource,
+) -> Result<GraphQLTypeSystemDocument, Vec<WithSpan<SchemaParseError>>> {
+    let mut tokens = PeekableLexer::new(source);
+
+    let mut type_system_definitions = vec![];
+    let mut errors = vec![];
+    while !tokens.reached_eof() {
+        let span_before_definition = tokens.peek().span;
+        match parse_type_system_definition(&mut tokens, text_source) {
+            Ok(type_system_definition) => type_system_definitions.push(type_system_definition),
+            Err(error) => {
+                errors.push(error);
+                // Always make progress, even if the definition failed on its first token
+                if tokens.peek().span == span_before_definition {
+                    tokens.parse_token();
+                }
+                skip_to_next_top_level_definition(&mut tokens);
+            }
+        }
+    }
+
+    if errors.is_empty() {
+        Ok(GraphQLTypeSystemDocument(type_system_definitions))
+    } else {
+        Err(errors)
+    }
+}
+
+/// Skips tokens until the start of what is likely the next top-level definition,
+/// i.e. a keyword or a description that is not indented. (Fields, arguments and
+/// enum values are conventionally indented.)
+fn skip_to_next_top_level_definition(tokens: &mut PeekableLexer) {
+    while !tokens.reached_eof() {
+        if tokens.next_token_is_at_start_of_line() {
+            let next_token = tokens.peek();
+            let starts_definition = match next_token.item {
+                TokenKind::StringLiteral | TokenKind::BlockStringLiteral => true,
+                TokenKind::Identifier => matches!(
+                    tokens.source(next_token.span),
+                    "type"
+                        | "scalar"
+                        | "interface"
+                        | "input"
+                        | "directive"
+                        | "enum"
+                        | "union"
+                        | "schema"
+                        | "extend"
+                ),
+                _ => false,
+            };
+            if starts_definition {
+                return;
+            }
+        }
+        tokens.parse_token();
+    }
+}
+
 pub fn parse_schema_extensions(
     source: &str,
     text_source: TextSource,
@@ -110,6 +175,16 @@ fn parse_type_system_extension(
             match identifier.item {
                 "type" => parse_object_type_extension(tokens, text_source)
                     .map(GraphQLTypeSystemExtension::from),
+                "scalar" => parse_scalar_type_extension(tokens, text_source)
+                    .map(GraphQLTypeSystemExtension::from),
+                "interface" => parse_interface_type_extension(tokens, text_source)
+                    .map(GraphQLTypeSystemExtension::from),
+                "input" => parse_input_object_type_extension(tokens, text_source)
+                    .map(GraphQLTypeSystemExtension::from),
+                "enum" => parse_enum_type_extension(tokens, text_source)
+                    .map(GraphQLTypeSystemExtension::from),
+                "union" => parse_union_type_extension(tokens, text_source)
+                    .map(GraphQLTypeSystemExtension::from),
                 _ => Err(WithSpan::new(
                     SchemaParseError::TopLevelSchemaDeclarationExpected {
                         found_text: identifier.to_string(),
@@ -233,6 +308,28 @@ fn parse_interface_type_definition(
     })
 }
 
+/// The state of the PeekableLexer is that it has processed the "interface" keyword
+fn parse_interface_type_extension(
+    tokens: &mut PeekableLexer,
+    text_source: TextSource,
+) -> ParseResult<GraphQLInterfaceTypeExtension> {
+    let name = tokens
+        .parse_string_key_type(TokenKind::Identifier)
+        .map_err(|with_span| with_span.map(SchemaParseError::from))?
+        .to_with_location(text_source);
+
+    let interfaces = parse_implements_interfaces_if_present(tokens, text_source)?;
+    let directives = parse_constant_directives(tokens, text_source)?;
+    let fields = parse_optional_fields(tokens, text_source)?;
+
+    Ok(GraphQLInterfaceTypeExtension {
+        name,
+        interfaces,
+        directives,
+        fields,
+    })
+}
+
 fn parse_input_object_type_definition(
     tokens: &mut PeekableLexer,
     description: Option<WithSpan<DescriptionValue>>,
@@ -260,6 +357,32 @@ fn parse_input_object_type_definition(
     })
 }
 
+/// The state of the PeekableLexer is that it has processed the "input" keyword
+fn parse_input_object_type_extension(
+    tokens: &mut PeekableLexer,
+    text_source: TextSource,
+) -> ParseResult<GraphQLInputObjectTypeExtension> {
+    let name = tokens
+        .parse_string_key_type(TokenKind::Identifier)
+        .map_err(|with_span| with_span.map(SchemaParseError::from))?
+        .to_with_location(text_source);
+
+    let directives = parse_constant_directives(tokens, text_source)?;
+    let fields = parse_optional_enclosed_items(
+        tokens,
+        text_source,
+        TokenKind::OpenBrace,
+        TokenKind::CloseBrace,
+        parse_argument_definition,
+    )?;
+
+    Ok(GraphQLInputObjectTypeExtension {
+        name,
+        directives,
+        fields,
+    })
+}
+
 /// The state of the PeekableLexer is that it has processed the "directive" keyword
 fn parse_directive_definition(
     tokens: &mut PeekableLexer,
@@ -360,6 +483,27 @@ fn parse_enum_definition(
     })
 }
 
+/// The state of the PeekableLexer is that it has processed the "enum" keyword
+fn parse_enum_type_extension(
+    tokens: &mut PeekableLexer,
+    text_source: TextSource,
+) -> ParseResult<GraphQLEnumTypeExtension> {
+    let name = tokens
+        .parse_string_key_type(TokenKind::Identifier)
+        .map_err(|with_span| with_span.map(SchemaParseError::from))?
+        .to_with_location(text_source);
+
+    let directives = parse_constant_directives(tokens, text_source)?;
+
+    let enum_value_definitions = parse_enum_value_definitions(tokens, text_source)?;
+
+    Ok(GraphQLEnumTypeExtension {
+        name,
+        directives,
+        enum_value_definitions,
+    })
+}
+
 fn parse_enum_value_definitions(
     tokens: &mut PeekableLexer,
     text_source: TextSource,
@@ -435,6 +579,32 @@ fn parse_union_definition(
     })
 }
 
+/// The state of the PeekableLexer is that it has processed the "union" keyword.
+/// Unlike in a union definition, the member types are optional.
+fn parse_union_type_extension(
+    tokens: &mut PeekableLexer,
+    text_source: TextSource,
+) -> ParseResult<GraphQLUnionTypeExtension> {
+    let name = tokens
+        .parse_string_key_type(TokenKind::Identifier)
+        .map_err(|with_span| with_span.map(SchemaParseError::from))?
+        .to_with_location(text_source);
+
+    let directives = parse_constant_directives(tokens, text_source)?;
+
+    let union_member_types = if tokens.parse_token_of_kind(TokenKind::Equals).is_ok() {
+        parse_union_member_types(tokens, text_source)?
+    } else {
+        vec![]
+    };
+
+    Ok(GraphQLUnionTypeExtension {
+        name,
+        directives,
+        union_member_types,
+    })
+}
+
 fn parse_union_member_types(
     tokens: &mut PeekableLexer,
     text_source: TextSource,
@@ -578,6 +748,21 @@ fn parse_scalar_type_definition(
     })
 }
 
+/// The state of the PeekableLexer is that it has processed the "scalar" keyword
+fn parse_scalar_type_extension(
+    tokens: &mut PeekableLexer,
+    text_source: TextSource,
+) -> ParseResult<GraphQLScalarTypeExtension> {
+    let name = tokens
+        .parse_string_key_type(TokenKind::Identifier)
+        .map_err(|with_span| with_span.map(SchemaParseError::from))?
+        .to_with_location(text_source);
+
+    let directives = parse_constant_directives(tokens, text_source)?;
+
+    Ok(GraphQLScalarTypeExtension { name, directives })
+}
+
 /// The state of the PeekableLexer is that we have not parsed the "implements" keyword.
 fn parse_implements_interfaces_if_present(
     tokens: &mut PeekableLexer,
@@ -687,6 +872,23 @@ fn parse_constant_name_value_pair<T: From<StringKey>, TValue: ValueType>(
     Ok(NameValuePair { name, value })
 }
 
+/// Parses source, which must consist of a single constant value, e.g. the default
+/// value of an argument in an introspection result.
+pub(crate) fn parse_constant_value_text(
+    source: &str,
+    text_source: TextSource,
+) -> ParseResult<WithLocation<ConstantValue>> {
+    let mut tokens = PeekableLexer::new(source);
+    let constant_value = parse_constant_value(&mut tokens, text_source)?;
+    if !tokens.reached_eof() {
+        return Err(WithSpan::new(
+            SchemaParseError::UnableToParseConstantValue,
+            tokens.peek().span,
+        ));
+    }
+    Ok(constant_value)
+}
+
 fn parse_constant_value(
     tokens: &mut PeekableLexer,
     text_source: TextSource,
@@ -75,6 +75,14 @@ impl<'source> PeekableLexer<'source> {
         self.current.item == TokenKind::EndOfFile
     }
 
+    /// Whether the next token starts at the very beginning of a line, i.e. is not
+    /// indented
+    pub fn next_token_is_at_start_of_line(&self) -> bool {
+        let (raw_start, _) = self.current.span.as_usize();
+        let start = raw_start - self.offset as usize;
+        start == 0 || matches!(self.source.as_bytes()[start - 1], b'\n' | b'\r')
+    }
+
     /// A &str for the source of the given span
     pub fn source(&self, span: Span) -> &'source str {
         let (raw_start, raw_end) = span.as_usize();
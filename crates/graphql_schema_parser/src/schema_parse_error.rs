@@ -40,6 +40,15 @@ pub enum SchemaParseError {
 
     #[error("Root operation types (query, subscription and mutation) cannot be defined twice in a schema definition")]
     RootOperationTypeRedefined,
+
+    #[error("Unable to parse the introspection result. {message}")]
+    InvalidIntrospectionJson { message: String },
+
+    #[error("The type of `{name}` in the introspection result is invalid. Lists and non-null types must have an ofType, and other types must have a name.")]
+    InvalidIntrospectionTypeReference { name: String },
+
+    #[error("Unable to parse the default value of `{name}` in the introspection result. Received {default_value}")]
+    InvalidIntrospectionDefaultValue { name: String, default_value: String },
 }
 
 impl From<LowLevelParseError> for SchemaParseError {
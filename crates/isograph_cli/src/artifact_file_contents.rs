@@ -1,39 +1,60 @@
-use std::collections::HashMap;
+use std::collections::BTreeMap;
 
 use common_lang_types::{IsographObjectTypeName, SelectableFieldName};
-use isograph_schema::{ClientFieldVariant, ObjectTypeAndFieldNames};
+use isograph_schema::{artifact_type_directory, ClientFieldVariant, ObjectTypeAndFieldNames};
 
 use crate::generate_artifacts::{
-    ClientFieldOutputType, EntrypointArtifactInfo, JavaScriptImports, ReaderArtifactInfo,
-    RefetchArtifactInfo,
+    get_complexity_comment, get_jsdoc_comment, variable_names_to_string, variables_type_name,
+    ClientFieldOutputType, EntrypointArtifactInfo, JavaScriptImports, QueryTextDeclaration,
+    ReaderArtifactInfo, RefetchArtifactInfo, SharedQueryTextArtifactInfo,
+    NODE_REFETCH_QUERY_FILE_NAME,
 };
 
 impl<'schema> EntrypointArtifactInfo<'schema> {
     pub(crate) fn file_contents(self) -> String {
         let EntrypointArtifactInfo {
+            query_id,
             query_text,
             normalization_ast,
             refetch_query_artifact_import,
             query_name,
             parent_type,
+            operation_kind,
+            complexity,
+            include_complexity_comment,
+            variables_type_declarations,
+            ..
         } = self;
+        let complexity_comment = if include_complexity_comment {
+            get_complexity_comment(complexity)
+        } else {
+            "".to_string()
+        };
+        let persisted_query_hash_property = get_persisted_query_hash_property(&query_text);
         let entrypoint_params_typename = format!("{}__{}__param", parent_type.name, query_name);
         let entrypoint_output_type_name =
             format!("{}__{}__outputType", parent_type.name, query_name);
+        let variables_type_name = variables_type_name(parent_type.name, query_name);
         format!(
             "import type {{IsographEntrypoint, \
             NormalizationAst, RefetchQueryArtifactWrapper}} from '@isograph/react';\n\
             import type {{{entrypoint_params_typename}, {entrypoint_output_type_name}}} from './reader';\n\
             import readerResolver from './reader';\n\
             {refetch_query_artifact_import}\n\n\
-            const queryText = '{query_text}';\n\n\
-            const normalizationAst: NormalizationAst = {normalization_ast};\n\
+            {complexity_comment}\
+            {query_text}\n\n\
+            const normalizationAst: NormalizationAst = {normalization_ast};\n\n\
+            {variables_type_declarations}\n\
             const artifact: IsographEntrypoint<\n\
             {}{entrypoint_params_typename},\n\
-            {}{entrypoint_output_type_name}\n\
+            {}{entrypoint_output_type_name},\n\
+            {}{variables_type_name}\n\
             > = {{\n\
             {}kind: \"Entrypoint\",\n\
+            {}operationKind: \"{operation_kind}\",\n\
+            {}queryId: \"{query_id}\",\n\
             {}queryText,\n\
+            {persisted_query_hash_property}\
             {}normalizationAst,\n\
             {}nestedRefetchQueries,\n\
             {}readerArtifact: readerResolver,\n\
@@ -46,6 +67,9 @@ impl<'schema> EntrypointArtifactInfo<'schema> {
             "  ",
             "  ",
             "  ",
+            "  ",
+            "  ",
+            "  ",
         )
     }
 }
@@ -57,15 +81,19 @@ impl<'schema> ReaderArtifactInfo<'schema> {
             client_field_parameter_type,
             client_field_output_type,
             reader_ast,
+            read_data_assertion,
+            refetch_query_allowed_variables,
             nested_client_field_artifact_imports,
             parent_type,
             client_field_variant: resolver_variant,
             client_field_name: resolver_field_name,
+            client_field_description,
+            artifact_namespace,
             ..
         } = self;
         let nested_client_field_import_statement = nested_client_field_names_to_import_statement(
             nested_client_field_artifact_imports,
-            parent_type.name,
+            &artifact_type_directory(parent_type.name, artifact_namespace),
         );
         let output_type_text = get_output_type_text(
             parent_type.name,
@@ -73,22 +101,35 @@ impl<'schema> ReaderArtifactInfo<'schema> {
             client_field_output_type,
         );
 
-        // We are not modeling this well, I think.
         let parent_name = parent_type.name;
-        let variant = match resolver_variant {
-            ClientFieldVariant::Component => {
-                format!("{{ kind: \"Component\", componentName: \"{parent_name}.{resolver_field_name}\" }}")
+        let variant = get_reader_variant(parent_name, resolver_field_name, &resolver_variant);
+        let (read_data_assertion, read_data_assertion_property) = match read_data_assertion {
+            Some(read_data_assertion) => {
+                (format!("{read_data_assertion}\n\n"), "  assertReadData,\n")
             }
-            _ => "{ kind: \"Eager\" }".to_string(),
+            None => (String::new(), ""),
         };
+        let (refetch_query_import, refetch_query_property) = match refetch_query_allowed_variables {
+            Some(allowed_variables) => (
+                format!("import refetchQuery from './{NODE_REFETCH_QUERY_FILE_NAME}';\n"),
+                format!(
+                    "  refetchQuery: {{ artifact: refetchQuery, allowedVariables: {} }},\n",
+                    variable_names_to_string(&allowed_variables)
+                ),
+            ),
+            None => (String::new(), String::new()),
+        };
+        let description_comment = get_jsdoc_comment(client_field_description, 0);
         let reader_param_type = format!("{parent_name}__{resolver_field_name}__param");
         let reader_output_type = format!("{parent_name}__{resolver_field_name}__outputType");
         format!(
             "import type {{ReaderArtifact, ReaderAst, ExtractSecondParam}} from '@isograph/react';\n\
             {function_import_statement}\n\
+            {refetch_query_import}\
             {nested_client_field_import_statement}\n\
             {output_type_text}\n\n\
             const readerAst: ReaderAst<{reader_param_type}> = {reader_ast};\n\n\
+            {read_data_assertion}\
             export type {reader_param_type} = {client_field_parameter_type};\n\n\
             const artifact: ReaderArtifact<\n\
             {}{reader_param_type},\n\
@@ -98,8 +139,10 @@ impl<'schema> ReaderArtifactInfo<'schema> {
             {}resolver: resolver as any,\n\
             {}readerAst,\n\
             {}variant: {variant},\n\
+            {read_data_assertion_property}\
+            {refetch_query_property}\
             }};\n\n\
-            export default artifact;\n",
+            {description_comment}export default artifact;\n",
             "  ",
             "  ",
             "  ",
@@ -114,44 +157,85 @@ impl RefetchArtifactInfo {
     pub(crate) fn file_contents(self) -> String {
         let RefetchArtifactInfo {
             normalization_ast,
+            query_id,
             query_text,
+            operation_kind,
+            complexity,
+            include_complexity_comment,
             ..
         } = self;
+        let complexity_comment = if include_complexity_comment {
+            get_complexity_comment(complexity)
+        } else {
+            "".to_string()
+        };
+        let persisted_query_hash_property = get_persisted_query_hash_property(&query_text);
 
         format!(
             "import type {{IsographEntrypoint, ReaderAst, FragmentReference, NormalizationAst}} from '@isograph/react';\n\
-            const queryText = '{query_text}';\n\n\
+            {complexity_comment}\
+            {query_text}\n\n\
             const normalizationAst: NormalizationAst = {normalization_ast};\n\
             const artifact: any = {{\n\
             {}kind: \"RefetchQuery\",\n\
+            {}operationKind: \"{operation_kind}\",\n\
+            {}queryId: \"{query_id}\",\n\
             {}queryText,\n\
+            {persisted_query_hash_property}\
             {}normalizationAst,\n\
             }};\n\n\
             export default artifact;\n",
             "  ",
             "  ",
             "  ",
+            "  ",
+            "  ",
 
         )
     }
 }
 
+/// Artifacts whose query text is persisted contain the hash that is sent instead.
+fn get_persisted_query_hash_property(query_text: &QueryTextDeclaration) -> String {
+    match query_text.persisted_query_hash() {
+        Some(hash) => format!("  persistedQueryHash: \"{hash}\",\n"),
+        None => "".to_string(),
+    }
+}
+
+impl SharedQueryTextArtifactInfo {
+    pub(crate) fn file_contents(self) -> String {
+        let SharedQueryTextArtifactInfo { query_text, .. } = self;
+        format!("export default '{query_text}';\n")
+    }
+}
+
+fn get_reader_variant(
+    parent_type_name: IsographObjectTypeName,
+    client_field_name: SelectableFieldName,
+    client_field_variant: &ClientFieldVariant,
+) -> String {
+    // We are not modeling this well, I think.
+    match client_field_variant {
+        ClientFieldVariant::Component => {
+            format!("{{ kind: \"Component\", componentName: \"{parent_type_name}.{client_field_name}\" }}")
+        }
+        _ => "{ kind: \"Eager\" }".to_string(),
+    }
+}
+
 fn nested_client_field_names_to_import_statement(
-    nested_client_field_imports: HashMap<ObjectTypeAndFieldNames, JavaScriptImports>,
-    current_file_type_name: IsographObjectTypeName,
+    nested_client_field_imports: BTreeMap<ObjectTypeAndFieldNames, JavaScriptImports>,
+    current_file_type_directory: &str,
 ) -> String {
     let mut overall = String::new();
 
-    // TODO we should always sort outputs. We should find a nice generic way to ensure that.
-    let mut nested_client_field_imports: Vec<_> = nested_client_field_imports.into_iter().collect();
-    nested_client_field_imports.sort_by(|(a, _), (b, _)| a.cmp(b));
-
     for (nested_client_field_name, javascript_import) in nested_client_field_imports {
         write_client_field_import(
             javascript_import,
             nested_client_field_name,
             &mut overall,
-            current_file_type_name,
+            current_file_type_directory,
         );
     }
     overall
@@ -161,7 +245,7 @@ fn write_client_field_import(
     javascript_import: JavaScriptImports,
     nested_client_field_name: ObjectTypeAndFieldNames,
     overall: &mut String,
-    current_file_type_name: IsographObjectTypeName,
+    current_file_type_directory: &str,
 ) {
     if !javascript_import.default_import && javascript_import.types.is_empty() {
         panic!(
@@ -191,7 +275,7 @@ fn write_client_field_import(
     }
     s.push_str(&format!(
         " from '{}';\n",
-        nested_client_field_name.relative_path(current_file_type_name)
+        nested_client_field_name.relative_path(current_file_type_directory)
     ));
     overall.push_str(&s);
 }
@@ -1,40 +1,73 @@
 use std::{
-    path::PathBuf,
+    collections::{HashMap, HashSet},
+    path::{Path, PathBuf},
     str::Utf8Error,
     time::{Duration, Instant},
 };
 
 use colored::Colorize;
 use common_lang_types::{
-    FilePath, Location, SourceFileName, Span, TextSource, WithLocation, WithSpan,
+    ArtifactNamespace, FilePath, Location, SourceFileName, Span, TextSource, WithLocation, WithSpan,
 };
-use graphql_schema_parser::{parse_schema, parse_schema_extensions, SchemaParseError};
-use intern::string_key::Intern;
-use isograph_config::CompilerConfig;
+use graphql_lang_types::{GraphQLTypeSystemDocument, GraphQLTypeSystemExtensionDocument};
+use graphql_schema_parser::{
+    parse_introspection_json, parse_schema_extensions, parse_schema_with_error_recovery,
+    SchemaParseError,
+};
+use intern::{string_key::Intern, Lookup};
+use isograph_config::{normalize_path, CompilerConfig, CustomScalarModule};
 use isograph_lang_parser::{
     parse_iso_literal, IsoLiteralExtractionResult, IsographLiteralParseError,
 };
 use isograph_lang_types::{ClientFieldDeclaration, EntrypointTypeAndField};
 use isograph_schema::{
-    ProcessClientFieldDeclarationError, Schema, UnvalidatedSchema, ValidateSchemaError,
+    check_client_field_naming_conventions, check_forbidden_server_fields, check_lint_rules,
+    validate_directives_and_default_values, ForbiddenServerFieldError, LintRule, LintRuleError,
+    NamingConventionError, ObjectTypeAndFieldNames, ProcessClientFieldDeclarationError,
+    ProcessGraphQLDocumentOutcome, Schema, UnvalidatedSchema, ValidateSchemaError, ValidatedSchema,
 };
 use pretty_duration::pretty_duration;
 use thiserror::Error;
 
 use crate::{
-    generate_artifacts::{generate_and_write_artifacts, GenerateArtifactsError},
+    build_report::write_build_report,
+    generate_artifacts::{
+        find_orphaned_artifacts, generate_and_write_artifacts_reporting_progress,
+        relative_import_path, GenerateArtifactsError, OperationComplexityReport,
+        OversizedArtifactReport,
+    },
     isograph_literals::{
-        extract_iso_literal_from_file_content, read_files_in_folder, IsoLiteralExtraction,
+        extract_iso_literals_from_file, read_files_in_folder, IsoLiteralExtraction,
     },
+    memory_usage::{estimate_schema_size, format_megabytes, peak_resident_bytes, MemoryUsage},
+    post_emit_command::{run_post_emit_command, PostEmitManifest},
+    progress::{ProgressBar, ProgressReporting},
     schema::read_schema_file,
+    schema_stitching::read_and_stitch_schemas,
+    schema_version::schema_hash,
 };
 
-pub(crate) struct CompilationStats {
+pub struct CompilationStats {
     pub client_field_count: usize,
     pub entrypoint_count: usize,
     pub total_artifacts_written: usize,
+    /// The absolute paths of the artifacts that were written, e.g. for tools that
+    /// embed the compiler and post-process them
+    pub written_artifact_paths: Vec<PathBuf>,
+    /// The number of artifacts that were not written, because their contents did
+    /// not change
+    pub total_artifacts_unchanged: usize,
+    /// The number of stale files that were deleted from the artifact directory
+    pub total_artifacts_removed: usize,
+    pub operation_complexities: Vec<OperationComplexityReport>,
+    /// Client fields annotated with @skipCodegen, which were validated, but for
+    /// which no artifacts were generated
+    pub skipped_client_fields: Vec<ObjectTypeAndFieldNames>,
+    pub memory_usage: MemoryUsage,
+    /// The artifacts that are larger than the max_artifact_size_bytes option
+    pub oversized_artifacts: Vec<OversizedArtifactReport>,
 }
-pub(crate) struct WithDuration<T> {
+pub struct WithDuration<T> {
     pub elapsed_time: Duration,
     pub item: T,
 }
@@ -50,12 +83,23 @@ impl<T> WithDuration<T> {
     }
 }
 
-pub(crate) fn compile_and_print(
+pub fn compile_and_print(
     config: &CompilerConfig,
+    lint_rules: &[Box<dyn LintRule>],
 ) -> Result<CompilationStats, BatchCompileError> {
     eprintln!("{}", "Starting to compile.".cyan());
+    print_compilation_result(
+        config,
+        compile(config, lint_rules, ProgressReporting::Shown),
+    )
+}
 
-    let result = handle_compile_command(config);
+/// Prints the stats of a successful compilation (and the warnings that the config
+/// asks for), or the error of a failed compilation, and returns the result.
+pub(crate) fn print_compilation_result(
+    config: &CompilerConfig,
+    result: WithDuration<Result<CompilationStats, BatchCompileError>>,
+) -> Result<CompilationStats, BatchCompileError> {
     let elapsed_time = result.elapsed_time;
 
     match result.item {
@@ -63,14 +107,27 @@ pub(crate) fn compile_and_print(
             eprintln!(
                     "{}",
                     format!(
-                        "Successfully compiled {} client fields and {} entrypoints, and wrote {} artifacts, in {}.\n",
+                        "Successfully compiled {} client fields and {} entrypoints, and wrote {} artifacts ({} unchanged, {} removed), in {}.\n",
                         stats.client_field_count,
                         stats.entrypoint_count,
                         stats.total_artifacts_written,
+                        stats.total_artifacts_unchanged,
+                        stats.total_artifacts_removed,
                         pretty_duration(&elapsed_time, None)
                     )
                     .bright_green()
                 );
+            print_operation_complexities(&stats.operation_complexities);
+            print_skipped_client_fields(&stats.skipped_client_fields);
+            if config.options.report_memory_usage {
+                print_memory_usage(&stats.memory_usage);
+            }
+            if let Some(memory_budget_megabytes) = config.options.memory_budget_megabytes {
+                warn_if_memory_budget_exceeded(&stats.memory_usage, memory_budget_megabytes);
+            }
+            if let Some(max_artifact_size_bytes) = config.options.max_artifact_size_bytes {
+                warn_about_oversized_artifacts(&stats.oversized_artifacts, max_artifact_size_bytes);
+            }
             Ok(stats)
         }
         Err(err) => {
@@ -85,130 +142,596 @@ pub(crate) fn compile_and_print(
     }
 }
 
-pub(crate) fn handle_compile_command(
+/// Compiles the project and returns the result, without printing anything other
+/// than warnings (in particular, without reporting progress).
+pub fn handle_compile_command(
+    config: &CompilerConfig,
+    lint_rules: &[Box<dyn LintRule>],
+) -> WithDuration<Result<CompilationStats, BatchCompileError>> {
+    compile(config, lint_rules, ProgressReporting::Hidden)
+}
+
+pub(crate) fn compile(
+    config: &CompilerConfig,
+    lint_rules: &[Box<dyn LintRule>],
+    progress_reporting: ProgressReporting,
+) -> WithDuration<Result<CompilationStats, BatchCompileError>> {
+    compile_with_extracted_iso_literals(config, lint_rules, None, progress_reporting)
+}
+
+/// The iso literals of a project, which were extracted before compiling it, e.g. by
+/// the compilation cache, which only extracts the iso literals of the source files
+/// that changed since the last compilation.
+pub(crate) struct ExtractedIsoLiterals<'a> {
+    pub normalized_root_path: PathBuf,
+    /// The iso literals of every source file, by path relative to
+    /// normalized_root_path
+    pub files: Vec<(PathBuf, Vec<IsoLiteralExtraction<'a>>)>,
+    /// The absolute paths of the source files whose iso literals changed since the
+    /// last compilation, or None if every artifact must be regenerated.
+    pub changed_files: Option<HashSet<PathBuf>>,
+}
+
+/// Like compile, but if extracted_iso_literals is passed, the source files are not
+/// read, and only the artifacts affected by its changed files are regenerated.
+pub(crate) fn compile_with_extracted_iso_literals(
     config: &CompilerConfig,
+    lint_rules: &[Box<dyn LintRule>],
+    extracted_iso_literals: Option<ExtractedIsoLiterals>,
+    progress_reporting: ProgressReporting,
 ) -> WithDuration<Result<CompilationStats, BatchCompileError>> {
     WithDuration::new(|| {
-        let content = read_schema_file(&config.schema)?;
-        let schema_text_source = TextSource {
-            path: config
-                .schema
-                .to_str()
-                .expect("Expected schema to be valid string")
-                .intern()
-                .into(),
-            span: None,
+        let (validated_schema, stats) = match &extracted_iso_literals {
+            Some(extracted_iso_literals) => {
+                create_validated_schema_with_iso_literals(config, || {
+                    parse_iso_literals(
+                        extracted_iso_literals
+                            .files
+                            .iter()
+                            .map(|(file_path, extractions)| (file_path, extractions.clone())),
+                        &extracted_iso_literals.normalized_root_path,
+                    )
+                    .map_err(BatchCompileError::from)
+                })?
+            }
+            None => create_validated_schema(config, progress_reporting)?,
         };
-        let type_system_document = parse_schema(&content, schema_text_source)
-            .map_err(|with_span| with_span.to_with_location(schema_text_source))?;
 
-        let type_extension_documents = config
-            .schema_extensions
+        check_lint_rules(&validated_schema, lint_rules, &config.lint_rules)?;
+        check_client_field_naming_conventions(&validated_schema, &config.client_field_naming)?;
+        check_forbidden_server_fields(&validated_schema, &config.forbidden_server_fields)?;
+
+        let schema_hash = schema_hash(config)?;
+        let mut post_emit_manifest = config
+            .post_emit_command
+            .as_ref()
+            .map(|_| PostEmitManifest::read(config));
+        let generated_artifacts_stats = generate_and_write_artifacts_reporting_progress(
+            &validated_schema,
+            &config.project_root,
+            &config.artifact_directory,
+            config.options,
+            &schema_hash,
+            extracted_iso_literals
+                .as_ref()
+                .and_then(|extracted_iso_literals| extracted_iso_literals.changed_files.as_ref()),
+            post_emit_manifest.as_mut(),
+            progress_reporting,
+        )?;
+
+        let memory_usage = MemoryUsage {
+            peak_resident_bytes: peak_resident_bytes(),
+            schema_bytes: estimate_schema_size(&validated_schema),
+            artifact_bytes: generated_artifacts_stats.artifact_bytes,
+        };
+
+        let stats = CompilationStats {
+            total_artifacts_written: generated_artifacts_stats.written_artifact_count,
+            written_artifact_paths: generated_artifacts_stats.written_artifact_paths,
+            total_artifacts_unchanged: generated_artifacts_stats.unchanged_artifact_count,
+            total_artifacts_removed: generated_artifacts_stats.removed_artifact_count,
+            operation_complexities: generated_artifacts_stats.operation_complexities,
+            memory_usage,
+            oversized_artifacts: generated_artifacts_stats.oversized_artifacts,
+            ..stats
+        };
+
+        if let (Some(post_emit_command), Some(post_emit_manifest)) =
+            (&config.post_emit_command, post_emit_manifest)
+        {
+            run_post_emit_command(post_emit_command, &stats.written_artifact_paths)?;
+            post_emit_manifest.record_emitted_contents_and_write(config)?;
+        }
+
+        if let Some(build_report_path) = &config.build_report {
+            write_build_report(config, &stats, &schema_hash, build_report_path)?;
+        }
+
+        Ok(stats)
+    })
+}
+
+/// Compiles several independent projects concurrently, on one thread per project,
+/// and returns the result of compiling each project, in the order of configs.
+/// Each project has its own schema, but interned strings are shared between them.
+///
+/// Nothing is printed other than warnings (in particular, progress is not reported,
+/// since the progress of concurrent compilations would overwrite each other), so
+/// callers can report results per project. Projects must not share an artifact
+/// directory, since compiling a project replaces the contents of its artifact
+/// directory.
+pub fn compile_projects(
+    configs: &[CompilerConfig],
+    lint_rules: &[Box<dyn LintRule>],
+) -> Vec<WithDuration<Result<CompilationStats, BatchCompileError>>> {
+    std::thread::scope(|scope| {
+        let handles = configs
             .iter()
-            .map(|schema_extension_path| {
-                let extension_text_source = TextSource {
-                    path: schema_extension_path
-                        .to_str()
-                        .expect("Expected schema extension to be valid string")
-                        .intern()
-                        .into(),
-                    span: None,
-                };
-                let extension_content = read_schema_file(schema_extension_path)?;
-                let type_extension_document =
-                    parse_schema_extensions(&extension_content, extension_text_source)
-                        .map_err(|with_span| with_span.to_with_location(extension_text_source))?;
-                Ok(type_extension_document)
+            .map(|config| scope.spawn(move || handle_compile_command(config, lint_rules)))
+            .collect::<Vec<_>>();
+        handles
+            .into_iter()
+            .map(|handle| {
+                handle
+                    .join()
+                    .unwrap_or_else(|panic| std::panic::resume_unwind(panic))
             })
-            .collect::<Result<Vec<_>, BatchCompileError>>()?;
+            .collect()
+    })
+}
 
-        let mut schema = UnvalidatedSchema::new();
+fn print_operation_complexities(operation_complexities: &[OperationComplexityReport]) {
+    if operation_complexities.is_empty() {
+        return;
+    }
+    eprintln!("Operation complexity:");
+    for operation_complexity in operation_complexities {
+        eprintln!(
+            "- {}: {}",
+            operation_complexity.operation_name, operation_complexity.complexity
+        );
+    }
+    eprintln!();
+}
 
-        let original_outcome =
-            schema.process_graphql_type_system_document(type_system_document, config.options)?;
+fn print_skipped_client_fields(skipped_client_fields: &[ObjectTypeAndFieldNames]) {
+    if skipped_client_fields.is_empty() {
+        return;
+    }
+    eprintln!("Skipped generating artifacts for client fields annotated with @skipCodegen:");
+    for skipped_client_field in skipped_client_fields {
+        eprintln!(
+            "- {}.{}",
+            skipped_client_field.type_name, skipped_client_field.field_name
+        );
+    }
+    eprintln!();
+}
 
-        // TODO validate here! We should not allow a situation in which a base schema is invalid,
-        // but is made valid by the presence of schema extensions.
+fn print_memory_usage(memory_usage: &MemoryUsage) {
+    eprintln!("Memory usage:");
+    eprintln!(
+        "- peak resident memory: {}",
+        match memory_usage.peak_resident_bytes {
+            Some(peak_resident_bytes) => format_megabytes(peak_resident_bytes),
+            None => "unavailable".to_string(),
+        }
+    );
+    eprintln!(
+        "- schema (estimated): {}",
+        format_megabytes(memory_usage.schema_bytes)
+    );
+    eprintln!(
+        "- artifacts: {}",
+        format_megabytes(memory_usage.artifact_bytes)
+    );
+    eprintln!();
+}
 
-        for extension_document in type_extension_documents {
-            let _extension_outcome = schema
-                .process_graphql_type_extension_document(extension_document, config.options)?;
-            // TODO extend the process_graphql_outcome.type_refinement_map and the one
-            // from the extensions? Does that even make sense?
-            // TODO validate that we didn't define any new root types (as they are ignored)
+fn warn_if_memory_budget_exceeded(memory_usage: &MemoryUsage, memory_budget_megabytes: usize) {
+    if let Some(peak_resident_bytes) = memory_usage.peak_resident_bytes {
+        if memory_usage.exceeds_budget(memory_budget_megabytes) {
+            eprintln!(
+                "{}\nThe peak memory usage of the compiler was {}, which exceeds the memory budget of {} MB.\n",
+                "Warning:".yellow(),
+                format_megabytes(peak_resident_bytes),
+                memory_budget_megabytes
+            );
         }
+    }
+}
 
-        // TODO the ordering should be:
-        // - process schema
-        // - validate
-        // - process schema extension
-        // - validate
-        // - add mutation fields
-        // - process parsed iso field definitions
-        // - validate client fields
-        if let Some(mutation_id) = &original_outcome.root_types.mutation {
-            schema
-                .create_mutation_fields_from_expose_as_directives(*mutation_id, config.options)?;
+fn warn_about_oversized_artifacts(
+    oversized_artifacts: &[OversizedArtifactReport],
+    max_artifact_size_bytes: usize,
+) {
+    for oversized_artifact in oversized_artifacts {
+        let mut warning = format!(
+            "The artifact {} is {} bytes, which exceeds the max artifact size of {} bytes.",
+            oversized_artifact.path.display(),
+            oversized_artifact.size_bytes,
+            max_artifact_size_bytes
+        );
+        if let Some(client_field_name) = &oversized_artifact.client_field_name {
+            warning.push_str(&format!(" It belongs to {}.", client_field_name));
         }
+        if !oversized_artifact.biggest_nested_client_fields.is_empty() {
+            warning.push_str(" The biggest client fields that it selects are:");
+            for (nested_client_field_name, field_count) in
+                oversized_artifact.biggest_nested_client_fields.iter()
+            {
+                warning.push_str(&format!(
+                    "\n- {} ({} server fields)",
+                    nested_client_field_name, field_count
+                ));
+            }
+        }
+        eprintln!("{}\n{}\n", "Warning:".yellow(), warning);
+    }
+}
 
-        let canonicalized_root_path = {
-            let current_dir = std::env::current_dir().expect("current_dir should exist");
-            let joined = current_dir.join(&config.project_root);
-            joined
-                .canonicalize()
-                .map_err(|message| BatchCompileError::UnableToLoadSchema {
-                    path: joined.clone(),
-                    message,
-                })?
-        };
+/// Lists the files in the artifact directory that no longer correspond to any
+/// client field or entrypoint. Nothing is written or deleted.
+pub fn report_orphaned_artifacts_and_print(
+    config: &CompilerConfig,
+) -> Result<Vec<PathBuf>, BatchCompileError> {
+    eprintln!("{}", "Looking for orphaned artifacts.".cyan());
+
+    let result = create_validated_schema(config, ProgressReporting::Shown).and_then(
+        |(validated_schema, _)| {
+            find_orphaned_artifacts(
+                &validated_schema,
+                &config.project_root,
+                &config.artifact_directory,
+                config.options,
+            )
+            .map_err(BatchCompileError::from)
+        },
+    );
+
+    match result {
+        Ok(orphaned_artifacts) => {
+            if orphaned_artifacts.is_empty() {
+                eprintln!("{}", "Found no orphaned artifacts.\n".bright_green());
+            } else {
+                eprintln!(
+                    "{}",
+                    format!(
+                        "Found {} orphaned artifact{}:",
+                        orphaned_artifacts.len(),
+                        if orphaned_artifacts.len() == 1 {
+                            ""
+                        } else {
+                            "s"
+                        }
+                    )
+                    .yellow()
+                );
+                for orphaned_artifact in orphaned_artifacts.iter() {
+                    let relative_path = orphaned_artifact
+                        .strip_prefix(&config.artifact_directory)
+                        .unwrap_or(orphaned_artifact);
+                    println!("{}", relative_path.display());
+                }
+            }
+            Ok(orphaned_artifacts)
+        }
+        Err(err) => {
+            eprintln!(
+                "{}\n{}",
+                "Error when looking for orphaned artifacts.\n".bright_red(),
+                err
+            );
+            Err(err)
+        }
+    }
+}
 
-        // TODO return an iterator
-        let project_files = read_files_in_folder(&canonicalized_root_path)?;
+/// Processes the schema, schema extensions and iso literals, and validates them.
+/// The returned stats do not include any written artifacts.
+pub(crate) fn create_validated_schema(
+    config: &CompilerConfig,
+    progress_reporting: ProgressReporting,
+) -> Result<(ValidatedSchema, CompilationStats), BatchCompileError> {
+    create_validated_schema_with_iso_literals(config, || {
+        read_and_extract_iso_literals(config, progress_reporting)
+    })
+}
 
-        let (client_field_declarations, parsed_entrypoints) =
-            extract_iso_literals(project_files, canonicalized_root_path)
-                .map_err(BatchCompileError::from)?;
-        let client_field_count = client_field_declarations.len();
-        let entrypoint_count = parsed_entrypoints.len();
+/// Like create_validated_schema, but the iso literals are returned by
+/// read_iso_literals, which is called once the schema has been processed.
+fn create_validated_schema_with_iso_literals(
+    config: &CompilerConfig,
+    read_iso_literals: impl FnOnce() -> Result<
+        (
+            Vec<(WithSpan<ClientFieldDeclaration>, TextSource)>,
+            Vec<(WithSpan<EntrypointTypeAndField>, TextSource)>,
+        ),
+        BatchCompileError,
+    >,
+) -> Result<(ValidatedSchema, CompilationStats), BatchCompileError> {
+    let content = read_schema_file(&config.schema, config.options.path_normalization)?;
+    let type_system_document = parse_schema_with_config_path(&content, config)?;
+    // Stitched schemas are merged before the schema extensions, so that the schema
+    // extensions can extend the types of the stitched schemas.
+    let mut type_extension_documents = read_and_stitch_schemas(config, &type_system_document)?;
+    type_extension_documents.extend(read_and_parse_schema_extensions(config)?);
 
-        process_client_fields_and_entrypoints(
-            &mut schema,
-            client_field_declarations,
-            parsed_entrypoints,
-        )?;
+    let (mut schema, original_outcome) =
+        create_unvalidated_schema(type_system_document, type_extension_documents, config)?;
 
-        schema.add_fields_to_subtypes(
-            &original_outcome
-                .type_refinement_maps
-                .supertype_to_subtype_map,
-        )?;
+    let (client_field_declarations, parsed_entrypoints) = read_iso_literals()?;
+    let client_field_count = client_field_declarations.len();
+    let entrypoint_count = parsed_entrypoints.len();
 
-        let validated_schema = Schema::validate_and_construct(schema)?;
+    process_client_fields_and_entrypoints(
+        &mut schema,
+        client_field_declarations,
+        parsed_entrypoints,
+        &config.packages,
+    )?;
 
-        let total_artifacts_written = generate_and_write_artifacts(
-            &validated_schema,
-            &config.project_root,
-            &config.artifact_directory,
-        )?;
+    schema.add_fields_to_subtypes(
+        &original_outcome
+            .type_refinement_maps
+            .supertype_to_subtype_map,
+    )?;
+
+    let validated_schema = Schema::validate_and_construct(schema)?;
+
+    let mut skipped_client_fields = validated_schema
+        .client_fields
+        .iter()
+        .filter(|client_field| client_field.skip_codegen)
+        .map(|client_field| client_field.type_and_field)
+        .collect::<Vec<_>>();
+    skipped_client_fields.sort();
 
-        Ok(CompilationStats {
+    Ok((
+        validated_schema,
+        CompilationStats {
             client_field_count,
             entrypoint_count,
-            total_artifacts_written,
+            total_artifacts_written: 0,
+            written_artifact_paths: vec![],
+            total_artifacts_unchanged: 0,
+            total_artifacts_removed: 0,
+            operation_complexities: vec![],
+            skipped_client_fields,
+            memory_usage: MemoryUsage::default(),
+            oversized_artifacts: vec![],
+        },
+    ))
+}
+
+pub(crate) fn parse_schema_with_config_path(
+    content: &str,
+    config: &CompilerConfig,
+) -> Result<GraphQLTypeSystemDocument, BatchCompileError> {
+    let schema_text_source = TextSource {
+        path: config
+            .schema
+            .to_str()
+            .expect("Expected schema to be valid string")
+            .intern()
+            .into(),
+        span: None,
+    };
+    // The schema may be the JSON result of an introspection query instead of SDL, in
+    // which case it is converted to the same document as the equivalent SDL.
+    let is_introspection_result = config
+        .schema
+        .extension()
+        .and_then(|extension| extension.to_str())
+        == Some("json");
+    let parsed = if is_introspection_result {
+        parse_introspection_json(content, schema_text_source).map_err(|error| vec![error])
+    } else {
+        parse_schema_with_error_recovery(content, schema_text_source)
+    };
+    let type_system_document = parsed.map_err(|errors| {
+        errors
+            .into_iter()
+            .map(|with_span| with_span.to_with_location(schema_text_source))
+            .collect::<Vec<_>>()
+    })?;
+    Ok(type_system_document)
+}
+
+pub(crate) fn read_and_parse_schema_extensions(
+    config: &CompilerConfig,
+) -> Result<Vec<GraphQLTypeSystemExtensionDocument>, BatchCompileError> {
+    config
+        .schema_extensions
+        .iter()
+        .map(|schema_extension_path| {
+            let extension_text_source = TextSource {
+                path: schema_extension_path
+                    .to_str()
+                    .expect("Expected schema extension to be valid string")
+                    .intern()
+                    .into(),
+                span: None,
+            };
+            let extension_content =
+                read_schema_file(schema_extension_path, config.options.path_normalization)?;
+            let type_extension_document =
+                parse_schema_extensions(&extension_content, extension_text_source)
+                    .map_err(|with_span| with_span.to_with_location(extension_text_source))?;
+            Ok(type_extension_document)
         })
-    })
+        .collect()
+}
+
+pub(crate) fn create_unvalidated_schema(
+    type_system_document: GraphQLTypeSystemDocument,
+    type_extension_documents: Vec<GraphQLTypeSystemExtensionDocument>,
+    config: &CompilerConfig,
+) -> Result<(UnvalidatedSchema, ProcessGraphQLDocumentOutcome), BatchCompileError> {
+    let options = config.options;
+    validate_directives_and_default_values(&type_system_document, &type_extension_documents)?;
+
+    let mut schema = UnvalidatedSchema::new();
+
+    let mut original_outcome =
+        schema.process_graphql_type_system_document(type_system_document, options)?;
+
+    // TODO validate here! We should not allow a situation in which a base schema is invalid,
+    // but is made valid by the presence of schema extensions.
+
+    for extension_document in type_extension_documents {
+        let extension_outcome =
+            schema.process_graphql_type_extension_document(extension_document, options)?;
+        // Types defined or extended in extensions may implement interfaces (or be added
+        // to unions), in which case they must receive the fields of their supertypes.
+        let refinement_maps = &mut original_outcome.type_refinement_maps;
+        for (subtype_id, supertype_ids) in extension_outcome
+            .type_refinement_maps
+            .subtype_to_supertype_map
+        {
+            refinement_maps
+                .subtype_to_supertype_map
+                .entry(subtype_id)
+                .or_default()
+                .extend(supertype_ids);
+        }
+        for (supertype_id, subtype_ids) in extension_outcome
+            .type_refinement_maps
+            .supertype_to_subtype_map
+        {
+            refinement_maps
+                .supertype_to_subtype_map
+                .entry(supertype_id)
+                .or_default()
+                .extend(subtype_ids);
+        }
+        // TODO validate that we didn't define any new root types (as they are ignored)
+    }
+
+    // TODO the ordering should be:
+    // - process schema
+    // - validate
+    // - process schema extension
+    // - validate
+    // - add mutation fields
+    // - process parsed iso field definitions
+    // - validate client fields
+    if let Some(mutation_id) = &original_outcome.root_types.mutation {
+        schema.create_mutation_fields_from_expose_as_directives(*mutation_id, options)?;
+    }
+
+    apply_custom_scalar_types(&mut schema, config)?;
+
+    Ok((schema, original_outcome))
 }
 
-fn process_client_fields_and_entrypoints(
+/// Custom scalars are typed as strings, unless the config specifies their TypeScript
+/// type. Imported types are referred to with import types, as enums are, which works
+/// because artifacts are always two levels deep in the artifact directory. The config
+/// may also specify how their literals are canonicalized and validated.
+fn apply_custom_scalar_types(
+    schema: &mut UnvalidatedSchema,
+    config: &CompilerConfig,
+) -> Result<(), BatchCompileError> {
+    for (scalar_name, custom_scalar_type) in config.custom_scalar_types.iter() {
+        let scalar = schema
+            .schema_data
+            .scalars
+            .iter_mut()
+            .find(|scalar| {
+                scalar.name.item.lookup() == scalar_name.as_str() && scalar.enum_values.is_none()
+            })
+            .ok_or_else(|| BatchCompileError::UnknownCustomScalar {
+                scalar_name: scalar_name.clone(),
+            })?;
+
+        let typescript_type = &custom_scalar_type.typescript_type;
+        let javascript_type = match &custom_scalar_type.import_from {
+            None => typescript_type.clone(),
+            Some(CustomScalarModule::Package(package)) => {
+                format!("import('{package}').{typescript_type}")
+            }
+            Some(CustomScalarModule::Path(path)) => format!(
+                "import('../../{}').{typescript_type}",
+                relative_import_path(&config.artifact_directory, path).trim_start_matches("./")
+            ),
+        };
+        scalar.javascript_name = javascript_type.intern().into();
+        scalar.literals = custom_scalar_type.literals.clone();
+    }
+    Ok(())
+}
+
+pub(crate) fn read_and_extract_iso_literals(
+    config: &CompilerConfig,
+    progress_reporting: ProgressReporting,
+) -> Result<
+    (
+        Vec<(WithSpan<ClientFieldDeclaration>, TextSource)>,
+        Vec<(WithSpan<EntrypointTypeAndField>, TextSource)>,
+    ),
+    BatchCompileError,
+> {
+    read_and_extract_iso_literals_with_unsaved_files(config, &HashMap::new(), progress_reporting)
+}
+
+/// Like read_and_extract_iso_literals, but the contents of the files in
+/// unsaved_files (by absolute path) are used instead of their contents on disk,
+/// e.g. for files that are being edited in an editor.
+pub(crate) fn read_and_extract_iso_literals_with_unsaved_files(
+    config: &CompilerConfig,
+    unsaved_files: &HashMap<PathBuf, String>,
+    progress_reporting: ProgressReporting,
+) -> Result<
+    (
+        Vec<(WithSpan<ClientFieldDeclaration>, TextSource)>,
+        Vec<(WithSpan<EntrypointTypeAndField>, TextSource)>,
+    ),
+    BatchCompileError,
+> {
+    let normalized_root_path =
+        normalize_path(&config.project_root, config.options.path_normalization).map_err(
+            |message| BatchCompileError::UnableToLoadSchema {
+                path: config.project_root.clone(),
+                message,
+            },
+        )?;
+
+    // TODO return an iterator
+    let mut project_files = read_files_in_folder(
+        &normalized_root_path,
+        &config.single_file_component_extensions,
+        progress_reporting,
+    )?;
+    for (file_path, file_content) in project_files.iter_mut() {
+        if let Some(unsaved_content) = unsaved_files.get(&normalized_root_path.join(&file_path)) {
+            *file_content = unsaved_content.clone();
+        }
+    }
+
+    extract_iso_literals(
+        project_files,
+        normalized_root_path,
+        &config.single_file_component_extensions,
+        progress_reporting,
+    )
+    .map_err(BatchCompileError::from)
+}
+
+pub(crate) fn process_client_fields_and_entrypoints(
     schema: &mut UnvalidatedSchema,
     client_fields: Vec<(WithSpan<ClientFieldDeclaration>, TextSource)>,
     entrypoint_declarations: Vec<(WithSpan<EntrypointTypeAndField>, TextSource)>,
+    packages: &HashMap<String, PathBuf>,
 ) -> Result<(), Vec<WithLocation<ProcessClientFieldDeclarationError>>> {
     let mut errors = vec![];
     for (client_field_declaration, text_source) in client_fields {
-        if let Err(e) =
-            schema.process_client_field_declaration(client_field_declaration, text_source)
-        {
+        let artifact_namespace = find_artifact_namespace(text_source.path, packages);
+        if let Err(e) = schema.process_client_field_declaration(
+            client_field_declaration,
+            text_source,
+            artifact_namespace,
+        ) {
             errors.push(e);
         }
     }
@@ -225,9 +748,54 @@ fn process_client_fields_and_entrypoints(
     }
 }
 
+/// The artifacts of a client field are namespaced by the package in which it is
+/// declared. If package roots are nested, the innermost package wins.
+fn find_artifact_namespace(
+    file_name: SourceFileName,
+    packages: &HashMap<String, PathBuf>,
+) -> Option<ArtifactNamespace> {
+    let file_path = Path::new(file_name.lookup());
+    packages
+        .iter()
+        .filter(|(_, package_root)| file_path.starts_with(package_root))
+        .max_by_key(|(_, package_root)| package_root.components().count())
+        .map(|(package_name, _)| package_name.as_str().intern().into())
+}
+
 fn extract_iso_literals(
     project_files: Vec<(PathBuf, String)>,
-    canonicalized_root_path: PathBuf,
+    normalized_root_path: PathBuf,
+    single_file_component_extensions: &[String],
+    progress_reporting: ProgressReporting,
+) -> Result<
+    (
+        Vec<(WithSpan<ClientFieldDeclaration>, TextSource)>,
+        Vec<(WithSpan<EntrypointTypeAndField>, TextSource)>,
+    ),
+    Vec<WithLocation<IsographLiteralParseError>>,
+> {
+    let mut progress_bar = ProgressBar::new(
+        "Extracting iso literals",
+        project_files.len(),
+        progress_reporting,
+    );
+    let iso_literal_extractions = project_files.iter().map(|(file_path, file_content)| {
+        let iso_literal_extractions = extract_iso_literals_from_file(
+            file_path,
+            file_content,
+            single_file_component_extensions,
+        );
+        progress_bar.increment();
+        (file_path, iso_literal_extractions)
+    });
+    parse_iso_literals(iso_literal_extractions, &normalized_root_path)
+}
+
+/// Parses the iso literals extracted from each source file, by path relative to
+/// normalized_root_path.
+fn parse_iso_literals<'a>(
+    iso_literal_extractions: impl Iterator<Item = (&'a PathBuf, Vec<IsoLiteralExtraction<'a>>)>,
+    normalized_root_path: &Path,
 ) -> Result<
     (
         Vec<(WithSpan<ClientFieldDeclaration>, TextSource)>,
@@ -239,18 +807,18 @@ fn extract_iso_literals(
     let mut client_field_declarations_and_text_sources = vec![];
     let mut entrypoint_declarations_and_text_sources = vec![];
 
-    for (file_path, file_content) in project_files {
+    for (file_path, iso_literal_extractions) in iso_literal_extractions {
         // TODO don't intern unless there's a match
         let interned_file_path = file_path.to_string_lossy().into_owned().intern().into();
 
-        let file_name = canonicalized_root_path
+        let file_name = normalized_root_path
             .join(file_path)
             .to_str()
             .expect("file_path should be a valid string")
             .intern()
             .into();
 
-        for iso_literal_extraction in extract_iso_literal_from_file_content(&file_content) {
+        for iso_literal_extraction in iso_literal_extractions {
             match process_iso_literal_extraction(
                 iso_literal_extraction,
                 file_name,
@@ -311,26 +879,15 @@ fn process_iso_literal_extraction(
         &iso_literal_text,
         interned_file_path,
         const_export_name,
+        has_associated_js_function,
         text_source,
     )?;
 
-    if matches!(
-        &iso_literal_extraction_result,
-        IsoLiteralExtractionResult::ClientFieldDeclaration(_)
-    ) {
-        if !has_associated_js_function {
-            return Err(WithLocation::new(
-                IsographLiteralParseError::ExpectedAssociatedJsFunction,
-                Location::new(text_source, Span::todo_generated()),
-            ));
-        }
-    }
-
     Ok((iso_literal_extraction_result, text_source))
 }
 
 #[derive(Error, Debug)]
-pub(crate) enum BatchCompileError {
+pub enum BatchCompileError {
     #[error("Unable to load schema file at path {path:?}.\nReason: {message}")]
     UnableToLoadSchema {
         path: PathBuf,
@@ -349,12 +906,60 @@ pub(crate) enum BatchCompileError {
         message: std::io::Error,
     },
 
+    #[error(
+        "Unable to write the build report at the following path: {path:?}.\nReason: {message}"
+    )]
+    UnableToWriteBuildReport {
+        path: PathBuf,
+        message: std::io::Error,
+    },
+
+    #[error(
+        "Unable to write the compilation cache at the following path: {path:?}.\nReason: {message}"
+    )]
+    UnableToWriteCompilationCache {
+        path: PathBuf,
+        message: std::io::Error,
+    },
+
+    #[error(
+        "Unable to write the post emit manifest at the following path: {path:?}.\nReason: {message}"
+    )]
+    UnableToWritePostEmitManifest {
+        path: PathBuf,
+        message: std::io::Error,
+    },
+
+    #[error("Unable to communicate with the language client.\nReason: {message}")]
+    UnableToCommunicateWithLanguageClient { message: std::io::Error },
+
+    #[error("Unable to run the post_emit_command {program}.\nReason: {message}")]
+    UnableToRunPostEmitCommand {
+        program: String,
+        message: std::io::Error,
+    },
+
+    #[error("The post_emit_command {program} failed ({status}).")]
+    PostEmitCommandFailed {
+        program: String,
+        status: std::process::ExitStatus,
+    },
+
     #[error("Unable to traverse directory.\nReason: {0}")]
     UnableToTraverseDirectory(#[from] std::io::Error),
 
     #[error("Unable to parse schema.\n\n{0}")]
     UnableToParseSchema(#[from] WithLocation<SchemaParseError>),
 
+    #[error(
+        "{}{}",
+        if messages.len() == 1 { "Unable to parse schema:" } else { "Unable to parse schema, which contains multiple errors:" },
+        messages.into_iter().map(|x| format!("\n\n{x}")).collect::<String>()
+    )]
+    UnableToParseSchemaDefinitions {
+        messages: Vec<WithLocation<SchemaParseError>>,
+    },
+
     #[error(
         "{}{}",
         if messages.len() == 1 { "Unable to parse Isograph literal:" } else { "Unable to parse Isograph literals:" },
@@ -364,6 +969,12 @@ pub(crate) enum BatchCompileError {
         messages: Vec<WithLocation<IsographLiteralParseError>>,
     },
 
+    #[error(
+        "The config specifies the TypeScript type of `{scalar_name}`, but the schema \
+        defines no scalar with that name."
+    )]
+    UnknownCustomScalar { scalar_name: String },
+
     #[error("Unable to create schema.\nReason: {0}")]
     UnableToCreateSchema(#[from] WithLocation<isograph_schema::ProcessTypeDefinitionError>),
 
@@ -397,11 +1008,148 @@ pub(crate) enum BatchCompileError {
         messages: Vec<WithLocation<isograph_schema::ValidateSchemaError>>,
     },
 
+    #[error(
+        "{}{}",
+        if messages.len() == 1 { "Error when checking lint rules:" } else { "Errors when checking lint rules:" },
+        messages.into_iter().map(|x| format!("\n\n{x}")).collect::<String>()
+    )]
+    LintRulesViolated {
+        messages: Vec<WithLocation<LintRuleError>>,
+    },
+
+    #[error(
+        "{}{}",
+        if messages.len() == 1 { "Error when checking naming conventions:" } else { "Errors when checking naming conventions:" },
+        messages.into_iter().map(|x| format!("\n\n{x}")).collect::<String>()
+    )]
+    NamingConventionsViolated {
+        messages: Vec<WithLocation<NamingConventionError>>,
+    },
+
+    #[error(
+        "{}{}",
+        if messages.len() == 1 { "Forbidden server field selected:" } else { "Forbidden server fields selected:" },
+        messages.into_iter().map(|x| format!("\n\n{x}")).collect::<String>()
+    )]
+    ForbiddenServerFieldsSelected {
+        messages: Vec<WithLocation<ForbiddenServerFieldError>>,
+    },
+
     #[error("Unable to print.\nReason: {0}")]
     UnableToPrint(#[from] GenerateArtifactsError),
 
     #[error("Unable to convert file {path:?} to utf8.\nDetailed reason: {reason}")]
     UnableToConvertToString { path: PathBuf, reason: Utf8Error },
+
+    #[error(
+        "`{type_and_field}` is not an entrypoint. Expected the parent type and name \
+        of a client field for which an entrypoint is declared, e.g. `Query.HomeRoute`."
+    )]
+    NotAnEntrypoint { type_and_field: String },
+
+    #[error("Expected the header `{header}` to be of the form `Name: value`.")]
+    InvalidHeader { header: String },
+
+    #[error("Unable to send the introspection query to {endpoint}.\nReason: {message}")]
+    UnableToPullSchema { endpoint: String, message: String },
+
+    #[error("Unable to write the schema at the following path: {path:?}.\nReason: {message}")]
+    UnableToWriteSchema {
+        path: PathBuf,
+        message: std::io::Error,
+    },
+
+    #[error(
+        "The artifact {path:?} was generated from a different version of the schema \
+        (with hash {artifact_schema_hash}) than the current one (with hash {schema_hash}). \
+        Compile to regenerate the artifacts."
+    )]
+    ArtifactGeneratedFromDifferentSchema {
+        path: PathBuf,
+        artifact_schema_hash: String,
+        schema_hash: String,
+    },
+
+    #[error(
+        "The artifact {path:?} does not record the hash of the schema from which it was \
+        generated, so it was likely generated by an older version of the compiler. \
+        Compile to regenerate the artifacts."
+    )]
+    ArtifactWithoutSchemaHash { path: PathBuf },
+
+    #[error("Found no artifacts in {path:?}. Compile to generate the artifacts.")]
+    NoArtifacts { path: PathBuf },
+
+    #[error("Unable to read the used entrypoints.\nReason: {message}")]
+    UnableToReadUsedEntrypoints { message: String },
+
+    #[error(
+        "The used entrypoint \"{entrypoint}\" is not an entrypoint. \
+        Used entrypoints must have the form Type.field, e.g. Query.HomeRoute."
+    )]
+    UnknownUsedEntrypoint { entrypoint: String },
+
+    #[error(
+        "Unreachable artifacts can only be reported for the typescript artifact format, \
+        since other artifacts do not import each other."
+    )]
+    UnreachableArtifactsRequireTypeScript,
+}
+
+impl BatchCompileError {
+    /// Every error contained in this error, with its location, so that tools such as
+    /// editors can report each error in the file in which it occurs (e.g. schema
+    /// errors in the schema or schema extension file, not in an iso literal). Errors
+    /// without a location in a file have a generated location.
+    pub fn located_messages(&self) -> Vec<WithLocation<String>> {
+        fn to_located_messages<T: std::fmt::Display>(
+            messages: &[WithLocation<T>],
+        ) -> Vec<WithLocation<String>> {
+            messages
+                .iter()
+                .map(|message| WithLocation::new(message.item.to_string(), message.location))
+                .collect()
+        }
+
+        match self {
+            BatchCompileError::UnableToParseSchema(message) => {
+                to_located_messages(std::slice::from_ref(message))
+            }
+            BatchCompileError::UnableToParseSchemaDefinitions { messages } => {
+                to_located_messages(messages)
+            }
+            BatchCompileError::UnableToParseIsographLiterals { messages } => {
+                to_located_messages(messages)
+            }
+            BatchCompileError::UnableToCreateSchema(message) => {
+                to_located_messages(std::slice::from_ref(message))
+            }
+            BatchCompileError::ErrorWhenProcessingClientFieldDeclaration { messages } => {
+                to_located_messages(messages)
+            }
+            BatchCompileError::ErrorWhenProcessingEntrypointDeclaration(message) => {
+                to_located_messages(std::slice::from_ref(message))
+            }
+            BatchCompileError::UnableToValidateSchema { messages } => to_located_messages(messages),
+            BatchCompileError::LintRulesViolated { messages } => to_located_messages(messages),
+            BatchCompileError::NamingConventionsViolated { messages } => {
+                to_located_messages(messages)
+            }
+            BatchCompileError::ForbiddenServerFieldsSelected { messages } => {
+                to_located_messages(messages)
+            }
+            BatchCompileError::UnableToPrint(
+                error @ GenerateArtifactsError::ClientFieldFileDoesNotExist { location, .. },
+            ) => vec![WithLocation::new(error.to_string(), *location)],
+            _ => vec![WithLocation::new(self.to_string(), Location::generated())],
+        }
+    }
+}
+
+impl From<Vec<WithLocation<SchemaParseError>>> for BatchCompileError {
+    fn from(messages: Vec<WithLocation<SchemaParseError>>) -> Self {
+        BatchCompileError::UnableToParseSchemaDefinitions { messages }
+    }
 }
 
 impl From<Vec<WithLocation<IsographLiteralParseError>>> for BatchCompileError {
@@ -416,6 +1164,24 @@ impl From<Vec<WithLocation<ValidateSchemaError>>> for BatchCompileError {
     }
 }
 
+impl From<Vec<WithLocation<LintRuleError>>> for BatchCompileError {
+    fn from(messages: Vec<WithLocation<LintRuleError>>) -> Self {
+        BatchCompileError::LintRulesViolated { messages }
+    }
+}
+
+impl From<Vec<WithLocation<NamingConventionError>>> for BatchCompileError {
+    fn from(messages: Vec<WithLocation<NamingConventionError>>) -> Self {
+        BatchCompileError::NamingConventionsViolated { messages }
+    }
+}
+
+impl From<Vec<WithLocation<ForbiddenServerFieldError>>> for BatchCompileError {
+    fn from(messages: Vec<WithLocation<ForbiddenServerFieldError>>) -> Self {
+        BatchCompileError::ForbiddenServerFieldsSelected { messages }
+    }
+}
+
 impl From<Vec<WithLocation<ProcessClientFieldDeclarationError>>> for BatchCompileError {
     fn from(messages: Vec<WithLocation<ProcessClientFieldDeclarationError>>) -> Self {
         BatchCompileError::ErrorWhenProcessingClientFieldDeclaration { messages }
@@ -0,0 +1,132 @@
+use std::{
+    path::{Path, PathBuf},
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+use isograph_config::CompilerConfig;
+use serde_json::json;
+
+use crate::{
+    batch_compile::{BatchCompileError, CompilationStats},
+    generate_artifacts::content_hash,
+    isograph_literals::read_files_in_folder,
+    progress::ProgressReporting,
+};
+
+/// Writes a report of a successful compilation to build_report_path: the time at
+/// which it finished, the hash of the schema from which artifacts were generated,
+/// the number of artifacts, and the absolute path and content
+/// hash of every input (the schema, schema extensions, stitched schemas and source
+/// files).
+///
+/// Artifacts must be identical on every machine, so that they can be cached and
+/// checked in. Anything that is not (e.g. timestamps or absolute paths) belongs in
+/// this report instead.
+pub(crate) fn write_build_report(
+    config: &CompilerConfig,
+    stats: &CompilationStats,
+    schema_hash: &str,
+    build_report_path: &Path,
+) -> Result<(), BatchCompileError> {
+    let compiled_at = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|duration| duration.as_secs())
+        .unwrap_or(0);
+
+    let inputs = input_fingerprints(config)?
+        .into_iter()
+        .map(|(path, fingerprint)| {
+            format!(
+                "{}{{ \"path\": {}, \"fingerprint\": \"{fingerprint}\" }}",
+                "    ",
+                json!(path.to_string_lossy())
+            )
+        })
+        .collect::<Vec<_>>()
+        .join(",\n");
+
+    let build_report = format!(
+        "{{\n\
+        {}\"compiledAt\": {compiled_at},\n\
+        {}\"schemaHash\": \"{schema_hash}\",\n\
+        {}\"clientFieldCount\": {},\n\
+        {}\"entrypointCount\": {},\n\
+        {}\"artifactsWritten\": {},\n\
+        {}\"artifactsUnchanged\": {},\n\
+        {}\"artifactsRemoved\": {},\n\
+        {}\"inputs\": [\n{inputs}\n{}]\n\
+        }}\n",
+        "  ",
+        "  ",
+        "  ",
+        stats.client_field_count,
+        "  ",
+        stats.entrypoint_count,
+        "  ",
+        stats.total_artifacts_written,
+        "  ",
+        stats.total_artifacts_unchanged,
+        "  ",
+        stats.total_artifacts_removed,
+        "  ",
+        "  "
+    );
+
+    std::fs::write(build_report_path, build_report).map_err(|message| {
+        BatchCompileError::UnableToWriteBuildReport {
+            path: build_report_path.to_path_buf(),
+            message,
+        }
+    })
+}
+
+/// The absolute path and content hash of every input of the compilation, sorted by
+/// path
+pub(crate) fn input_fingerprints(
+    config: &CompilerConfig,
+) -> Result<Vec<(PathBuf, String)>, BatchCompileError> {
+    let mut fingerprints = schema_fingerprints(config)?;
+    fingerprints.extend(
+        read_files_in_folder(
+            &config.project_root,
+            &config.single_file_component_extensions,
+            ProgressReporting::Hidden,
+        )?
+        .into_iter()
+        .map(|(relative_path, contents)| {
+            (
+                config.project_root.join(relative_path),
+                content_hash(contents.as_bytes()),
+            )
+        }),
+    );
+
+    fingerprints.sort();
+    Ok(fingerprints)
+}
+
+/// The absolute path and content hash of the schema, the schema extensions and the
+/// stitched schemas
+pub(crate) fn schema_fingerprints(
+    config: &CompilerConfig,
+) -> Result<Vec<(PathBuf, String)>, BatchCompileError> {
+    let schema_paths = std::iter::once(&config.schema)
+        .chain(config.schema_extensions.iter())
+        .chain(
+            config
+                .stitched_schemas
+                .iter()
+                .map(|stitched_schema| &stitched_schema.path),
+        );
+
+    schema_paths
+        .map(|path| {
+            let contents =
+                std::fs::read(path).map_err(|message| BatchCompileError::UnableToReadFile {
+                    path: path.clone(),
+                    message,
+                })?;
+            Ok((path.clone(), content_hash(&contents)))
+        })
+        .collect()
+}
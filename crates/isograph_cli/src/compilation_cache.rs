@@ -0,0 +1,373 @@
+use std::{
+    collections::{BTreeMap, HashSet},
+    fs,
+    path::{Path, PathBuf},
+};
+
+use colored::Colorize;
+use isograph_config::{normalize_path, CompilerConfig};
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    batch_compile::{
+        compile_with_extracted_iso_literals, print_compilation_result, BatchCompileError,
+        CompilationStats, ExtractedIsoLiterals, WithDuration,
+    },
+    build_report::schema_fingerprints,
+    generate_artifacts::content_hash,
+    isograph_literals::{
+        extract_iso_literals_from_file, read_files_in_folder, IsoLiteralExtraction,
+    },
+    progress::ProgressReporting,
+    write_artifacts::visit_files,
+};
+
+/// The compilation cache is written next to the config file. It contains absolute
+/// paths, so it should not be checked in.
+pub(crate) static CACHE_FILE_NAME: &'static str = ".isograph_cache";
+
+/// What the last successful compilation read and wrote, by absolute path: the
+/// SHA-256 hashes of the config, schema, schema extensions and stitched schemas, the
+/// hash and iso literals of every source file, and the hashes of the artifacts.
+///
+/// It is written as JSON, e.g.
+/// `{"compiler_version":"0.0.4","inputs":{"/project/schema.graphql":"9f86d0…"},
+/// "source_files":{"/project/src/HomeRoute.tsx":{"hash":"2c26b4…","iso_literals":[…]}},
+/// "artifacts":{…}}`.
+#[derive(Debug, Serialize, Deserialize)]
+struct CompilationCache {
+    compiler_version: String,
+    inputs: BTreeMap<PathBuf, String>,
+    source_files: BTreeMap<PathBuf, CachedSourceFile>,
+    artifacts: BTreeMap<PathBuf, String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct CachedSourceFile {
+    hash: String,
+    iso_literals: Vec<CachedIsoLiteral>,
+}
+
+/// An owned IsoLiteralExtraction
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct CachedIsoLiteral {
+    const_export_name: Option<String>,
+    text: String,
+    start_index: usize,
+    has_associated_js_function: bool,
+    has_paren: bool,
+}
+
+/// Compiles the project, unless nothing that affects the artifacts changed since the
+/// last successful compilation, as recorded in the compilation cache. Returns None
+/// if compilation was skipped.
+///
+/// The iso literals of each source file are cached, so only the source files that
+/// changed are read for iso literals again, and edits that do not touch iso literals
+/// do not cause a compilation. If iso literals changed, the schema is validated
+/// again (which depends on every iso literal), but only the reader artifacts of the
+/// client fields that are affected by the changed files are regenerated. If the
+/// config, the schema or the artifact directory changed, every artifact is
+/// regenerated. As always, only the artifacts whose contents changed are written.
+///
+/// Nothing is printed other than warnings (in particular, progress is not
+/// reported).
+pub fn compile_with_cache(
+    config: &CompilerConfig,
+) -> Result<Option<CompilationStats>, BatchCompileError> {
+    compile_with_cache_reporting_progress(config, ProgressReporting::Hidden)
+}
+
+fn compile_with_cache_reporting_progress(
+    config: &CompilerConfig,
+    progress_reporting: ProgressReporting,
+) -> Result<Option<CompilationStats>, BatchCompileError> {
+    let cache_path = cache_path(config);
+    let compiler_version = env!("CARGO_PKG_VERSION").to_string();
+    let inputs = schema_fingerprints(config)?
+        .into_iter()
+        .chain(std::iter::once((
+            config.config_file.clone(),
+            fingerprint_file(&config.config_file)?,
+        )))
+        .collect::<BTreeMap<_, _>>();
+
+    // The cached iso literals can be reused even if the previous compilation used a
+    // different schema or config, since they only depend on the source file.
+    let previous_cache = read_cache(&cache_path);
+    let previous_source_files = previous_cache
+        .as_ref()
+        .filter(|previous_cache| previous_cache.compiler_version == compiler_version)
+        .map(|previous_cache| &previous_cache.source_files);
+    let artifacts_are_current = match &previous_cache {
+        Some(previous_cache) => {
+            previous_cache.compiler_version == compiler_version
+                && previous_cache.inputs == inputs
+                && previous_cache.artifacts == artifact_fingerprints(config)?
+        }
+        None => false,
+    };
+
+    let normalized_root_path =
+        normalize_path(&config.project_root, config.options.path_normalization).map_err(
+            |message| BatchCompileError::UnableToLoadSchema {
+                path: config.project_root.clone(),
+                message,
+            },
+        )?;
+    let project_files = read_files_in_folder(
+        &normalized_root_path,
+        &config.single_file_component_extensions,
+        progress_reporting,
+    )?;
+    let source_files = project_files
+        .iter()
+        .map(|(file_path, file_content)| {
+            let absolute_file_path = normalized_root_path.join(file_path);
+            let hash = content_hash(file_content.as_bytes());
+            let cached_source_file = previous_source_files
+                .and_then(|previous_source_files| previous_source_files.get(&absolute_file_path))
+                .filter(|previous_source_file| previous_source_file.hash == hash)
+                .cloned()
+                .unwrap_or_else(|| CachedSourceFile {
+                    hash,
+                    iso_literals: extract_iso_literals_from_file(
+                        file_path,
+                        file_content,
+                        &config.single_file_component_extensions,
+                    )
+                    .into_iter()
+                    .map(CachedIsoLiteral::from)
+                    .collect(),
+                });
+            (absolute_file_path, cached_source_file)
+        })
+        .collect::<BTreeMap<_, _>>();
+
+    let changed_files = match (&previous_cache, artifacts_are_current) {
+        (Some(previous_cache), true) => changed_files(&previous_cache.source_files, &source_files),
+        _ => None,
+    };
+    if artifacts_are_current && changed_files.as_ref().map_or(false, HashSet::is_empty) {
+        // The hashes of the source files are updated, so that their iso literals are
+        // not extracted again next time.
+        write_cache(
+            &cache_path,
+            &CompilationCache {
+                compiler_version,
+                inputs,
+                source_files,
+                artifacts: previous_cache
+                    .map(|cache| cache.artifacts)
+                    .unwrap_or_default(),
+            },
+        )?;
+        return Ok(None);
+    }
+
+    let files = project_files
+        .iter()
+        .map(|(file_path, _)| {
+            let iso_literals = source_files[&normalized_root_path.join(file_path)]
+                .iso_literals
+                .iter()
+                .map(CachedIsoLiteral::as_extraction)
+                .collect();
+            (file_path.clone(), iso_literals)
+        })
+        .collect();
+    let stats = compile_with_extracted_iso_literals(
+        config,
+        &[],
+        Some(ExtractedIsoLiterals {
+            normalized_root_path,
+            files,
+            changed_files,
+        }),
+        progress_reporting,
+    )
+    .item?;
+
+    // If an input changed during compilation, its fingerprint will not match the
+    // one recorded here, so the next compilation will not be skipped.
+    write_cache(
+        &cache_path,
+        &CompilationCache {
+            compiler_version,
+            inputs,
+            source_files,
+            artifacts: artifact_fingerprints(config)?,
+        },
+    )?;
+
+    Ok(Some(stats))
+}
+
+/// Like compile_with_cache, but prints the result, as the CLI does.
+pub fn compile_with_cache_and_print(
+    config: &CompilerConfig,
+) -> Result<Option<CompilationStats>, BatchCompileError> {
+    eprintln!("{}", "Starting to compile.".cyan());
+
+    let result = WithDuration::new(|| {
+        compile_with_cache_reporting_progress(config, ProgressReporting::Shown)
+    });
+    match result.item {
+        Ok(None) => {
+            eprintln!(
+                "{}",
+                "Nothing that affects the artifacts changed since the last compilation, so \
+                there is nothing to compile. Pass --no-cache to compile anyway.\n"
+                    .bright_green()
+            );
+            Ok(None)
+        }
+        Ok(Some(stats)) => print_compilation_result(
+            config,
+            WithDuration {
+                elapsed_time: result.elapsed_time,
+                item: Ok(stats),
+            },
+        )
+        .map(Some),
+        Err(err) => print_compilation_result(
+            config,
+            WithDuration {
+                elapsed_time: result.elapsed_time,
+                item: Err(err),
+            },
+        )
+        .map(Some),
+    }
+}
+
+/// The absolute paths of the source files whose iso literals changed (including
+/// source files that were added or deleted), or None if every artifact must be
+/// regenerated.
+///
+/// Whether a client field is an entrypoint is not recorded by file, so if an
+/// entrypoint was added or removed, every artifact is regenerated.
+fn changed_files(
+    previous_source_files: &BTreeMap<PathBuf, CachedSourceFile>,
+    source_files: &BTreeMap<PathBuf, CachedSourceFile>,
+) -> Option<HashSet<PathBuf>> {
+    if entrypoint_declarations(previous_source_files) != entrypoint_declarations(source_files) {
+        return None;
+    }
+
+    let iso_literals = |source_files: &BTreeMap<PathBuf, CachedSourceFile>, path: &PathBuf| {
+        source_files
+            .get(path)
+            .map(|source_file| source_file.iso_literals.as_slice())
+            .unwrap_or_default()
+    };
+
+    let mut changed_files = HashSet::new();
+    for path in previous_source_files.keys().chain(source_files.keys()) {
+        let previous_iso_literals = iso_literals(previous_source_files, path);
+        let current_iso_literals = iso_literals(source_files, path);
+        // Iso literals that merely moved within the file do not affect the artifacts
+        let unchanged = previous_iso_literals.len() == current_iso_literals.len()
+            && previous_iso_literals
+                .iter()
+                .zip(current_iso_literals)
+                .all(|(previous, current)| previous.is_equivalent_to(current));
+        if !unchanged {
+            changed_files.insert(path.clone());
+        }
+    }
+    Some(changed_files)
+}
+
+/// The texts of the entrypoint declarations of every source file, sorted
+fn entrypoint_declarations(source_files: &BTreeMap<PathBuf, CachedSourceFile>) -> Vec<&str> {
+    let mut entrypoint_declarations = source_files
+        .values()
+        .flat_map(|source_file| source_file.iso_literals.iter())
+        .map(|iso_literal| iso_literal.text.trim())
+        .filter(|text| text.starts_with("entrypoint"))
+        .collect::<Vec<_>>();
+    entrypoint_declarations.sort();
+    entrypoint_declarations
+}
+
+fn cache_path(config: &CompilerConfig) -> PathBuf {
+    config.config_file.with_file_name(CACHE_FILE_NAME)
+}
+
+/// A missing or unreadable cache (e.g. one written in a different format) is
+/// treated as if nothing was compiled before.
+fn read_cache(cache_path: &Path) -> Option<CompilationCache> {
+    let contents = fs::read_to_string(cache_path).ok()?;
+    serde_json::from_str(&contents).ok()
+}
+
+fn write_cache(cache_path: &Path, cache: &CompilationCache) -> Result<(), BatchCompileError> {
+    let contents = serde_json::to_string(cache).expect("The cache should be serializable");
+    fs::write(cache_path, contents).map_err(|message| {
+        BatchCompileError::UnableToWriteCompilationCache {
+            path: cache_path.to_path_buf(),
+            message,
+        }
+    })
+}
+
+/// The fingerprints of every file in the artifact directory, which, after a
+/// successful compilation, contains exactly the artifacts.
+fn artifact_fingerprints(
+    config: &CompilerConfig,
+) -> Result<BTreeMap<PathBuf, String>, BatchCompileError> {
+    let mut artifact_paths = vec![];
+    if config.artifact_directory.exists() {
+        visit_files(&config.artifact_directory, &mut |path| {
+            artifact_paths.push(path)
+        })?;
+    }
+    artifact_paths
+        .into_iter()
+        .map(|path| {
+            let fingerprint = fingerprint_file(&path)?;
+            Ok((path, fingerprint))
+        })
+        .collect()
+}
+
+fn fingerprint_file(path: &Path) -> Result<String, BatchCompileError> {
+    let contents = fs::read(path).map_err(|message| BatchCompileError::UnableToReadFile {
+        path: path.to_path_buf(),
+        message,
+    })?;
+    Ok(content_hash(&contents))
+}
+
+impl CachedIsoLiteral {
+    fn as_extraction(&self) -> IsoLiteralExtraction<'_> {
+        IsoLiteralExtraction {
+            const_export_name: self.const_export_name.as_deref(),
+            iso_literal_text: &self.text,
+            iso_literal_start_index: self.start_index,
+            has_associated_js_function: self.has_associated_js_function,
+            has_paren: self.has_paren,
+        }
+    }
+
+    /// Whether the iso literals are the same, except for where they are in the file
+    fn is_equivalent_to(&self, other: &CachedIsoLiteral) -> bool {
+        self.const_export_name == other.const_export_name
+            && self.text == other.text
+            && self.has_associated_js_function == other.has_associated_js_function
+            && self.has_paren == other.has_paren
+    }
+}
+
+impl<'a> From<IsoLiteralExtraction<'a>> for CachedIsoLiteral {
+    fn from(iso_literal_extraction: IsoLiteralExtraction<'a>) -> Self {
+        CachedIsoLiteral {
+            const_export_name: iso_literal_extraction.const_export_name.map(str::to_string),
+            text: iso_literal_extraction.iso_literal_text.to_string(),
+            start_index: iso_literal_extraction.iso_literal_start_index,
+            has_associated_js_function: iso_literal_extraction.has_associated_js_function,
+            has_paren: iso_literal_extraction.has_paren,
+        }
+    }
+}
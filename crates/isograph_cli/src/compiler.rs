@@ -0,0 +1,339 @@
+use std::{
+    collections::{BTreeSet, HashMap},
+    fmt,
+    path::{Path, PathBuf},
+};
+
+use common_lang_types::{Location, ScalarFieldName, TextSource, UnvalidatedTypeName, WithLocation};
+use intern::{string_key::Intern, Lookup};
+use isograph_config::CompilerConfig;
+use isograph_schema::{Entity, Schema, ValidatedSchema};
+
+use crate::{
+    batch_compile::{
+        create_unvalidated_schema, parse_schema_with_config_path,
+        process_client_fields_and_entrypoints, read_and_extract_iso_literals_with_unsaved_files,
+        read_and_parse_schema_extensions, BatchCompileError,
+    },
+    progress::ProgressReporting,
+    project_index::ProjectIndex,
+    schema::read_schema_file,
+    schema_stitching::read_and_stitch_schemas,
+};
+
+/// A long-lived compiler for a single project, for processes (such as a daemon
+/// or a language server) that are handed new versions of the schema, e.g. by
+/// a dev server, instead of reading them from disk.
+pub struct Compiler<'config> {
+    config: &'config CompilerConfig,
+    client_field_validation: ClientFieldValidationOutcome,
+    index: Option<ProjectIndex>,
+}
+
+impl<'config> Compiler<'config> {
+    /// Reads the schema at config.schema, and validates every client field
+    /// in the project against it.
+    pub fn new(config: &'config CompilerConfig) -> Result<Self, BatchCompileError> {
+        Self::with_unsaved_files(config, &HashMap::new())
+    }
+
+    /// Like Compiler::new, but the contents of the files in unsaved_files (by
+    /// absolute path) are used instead of their contents on disk, e.g. for files
+    /// that are being edited in an editor.
+    pub fn with_unsaved_files(
+        config: &'config CompilerConfig,
+        unsaved_files: &HashMap<PathBuf, String>,
+    ) -> Result<Self, BatchCompileError> {
+        let schema_content = read_schema_file(&config.schema, config.options.path_normalization)?;
+        let client_field_validation =
+            validate_client_fields(config, &schema_content, unsaved_files)?;
+        let index = create_index(config, &client_field_validation);
+        Ok(Compiler {
+            config,
+            client_field_validation,
+            index,
+        })
+    }
+
+    /// Rebuilds the schema from new_sdl, revalidates every client field in the
+    /// project against it, and reports which client fields were broken or fixed
+    /// by the change.
+    ///
+    /// If new_sdl cannot be processed, an error is returned and the previous
+    /// results are kept, so the next delta is relative to the last schema that
+    /// could be processed.
+    pub fn replace_schema(
+        &mut self,
+        new_sdl: &str,
+    ) -> Result<SchemaReplacementDelta, BatchCompileError> {
+        // TODO errors are printed by reading the schema file from disk, which
+        // may not match new_sdl.
+        let client_field_validation =
+            validate_client_fields(self.config, new_sdl, &HashMap::new())?;
+        let delta =
+            SchemaReplacementDelta::new(&self.client_field_validation, &client_field_validation);
+
+        self.index = create_index(self.config, &client_field_validation);
+        self.client_field_validation = client_field_validation;
+
+        Ok(delta)
+    }
+
+    /// Rereads the schema and the iso literals in the project, e.g. after files
+    /// were modified, and revalidates every client field, so that the schema and
+    /// the index reflect the current version of the project.
+    ///
+    /// If the schema cannot be processed, an error is returned and the previous
+    /// results are kept.
+    pub fn refresh(&mut self) -> Result<(), BatchCompileError> {
+        self.refresh_with_unsaved_files(&HashMap::new())
+    }
+
+    /// Like Compiler::refresh, but the contents of the files in unsaved_files (by
+    /// absolute path) are used instead of their contents on disk.
+    pub fn refresh_with_unsaved_files(
+        &mut self,
+        unsaved_files: &HashMap<PathBuf, String>,
+    ) -> Result<(), BatchCompileError> {
+        let schema_content =
+            read_schema_file(&self.config.schema, self.config.options.path_normalization)?;
+        let client_field_validation =
+            validate_client_fields(self.config, &schema_content, unsaved_files)?;
+
+        self.index = create_index(self.config, &client_field_validation);
+        self.client_field_validation = client_field_validation;
+
+        Ok(())
+    }
+
+    /// The errors in client fields (and entrypoints) that were found when the
+    /// project was last validated.
+    pub fn errors(&self) -> &[WithLocation<String>] {
+        &self.client_field_validation.errors
+    }
+
+    /// The validated schema, or None if any client field failed validation.
+    pub fn schema(&self) -> Option<&ValidatedSchema> {
+        self.client_field_validation.validated_schema.as_ref()
+    }
+
+    /// The index of the validated schema, or None if any client field failed
+    /// validation.
+    pub fn index(&self) -> Option<&ProjectIndex> {
+        self.index.as_ref()
+    }
+
+    /// Finds the client field declaration, selection or schema type whose name is
+    /// at offset (relative to the start of the file) in the file at path, which
+    /// must be absolute. The entity refers to the schema returned by
+    /// Compiler::schema, so nothing is found if any client field failed validation.
+    pub fn lookup(&self, path: &Path, offset: u32) -> Option<Entity> {
+        let path = path.to_str()?.intern().into();
+        self.schema()?.lookup(path, offset)
+    }
+}
+
+fn create_index(
+    config: &CompilerConfig,
+    client_field_validation: &ClientFieldValidationOutcome,
+) -> Option<ProjectIndex> {
+    client_field_validation
+        .validated_schema
+        .as_ref()
+        .map(|validated_schema| ProjectIndex::new(validated_schema, config))
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub struct ClientFieldKey {
+    pub parent_type_name: UnvalidatedTypeName,
+    pub client_field_name: ScalarFieldName,
+}
+
+impl fmt::Display for ClientFieldKey {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{}.{}",
+            self.parent_type_name.lookup(),
+            self.client_field_name.lookup()
+        )
+    }
+}
+
+#[derive(Debug, Default)]
+pub struct SchemaReplacementDelta {
+    /// Client fields that were valid against the previous schema, and are
+    /// invalid against the new one.
+    pub newly_broken_client_fields: Vec<ClientFieldKey>,
+    /// Client fields that were invalid against the previous schema, and are
+    /// valid against the new one.
+    pub newly_fixed_client_fields: Vec<ClientFieldKey>,
+}
+
+impl SchemaReplacementDelta {
+    fn new(
+        previous: &ClientFieldValidationOutcome,
+        current: &ClientFieldValidationOutcome,
+    ) -> Self {
+        // Client fields that were added or removed since the previous schema was
+        // processed are neither broken nor fixed by the schema change.
+        let newly_broken_client_fields = current
+            .broken_client_fields
+            .iter()
+            .filter(|key| {
+                previous.client_fields.contains(key) && !previous.broken_client_fields.contains(key)
+            })
+            .copied()
+            .collect();
+        let newly_fixed_client_fields = previous
+            .broken_client_fields
+            .iter()
+            .filter(|key| {
+                current.client_fields.contains(key) && !current.broken_client_fields.contains(key)
+            })
+            .copied()
+            .collect();
+
+        SchemaReplacementDelta {
+            newly_broken_client_fields,
+            newly_fixed_client_fields,
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.newly_broken_client_fields.is_empty() && self.newly_fixed_client_fields.is_empty()
+    }
+}
+
+impl fmt::Display for SchemaReplacementDelta {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.is_empty() {
+            return write!(
+                f,
+                "No client fields were broken or fixed by the schema change."
+            );
+        }
+        if !self.newly_broken_client_fields.is_empty() {
+            write!(f, "Newly broken client fields:")?;
+            for key in self.newly_broken_client_fields.iter() {
+                write!(f, "\n- {}", key)?;
+            }
+        }
+        if !self.newly_fixed_client_fields.is_empty() {
+            if !self.newly_broken_client_fields.is_empty() {
+                writeln!(f)?;
+            }
+            write!(f, "Newly fixed client fields:")?;
+            for key in self.newly_fixed_client_fields.iter() {
+                write!(f, "\n- {}", key)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+#[derive(Debug, Default)]
+struct ClientFieldValidationOutcome {
+    client_fields: BTreeSet<ClientFieldKey>,
+    broken_client_fields: BTreeSet<ClientFieldKey>,
+    errors: Vec<WithLocation<String>>,
+    validated_schema: Option<ValidatedSchema>,
+}
+
+/// Processes and validates every client field in the project against the schema
+/// in schema_content. The iso literals in unsaved_files are read from there, instead
+/// of from disk.
+///
+/// Errors that prevent the schema itself from being processed are returned. Errors
+/// in client fields are not; instead, a client field is considered broken if any
+/// error is reported within its iso literal.
+fn validate_client_fields(
+    config: &CompilerConfig,
+    schema_content: &str,
+    unsaved_files: &HashMap<PathBuf, String>,
+) -> Result<ClientFieldValidationOutcome, BatchCompileError> {
+    let type_system_document = parse_schema_with_config_path(schema_content, config)?;
+    // Stitched schemas are merged before the schema extensions, so that the schema
+    // extensions can extend the types of the stitched schemas.
+    let mut type_extension_documents = read_and_stitch_schemas(config, &type_system_document)?;
+    type_extension_documents.extend(read_and_parse_schema_extensions(config)?);
+
+    let (mut schema, original_outcome) =
+        create_unvalidated_schema(type_system_document, type_extension_documents, config)?;
+
+    let (client_field_declarations, parsed_entrypoints) =
+        read_and_extract_iso_literals_with_unsaved_files(
+            config,
+            unsaved_files,
+            ProgressReporting::Hidden,
+        )?;
+
+    let client_field_text_sources = client_field_declarations
+        .iter()
+        .map(|(client_field_declaration, text_source)| {
+            (
+                ClientFieldKey {
+                    parent_type_name: client_field_declaration.item.parent_type.item,
+                    client_field_name: client_field_declaration.item.client_field_name.item,
+                },
+                *text_source,
+            )
+        })
+        .collect::<Vec<_>>();
+
+    let mut errors = vec![];
+    if let Err(process_errors) = process_client_fields_and_entrypoints(
+        &mut schema,
+        client_field_declarations,
+        parsed_entrypoints,
+        &config.packages,
+    ) {
+        errors.extend(to_located_messages(process_errors));
+    }
+
+    schema.add_fields_to_subtypes(
+        &original_outcome
+            .type_refinement_maps
+            .supertype_to_subtype_map,
+    )?;
+
+    let validated_schema = match Schema::validate_and_construct(schema) {
+        Ok(validated_schema) => Some(validated_schema),
+        Err(validation_errors) => {
+            errors.extend(to_located_messages(validation_errors));
+            None
+        }
+    };
+    // Client fields that failed to be processed are missing from the validated
+    // schema, so it is only kept if every client field is valid.
+    let validated_schema = validated_schema.filter(|_| errors.is_empty());
+
+    let broken_text_sources = errors
+        .iter()
+        .filter_map(|error| match error.location {
+            Location::Embedded(embedded_location) => Some(embedded_location.text_source),
+            Location::Generated => None,
+        })
+        .collect::<BTreeSet<TextSource>>();
+
+    let mut outcome = ClientFieldValidationOutcome {
+        validated_schema,
+        errors,
+        ..Default::default()
+    };
+    for (key, text_source) in client_field_text_sources {
+        if broken_text_sources.contains(&text_source) {
+            outcome.broken_client_fields.insert(key);
+        }
+        outcome.client_fields.insert(key);
+    }
+    Ok(outcome)
+}
+
+fn to_located_messages<T: fmt::Display>(
+    errors: Vec<WithLocation<T>>,
+) -> impl Iterator<Item = WithLocation<String>> {
+    errors
+        .into_iter()
+        .map(|error| WithLocation::new(error.item.to_string(), error.location))
+}
@@ -1,39 +1,74 @@
 use std::{
     cmp::Ordering,
-    collections::{hash_map::Entry, HashMap, HashSet},
+    collections::{btree_map::Entry, BTreeMap, BTreeSet, HashMap, HashSet},
     fmt::{self, Debug, Display},
     io,
-    path::PathBuf,
+    path::{Path, PathBuf},
     str::FromStr,
 };
 
 use common_lang_types::{
-    HasName, IsographObjectTypeName, Location, QueryOperationName, SelectableFieldName, Span,
-    UnvalidatedTypeName, VariableName, WithLocation, WithSpan,
+    ArtifactNamespace, DescriptionValue, HasName, IsographObjectTypeName, Location,
+    QueryOperationName, SelectableFieldName, Span, UnvalidatedTypeName, VariableName, WithLocation,
+    WithSpan,
 };
 use graphql_lang_types::{
     GraphQLInputValueDefinition, ListTypeAnnotation, NamedTypeAnnotation, NonNullTypeAnnotation,
     TypeAnnotation,
 };
 use intern::{string_key::Intern, Lookup};
+use isograph_config::{
+    ArtifactFormat, ComplexityBudget, ConfigOptions, EnumRepresentation, FieldOrdering,
+    NormalizationAliasStrategy, PersistedQueries,
+};
 use isograph_lang_types::{
-    ClientFieldId, NonConstantValue, SelectableFieldId, Selection, SelectionFieldArgument,
-    ServerFieldSelection, VariableDefinition,
+    ClientFieldId, NonConstantValue, ObjectId, SelectableFieldId, Selection,
+    SelectionFieldArgument, ServerFieldSelection, Unwrap, VariableDefinition,
 };
 use isograph_schema::{
-    create_merged_selection_set, into_name_and_arguments, refetched_paths_for_resolver,
-    ArtifactQueueItem, ClientFieldActionKind, ClientFieldVariant, FieldDefinitionLocation,
-    FieldMapItem, MergedLinkedFieldSelection, MergedScalarFieldSelection, MergedSelectionSet,
-    MergedServerFieldSelection, MutationFieldResolverInfo, NameAndArguments,
-    ObjectTypeAndFieldNames, PathToRefetchField, RefetchFieldResolverInfo, RequiresRefinement,
-    RootRefetchedPath, ValidatedClientField, ValidatedSchema, ValidatedSchemaObject,
-    ValidatedSelection, ValidatedVariableDefinition, ENTRYPOINT, READER,
+    artifact_type_directory, create_merged_selection_set, data_requirements,
+    into_name_and_arguments, refetched_paths_for_resolver, ArtifactQueueItem,
+    ClientFieldActionKind, ClientFieldVariant, DataRequirement, FieldDefinitionLocation,
+    FieldMapItem, MergedDeferredFragmentSelection, MergedInlineFragmentSelection,
+    MergedLinkedFieldSelection, MergedScalarFieldSelection, MergedSelectionSet,
+    MergedServerFieldSelection, MergedStreamDirective, MutationFieldResolverInfo, NameAndArguments,
+    ObjectTypeAndFieldNames, OperationComplexity, OperationKind, PathToRefetchField,
+    RefetchFieldResolverInfo, RequiresRefinement, RootRefetchedPath, SchemaScalar,
+    ValidatedClientField, ValidatedSchema, ValidatedSchemaObject, ValidatedSelection,
+    ValidatedVariableDefinition, ENTRYPOINT, READER,
 };
+use lazy_static::lazy_static;
+use serde_json::{json, Value};
+use sha2::{Digest, Sha256};
 use thiserror::Error;
 
-use crate::write_artifacts::write_to_disk;
+use crate::{
+    post_emit_command::PostEmitManifest,
+    progress::ProgressReporting,
+    schema_version::stamp_schema_hash,
+    write_artifacts::{find_orphaned_files, write_to_disk},
+};
+
+/// Ordered, so that import statements (and thus artifacts) are identical across
+/// compilations.
+type NestedClientFieldImports = BTreeMap<ObjectTypeAndFieldNames, JavaScriptImports>;
+
+/// GraphQL type names cannot start with __, so this cannot clash with the
+/// directories of artifacts for types.
+static SHARED_QUERY_TEXT_DIRECTORY: &'static str = "__queryText";
 
-type NestedClientFieldImports = HashMap<ObjectTypeAndFieldNames, JavaScriptImports>;
+/// Refetch queries of client fields are named so as not to clash with the
+/// numbered refetch queries of entrypoints, which are in the same directory.
+pub(crate) static NODE_REFETCH_QUERY_FILE_NAME: &'static str = "refetch_query";
+
+lazy_static! {
+    static ref ENUMS: SelectableFieldName = "enums".intern().into();
+    static ref DATA_REQUIREMENTS: SelectableFieldName = "data_requirements".intern().into();
+    static ref PERSISTED_QUERIES: SelectableFieldName = "persisted_queries".intern().into();
+}
+
+static TS_FILE_EXTENSION: &'static str = "ts";
+static JSON_FILE_EXTENSION: &'static str = "json";
 
 macro_rules! derive_display {
     ($type:ident) => {
@@ -49,20 +84,390 @@ pub(crate) struct PathAndContent {
     pub(crate) relative_directory: PathBuf,
     // It doesn't make sense that this is a SelectableFieldName
     pub(crate) file_name_prefix: SelectableFieldName,
+    pub(crate) file_extension: &'static str,
     pub(crate) file_content: String,
 }
 
+pub struct GeneratedArtifactsStats {
+    /// The number of generated artifacts, whether or not they were written
+    pub artifact_count: usize,
+    /// The number of artifacts that were written, because their contents changed
+    pub written_artifact_count: usize,
+    /// The absolute paths of the artifacts that were written, in the order in
+    /// which they were written
+    pub written_artifact_paths: Vec<PathBuf>,
+    /// The number of artifacts that were not written, because their contents did
+    /// not change
+    pub unchanged_artifact_count: usize,
+    /// The number of stale files that were deleted from the artifact directory
+    pub removed_artifact_count: usize,
+    /// The total size of the contents of the generated artifacts
+    pub artifact_bytes: usize,
+    /// The complexity of each generated operation, sorted by operation name
+    pub operation_complexities: Vec<OperationComplexityReport>,
+    /// The artifacts that are larger than the max_artifact_size_bytes option, sorted
+    /// by path
+    pub oversized_artifacts: Vec<OversizedArtifactReport>,
+}
+
+pub struct OperationComplexityReport {
+    pub operation_name: String,
+    pub complexity: OperationComplexity,
+}
+
+/// An artifact that is larger than the max_artifact_size_bytes option.
+pub struct OversizedArtifactReport {
+    /// The path of the artifact, relative to the artifact directory
+    pub path: PathBuf,
+    pub size_bytes: usize,
+    /// The client field to which the artifact belongs, e.g. `Query.HomePage`.
+    /// None for artifacts that do not belong to a single client field (e.g. iso.ts).
+    pub client_field_name: Option<String>,
+    /// The client fields that are selected, directly or transitively, by that client
+    /// field, and which select the most server fields, with the number of server
+    /// fields they select. Sorted by that number, in descending order.
+    pub biggest_nested_client_fields: Vec<(String, usize)>,
+}
+
 // TODO move to another module
-pub(crate) fn generate_and_write_artifacts(
+/// Generates the artifacts of the validated schema and writes the ones that changed,
+/// without reporting progress.
+pub fn generate_and_write_artifacts(
     schema: &ValidatedSchema,
     project_root: &PathBuf,
     artifact_directory: &PathBuf,
-) -> Result<usize, GenerateArtifactsError> {
-    let paths_and_contents =
-        get_artifact_path_and_contents(schema, project_root, artifact_directory);
-    let artifact_count = write_to_disk(paths_and_contents, artifact_directory)?;
+    options: ConfigOptions,
+    schema_hash: &str,
+) -> Result<GeneratedArtifactsStats, GenerateArtifactsError> {
+    generate_and_write_artifacts_reporting_progress(
+        schema,
+        project_root,
+        artifact_directory,
+        options,
+        schema_hash,
+        None,
+        None,
+        ProgressReporting::Hidden,
+    )
+}
+
+/// Like generate_and_write_artifacts, but compares the artifacts with (and
+/// updates) post_emit_manifest, if passed, and reports progress if asked to.
+///
+/// If changed_source_files is passed, the reader artifacts of the client fields
+/// that are unaffected by them are not generated, and are left as they are.
+pub(crate) fn generate_and_write_artifacts_reporting_progress(
+    schema: &ValidatedSchema,
+    project_root: &PathBuf,
+    artifact_directory: &PathBuf,
+    options: ConfigOptions,
+    schema_hash: &str,
+    changed_source_files: Option<&HashSet<PathBuf>>,
+    post_emit_manifest: Option<&mut PostEmitManifest>,
+    progress_reporting: ProgressReporting,
+) -> Result<GeneratedArtifactsStats, GenerateArtifactsError> {
+    let unaffected_client_field_ids = changed_source_files
+        .map(|changed_source_files| unaffected_client_field_ids(schema, changed_source_files))
+        .unwrap_or_default();
+    let mut retained_artifact_paths = vec![];
+    let artifact_infos = get_artifact_infos(
+        schema,
+        project_root,
+        artifact_directory,
+        options,
+        &unaffected_client_field_ids,
+        &mut retained_artifact_paths,
+    );
+
+    let mut operation_complexities: Vec<_> = artifact_infos
+        .iter()
+        .filter_map(ArtifactInfo::operation_complexity)
+        .collect();
+    operation_complexities
+        .sort_by(|report_1, report_2| report_1.operation_name.cmp(&report_2.operation_name));
+    for operation_complexity in operation_complexities.iter() {
+        check_complexity_budget(operation_complexity, options.complexity_budget)?;
+    }
+    check_no_conflicting_selections_without_aliases(schema, options)?;
+    check_client_field_files_exist(schema, project_root)?;
+
+    let mut oversized_artifacts = vec![];
+    let paths_and_contents = artifact_infos_to_paths_and_contents(schema, artifact_infos, options)
+        .map(|path_and_content| stamp_schema_hash(path_and_content, schema_hash))
+        .inspect(|path_and_content| {
+            if let Some(max_artifact_size_bytes) = options.max_artifact_size_bytes {
+                if path_and_content.file_content.len() > max_artifact_size_bytes {
+                    oversized_artifacts.push(oversized_artifact_report(schema, path_and_content));
+                }
+            }
+        });
+    let written_artifacts = write_to_disk(
+        paths_and_contents,
+        artifact_directory,
+        &retained_artifact_paths,
+        post_emit_manifest,
+        progress_reporting,
+    )?;
+    oversized_artifacts.sort_by(|report_1, report_2| report_1.path.cmp(&report_2.path));
+
+    Ok(GeneratedArtifactsStats {
+        artifact_count: written_artifacts.written_paths.len() + written_artifacts.unchanged_count,
+        written_artifact_count: written_artifacts.written_paths.len(),
+        written_artifact_paths: written_artifacts.written_paths,
+        unchanged_artifact_count: written_artifacts.unchanged_count,
+        removed_artifact_count: written_artifacts.removed_count,
+        artifact_bytes: written_artifacts.total_bytes,
+        operation_complexities,
+        oversized_artifacts,
+    })
+}
+
+/// The number of nested client fields that are named when an artifact is too large
+static BIGGEST_NESTED_CLIENT_FIELD_COUNT: usize = 3;
+
+/// Artifacts are attributed to client fields by their directory, so e.g. refetch
+/// query artifacts are attributed to the entrypoint from which they are reachable.
+fn oversized_artifact_report(
+    schema: &ValidatedSchema,
+    path_and_content: &PathAndContent,
+) -> OversizedArtifactReport {
+    let client_field = schema.client_fields.iter().find(|client_field| {
+        generate_path(
+            schema
+                .schema_data
+                .object(client_field.parent_object_id)
+                .name,
+            client_field.name,
+            client_field.type_and_field.artifact_namespace,
+        ) == path_and_content.relative_directory
+    });
+
+    let mut biggest_nested_client_fields = client_field
+        .and_then(|client_field| client_field.merged_selection_set.as_ref())
+        .map(|merged| {
+            merged
+                .encountered_client_field_ids
+                .iter()
+                .filter_map(|nested_client_field_id| {
+                    let nested_client_field = schema.resolver(*nested_client_field_id);
+                    let nested_merged = nested_client_field.merged_selection_set.as_ref()?;
+                    let field_count = OperationComplexity::new(
+                        schema,
+                        nested_client_field.parent_object_id,
+                        &nested_merged.selection_set,
+                    )
+                    .field_count;
+                    Some((
+                        format!(
+                            "{}.{}",
+                            nested_client_field.type_and_field.type_name, nested_client_field.name
+                        ),
+                        field_count,
+                    ))
+                })
+                .collect::<Vec<_>>()
+        })
+        .unwrap_or_default();
+    biggest_nested_client_fields.sort_by(|(name_1, field_count_1), (name_2, field_count_2)| {
+        field_count_2.cmp(field_count_1).then(name_1.cmp(name_2))
+    });
+    biggest_nested_client_fields.truncate(BIGGEST_NESTED_CLIENT_FIELD_COUNT);
+
+    OversizedArtifactReport {
+        path: path_and_content.relative_directory.join(format!(
+            "{}.{}",
+            path_and_content.file_name_prefix, path_and_content.file_extension
+        )),
+        size_bytes: path_and_content.file_content.len(),
+        client_field_name: client_field.map(|client_field| {
+            format!(
+                "{}.{}",
+                client_field.type_and_field.type_name, client_field.name
+            )
+        }),
+        biggest_nested_client_fields,
+    }
+}
+
+/// Artifacts import client fields from the files in which they are declared, so
+/// those files must exist. Otherwise (e.g. if the path in a @resolver directive is
+/// out of date), the generated import statements would be broken.
+fn check_client_field_files_exist(
+    schema: &ValidatedSchema,
+    project_root: &PathBuf,
+) -> Result<(), GenerateArtifactsError> {
+    for client_field in sorted_client_defined_fields(schema) {
+        if let ClientFieldActionKind::NamedImport((_, path)) = &client_field.action_kind {
+            if !project_root.join(path.lookup()).is_file() {
+                return Err(GenerateArtifactsError::ClientFieldFileDoesNotExist {
+                    type_name: client_field.type_and_field.type_name,
+                    field_name: client_field.type_and_field.field_name,
+                    path: path.lookup().to_string(),
+                    location: client_field.name_location,
+                });
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Without aliases, the network response contains each field once per record, under
+/// its name, so entrypoints that are generated without aliases cannot select a field
+/// with different arguments (e.g. `avatar(size: 50)` and `avatar(size: 100)`) on the
+/// same record. Refetch and mutation queries select a subset of the fields of their
+/// entrypoint, so checking entrypoints suffices.
+fn check_no_conflicting_selections_without_aliases(
+    schema: &ValidatedSchema,
+    options: ConfigOptions,
+) -> Result<(), GenerateArtifactsError> {
+    for entrypoint in sorted_entrypoints(schema) {
+        if entrypoint_options(entrypoint, options).normalization_alias_strategy
+            != NormalizationAliasStrategy::None
+        {
+            continue;
+        }
+        let Some(merged) = &entrypoint.merged_selection_set else {
+            continue;
+        };
+        if let Some((selection_1, selection_2)) = find_conflicting_selections(&merged.selection_set)
+        {
+            return Err(
+                GenerateArtifactsError::ConflictingSelectionsWithoutAliases {
+                    type_name: entrypoint.type_and_field.type_name,
+                    field_name: entrypoint.type_and_field.field_name,
+                    selection_1,
+                    selection_2,
+                    location: entrypoint.name_location,
+                },
+            );
+        }
+    }
+    Ok(())
+}
+
+/// Returns the first two selections (as printed in the query text) of the same field
+/// with different arguments in any selection set.
+fn find_conflicting_selections(
+    selection_set: &[WithSpan<MergedServerFieldSelection>],
+) -> Option<(String, String)> {
+    let mut arguments_by_field_name: HashMap<&str, &[WithLocation<SelectionFieldArgument>]> =
+        HashMap::new();
+    for selection in selection_set.iter() {
+        let (field_name, arguments) = match &selection.item {
+            MergedServerFieldSelection::ScalarField(scalar_field) => {
+                (scalar_field.name.item.lookup(), &scalar_field.arguments)
+            }
+            MergedServerFieldSelection::LinkedField(linked_field) => {
+                if let Some(conflict) = find_conflicting_selections(&linked_field.selection_set) {
+                    return Some(conflict);
+                }
+                (linked_field.name.item.lookup(), &linked_field.arguments)
+            }
+            MergedServerFieldSelection::InlineFragment(inline_fragment) => {
+                if let Some(conflict) = find_conflicting_selections(&inline_fragment.selection_set)
+                {
+                    return Some(conflict);
+                }
+                continue;
+            }
+            MergedServerFieldSelection::DeferredFragment(deferred_fragment) => {
+                if let Some(conflict) =
+                    find_conflicting_selections(&deferred_fragment.selection_set)
+                {
+                    return Some(conflict);
+                }
+                continue;
+            }
+        };
+        match arguments_by_field_name.entry(field_name) {
+            std::collections::hash_map::Entry::Occupied(occupied) => {
+                if *occupied.get() != arguments.as_slice() {
+                    return Some((
+                        format!(
+                            "{field_name}{}",
+                            get_serialized_arguments_for_query_text(occupied.get())
+                        ),
+                        format!(
+                            "{field_name}{}",
+                            get_serialized_arguments_for_query_text(arguments)
+                        ),
+                    ));
+                }
+            }
+            std::collections::hash_map::Entry::Vacant(vacant) => {
+                vacant.insert(arguments);
+            }
+        }
+    }
+    None
+}
+
+/// The options with which the operations of an entrypoint, and the refetch and
+/// mutation queries that are reachable from it, are generated.
+fn entrypoint_options(entrypoint: &ValidatedClientField, options: ConfigOptions) -> ConfigOptions {
+    if entrypoint.no_aliases {
+        ConfigOptions {
+            normalization_alias_strategy: NormalizationAliasStrategy::None,
+            ..options
+        }
+    } else {
+        options
+    }
+}
+
+fn check_complexity_budget(
+    operation_complexity: &OperationComplexityReport,
+    complexity_budget: ComplexityBudget,
+) -> Result<(), GenerateArtifactsError> {
+    let OperationComplexityReport {
+        operation_name,
+        complexity,
+    } = operation_complexity;
+    let metrics = [
+        (
+            "field count",
+            complexity.field_count,
+            complexity_budget.max_field_count,
+        ),
+        (
+            "max depth",
+            complexity.max_depth,
+            complexity_budget.max_depth,
+        ),
+        (
+            "estimated response size",
+            complexity.estimated_response_size,
+            complexity_budget.max_estimated_response_size,
+        ),
+    ];
+    for (metric, value, limit) in metrics {
+        if let Some(limit) = limit {
+            if value > limit {
+                complexity_budget.on_exceeded.on_failure(|| {
+                    GenerateArtifactsError::ComplexityBudgetExceeded {
+                        operation_name: operation_name.clone(),
+                        metric,
+                        value,
+                        limit,
+                    }
+                })?;
+            }
+        }
+    }
+    Ok(())
+}
 
-    Ok(artifact_count)
+/// Returns the files in the artifact directory that would not be written by
+/// compiling the current schema, without deleting them.
+pub(crate) fn find_orphaned_artifacts(
+    schema: &ValidatedSchema,
+    project_root: &PathBuf,
+    artifact_directory: &PathBuf,
+    options: ConfigOptions,
+) -> Result<Vec<PathBuf>, GenerateArtifactsError> {
+    let paths_and_contents =
+        get_artifact_path_and_contents(schema, project_root, artifact_directory, options);
+    find_orphaned_files(paths_and_contents, artifact_directory)
 }
 
 fn build_iso_overload_for_entrypoint<'schema>(
@@ -70,10 +475,18 @@ fn build_iso_overload_for_entrypoint<'schema>(
 ) -> (String, String) {
     let mut s: String = "".to_string();
     let import = format!(
-        "import entrypoint_{} from '../__isograph/{}/{}/entrypoint'\n",
+        "import entrypoint_{} from '{}'\n",
         validated_client_field.type_and_field.underscore_separated(),
-        validated_client_field.type_and_field.type_name,
-        validated_client_field.type_and_field.field_name,
+        // iso.ts is at the root of the artifact directory
+        relative_import_path(
+            Path::new(""),
+            &generate_path(
+                validated_client_field.type_and_field.type_name,
+                validated_client_field.type_and_field.field_name,
+                validated_client_field.type_and_field.artifact_namespace
+            )
+            .join("entrypoint")
+        ),
     );
     let formatted_field = format!(
         "entrypoint {}.{}",
@@ -98,18 +511,32 @@ fn build_iso_overload_for_client_defined_field(
     let import = format!(
         "import {{ {}__param }} from './{}/{}/reader'\n",
         client_field.type_and_field.underscore_separated(),
-        client_field.type_and_field.type_name,
-        client_field.type_and_field.field_name,
-    );
-    let formatted_field = format!(
-        "field {}.{}",
-        client_field.type_and_field.type_name, client_field.type_and_field.field_name
+        artifact_type_directory(
+            client_field.type_and_field.type_name,
+            client_field.type_and_field.artifact_namespace
+        ),
+        client_field.type_and_field.field_name
     );
+    let formatted_field = match client_field.fetchable_variant_of {
+        // The iso literal of a variant has the name of the client field that it is a
+        // variant of, so it is matched on the name of the variant as well, which may be
+        // preceded by variable definitions and other directives.
+        Some(fetchable_variant_of) => format!(
+            "`field {}.{}${{string}}@fetchable(name: \"{}\")`",
+            client_field.type_and_field.type_name,
+            fetchable_variant_of,
+            client_field.type_and_field.field_name
+        ),
+        None => format!(
+            "'field {}.{}'",
+            client_field.type_and_field.type_name, client_field.type_and_field.field_name
+        ),
+    };
     if matches!(client_field.variant, ClientFieldVariant::Component) {
         s.push_str(&format!(
             "
 export function iso<T>(
-  param: T & MatchesWhitespaceAndString<'{}', T>
+  param: T & MatchesWhitespaceAndString<{}, T>
 ): IdentityWithParamComponent<{}__param>;\n",
             formatted_field,
             client_field.type_and_field.underscore_separated(),
@@ -118,7 +545,7 @@ export function iso<T>(
         s.push_str(&format!(
             "
 export function iso<T>(
-  param: T & MatchesWhitespaceAndString<'{}', T>
+  param: T & MatchesWhitespaceAndString<{}, T>
 ): IdentityWithParam<{}__param>;\n",
             formatted_field,
             client_field.type_and_field.underscore_separated(),
@@ -184,14 +611,16 @@ export function iso(_isographLiteralText: string):
         file_content: imports,
         relative_directory: PathBuf::new(),
         file_name_prefix: "iso".intern().into(),
+        file_extension: TS_FILE_EXTENSION,
     }
 }
 
-fn sorted_entrypoints(schema: &ValidatedSchema) -> Vec<&ValidatedClientField> {
+pub(crate) fn sorted_entrypoints(schema: &ValidatedSchema) -> Vec<&ValidatedClientField> {
     let mut entrypoints = schema
         .entrypoints
         .iter()
         .map(|client_field_id| schema.resolver(*client_field_id))
+        .filter(|client_field| !client_field.skip_codegen)
         .collect::<Vec<_>>();
     entrypoints.sort_by(|client_field_1, client_field_2| {
         match client_field_1
@@ -243,15 +672,38 @@ fn sorted_client_defined_fields(schema: &ValidatedSchema) -> Vec<&ValidatedClien
         {
             Ordering::Less => Ordering::Less,
             Ordering::Greater => Ordering::Greater,
+            // Overloads are matched on the name written in the iso literal, which
+            // is shared by a client field and its variants. The overloads of the
+            // variants are more specific, so they come first.
             Ordering::Equal => sort_field_name(
-                client_field_1.type_and_field.field_name,
-                client_field_2.type_and_field.field_name,
-            ),
+                iso_literal_field_name(client_field_1),
+                iso_literal_field_name(client_field_2),
+            )
+            .then_with(|| {
+                client_field_2
+                    .fetchable_variant_of
+                    .is_some()
+                    .cmp(&client_field_1.fetchable_variant_of.is_some())
+            })
+            .then_with(|| {
+                client_field_1
+                    .type_and_field
+                    .field_name
+                    .cmp(&client_field_2.type_and_field.field_name)
+            }),
         }
     });
     fields
 }
 
+/// The name of the client field in its iso literal, which is not the name of the
+/// client field if it is a variant declared with @fetchable(name: "...").
+fn iso_literal_field_name(client_field: &ValidatedClientField) -> SelectableFieldName {
+    client_field
+        .fetchable_variant_of
+        .unwrap_or(client_field.type_and_field.field_name)
+}
+
 fn client_defined_fields<'a>(
     schema: &'a ValidatedSchema,
 ) -> impl Iterator<Item = &'a ValidatedClientField> + 'a {
@@ -259,20 +711,47 @@ fn client_defined_fields<'a>(
         matches!(
             client_field.action_kind,
             ClientFieldActionKind::NamedImport(_)
-        )
+        ) && !client_field.skip_codegen
     })
 }
 
-fn get_artifact_path_and_contents<'schema>(
+pub(crate) fn get_artifact_path_and_contents<'schema>(
     schema: &'schema ValidatedSchema,
     project_root: &PathBuf,
     artifact_directory: &PathBuf,
+    options: ConfigOptions,
+) -> impl Iterator<Item = PathAndContent> + 'schema {
+    let artifact_infos = get_artifact_infos(
+        schema,
+        project_root,
+        artifact_directory,
+        options,
+        &HashSet::new(),
+        &mut vec![],
+    );
+    artifact_infos_to_paths_and_contents(schema, artifact_infos, options)
+}
+
+/// The iso.ts and enums files only make sense alongside TypeScript artifacts, so
+/// they are not generated for other artifact formats.
+fn artifact_infos_to_paths_and_contents<'schema>(
+    schema: &'schema ValidatedSchema,
+    artifact_infos: Vec<ArtifactInfo<'schema>>,
+    options: ConfigOptions,
 ) -> impl Iterator<Item = PathAndContent> + 'schema {
-    let artifact_infos = get_artifact_infos(schema, project_root, artifact_directory);
+    let artifact_format = options.artifact_format;
+    let typescript_artifacts = match artifact_format {
+        ArtifactFormat::TypeScript => {
+            let mut typescript_artifacts = vec![build_iso_overload(schema)];
+            typescript_artifacts.extend(build_enums_artifact(schema, options.enum_representation));
+            typescript_artifacts
+        }
+        ArtifactFormat::Json => vec![],
+    };
     artifact_infos
         .into_iter()
-        .map(ArtifactInfo::to_path_and_content)
-        .chain(std::iter::once(build_iso_overload(schema)))
+        .map(move |artifact_info| artifact_info.to_path_and_content(artifact_format))
+        .chain(typescript_artifacts)
 }
 
 /// Get all artifacts according to the following scheme:
@@ -288,23 +767,44 @@ fn get_artifact_path_and_contents<'schema>(
 /// artifacts. In the future, we should just generate types for these client fields, not
 /// readers, etc.
 ///
+/// The reader artifacts (and data requirements) of unaffected_client_field_ids are
+/// not generated. Instead, their paths are added to retained_artifact_paths, so that
+/// they are not deleted.
+///
 /// TODO The artifact queue abstraction doesn't make much sense here.
 fn get_artifact_infos<'schema>(
     schema: &'schema ValidatedSchema,
     project_root: &PathBuf,
     artifact_directory: &PathBuf,
+    options: ConfigOptions,
+    unaffected_client_field_ids: &HashSet<ClientFieldId>,
+    retained_artifact_paths: &mut Vec<PathBuf>,
 ) -> Vec<ArtifactInfo<'schema>> {
     let mut artifact_queue = vec![];
     let mut encountered_client_field_ids = HashSet::new();
     let mut artifact_infos = vec![];
 
     for client_field_id in schema.entrypoints.iter() {
+        // Client fields with @skipCodegen cannot be selected by other client fields,
+        // so skipping them here means that no artifacts are generated for them.
+        let entrypoint = schema.resolver(*client_field_id);
+        if entrypoint.skip_codegen {
+            continue;
+        }
+        let entrypoint_options = entrypoint_options(entrypoint, options);
+        let mut entrypoint_artifact_queue = vec![];
         artifact_infos.push(ArtifactInfo::Entrypoint(generate_entrypoint_artifact(
             schema,
             *client_field_id,
-            &mut artifact_queue,
+            &mut entrypoint_artifact_queue,
             &mut encountered_client_field_ids,
+            entrypoint_options,
         )));
+        artifact_queue.extend(
+            entrypoint_artifact_queue
+                .into_iter()
+                .map(|queue_item| (queue_item, entrypoint_options)),
+        );
 
         // We also need to generate reader artifacts for the entrypoint client fields themselves
         encountered_client_field_ids.insert(*client_field_id);
@@ -312,62 +812,262 @@ fn get_artifact_infos<'schema>(
 
     for client_defined_field in client_defined_fields(schema) {
         if encountered_client_field_ids.insert(client_defined_field.id) {
-            // What are we doing here?
-            // We are generating, and throwing away, an entrypoint artifact. This has the effect of
-            // encountering selected __refetch fields. Refetch fields reachable from orphaned
-            // client fields still need reader (well... type) artifacts generated.
-            //
-            // Anyway, this sucks and should be improved.
-            let _ = generate_entrypoint_artifact(
-                schema,
-                client_defined_field.id,
-                &mut vec![],
-                &mut encountered_client_field_ids,
-            );
+            // Refetch fields reachable from orphaned client fields still need reader
+            // (well... type) artifacts generated.
+            if let Some(merged) = &client_defined_field.merged_selection_set {
+                encountered_client_field_ids
+                    .extend(merged.encountered_client_field_ids.iter().copied());
+            }
         }
     }
 
     for encountered_client_field_id in encountered_client_field_ids {
         let encountered_client_field = schema.resolver(encountered_client_field_id);
-        artifact_infos.push(ArtifactInfo::Reader(generate_reader_artifact(
+        // Client fields without a js function are inlined into the reader ASTs of the
+        // client fields that select them, so they only need a reader artifact if they
+        // are also entrypoints.
+        if matches!(
+            encountered_client_field.action_kind,
+            ClientFieldActionKind::Identity
+        ) && !schema.entrypoints.contains(&encountered_client_field_id)
+        {
+            continue;
+        }
+        let node_refetch_query = if options.generate_refetch_queries {
+            generate_node_refetch_query_artifact(schema, encountered_client_field, options)
+        } else {
+            None
+        };
+        // Refetch queries are generated regardless, since their query texts are
+        // deduplicated (and persisted) along with those of every other artifact.
+        if unaffected_client_field_ids.contains(&encountered_client_field_id) {
+            retained_artifact_paths.extend(retained_reader_artifact_paths(
+                schema,
+                encountered_client_field,
+                options,
+            ));
+            artifact_infos.extend(
+                node_refetch_query
+                    .map(|(refetch_query, _)| ArtifactInfo::RefetchQuery(refetch_query)),
+            );
+            continue;
+        }
+
+        let mut reader_artifact = generate_reader_artifact(
             schema,
             encountered_client_field,
             project_root,
             artifact_directory,
-        )))
+            options,
+        );
+        if let Some((_, allowed_variables)) = &node_refetch_query {
+            reader_artifact.refetch_query_allowed_variables = Some(allowed_variables.clone());
+        }
+        artifact_infos.push(ArtifactInfo::Reader(reader_artifact));
+        artifact_infos.extend(
+            node_refetch_query.map(|(refetch_query, _)| ArtifactInfo::RefetchQuery(refetch_query)),
+        );
+        if options.generate_data_requirements {
+            artifact_infos.extend(
+                generate_data_requirements_artifact(schema, encountered_client_field)
+                    .map(ArtifactInfo::DataRequirements),
+            );
+        }
     }
 
-    for queue_item in artifact_queue {
+    for (queue_item, entrypoint_options) in artifact_queue {
         artifact_infos.push(ArtifactInfo::RefetchQuery(match queue_item {
             ArtifactQueueItem::RefetchField(refetch_info) => {
-                get_artifact_for_refetch_field(schema, refetch_info)
+                get_artifact_for_refetch_field(schema, refetch_info, entrypoint_options)
             }
             ArtifactQueueItem::MutationField(mutation_info) => {
-                get_artifact_for_mutation_field(schema, mutation_info)
+                get_artifact_for_mutation_field(schema, mutation_info, entrypoint_options)
             }
         }))
     }
 
+    if options.minify_query_text {
+        for artifact_info in artifact_infos.iter_mut() {
+            if let Some(QueryTextDeclaration::Inline(query_text)) = artifact_info.query_text_mut() {
+                query_text.strip_ignored_characters();
+            }
+        }
+    }
+
+    // Persisted query texts are removed from the artifacts, so this must happen
+    // before they are deduplicated.
+    let persisted_queries_manifest = options
+        .persisted_queries
+        .map(|persisted_queries| persist_query_texts(&mut artifact_infos, persisted_queries));
+
+    // Shared query texts are imported by the artifacts that use them, which is
+    // not possible in JSON artifacts.
+    if options.artifact_format == ArtifactFormat::TypeScript {
+        deduplicate_query_texts(&mut artifact_infos);
+    }
+
+    artifact_infos.extend(persisted_queries_manifest.map(ArtifactInfo::PersistedQueriesManifest));
+
     artifact_infos
 }
 
+/// The client fields whose reader artifacts do not change if only the iso literals in
+/// changed_source_files (by absolute path) changed, i.e. the client fields that are
+/// neither declared in one of them, nor select (directly or transitively) a client
+/// field that is. The reader artifacts of entrypoints are always generated, since
+/// whether a client field is an entrypoint is not recorded in the schema by file.
+fn unaffected_client_field_ids(
+    schema: &ValidatedSchema,
+    changed_source_files: &HashSet<PathBuf>,
+) -> HashSet<ClientFieldId> {
+    let is_declared_in_changed_source_file = |client_field_id: ClientFieldId| {
+        match schema.resolver(client_field_id).name_location {
+            Location::Embedded(location) => {
+                changed_source_files.contains(Path::new(location.text_source.path.lookup()))
+            }
+            // E.g. refetch fields, which are not declared in a source file
+            Location::Generated => false,
+        }
+    };
+
+    schema
+        .client_fields
+        .iter()
+        .filter(|client_field| {
+            !schema.entrypoints.contains(&client_field.id)
+                && !is_declared_in_changed_source_file(client_field.id)
+                && client_field
+                    .merged_selection_set
+                    .as_ref()
+                    .map_or(false, |merged| {
+                        !merged
+                            .encountered_client_field_ids
+                            .iter()
+                            .any(|id| is_declared_in_changed_source_file(*id))
+                    })
+        })
+        .map(|client_field| client_field.id)
+        .collect()
+}
+
+/// The paths (relative to the artifact directory) of the reader artifact and data
+/// requirements of a client field, which are left as they are if it is unaffected by
+/// the changed source files.
+fn retained_reader_artifact_paths(
+    schema: &ValidatedSchema,
+    client_field: &ValidatedClientField,
+    options: ConfigOptions,
+) -> Vec<PathBuf> {
+    let relative_directory = generate_path(
+        schema
+            .schema_data
+            .object(client_field.parent_object_id)
+            .name,
+        client_field.name,
+        client_field.type_and_field.artifact_namespace,
+    );
+    let reader_file_extension = match options.artifact_format {
+        ArtifactFormat::TypeScript => TS_FILE_EXTENSION,
+        ArtifactFormat::Json => JSON_FILE_EXTENSION,
+    };
+    let mut paths = vec![relative_directory.join(format!("{}.{reader_file_extension}", *READER))];
+    if options.generate_data_requirements {
+        paths
+            .push(relative_directory.join(format!("{}.{JSON_FILE_EXTENSION}", *DATA_REQUIREMENTS)));
+    }
+    paths
+}
+
+/// Collects the query text of every artifact into a manifest, keyed by the SHA-256
+/// hash of the query text. Servers that only accept persisted queries load this
+/// manifest, and clients send the hash instead of the query text.
+///
+/// If persisted_queries.persist is set, the query texts are replaced by their hashes
+/// in the artifacts.
+fn persist_query_texts(
+    artifact_infos: &mut [ArtifactInfo<'_>],
+    persisted_queries: PersistedQueries,
+) -> PersistedQueriesManifestArtifactInfo {
+    let mut query_texts = BTreeMap::new();
+    for artifact_info in artifact_infos.iter_mut() {
+        if let Some(declaration) = artifact_info.query_text_mut() {
+            if let QueryTextDeclaration::Inline(query_text) = declaration {
+                let query_text = query_text.to_graphql();
+                let hash = PersistedQueryHash(content_hash(query_text.as_bytes()));
+                query_texts.insert(hash.clone(), query_text);
+                if persisted_queries.persist {
+                    *declaration = QueryTextDeclaration::Persisted(hash);
+                }
+            }
+        }
+    }
+    PersistedQueriesManifestArtifactInfo { query_texts }
+}
+
+/// Byte-identical query texts (e.g. of refetch queries for the same type and selections,
+/// reached from different entrypoints) are emitted once, in a shared module named after
+/// the hash of their content, and imported by each artifact that uses them.
+fn deduplicate_query_texts(artifact_infos: &mut Vec<ArtifactInfo<'_>>) {
+    let mut occurrences: HashMap<String, usize> = HashMap::new();
+    for artifact_info in artifact_infos.iter_mut() {
+        if let Some(QueryTextDeclaration::Inline(query_text)) = artifact_info.query_text_mut() {
+            *occurrences.entry(query_text.0.clone()).or_default() += 1;
+        }
+    }
+
+    let mut shared_query_texts = HashMap::new();
+    for artifact_info in artifact_infos.iter_mut() {
+        let artifact_directory = artifact_info.relative_directory();
+        if let Some(declaration) = artifact_info.query_text_mut() {
+            if let QueryTextDeclaration::Inline(query_text) = declaration {
+                if occurrences[&query_text.0] > 1 {
+                    let hash = query_text.content_hash();
+                    let import_path = relative_import_path(
+                        &artifact_directory,
+                        &Path::new(SHARED_QUERY_TEXT_DIRECTORY).join(&hash.0),
+                    );
+                    shared_query_texts
+                        .entry(hash)
+                        .or_insert_with(|| QueryText(query_text.0.clone()));
+                    *declaration = QueryTextDeclaration::Shared {
+                        import_path,
+                        query_text: QueryText(query_text.0.clone()),
+                    };
+                }
+            }
+        }
+    }
+
+    let mut shared_query_texts: Vec<_> = shared_query_texts.into_iter().collect();
+    shared_query_texts.sort_by(|(hash_1, _), (hash_2, _)| hash_1.cmp(hash_2));
+    artifact_infos.extend(shared_query_texts.into_iter().map(|(hash, query_text)| {
+        ArtifactInfo::SharedQueryText(SharedQueryTextArtifactInfo { hash, query_text })
+    }));
+}
+
 // N.B. this was originally copied from generate_entrypoint_artifact,
 // and it could use some de-duplication
 fn get_artifact_for_refetch_field(
     schema: &ValidatedSchema,
     refetch_info: RefetchFieldResolverInfo,
+    options: ConfigOptions,
 ) -> RefetchArtifactInfo {
     let RefetchFieldResolverInfo {
-        merged_selection_set,
+        mut merged_selection_set,
         refetch_field_parent_id: parent_id,
         variable_definitions,
         root_fetchable_field,
         root_parent_object,
+        root_artifact_namespace,
         refetch_query_index,
         ..
     } = refetch_info;
 
     let parent_object = schema.schema_data.object(parent_id);
+    apply_normalization_alias_strategy(
+        &mut merged_selection_set,
+        options.normalization_alias_strategy,
+    );
 
     // --------- HACK ---------
     // Merged selection sets do not support type refinements, so for now,
@@ -379,35 +1079,137 @@ fn get_artifact_for_refetch_field(
         schema,
         &merged_selection_set,
         variable_definitions,
+        options.normalization_alias_strategy,
+        options.generate_named_fragments,
     );
 
+    let node_alias = match options.normalization_alias_strategy {
+        NormalizationAliasStrategy::None => "alias: \"node\", ",
+        _ => "",
+    };
     let normalization_ast = NormalizationAst(format!(
-        "[{{ kind: \"Linked\", fieldName: \"node\", \
+        "[{{ kind: \"Linked\", fieldName: \"node\", {node_alias}\
         arguments: [[ \"id\", {{ kind: \"Variable\", name: \"id\" }}]], \
         selections: {} }}]",
-        generate_normalization_ast(schema, &merged_selection_set, 0).0,
+        generate_normalization_ast(
+            schema,
+            &merged_selection_set,
+            0,
+            options.normalization_alias_strategy,
+        )
+        .0,
     ));
+    let mut node_field_json = json!({
+        "kind": "Linked",
+        "fieldName": "node",
+        "arguments": [["id", { "kind": "Variable", "name": "id" }]],
+        "selections": generate_normalization_ast_json(
+            &merged_selection_set,
+            options.normalization_alias_strategy,
+        ),
+    });
+    if options.normalization_alias_strategy == NormalizationAliasStrategy::None {
+        node_field_json["alias"] = json!("node");
+    }
+    let normalization_ast_json = NormalizationAstJson(json!([node_field_json]));
     // ------- END HACK -------
 
     RefetchArtifactInfo {
         normalization_ast,
-        query_text,
+        normalization_ast_json,
+        query_id: query_text.query_id(),
+        query_text: QueryTextDeclaration::Inline(query_text),
         root_fetchable_field,
         root_fetchable_field_parent_object: root_parent_object,
-        refetch_query_index,
+        artifact_namespace: root_artifact_namespace,
+        file_name_prefix: generated_file_name(schema, OperationKind::Query, refetch_query_index),
+        operation_kind: OperationKind::Query,
+        complexity: OperationComplexity::new(schema, parent_id, &merged_selection_set),
+        include_complexity_comment: options.include_complexity_comments,
+    }
+}
+
+/// Generates the refetch query of a client field on a type with an id field, i.e. a
+/// query that selects the merged selection set of the client field with
+/// `node(id: $id) { ... on Type { ... } }`, so that the runtime can refetch the data
+/// of the client field independently of the query that first fetched it. Also
+/// returns the variables of the refetch query, starting with id.
+fn generate_node_refetch_query_artifact(
+    schema: &ValidatedSchema,
+    client_field: &ValidatedClientField,
+    options: ConfigOptions,
+) -> Option<(RefetchArtifactInfo, Vec<VariableName>)> {
+    // __refetch and magic mutation fields have refetch queries of their own.
+    if !matches!(
+        client_field.variant,
+        ClientFieldVariant::Component | ClientFieldVariant::Eager
+    ) {
+        return None;
     }
+    let parent_object = schema.schema_data.object(client_field.parent_object_id);
+    parent_object.id_field?;
+    let (selection_set, _) = client_field.selection_set_and_unwraps.as_ref()?;
+
+    // The merged selection set stored on the client field is relative to the Query
+    // type, so it is merged again relative to the parent object, which e.g. selects
+    // its id.
+    let (merged_selection_set, _) = create_merged_selection_set(
+        schema,
+        parent_object,
+        selection_set,
+        None,
+        None,
+        client_field,
+    );
+
+    let mut variables: Vec<_> = merged_selection_set
+        .reachable_variables()
+        .into_iter()
+        .collect();
+    variables.sort();
+    let variable_definitions = variables
+        .iter()
+        .filter_map(|variable_name| {
+            client_field
+                .variable_definitions
+                .iter()
+                .find(|definition| definition.item.name.item == *variable_name)
+                .cloned()
+        })
+        .collect();
+
+    let mut refetch_query = get_artifact_for_refetch_field(
+        schema,
+        RefetchFieldResolverInfo {
+            merged_selection_set,
+            refetch_field_parent_id: client_field.parent_object_id,
+            variable_definitions,
+            root_parent_object: parent_object.name,
+            root_fetchable_field: client_field.name,
+            root_artifact_namespace: client_field.type_and_field.artifact_namespace,
+            refetch_query_index: 0,
+        },
+        options,
+    );
+    refetch_query.file_name_prefix = NODE_REFETCH_QUERY_FILE_NAME.to_string();
+
+    let mut allowed_variables = vec!["id".intern().into()];
+    allowed_variables.extend(variables);
+    Some((refetch_query, allowed_variables))
 }
 
 fn get_artifact_for_mutation_field<'schema>(
     schema: &'schema ValidatedSchema,
     mutation_info: MutationFieldResolverInfo,
+    options: ConfigOptions,
 ) -> RefetchArtifactInfo {
     let MutationFieldResolverInfo {
-        merged_selection_set,
+        mut merged_selection_set,
         refetch_field_parent_id: parent_id,
         variable_definitions,
         root_fetchable_field,
         root_parent_object,
+        root_artifact_namespace,
         refetch_query_index,
         mutation_field_name,
         mutation_primary_field_name,
@@ -416,29 +1218,35 @@ fn get_artifact_for_mutation_field<'schema>(
         ..
     } = mutation_info;
 
-    let arguments = get_serialized_field_arguments(
-        &mutation_field_arguments
-            .iter()
-            .map(|input_value_definition| {
-                input_value_definition
-                    .clone()
-                    .map(|input_value_definition| SelectionFieldArgument {
-                        name: input_value_definition
-                            .name
-                            .map(|x| x.into())
-                            .hack_to_with_span(),
-                        value: input_value_definition
-                            .name
-                            .map(|x| NonConstantValue::Variable(x.into()))
-                            .hack_to_with_span(),
-                    })
-            })
-            .collect::<Vec<_>>(),
-        1,
-    );
+    let mutation_field_selection_arguments = mutation_field_arguments
+        .iter()
+        .map(|input_value_definition| {
+            input_value_definition
+                .clone()
+                .map(|input_value_definition| SelectionFieldArgument {
+                    name: input_value_definition
+                        .name
+                        .map(|x| x.into())
+                        .hack_to_with_span(),
+                    value: input_value_definition
+                        .name
+                        .map(|x| NonConstantValue::Variable(x.into()))
+                        .hack_to_with_span(),
+                })
+        })
+        .collect::<Vec<_>>();
+    let arguments = get_serialized_field_arguments(&mutation_field_selection_arguments, 1);
 
     let parent_object = schema.schema_data.object(parent_id);
 
+    let space_2 = "  ";
+    let mutation_field_alias = match options.normalization_alias_strategy {
+        NormalizationAliasStrategy::None if !mutation_field_arguments.is_empty() => {
+            format!("{space_2}alias: \"{mutation_field_name}\",\n")
+        }
+        _ => String::new(),
+    };
+
     let query_text = generate_mutation_query_text(
         parent_object,
         schema,
@@ -448,16 +1256,27 @@ fn get_artifact_for_mutation_field<'schema>(
         mutation_primary_field_name,
         mutation_field_arguments,
         requires_refinement,
+        options.normalization_alias_strategy,
+        options.generate_named_fragments,
     );
 
-    let selections = generate_normalization_ast(schema, &merged_selection_set, 2);
-    let space_2 = "  ";
+    apply_normalization_alias_strategy(
+        &mut merged_selection_set,
+        options.normalization_alias_strategy,
+    );
+    let selections = generate_normalization_ast(
+        schema,
+        &merged_selection_set,
+        2,
+        options.normalization_alias_strategy,
+    );
     let space_4 = "    ";
     let space_6 = "      ";
     let normalization_ast = NormalizationAst(format!(
         "[{{\n\
         {space_2}kind: \"Linked\",\n\
         {space_2}fieldName: \"{mutation_field_name}\",\n\
+        {mutation_field_alias}\
         {space_2}arguments: {arguments},\n\
         {space_2}selections: [\n\
         {space_4}{{\n\
@@ -469,13 +1288,39 @@ fn get_artifact_for_mutation_field<'schema>(
         {space_2}],\n\
         }}]",
     ));
+    let mut mutation_field_json = json!({
+        "kind": "Linked",
+        "fieldName": mutation_field_name.to_string(),
+        "arguments": generate_field_arguments_json(&mutation_field_selection_arguments),
+        "selections": [{
+            "kind": "Linked",
+            "fieldName": mutation_primary_field_name.to_string(),
+            "arguments": null,
+            "selections": generate_normalization_ast_json(
+                &merged_selection_set,
+                options.normalization_alias_strategy,
+            ),
+        }],
+    });
+    if options.normalization_alias_strategy == NormalizationAliasStrategy::None
+        && !mutation_field_selection_arguments.is_empty()
+    {
+        mutation_field_json["alias"] = json!(mutation_field_name.to_string());
+    }
+    let normalization_ast_json = NormalizationAstJson(json!([mutation_field_json]));
 
     RefetchArtifactInfo {
         normalization_ast,
-        query_text,
+        normalization_ast_json,
+        query_id: query_text.query_id(),
+        query_text: QueryTextDeclaration::Inline(query_text),
         root_fetchable_field,
         root_fetchable_field_parent_object: root_parent_object,
-        refetch_query_index,
+        artifact_namespace: root_artifact_namespace,
+        file_name_prefix: generated_file_name(schema, OperationKind::Mutation, refetch_query_index),
+        operation_kind: OperationKind::Mutation,
+        complexity: OperationComplexity::new(schema, parent_id, &merged_selection_set),
+        include_complexity_comment: options.include_complexity_comments,
     }
 }
 
@@ -484,6 +1329,8 @@ fn generate_refetchable_query_text<'schema>(
     schema: &'schema ValidatedSchema,
     merged_selection_set: &MergedSelectionSet,
     mut variable_definitions: Vec<WithSpan<ValidatedVariableDefinition>>,
+    normalization_alias_strategy: NormalizationAliasStrategy,
+    generate_named_fragments: bool,
 ) -> QueryText {
     let mut query_text = String::new();
 
@@ -501,12 +1348,29 @@ fn generate_refetchable_query_text<'schema>(
     });
     let variable_text = write_variables_to_string(schema, variable_definitions.iter());
 
+    let node_alias = match normalization_alias_strategy {
+        NormalizationAliasStrategy::None => "",
+        _ => "node____id___id: ",
+    };
+    let operation_name = format!("{}_refetch", parent_object_type.name);
+    let named_fragments = NamedFragments::new(
+        &operation_name,
+        merged_selection_set,
+        generate_named_fragments,
+    );
     query_text.push_str(&format!(
-        "query {}_refetch {} {{ node____id___id: node(id: $id) {{ ... on {} {{ \\\n",
-        parent_object_type.name, variable_text, parent_object_type.name,
+        "query {} {} {{ {}node(id: $id) {{ ... on {} {{ \\\n",
+        operation_name, variable_text, node_alias, parent_object_type.name,
     ));
-    write_selections_for_query_text(&mut query_text, schema, &merged_selection_set, 1);
+    write_selections_for_query_text(
+        &mut query_text,
+        schema,
+        &merged_selection_set,
+        1,
+        &named_fragments,
+    );
     query_text.push_str("}}}");
+    named_fragments.write_fragment_definitions(&mut query_text, schema);
     QueryText(query_text)
 }
 
@@ -519,6 +1383,8 @@ fn generate_mutation_query_text<'schema>(
     mutation_primary_field_name: SelectableFieldName,
     mutation_field_arguments: Vec<WithLocation<GraphQLInputValueDefinition>>,
     requires_refinement: RequiresRefinement,
+    normalization_alias_strategy: NormalizationAliasStrategy,
+    generate_named_fragments: bool,
 ) -> QueryText {
     let mut query_text = String::new();
 
@@ -554,25 +1420,49 @@ fn generate_mutation_query_text<'schema>(
     let variable_text = write_variables_to_string(schema, &mut variable_definitions.iter());
     let mutation_field_arguments = get_serialized_arguments_for_query_text(&mutation_parameters);
 
-    let aliased_mutation_field_name =
-        get_aliased_mutation_field_name(mutation_field_name, &mutation_parameters);
+    let mutation_field_alias = match normalization_alias_strategy {
+        NormalizationAliasStrategy::None => String::new(),
+        _ => format!(
+            "{}: ",
+            get_aliased_mutation_field_name(mutation_field_name, &mutation_parameters)
+        ),
+    };
 
     let parent_object_name = parent_object_type.name;
+    let operation_name = format!("{parent_object_name}{mutation_field_name}");
+    let named_fragments = NamedFragments::new(
+        &operation_name,
+        merged_selection_set,
+        generate_named_fragments,
+    );
     query_text.push_str(&format!(
-        "mutation {parent_object_name}{mutation_field_name} {variable_text} {{\\\n\
-        {aliased_mutation_field_name}: {mutation_field_name}{mutation_field_arguments} {{\\\n\
+        "mutation {operation_name} {variable_text} {{\\\n\
+        {mutation_field_alias}{mutation_field_name}{mutation_field_arguments} {{\\\n\
         {mutation_primary_field_name} {{ \\\n",
     ));
 
     if let RequiresRefinement::Yes(refine_to) = requires_refinement {
         query_text.push_str(&format!("... on {} {{\\\n", refine_to));
-        write_selections_for_query_text(&mut query_text, schema, &merged_selection_set, 1);
+        write_selections_for_query_text(
+            &mut query_text,
+            schema,
+            &merged_selection_set,
+            1,
+            &named_fragments,
+        );
         query_text.push_str("}\\\n");
     } else {
-        write_selections_for_query_text(&mut query_text, schema, &merged_selection_set, 1);
+        write_selections_for_query_text(
+            &mut query_text,
+            schema,
+            &merged_selection_set,
+            1,
+            &named_fragments,
+        );
     }
 
     query_text.push_str("}}}");
+    named_fragments.write_fragment_definitions(&mut query_text, schema);
     QueryText(query_text)
 }
 
@@ -596,45 +1486,77 @@ fn generate_entrypoint_artifact<'schema>(
     client_field_id: ClientFieldId,
     artifact_queue: &mut Vec<ArtifactQueueItem>,
     encountered_cliend_field_ids: &mut HashSet<ClientFieldId>,
+    options: ConfigOptions,
 ) -> EntrypointArtifactInfo<'schema> {
     let top_level_client_field = schema.resolver(client_field_id);
-    if let Some((ref selection_set, _)) = top_level_client_field.selection_set_and_unwraps {
+    if let Some(merged) = &top_level_client_field.merged_selection_set {
         let query_name = top_level_client_field.name.into();
 
-        let (merged_selection_set, root_refetched_paths) = create_merged_selection_set(
-            schema,
-            // TODO here we are assuming that the client field is only on the Query type.
-            // That restriction should be loosened.
-            schema
-                .schema_data
-                .object(schema.query_type_id.expect("expect query type to exist"))
-                .into(),
-            selection_set,
-            Some(artifact_queue),
-            Some(encountered_cliend_field_ids),
-            &top_level_client_field,
+        artifact_queue.extend(merged.artifact_queue.iter().cloned());
+        encountered_cliend_field_ids.extend(merged.encountered_client_field_ids.iter().copied());
+        let root_refetched_paths = &merged.root_refetched_paths;
+
+        // The selections were merged and sorted when validating the schema. Here, we
+        // only rewrite the normalization aliases, which affects how they are printed.
+        let mut merged_selection_set = merged.selection_set.clone();
+        apply_normalization_alias_strategy(
+            &mut merged_selection_set,
+            options.normalization_alias_strategy,
         );
 
-        let query_object = schema
-            .query_object()
-            .expect("Expected query object to exist");
+        // Entrypoints can only be declared on root types, e.g. Query or Mutation
+        let root_object = schema
+            .schema_data
+            .object(top_level_client_field.parent_object_id);
+        let operation_kind = schema.root_operation_kind(root_object.id).expect(
+            "Expected entrypoint to be on a root type. This is indicative of a bug in Isograph.",
+        );
         let query_text = generate_query_text(
             query_name,
+            operation_kind,
             schema,
             &merged_selection_set,
             &top_level_client_field.variable_definitions,
+            options.generate_named_fragments,
         );
         let refetch_query_artifact_imports =
-            generate_refetch_query_artifact_imports(&root_refetched_paths);
+            generate_refetch_query_artifact_imports(schema, root_refetched_paths);
+        let nested_refetch_queries = generate_nested_refetch_queries(schema, root_refetched_paths);
 
-        let normalization_ast = generate_normalization_ast(schema, &merged_selection_set, 0);
+        let normalization_ast = generate_normalization_ast(
+            schema,
+            &merged_selection_set,
+            0,
+            options.normalization_alias_strategy,
+        );
+        let normalization_ast_json = NormalizationAstJson(generate_normalization_ast_json(
+            &merged_selection_set,
+            options.normalization_alias_strategy,
+        ));
+
+        let complexity = OperationComplexity::new(schema, root_object.id, &merged_selection_set);
+
+        let variables_type_declarations = generate_variables_type_declarations(
+            schema,
+            &variables_type_name(root_object.name, query_name),
+            &top_level_client_field.variable_definitions,
+            options.enum_representation,
+        );
 
         EntrypointArtifactInfo {
-            query_text,
+            query_id: query_text.query_id(),
+            query_text: QueryTextDeclaration::Inline(query_text),
             query_name,
-            parent_type: query_object.into(),
+            parent_type: root_object.into(),
+            artifact_namespace: top_level_client_field.type_and_field.artifact_namespace,
             normalization_ast,
+            normalization_ast_json,
             refetch_query_artifact_import: refetch_query_artifact_imports,
+            nested_refetch_queries,
+            operation_kind,
+            complexity,
+            include_complexity_comment: options.include_complexity_comments,
+            variables_type_declarations,
         }
     } else {
         // TODO convert to error
@@ -642,37 +1564,208 @@ fn generate_entrypoint_artifact<'schema>(
     }
 }
 
+/// The name of the TypeScript type of the variables of an entrypoint, e.g.
+/// `Query__HomePage__variables`.
+pub(crate) fn variables_type_name(
+    parent_type_name: IsographObjectTypeName,
+    query_name: QueryOperationName,
+) -> String {
+    format!("{}__{}__variables", parent_type_name, query_name)
+}
+
+/// Declares the TypeScript type of the variables of an entrypoint, followed by the
+/// types of the input objects to which it (transitively) refers, e.g.
+/// `Query__HomePage__variables__UserFilter`. Nullable variables and input object
+/// fields are optional.
+fn generate_variables_type_declarations(
+    schema: &ValidatedSchema,
+    variables_type_name: &str,
+    variable_definitions: &[WithSpan<ValidatedVariableDefinition>],
+    enum_representation: EnumRepresentation,
+) -> String {
+    let mut input_object_ids = vec![];
+    let variables_type = print_input_fields_type(
+        schema,
+        variable_definitions.iter().map(|variable_definition| {
+            (
+                variable_definition.item.name.item.lookup(),
+                &variable_definition.item.type_,
+            )
+        }),
+        variables_type_name,
+        enum_representation,
+        &mut input_object_ids,
+    );
+    let mut declarations = format!("export type {variables_type_name} = {variables_type};\n");
+
+    // Input objects can refer to themselves (e.g. `and: [UserFilter!]`), so each is
+    // declared once, in the order in which they are encountered.
+    let mut index = 0;
+    while let Some(input_object_id) = input_object_ids.get(index).copied() {
+        index += 1;
+        let input_object = schema.schema_data.object(input_object_id);
+        let fields_type = print_input_fields_type(
+            schema,
+            input_object
+                .server_fields
+                .iter()
+                .map(|server_field_id| schema.field(*server_field_id))
+                .filter(|server_field| server_field.name.item != "__typename".intern().into())
+                .map(|server_field| {
+                    (
+                        server_field.name.item.lookup(),
+                        &server_field.associated_data,
+                    )
+                }),
+            variables_type_name,
+            enum_representation,
+            &mut input_object_ids,
+        );
+        declarations.push_str(&format!(
+            "export type {variables_type_name}__{} = {fields_type};\n",
+            input_object.name
+        ));
+    }
+    declarations
+}
+
+fn print_input_fields_type<'a>(
+    schema: &ValidatedSchema,
+    fields: impl Iterator<Item = (&'a str, &'a TypeAnnotation<SelectableFieldId>)>,
+    variables_type_name: &str,
+    enum_representation: EnumRepresentation,
+    input_object_ids: &mut Vec<ObjectId>,
+) -> String {
+    let mut fields_type = String::new();
+    for (field_name, type_) in fields {
+        let optional = match type_ {
+            TypeAnnotation::NonNull(_) => "",
+            _ => "?",
+        };
+        let type_ = print_input_type(
+            schema,
+            type_,
+            variables_type_name,
+            enum_representation,
+            input_object_ids,
+        );
+        fields_type.push_str(&format!("  readonly {field_name}{optional}: {type_},\n"));
+    }
+    if fields_type.is_empty() {
+        "{}".to_string()
+    } else {
+        format!("{{\n{fields_type}}}")
+    }
+}
+
+fn print_input_type(
+    schema: &ValidatedSchema,
+    type_: &TypeAnnotation<SelectableFieldId>,
+    variables_type_name: &str,
+    enum_representation: EnumRepresentation,
+    input_object_ids: &mut Vec<ObjectId>,
+) -> String {
+    match type_ {
+        TypeAnnotation::Named(named) => format!(
+            "({} | null)",
+            print_input_named_type(
+                schema,
+                named.item,
+                variables_type_name,
+                enum_representation,
+                input_object_ids
+            )
+        ),
+        TypeAnnotation::List(list) => format!(
+            "({}[] | null)",
+            print_input_type(
+                schema,
+                &list.0,
+                variables_type_name,
+                enum_representation,
+                input_object_ids
+            )
+        ),
+        TypeAnnotation::NonNull(non_null) => match non_null.as_ref() {
+            NonNullTypeAnnotation::Named(named) => print_input_named_type(
+                schema,
+                named.item,
+                variables_type_name,
+                enum_representation,
+                input_object_ids,
+            ),
+            NonNullTypeAnnotation::List(list) => format!(
+                "{}[]",
+                print_input_type(
+                    schema,
+                    &list.0,
+                    variables_type_name,
+                    enum_representation,
+                    input_object_ids
+                )
+            ),
+        },
+    }
+}
+
+/// Input objects are referred to by the names of their declarations, see
+/// generate_variables_type_declarations.
+fn print_input_named_type(
+    schema: &ValidatedSchema,
+    selectable_field_id: SelectableFieldId,
+    variables_type_name: &str,
+    enum_representation: EnumRepresentation,
+    input_object_ids: &mut Vec<ObjectId>,
+) -> String {
+    match selectable_field_id {
+        SelectableFieldId::Scalar(scalar_id) => {
+            get_scalar_javascript_type(schema.schema_data.scalar(scalar_id), enum_representation)
+        }
+        SelectableFieldId::Object(object_id) => {
+            if !input_object_ids.contains(&object_id) {
+                input_object_ids.push(object_id);
+            }
+            format!(
+                "{variables_type_name}__{}",
+                schema.schema_data.object(object_id).name
+            )
+        }
+    }
+}
+
 fn generate_reader_artifact<'schema>(
     schema: &'schema ValidatedSchema,
     client_field: &ValidatedClientField,
     project_root: &PathBuf,
     artifact_directory: &PathBuf,
+    options: ConfigOptions,
 ) -> ReaderArtifactInfo<'schema> {
     if let Some((selection_set, _)) = &client_field.selection_set_and_unwraps {
         let parent_type = schema.schema_data.object(client_field.parent_object_id);
-        let mut nested_client_field_artifact_imports = HashMap::new();
-
-        let (_merged_selection_set, root_refetched_paths) = create_merged_selection_set(
-            schema,
-            // TODO here we are assuming that the client field is only on the Query type.
-            // That restriction should be loosened.
-            schema
-                .schema_data
-                .object(schema.query_type_id.expect("expect query type to exist"))
-                .into(),
-            selection_set,
-            None,
-            None,
-            client_field,
-        );
+        let mut nested_client_field_artifact_imports = BTreeMap::new();
+
+        let root_refetched_paths = &client_field
+            .merged_selection_set
+            .as_ref()
+            .expect(
+                "Expected client field with a selection set to have a merged selection set. \
+                This is indicative of a bug in Isograph.",
+            )
+            .root_refetched_paths;
 
         let reader_ast = generate_reader_ast(
             schema,
             selection_set,
             0,
             &mut nested_client_field_artifact_imports,
-            &root_refetched_paths,
+            root_refetched_paths,
         );
+        let reader_ast_json = ReaderAstJson(generate_reader_ast_json(
+            schema,
+            selection_set,
+            root_refetched_paths,
+            &mut vec![],
+        ));
 
         let client_field_parameter_type = generate_client_field_parameter_type(
             schema,
@@ -681,17 +1774,36 @@ fn generate_reader_artifact<'schema>(
             parent_type.into(),
             &mut nested_client_field_artifact_imports,
             0,
+            options,
         );
         let client_field_output_type = generate_output_type(client_field);
         let function_import_statement = generate_function_import_statement(
             &client_field.action_kind,
             project_root,
             artifact_directory,
+            &generate_path(
+                parent_type.name,
+                client_field.name,
+                client_field.type_and_field.artifact_namespace,
+            ),
         );
+        let read_data_assertion = if options.generate_dev_assertions {
+            Some(generate_read_data_assertion(
+                &client_field.type_and_field,
+                selection_set,
+            ))
+        } else {
+            None
+        };
         ReaderArtifactInfo {
             parent_type: parent_type.into(),
             client_field_name: client_field.name,
+            artifact_namespace: client_field.type_and_field.artifact_namespace,
+            client_field_description: client_field.description,
             reader_ast,
+            reader_ast_json,
+            read_data_assertion,
+            refetch_query_allowed_variables: None,
             nested_client_field_artifact_imports,
             function_import_statement,
             client_field_output_type,
@@ -703,6 +1815,30 @@ fn generate_reader_artifact<'schema>(
     }
 }
 
+fn generate_data_requirements_artifact(
+    schema: &ValidatedSchema,
+    client_field: &ValidatedClientField,
+) -> Option<DataRequirementsArtifactInfo> {
+    let merged = client_field.merged_selection_set.as_ref()?;
+    Some(DataRequirementsArtifactInfo {
+        parent_type_name: schema
+            .schema_data
+            .object(client_field.parent_object_id)
+            .name,
+        client_field_name: client_field.name,
+        artifact_namespace: client_field.type_and_field.artifact_namespace,
+        data_requirements: data_requirements(
+            schema,
+            client_field.parent_object_id,
+            &merged.selection_set,
+        ),
+    })
+}
+
+pub(crate) fn get_complexity_comment(complexity: OperationComplexity) -> String {
+    format!("// Complexity: {complexity}\n")
+}
+
 /// A data structure that contains enough information to infallibly
 /// generate the contents of the generated file (e.g. of the entrypoint
 /// artifact), as well as the path to the generated file.
@@ -711,14 +1847,86 @@ pub(crate) enum ArtifactInfo<'schema> {
     Entrypoint(EntrypointArtifactInfo<'schema>),
     Reader(ReaderArtifactInfo<'schema>),
     RefetchQuery(RefetchArtifactInfo),
+    SharedQueryText(SharedQueryTextArtifactInfo),
+    DataRequirements(DataRequirementsArtifactInfo),
+    PersistedQueriesManifest(PersistedQueriesManifestArtifactInfo),
 }
 
 impl<'schema> ArtifactInfo<'schema> {
-    pub fn to_path_and_content(self) -> PathAndContent {
+    pub fn to_path_and_content(self, artifact_format: ArtifactFormat) -> PathAndContent {
+        match self {
+            ArtifactInfo::Entrypoint(entrypoint_artifact) => {
+                entrypoint_artifact.path_and_content(artifact_format)
+            }
+            ArtifactInfo::Reader(reader_artifact) => {
+                reader_artifact.path_and_content(artifact_format)
+            }
+            ArtifactInfo::RefetchQuery(refetch_query) => {
+                refetch_query.path_and_content(artifact_format)
+            }
+            ArtifactInfo::SharedQueryText(shared_query_text) => {
+                shared_query_text.path_and_content()
+            }
+            ArtifactInfo::DataRequirements(data_requirements) => {
+                data_requirements.path_and_content()
+            }
+            ArtifactInfo::PersistedQueriesManifest(persisted_queries_manifest) => {
+                persisted_queries_manifest.path_and_content()
+            }
+        }
+    }
+
+    /// The directory (within the artifact directory) that contains the artifact.
+    fn relative_directory(&self) -> PathBuf {
+        match self {
+            ArtifactInfo::Entrypoint(entrypoint_artifact) => {
+                entrypoint_artifact.relative_directory()
+            }
+            ArtifactInfo::Reader(reader_artifact) => reader_artifact.relative_directory(),
+            ArtifactInfo::RefetchQuery(refetch_query) => refetch_query.relative_directory(),
+            ArtifactInfo::SharedQueryText(_) => PathBuf::from(SHARED_QUERY_TEXT_DIRECTORY),
+            ArtifactInfo::DataRequirements(data_requirements) => {
+                data_requirements.relative_directory()
+            }
+            ArtifactInfo::PersistedQueriesManifest(_) => PathBuf::new(),
+        }
+    }
+
+    fn query_text_mut(&mut self) -> Option<&mut QueryTextDeclaration> {
+        match self {
+            ArtifactInfo::Entrypoint(entrypoint_artifact) => {
+                Some(&mut entrypoint_artifact.query_text)
+            }
+            ArtifactInfo::RefetchQuery(refetch_query) => Some(&mut refetch_query.query_text),
+            ArtifactInfo::Reader(_)
+            | ArtifactInfo::SharedQueryText(_)
+            | ArtifactInfo::DataRequirements(_)
+            | ArtifactInfo::PersistedQueriesManifest(_) => None,
+        }
+    }
+
+    fn operation_complexity(&self) -> Option<OperationComplexityReport> {
         match self {
-            ArtifactInfo::Entrypoint(entrypoint_artifact) => entrypoint_artifact.path_and_content(),
-            ArtifactInfo::Reader(reader_artifact) => reader_artifact.path_and_content(),
-            ArtifactInfo::RefetchQuery(refetch_query) => refetch_query.path_and_content(),
+            ArtifactInfo::Entrypoint(entrypoint_artifact) => Some(OperationComplexityReport {
+                operation_name: format!(
+                    "{}.{}",
+                    entrypoint_artifact.parent_type.name, entrypoint_artifact.query_name
+                ),
+                complexity: entrypoint_artifact.complexity,
+            }),
+            ArtifactInfo::RefetchQuery(refetch_query) => Some(OperationComplexityReport {
+                operation_name: format!(
+                    "{}.{}/{}",
+                    refetch_query.root_fetchable_field_parent_object,
+                    refetch_query.root_fetchable_field,
+                    refetch_query.file_name_prefix
+                ),
+                complexity: refetch_query.complexity,
+            }),
+            ArtifactInfo::Reader(_)
+            | ArtifactInfo::SharedQueryText(_)
+            | ArtifactInfo::DataRequirements(_)
+            | ArtifactInfo::PersistedQueriesManifest(_) => None,
         }
     }
 }
@@ -731,6 +1939,162 @@ derive_display!(ClientFieldParameterType);
 pub(crate) struct QueryText(pub String);
 derive_display!(QueryText);
 
+impl QueryText {
+    /// Removes the characters that GraphQL ignores (whitespace, commas and the line
+    /// continuations we emit), keeping a single space only where two adjacent names,
+    /// numbers or keywords would otherwise run together. Strings and block strings
+    /// are kept as they are.
+    fn strip_ignored_characters(&mut self) {
+        let text = self.0.as_str();
+        let mut stripped = String::with_capacity(text.len());
+        let mut chars = text.char_indices().peekable();
+        let mut separator_pending = false;
+
+        while let Some((index, c)) = chars.next() {
+            match c {
+                ' ' | '\t' | '\n' | '\r' | ',' => separator_pending = true,
+                '\\' if chars.peek().map(|(_, next)| *next) == Some('\n') => {
+                    chars.next();
+                    separator_pending = true;
+                }
+                _ => {
+                    if separator_pending
+                        && is_name_character(c)
+                        && stripped
+                            .chars()
+                            .next_back()
+                            .map_or(false, is_name_character)
+                    {
+                        stripped.push(' ');
+                    }
+                    separator_pending = false;
+                    if c == '"' {
+                        let string_end = index + string_length(&text[index..]);
+                        stripped.push_str(&text[index..string_end]);
+                        while chars.next_if(|(index, _)| *index < string_end).is_some() {}
+                    } else {
+                        stripped.push(c);
+                    }
+                }
+            }
+        }
+
+        self.0 = stripped;
+    }
+}
+
+/// The length (in bytes, including the quotes) of the string or block string at the
+/// start of text, which starts with a quote. An unterminated string extends to the
+/// end of text.
+fn string_length(text: &str) -> usize {
+    if let Some(block_string) = text.strip_prefix("\"\"\"") {
+        // Block strings end at the first triple quote that is not escaped as \"""
+        let mut search_start = 0;
+        while let Some(offset) = block_string[search_start..].find("\"\"\"") {
+            let quotes_start = search_start + offset;
+            if !block_string[..quotes_start].ends_with('\\') {
+                return 3 + quotes_start + 3;
+            }
+            search_start = quotes_start + 3;
+        }
+        return text.len();
+    }
+
+    let mut is_escaped = false;
+    for (index, c) in text.char_indices().skip(1) {
+        if is_escaped {
+            is_escaped = false;
+        } else if c == '\\' {
+            is_escaped = true;
+        } else if c == '"' {
+            return index + 1;
+        }
+    }
+    text.len()
+}
+
+/// The hex-encoded SHA-256 hash of the bytes. This is the only hash used by the
+/// compiler (for query ids, persisted queries, shared query text modules, hashed
+/// aliases and input fingerprints). Unlike the std hasher, it is guaranteed to be
+/// stable across compilations and Rust versions.
+pub(crate) fn content_hash(bytes: &[u8]) -> String {
+    format!("{:x}", Sha256::digest(bytes))
+}
+
+fn is_name_character(c: char) -> bool {
+    c.is_ascii_alphanumeric() || c == '_'
+}
+
+impl QueryText {
+    fn content_hash(&self) -> QueryTextHash {
+        QueryTextHash(content_hash(self.0.as_bytes()))
+    }
+
+    /// A stable identifier of the operation, e.g. for logging, caching or server
+    /// allow-lists. This is the content hash of the query text with the characters
+    /// that GraphQL ignores stripped, so it does not depend on whether query texts
+    /// are minified.
+    fn query_id(&self) -> QueryTextHash {
+        let mut canonical_query_text = QueryText(self.0.clone());
+        canonical_query_text.strip_ignored_characters();
+        canonical_query_text.content_hash()
+    }
+
+    /// The query text is the contents of a JavaScript string literal, in which
+    /// lines are continued with a trailing backslash.
+    pub(crate) fn to_graphql(&self) -> String {
+        self.0.replace("\\\n", "\n")
+    }
+}
+
+/// The hex-encoded SHA-256 hash of a query text, under which it is persisted.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub(crate) struct PersistedQueryHash(pub String);
+derive_display!(PersistedQueryHash);
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub(crate) struct QueryTextHash(pub String);
+derive_display!(QueryTextHash);
+
+/// How an artifact gets its query text.
+#[derive(Debug)]
+pub(crate) enum QueryTextDeclaration {
+    /// The query text is declared in the artifact itself
+    Inline(QueryText),
+    /// The query text is identical to that of other artifacts, and is imported
+    /// from the shared module with this module specifier (relative to the artifact).
+    /// The query text is kept for artifact formats that cannot import it.
+    Shared {
+        import_path: String,
+        query_text: QueryText,
+    },
+    /// The query text is persisted, and the artifact contains only its hash
+    Persisted(PersistedQueryHash),
+}
+
+impl QueryTextDeclaration {
+    pub(crate) fn persisted_query_hash(&self) -> Option<&PersistedQueryHash> {
+        match self {
+            QueryTextDeclaration::Persisted(hash) => Some(hash),
+            QueryTextDeclaration::Inline(_) | QueryTextDeclaration::Shared { .. } => None,
+        }
+    }
+}
+
+impl fmt::Display for QueryTextDeclaration {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            QueryTextDeclaration::Inline(query_text) => {
+                write!(f, "const queryText = '{query_text}';")
+            }
+            QueryTextDeclaration::Shared { import_path, .. } => {
+                write!(f, "import queryText from '{import_path}';")
+            }
+            QueryTextDeclaration::Persisted(_) => write!(f, "const queryText = null;"),
+        }
+    }
+}
+
 #[derive(Debug)]
 pub(crate) struct ClientFieldFunctionImportStatement(pub String);
 derive_display!(ClientFieldFunctionImportStatement);
@@ -747,6 +2111,18 @@ derive_display!(ReaderAst);
 pub(crate) struct NormalizationAst(pub String);
 derive_display!(NormalizationAst);
 
+/// The reader AST as JSON, for JSON artifacts
+#[derive(Debug)]
+pub(crate) struct ReaderAstJson(pub Value);
+
+/// The normalization AST as JSON, for JSON artifacts
+#[derive(Debug)]
+pub(crate) struct NormalizationAstJson(pub Value);
+
+#[derive(Debug)]
+pub(crate) struct ReadDataAssertion(pub String);
+derive_display!(ReadDataAssertion);
+
 #[derive(Debug)]
 pub(crate) struct ConvertFunction(pub String);
 derive_display!(ConvertFunction);
@@ -755,29 +2131,49 @@ derive_display!(ConvertFunction);
 pub(crate) struct RefetchQueryArtifactImport(pub String);
 derive_display!(RefetchQueryArtifactImport);
 
+#[derive(Debug)]
+pub(crate) struct NestedRefetchQueries(pub Value);
+
 #[derive(Debug)]
 pub(crate) struct EntrypointArtifactInfo<'schema> {
     pub(crate) query_name: QueryOperationName,
     pub parent_type: &'schema ValidatedSchemaObject,
-    pub query_text: QueryText,
+    pub artifact_namespace: Option<ArtifactNamespace>,
+    pub query_id: QueryTextHash,
+    pub query_text: QueryTextDeclaration,
     pub normalization_ast: NormalizationAst,
+    pub normalization_ast_json: NormalizationAstJson,
     pub refetch_query_artifact_import: RefetchQueryArtifactImport,
+    pub nested_refetch_queries: NestedRefetchQueries,
+    pub operation_kind: OperationKind,
+    pub complexity: OperationComplexity,
+    pub include_complexity_comment: bool,
+    /// The declarations of the type of the variables of the entrypoint (and of the
+    /// input objects to which it refers)
+    pub variables_type_declarations: String,
 }
 
 impl<'schema> EntrypointArtifactInfo<'schema> {
-    pub fn path_and_content(self) -> PathAndContent {
-        let EntrypointArtifactInfo {
-            query_name,
-            parent_type,
-            ..
-        } = &self;
+    fn relative_directory(&self) -> PathBuf {
+        generate_path(
+            self.parent_type.name,
+            self.query_name.into(),
+            self.artifact_namespace,
+        )
+    }
 
-        let directory = generate_path(parent_type.name, (*query_name).into());
+    pub fn path_and_content(self, artifact_format: ArtifactFormat) -> PathAndContent {
+        let directory = self.relative_directory();
 
+        let (file_content, file_extension) = match artifact_format {
+            ArtifactFormat::TypeScript => (self.file_contents(), TS_FILE_EXTENSION),
+            ArtifactFormat::Json => (self.json_file_contents(), JSON_FILE_EXTENSION),
+        };
         PathAndContent {
             relative_directory: directory,
-            file_content: self.file_contents(),
+            file_content,
             file_name_prefix: *ENTRYPOINT,
+            file_extension,
         }
     }
 }
@@ -786,28 +2182,108 @@ impl<'schema> EntrypointArtifactInfo<'schema> {
 pub(crate) struct ReaderArtifactInfo<'schema> {
     pub parent_type: &'schema ValidatedSchemaObject,
     pub(crate) client_field_name: SelectableFieldName,
+    pub artifact_namespace: Option<ArtifactNamespace>,
+    pub client_field_description: Option<DescriptionValue>,
     pub nested_client_field_artifact_imports: NestedClientFieldImports,
     pub client_field_output_type: ClientFieldOutputType,
     pub reader_ast: ReaderAst,
+    pub reader_ast_json: ReaderAstJson,
+    pub read_data_assertion: Option<ReadDataAssertion>,
+    /// The variables of the refetch query of the client field (the first of which
+    /// is id), if one is generated
+    pub refetch_query_allowed_variables: Option<Vec<VariableName>>,
     pub client_field_parameter_type: ClientFieldParameterType,
     pub function_import_statement: ClientFieldFunctionImportStatement,
     pub client_field_variant: ClientFieldVariant,
 }
 
 impl<'schema> ReaderArtifactInfo<'schema> {
-    pub fn path_and_content(self) -> PathAndContent {
-        let ReaderArtifactInfo {
-            parent_type,
-            client_field_name,
-            ..
-        } = &self;
+    fn relative_directory(&self) -> PathBuf {
+        generate_path(
+            self.parent_type.name,
+            self.client_field_name,
+            self.artifact_namespace,
+        )
+    }
 
-        let relative_directory = generate_path(parent_type.name, *client_field_name);
+    pub fn path_and_content(self, artifact_format: ArtifactFormat) -> PathAndContent {
+        let relative_directory = self.relative_directory();
 
+        let (file_content, file_extension) = match artifact_format {
+            ArtifactFormat::TypeScript => (self.file_contents(), TS_FILE_EXTENSION),
+            ArtifactFormat::Json => (self.json_file_contents(), JSON_FILE_EXTENSION),
+        };
         PathAndContent {
-            file_content: self.file_contents(),
+            file_content,
             relative_directory,
             file_name_prefix: *READER,
+            file_extension,
+        }
+    }
+}
+
+#[derive(Debug)]
+pub(crate) struct SharedQueryTextArtifactInfo {
+    pub hash: QueryTextHash,
+    pub query_text: QueryText,
+}
+
+impl SharedQueryTextArtifactInfo {
+    pub fn path_and_content(self) -> PathAndContent {
+        PathAndContent {
+            relative_directory: PathBuf::from(SHARED_QUERY_TEXT_DIRECTORY),
+            file_name_prefix: self.hash.0.intern().into(),
+            file_extension: TS_FILE_EXTENSION,
+            file_content: self.file_contents(),
+        }
+    }
+}
+
+/// The query texts of all artifacts, keyed by their PersistedQueryHash, which is
+/// written as JSON regardless of the artifact format, as it is meant to be loaded
+/// by servers.
+#[derive(Debug)]
+pub(crate) struct PersistedQueriesManifestArtifactInfo {
+    pub query_texts: BTreeMap<PersistedQueryHash, String>,
+}
+
+impl PersistedQueriesManifestArtifactInfo {
+    pub fn path_and_content(self) -> PathAndContent {
+        PathAndContent {
+            relative_directory: PathBuf::new(),
+            file_name_prefix: *PERSISTED_QUERIES,
+            file_extension: JSON_FILE_EXTENSION,
+            file_content: self.json_file_contents(),
+        }
+    }
+}
+
+/// The server fields that a client field requires, directly or transitively, which
+/// are written as JSON regardless of the artifact format, as they are meant to be
+/// read by tools (or people), not imported.
+#[derive(Debug)]
+pub(crate) struct DataRequirementsArtifactInfo {
+    pub parent_type_name: IsographObjectTypeName,
+    pub client_field_name: SelectableFieldName,
+    pub artifact_namespace: Option<ArtifactNamespace>,
+    pub data_requirements: Vec<DataRequirement>,
+}
+
+impl DataRequirementsArtifactInfo {
+    fn relative_directory(&self) -> PathBuf {
+        generate_path(
+            self.parent_type_name,
+            self.client_field_name,
+            self.artifact_namespace,
+        )
+    }
+
+    pub fn path_and_content(self) -> PathAndContent {
+        PathAndContent {
+            relative_directory: self.relative_directory(),
+            file_name_prefix: *DATA_REQUIREMENTS,
+            file_extension: JSON_FILE_EXTENSION,
+            file_content: self.json_file_contents(),
         }
     }
 }
@@ -815,65 +2291,314 @@ impl<'schema> ReaderArtifactInfo<'schema> {
 #[derive(Debug)]
 pub(crate) struct RefetchArtifactInfo {
     pub normalization_ast: NormalizationAst,
-    pub query_text: QueryText,
+    pub normalization_ast_json: NormalizationAstJson,
+    pub query_id: QueryTextHash,
+    pub query_text: QueryTextDeclaration,
     pub root_fetchable_field: SelectableFieldName,
     pub root_fetchable_field_parent_object: IsographObjectTypeName,
-    // TODO wrap in a newtype
-    pub refetch_query_index: usize,
+    pub artifact_namespace: Option<ArtifactNamespace>,
+    pub file_name_prefix: String,
+    pub operation_kind: OperationKind,
+    pub complexity: OperationComplexity,
+    pub include_complexity_comment: bool,
 }
 
 impl RefetchArtifactInfo {
-    pub fn path_and_content(self) -> PathAndContent {
-        let RefetchArtifactInfo {
-            root_fetchable_field,
-            root_fetchable_field_parent_object,
-            refetch_query_index,
-            ..
-        } = &self;
+    fn relative_directory(&self) -> PathBuf {
+        generate_path(
+            self.root_fetchable_field_parent_object,
+            self.root_fetchable_field,
+            self.artifact_namespace,
+        )
+    }
 
-        let relative_directory =
-            generate_path(*root_fetchable_field_parent_object, *root_fetchable_field);
-        let file_name_prefix = format!("__refetch__{}", refetch_query_index)
-            .intern()
-            .into();
+    pub fn path_and_content(self, artifact_format: ArtifactFormat) -> PathAndContent {
+        let relative_directory = self.relative_directory();
+        let file_name_prefix = self.file_name_prefix.intern().into();
 
+        let (file_content, file_extension) = match artifact_format {
+            ArtifactFormat::TypeScript => (self.file_contents(), TS_FILE_EXTENSION),
+            ArtifactFormat::Json => (self.json_file_contents(), JSON_FILE_EXTENSION),
+        };
         PathAndContent {
-            file_content: self.file_contents(),
+            file_content,
             relative_directory,
             file_name_prefix,
+            file_extension,
         }
     }
 }
 
 fn generate_query_text(
     query_name: QueryOperationName,
+    operation_kind: OperationKind,
     schema: &ValidatedSchema,
     merged_selection_set: &MergedSelectionSet,
     query_variables: &[WithSpan<ValidatedVariableDefinition>],
+    generate_named_fragments: bool,
 ) -> QueryText {
     let mut query_text = String::new();
 
     let variable_text = write_variables_to_string(schema, query_variables.iter());
+    let named_fragments = NamedFragments::new(
+        &query_name.to_string(),
+        merged_selection_set,
+        generate_named_fragments,
+    );
 
-    query_text.push_str(&format!("query {} {} {{\\\n", query_name, variable_text));
-    write_selections_for_query_text(&mut query_text, schema, &merged_selection_set, 1);
+    query_text.push_str(&format!(
+        "{} {} {} {{\\\n",
+        operation_kind.keyword(),
+        query_name,
+        variable_text
+    ));
+    write_selections_for_query_text(
+        &mut query_text,
+        schema,
+        &merged_selection_set,
+        1,
+        &named_fragments,
+    );
     query_text.push_str("}");
+    named_fragments.write_fragment_definitions(&mut query_text, schema);
     QueryText(query_text)
 }
 
+/// Linked fields whose (merged) selection sets are repeated within an operation are
+/// printed as a spread of a named fragment, instead of being inlined each time.
+#[derive(Default)]
+struct NamedFragments<'a> {
+    /// Keyed by the structural hash of the linked fields that are spread.
+    fragments: HashMap<String, NamedFragment<'a>>,
+    /// The structural hash of every linked field of the operation that could be
+    /// spread. These are computed in a single bottom-up pass, so that finding the
+    /// fragment of a linked field does not re-visit (or re-print) its subtree.
+    linked_field_hashes: HashMap<*const MergedLinkedFieldSelection, String>,
+}
+
+struct NamedFragment<'a> {
+    name: String,
+    concrete_type: IsographObjectTypeName,
+    selection_set: &'a [WithSpan<MergedServerFieldSelection>],
+}
+
+impl<'a> NamedFragments<'a> {
+    /// Returns no fragments if generate_named_fragments is false, so that
+    /// every selection is inlined.
+    fn new(
+        operation_name: &str,
+        merged_selection_set: &'a [WithSpan<MergedServerFieldSelection>],
+        generate_named_fragments: bool,
+    ) -> Self {
+        if !generate_named_fragments {
+            return NamedFragments::default();
+        }
+
+        let mut linked_field_hashes = HashMap::new();
+        let mut subtrees = HashMap::new();
+        structural_hash(
+            merged_selection_set,
+            &mut linked_field_hashes,
+            &mut subtrees,
+        );
+
+        let mut repeated_subtrees: Vec<_> = subtrees
+            .into_iter()
+            .filter(|(_, subtree)| subtree.count > 1)
+            .collect();
+        // Sort, so that fragment names are stable across compilations
+        repeated_subtrees.sort_by(|(hash_1, subtree_1), (hash_2, subtree_2)| {
+            subtree_1
+                .concrete_type
+                .lookup()
+                .cmp(subtree_2.concrete_type.lookup())
+                .then_with(|| hash_1.cmp(hash_2))
+        });
+
+        let fragments = repeated_subtrees
+            .into_iter()
+            .enumerate()
+            .map(|(index, (hash, subtree))| {
+                (
+                    hash,
+                    NamedFragment {
+                        name: format!("{}__{}__{}", operation_name, subtree.concrete_type, index),
+                        concrete_type: subtree.concrete_type,
+                        selection_set: subtree.selection_set,
+                    },
+                )
+            })
+            .collect();
+
+        NamedFragments {
+            fragments,
+            linked_field_hashes,
+        }
+    }
+
+    fn spread_for(&self, linked_field: &MergedLinkedFieldSelection) -> Option<&str> {
+        self.linked_field_hashes
+            .get(&(linked_field as *const MergedLinkedFieldSelection))
+            .and_then(|hash| self.fragments.get(hash))
+            .map(|fragment| fragment.name.as_str())
+    }
+
+    fn write_fragment_definitions(&self, query_text: &mut String, schema: &ValidatedSchema) {
+        let mut fragments: Vec<_> = self.fragments.values().collect();
+        fragments.sort_by(|fragment_1, fragment_2| fragment_1.name.cmp(&fragment_2.name));
+
+        for fragment in fragments {
+            query_text.push_str(&format!(
+                "\\\nfragment {} on {} {{\\\n",
+                fragment.name, fragment.concrete_type
+            ));
+            write_selections_for_query_text(query_text, schema, fragment.selection_set, 1, self);
+            query_text.push_str("}");
+        }
+    }
+}
+
+/// A linked field subtree that could be spread, and how often it occurs.
+struct Subtree<'a> {
+    count: usize,
+    concrete_type: IsographObjectTypeName,
+    selection_set: &'a [WithSpan<MergedServerFieldSelection>],
+}
+
+/// Returns a hash of selection_set that is equal for structurally equal selection
+/// sets, i.e. selection sets that are printed identically. The hash of each
+/// selection combines the hashes of its children, so every selection is visited
+/// once. Along the way, records the hash of (and counts) every linked field whose
+/// selection set could be spread.
+fn structural_hash<'a>(
+    selection_set: &'a [WithSpan<MergedServerFieldSelection>],
+    linked_field_hashes: &mut HashMap<*const MergedLinkedFieldSelection, String>,
+    subtrees: &mut HashMap<String, Subtree<'a>>,
+) -> String {
+    let mut printed_selections = String::new();
+    for item in selection_set.iter() {
+        match &item.item {
+            MergedServerFieldSelection::ScalarField(scalar_field) => {
+                if let Some(alias) = scalar_field.normalization_alias {
+                    printed_selections.push_str(&format!("{}: ", alias.item));
+                }
+                printed_selections.push_str(&format!(
+                    "{}{}{},",
+                    scalar_field.name.item,
+                    get_serialized_arguments_for_query_text(&scalar_field.arguments),
+                    scalar_field
+                        .stream
+                        .as_ref()
+                        .map(get_serialized_stream_directive_for_query_text)
+                        .unwrap_or_default()
+                ));
+            }
+            MergedServerFieldSelection::LinkedField(linked_field) => {
+                let selection_set_hash =
+                    structural_hash(&linked_field.selection_set, linked_field_hashes, subtrees);
+                let subtree_hash = content_hash(
+                    format!("{}{{{}}}", linked_field.concrete_type, selection_set_hash).as_bytes(),
+                );
+
+                // Spreading a fragment containing a single field does not make
+                // the query text any shorter.
+                if linked_field.selection_set.len() > 1 {
+                    linked_field_hashes.insert(linked_field, subtree_hash.clone());
+                    subtrees
+                        .entry(subtree_hash.clone())
+                        .or_insert_with(|| Subtree {
+                            count: 0,
+                            concrete_type: linked_field.concrete_type,
+                            selection_set: &linked_field.selection_set,
+                        })
+                        .count += 1;
+                }
+
+                if let Some(alias) = linked_field.normalization_alias {
+                    printed_selections.push_str(&format!("{}: ", alias.item));
+                }
+                printed_selections.push_str(&format!(
+                    "{}{}{} {{{}}},",
+                    linked_field.name.item,
+                    get_serialized_arguments_for_query_text(&linked_field.arguments),
+                    linked_field
+                        .stream
+                        .as_ref()
+                        .map(get_serialized_stream_directive_for_query_text)
+                        .unwrap_or_default(),
+                    subtree_hash
+                ));
+            }
+            MergedServerFieldSelection::InlineFragment(inline_fragment) => {
+                let selection_set_hash = structural_hash(
+                    &inline_fragment.selection_set,
+                    linked_field_hashes,
+                    subtrees,
+                );
+                printed_selections.push_str(&format!(
+                    "... on {} {{{}}},",
+                    inline_fragment.type_to_refine_to, selection_set_hash
+                ));
+            }
+            MergedServerFieldSelection::DeferredFragment(deferred_fragment) => {
+                let selection_set_hash = structural_hash(
+                    &deferred_fragment.selection_set,
+                    linked_field_hashes,
+                    subtrees,
+                );
+                printed_selections.push_str(&format!(
+                    "... @defer(label: \"{}\"{}) {{{}}},",
+                    deferred_fragment.label,
+                    get_serialized_if_argument_for_query_text(&deferred_fragment.if_condition),
+                    selection_set_hash
+                ));
+            }
+        }
+    }
+    content_hash(printed_selections.as_bytes())
+}
+
+/// The name of the artifact (without extension) for the refetch query or mutation
+/// with the given index. If the schema has mutation or subscription root types,
+/// the operation kind is part of the name, so that artifacts for different kinds
+/// of operations can be told apart at a glance.
+fn generated_file_name(
+    schema: &ValidatedSchema,
+    operation_kind: OperationKind,
+    refetch_query_index: usize,
+) -> String {
+    if schema.has_non_query_root_types() {
+        format!(
+            "__refetch__{}__{}",
+            operation_kind.keyword(),
+            refetch_query_index
+        )
+    } else {
+        format!("__refetch__{}", refetch_query_index)
+    }
+}
+
 fn generate_refetch_query_artifact_imports(
+    schema: &ValidatedSchema,
     root_refetched_paths: &[RootRefetchedPath],
 ) -> RefetchQueryArtifactImport {
     // TODO name the refetch queries with the path, or something, instead of
     // with indexes.
     let mut output = String::new();
     let mut array_syntax = String::new();
-    for (query_index, RootRefetchedPath { variables, .. }) in
-        root_refetched_paths.iter().enumerate()
+    for (
+        query_index,
+        RootRefetchedPath {
+            variables,
+            operation_kind,
+            ..
+        },
+    ) in root_refetched_paths.iter().enumerate()
     {
         output.push_str(&format!(
-            "import refetchQuery{} from './__refetch__{}';\n",
-            query_index, query_index,
+            "import refetchQuery{} from './{}';\n",
+            query_index,
+            generated_file_name(schema, *operation_kind, query_index),
         ));
         let variable_names_str = variable_names_to_string(&variables);
         array_syntax.push_str(&format!(
@@ -888,7 +2613,44 @@ fn generate_refetch_query_artifact_imports(
     RefetchQueryArtifactImport(output)
 }
 
-fn variable_names_to_string(variable_names: &[VariableName]) -> String {
+/// Like generate_refetch_query_artifact_imports, but refers to the refetch query
+/// artifacts by file name instead of importing them, for artifact formats that
+/// cannot import other artifacts.
+fn generate_nested_refetch_queries(
+    schema: &ValidatedSchema,
+    root_refetched_paths: &[RootRefetchedPath],
+) -> NestedRefetchQueries {
+    NestedRefetchQueries(
+        root_refetched_paths
+            .iter()
+            .enumerate()
+            .map(
+                |(
+                    query_index,
+                    RootRefetchedPath {
+                        variables,
+                        operation_kind,
+                        ..
+                    },
+                )| {
+                    json!({
+                        "artifact": generated_file_name(schema, *operation_kind, query_index),
+                        "allowedVariables": variable_names_to_json(variables),
+                    })
+                },
+            )
+            .collect(),
+    )
+}
+
+pub(crate) fn variable_names_to_json(variable_names: &[VariableName]) -> Value {
+    variable_names
+        .iter()
+        .map(|variable_name| variable_name.to_string())
+        .collect()
+}
+
+pub(crate) fn variable_names_to_string(variable_names: &[VariableName]) -> String {
     let mut s = "[".to_string();
 
     for variable in variable_names {
@@ -900,7 +2662,7 @@ fn variable_names_to_string(variable_names: &[VariableName]) -> String {
     s
 }
 
-fn write_variables_to_string<'a>(
+pub(crate) fn write_variables_to_string<'a>(
     schema: &ValidatedSchema,
     mut variables: impl Iterator<Item = &'a WithSpan<ValidatedVariableDefinition>> + 'a,
 ) -> String {
@@ -945,6 +2707,53 @@ pub enum GenerateArtifactsError {
 
     #[error("Unable to delete directory at path {path:?}.\nReason: {message:?}")]
     UnableToDeleteDirectory { path: PathBuf, message: io::Error },
+
+    #[error("Unable to delete stale artifact file at path {path:?}.\nReason: {message:?}")]
+    UnableToDeleteArtifactFile { path: PathBuf, message: io::Error },
+
+    #[error("Unable to read directory at path {path:?}.\nReason: {message:?}")]
+    UnableToReadDirectory { path: PathBuf, message: io::Error },
+
+    #[error(
+        "The operation {operation_name} exceeds the complexity budget. \
+        Its {metric} is {value}, but at most {limit} is allowed."
+    )]
+    ComplexityBudgetExceeded {
+        operation_name: String,
+        metric: &'static str,
+        value: usize,
+        limit: usize,
+    },
+
+    #[error(
+        "The client field `{type_name}.{field_name}` is declared in `{path}`, but that \
+        file does not exist, so its artifacts would import from a missing file. \
+        Was the file renamed or deleted?\n\
+        `{type_name}.{field_name}` is declared here:\n\
+        {location}"
+    )]
+    ClientFieldFileDoesNotExist {
+        type_name: IsographObjectTypeName,
+        field_name: SelectableFieldName,
+        path: String,
+        location: Location,
+    },
+
+    #[error(
+        "The entrypoint `{type_name}.{field_name}` is generated without aliases, but it \
+        selects `{selection_1}` and `{selection_2}` on the same record, which requires \
+        aliases. Select the field with the same arguments, or enable aliases (remove \
+        @noAliases, or change the normalization_alias_strategy option).\n\
+        `{type_name}.{field_name}` is declared here:\n\
+        {location}"
+    )]
+    ConflictingSelectionsWithoutAliases {
+        type_name: IsographObjectTypeName,
+        field_name: SelectableFieldName,
+        selection_1: String,
+        selection_2: String,
+        location: Location,
+    },
 }
 
 fn write_selections_for_query_text(
@@ -952,6 +2761,7 @@ fn write_selections_for_query_text(
     schema: &ValidatedSchema,
     items: &[WithSpan<MergedServerFieldSelection>],
     indentation_level: u8,
+    named_fragments: &NamedFragments,
 ) {
     for item in items.iter() {
         match &item.item {
@@ -962,7 +2772,12 @@ fn write_selections_for_query_text(
                 }
                 let name = scalar_field.name.item;
                 let arguments = get_serialized_arguments_for_query_text(&scalar_field.arguments);
-                query_text.push_str(&format!("{}{},\\\n", name, arguments));
+                let stream = scalar_field
+                    .stream
+                    .as_ref()
+                    .map(get_serialized_stream_directive_for_query_text)
+                    .unwrap_or_default();
+                query_text.push_str(&format!("{}{}{},\\\n", name, arguments, stream));
             }
             MergedServerFieldSelection::LinkedField(linked_field) => {
                 query_text.push_str(&format!("{}", "  ".repeat(indentation_level as usize)));
@@ -972,12 +2787,62 @@ fn write_selections_for_query_text(
                 }
                 let name = linked_field.name.item;
                 let arguments = get_serialized_arguments_for_query_text(&linked_field.arguments);
-                query_text.push_str(&format!("{}{} {{\\\n", name, arguments));
+                let stream = linked_field
+                    .stream
+                    .as_ref()
+                    .map(get_serialized_stream_directive_for_query_text)
+                    .unwrap_or_default();
+                query_text.push_str(&format!("{}{}{} {{\\\n", name, arguments, stream));
+                match named_fragments.spread_for(linked_field) {
+                    Some(fragment_name) => query_text.push_str(&format!(
+                        "{}...{},\\\n",
+                        "  ".repeat((indentation_level + 1) as usize),
+                        fragment_name
+                    )),
+                    None => write_selections_for_query_text(
+                        query_text,
+                        schema,
+                        &linked_field.selection_set,
+                        indentation_level + 1,
+                        named_fragments,
+                    ),
+                }
+                query_text.push_str(&format!(
+                    "{}}},\\\n",
+                    "  ".repeat(indentation_level as usize)
+                ));
+            }
+            MergedServerFieldSelection::InlineFragment(inline_fragment) => {
+                query_text.push_str(&format!(
+                    "{}... on {} {{\\\n",
+                    "  ".repeat(indentation_level as usize),
+                    inline_fragment.type_to_refine_to
+                ));
                 write_selections_for_query_text(
                     query_text,
                     schema,
-                    &linked_field.selection_set,
+                    &inline_fragment.selection_set,
+                    indentation_level + 1,
+                    named_fragments,
+                );
+                query_text.push_str(&format!(
+                    "{}}},\\\n",
+                    "  ".repeat(indentation_level as usize)
+                ));
+            }
+            MergedServerFieldSelection::DeferredFragment(deferred_fragment) => {
+                query_text.push_str(&format!(
+                    "{}... @defer(label: \"{}\"{}) {{\\\n",
+                    "  ".repeat(indentation_level as usize),
+                    deferred_fragment.label,
+                    get_serialized_if_argument_for_query_text(&deferred_fragment.if_condition)
+                ));
+                write_selections_for_query_text(
+                    query_text,
+                    schema,
+                    &deferred_fragment.selection_set,
                     indentation_level + 1,
+                    named_fragments,
                 );
                 query_text.push_str(&format!(
                     "{}}},\\\n",
@@ -988,6 +2853,29 @@ fn write_selections_for_query_text(
     }
 }
 
+pub(crate) fn get_serialized_stream_directive_for_query_text(
+    stream: &MergedStreamDirective,
+) -> String {
+    format!(
+        " @stream(label: \"{}\", initialCount: {}{})",
+        stream.label,
+        serialize_non_constant_value_for_graphql(&stream.initial_count),
+        get_serialized_if_argument_for_query_text(&stream.if_condition)
+    )
+}
+
+pub(crate) fn get_serialized_if_argument_for_query_text(
+    if_condition: &Option<NonConstantValue>,
+) -> String {
+    match if_condition {
+        Some(if_condition) => format!(
+            ", if: {}",
+            serialize_non_constant_value_for_graphql(if_condition)
+        ),
+        None => String::new(),
+    }
+}
+
 fn generate_client_field_parameter_type(
     schema: &ValidatedSchema,
     selection_set: &[WithSpan<ValidatedSelection>],
@@ -995,10 +2883,25 @@ fn generate_client_field_parameter_type(
     parent_type: &ValidatedSchemaObject,
     nested_client_field_imports: &mut NestedClientFieldImports,
     indentation_level: u8,
+    options: ConfigOptions,
 ) -> ClientFieldParameterType {
-    // TODO use unwraps
     let mut client_field_parameter_type = "{\n".to_string();
-    for selection in selection_set.iter() {
+    let mut selections = selection_set
+        .iter()
+        .filter(|selection| !selection.item.is_statically_excluded())
+        .collect::<Vec<_>>();
+    match options.field_ordering {
+        FieldOrdering::Selection => {}
+        FieldOrdering::Schema => selections.sort_by_key(|selection| match &selection.item {
+            Selection::ServerField(server_field) => {
+                schema_order_position(parent_type, server_field.name())
+            }
+        }),
+        FieldOrdering::Alphabetical => selections.sort_by_key(|selection| match &selection.item {
+            Selection::ServerField(server_field) => server_field.name_or_alias().item.lookup(),
+        }),
+    }
+    for selection in selections {
         write_query_types_from_selection(
             schema,
             &mut client_field_parameter_type,
@@ -1011,6 +2914,7 @@ fn generate_client_field_parameter_type(
             parent_type,
             nested_client_field_imports,
             indentation_level + 1,
+            options,
         );
     }
     client_field_parameter_type.push_str(&format!("{}}}", "  ".repeat(indentation_level as usize)));
@@ -1026,6 +2930,87 @@ fn generate_client_field_parameter_type(
     ClientFieldParameterType(client_field_parameter_type)
 }
 
+/// The position of a field in its parent type, which is the order in which server
+/// fields are defined in the schema, followed by the order in which client fields
+/// are processed.
+fn schema_order_position(
+    parent_type: &ValidatedSchemaObject,
+    field_name: SelectableFieldName,
+) -> usize {
+    match parent_type.encountered_fields.get(&field_name) {
+        Some(FieldDefinitionLocation::Server(server_field_id)) => parent_type
+            .server_fields
+            .iter()
+            .position(|id| id == server_field_id),
+        Some(FieldDefinitionLocation::Client(client_field_id)) => parent_type
+            .resolvers
+            .iter()
+            .position(|id| id == client_field_id)
+            .map(|position| parent_type.server_fields.len() + position),
+        None => None,
+    }
+    .unwrap_or(usize::MAX)
+}
+
+/// A JSDoc comment containing the description, so that editors show it when hovering
+/// over usages. The comment is followed by indentation, so that it can be inserted
+/// wherever the commented item would be.
+pub(crate) fn get_jsdoc_comment(
+    description: Option<DescriptionValue>,
+    indentation_level: u8,
+) -> String {
+    let description = match description {
+        Some(description) => description,
+        None => return String::new(),
+    };
+    let indent = "  ".repeat(indentation_level as usize);
+    // Descriptions cannot end the comment early
+    let description = description.lookup().replace("*/", "*\\/");
+    let mut comment = "/**\n".to_string();
+    for line in description.lines() {
+        if line.is_empty() {
+            comment.push_str(&format!("{indent} *\n"));
+        } else {
+            comment.push_str(&format!("{indent} * {line}\n"));
+        }
+    }
+    comment.push_str(&format!("{indent} */\n{indent}"));
+    comment
+}
+
+/// Like get_jsdoc_comment, but the comment also marks the item as deprecated if there
+/// is a deprecation reason, so that editors show usages with a strikethrough.
+fn get_jsdoc_comment_with_deprecation(
+    description: Option<DescriptionValue>,
+    deprecation_reason: Option<DescriptionValue>,
+    indentation_level: u8,
+) -> String {
+    let deprecation_reason = match deprecation_reason {
+        Some(deprecation_reason) => deprecation_reason,
+        None => return get_jsdoc_comment(description, indentation_level),
+    };
+    if let Some(description) = description {
+        return get_jsdoc_comment(
+            Some(
+                format!("{description}\n@deprecated {deprecation_reason}")
+                    .intern()
+                    .into(),
+            ),
+            indentation_level,
+        );
+    }
+    let indent = "  ".repeat(indentation_level as usize);
+    let deprecation_reason = deprecation_reason
+        .lookup()
+        .replace("*/", "*\\/")
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty())
+        .collect::<Vec<_>>()
+        .join(" ");
+    format!("/** @deprecated {deprecation_reason} */\n{indent}")
+}
+
 fn write_query_types_from_selection(
     schema: &ValidatedSchema,
     query_type_declaration: &mut String,
@@ -1034,6 +3019,7 @@ fn write_query_types_from_selection(
     parent_type: &ValidatedSchemaObject,
     nested_client_field_imports: &mut NestedClientFieldImports,
     indentation_level: u8,
+    options: ConfigOptions,
 ) {
     query_type_declaration.push_str(&format!("{}", "  ".repeat(indentation_level as usize)));
 
@@ -1050,30 +3036,89 @@ fn write_query_types_from_selection(
                             .expect("parent_field should exist and be server field");
                         let field = schema.field(*parent_field);
                         let name_or_alias = scalar_field.name_or_alias().item;
+                        query_type_declaration.push_str(&get_jsdoc_comment_with_deprecation(
+                            scalar_field.description,
+                            field.deprecation_reason,
+                            indentation_level,
+                        ));
+
+                        if scalar_field.name.item == "__typename".intern().into() {
+                            query_type_declaration.push_str(&format!(
+                                "{}: {},\n",
+                                name_or_alias,
+                                typename_type(schema, parent_type)
+                            ));
+                            return;
+                        }
 
                         // TODO there should be a clever way to print without cloning
                         let output_type = field.associated_data.clone().map(|output_type_id| {
-                            // TODO not just scalars, enums as well. Both should have a javascript name
                             let scalar_id =
                                 if let SelectableFieldId::Scalar(scalar) = output_type_id {
                                     scalar
                                 } else {
                                     panic!("output_type_id should be a scalar");
                                 };
-                            schema.schema_data.scalar(scalar_id).javascript_name
+                            get_scalar_javascript_type(
+                                schema.schema_data.scalar(scalar_id),
+                                options.enum_representation,
+                            )
                         });
                         query_type_declaration.push_str(&format!(
                             "{}: {},\n",
                             name_or_alias,
-                            print_type_annotation(&output_type)
+                            print_server_field_type_annotation(
+                                &output_type,
+                                &scalar_field.unwraps,
+                                options,
+                            )
                         ));
                     }
                     FieldDefinitionLocation::Client(client_field_id) => {
                         let client_field = schema.resolver(client_field_id);
+                        // The description of the client field is followed by the
+                        // comments on the selection, if any.
+                        let description = match (client_field.description, scalar_field.description)
+                        {
+                            (Some(description), Some(selection_description)) => Some(
+                                format!("{description}\n\n{selection_description}")
+                                    .intern()
+                                    .into(),
+                            ),
+                            (description, selection_description) => {
+                                description.or(selection_description)
+                            }
+                        };
+                        query_type_declaration
+                            .push_str(&get_jsdoc_comment(description, indentation_level));
+
+                        if let ClientFieldActionKind::Identity = client_field.action_kind {
+                            // Inlined client fields have no output type to import. Instead,
+                            // they evaluate to the data they read.
+                            let (selection_set, _) = client_field
+                                .selection_set_and_unwraps
+                                .as_ref()
+                                .expect("Client fields should have a selection set");
+                            let inner = generate_client_field_parameter_type(
+                                schema,
+                                selection_set,
+                                &ClientFieldVariant::Eager,
+                                parent_type,
+                                nested_client_field_imports,
+                                indentation_level,
+                                options,
+                            );
+                            query_type_declaration.push_str(&format!(
+                                "{}: {},\n",
+                                scalar_field.name_or_alias().item,
+                                inner
+                            ));
+                            return;
+                        }
 
                         match nested_client_field_imports.entry(client_field.type_and_field) {
                             Entry::Occupied(mut occupied) => {
-                                occupied.get_mut().types.push(ResolverImportType {
+                                occupied.get_mut().types.insert(ResolverImportType {
                                     globally_unique_type_name: ResolverImportName(format!(
                                         "{}__outputType",
                                         client_field.type_and_field.underscore_separated()
@@ -1083,20 +3128,29 @@ fn write_query_types_from_selection(
                             Entry::Vacant(vacant) => {
                                 vacant.insert(JavaScriptImports {
                                     default_import: false,
-                                    types: vec![ResolverImportType {
+                                    types: BTreeSet::from([ResolverImportType {
                                         globally_unique_type_name: ResolverImportName(format!(
                                             "{}__outputType",
                                             client_field.type_and_field.underscore_separated()
                                         )),
-                                    }],
+                                    }]),
                                 });
                             }
                         }
 
+                        let output_type = format!(
+                            "{}__outputType",
+                            client_field.type_and_field.underscore_separated()
+                        );
+                        let (is_unwrapped, _) = split_first_unwrap(&scalar_field.unwraps);
                         query_type_declaration.push_str(&format!(
-                            "{}: {}__outputType,\n",
+                            "{}: {},\n",
                             scalar_field.name_or_alias().item,
-                            client_field.type_and_field.underscore_separated()
+                            if is_unwrapped {
+                                format!("NonNullable<{output_type}>")
+                            } else {
+                                output_type
+                            }
                         ));
                     }
                 }
@@ -1110,6 +3164,11 @@ fn write_query_types_from_selection(
                     .expect("Parent field should exist and be server field");
                 let field = schema.field(*parent_field);
                 let name_or_alias = linked_field.name_or_alias().item;
+                query_type_declaration.push_str(&get_jsdoc_comment_with_deprecation(
+                    linked_field.description,
+                    field.deprecation_reason,
+                    indentation_level,
+                ));
                 let type_annotation = field.associated_data.clone().map(|output_type_id| {
                     // TODO Or interface or union type
                     let object_id = if let SelectableFieldId::Object(object) = output_type_id {
@@ -1125,88 +3184,365 @@ fn write_query_types_from_selection(
                         object.into(),
                         nested_client_field_imports,
                         indentation_level,
+                        options,
                     );
                     inner
                 });
                 query_type_declaration.push_str(&format!(
                     "{}: {},\n",
                     name_or_alias,
-                    print_type_annotation(&type_annotation),
+                    print_server_field_type_annotation(
+                        &type_annotation,
+                        &linked_field.unwraps,
+                        options,
+                    ),
+                ));
+            }
+        },
+    }
+}
+
+/// The type of the __typename field of parent_type. Objects are concrete, so it is the
+/// string literal type of the object's name. For interfaces and unions, it is the
+/// union of the string literal types of the names of the concrete types that
+/// implement them (or are members of them), sorted by name.
+fn typename_type(schema: &ValidatedSchema, parent_type: &ValidatedSchemaObject) -> String {
+    if parent_type.valid_refinements.is_empty() {
+        return format!("\"{}\"", parent_type.name);
+    }
+    let mut concrete_type_names = parent_type
+        .valid_refinements
+        .iter()
+        .map(|refinement| schema.schema_data.object(refinement.target).name.lookup())
+        .collect::<Vec<_>>();
+    concrete_type_names.sort();
+    concrete_type_names.dedup();
+    concrete_type_names
+        .iter()
+        .map(|concrete_type_name| format!("\"{concrete_type_name}\""))
+        .collect::<Vec<_>>()
+        .join(" | ")
+}
+
+/// Enums are represented according to the enum_representation option. Const objects
+/// and TypeScript enums are declared in the enums artifact, and referred to with
+/// import types, because the readers that use them are two levels deep in the
+/// artifact directory.
+fn get_scalar_javascript_type(
+    scalar: &SchemaScalar,
+    enum_representation: EnumRepresentation,
+) -> String {
+    let enum_values = match &scalar.enum_values {
+        Some(enum_values) => enum_values,
+        None => return scalar.javascript_name.to_string(),
+    };
+    match enum_representation {
+        EnumRepresentation::StringLiteralUnion => {
+            if enum_values.is_empty() {
+                return "never".to_string();
+            }
+            enum_values
+                .iter()
+                .map(|enum_value| format!("\"{}\"", enum_value.value))
+                .collect::<Vec<_>>()
+                .join(" | ")
+        }
+        EnumRepresentation::ConstObject | EnumRepresentation::TypeScriptEnum => {
+            format!(
+                "import('../../{}').{}",
+                *ENUMS,
+                enum_identifier(scalar.name.item.lookup())
+            )
+        }
+    }
+}
+
+/// Words that cannot be the names of const objects, types or TypeScript enums,
+/// i.e. reserved words (including in strict mode) and the names of built-in types.
+static RESERVED_TYPESCRIPT_WORDS: &[&str] = &[
+    "any",
+    "arguments",
+    "await",
+    "bigint",
+    "boolean",
+    "break",
+    "case",
+    "catch",
+    "class",
+    "const",
+    "continue",
+    "debugger",
+    "default",
+    "delete",
+    "do",
+    "else",
+    "enum",
+    "eval",
+    "export",
+    "extends",
+    "false",
+    "finally",
+    "for",
+    "function",
+    "if",
+    "implements",
+    "import",
+    "in",
+    "instanceof",
+    "interface",
+    "let",
+    "never",
+    "new",
+    "null",
+    "number",
+    "object",
+    "package",
+    "private",
+    "protected",
+    "public",
+    "return",
+    "static",
+    "string",
+    "super",
+    "switch",
+    "symbol",
+    "this",
+    "throw",
+    "true",
+    "try",
+    "typeof",
+    "undefined",
+    "unknown",
+    "var",
+    "void",
+    "while",
+    "with",
+    "yield",
+];
+
+/// The identifier with which an enum is declared in, and imported from, the enums
+/// artifact. This is the name of the enum, with a trailing underscore if the name
+/// is a reserved word, so that the artifact always compiles.
+fn enum_identifier(enum_name: &str) -> String {
+    if RESERVED_TYPESCRIPT_WORDS.contains(&enum_name) {
+        format!("{enum_name}_")
+    } else {
+        enum_name.to_string()
+    }
+}
+
+/// Declares every enum in the schema as a const object or a TypeScript enum.
+/// Enums that are represented as unions of string literals need no declarations.
+fn build_enums_artifact(
+    schema: &ValidatedSchema,
+    enum_representation: EnumRepresentation,
+) -> Option<PathAndContent> {
+    if enum_representation == EnumRepresentation::StringLiteralUnion {
+        return None;
+    }
+
+    let mut enums = schema
+        .schema_data
+        .scalars
+        .iter()
+        .filter_map(|scalar| {
+            scalar
+                .enum_values
+                .as_ref()
+                .map(|enum_values| (scalar.name.item, enum_values))
+        })
+        .collect::<Vec<_>>();
+    enums.sort_by(|(name_1, _), (name_2, _)| name_1.lookup().cmp(name_2.lookup()));
+
+    let mut content = String::new();
+    for (enum_name, enum_values) in enums {
+        let enum_name = enum_identifier(enum_name.lookup());
+        match enum_representation {
+            EnumRepresentation::ConstObject => {
+                content.push_str(&format!("export const {enum_name} = {{\n"));
+                for enum_value in enum_values {
+                    content.push_str("  ");
+                    content.push_str(&get_jsdoc_comment_with_deprecation(
+                        None,
+                        enum_value.deprecation_reason,
+                        1,
+                    ));
+                    content.push_str(&format!("{0}: \"{0}\",\n", enum_value.value));
+                }
+                content.push_str(&format!(
+                    "}} as const;\n\
+                    export type {enum_name} = (typeof {enum_name})[keyof typeof {enum_name}];\n\n"
                 ));
             }
+            EnumRepresentation::TypeScriptEnum => {
+                content.push_str(&format!("export enum {enum_name} {{\n"));
+                for enum_value in enum_values {
+                    content.push_str("  ");
+                    content.push_str(&get_jsdoc_comment_with_deprecation(
+                        None,
+                        enum_value.deprecation_reason,
+                        1,
+                    ));
+                    content.push_str(&format!("{0} = \"{0}\",\n", enum_value.value));
+                }
+                content.push_str("}\n\n");
+            }
+            EnumRepresentation::StringLiteralUnion => {}
+        }
+    }
+
+    Some(PathAndContent {
+        file_content: content,
+        relative_directory: PathBuf::new(),
+        file_name_prefix: *ENUMS,
+        file_extension: TS_FILE_EXTENSION,
+    })
+}
+
+/// unwraps are the unwraps of the selection (e.g. `field!`). The first unwrap applies
+/// to the field itself, the second to the items of the list it contains, and so on.
+/// A field that is unwrapped with `!` is typed as non-null, even if it is nullable
+/// in the schema.
+fn print_server_field_type_annotation<T: Display>(
+    type_annotation: &TypeAnnotation<T>,
+    unwraps: &[WithSpan<Unwrap>],
+    options: ConfigOptions,
+) -> String {
+    if options.treat_server_fields_as_nullable {
+        print_nullable_type_annotation(type_annotation, unwraps)
+    } else {
+        print_type_annotation(type_annotation, unwraps)
+    }
+}
+
+/// Splits off the unwrap that applies to the outermost type, and returns whether it
+/// is an actual unwrap (i.e. `!`) and the unwraps of the list items.
+fn split_first_unwrap(unwraps: &[WithSpan<Unwrap>]) -> (bool, &[WithSpan<Unwrap>]) {
+    match unwraps.split_first() {
+        Some((unwrap, rest)) => (unwrap.item == Unwrap::ActualUnwrap, rest),
+        None => (false, &[]),
+    }
+}
+
+/// Prints the type annotation as if neither it, nor any of the list items it contains,
+/// were non-null, and as if any of them could be undefined, for servers that do not
+/// respect the nullability declared in their schema. Unwrapped types are the exception,
+/// as the unwrap asserts that they are present.
+fn print_nullable_type_annotation<T: Display>(
+    type_annotation: &TypeAnnotation<T>,
+    unwraps: &[WithSpan<Unwrap>],
+) -> String {
+    let (is_unwrapped, item_unwraps) = split_first_unwrap(unwraps);
+    let inner = match type_annotation {
+        TypeAnnotation::Named(named) => named.item.to_string(),
+        TypeAnnotation::List(list) => {
+            format!(
+                "{}[]",
+                print_nullable_type_annotation(&list.0, item_unwraps)
+            )
+        }
+        TypeAnnotation::NonNull(non_null) => match non_null.as_ref() {
+            NonNullTypeAnnotation::Named(named) => named.item.to_string(),
+            NonNullTypeAnnotation::List(list) => {
+                format!(
+                    "{}[]",
+                    print_nullable_type_annotation(&list.0, item_unwraps)
+                )
+            }
         },
+    };
+    if is_unwrapped {
+        inner
+    } else {
+        format!("({inner} | null | undefined)")
     }
 }
 
-fn print_type_annotation<T: Display>(type_annotation: &TypeAnnotation<T>) -> String {
+fn print_type_annotation<T: Display>(
+    type_annotation: &TypeAnnotation<T>,
+    unwraps: &[WithSpan<Unwrap>],
+) -> String {
     let mut s = String::new();
-    print_type_annotation_impl(type_annotation, &mut s);
+    print_type_annotation_impl(type_annotation, unwraps, &mut s);
     s
 }
 
-fn print_type_annotation_impl<T: Display>(type_annotation: &TypeAnnotation<T>, s: &mut String) {
+fn print_type_annotation_impl<T: Display>(
+    type_annotation: &TypeAnnotation<T>,
+    unwraps: &[WithSpan<Unwrap>],
+    s: &mut String,
+) {
+    let (is_unwrapped, item_unwraps) = split_first_unwrap(unwraps);
     match &type_annotation {
         TypeAnnotation::Named(named) => {
-            s.push_str("(");
-            s.push_str(&named.item.to_string());
-            s.push_str(" | null)");
+            if is_unwrapped {
+                s.push_str(&named.item.to_string());
+            } else {
+                s.push_str("(");
+                s.push_str(&named.item.to_string());
+                s.push_str(" | null)");
+            }
         }
         TypeAnnotation::List(list) => {
-            print_list_type_annotation(list, s);
+            print_list_type_annotation(list, item_unwraps, s);
         }
         TypeAnnotation::NonNull(non_null) => {
-            print_non_null_type_annotation(non_null, s);
+            print_non_null_type_annotation(non_null, item_unwraps, s);
         }
     }
 }
 
-fn print_list_type_annotation<T: Display>(list: &ListTypeAnnotation<T>, s: &mut String) {
+fn print_list_type_annotation<T: Display>(
+    list: &ListTypeAnnotation<T>,
+    item_unwraps: &[WithSpan<Unwrap>],
+    s: &mut String,
+) {
     s.push_str("(");
-    print_type_annotation_impl(&list.0, s);
+    print_type_annotation_impl(&list.0, item_unwraps, s);
     s.push_str(")[]");
 }
 
-fn print_non_null_type_annotation<T: Display>(non_null: &NonNullTypeAnnotation<T>, s: &mut String) {
+fn print_non_null_type_annotation<T: Display>(
+    non_null: &NonNullTypeAnnotation<T>,
+    item_unwraps: &[WithSpan<Unwrap>],
+    s: &mut String,
+) {
     match non_null {
         NonNullTypeAnnotation::Named(named) => {
             s.push_str(&named.item.to_string());
         }
         NonNullTypeAnnotation::List(list) => {
-            print_list_type_annotation(list, s);
+            print_list_type_annotation(list, item_unwraps, s);
         }
     }
 }
 
+/// artifact_relative_directory is the directory of the artifact that contains the
+/// import statement, relative to the artifact directory.
 fn generate_function_import_statement(
     action_kind: &ClientFieldActionKind,
     project_root: &PathBuf,
     artifact_directory: &PathBuf,
+    artifact_relative_directory: &Path,
 ) -> ClientFieldFunctionImportStatement {
     match action_kind {
         ClientFieldActionKind::NamedImport((name, path)) => {
+            // Paths of client fields are relative to the project root.
             let path_to_client_field = project_root
                 .join(PathBuf::from_str(path.lookup()).expect(
                     "paths should be legal here. This is indicative of a bug in Isograph.",
                 ));
-            let relative_path =
-                // artifact directory includes __isograph, so artifact_directory.join("Type/Field")
-                // is a directory "two levels deep" within the artifact_directory.
-                //
-                // So diff_paths(path_to_client_field, artifact_directory.join("Type/Field"))
-                // is a lazy way of saying "make a relative path from two levels deep in the artifact
-                // dir to the client field".
-                //
-                // Since we will always go ../../../ the Type/Field part will never show up
-                // in the output.
-                //
-                // Anyway, TODO do better.
-                pathdiff::diff_paths(path_to_client_field, artifact_directory.join("Type/Field"))
-                    .expect("Relative path should work");
             ClientFieldFunctionImportStatement(format!(
                 "import {{ {name} as resolver }} from '{}';",
-                relative_path.to_str().expect("This path should be stringifiable. This probably is indicative of a bug in Relay.")
+                relative_import_path(
+                    &artifact_directory.join(artifact_relative_directory),
+                    &path_to_client_field
+                )
             ))
         }
+        ClientFieldActionKind::Identity => ClientFieldFunctionImportStatement(
+            "const resolver = (data: any) => data;".to_string(),
+        ),
         ClientFieldActionKind::RefetchField => ClientFieldFunctionImportStatement(format!(
             "import {{ makeNetworkRequest, type IsographEnvironment, type IsographEntrypoint }} from '@isograph/react';\n\
                 const resolver = (\n\
@@ -1278,18 +3614,20 @@ fn get_read_out_data(field_map: &[FieldMapItem]) -> String {
     s
 }
 
-#[derive(Debug)]
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord)]
 pub(crate) struct ResolverImportName(pub String);
 derive_display!(ResolverImportName);
 
-#[derive(Debug)]
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord)]
 pub struct ResolverImportType {
     pub(crate) globally_unique_type_name: ResolverImportName,
 }
 #[derive(Debug)]
 pub struct JavaScriptImports {
     pub(crate) default_import: bool,
-    pub(crate) types: Vec<ResolverImportType>,
+    /// A client field can be selected more than once (e.g. with different aliases),
+    /// but its types are imported once.
+    pub(crate) types: BTreeSet<ResolverImportType>,
 }
 
 fn generate_reader_ast<'schema>(
@@ -1322,7 +3660,10 @@ fn generate_reader_ast_with_path<'schema>(
     path: &mut Vec<NameAndArguments>,
 ) -> ReaderAst {
     let mut reader_ast = "[\n".to_string();
-    for item in selection_set {
+    for item in selection_set
+        .iter()
+        .filter(|selection| !selection.item.is_statically_excluded())
+    {
         let s = generate_reader_ast_node(
             item,
             schema,
@@ -1385,6 +3726,34 @@ fn generate_reader_ast_node(
                         );
                         let indent_1 = "  ".repeat(indentation_level as usize);
                         let indent_2 = "  ".repeat((indentation_level + 1) as usize);
+
+                        if let ClientFieldActionKind::Identity = client_field.action_kind {
+                            // This client field has no js function, so instead of importing
+                            // its reader artifact, we inline its reader AST. Its selections
+                            // are read from the same record as this field, so they share our
+                            // path and refetched paths.
+                            let (selection_set, _) = client_field
+                                .selection_set_and_unwraps
+                                .as_ref()
+                                .expect("Client fields should have a selection set");
+                            let inner_reader_ast = generate_reader_ast_with_path(
+                                schema,
+                                selection_set,
+                                indentation_level + 1,
+                                nested_client_field_imports,
+                                root_refetched_paths,
+                                path,
+                            );
+                            return format!(
+                                "{indent_1}{{\n\
+                                {indent_2}kind: \"InlineResolver\",\n\
+                                {indent_2}alias: \"{alias}\",\n\
+                                {indent_2}arguments: {arguments},\n\
+                                {indent_2}selections: {inner_reader_ast},\n\
+                                {indent_1}}},\n",
+                            );
+                        }
+
                         let client_field_string =
                             client_field.type_and_field.underscore_separated();
 
@@ -1403,7 +3772,7 @@ fn generate_reader_ast_node(
                             Entry::Vacant(vacant) => {
                                 vacant.insert(JavaScriptImports {
                                     default_import: true,
-                                    types: vec![],
+                                    types: BTreeSet::new(),
                                 });
                             }
                         }
@@ -1452,6 +3821,42 @@ fn generate_reader_ast_node(
                     }
                 }
             }
+            ServerFieldSelection::LinkedField(linked_field)
+                if linked_field.associated_data.is_refinement =>
+            {
+                // Refinements read from the same record as their parent, so they do not
+                // add to the path.
+                let name = linked_field.name.item;
+                let alias = linked_field
+                    .reader_alias
+                    .map(|x| format!("\"{}\"", x.item))
+                    .unwrap_or("null".to_string());
+                let concrete_type = schema
+                    .schema_data
+                    .object(linked_field.associated_data.parent_object_id)
+                    .name;
+
+                let inner_reader_ast = generate_reader_ast_with_path(
+                    schema,
+                    &linked_field.selection_set,
+                    indentation_level + 1,
+                    nested_client_field_imports,
+                    root_refetched_paths,
+                    path,
+                );
+
+                let indent_1 = "  ".repeat(indentation_level as usize);
+                let indent_2 = "  ".repeat((indentation_level + 1) as usize);
+                format!(
+                    "{indent_1}{{\n\
+                    {indent_2}kind: \"Refinement\",\n\
+                    {indent_2}fieldName: \"{name}\",\n\
+                    {indent_2}alias: {alias},\n\
+                    {indent_2}concreteType: \"{concrete_type}\",\n\
+                    {indent_2}selections: {inner_reader_ast},\n\
+                    {indent_1}}},\n",
+                )
+            }
             ServerFieldSelection::LinkedField(linked_field) => {
                 let name = linked_field.name.item;
                 let alias = linked_field
@@ -1490,14 +3895,285 @@ fn generate_reader_ast_node(
     }
 }
 
+/// Like generate_reader_ast, but generates JSON, for JSON artifacts. These cannot
+/// import each other, so client fields refer to their reader artifacts by name (e.g.
+/// User__avatar).
+fn generate_reader_ast_json(
+    schema: &ValidatedSchema,
+    selection_set: &[WithSpan<ValidatedSelection>],
+    root_refetched_paths: &[RootRefetchedPath],
+    path: &mut Vec<NameAndArguments>,
+) -> Value {
+    selection_set
+        .iter()
+        .filter(|selection| !selection.item.is_statically_excluded())
+        .map(|selection| {
+            generate_reader_ast_node_json(selection, schema, root_refetched_paths, path)
+        })
+        .collect()
+}
+
+fn generate_reader_ast_node_json(
+    selection: &WithSpan<ValidatedSelection>,
+    schema: &ValidatedSchema,
+    root_refetched_paths: &[RootRefetchedPath],
+    path: &mut Vec<NameAndArguments>,
+) -> Value {
+    match &selection.item {
+        Selection::ServerField(field) => match field {
+            ServerFieldSelection::ScalarField(scalar_field) => {
+                let arguments = generate_field_arguments_json(&scalar_field.arguments);
+                match scalar_field.associated_data {
+                    FieldDefinitionLocation::Server(_) => json!({
+                        "kind": "Scalar",
+                        "fieldName": scalar_field.name.item.to_string(),
+                        "alias": scalar_field.reader_alias.map(|alias| alias.item.to_string()),
+                        "arguments": arguments,
+                    }),
+                    FieldDefinitionLocation::Client(client_field_id) => {
+                        let alias = scalar_field.name_or_alias().item.to_string();
+                        let client_field = schema.resolver(client_field_id);
+
+                        if let ClientFieldActionKind::Identity = client_field.action_kind {
+                            let (selection_set, _) = client_field
+                                .selection_set_and_unwraps
+                                .as_ref()
+                                .expect("Client fields should have a selection set");
+                            return json!({
+                                "kind": "InlineResolver",
+                                "alias": alias,
+                                "arguments": arguments,
+                                "selections": generate_reader_ast_json(
+                                    schema,
+                                    selection_set,
+                                    root_refetched_paths,
+                                    path,
+                                ),
+                            });
+                        }
+
+                        let reader_artifact = client_field.type_and_field.underscore_separated();
+                        match client_field.variant {
+                            ClientFieldVariant::RefetchField => json!({
+                                "kind": "RefetchField",
+                                "alias": alias,
+                                "readerArtifact": reader_artifact,
+                                "refetchQuery": find_refetch_query_index(root_refetched_paths, path),
+                            }),
+                            ClientFieldVariant::MutationField(ref s) => json!({
+                                "kind": "MutationField",
+                                "alias": alias,
+                                "readerArtifact": reader_artifact,
+                                "refetchQuery": find_mutation_query_index(
+                                    root_refetched_paths,
+                                    path,
+                                    s.mutation_field_name,
+                                ),
+                            }),
+                            _ => {
+                                let client_field_refetched_paths =
+                                    refetched_paths_for_resolver(client_field, schema, path);
+                                json!({
+                                    "kind": "Resolver",
+                                    "alias": alias,
+                                    "arguments": arguments,
+                                    "readerArtifact": reader_artifact,
+                                    "usedRefetchQueries": get_nested_refetch_query_indices(
+                                        root_refetched_paths,
+                                        &client_field_refetched_paths,
+                                    ),
+                                })
+                            }
+                        }
+                    }
+                }
+            }
+            ServerFieldSelection::LinkedField(linked_field)
+                if linked_field.associated_data.is_refinement =>
+            {
+                let concrete_type = schema
+                    .schema_data
+                    .object(linked_field.associated_data.parent_object_id)
+                    .name;
+                json!({
+                    "kind": "Refinement",
+                    "fieldName": linked_field.name.item.to_string(),
+                    "alias": linked_field.reader_alias.map(|alias| alias.item.to_string()),
+                    "concreteType": concrete_type.to_string(),
+                    "selections": generate_reader_ast_json(
+                        schema,
+                        &linked_field.selection_set,
+                        root_refetched_paths,
+                        path,
+                    ),
+                })
+            }
+            ServerFieldSelection::LinkedField(linked_field) => {
+                path.push(into_name_and_arguments(&linked_field));
+                let selections = generate_reader_ast_json(
+                    schema,
+                    &linked_field.selection_set,
+                    root_refetched_paths,
+                    path,
+                );
+                path.pop();
+
+                json!({
+                    "kind": "Linked",
+                    "fieldName": linked_field.name.item.to_string(),
+                    "alias": linked_field.reader_alias.map(|alias| alias.item.to_string()),
+                    "arguments": generate_field_arguments_json(&linked_field.arguments),
+                    "selections": selections,
+                })
+            }
+        },
+    }
+}
+
+/// Generates a function that throws if the data read for a client field lacks
+/// any of the fields its reader AST selects, which indicates that the store and
+/// the normalization or reader ASTs are out of sync.
+fn generate_read_data_assertion(
+    type_and_field: &ObjectTypeAndFieldNames,
+    selection_set: &[WithSpan<ValidatedSelection>],
+) -> ReadDataAssertion {
+    let expected_fields = selection_set
+        .iter()
+        .filter(|selection| !selection.item.is_statically_excluded())
+        .map(|selection| match &selection.item {
+            Selection::ServerField(server_field) => {
+                format!("\"{}\"", server_field.name_or_alias().item)
+            }
+        })
+        .collect::<Vec<_>>()
+        .join(", ");
+
+    // Fields unwrapped with `!` are typed as non-null, so we also check that they are
+    // not null.
+    let unwrapped_fields = selection_set
+        .iter()
+        .filter(|selection| !selection.item.is_statically_excluded())
+        .filter_map(|selection| match &selection.item {
+            Selection::ServerField(server_field) => {
+                let unwraps = match server_field {
+                    ServerFieldSelection::ScalarField(scalar_field) => &scalar_field.unwraps,
+                    ServerFieldSelection::LinkedField(linked_field) => &linked_field.unwraps,
+                };
+                if split_first_unwrap(unwraps).0 {
+                    Some(format!("\"{}\"", server_field.name_or_alias().item))
+                } else {
+                    None
+                }
+            }
+        })
+        .collect::<Vec<_>>()
+        .join(", ");
+
+    let ObjectTypeAndFieldNames {
+        type_name,
+        field_name,
+    } = type_and_field;
+    let unwrapped_fields_assertion = if unwrapped_fields.is_empty() {
+        String::new()
+    } else {
+        format!(
+            "{}for (const fieldName of [{unwrapped_fields}]) {{\n\
+            {}if (data != null && data[fieldName] == null) {{\n\
+            {}throw new Error(\n\
+            {}'Expected ' + fieldName + ' in the data read for {type_name}.{field_name} ' +\n\
+            {}'not to be null, because it is unwrapped with !.'\n\
+            {});\n\
+            {}}}\n\
+            {}}}\n",
+            "  ", "    ", "      ", "        ", "        ", "      ", "    ", "  ",
+        )
+    };
+    ReadDataAssertion(format!(
+        "function assertReadData(data: any): void {{\n\
+        {}for (const fieldName of [{expected_fields}]) {{\n\
+        {}if (data != null && !(fieldName in data)) {{\n\
+        {}throw new Error(\n\
+        {}'Expected the data read for {type_name}.{field_name} to contain ' + fieldName + '. ' +\n\
+        {}'This indicates a mismatch between the store and the reader AST.'\n\
+        {});\n\
+        {}}}\n\
+        {}}}\n\
+        {unwrapped_fields_assertion}\
+        }}",
+        "  ", "    ", "      ", "        ", "        ", "      ", "    ", "  ",
+    ))
+}
+
+/// Rewrites the normalization aliases of fields with arguments, which are synthesized
+/// in their readable form when parsing, according to the strategy, i.e. hashes or
+/// removes them.
+pub(crate) fn apply_normalization_alias_strategy(
+    selection_set: &mut [WithSpan<MergedServerFieldSelection>],
+    normalization_alias_strategy: NormalizationAliasStrategy,
+) {
+    if normalization_alias_strategy == NormalizationAliasStrategy::Readable {
+        return;
+    }
+    for selection in selection_set.iter_mut() {
+        match &mut selection.item {
+            MergedServerFieldSelection::ScalarField(scalar_field) => {
+                if normalization_alias_strategy == NormalizationAliasStrategy::None {
+                    scalar_field.normalization_alias = None;
+                } else if let Some(alias) = &mut scalar_field.normalization_alias {
+                    alias.item = hashed_normalization_alias(alias.item.lookup())
+                        .intern()
+                        .into();
+                }
+            }
+            MergedServerFieldSelection::LinkedField(linked_field) => {
+                if normalization_alias_strategy == NormalizationAliasStrategy::None {
+                    linked_field.normalization_alias = None;
+                } else if let Some(alias) = &mut linked_field.normalization_alias {
+                    alias.item = hashed_normalization_alias(alias.item.lookup())
+                        .intern()
+                        .into();
+                }
+                apply_normalization_alias_strategy(
+                    &mut linked_field.selection_set,
+                    normalization_alias_strategy,
+                );
+            }
+            MergedServerFieldSelection::InlineFragment(inline_fragment) => {
+                apply_normalization_alias_strategy(
+                    &mut inline_fragment.selection_set,
+                    normalization_alias_strategy,
+                );
+            }
+            MergedServerFieldSelection::DeferredFragment(deferred_fragment) => {
+                apply_normalization_alias_strategy(
+                    &mut deferred_fragment.selection_set,
+                    normalization_alias_strategy,
+                );
+            }
+        }
+    }
+}
+
+/// The first 32 bits of the content hash of the readable alias. Aliases only need to
+/// be unique among the fields selected on a single record, so 32 bits are plenty.
+fn hashed_normalization_alias(readable_alias: &str) -> String {
+    format!("_{}", &content_hash(readable_alias.as_bytes())[..8])
+}
+
 fn generate_normalization_ast<'schema>(
     schema: &'schema ValidatedSchema,
     selection_set: &[WithSpan<MergedServerFieldSelection>],
     indentation_level: u8,
+    normalization_alias_strategy: NormalizationAliasStrategy,
 ) -> NormalizationAst {
     let mut normalization_ast = "[\n".to_string();
     for item in selection_set.iter() {
-        let s = generate_normalization_ast_node(item, schema, indentation_level + 1);
+        let s = generate_normalization_ast_node(
+            item,
+            schema,
+            indentation_level + 1,
+            normalization_alias_strategy,
+        );
         normalization_ast.push_str(&s);
     }
     normalization_ast.push_str(&format!("{}]", "  ".repeat(indentation_level as usize)));
@@ -1508,16 +4184,29 @@ fn generate_normalization_ast_node(
     item: &WithSpan<MergedServerFieldSelection>,
     schema: &ValidatedSchema,
     indentation_level: u8,
+    normalization_alias_strategy: NormalizationAliasStrategy,
 ) -> String {
     match &item.item {
         MergedServerFieldSelection::ScalarField(scalar_field) => {
             let MergedScalarFieldSelection {
-                name, arguments, ..
+                name,
+                arguments,
+                normalization_alias,
+                ..
             } = scalar_field;
             let indent = "  ".repeat(indentation_level as usize);
             let indent_2 = "  ".repeat((indentation_level + 1) as usize);
             let serialized_arguments =
                 get_serialized_field_arguments(arguments, indentation_level + 1);
+            let serialized_alias = get_serialized_normalization_alias(
+                normalization_alias
+                    .as_ref()
+                    .map(|alias| alias.item.lookup()),
+                name.item.lookup(),
+                arguments,
+                normalization_alias_strategy,
+                &indent_2,
+            );
             // TODO this is bad, name is a WithLocation and impl's Display, we should fix
             let name = name.item;
 
@@ -1525,6 +4214,7 @@ fn generate_normalization_ast_node(
                 "{indent}{{\n\
                 {indent_2}kind: \"Scalar\",\n\
                 {indent_2}fieldName: \"{name}\",\n\
+                {serialized_alias}\
                 {indent_2}arguments: {serialized_arguments},\n\
                 {indent}}},\n"
             )
@@ -1534,15 +4224,29 @@ fn generate_normalization_ast_node(
                 name,
                 selection_set,
                 arguments,
+                normalization_alias,
                 ..
             } = linked_field;
             let indent = "  ".repeat(indentation_level as usize);
             let indent_2 = "  ".repeat((indentation_level + 1) as usize);
             let serialized_arguments =
                 get_serialized_field_arguments(arguments, indentation_level + 1);
+            let serialized_alias = get_serialized_normalization_alias(
+                normalization_alias
+                    .as_ref()
+                    .map(|alias| alias.item.lookup()),
+                name.item.lookup(),
+                arguments,
+                normalization_alias_strategy,
+                &indent_2,
+            );
 
-            let selections =
-                generate_normalization_ast(schema, selection_set, indentation_level + 1);
+            let selections = generate_normalization_ast(
+                schema,
+                selection_set,
+                indentation_level + 1,
+                normalization_alias_strategy,
+            );
 
             // TODO this is bad, name is a WithLocation which impl's Display
             let name = name.item;
@@ -1551,15 +4255,201 @@ fn generate_normalization_ast_node(
                 "{indent}{{\n\
                 {indent_2}kind: \"Linked\",\n\
                 {indent_2}fieldName: \"{name}\",\n\
+                {serialized_alias}\
                 {indent_2}arguments: {serialized_arguments},\n\
                 {indent_2}selections: {selections},\n\
                 {indent}}},\n"
             )
         }
+        MergedServerFieldSelection::InlineFragment(inline_fragment) => {
+            let MergedInlineFragmentSelection {
+                type_to_refine_to,
+                selection_set,
+            } = inline_fragment;
+            let indent = "  ".repeat(indentation_level as usize);
+            let indent_2 = "  ".repeat((indentation_level + 1) as usize);
+
+            let selections = generate_normalization_ast(
+                schema,
+                selection_set,
+                indentation_level + 1,
+                normalization_alias_strategy,
+            );
+
+            format!(
+                "{indent}{{\n\
+                {indent_2}kind: \"InlineFragment\",\n\
+                {indent_2}type: \"{type_to_refine_to}\",\n\
+                {indent_2}selections: {selections},\n\
+                {indent}}},\n"
+            )
+        }
+        MergedServerFieldSelection::DeferredFragment(deferred_fragment) => {
+            let MergedDeferredFragmentSelection {
+                label,
+                selection_set,
+                ..
+            } = deferred_fragment;
+            let indent = "  ".repeat(indentation_level as usize);
+            let indent_2 = "  ".repeat((indentation_level + 1) as usize);
+
+            let selections = generate_normalization_ast(
+                schema,
+                selection_set,
+                indentation_level + 1,
+                normalization_alias_strategy,
+            );
+
+            format!(
+                "{indent}{{\n\
+                {indent_2}kind: \"Defer\",\n\
+                {indent_2}label: \"{label}\",\n\
+                {indent_2}selections: {selections},\n\
+                {indent}}},\n"
+            )
+        }
+    }
+}
+
+/// Like generate_normalization_ast, but generates JSON, for JSON artifacts.
+fn generate_normalization_ast_json(
+    selection_set: &[WithSpan<MergedServerFieldSelection>],
+    normalization_alias_strategy: NormalizationAliasStrategy,
+) -> Value {
+    selection_set
+        .iter()
+        .map(|selection| {
+            generate_normalization_ast_node_json(selection, normalization_alias_strategy)
+        })
+        .collect()
+}
+
+fn generate_normalization_ast_node_json(
+    item: &WithSpan<MergedServerFieldSelection>,
+    normalization_alias_strategy: NormalizationAliasStrategy,
+) -> Value {
+    match &item.item {
+        MergedServerFieldSelection::ScalarField(scalar_field) => {
+            let MergedScalarFieldSelection {
+                name,
+                arguments,
+                normalization_alias,
+                ..
+            } = scalar_field;
+            let mut node = json!({
+                "kind": "Scalar",
+                "fieldName": name.item.to_string(),
+                "arguments": generate_field_arguments_json(arguments),
+            });
+            if let Some(alias) = get_normalization_alias(
+                normalization_alias
+                    .as_ref()
+                    .map(|alias| alias.item.lookup()),
+                name.item.lookup(),
+                arguments,
+                normalization_alias_strategy,
+            ) {
+                node["alias"] = json!(alias);
+            }
+            node
+        }
+        MergedServerFieldSelection::LinkedField(linked_field) => {
+            let MergedLinkedFieldSelection {
+                name,
+                selection_set,
+                arguments,
+                normalization_alias,
+                ..
+            } = linked_field;
+            let mut node = json!({
+                "kind": "Linked",
+                "fieldName": name.item.to_string(),
+                "arguments": generate_field_arguments_json(arguments),
+                "selections": generate_normalization_ast_json(
+                    selection_set,
+                    normalization_alias_strategy,
+                ),
+            });
+            if let Some(alias) = get_normalization_alias(
+                normalization_alias
+                    .as_ref()
+                    .map(|alias| alias.item.lookup()),
+                name.item.lookup(),
+                arguments,
+                normalization_alias_strategy,
+            ) {
+                node["alias"] = json!(alias);
+            }
+            node
+        }
+        MergedServerFieldSelection::InlineFragment(inline_fragment) => {
+            let MergedInlineFragmentSelection {
+                type_to_refine_to,
+                selection_set,
+            } = inline_fragment;
+            json!({
+                "kind": "InlineFragment",
+                "type": type_to_refine_to.to_string(),
+                "selections": generate_normalization_ast_json(
+                    selection_set,
+                    normalization_alias_strategy,
+                ),
+            })
+        }
+        MergedServerFieldSelection::DeferredFragment(deferred_fragment) => {
+            let MergedDeferredFragmentSelection {
+                label,
+                selection_set,
+                ..
+            } = deferred_fragment;
+            json!({
+                "kind": "Defer",
+                "label": label.to_string(),
+                "selections": generate_normalization_ast_json(
+                    selection_set,
+                    normalization_alias_strategy,
+                ),
+            })
+        }
+    }
+}
+
+/// Readable aliases are derived by the runtime from the field name and arguments, so
+/// they are left out of the normalization AST. Other aliases are not derivable, and
+/// neither is the network response key of an unaliased field with arguments (i.e.
+/// its name).
+fn get_normalization_alias<'a>(
+    normalization_alias: Option<&'a str>,
+    field_name: &'a str,
+    arguments: &[WithLocation<SelectionFieldArgument>],
+    normalization_alias_strategy: NormalizationAliasStrategy,
+) -> Option<&'a str> {
+    match (normalization_alias, normalization_alias_strategy) {
+        (Some(alias), NormalizationAliasStrategy::Hashed) => Some(alias),
+        (_, NormalizationAliasStrategy::None) if !arguments.is_empty() => Some(field_name),
+        _ => None,
+    }
+}
+
+fn get_serialized_normalization_alias(
+    normalization_alias: Option<&str>,
+    field_name: &str,
+    arguments: &[WithLocation<SelectionFieldArgument>],
+    normalization_alias_strategy: NormalizationAliasStrategy,
+    indent: &str,
+) -> String {
+    match get_normalization_alias(
+        normalization_alias,
+        field_name,
+        arguments,
+        normalization_alias_strategy,
+    ) {
+        Some(alias) => format!("{indent}alias: \"{alias}\",\n"),
+        None => String::new(),
     }
 }
 
-fn get_serialized_arguments_for_query_text(
+pub(crate) fn get_serialized_arguments_for_query_text(
     arguments: &[WithLocation<SelectionFieldArgument>],
 ) -> String {
     if arguments.is_empty() {
@@ -1617,6 +4507,24 @@ fn get_serialized_field_arguments(
                     {indent_1}],\n"
                 )
             }
+            NonConstantValue::Boolean(bool_value) => {
+                format!(
+                    "\n\
+                    {indent_1}[\n\
+                    {indent_2}\"{argument_name}\",\n\
+                    {indent_2}{{ kind: \"Literal\", value: {bool_value} }},\n\
+                    {indent_1}],\n"
+                )
+            }
+            NonConstantValue::String(string_value) => {
+                format!(
+                    "\n\
+                    {indent_1}[\n\
+                    {indent_2}\"{argument_name}\",\n\
+                    {indent_2}{{ kind: \"Literal\", value: \"{string_value}\" }},\n\
+                    {indent_1}],\n"
+                )
+            }
         };
 
         s.push_str(&arg_value);
@@ -1626,10 +4534,40 @@ fn get_serialized_field_arguments(
     s
 }
 
-fn serialize_non_constant_value_for_graphql(value: &NonConstantValue) -> String {
+/// Like get_serialized_field_arguments, but generates JSON, for JSON artifacts.
+fn generate_field_arguments_json(arguments: &[WithLocation<SelectionFieldArgument>]) -> Value {
+    if arguments.is_empty() {
+        return Value::Null;
+    }
+
+    arguments
+        .iter()
+        .map(|argument| {
+            let value = match argument.item.value.item {
+                NonConstantValue::Variable(variable_name) => {
+                    json!({ "kind": "Variable", "name": variable_name.to_string() })
+                }
+                NonConstantValue::Integer(int_value) => {
+                    json!({ "kind": "Literal", "value": int_value.to_string() })
+                }
+                NonConstantValue::Boolean(bool_value) => {
+                    json!({ "kind": "Literal", "value": bool_value })
+                }
+                NonConstantValue::String(string_value) => {
+                    json!({ "kind": "Literal", "value": string_value.to_string() })
+                }
+            };
+            json!([argument.item.name.item.to_string(), value])
+        })
+        .collect()
+}
+
+pub(crate) fn serialize_non_constant_value_for_graphql(value: &NonConstantValue) -> String {
     match value {
         NonConstantValue::Variable(variable_name) => format!("${}", variable_name),
         NonConstantValue::Integer(int_value) => int_value.to_string(),
+        NonConstantValue::Boolean(bool_value) => bool_value.to_string(),
+        NonConstantValue::String(string_value) => format!("\"{}\"", string_value),
     }
 }
 
@@ -1638,6 +4576,19 @@ fn get_nested_refetch_query_text(
     nested_refetch_queries: &[PathToRefetchField],
 ) -> String {
     let mut s = "[".to_string();
+    for index in get_nested_refetch_query_indices(root_refetched_paths, nested_refetch_queries) {
+        s.push_str(&format!("{}, ", index));
+    }
+    s.push_str("]");
+    s
+}
+
+/// The indexes of the root refetched paths of each of nested_refetch_queries
+fn get_nested_refetch_query_indices(
+    root_refetched_paths: &[RootRefetchedPath],
+    nested_refetch_queries: &[PathToRefetchField],
+) -> Vec<usize> {
+    let mut indices = vec![];
     for nested_refetch_query in nested_refetch_queries.iter() {
         let mut found_at_least_one = false;
         for index in root_refetched_paths
@@ -1652,7 +4603,7 @@ fn get_nested_refetch_query_text(
             })
         {
             found_at_least_one = true;
-            s.push_str(&format!("{}, ", index));
+            indices.push(index);
         }
 
         assert!(
@@ -1661,11 +4612,17 @@ fn get_nested_refetch_query_text(
             This is indicative of a bug in Isograph."
         );
     }
-    s.push_str("]");
-    s
+    indices
 }
 
 fn generate_output_type(client_field: &ValidatedClientField) -> ClientFieldOutputType {
+    if let ClientFieldActionKind::Identity = client_field.action_kind {
+        // Client fields without a js function evaluate to the data they read
+        return ClientFieldOutputType(format!(
+            "{}__param",
+            client_field.type_and_field.underscore_separated()
+        ));
+    }
     match &client_field.variant {
         variant => match variant {
             ClientFieldVariant::Component => {
@@ -1719,6 +4676,91 @@ fn find_mutation_query_index(
         .expect("Expected refetch query to be found")
 }
 
-fn generate_path(object_name: IsographObjectTypeName, field_name: SelectableFieldName) -> PathBuf {
-    PathBuf::from(object_name.lookup()).join(field_name.lookup())
+pub(crate) fn generate_path(
+    object_name: IsographObjectTypeName,
+    field_name: SelectableFieldName,
+    artifact_namespace: Option<ArtifactNamespace>,
+) -> PathBuf {
+    PathBuf::from(artifact_type_directory(object_name, artifact_namespace))
+        .join(field_name.lookup())
+}
+
+/// The module specifier with which a module in from_directory imports the module at
+/// to. Both paths must be absolute, or relative to the same directory.
+pub(crate) fn relative_import_path(from_directory: &Path, to: &Path) -> String {
+    let relative_path =
+        pathdiff::diff_paths(to, from_directory).expect("Relative path should work");
+    // Module specifiers use forward slashes, regardless of the platform
+    let relative_path = relative_path
+        .components()
+        .map(|component| {
+            component.as_os_str().to_str().expect(
+                "This path should be stringifiable. This probably is indicative of a bug in Isograph.",
+            )
+        })
+        .collect::<Vec<_>>()
+        .join("/");
+    if relative_path.starts_with("../") {
+        relative_path
+    } else {
+        format!("./{}", relative_path)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::QueryText;
+
+    fn strip_ignored_characters(query_text: &str) -> String {
+        let mut query_text = QueryText(query_text.to_string());
+        query_text.strip_ignored_characters();
+        query_text.0
+    }
+
+    #[test]
+    fn strip_whitespace_and_commas_between_arguments() {
+        assert_eq!(
+            strip_ignored_characters(
+                "query UserProfile($id: ID!, $size: Int) {\n  \
+                node(id: $id, size: $size) {\n    \
+                id,\n    \
+                name,\n  \
+                }\n\
+                }"
+            ),
+            "query UserProfile($id:ID!$size:Int){node(id:$id size:$size){id name}}"
+        );
+    }
+
+    #[test]
+    fn strip_line_continuations() {
+        assert_eq!(
+            strip_ignored_characters("query UserProfile {\\\n  me {\\\n    id\\\n  }\\\n}"),
+            "query UserProfile{me{id}}"
+        );
+    }
+
+    #[test]
+    fn keep_string_literals() {
+        assert_eq!(
+            strip_ignored_characters(
+                "query UserProfile {\n  user(name: \"Jane  Doe, Jr.\", title: \"a \\\"b,  c\\\" d\") {\n    id\n  }\n}"
+            ),
+            "query UserProfile{user(name:\"Jane  Doe, Jr.\"title:\"a \\\"b,  c\\\" d\"){id}}"
+        );
+    }
+
+    #[test]
+    fn keep_block_strings() {
+        assert_eq!(
+            strip_ignored_characters(
+                "query UserProfile {\n  \
+                user(bio: \"\"\"\n    She said \"hi,  there\"\n    and \\\"\"\" left\n  \"\"\", id: 4) {\n    \
+                id\n  \
+                }\n\
+                }"
+            ),
+            "query UserProfile{user(bio:\"\"\"\n    She said \"hi,  there\"\n    and \\\"\"\" left\n  \"\"\"id:4){id}}"
+        );
+    }
 }
@@ -4,39 +4,66 @@ use std::{
     path::{Path, PathBuf},
 };
 
+use isograph_lang_parser::format_iso_literal;
 use lazy_static::lazy_static;
 use regex::Regex;
 
-use crate::batch_compile::BatchCompileError;
+use crate::{
+    batch_compile::BatchCompileError,
+    progress::{ProgressBar, ProgressReporting},
+};
 
 pub(crate) fn read_files_in_folder(
-    canonicalized_root_path: &PathBuf,
+    normalized_root_path: &PathBuf,
+    single_file_component_extensions: &[String],
+    progress_reporting: ProgressReporting,
 ) -> Result<Vec<(PathBuf, String)>, BatchCompileError> {
-    if !canonicalized_root_path.is_dir() {
+    if !normalized_root_path.is_dir() {
         return Err(BatchCompileError::ProjectRootNotADirectory {
             // TODO avoid cloning
-            path: canonicalized_root_path.clone(),
+            path: normalized_root_path.clone(),
         });
     }
 
-    read_dir_recursive(&canonicalized_root_path)?
+    let paths = read_dir_recursive(&normalized_root_path)?
+        .into_iter()
+        .filter(|path| has_valid_extension(path, single_file_component_extensions))
+        .collect::<Vec<_>>();
+
+    let mut progress_bar = ProgressBar::new("Reading files", paths.len(), progress_reporting);
+    paths
         .into_iter()
-        .filter(has_valid_extension)
-        .map(|path| read_file(path, canonicalized_root_path))
+        .map(|path| {
+            let file = read_file(path, normalized_root_path);
+            progress_bar.increment();
+            file
+        })
         .collect()
 }
 
-fn has_valid_extension(path: &PathBuf) -> bool {
+fn has_valid_extension(path: &PathBuf, single_file_component_extensions: &[String]) -> bool {
     let extension = path.extension().and_then(|x| x.to_str());
     match extension {
         Some("ts") | Some("tsx") | Some("js") | Some("jsx") => true,
-        _ => false,
+        _ => is_single_file_component(path, single_file_component_extensions),
+    }
+}
+
+pub(crate) fn is_single_file_component(
+    path: &Path,
+    single_file_component_extensions: &[String],
+) -> bool {
+    match path.extension().and_then(|x| x.to_str()) {
+        Some(extension) => single_file_component_extensions
+            .iter()
+            .any(|single_file_component_extension| single_file_component_extension == extension),
+        None => false,
     }
 }
 
 fn read_file(
     path: PathBuf,
-    canonicalized_root_path: &PathBuf,
+    normalized_root_path: &PathBuf,
 ) -> Result<(PathBuf, String), BatchCompileError> {
     // This isn't ideal. We can avoid a clone if we changed .map_err to match
     let path_2 = path.clone();
@@ -55,7 +82,7 @@ fn read_file(
         .to_owned();
 
     Ok((
-        path.strip_prefix(&canonicalized_root_path)?.to_path_buf(),
+        path.strip_prefix(&normalized_root_path)?.to_path_buf(),
         contents,
     ))
 }
@@ -91,8 +118,11 @@ pub(crate) static ISOGRAPH_FOLDER: &'static str = "__isograph";
 lazy_static! {
     static ref EXTRACT_ISO_LITERAL: Regex =
         Regex::new(r"(export const ([^ ]+) =\s+)?iso(\()?`([^`]+)`(\))?(\()?").unwrap();
+    static ref EXTRACT_SCRIPT_BLOCK: Regex =
+        Regex::new(r"(?s)<script\b[^>]*>(.*?)</script>").unwrap();
 }
 
+#[derive(Debug, Clone, Copy)]
 pub(crate) struct IsoLiteralExtraction<'a> {
     pub(crate) const_export_name: Option<&'a str>,
     pub(crate) iso_literal_text: &'a str,
@@ -101,6 +131,20 @@ pub(crate) struct IsoLiteralExtraction<'a> {
     pub(crate) has_paren: bool,
 }
 
+/// Extracts the iso literals from a source file, which is either a single file
+/// component or a JavaScript or TypeScript file.
+pub(crate) fn extract_iso_literals_from_file<'a>(
+    file_path: &Path,
+    file_content: &'a str,
+    single_file_component_extensions: &[String],
+) -> Vec<IsoLiteralExtraction<'a>> {
+    if is_single_file_component(file_path, single_file_component_extensions) {
+        extract_iso_literal_from_single_file_component(file_content).collect()
+    } else {
+        extract_iso_literal_from_file_content(file_content).collect()
+    }
+}
+
 pub(crate) fn extract_iso_literal_from_file_content<'a>(
     content: &'a str,
 ) -> impl Iterator<Item = IsoLiteralExtraction<'a>> + 'a {
@@ -118,3 +162,46 @@ pub(crate) fn extract_iso_literal_from_file_content<'a>(
             }
         })
 }
+
+/// Extracts the iso literals from the `<script>` blocks of a single file component
+/// (e.g. a `.vue` or `.svelte` file). The start indices of the extractions are
+/// relative to the start of the file, not of the block, so that errors point to the
+/// right location.
+pub(crate) fn extract_iso_literal_from_single_file_component<'a>(
+    content: &'a str,
+) -> impl Iterator<Item = IsoLiteralExtraction<'a>> + 'a {
+    EXTRACT_SCRIPT_BLOCK
+        .captures_iter(content)
+        .flat_map(|captures| {
+            let script_block_match = captures.get(1).unwrap();
+            let script_block_start_index = script_block_match.start();
+            extract_iso_literal_from_file_content(script_block_match.as_str()).map(
+                move |iso_literal_extraction| IsoLiteralExtraction {
+                    iso_literal_start_index: script_block_start_index
+                        + iso_literal_extraction.iso_literal_start_index,
+                    ..iso_literal_extraction
+                },
+            )
+        })
+}
+
+/// Returns content with the text of every iso literal replaced by its canonical
+/// formatting. Everything outside of the iso literals, as well as literals that
+/// cannot be parsed, is left untouched.
+pub fn format_iso_literals_in_file_content(content: &str) -> String {
+    let mut formatted_content = String::with_capacity(content.len());
+    let mut last_index = 0;
+    for iso_literal_extraction in extract_iso_literal_from_file_content(content) {
+        let start_index = iso_literal_extraction.iso_literal_start_index;
+        let end_index = start_index + iso_literal_extraction.iso_literal_text.len();
+        if let Some(formatted_iso_literal) =
+            format_iso_literal(iso_literal_extraction.iso_literal_text)
+        {
+            formatted_content.push_str(&content[last_index..start_index]);
+            formatted_content.push_str(&formatted_iso_literal);
+            last_index = end_index;
+        }
+    }
+    formatted_content.push_str(&content[last_index..]);
+    formatted_content
+}
@@ -0,0 +1,173 @@
+use common_lang_types::{IsographObjectTypeName, SelectableFieldName};
+use intern::Lookup;
+use isograph_schema::ClientFieldVariant;
+use serde_json::{json, Map, Value};
+
+use crate::generate_artifacts::{
+    serialize_non_constant_value_for_graphql, variable_names_to_json, DataRequirementsArtifactInfo,
+    EntrypointArtifactInfo, PersistedQueriesManifestArtifactInfo, QueryTextDeclaration,
+    ReaderArtifactInfo, RefetchArtifactInfo, NODE_REFETCH_QUERY_FILE_NAME,
+};
+
+impl<'schema> EntrypointArtifactInfo<'schema> {
+    pub(crate) fn json_file_contents(self) -> String {
+        let EntrypointArtifactInfo {
+            query_id,
+            query_text,
+            normalization_ast_json,
+            nested_refetch_queries,
+            operation_kind,
+            ..
+        } = self;
+        let mut artifact = json!({
+            "kind": "Entrypoint",
+            "operationKind": operation_kind.to_string(),
+            "queryId": query_id.to_string(),
+            "queryText": query_text_json(&query_text),
+            "normalizationAst": normalization_ast_json.0,
+            "nestedRefetchQueries": nested_refetch_queries.0,
+        });
+        insert_persisted_query_hash(&mut artifact, &query_text);
+        json_file_contents(&artifact)
+    }
+}
+
+impl<'schema> ReaderArtifactInfo<'schema> {
+    pub(crate) fn json_file_contents(self) -> String {
+        let ReaderArtifactInfo {
+            parent_type,
+            client_field_name,
+            client_field_variant,
+            reader_ast_json,
+            refetch_query_allowed_variables,
+            ..
+        } = self;
+        let mut artifact = json!({
+            "kind": "ReaderArtifact",
+            "readerAst": reader_ast_json.0,
+            "variant": reader_variant_json(
+                parent_type.name,
+                client_field_name,
+                &client_field_variant,
+            ),
+        });
+        // Refetch queries are referred to by file name, as in nestedRefetchQueries.
+        if let Some(allowed_variables) = refetch_query_allowed_variables {
+            artifact["refetchQuery"] = json!({
+                "artifact": NODE_REFETCH_QUERY_FILE_NAME,
+                "allowedVariables": variable_names_to_json(&allowed_variables),
+            });
+        }
+        json_file_contents(&artifact)
+    }
+}
+
+impl RefetchArtifactInfo {
+    pub(crate) fn json_file_contents(self) -> String {
+        let RefetchArtifactInfo {
+            normalization_ast_json,
+            query_id,
+            query_text,
+            operation_kind,
+            ..
+        } = self;
+        let mut artifact = json!({
+            "kind": "RefetchQuery",
+            "operationKind": operation_kind.to_string(),
+            "queryId": query_id.to_string(),
+            "queryText": query_text_json(&query_text),
+            "normalizationAst": normalization_ast_json.0,
+        });
+        insert_persisted_query_hash(&mut artifact, &query_text);
+        json_file_contents(&artifact)
+    }
+}
+
+impl DataRequirementsArtifactInfo {
+    pub(crate) fn json_file_contents(self) -> String {
+        let DataRequirementsArtifactInfo {
+            parent_type_name,
+            client_field_name,
+            data_requirements,
+            ..
+        } = self;
+        let server_fields = data_requirements
+            .iter()
+            .map(|data_requirement| {
+                let arguments = data_requirement
+                    .arguments
+                    .iter()
+                    .map(|argument| {
+                        json!({
+                            "name": argument.name.lookup(),
+                            "type": argument.argument_type,
+                            "value": serialize_non_constant_value_for_graphql(&argument.value),
+                        })
+                    })
+                    .collect::<Vec<_>>();
+                json!({
+                    "field": format!(
+                        "{}.{}",
+                        data_requirement.parent_type_name, data_requirement.field_name
+                    ),
+                    "type": data_requirement.field_type,
+                    "arguments": arguments,
+                })
+            })
+            .collect::<Vec<_>>();
+        json_file_contents(&json!({
+            "kind": "DataRequirements",
+            "clientField": format!("{parent_type_name}.{client_field_name}"),
+            "serverFields": server_fields,
+        }))
+    }
+}
+
+impl PersistedQueriesManifestArtifactInfo {
+    pub(crate) fn json_file_contents(self) -> String {
+        let query_texts = self
+            .query_texts
+            .into_iter()
+            .map(|(hash, query_text)| (hash.to_string(), Value::String(query_text)))
+            .collect::<Map<_, _>>();
+        json_file_contents(&Value::Object(query_texts))
+    }
+}
+
+fn json_file_contents(artifact: &Value) -> String {
+    let mut contents =
+        serde_json::to_string_pretty(artifact).expect("Artifacts should be serializable");
+    contents.push('\n');
+    contents
+}
+
+fn insert_persisted_query_hash(artifact: &mut Value, query_text: &QueryTextDeclaration) {
+    if let Some(hash) = query_text.persisted_query_hash() {
+        artifact["persistedQueryHash"] = json!(hash.to_string());
+    }
+}
+
+/// JSON artifacts cannot import the query texts that TypeScript artifacts share, so
+/// shared query texts are inlined.
+fn query_text_json(query_text: &QueryTextDeclaration) -> Value {
+    match query_text {
+        QueryTextDeclaration::Inline(query_text)
+        | QueryTextDeclaration::Shared { query_text, .. } => json!(query_text.to_graphql()),
+        QueryTextDeclaration::Persisted(_) => Value::Null,
+    }
+}
+
+/// Like get_reader_variant, but generates JSON.
+fn reader_variant_json(
+    parent_type_name: IsographObjectTypeName,
+    client_field_name: SelectableFieldName,
+    client_field_variant: &ClientFieldVariant,
+) -> Value {
+    match client_field_variant {
+        ClientFieldVariant::Component => json!({
+            "kind": "Component",
+            "componentName": format!("{parent_type_name}.{client_field_name}"),
+        }),
+        _ => json!({ "kind": "Eager" }),
+    }
+}
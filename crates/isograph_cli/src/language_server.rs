@@ -0,0 +1,937 @@
+use std::{
+    borrow::Cow,
+    collections::{BTreeMap, HashMap, HashSet, VecDeque},
+    fs,
+    io::{self, BufRead, Write},
+    path::{Path, PathBuf},
+};
+
+use common_lang_types::{
+    Location, SelectableFieldName, Span, UnvalidatedTypeName, WithLocation, WithSpan,
+};
+use intern::{string_key::Intern, Lookup};
+use isograph_config::{normalize_path, CompilerConfig, PathNormalization};
+use isograph_lang_parser::{selection_completion_context, semantic_tokens, SemanticTokenKind};
+use isograph_lang_types::{ObjectId, SelectableFieldId};
+use isograph_schema::{
+    check_client_field_naming_conventions, check_forbidden_server_fields, check_lint_rules, Entity,
+    FieldDefinitionLocation, ValidatedSchema,
+};
+use serde_json::{json, Value};
+
+use crate::{
+    batch_compile::BatchCompileError,
+    compiler::Compiler,
+    generate_artifacts::write_variables_to_string,
+    isograph_literals::{
+        extract_iso_literals_from_file, format_iso_literals_in_file_content, IsoLiteralExtraction,
+    },
+    print_schema::print_server_field,
+    workspace_symbols::find_client_field_symbols_with_unsaved_files,
+};
+
+/// https://microsoft.github.io/language-server-protocol/specifications/lsp/3.17/specification/#errorCodes
+const METHOD_NOT_FOUND: i64 = -32601;
+/// https://microsoft.github.io/language-server-protocol/specifications/lsp/3.17/specification/#diagnosticSeverity
+const DIAGNOSTIC_SEVERITY_ERROR: i64 = 1;
+/// https://microsoft.github.io/language-server-protocol/specifications/lsp/3.17/specification/#textDocumentSyncKind
+const TEXT_DOCUMENT_SYNC_KIND_FULL: i64 = 1;
+/// https://microsoft.github.io/language-server-protocol/specifications/lsp/3.17/specification/#completionItemKind
+const COMPLETION_ITEM_KIND_METHOD: i64 = 2;
+const COMPLETION_ITEM_KIND_FIELD: i64 = 5;
+/// https://microsoft.github.io/language-server-protocol/specifications/lsp/3.17/specification/#symbolKind
+const SYMBOL_KIND_METHOD: i64 = 6;
+/// The legend of the semantic tokens, i.e. the types that tokens are classified as,
+/// which encoded tokens refer to by index. See semantic_token_type.
+/// https://microsoft.github.io/language-server-protocol/specifications/lsp/3.17/specification/#semanticTokenTypes
+const SEMANTIC_TOKEN_TYPES: [&str; 8] = [
+    "keyword",
+    "type",
+    "property",
+    "parameter",
+    "variable",
+    "decorator",
+    "number",
+    "string",
+];
+
+/// Runs a language server for the project, which communicates with the editor over
+/// stdin and stdout, until the editor asks it to exit. It:
+/// - publishes the errors in the schema, schema extensions and iso literals as
+///   diagnostics, whenever a file is changed or saved,
+/// - shows the type and description of fields and types on hover,
+/// - goes from a selection to the definition of the field it selects (e.g. in the
+///   schema),
+/// - completes the fields that can be selected in iso literals,
+/// - formats the iso literals in a file,
+/// - finds client fields by name, anywhere in the project,
+/// - classifies the tokens in iso literals, for syntax highlighting, and
+/// - reports the progress of revalidating the project, if the editor supports it.
+///
+/// The project is kept in memory by a Compiler, and revalidated whenever a file is
+/// changed or saved. The unsaved contents of open files are used instead of their
+/// contents on disk, since they are what the editor shows. Nothing is written.
+pub fn run_language_server(config: &CompilerConfig) -> Result<(), BatchCompileError> {
+    let stdin = io::stdin();
+    let stdout = io::stdout();
+    serve_language_client(config, &mut stdin.lock(), &mut stdout.lock())
+}
+
+/// Like run_language_server, but reads the messages of the editor from input, and
+/// writes the messages to the editor to output.
+pub fn serve_language_client(
+    config: &CompilerConfig,
+    input: &mut impl BufRead,
+    output: &mut impl Write,
+) -> Result<(), BatchCompileError> {
+    let mut language_server = LanguageServer {
+        config,
+        compiler: None,
+        files_with_diagnostics: HashSet::new(),
+        open_documents: HashMap::new(),
+        supports_work_done_progress: false,
+        last_request_id: 0,
+        pending_messages: VecDeque::new(),
+    };
+    language_server
+        .run(input, output)
+        .map_err(|message| BatchCompileError::UnableToCommunicateWithLanguageClient { message })
+}
+
+struct LanguageServer<'config> {
+    config: &'config CompilerConfig,
+    /// None until the project has been processed successfully once
+    compiler: Option<Compiler<'config>>,
+    /// The files for which errors were last published, so that their diagnostics
+    /// can be cleared once the errors are fixed
+    files_with_diagnostics: HashSet<PathBuf>,
+    /// The contents of the files that are open in the editor, which may not have
+    /// been saved
+    open_documents: HashMap<PathBuf, String>,
+    /// Whether the editor can show the progress of work that the server starts
+    supports_work_done_progress: bool,
+    /// The id of the last request sent to the editor
+    last_request_id: u64,
+    /// Messages that were read while waiting for the response to a request, and
+    /// have not been handled yet
+    pending_messages: VecDeque<Value>,
+}
+
+impl<'config> LanguageServer<'config> {
+    fn run(&mut self, input: &mut impl BufRead, output: &mut impl Write) -> io::Result<()> {
+        while let Some(message) = self.next_message(input)? {
+            // Responses (to requests that were not waited for) have no method.
+            let Some(method) = message["method"].as_str() else {
+                continue;
+            };
+            let params = &message["params"];
+            let result = match method {
+                "initialize" => {
+                    self.supports_work_done_progress = params["capabilities"]["window"]
+                        ["workDoneProgress"]
+                        .as_bool()
+                        .unwrap_or(false);
+                    Some(json!({
+                        "capabilities": {
+                            "textDocumentSync": {
+                                "openClose": true,
+                                "change": TEXT_DOCUMENT_SYNC_KIND_FULL,
+                                "save": true,
+                            },
+                            "hoverProvider": true,
+                            "definitionProvider": true,
+                            "completionProvider": {},
+                            "documentFormattingProvider": true,
+                            "workspaceSymbolProvider": true,
+                            "semanticTokensProvider": {
+                                "legend": {
+                                    "tokenTypes": SEMANTIC_TOKEN_TYPES,
+                                    "tokenModifiers": [],
+                                },
+                                "full": true,
+                            },
+                        },
+                        "serverInfo": {
+                            "name": "isograph",
+                            "version": env!("CARGO_PKG_VERSION"),
+                        },
+                    }))
+                }
+                "initialized" | "textDocument/didSave" | "workspace/didChangeWatchedFiles" => {
+                    self.revalidate(input, output)?;
+                    None
+                }
+                "textDocument/didOpen" => {
+                    self.update_open_document(params);
+                    None
+                }
+                "textDocument/didChange" => {
+                    self.update_open_document(params);
+                    self.revalidate(input, output)?;
+                    None
+                }
+                "textDocument/didClose" => {
+                    // The unsaved changes of the document are discarded, so the
+                    // project is revalidated against its contents on disk.
+                    if let Some(path) =
+                        document_path(params, self.config.options.path_normalization)
+                    {
+                        if self.open_documents.remove(&path).is_some() {
+                            self.revalidate(input, output)?;
+                        }
+                    }
+                    None
+                }
+                "textDocument/hover" => Some(self.hover(params).unwrap_or(Value::Null)),
+                "textDocument/definition" => Some(self.definition(params).unwrap_or(Value::Null)),
+                "textDocument/completion" => Some(self.completion(params).unwrap_or(Value::Null)),
+                "textDocument/formatting" => Some(self.formatting(params).unwrap_or(Value::Null)),
+                "workspace/symbol" => Some(self.workspace_symbols(params).unwrap_or(Value::Null)),
+                "textDocument/semanticTokens/full" => {
+                    Some(self.semantic_tokens_full(params).unwrap_or(Value::Null))
+                }
+                "shutdown" => Some(Value::Null),
+                "exit" => return Ok(()),
+                _ => None,
+            };
+
+            // Notifications have no id, and must not be responded to.
+            let id = &message["id"];
+            if id.is_null() {
+                continue;
+            }
+            let response = match result {
+                Some(result) => json!({ "jsonrpc": "2.0", "id": id, "result": result }),
+                None => json!({
+                    "jsonrpc": "2.0",
+                    "id": id,
+                    "error": {
+                        "code": METHOD_NOT_FOUND,
+                        "message": format!("Unsupported method {method}"),
+                    },
+                }),
+            };
+            write_message(output, &response)?;
+        }
+        Ok(())
+    }
+
+    fn next_message(&mut self, input: &mut impl BufRead) -> io::Result<Option<Value>> {
+        match self.pending_messages.pop_front() {
+            Some(message) => Ok(Some(message)),
+            None => read_message(input),
+        }
+    }
+
+    /// Sends a request to the editor, and waits for its response, which is None if
+    /// input was closed first. Messages that are read in the meantime are handled
+    /// afterwards.
+    fn send_request(
+        &mut self,
+        method: &str,
+        params: Value,
+        input: &mut impl BufRead,
+        output: &mut impl Write,
+    ) -> io::Result<Option<Value>> {
+        self.last_request_id += 1;
+        let id = json!(self.last_request_id);
+        write_message(
+            output,
+            &json!({ "jsonrpc": "2.0", "id": id, "method": method, "params": params }),
+        )?;
+        while let Some(message) = read_message(input)? {
+            if message["id"] == id && message["method"].is_null() {
+                return Ok(Some(message));
+            }
+            self.pending_messages.push_back(message);
+        }
+        Ok(None)
+    }
+
+    /// Asks the editor to show the progress of work named title, if it supports
+    /// it, and returns the token that the progress is reported with.
+    fn begin_progress(
+        &mut self,
+        title: &str,
+        input: &mut impl BufRead,
+        output: &mut impl Write,
+    ) -> io::Result<Option<Value>> {
+        if !self.supports_work_done_progress {
+            return Ok(None);
+        }
+        let token = json!(format!("isograph/{}", self.last_request_id + 1));
+        let response = self.send_request(
+            "window/workDoneProgress/create",
+            json!({ "token": token }),
+            input,
+            output,
+        )?;
+        // Progress can only be reported once the editor has created the token.
+        if !matches!(&response, Some(response) if response["error"].is_null()) {
+            return Ok(None);
+        }
+        report_progress(output, &token, json!({ "kind": "begin", "title": title }))?;
+        Ok(Some(token))
+    }
+
+    /// Rereads and revalidates the project, using the unsaved contents of open
+    /// documents, and publishes its errors. If the project cannot be processed, the
+    /// previous version is kept for hovers and definitions.
+    fn revalidate(&mut self, input: &mut impl BufRead, output: &mut impl Write) -> io::Result<()> {
+        let progress_token = self.begin_progress("Validating the project", input, output)?;
+
+        let result = if let Some(compiler) = &mut self.compiler {
+            compiler.refresh_with_unsaved_files(&self.open_documents)
+        } else {
+            Compiler::with_unsaved_files(self.config, &self.open_documents).map(|compiler| {
+                self.compiler = Some(compiler);
+            })
+        };
+        let errors = match (result, &self.compiler) {
+            (Ok(()), Some(compiler)) => {
+                let mut errors = compiler.errors().to_vec();
+                if let Some(schema) = compiler.schema() {
+                    errors.extend(check_validated_schema(self.config, schema));
+                }
+                errors
+            }
+            (Ok(()), None) => vec![],
+            (Err(error), _) => error.located_messages(),
+        };
+        let message = match errors.len() {
+            0 => "No errors".to_string(),
+            1 => "Found 1 error".to_string(),
+            error_count => format!("Found {error_count} errors"),
+        };
+        self.publish_diagnostics(errors, output)?;
+        if let Some(progress_token) = progress_token {
+            report_progress(
+                output,
+                &progress_token,
+                json!({ "kind": "end", "message": message }),
+            )?;
+        }
+        Ok(())
+    }
+
+    fn publish_diagnostics(
+        &mut self,
+        errors: Vec<WithLocation<String>>,
+        output: &mut impl Write,
+    ) -> io::Result<()> {
+        // Errors that are not located in a file are reported on the config file.
+        let mut diagnostics_by_file: BTreeMap<PathBuf, Vec<(Span, String)>> = BTreeMap::new();
+        for error in errors {
+            let (path, span) = match error.location {
+                Location::Embedded(embedded_location) => (
+                    PathBuf::from(embedded_location.text_source.path.lookup()),
+                    embedded_location.span_in_file(),
+                ),
+                Location::Generated => (self.config.config_file.clone(), Span::new(0, 0)),
+            };
+            diagnostics_by_file
+                .entry(path)
+                .or_default()
+                .push((span, error.item));
+        }
+
+        let fixed_files = self
+            .files_with_diagnostics
+            .iter()
+            .filter(|path| !diagnostics_by_file.contains_key(*path))
+            .cloned()
+            .collect::<Vec<_>>();
+        for path in fixed_files {
+            diagnostics_by_file.insert(path, vec![]);
+        }
+
+        self.files_with_diagnostics.clear();
+        for (path, diagnostics) in diagnostics_by_file {
+            let diagnostics = {
+                let content = self.document_content(&path).unwrap_or_default();
+                diagnostics
+                    .into_iter()
+                    .map(|(span, message)| {
+                        json!({
+                            "range": span_to_range(&content, span),
+                            "severity": DIAGNOSTIC_SEVERITY_ERROR,
+                            "source": "isograph",
+                            "message": message,
+                        })
+                    })
+                    .collect::<Vec<_>>()
+            };
+            if !diagnostics.is_empty() {
+                self.files_with_diagnostics.insert(path.clone());
+            }
+            write_message(
+                output,
+                &json!({
+                    "jsonrpc": "2.0",
+                    "method": "textDocument/publishDiagnostics",
+                    "params": { "uri": path_to_uri(&path), "diagnostics": diagnostics },
+                }),
+            )?;
+        }
+        Ok(())
+    }
+
+    fn hover(&self, params: &Value) -> Option<Value> {
+        let (schema, entity) = self.entity_at_position(params)?;
+        let (signature, description) = match entity {
+            Entity::ClientFieldDeclaration(client_field_id)
+            | Entity::FieldSelection {
+                field: FieldDefinitionLocation::Client(client_field_id),
+                ..
+            } => {
+                let client_field = schema.resolver(client_field_id);
+                (
+                    format!(
+                        "{}.{}{} # client field ({})",
+                        client_field.type_and_field.type_name,
+                        client_field.name,
+                        write_variables_to_string(schema, client_field.variable_definitions.iter()),
+                        client_field.variant
+                    ),
+                    client_field.description,
+                )
+            }
+            Entity::ServerFieldDefinition(server_field_id)
+            | Entity::FieldSelection {
+                field: FieldDefinitionLocation::Server(server_field_id),
+                ..
+            } => {
+                let server_field = schema.field(server_field_id);
+                (
+                    format!(
+                        "{}.{}",
+                        schema.schema_data.object(server_field.parent_type_id).name,
+                        print_server_field(schema, server_field)
+                    ),
+                    server_field.description,
+                )
+            }
+            Entity::SchemaType(SelectableFieldId::Object(object_id)) => {
+                let object = schema.schema_data.object(object_id);
+                (
+                    format!(
+                        "{} {}",
+                        if object.is_input_object {
+                            "input"
+                        } else {
+                            "type"
+                        },
+                        object.name
+                    ),
+                    object.description,
+                )
+            }
+            Entity::SchemaType(SelectableFieldId::Scalar(scalar_id)) => {
+                let scalar = schema.schema_data.scalar(scalar_id);
+                (
+                    format!(
+                        "{} {}",
+                        if scalar.enum_values.is_some() {
+                            "enum"
+                        } else {
+                            "scalar"
+                        },
+                        scalar.name.item
+                    ),
+                    scalar.description.map(|description| description.item),
+                )
+            }
+        };
+
+        let mut contents = format!("```graphql\n{signature}\n```");
+        if let Some(description) = description {
+            contents.push_str(&format!("\n\n{}", description.lookup()));
+        }
+        Some(json!({ "contents": { "kind": "markdown", "value": contents } }))
+    }
+
+    fn definition(&self, params: &Value) -> Option<Value> {
+        let (schema, entity) = self.entity_at_position(params)?;
+        let location = match entity {
+            Entity::ClientFieldDeclaration(client_field_id)
+            | Entity::FieldSelection {
+                field: FieldDefinitionLocation::Client(client_field_id),
+                ..
+            } => schema.resolver(client_field_id).name_location,
+            Entity::ServerFieldDefinition(server_field_id)
+            | Entity::FieldSelection {
+                field: FieldDefinitionLocation::Server(server_field_id),
+                ..
+            } => schema.field(server_field_id).name.location,
+            Entity::SchemaType(SelectableFieldId::Object(object_id)) => {
+                schema.schema_data.object(object_id).name_location
+            }
+            Entity::SchemaType(SelectableFieldId::Scalar(scalar_id)) => {
+                schema.schema_data.scalar(scalar_id).name.location
+            }
+        };
+
+        match location {
+            Location::Embedded(embedded_location) => {
+                let path = PathBuf::from(embedded_location.text_source.path.lookup());
+                let content = self.document_content(&path)?;
+                Some(json!({
+                    "uri": path_to_uri(&path),
+                    "range": span_to_range(&content, embedded_location.span_in_file()),
+                }))
+            }
+            // e.g. __typename, or fields generated for refinements
+            Location::Generated => None,
+        }
+    }
+
+    /// The entity at the position in the text document of params, which are the
+    /// params of a hover or definition request. Positions are looked up in the
+    /// unsaved contents of the document, which is what the project was validated
+    /// against.
+    fn entity_at_position(&self, params: &Value) -> Option<(&ValidatedSchema, Entity)> {
+        let compiler = self.compiler.as_ref()?;
+        let path = document_path(params, self.config.options.path_normalization)?;
+        let content = self.document_content(&path)?;
+        let offset = position_to_offset(
+            &content,
+            params["position"]["line"].as_u64()?,
+            params["position"]["character"].as_u64()?,
+        );
+        let entity = compiler.lookup(&path, offset)?;
+        Some((compiler.schema()?, entity))
+    }
+
+    /// The unsaved contents of the file, if it is open in the editor, and otherwise
+    /// its contents on disk.
+    fn document_content(&self, path: &Path) -> Option<Cow<'_, str>> {
+        match self.open_documents.get(path) {
+            Some(content) => Some(Cow::Borrowed(content)),
+            None => fs::read_to_string(path).ok().map(Cow::Owned),
+        }
+    }
+
+    fn update_open_document(&mut self, params: &Value) {
+        let Some(path) = document_path(params, self.config.options.path_normalization) else {
+            return;
+        };
+        // didOpen contains the text of the document, and didChange (since changes are
+        // synced in full) contains it in its last change.
+        let text = params["textDocument"]["text"]
+            .as_str()
+            .or_else(|| params["contentChanges"].as_array()?.last()?["text"].as_str());
+        if let Some(text) = text {
+            self.open_documents.insert(path, text.to_string());
+        }
+    }
+
+    /// The fields that can be selected at the position in the text document of
+    /// params, if it is in a selection set in an iso literal. The selection set is
+    /// found in the unsaved contents of the document, but its fields are those of
+    /// the last version of the project that could be validated.
+    fn completion(&self, params: &Value) -> Option<Value> {
+        let schema = self.compiler.as_ref()?.schema()?;
+        let path = document_path(params, self.config.options.path_normalization)?;
+        let content = self.document_content(&path)?;
+        let content = &*content;
+        let offset = position_to_offset(
+            content,
+            params["position"]["line"].as_u64()?,
+            params["position"]["character"].as_u64()?,
+        ) as usize;
+
+        let iso_literal_extraction =
+            self.extract_iso_literals(&path, content)
+                .into_iter()
+                .find(|extraction| {
+                    extraction.iso_literal_start_index <= offset
+                        && offset
+                            <= extraction.iso_literal_start_index
+                                + extraction.iso_literal_text.len()
+                })?;
+        let context = selection_completion_context(
+            iso_literal_extraction.iso_literal_text,
+            offset - iso_literal_extraction.iso_literal_start_index,
+        )?;
+
+        let parent_type_name: UnvalidatedTypeName = context.parent_type_name.intern().into();
+        let mut object_id = match schema.schema_data.defined_types.get(&parent_type_name)? {
+            SelectableFieldId::Object(object_id) => *object_id,
+            SelectableFieldId::Scalar(_) => return None,
+        };
+        for linked_field_name in context.linked_field_names {
+            object_id = linked_field_target(schema, object_id, linked_field_name.intern().into())?;
+        }
+
+        let mut completion_items = schema
+            .schema_data
+            .object(object_id)
+            .encountered_fields
+            .iter()
+            .filter_map(|(field_name, field)| {
+                let (kind, detail, description, is_deprecated) = match field {
+                    FieldDefinitionLocation::Server(server_field_id) => {
+                        let server_field = schema.field(*server_field_id);
+                        if server_field.is_internal {
+                            return None;
+                        }
+                        (
+                            COMPLETION_ITEM_KIND_FIELD,
+                            print_server_field(schema, server_field),
+                            server_field.description,
+                            server_field.deprecation_reason.is_some(),
+                        )
+                    }
+                    FieldDefinitionLocation::Client(client_field_id) => {
+                        let client_field = schema.resolver(*client_field_id);
+                        (
+                            COMPLETION_ITEM_KIND_METHOD,
+                            format!(
+                                "{}{} # client field ({})",
+                                client_field.name,
+                                write_variables_to_string(
+                                    schema,
+                                    client_field.variable_definitions.iter()
+                                ),
+                                client_field.variant
+                            ),
+                            client_field.description,
+                            false,
+                        )
+                    }
+                };
+                let mut completion_item = json!({
+                    "label": field_name.lookup(),
+                    "kind": kind,
+                    "detail": detail,
+                    "deprecated": is_deprecated,
+                });
+                if let Some(description) = description {
+                    completion_item["documentation"] =
+                        json!({ "kind": "markdown", "value": description.lookup() });
+                }
+                Some((field_name.lookup(), completion_item))
+            })
+            .collect::<Vec<_>>();
+        completion_items.sort_by_key(|(label, _)| *label);
+        Some(Value::Array(
+            completion_items
+                .into_iter()
+                .map(|(_, completion_item)| completion_item)
+                .collect(),
+        ))
+    }
+
+    fn extract_iso_literals<'a>(
+        &self,
+        path: &Path,
+        content: &'a str,
+    ) -> Vec<IsoLiteralExtraction<'a>> {
+        extract_iso_literals_from_file(path, content, &self.config.single_file_component_extensions)
+    }
+
+    /// Formats the iso literals in the unsaved contents of the text document of
+    /// params. The formatted document replaces the whole document in a single edit,
+    /// unless it is already formatted, in which case there are no edits.
+    fn formatting(&self, params: &Value) -> Option<Value> {
+        let path = document_path(params, self.config.options.path_normalization)?;
+        let content = self.document_content(&path)?;
+        let formatted_content = format_iso_literals_in_file_content(&content);
+        if formatted_content == *content {
+            return Some(json!([]));
+        }
+        Some(json!([{
+            "range": span_to_range(&content, Span::new(0, content.len() as u32)),
+            "newText": formatted_content,
+        }]))
+    }
+
+    /// The client fields whose names fuzzily match the query of params, best matches
+    /// first. Client fields are read from the unsaved contents of open documents, so
+    /// this works even if the project cannot be validated.
+    fn workspace_symbols(&self, params: &Value) -> Option<Value> {
+        let query = params["query"].as_str().unwrap_or_default();
+        let symbols =
+            find_client_field_symbols_with_unsaved_files(self.config, query, &self.open_documents)
+                .ok()?;
+        Some(Value::Array(
+            symbols
+                .into_iter()
+                .filter_map(|symbol| {
+                    let Location::Embedded(embedded_location) = symbol.location else {
+                        return None;
+                    };
+                    let path = PathBuf::from(embedded_location.text_source.path.lookup());
+                    let content = self.document_content(&path)?;
+                    Some(json!({
+                        "name": symbol.key.to_string(),
+                        "kind": SYMBOL_KIND_METHOD,
+                        "location": {
+                            "uri": path_to_uri(&path),
+                            "range": span_to_range(&content, embedded_location.span_in_file()),
+                        },
+                    }))
+                })
+                .collect(),
+        ))
+    }
+
+    /// Classifies the tokens in the iso literals in the unsaved contents of the text
+    /// document of params, for syntax highlighting.
+    fn semantic_tokens_full(&self, params: &Value) -> Option<Value> {
+        let path = document_path(params, self.config.options.path_normalization)?;
+        let content = self.document_content(&path)?;
+        let tokens = self
+            .extract_iso_literals(&path, &content)
+            .into_iter()
+            .flat_map(|extraction| {
+                let start_index = extraction.iso_literal_start_index as u32;
+                semantic_tokens(extraction.iso_literal_text)
+                    .into_iter()
+                    .map(move |token| {
+                        WithSpan::new(token.item, token.span.with_offset(start_index))
+                    })
+            })
+            .collect::<Vec<_>>();
+        Some(json!({ "data": encode_semantic_tokens(&content, tokens) }))
+    }
+}
+
+/// The index of the type of tokens of semantic_token_kind in SEMANTIC_TOKEN_TYPES.
+fn semantic_token_type(semantic_token_kind: SemanticTokenKind) -> u32 {
+    match semantic_token_kind {
+        SemanticTokenKind::Keyword => 0,
+        SemanticTokenKind::TypeName => 1,
+        SemanticTokenKind::Field => 2,
+        SemanticTokenKind::Argument => 3,
+        SemanticTokenKind::Variable => 4,
+        SemanticTokenKind::Directive => 5,
+        SemanticTokenKind::Number => 6,
+        SemanticTokenKind::String => 7,
+    }
+}
+
+/// Encodes tokens as five integers each: the line of the token relative to the
+/// previous token, its start character (relative to the previous token, if that
+/// is on the same line), its length, its type and its modifiers. Tokens cannot
+/// span multiple lines, so those that do (i.e. block strings) are split into one
+/// token per line.
+fn encode_semantic_tokens(content: &str, mut tokens: Vec<WithSpan<SemanticTokenKind>>) -> Vec<u32> {
+    tokens.sort_by_key(|token| token.span.start);
+    let mut data = vec![];
+    let mut previous_line = 0;
+    let mut previous_character = 0;
+    for token in tokens {
+        let (start, end) = token.span.as_usize();
+        let Some(token_text) = content.get(start..end) else {
+            continue;
+        };
+        let (mut line, mut character) = offset_to_line_and_character(content, start);
+        for (line_index, line_text) in token_text.split('\n').enumerate() {
+            if line_index > 0 {
+                line += 1;
+                character = 0;
+            }
+            let length = line_text.encode_utf16().count();
+            if length == 0 {
+                continue;
+            }
+            let delta_line = line - previous_line;
+            let delta_character = if delta_line == 0 {
+                character - previous_character
+            } else {
+                character
+            };
+            data.extend([
+                delta_line as u32,
+                delta_character as u32,
+                length as u32,
+                semantic_token_type(token.item),
+                0,
+            ]);
+            previous_line = line;
+            previous_character = character;
+        }
+    }
+    data
+}
+
+/// The type of the object that the linked field selects, if it is a server field
+/// whose type is an object.
+fn linked_field_target(
+    schema: &ValidatedSchema,
+    parent_object_id: ObjectId,
+    linked_field_name: SelectableFieldName,
+) -> Option<ObjectId> {
+    match schema
+        .schema_data
+        .object(parent_object_id)
+        .encountered_fields
+        .get(&linked_field_name)?
+    {
+        FieldDefinitionLocation::Server(server_field_id) => {
+            match schema.field(*server_field_id).associated_data.inner() {
+                SelectableFieldId::Object(object_id) => Some(*object_id),
+                SelectableFieldId::Scalar(_) => None,
+            }
+        }
+        FieldDefinitionLocation::Client(_) => None,
+    }
+}
+
+/// The path of the text document of params, normalized in the same way as the paths
+/// in the config, since the compiler reads files by their normalized path.
+fn document_path(params: &Value, path_normalization: PathNormalization) -> Option<PathBuf> {
+    let path = uri_to_path(params["textDocument"]["uri"].as_str()?)?;
+    Some(normalize_path(&path, path_normalization).unwrap_or(path))
+}
+
+/// The errors that batch compilation reports after validation, other than those
+/// reported by custom lint rules, which only tools that embed the compiler have.
+fn check_validated_schema(
+    config: &CompilerConfig,
+    schema: &ValidatedSchema,
+) -> Vec<WithLocation<String>> {
+    let mut errors = vec![];
+    if let Err(lint_rule_errors) = check_lint_rules(schema, &[], &config.lint_rules) {
+        errors.extend(BatchCompileError::from(lint_rule_errors).located_messages());
+    }
+    if let Err(naming_convention_errors) =
+        check_client_field_naming_conventions(schema, &config.client_field_naming)
+    {
+        errors.extend(BatchCompileError::from(naming_convention_errors).located_messages());
+    }
+    if let Err(forbidden_server_field_errors) =
+        check_forbidden_server_fields(schema, &config.forbidden_server_fields)
+    {
+        errors.extend(BatchCompileError::from(forbidden_server_field_errors).located_messages());
+    }
+    errors
+}
+
+/// Reads a message, i.e. a Content-Length header, followed by a JSON body. Returns
+/// None if input was closed.
+fn read_message(input: &mut impl BufRead) -> io::Result<Option<Value>> {
+    let mut content_length = None;
+    loop {
+        let mut header = String::new();
+        if input.read_line(&mut header)? == 0 {
+            return Ok(None);
+        }
+        let header = header.trim_end();
+        if header.is_empty() {
+            break;
+        }
+        if let Some((name, value)) = header.split_once(':') {
+            if name.eq_ignore_ascii_case("Content-Length") {
+                content_length = value.trim().parse::<usize>().ok();
+            }
+        }
+    }
+
+    let content_length = content_length.ok_or_else(|| {
+        io::Error::new(
+            io::ErrorKind::InvalidData,
+            "Expected a message to have a Content-Length header",
+        )
+    })?;
+    let mut content = vec![0; content_length];
+    input.read_exact(&mut content)?;
+    serde_json::from_slice(&content)
+        .map(Some)
+        .map_err(|error| io::Error::new(io::ErrorKind::InvalidData, error))
+}
+
+/// https://microsoft.github.io/language-server-protocol/specifications/lsp/3.17/specification/#progress
+fn report_progress(output: &mut impl Write, token: &Value, value: Value) -> io::Result<()> {
+    write_message(
+        output,
+        &json!({
+            "jsonrpc": "2.0",
+            "method": "$/progress",
+            "params": { "token": token, "value": value },
+        }),
+    )
+}
+
+fn write_message(output: &mut impl Write, message: &Value) -> io::Result<()> {
+    let content = message.to_string();
+    write!(
+        output,
+        "Content-Length: {}\r\n\r\n{}",
+        content.len(),
+        content
+    )?;
+    output.flush()
+}
+
+fn span_to_range(content: &str, span: Span) -> Value {
+    json!({
+        "start": offset_to_position(content, span.start),
+        "end": offset_to_position(content, span.end),
+    })
+}
+
+fn offset_to_position(content: &str, offset: u32) -> Value {
+    let (line, character) = offset_to_line_and_character(content, offset as usize);
+    json!({ "line": line, "character": character })
+}
+
+/// Positions are zero-based lines and (by default) UTF-16 code units within a line,
+/// whereas spans are byte offsets.
+fn offset_to_line_and_character(content: &str, offset: usize) -> (usize, usize) {
+    let mut offset = offset.min(content.len());
+    while !content.is_char_boundary(offset) {
+        offset -= 1;
+    }
+    let before = &content[..offset];
+    let line_start = before.rfind('\n').map(|index| index + 1).unwrap_or(0);
+    (
+        before.matches('\n').count(),
+        before[line_start..].encode_utf16().count(),
+    )
+}
+
+fn position_to_offset(content: &str, line: u64, character: u64) -> u32 {
+    let mut line_start = 0;
+    for (line_index, line_content) in content.split('\n').enumerate() {
+        if line_index as u64 == line {
+            let mut utf16_count = 0;
+            for (byte_index, c) in line_content.char_indices() {
+                if utf16_count >= character {
+                    return (line_start + byte_index) as u32;
+                }
+                utf16_count += c.len_utf16() as u64;
+            }
+            return (line_start + line_content.len()) as u32;
+        }
+        line_start += line_content.len() + 1;
+    }
+    content.len() as u32
+}
+
+fn path_to_uri(path: &Path) -> String {
+    let mut uri = "file://".to_string();
+    for byte in path.to_string_lossy().bytes() {
+        if byte.is_ascii_alphanumeric() || b"/-._~".contains(&byte) {
+            uri.push(byte as char);
+        } else {
+            uri.push_str(&format!("%{:02X}", byte));
+        }
+    }
+    uri
+}
+
+fn uri_to_path(uri: &str) -> Option<PathBuf> {
+    let mut bytes = uri.strip_prefix("file://")?.bytes();
+    let mut decoded = vec![];
+    while let Some(byte) = bytes.next() {
+        if byte == b'%' {
+            let hex = [bytes.next()?, bytes.next()?];
+            decoded.push(u8::from_str_radix(std::str::from_utf8(&hex).ok()?, 16).ok()?);
+        } else {
+            decoded.push(byte);
+        }
+    }
+    Some(PathBuf::from(String::from_utf8(decoded).ok()?))
+}
@@ -0,0 +1,57 @@
+mod artifact_file_contents;
+mod batch_compile;
+mod build_report;
+mod compilation_cache;
+mod compiler;
+mod generate_artifacts;
+mod isograph_literals;
+mod json_artifact_file_contents;
+mod language_server;
+mod memory_usage;
+mod opt;
+mod post_emit_command;
+mod print_merged;
+mod print_schema;
+mod progress;
+mod project_index;
+mod pull_schema;
+mod schema;
+mod schema_compatibility;
+mod schema_stitching;
+mod schema_version;
+mod unreachable_artifacts;
+mod watch;
+mod workspace_symbols;
+mod write_artifacts;
+
+// Entry points that return their results without printing them, for tools that
+// embed the compiler
+pub use batch_compile::{
+    compile_projects, handle_compile_command, BatchCompileError, CompilationStats, WithDuration,
+};
+pub use compilation_cache::compile_with_cache;
+pub use compiler::{ClientFieldKey, Compiler, SchemaReplacementDelta};
+pub use generate_artifacts::{
+    generate_and_write_artifacts, GenerateArtifactsError, GeneratedArtifactsStats,
+    OperationComplexityReport, OversizedArtifactReport,
+};
+pub use isograph_literals::format_iso_literals_in_file_content;
+pub use language_server::serve_language_client;
+pub use memory_usage::MemoryUsage;
+pub use project_index::{IndexedClientField, ProjectIndex, ServerFieldKey};
+pub use schema_version::check_schema_version;
+pub use unreachable_artifacts::find_unreachable_artifacts;
+pub use workspace_symbols::{find_client_field_symbols, ClientFieldSymbol};
+
+// Entry points that print their results, for the command line
+pub use batch_compile::{compile_and_print, report_orphaned_artifacts_and_print};
+pub use compilation_cache::compile_with_cache_and_print;
+pub use language_server::run_language_server;
+pub use opt::CliOptions;
+pub use print_merged::print_merged_selection_set_and_print;
+pub use print_schema::print_schema_and_print;
+pub use pull_schema::pull_schema_and_print;
+pub use schema_compatibility::check_schema_compatibility_and_print;
+pub use schema_version::check_schema_version_and_print;
+pub use unreachable_artifacts::report_unreachable_artifacts_and_print;
+pub use watch::handle_watch_command;
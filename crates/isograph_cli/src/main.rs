@@ -1,26 +1,62 @@
-mod artifact_file_contents;
-mod batch_compile;
-mod generate_artifacts;
-mod isograph_literals;
-mod opt;
-mod schema;
-mod watch;
-mod write_artifacts;
-
-use batch_compile::compile_and_print;
 use colored::Colorize;
-use isograph_config::create_config;
-use opt::CliOptions;
+use isograph_cli::{
+    check_schema_compatibility_and_print, check_schema_version_and_print, compile_and_print,
+    compile_with_cache_and_print, handle_watch_command, print_merged_selection_set_and_print,
+    print_schema_and_print, pull_schema_and_print, report_orphaned_artifacts_and_print,
+    report_unreachable_artifacts_and_print, run_language_server, CliOptions,
+};
+use isograph_config::{apply_config_overrides, create_config, find_config_file, CONFIG_FILE_NAME};
 use structopt::StructOpt;
-use watch::handle_watch_command;
 
 #[tokio::main]
 async fn main() {
     let opt = CliOptions::from_args();
-    let config = create_config(opt.config.unwrap_or("./isograph.config.json".into()));
+    let config_location = opt
+        .config
+        .clone()
+        .or_else(|| {
+            std::env::current_dir()
+                .ok()
+                .and_then(|current_dir| find_config_file(&current_dir))
+        })
+        .unwrap_or(CONFIG_FILE_NAME.into());
+    let mut config = create_config(config_location);
+    apply_config_overrides(&mut config, opt.config_overrides());
 
-    if opt.watch {
-        match handle_watch_command(config).await {
+    if opt.report_orphans {
+        if let Err(_) = report_orphaned_artifacts_and_print(&config) {
+            std::process::exit(1);
+        }
+    } else if opt.report_unreachable {
+        if let Err(_) = report_unreachable_artifacts_and_print(&config) {
+            std::process::exit(1);
+        }
+    } else if let Some(type_and_field) = opt.print_merged {
+        if let Err(_) = print_merged_selection_set_and_print(&config, &type_and_field) {
+            std::process::exit(1);
+        }
+    } else if opt.print_schema {
+        if let Err(_) = print_schema_and_print(&config) {
+            std::process::exit(1);
+        }
+    } else if opt.check_schema_version {
+        if let Err(_) = check_schema_version_and_print(&config) {
+            std::process::exit(1);
+        }
+    } else if let Some(proposed_schema) = &opt.against {
+        if let Err(_) = check_schema_compatibility_and_print(&config, proposed_schema) {
+            std::process::exit(1);
+        }
+    } else if let Some(endpoint) = &opt.pull_schema {
+        if let Err(_) = pull_schema_and_print(&config, endpoint, &opt.headers) {
+            std::process::exit(1);
+        }
+    } else if opt.lsp {
+        if let Err(_) = run_language_server(&config) {
+            std::process::exit(1);
+        }
+    } else if opt.watch {
+        match handle_watch_command(config, vec![]).await {
             Ok(res) => match res {
                 Ok(_) => {
                     eprintln!("{}", "Successfully watched. Exiting.\n".bright_green())
@@ -43,8 +79,12 @@ async fn main() {
                 std::process::exit(1);
             }
         };
+    } else if opt.no_cache {
+        if let Err(_) = compile_and_print(&config, &[]) {
+            std::process::exit(1);
+        }
     } else {
-        if let Err(_) = compile_and_print(&config) {
+        if let Err(_) = compile_with_cache_and_print(&config) {
             std::process::exit(1);
         }
     }
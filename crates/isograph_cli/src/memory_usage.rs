@@ -0,0 +1,123 @@
+use std::{collections::HashMap, mem::size_of};
+
+use common_lang_types::WithSpan;
+use isograph_lang_types::{Selection, ServerFieldSelection};
+use isograph_schema::{ValidatedSchema, ValidatedSelection};
+
+/// Approximate memory usage of a compilation, for diagnosing compilations that
+/// run out of memory (e.g. in constrained CI containers).
+#[derive(Debug, Clone, Copy, Default)]
+pub struct MemoryUsage {
+    /// The peak resident set size of the process. This is only available on
+    /// Linux, and includes everything the process did before compiling, e.g.
+    /// previous compilations in watch mode.
+    pub peak_resident_bytes: Option<usize>,
+    /// An estimate of the size of the validated schema, including the selection
+    /// sets of client fields. Interned strings are not included.
+    pub schema_bytes: usize,
+    /// The total size of the contents of the generated artifacts
+    pub artifact_bytes: usize,
+}
+
+impl MemoryUsage {
+    /// Whether the peak resident set size exceeds the budget. If the peak
+    /// resident set size is not available, the budget is never exceeded.
+    pub fn exceeds_budget(&self, memory_budget_megabytes: usize) -> bool {
+        match self.peak_resident_bytes {
+            Some(peak_resident_bytes) => peak_resident_bytes > memory_budget_megabytes * MEGABYTE,
+            None => false,
+        }
+    }
+}
+
+pub(crate) static MEGABYTE: usize = 1024 * 1024;
+
+pub(crate) fn format_megabytes(bytes: usize) -> String {
+    format!("{:.1} MB", bytes as f64 / MEGABYTE as f64)
+}
+
+/// Reads the peak resident set size (VmHWM) of the current process from
+/// /proc/self/status.
+pub(crate) fn peak_resident_bytes() -> Option<usize> {
+    let status = std::fs::read_to_string("/proc/self/status").ok()?;
+    let line = status.lines().find(|line| line.starts_with("VmHWM:"))?;
+    // e.g. "VmHWM:\t  123456 kB"
+    let kilobytes = line
+        .trim_start_matches("VmHWM:")
+        .trim()
+        .trim_end_matches("kB")
+        .trim()
+        .parse::<usize>()
+        .ok()?;
+    Some(kilobytes * 1024)
+}
+
+/// Estimates the heap size of the schema from the capacity of its collections.
+/// Heap allocations of individual items other than selection sets (e.g.
+/// descriptions, arguments and directives) are not counted.
+pub(crate) fn estimate_schema_size(schema: &ValidatedSchema) -> usize {
+    let server_fields_size = schema.server_fields.capacity() * size_of_item(&schema.server_fields);
+    let client_fields_size = schema.client_fields.capacity() * size_of_item(&schema.client_fields)
+        + schema
+            .client_fields
+            .iter()
+            .map(
+                |client_field| match &client_field.selection_set_and_unwraps {
+                    Some((selection_set, _)) => estimate_selection_set_size(selection_set),
+                    None => 0,
+                },
+            )
+            .sum::<usize>();
+    let entrypoints_size = schema.entrypoints.capacity() * size_of_item(&schema.entrypoints);
+    let objects_size = schema.schema_data.objects.capacity()
+        * size_of_item(&schema.schema_data.objects)
+        + schema
+            .schema_data
+            .objects
+            .iter()
+            .map(|object| {
+                object.server_fields.capacity() * size_of_item(&object.server_fields)
+                    + object.resolvers.capacity() * size_of_item(&object.resolvers)
+                    + object.encountered_fields.capacity()
+                        * (size_of_key(&object.encountered_fields)
+                            + size_of_value(&object.encountered_fields))
+            })
+            .sum::<usize>();
+    let scalars_size =
+        schema.schema_data.scalars.capacity() * size_of_item(&schema.schema_data.scalars);
+    let defined_types_size = schema.schema_data.defined_types.capacity()
+        * (size_of_key(&schema.schema_data.defined_types)
+            + size_of_value(&schema.schema_data.defined_types));
+
+    server_fields_size
+        + client_fields_size
+        + entrypoints_size
+        + objects_size
+        + scalars_size
+        + defined_types_size
+}
+
+fn estimate_selection_set_size(selection_set: &Vec<WithSpan<ValidatedSelection>>) -> usize {
+    selection_set.capacity() * size_of_item(selection_set)
+        + selection_set
+            .iter()
+            .map(|selection| match &selection.item {
+                Selection::ServerField(ServerFieldSelection::ScalarField(_)) => 0,
+                Selection::ServerField(ServerFieldSelection::LinkedField(linked_field)) => {
+                    estimate_selection_set_size(&linked_field.selection_set)
+                }
+            })
+            .sum::<usize>()
+}
+
+fn size_of_item<T>(_: &Vec<T>) -> usize {
+    size_of::<T>()
+}
+
+fn size_of_key<K, V>(_: &HashMap<K, V>) -> usize {
+    size_of::<K>()
+}
+
+fn size_of_value<K, V>(_: &HashMap<K, V>) -> usize {
+    size_of::<V>()
+}
@@ -1,15 +1,99 @@
 use std::path::PathBuf;
 
+use isograph_config::ConfigOverrides;
 use structopt::StructOpt;
 
 /// Options if we're doing a batch compilation
 #[derive(Debug, StructOpt)]
-pub(crate) struct CliOptions {
+pub struct CliOptions {
     #[structopt(long)]
     pub watch: bool,
 
-    /// Compile using this config file. If not provided, searches for a config in
-    /// package.json under the `isograph` key.
+    /// Compile even if nothing changed since the last compilation, and do not read
+    /// or write the compilation cache.
+    #[structopt(long)]
+    pub no_cache: bool,
+
+    /// Run a language server, which communicates with the editor over stdin and
+    /// stdout, and publishes errors, shows hovers and goes to definitions.
+    #[structopt(long)]
+    pub lsp: bool,
+
+    /// List the files in the artifact directory that no longer correspond to
+    /// any client field or entrypoint, without writing or deleting anything.
+    #[structopt(long)]
+    pub report_orphans: bool,
+
+    /// List the artifacts that cannot be reached from the entrypoints used by the
+    /// app (the used_entrypoints in the config, or every entrypoint), so that they
+    /// can be pruned from the build, without writing or deleting anything.
+    #[structopt(long)]
+    pub report_unreachable: bool,
+
+    /// Print the merged selection set of an entrypoint (e.g. `Query.HomeRoute`),
+    /// annotated with the client fields that selected each field, without writing
+    /// anything. Useful for debugging why a field appears in a query.
+    #[structopt(long, value_name = "Type.field")]
+    pub print_merged: Option<String>,
+
+    /// Print the schema after schema extensions and client fields have been merged
+    /// into it, i.e. every field that can be selected in iso literals, without
+    /// writing anything.
+    #[structopt(long)]
+    pub print_schema: bool,
+
+    /// Check that the artifacts in the artifact directory were generated from the
+    /// current version of the schema, without compiling, and fail otherwise.
+    #[structopt(long)]
+    pub check_schema_version: bool,
+
+    /// Validate every client field and entrypoint against this schema (e.g. a
+    /// proposed change to the schema) instead of the schema in the config, and
+    /// list the ones that would break, without writing anything.
+    #[structopt(long, value_name = "schema")]
+    pub against: Option<PathBuf>,
+
+    /// Send the introspection query to this GraphQL endpoint, and write the
+    /// schema to the path of the schema in the config (as SDL, or as is if the
+    /// path ends with `.json`), without compiling.
+    #[structopt(long, value_name = "url")]
+    pub pull_schema: Option<String>,
+
+    /// A header to send with the introspection query when pulling the schema, e.g.
+    /// `Authorization: Bearer <token>`. Can be passed several times.
+    #[structopt(long = "header")]
+    pub headers: Vec<String>,
+
+    /// Compile using this config file. If not provided, searches for an
+    /// isograph.config.json in the current directory and its ancestors.
     #[structopt(long)]
     pub config: Option<PathBuf>,
+
+    /// Overrides the project_root in the config file
+    #[structopt(long)]
+    pub project_root: Option<PathBuf>,
+
+    /// Overrides the artifact_directory in the config file
+    #[structopt(long)]
+    pub artifact_directory: Option<PathBuf>,
+
+    /// Overrides the schema in the config file
+    #[structopt(long)]
+    pub schema: Option<PathBuf>,
+
+    /// Overrides the schema_extensions in the config file. Can be passed several
+    /// times.
+    #[structopt(long = "schema-extension")]
+    pub schema_extensions: Vec<PathBuf>,
+}
+
+impl CliOptions {
+    pub fn config_overrides(&self) -> ConfigOverrides {
+        ConfigOverrides {
+            project_root: self.project_root.clone(),
+            artifact_directory: self.artifact_directory.clone(),
+            schema: self.schema.clone(),
+            schema_extensions: self.schema_extensions.clone(),
+        }
+    }
 }
@@ -0,0 +1,218 @@
+use std::{
+    collections::BTreeMap,
+    fs,
+    path::{Path, PathBuf},
+    process::Command,
+};
+
+use isograph_config::{CompilerConfig, PostEmitCommand};
+
+use crate::{batch_compile::BatchCompileError, generate_artifacts::content_hash};
+
+/// The post emit manifest is written next to the config file. It contains absolute
+/// paths, so it should not be checked in.
+pub(crate) static POST_EMIT_MANIFEST_FILE_NAME: &str = ".isograph_post_emit";
+
+/// The maximum total length of the program and arguments of a single run of the
+/// post_emit_command. Command lines are limited to 32,767 characters on Windows
+/// (and by ARG_MAX elsewhere), so if many artifacts were written, the command is
+/// run several times, with a batch of paths each time.
+static MAX_COMMAND_LINE_LENGTH: usize = 30_000;
+
+/// Runs the post_emit_command from the config, with the absolute paths of the
+/// artifacts that were written appended to its arguments, and waits for it to
+/// finish. Nothing is run if no artifacts were written. If the paths do not fit on
+/// a single command line, the command is run once per batch of paths.
+///
+/// Its output is not captured, so that e.g. the warnings of a linter are shown.
+pub(crate) fn run_post_emit_command(
+    post_emit_command: &PostEmitCommand,
+    written_artifact_paths: &[PathBuf],
+) -> Result<(), BatchCompileError> {
+    // Each argument is followed by a space
+    let command_length = std::iter::once(&post_emit_command.program)
+        .chain(post_emit_command.arguments.iter())
+        .map(|argument| argument.len() + 1)
+        .sum::<usize>();
+
+    let mut batch = vec![];
+    let mut batch_length = command_length;
+    for path in written_artifact_paths {
+        let path_length = path.as_os_str().len() + 1;
+        // Each run receives at least one path, even if it does not fit, so that the
+        // error is reported by the operating system.
+        if !batch.is_empty() && batch_length + path_length > MAX_COMMAND_LINE_LENGTH {
+            run_post_emit_command_with_paths(post_emit_command, &batch)?;
+            batch.clear();
+            batch_length = command_length;
+        }
+        batch.push(path);
+        batch_length += path_length;
+    }
+    if !batch.is_empty() {
+        run_post_emit_command_with_paths(post_emit_command, &batch)?;
+    }
+    Ok(())
+}
+
+fn run_post_emit_command_with_paths(
+    post_emit_command: &PostEmitCommand,
+    paths: &[&PathBuf],
+) -> Result<(), BatchCompileError> {
+    let status = Command::new(&post_emit_command.program)
+        .args(&post_emit_command.arguments)
+        .args(paths)
+        .current_dir(&post_emit_command.working_directory)
+        .status()
+        .map_err(|message| BatchCompileError::UnableToRunPostEmitCommand {
+            program: post_emit_command.program.clone(),
+            message,
+        })?;
+
+    if status.success() {
+        Ok(())
+    } else {
+        Err(BatchCompileError::PostEmitCommandFailed {
+            program: post_emit_command.program.clone(),
+            status,
+        })
+    }
+}
+
+/// The post_emit_command rewrites artifacts (e.g. by formatting them), so the files
+/// in the artifact directory cannot be compared with the generated contents to
+/// determine whether an artifact changed. Instead, this records, for each artifact
+/// (by absolute path), the hash of the contents that the compiler generated, and
+/// the hash of the file that the post_emit_command left behind.
+///
+/// An artifact is unchanged if the compiler generates the same contents as last
+/// time, and the file was not modified since the post_emit_command ran.
+#[derive(Debug, Default, PartialEq, Eq)]
+pub(crate) struct PostEmitManifest {
+    artifacts: BTreeMap<PathBuf, EmittedArtifact>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct EmittedArtifact {
+    generated_hash: String,
+    /// None if the post_emit_command has not run since the artifact was written
+    emitted_hash: Option<String>,
+}
+
+impl PostEmitManifest {
+    /// A missing or unreadable manifest (e.g. one written in a different format) is
+    /// treated as if no artifacts were emitted before, so every artifact is written.
+    pub(crate) fn read(config: &CompilerConfig) -> PostEmitManifest {
+        fs::read_to_string(post_emit_manifest_path(config))
+            .ok()
+            .and_then(|contents| PostEmitManifest::from_file_contents(&contents))
+            .unwrap_or_default()
+    }
+
+    pub(crate) fn is_unchanged(&self, path: &Path, generated_hash: &str) -> bool {
+        match self.artifacts.get(path) {
+            Some(EmittedArtifact {
+                generated_hash: previous_generated_hash,
+                emitted_hash: Some(emitted_hash),
+            }) => {
+                previous_generated_hash == generated_hash
+                    && fs::read(path)
+                        .map(|contents| &content_hash(&contents) == emitted_hash)
+                        .unwrap_or(false)
+            }
+            _ => false,
+        }
+    }
+
+    /// Replaces the recorded artifacts with the artifacts that were just generated,
+    /// and the retained artifacts, which were not generated but are still current.
+    /// The artifacts that were written must be passed to the post_emit_command, and
+    /// then recorded with record_emitted_contents_and_write.
+    pub(crate) fn replace_artifacts(
+        &mut self,
+        generated_hashes: impl Iterator<Item = (PathBuf, String, bool)>,
+        retained_paths: &[PathBuf],
+    ) {
+        let mut previous_artifacts = std::mem::take(&mut self.artifacts);
+        for path in retained_paths {
+            if let Some(previous_artifact) = previous_artifacts.remove(path) {
+                self.artifacts.insert(path.clone(), previous_artifact);
+            }
+        }
+        for (path, generated_hash, is_unchanged) in generated_hashes {
+            let artifact = match previous_artifacts.get(&path) {
+                Some(previous_artifact) if is_unchanged => previous_artifact.clone(),
+                _ => EmittedArtifact {
+                    generated_hash,
+                    emitted_hash: None,
+                },
+            };
+            self.artifacts.insert(path, artifact);
+        }
+    }
+
+    /// Records the contents that the post_emit_command left behind, and writes the
+    /// manifest next to the config file.
+    pub(crate) fn record_emitted_contents_and_write(
+        mut self,
+        config: &CompilerConfig,
+    ) -> Result<(), BatchCompileError> {
+        for (path, artifact) in self.artifacts.iter_mut() {
+            if artifact.emitted_hash.is_none() {
+                let contents =
+                    fs::read(path).map_err(|message| BatchCompileError::UnableToReadFile {
+                        path: path.clone(),
+                        message,
+                    })?;
+                artifact.emitted_hash = Some(content_hash(&contents));
+            }
+        }
+
+        let manifest_path = post_emit_manifest_path(config);
+        fs::write(&manifest_path, self.file_contents()).map_err(|message| {
+            BatchCompileError::UnableToWritePostEmitManifest {
+                path: manifest_path.clone(),
+                message,
+            }
+        })
+    }
+
+    /// One line per artifact, with the hash of its generated contents and of its
+    /// emitted contents, and its path.
+    fn file_contents(&self) -> String {
+        let mut contents = String::new();
+        for (path, artifact) in self.artifacts.iter() {
+            if let Some(emitted_hash) = &artifact.emitted_hash {
+                contents.push_str(&format!(
+                    "{} {} {}\n",
+                    artifact.generated_hash,
+                    emitted_hash,
+                    path.display()
+                ));
+            }
+        }
+        contents
+    }
+
+    fn from_file_contents(contents: &str) -> Option<PostEmitManifest> {
+        let mut manifest = PostEmitManifest::default();
+        for line in contents.lines() {
+            let (generated_hash, rest) = line.split_once(' ')?;
+            let (emitted_hash, path) = rest.split_once(' ')?;
+            manifest.artifacts.insert(
+                PathBuf::from(path),
+                EmittedArtifact {
+                    generated_hash: generated_hash.to_string(),
+                    emitted_hash: Some(emitted_hash.to_string()),
+                },
+            );
+        }
+        Some(manifest)
+    }
+}
+
+pub(crate) fn post_emit_manifest_path(config: &CompilerConfig) -> PathBuf {
+    config
+        .config_file
+        .with_file_name(POST_EMIT_MANIFEST_FILE_NAME)
+}
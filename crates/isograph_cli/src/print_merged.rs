@@ -0,0 +1,247 @@
+use colored::Colorize;
+use common_lang_types::WithSpan;
+use isograph_config::CompilerConfig;
+use isograph_schema::{
+    create_merged_selection_set_with_origins, MergedSelectionOrigins, MergedServerFieldSelection,
+    ValidatedSchema,
+};
+
+use crate::{
+    batch_compile::{create_validated_schema, BatchCompileError},
+    generate_artifacts::{
+        apply_normalization_alias_strategy, get_serialized_arguments_for_query_text,
+        get_serialized_if_argument_for_query_text, get_serialized_stream_directive_for_query_text,
+        write_variables_to_string,
+    },
+    progress::ProgressReporting,
+};
+
+static INDENT: &str = "  ";
+
+/// Prints the merged selection set of the entrypoint type_and_field (e.g.
+/// `Query.HomeRoute`), i.e. the selections of its query, to stdout. Each selection
+/// is annotated with the client fields whose selection sets contained it, which
+/// helps with debugging why a field is part of a query.
+///
+/// Nothing is written or deleted. Returns the printed text.
+pub fn print_merged_selection_set_and_print(
+    config: &CompilerConfig,
+    type_and_field: &str,
+) -> Result<String, BatchCompileError> {
+    eprintln!(
+        "{}",
+        format!("Printing the merged selection set of {}.", type_and_field).cyan()
+    );
+
+    let result = create_validated_schema(config, ProgressReporting::Shown).and_then(
+        |(validated_schema, _)| {
+            print_merged_selection_set(&validated_schema, type_and_field, config)
+        },
+    );
+
+    match result {
+        Ok(printed) => {
+            println!("{}", printed);
+            Ok(printed)
+        }
+        Err(err) => {
+            eprintln!(
+                "{}\n{}",
+                "Error when printing the merged selection set.\n".bright_red(),
+                err
+            );
+            Err(err)
+        }
+    }
+}
+
+fn print_merged_selection_set(
+    schema: &ValidatedSchema,
+    type_and_field: &str,
+    config: &CompilerConfig,
+) -> Result<String, BatchCompileError> {
+    let not_an_entrypoint = || BatchCompileError::NotAnEntrypoint {
+        type_and_field: type_and_field.to_string(),
+    };
+    let (type_name, field_name) = type_and_field
+        .split_once('.')
+        .ok_or_else(not_an_entrypoint)?;
+    let entrypoint = schema
+        .entrypoints
+        .iter()
+        .map(|client_field_id| schema.resolver(*client_field_id))
+        .find(|client_field| {
+            client_field.type_and_field.type_name.to_string() == type_name
+                && client_field.name.to_string() == field_name
+        })
+        .ok_or_else(not_an_entrypoint)?;
+    let (selection_set, _) = entrypoint
+        .selection_set_and_unwraps
+        .as_ref()
+        .ok_or_else(not_an_entrypoint)?;
+
+    let (mut merged_selection_set, root_refetched_paths, origins) =
+        create_merged_selection_set_with_origins(
+            schema,
+            schema.schema_data.object(entrypoint.parent_object_id),
+            selection_set,
+            entrypoint,
+        );
+    apply_normalization_alias_strategy(
+        &mut merged_selection_set,
+        config.options.normalization_alias_strategy,
+    );
+
+    let operation_kind = schema
+        .root_operation_kind(entrypoint.parent_object_id)
+        .ok_or_else(not_an_entrypoint)?;
+    let mut s = format!(
+        "{} {} {}{{\n",
+        operation_kind.keyword(),
+        entrypoint.name,
+        // The variables of every selected client field are hoisted to the query
+        match write_variables_to_string(schema, entrypoint.variable_definitions.iter()) {
+            variables if variables.is_empty() => variables,
+            variables => format!("{} ", variables),
+        }
+    );
+    print_selections(
+        &mut s,
+        schema,
+        &origins,
+        &merged_selection_set,
+        &mut vec![],
+        1,
+    );
+    s.push('}');
+
+    for root_refetched_path in root_refetched_paths.iter() {
+        let path = root_refetched_path
+            .path
+            .linked_fields
+            .iter()
+            .map(|linked_field| linked_field.name.to_string())
+            .collect::<Vec<_>>();
+        s.push_str(&format!(
+            "\n# {} for {} at {}, with variables: {}",
+            root_refetched_path.operation_kind.keyword(),
+            root_refetched_path.field_name,
+            if path.is_empty() {
+                "the root".to_string()
+            } else {
+                path.join(".")
+            },
+            if root_refetched_path.variables.is_empty() {
+                "none".to_string()
+            } else {
+                root_refetched_path
+                    .variables
+                    .iter()
+                    .map(|variable| format!("${}", variable))
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            }
+        ));
+    }
+
+    Ok(s)
+}
+
+fn print_selections<'a>(
+    s: &mut String,
+    schema: &ValidatedSchema,
+    origins: &MergedSelectionOrigins,
+    selections: &'a [WithSpan<MergedServerFieldSelection>],
+    path: &mut Vec<&'a MergedServerFieldSelection>,
+    indentation_level: usize,
+) {
+    for selection in selections.iter() {
+        path.push(&selection.item);
+        s.push_str(&INDENT.repeat(indentation_level));
+        let nested_selections = match &selection.item {
+            MergedServerFieldSelection::ScalarField(scalar_field) => {
+                if let Some(alias) = scalar_field.normalization_alias {
+                    s.push_str(&format!("{}: ", alias.item));
+                }
+                s.push_str(&format!(
+                    "{}{}{}",
+                    scalar_field.name.item,
+                    get_serialized_arguments_for_query_text(&scalar_field.arguments),
+                    scalar_field
+                        .stream
+                        .as_ref()
+                        .map(get_serialized_stream_directive_for_query_text)
+                        .unwrap_or_default()
+                ));
+                None
+            }
+            MergedServerFieldSelection::LinkedField(linked_field) => {
+                if let Some(alias) = linked_field.normalization_alias {
+                    s.push_str(&format!("{}: ", alias.item));
+                }
+                s.push_str(&format!(
+                    "{}{}{} {{",
+                    linked_field.name.item,
+                    get_serialized_arguments_for_query_text(&linked_field.arguments),
+                    linked_field
+                        .stream
+                        .as_ref()
+                        .map(get_serialized_stream_directive_for_query_text)
+                        .unwrap_or_default()
+                ));
+                Some(&linked_field.selection_set)
+            }
+            MergedServerFieldSelection::InlineFragment(inline_fragment) => {
+                s.push_str(&format!("... on {} {{", inline_fragment.type_to_refine_to));
+                Some(&inline_fragment.selection_set)
+            }
+            MergedServerFieldSelection::DeferredFragment(deferred_fragment) => {
+                s.push_str(&format!(
+                    "... @defer(label: \"{}\"{}) {{",
+                    deferred_fragment.label,
+                    get_serialized_if_argument_for_query_text(&deferred_fragment.if_condition)
+                ));
+                Some(&deferred_fragment.selection_set)
+            }
+        };
+        s.push_str(&print_origins(schema, origins, path));
+        s.push('\n');
+
+        if let Some(nested_selections) = nested_selections {
+            print_selections(
+                s,
+                schema,
+                origins,
+                nested_selections,
+                path,
+                indentation_level + 1,
+            );
+            s.push_str(&INDENT.repeat(indentation_level));
+            s.push_str("}\n");
+        }
+        path.pop();
+    }
+}
+
+fn print_origins(
+    schema: &ValidatedSchema,
+    origins: &MergedSelectionOrigins,
+    path: &[&MergedServerFieldSelection],
+) -> String {
+    let client_field_ids = origins.client_field_ids(path);
+    if client_field_ids.is_empty() {
+        return " # selected by Isograph".to_string();
+    }
+    let client_fields = client_field_ids
+        .iter()
+        .map(|client_field_id| {
+            let client_field = schema.resolver(*client_field_id);
+            format!(
+                "{}.{}",
+                client_field.type_and_field.type_name, client_field.name
+            )
+        })
+        .collect::<Vec<_>>()
+        .join(", ");
+    format!(" # from {}", client_fields)
+}
@@ -0,0 +1,194 @@
+use colored::Colorize;
+use common_lang_types::{DescriptionValue, HasName, UnvalidatedTypeName};
+use graphql_lang_types::TypeAnnotation;
+use intern::Lookup;
+use isograph_config::CompilerConfig;
+use isograph_schema::{ValidatedSchema, ValidatedSchemaObject, ValidatedSchemaServerField};
+
+use crate::{
+    batch_compile::{create_validated_schema, BatchCompileError},
+    generate_artifacts::write_variables_to_string,
+    progress::ProgressReporting,
+};
+
+static INDENT: &str = "  ";
+
+/// Prints the schema after schema extensions and client fields have been merged into
+/// it, i.e. every field that can be selected in iso literals, to stdout. Client
+/// fields are printed with their variable definitions and annotated as such.
+///
+/// Nothing is written or deleted. Returns the printed text.
+pub fn print_schema_and_print(config: &CompilerConfig) -> Result<String, BatchCompileError> {
+    eprintln!("{}", "Printing the merged schema.".cyan());
+
+    let result = create_validated_schema(config, ProgressReporting::Shown)
+        .map(|(validated_schema, _)| print_schema(&validated_schema));
+
+    match result {
+        Ok(printed) => {
+            println!("{}", printed);
+            Ok(printed)
+        }
+        Err(err) => {
+            eprintln!(
+                "{}\n{}",
+                "Error when printing the merged schema.\n".bright_red(),
+                err
+            );
+            Err(err)
+        }
+    }
+}
+
+fn print_schema(schema: &ValidatedSchema) -> String {
+    let mut definitions = vec![];
+
+    let root_types = [
+        ("query", schema.query_type_id),
+        ("mutation", schema.mutation_type_id),
+        ("subscription", schema.subscription_type_id),
+    ]
+    .into_iter()
+    .filter_map(|(operation, root_type_id)| {
+        root_type_id.map(|object_id| {
+            format!(
+                "{}{}: {}\n",
+                INDENT,
+                operation,
+                schema.schema_data.object(object_id).name
+            )
+        })
+    })
+    .collect::<String>();
+    if !root_types.is_empty() {
+        definitions.push(format!("schema {{\n{}}}", root_types));
+    }
+
+    for scalar in schema.schema_data.scalars.iter() {
+        definitions.push(match &scalar.enum_values {
+            Some(enum_values) => format!(
+                "enum {} {{\n{}}}",
+                scalar.name.item,
+                enum_values
+                    .iter()
+                    .map(|enum_value| format!(
+                        "{}{}{}\n",
+                        INDENT,
+                        enum_value.value,
+                        print_deprecated_directive(enum_value.deprecation_reason)
+                    ))
+                    .collect::<String>()
+            ),
+            None => format!("scalar {}", scalar.name.item),
+        });
+    }
+
+    // Interfaces and unions are represented as objects, and are thus printed as
+    // types.
+    for object in schema.schema_data.objects.iter() {
+        definitions.push(print_object(schema, object));
+    }
+
+    definitions.join("\n\n")
+}
+
+fn print_object(schema: &ValidatedSchema, object: &ValidatedSchemaObject) -> String {
+    let mut s = String::new();
+    if !object.valid_refinements.is_empty() {
+        s.push_str(&format!(
+            "# Implemented by {}\n",
+            object
+                .valid_refinements
+                .iter()
+                .map(|refinement| schema
+                    .schema_data
+                    .object(refinement.target)
+                    .name
+                    .to_string())
+                .collect::<Vec<_>>()
+                .join(", ")
+        ));
+    }
+    s.push_str(&format!(
+        "{} {}",
+        if object.is_input_object {
+            "input"
+        } else {
+            "type"
+        },
+        object.name
+    ));
+    for directive in object.directives.iter() {
+        s.push_str(&format!(" {}", directive));
+    }
+    s.push_str(" {\n");
+
+    for server_field_id in object.server_fields.iter() {
+        let server_field = schema.field(*server_field_id);
+        s.push_str(INDENT);
+        s.push_str(&print_server_field(schema, server_field));
+        if server_field.is_internal {
+            s.push_str(" # internal, cannot be selected in iso literals");
+        }
+        s.push('\n');
+    }
+
+    for client_field_id in object.resolvers.iter() {
+        let client_field = schema.resolver(*client_field_id);
+        s.push_str(&format!(
+            "{}{}{} # client field ({})\n",
+            INDENT,
+            client_field.name,
+            write_variables_to_string(schema, client_field.variable_definitions.iter()),
+            client_field.variant
+        ));
+    }
+
+    s.push('}');
+    s
+}
+
+/// Prints the definition of a server field, e.g. `avatar(size: Int): String`.
+pub(crate) fn print_server_field(
+    schema: &ValidatedSchema,
+    server_field: &ValidatedSchemaServerField,
+) -> String {
+    let type_: TypeAnnotation<UnvalidatedTypeName> =
+        server_field
+            .associated_data
+            .clone()
+            .map(|selectable_field_id| {
+                schema
+                    .schema_data
+                    .lookup_unvalidated_type(selectable_field_id)
+                    .name()
+            });
+    format!(
+        "{}{}: {}{}",
+        server_field.name.item,
+        if server_field.arguments.is_empty() {
+            String::new()
+        } else {
+            format!(
+                "({})",
+                server_field
+                    .arguments
+                    .iter()
+                    .map(|argument| argument.item.to_string())
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            )
+        },
+        type_,
+        print_deprecated_directive(server_field.deprecation_reason)
+    )
+}
+
+fn print_deprecated_directive(deprecation_reason: Option<DescriptionValue>) -> String {
+    match deprecation_reason {
+        Some(deprecation_reason) => {
+            format!(" @deprecated(reason: {:?})", deprecation_reason.lookup())
+        }
+        None => String::new(),
+    }
+}
@@ -0,0 +1,91 @@
+use std::io::{IsTerminal, Write};
+
+static BAR_WIDTH: usize = 30;
+
+/// Whether the phases of compilation report their progress on stderr. Only the
+/// command line shows progress; the entry points that do not print (e.g. for
+/// tools that embed the compiler, or compile several projects concurrently)
+/// hide it, since the progress of concurrent phases would overwrite each other.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum ProgressReporting {
+    Shown,
+    Hidden,
+}
+
+/// Reports the progress of a phase of compilation (e.g. extracting iso literals)
+/// on a single line of stderr, which is overwritten as items are processed and
+/// cleared when the phase is finished.
+///
+/// Nothing is printed if progress is hidden, or if stderr is not a terminal, so
+/// that logs (e.g. in CI) are not cluttered.
+pub(crate) struct ProgressBar {
+    label: &'static str,
+    total: usize,
+    processed: usize,
+    last_printed_width: Option<usize>,
+    is_enabled: bool,
+}
+
+impl ProgressBar {
+    pub(crate) fn new(
+        label: &'static str,
+        total: usize,
+        progress_reporting: ProgressReporting,
+    ) -> Self {
+        let mut progress_bar = ProgressBar {
+            label,
+            total,
+            processed: 0,
+            last_printed_width: None,
+            is_enabled: progress_reporting == ProgressReporting::Shown
+                && std::io::stderr().is_terminal(),
+        };
+        progress_bar.print();
+        progress_bar
+    }
+
+    pub(crate) fn increment(&mut self) {
+        self.processed += 1;
+        self.print();
+    }
+
+    fn print(&mut self) {
+        if !self.is_enabled {
+            return;
+        }
+        let filled_width = if self.total == 0 {
+            BAR_WIDTH
+        } else {
+            (self.processed.min(self.total) * BAR_WIDTH) / self.total
+        };
+        // Only reprint when the bar changes, or the last item is processed, since
+        // writing to stderr is slow compared to processing an item.
+        if self.last_printed_width == Some(filled_width) && self.processed != self.total {
+            return;
+        }
+        self.last_printed_width = Some(filled_width);
+
+        let mut stderr = std::io::stderr();
+        let _ = write!(
+            stderr,
+            "\r{} [{}{}] {}/{}",
+            self.label,
+            "#".repeat(filled_width),
+            " ".repeat(BAR_WIDTH - filled_width),
+            self.processed,
+            self.total
+        );
+        let _ = stderr.flush();
+    }
+}
+
+impl Drop for ProgressBar {
+    fn drop(&mut self) {
+        if self.is_enabled {
+            // Clear the line, so that subsequent output is not appended to the bar
+            let mut stderr = std::io::stderr();
+            let _ = write!(stderr, "\r\x1b[2K");
+            let _ = stderr.flush();
+        }
+    }
+}
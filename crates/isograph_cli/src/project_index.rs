@@ -0,0 +1,277 @@
+use std::{
+    collections::{BTreeMap, BTreeSet, HashMap},
+    fmt,
+    path::{Path, PathBuf},
+};
+
+use common_lang_types::{
+    IsographObjectTypeName, Location, SelectableFieldName, UnvalidatedTypeName, WithSpan,
+};
+use intern::{string_key::Intern, Lookup};
+use isograph_config::CompilerConfig;
+use isograph_lang_types::{ObjectId, Selection, ServerFieldSelection};
+use isograph_schema::{
+    FieldDefinitionLocation, ValidatedClientField, ValidatedSchema, ValidatedSelection,
+};
+
+use crate::{
+    compiler::ClientFieldKey,
+    generate_artifacts::{generate_path, get_artifact_path_and_contents},
+};
+
+/// A server field, e.g. `User.name`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub struct ServerFieldKey {
+    pub parent_type_name: IsographObjectTypeName,
+    pub server_field_name: SelectableFieldName,
+}
+
+impl fmt::Display for ServerFieldKey {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}.{}", self.parent_type_name, self.server_field_name)
+    }
+}
+
+/// What the index records about a client field.
+#[derive(Debug)]
+pub struct IndexedClientField {
+    pub key: ClientFieldKey,
+    /// Where the client field's name is declared
+    pub location: Location,
+    pub is_entrypoint: bool,
+    /// The server fields that the client field selects itself, i.e. not via the
+    /// client fields it selects
+    pub selected_server_fields: BTreeSet<ServerFieldKey>,
+    /// The client fields that the client field selects
+    pub selected_client_fields: BTreeSet<ClientFieldKey>,
+    /// The artifacts generated for the client field, relative to the artifact
+    /// directory
+    pub artifacts: BTreeSet<PathBuf>,
+}
+
+/// An index of a validated project: the client fields defined on each type, the
+/// fields that each client field selects, and the artifacts generated for each
+/// client field, along with the reverse lookups. Tools (such as editors, or reports
+/// of the artifacts affected by a change) can query it instead of walking the
+/// schema and generating artifacts themselves.
+///
+/// The index is a snapshot. Compiler keeps an index of the latest version of the
+/// project that could be validated.
+#[derive(Debug, Default)]
+pub struct ProjectIndex {
+    client_fields: BTreeMap<ClientFieldKey, IndexedClientField>,
+    client_fields_by_type: BTreeMap<UnvalidatedTypeName, BTreeSet<ClientFieldKey>>,
+    client_fields_by_selected_server_field: BTreeMap<ServerFieldKey, BTreeSet<ClientFieldKey>>,
+    client_fields_by_selected_client_field: BTreeMap<ClientFieldKey, BTreeSet<ClientFieldKey>>,
+    artifact_owners: BTreeMap<PathBuf, ClientFieldKey>,
+}
+
+impl ProjectIndex {
+    /// Indexes the validated schema. Artifacts are generated (but not written) to
+    /// determine their paths.
+    pub fn new(schema: &ValidatedSchema, config: &CompilerConfig) -> Self {
+        let mut index = ProjectIndex::default();
+        let mut client_fields_by_directory = HashMap::new();
+
+        for client_field in schema.client_fields.iter() {
+            let key = client_field_key(client_field);
+            let mut indexed_client_field = IndexedClientField {
+                key,
+                location: client_field.name_location,
+                is_entrypoint: schema.entrypoints.contains(&client_field.id),
+                selected_server_fields: BTreeSet::new(),
+                selected_client_fields: BTreeSet::new(),
+                artifacts: BTreeSet::new(),
+            };
+            if let Some((selection_set, _)) = &client_field.selection_set_and_unwraps {
+                collect_selected_fields(
+                    schema,
+                    client_field.parent_object_id,
+                    selection_set,
+                    &mut indexed_client_field,
+                );
+            }
+
+            for server_field in indexed_client_field.selected_server_fields.iter() {
+                index
+                    .client_fields_by_selected_server_field
+                    .entry(*server_field)
+                    .or_default()
+                    .insert(key);
+            }
+            for selected_client_field in indexed_client_field.selected_client_fields.iter() {
+                index
+                    .client_fields_by_selected_client_field
+                    .entry(*selected_client_field)
+                    .or_default()
+                    .insert(key);
+            }
+            index
+                .client_fields_by_type
+                .entry(key.parent_type_name)
+                .or_default()
+                .insert(key);
+            client_fields_by_directory.insert(
+                generate_path(
+                    client_field.type_and_field.type_name,
+                    client_field.type_and_field.field_name,
+                    client_field.type_and_field.artifact_namespace,
+                ),
+                key,
+            );
+            index.client_fields.insert(key, indexed_client_field);
+        }
+
+        // The artifacts of a client field (including the refetch queries of an
+        // entrypoint) are generated in its directory. Other artifacts, e.g. iso.ts,
+        // belong to no client field.
+        for path_and_content in get_artifact_path_and_contents(
+            schema,
+            &config.project_root,
+            &config.artifact_directory,
+            config.options,
+        ) {
+            let Some(key) = client_fields_by_directory.get(&path_and_content.relative_directory)
+            else {
+                continue;
+            };
+            let path = path_and_content.relative_directory.join(format!(
+                "{}.{}",
+                path_and_content.file_name_prefix, path_and_content.file_extension
+            ));
+            if let Some(indexed_client_field) = index.client_fields.get_mut(key) {
+                indexed_client_field.artifacts.insert(path.clone());
+            }
+            index.artifact_owners.insert(path, *key);
+        }
+
+        index
+    }
+
+    /// Every client field in the project, in order of key.
+    pub fn client_fields(&self) -> impl Iterator<Item = &IndexedClientField> {
+        self.client_fields.values()
+    }
+
+    pub fn client_field(&self, key: ClientFieldKey) -> Option<&IndexedClientField> {
+        self.client_fields.get(&key)
+    }
+
+    /// The client fields defined on the type, in order of key.
+    pub fn client_fields_on_type(
+        &self,
+        type_name: UnvalidatedTypeName,
+    ) -> impl Iterator<Item = &IndexedClientField> {
+        self.client_fields_by_type
+            .get(&type_name)
+            .into_iter()
+            .flatten()
+            .filter_map(|key| self.client_fields.get(key))
+    }
+
+    /// The client fields that select the server field themselves, in order of key.
+    pub fn client_fields_selecting_server_field(
+        &self,
+        server_field: ServerFieldKey,
+    ) -> impl Iterator<Item = ClientFieldKey> + '_ {
+        self.client_fields_by_selected_server_field
+            .get(&server_field)
+            .into_iter()
+            .flatten()
+            .copied()
+    }
+
+    /// The client fields that select the client field, in order of key.
+    pub fn client_fields_selecting_client_field(
+        &self,
+        client_field: ClientFieldKey,
+    ) -> impl Iterator<Item = ClientFieldKey> + '_ {
+        self.client_fields_by_selected_client_field
+            .get(&client_field)
+            .into_iter()
+            .flatten()
+            .copied()
+    }
+
+    /// The client field for which the artifact at path (relative to the artifact
+    /// directory) is generated, if any.
+    pub fn artifact_owner(&self, path: &Path) -> Option<ClientFieldKey> {
+        self.artifact_owners.get(path).copied()
+    }
+
+    /// The artifacts (relative to the artifact directory) whose contents can depend
+    /// on the client field, i.e. its own artifacts and those of the client fields
+    /// that select it, directly or transitively, since the merged selection sets of
+    /// the latter contain its selections.
+    pub fn affected_artifacts(&self, client_field: ClientFieldKey) -> BTreeSet<PathBuf> {
+        let mut visited = BTreeSet::new();
+        let mut queue = vec![client_field];
+        let mut artifacts = BTreeSet::new();
+        while let Some(key) = queue.pop() {
+            if !visited.insert(key) {
+                continue;
+            }
+            if let Some(indexed_client_field) = self.client_fields.get(&key) {
+                artifacts.extend(indexed_client_field.artifacts.iter().cloned());
+            }
+            queue.extend(self.client_fields_selecting_client_field(key));
+        }
+        artifacts
+    }
+}
+
+fn client_field_key(client_field: &ValidatedClientField) -> ClientFieldKey {
+    ClientFieldKey {
+        parent_type_name: client_field
+            .type_and_field
+            .type_name
+            .lookup()
+            .intern()
+            .into(),
+        client_field_name: client_field.name.lookup().intern().into(),
+    }
+}
+
+fn collect_selected_fields(
+    schema: &ValidatedSchema,
+    parent_object_id: ObjectId,
+    selection_set: &[WithSpan<ValidatedSelection>],
+    indexed_client_field: &mut IndexedClientField,
+) {
+    let parent_type_name = schema.schema_data.object(parent_object_id).name;
+    for selection in selection_set {
+        match &selection.item {
+            Selection::ServerField(ServerFieldSelection::ScalarField(scalar_field)) => {
+                match scalar_field.associated_data {
+                    FieldDefinitionLocation::Server(_) => {
+                        indexed_client_field
+                            .selected_server_fields
+                            .insert(ServerFieldKey {
+                                parent_type_name,
+                                server_field_name: scalar_field.name.item.into(),
+                            });
+                    }
+                    FieldDefinitionLocation::Client(client_field_id) => {
+                        indexed_client_field
+                            .selected_client_fields
+                            .insert(client_field_key(schema.resolver(client_field_id)));
+                    }
+                }
+            }
+            Selection::ServerField(ServerFieldSelection::LinkedField(linked_field)) => {
+                indexed_client_field
+                    .selected_server_fields
+                    .insert(ServerFieldKey {
+                        parent_type_name,
+                        server_field_name: linked_field.name.item.into(),
+                    });
+                collect_selected_fields(
+                    schema,
+                    linked_field.associated_data.parent_object_id,
+                    &linked_field.selection_set,
+                    indexed_client_field,
+                );
+            }
+        }
+    }
+}
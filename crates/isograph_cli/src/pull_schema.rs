@@ -0,0 +1,178 @@
+use colored::Colorize;
+use common_lang_types::TextSource;
+use graphql_lang_types::print_type_system_document;
+use graphql_schema_parser::parse_introspection_json;
+use intern::string_key::Intern;
+use isograph_config::CompilerConfig;
+use serde_json::json;
+
+use crate::batch_compile::BatchCompileError;
+
+/// The standard introspection query, as sent by e.g. graphql-js. Fields that older
+/// servers do not support (e.g. isRepeatable) are not requested.
+static INTROSPECTION_QUERY: &str = "query IntrospectionQuery {
+  __schema {
+    queryType { name }
+    mutationType { name }
+    subscriptionType { name }
+    types { ...FullType }
+    directives {
+      name
+      description
+      locations
+      args { ...InputValue }
+    }
+  }
+}
+
+fragment FullType on __Type {
+  kind
+  name
+  description
+  fields(includeDeprecated: true) {
+    name
+    description
+    args { ...InputValue }
+    type { ...TypeRef }
+    isDeprecated
+    deprecationReason
+  }
+  inputFields { ...InputValue }
+  interfaces { ...TypeRef }
+  enumValues(includeDeprecated: true) {
+    name
+    description
+    isDeprecated
+    deprecationReason
+  }
+  possibleTypes { ...TypeRef }
+}
+
+fragment InputValue on __InputValue {
+  name
+  description
+  type { ...TypeRef }
+  defaultValue
+}
+
+fragment TypeRef on __Type {
+  kind
+  name
+  ofType {
+    kind
+    name
+    ofType {
+      kind
+      name
+      ofType {
+        kind
+        name
+        ofType {
+          kind
+          name
+          ofType {
+            kind
+            name
+            ofType {
+              kind
+              name
+              ofType {
+                kind
+                name
+              }
+            }
+          }
+        }
+      }
+    }
+  }
+}";
+
+/// Sends the introspection query to endpoint, and writes the schema to the path of
+/// the schema in the config, so that projects without a checked-in schema can be
+/// bootstrapped. headers are e.g. `Authorization: Bearer <token>`.
+///
+/// If the path of the schema ends with `.json`, the introspection result is
+/// written as is. Otherwise, it is converted to SDL.
+pub fn pull_schema_and_print(
+    config: &CompilerConfig,
+    endpoint: &str,
+    headers: &[String],
+) -> Result<(), BatchCompileError> {
+    eprintln!(
+        "{}",
+        format!("Pulling the schema from {}.", endpoint).cyan()
+    );
+
+    match pull_schema(config, endpoint, headers) {
+        Ok(()) => {
+            eprintln!(
+                "{}",
+                format!("Successfully wrote the schema to {:?}.\n", config.schema).bright_green()
+            );
+            Ok(())
+        }
+        Err(err) => {
+            eprintln!(
+                "{}\n{}",
+                "Error when pulling the schema.\n".bright_red(),
+                err
+            );
+            Err(err)
+        }
+    }
+}
+
+fn pull_schema(
+    config: &CompilerConfig,
+    endpoint: &str,
+    headers: &[String],
+) -> Result<(), BatchCompileError> {
+    let mut request = ureq::post(endpoint).set("Content-Type", "application/json");
+    for header in headers {
+        let (name, value) =
+            header
+                .split_once(':')
+                .ok_or_else(|| BatchCompileError::InvalidHeader {
+                    header: header.clone(),
+                })?;
+        request = request.set(name.trim(), value.trim());
+    }
+
+    let unable_to_pull_schema = |message: String| BatchCompileError::UnableToPullSchema {
+        endpoint: endpoint.to_string(),
+        message,
+    };
+    let introspection_result = request
+        .send_string(&json!({ "query": INTROSPECTION_QUERY }).to_string())
+        .map_err(|error| unable_to_pull_schema(error.to_string()))?
+        .into_string()
+        .map_err(|error| unable_to_pull_schema(error.to_string()))?;
+
+    // The introspection result is converted even if it is written as is, so that
+    // invalid results (e.g. errors returned by the endpoint) are not written.
+    let text_source = TextSource {
+        path: endpoint.intern().into(),
+        span: None,
+    };
+    let type_system_document = parse_introspection_json(&introspection_result, text_source)
+        .map_err(|error| vec![error.to_with_location(text_source)])?;
+
+    let is_introspection_result = config
+        .schema
+        .extension()
+        .and_then(|extension| extension.to_str())
+        == Some("json");
+    let contents = if is_introspection_result {
+        introspection_result
+    } else {
+        print_type_system_document(&type_system_document)
+    };
+
+    std::fs::write(&config.schema, contents).map_err(|message| {
+        BatchCompileError::UnableToWriteSchema {
+            path: config.schema.clone(),
+            message,
+        }
+    })
+}
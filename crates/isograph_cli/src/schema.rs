@@ -1,35 +1,37 @@
 use std::path::PathBuf;
 
+use isograph_config::{normalize_path, PathNormalization};
+
 use crate::batch_compile::BatchCompileError;
 
 /// Read schema file
-pub(crate) fn read_schema_file(path: &PathBuf) -> Result<String, BatchCompileError> {
-    let current_dir = std::env::current_dir().expect("current_dir should exist");
-    let joined = current_dir.join(path);
-    let canonicalized_existing_path =
-        joined
-            .canonicalize()
-            .map_err(|message| BatchCompileError::UnableToLoadSchema {
-                path: joined,
-                message,
-            })?;
+pub(crate) fn read_schema_file(
+    path: &PathBuf,
+    path_normalization: PathNormalization,
+) -> Result<String, BatchCompileError> {
+    let normalized_existing_path = normalize_path(path, path_normalization).map_err(|message| {
+        BatchCompileError::UnableToLoadSchema {
+            path: path.clone(),
+            message,
+        }
+    })?;
 
-    if !canonicalized_existing_path.is_file() {
+    if !normalized_existing_path.is_file() {
         return Err(BatchCompileError::SchemaNotAFile {
-            path: canonicalized_existing_path,
+            path: normalized_existing_path,
         });
     }
 
-    let contents = std::fs::read(canonicalized_existing_path.clone()).map_err(|message| {
+    let contents = std::fs::read(normalized_existing_path.clone()).map_err(|message| {
         BatchCompileError::UnableToReadFile {
-            path: canonicalized_existing_path.clone(),
+            path: normalized_existing_path.clone(),
             message,
         }
     })?;
 
     let contents = std::str::from_utf8(&contents)
         .map_err(|e| BatchCompileError::UnableToConvertToString {
-            path: canonicalized_existing_path.clone(),
+            path: normalized_existing_path.clone(),
             reason: e,
         })?
         .to_owned();
@@ -0,0 +1,115 @@
+use std::{collections::HashSet, path::Path};
+
+use colored::Colorize;
+use common_lang_types::Location;
+use isograph_config::CompilerConfig;
+
+use crate::{
+    batch_compile::{create_validated_schema, read_and_extract_iso_literals, BatchCompileError},
+    progress::ProgressReporting,
+};
+
+/// Validates every client field and entrypoint against the schema at
+/// proposed_schema (e.g. the schema of a backend change that has not landed yet)
+/// instead of the schema in the config, and lists the client fields and entrypoints
+/// that would no longer be valid. Schema extensions and stitched schemas are still
+/// read from the config.
+///
+/// Nothing is written or deleted.
+pub fn check_schema_compatibility_and_print(
+    config: &CompilerConfig,
+    proposed_schema: &Path,
+) -> Result<(), BatchCompileError> {
+    eprintln!(
+        "{}",
+        format!(
+            "Checking client fields and entrypoints against the schema at {:?}.",
+            proposed_schema
+        )
+        .cyan()
+    );
+
+    let mut proposed_config = config.clone();
+    proposed_config.schema = proposed_schema.to_path_buf();
+
+    match create_validated_schema(&proposed_config, ProgressReporting::Shown) {
+        Ok((_, stats)) => {
+            eprintln!(
+                "{}",
+                format!(
+                    "All {} client fields and {} entrypoints are compatible with the schema at {:?}.\n",
+                    stats.client_field_count, stats.entrypoint_count, proposed_schema
+                )
+                .bright_green()
+            );
+            Ok(())
+        }
+        Err(err) => {
+            let broken_client_fields = broken_client_fields(&proposed_config, &err);
+            if !broken_client_fields.is_empty() {
+                eprintln!(
+                    "{}",
+                    "The following client fields and entrypoints would break:".yellow()
+                );
+                for broken_client_field in broken_client_fields.iter() {
+                    println!("{}", broken_client_field);
+                }
+                eprintln!();
+            }
+            eprintln!(
+                "{}\n{}",
+                "Error when checking against the proposed schema.\n".bright_red(),
+                err
+            );
+            Err(err)
+        }
+    }
+}
+
+/// The client fields (e.g. `User.Avatar`) and entrypoints (e.g. `entrypoint
+/// Query.HomeRoute`) whose iso literals contain any of the errors, sorted. Errors
+/// in the proposed schema itself do not belong to any client field.
+fn broken_client_fields(config: &CompilerConfig, err: &BatchCompileError) -> Vec<String> {
+    let error_text_sources = err
+        .located_messages()
+        .into_iter()
+        .filter_map(|message| match message.location {
+            Location::Embedded(location) => Some(location.text_source),
+            Location::Generated => None,
+        })
+        .collect::<HashSet<_>>();
+    if error_text_sources.is_empty() {
+        return vec![];
+    }
+
+    let Ok((client_field_declarations, entrypoint_declarations)) =
+        read_and_extract_iso_literals(config, ProgressReporting::Shown)
+    else {
+        return vec![];
+    };
+
+    let mut broken_client_fields = client_field_declarations
+        .iter()
+        .filter(|(_, text_source)| error_text_sources.contains(text_source))
+        .map(|(declaration, _)| {
+            format!(
+                "{}.{}",
+                declaration.item.parent_type.item, declaration.item.client_field_name.item
+            )
+        })
+        .chain(
+            entrypoint_declarations
+                .iter()
+                .filter(|(_, text_source)| error_text_sources.contains(text_source))
+                .map(|(declaration, _)| {
+                    format!(
+                        "entrypoint {}.{}",
+                        declaration.item.parent_type.item, declaration.item.client_field_name.item
+                    )
+                }),
+        )
+        .collect::<Vec<_>>();
+    broken_client_fields.sort();
+    broken_client_fields.dedup();
+    broken_client_fields
+}
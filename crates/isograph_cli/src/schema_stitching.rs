@@ -0,0 +1,309 @@
+use std::collections::{HashMap, HashSet};
+
+use common_lang_types::{TextSource, WithLocation};
+use graphql_lang_types::{
+    GraphQLFieldDefinition, GraphQLInputObjectTypeExtension, GraphQLInputValueDefinition,
+    GraphQLInterfaceTypeExtension, GraphQLObjectTypeExtension, GraphQLTypeSystemDefinition,
+    GraphQLTypeSystemDocument, GraphQLTypeSystemExtension, GraphQLTypeSystemExtensionDocument,
+    GraphQLTypeSystemExtensionOrDefinition,
+};
+use graphql_schema_parser::parse_schema_with_error_recovery;
+use intern::{
+    string_key::{Intern, StringKey},
+    Lookup,
+};
+use isograph_config::CompilerConfig;
+
+use crate::{batch_compile::BatchCompileError, schema::read_schema_file};
+
+static BUILT_IN_SCALARS: [&str; 5] = ["ID", "String", "Boolean", "Float", "Int"];
+
+/// Reads the stitched schemas in the config, and converts each of them into a
+/// schema extension document, so that they are combined with the schema into a
+/// single schema, in which client fields can select fields from every service.
+///
+/// The types of a stitched schema are merged into the schema as follows:
+/// - its root types are merged into the root types of the schema,
+/// - if it has a type prefix, its other types are renamed with it,
+/// - objects, interfaces and input objects with the same name as an existing type
+///   are merged into that type, and their fields must not clash,
+/// - scalars and enums with the same name as an existing type are assumed to be
+///   the same type, and are skipped,
+/// - unions with the same name as an existing type are errors.
+pub(crate) fn read_and_stitch_schemas(
+    config: &CompilerConfig,
+    type_system_document: &GraphQLTypeSystemDocument,
+) -> Result<Vec<GraphQLTypeSystemExtensionDocument>, BatchCompileError> {
+    let root_type_names = root_type_names(type_system_document);
+    let mut existing_type_names = BUILT_IN_SCALARS
+        .iter()
+        .map(|built_in_scalar| built_in_scalar.to_string())
+        .chain(
+            type_system_document
+                .iter()
+                .filter_map(|definition| type_definition_name(&definition.item)),
+        )
+        .collect::<HashSet<_>>();
+
+    config
+        .stitched_schemas
+        .iter()
+        .map(|stitched_schema| {
+            let text_source = TextSource {
+                path: stitched_schema
+                    .path
+                    .to_str()
+                    .expect("Expected stitched schema to be valid string")
+                    .intern()
+                    .into(),
+                span: None,
+            };
+            let content =
+                read_schema_file(&stitched_schema.path, config.options.path_normalization)?;
+            let stitched_document = parse_schema_with_error_recovery(&content, text_source)
+                .map_err(|errors| {
+                    errors
+                        .into_iter()
+                        .map(|with_span| with_span.to_with_location(text_source))
+                        .collect::<Vec<_>>()
+                })?;
+            Ok(stitch_schema(
+                stitched_document,
+                stitched_schema.type_prefix.as_deref(),
+                &root_type_names,
+                &mut existing_type_names,
+            ))
+        })
+        .collect()
+}
+
+fn stitch_schema(
+    stitched_document: GraphQLTypeSystemDocument,
+    type_prefix: Option<&str>,
+    root_type_names: &RootTypeNames,
+    existing_type_names: &mut HashSet<String>,
+) -> GraphQLTypeSystemExtensionDocument {
+    let stitched_root_type_names = root_type_names(&stitched_document);
+
+    let mut type_names = HashMap::new();
+    for definition in stitched_document.iter() {
+        if let Some(name) = type_definition_name(&definition.item) {
+            let new_name = match stitched_root_type_names.operation_of(&name) {
+                Some(operation_index) => root_type_names.0[operation_index].clone(),
+                None => match type_prefix {
+                    Some(type_prefix) if !BUILT_IN_SCALARS.contains(&name.as_str()) => {
+                        format!("{type_prefix}{name}")
+                    }
+                    _ => name.clone(),
+                },
+            };
+            type_names.insert(name, new_name);
+        }
+    }
+    let renamer = TypeRenamer(type_names);
+
+    let mut stitched_definitions = vec![];
+    for definition in stitched_document.0 {
+        let WithLocation { item, location } = definition;
+        let stitched_definition = match item {
+            GraphQLTypeSystemDefinition::ObjectTypeDefinition(mut object_type_definition) => {
+                renamer.rename(&mut object_type_definition.name.item);
+                renamer.rename_all(&mut object_type_definition.interfaces);
+                renamer.rename_fields(&mut object_type_definition.fields);
+                if existing_type_names.insert(object_type_definition.name.item.to_string()) {
+                    Some(GraphQLTypeSystemExtensionOrDefinition::Definition(
+                        object_type_definition.into(),
+                    ))
+                } else {
+                    Some(GraphQLTypeSystemExtensionOrDefinition::Extension(
+                        GraphQLTypeSystemExtension::ObjectTypeExtension(
+                            GraphQLObjectTypeExtension {
+                                name: object_type_definition.name,
+                                interfaces: object_type_definition.interfaces,
+                                directives: object_type_definition.directives,
+                                fields: object_type_definition.fields,
+                            },
+                        ),
+                    ))
+                }
+            }
+            GraphQLTypeSystemDefinition::InterfaceTypeDefinition(mut interface_type_definition) => {
+                renamer.rename(&mut interface_type_definition.name.item);
+                renamer.rename_all(&mut interface_type_definition.interfaces);
+                renamer.rename_fields(&mut interface_type_definition.fields);
+                if existing_type_names.insert(interface_type_definition.name.item.to_string()) {
+                    Some(GraphQLTypeSystemExtensionOrDefinition::Definition(
+                        interface_type_definition.into(),
+                    ))
+                } else {
+                    Some(GraphQLTypeSystemExtensionOrDefinition::Extension(
+                        GraphQLTypeSystemExtension::InterfaceTypeExtension(
+                            GraphQLInterfaceTypeExtension {
+                                name: interface_type_definition.name,
+                                interfaces: interface_type_definition.interfaces,
+                                directives: interface_type_definition.directives,
+                                fields: interface_type_definition.fields,
+                            },
+                        ),
+                    ))
+                }
+            }
+            GraphQLTypeSystemDefinition::InputObjectTypeDefinition(
+                mut input_object_type_definition,
+            ) => {
+                renamer.rename(&mut input_object_type_definition.name.item);
+                renamer.rename_input_values(&mut input_object_type_definition.fields);
+                if existing_type_names.insert(input_object_type_definition.name.item.to_string()) {
+                    Some(GraphQLTypeSystemExtensionOrDefinition::Definition(
+                        input_object_type_definition.into(),
+                    ))
+                } else {
+                    Some(GraphQLTypeSystemExtensionOrDefinition::Extension(
+                        GraphQLTypeSystemExtension::InputObjectTypeExtension(
+                            GraphQLInputObjectTypeExtension {
+                                name: input_object_type_definition
+                                    .name
+                                    .map(|name| name.lookup().intern().into()),
+                                directives: input_object_type_definition.directives,
+                                fields: input_object_type_definition.fields,
+                            },
+                        ),
+                    ))
+                }
+            }
+            GraphQLTypeSystemDefinition::ScalarTypeDefinition(mut scalar_type_definition) => {
+                renamer.rename(&mut scalar_type_definition.name.item);
+                existing_type_names
+                    .insert(scalar_type_definition.name.item.to_string())
+                    .then(|| {
+                        GraphQLTypeSystemExtensionOrDefinition::Definition(
+                            scalar_type_definition.into(),
+                        )
+                    })
+            }
+            GraphQLTypeSystemDefinition::EnumDefinition(mut enum_definition) => {
+                renamer.rename(&mut enum_definition.name.item);
+                existing_type_names
+                    .insert(enum_definition.name.item.to_string())
+                    .then(|| {
+                        GraphQLTypeSystemExtensionOrDefinition::Definition(enum_definition.into())
+                    })
+            }
+            GraphQLTypeSystemDefinition::UnionTypeDefinition(mut union_definition) => {
+                renamer.rename(&mut union_definition.name.item);
+                renamer.rename_all(&mut union_definition.union_member_types);
+                existing_type_names.insert(union_definition.name.item.to_string());
+                Some(GraphQLTypeSystemExtensionOrDefinition::Definition(
+                    union_definition.into(),
+                ))
+            }
+            GraphQLTypeSystemDefinition::DirectiveDefinition(mut directive_definition) => {
+                renamer.rename_input_values(&mut directive_definition.arguments);
+                Some(GraphQLTypeSystemExtensionOrDefinition::Definition(
+                    directive_definition.into(),
+                ))
+            }
+            // The root types of the stitched schema are merged into the root types
+            // of the schema
+            GraphQLTypeSystemDefinition::SchemaDefinition(_) => None,
+        };
+        stitched_definitions.extend(
+            stitched_definition
+                .map(|stitched_definition| WithLocation::new(stitched_definition, location)),
+        );
+    }
+
+    GraphQLTypeSystemExtensionDocument(stitched_definitions)
+}
+
+/// The names of the query, mutation and subscription types of a schema, in that
+/// order.
+struct RootTypeNames([String; 3]);
+
+impl RootTypeNames {
+    fn operation_of(&self, type_name: &str) -> Option<usize> {
+        self.0
+            .iter()
+            .position(|root_type_name| root_type_name == type_name)
+    }
+}
+
+fn root_type_names(type_system_document: &GraphQLTypeSystemDocument) -> RootTypeNames {
+    let mut root_type_names = RootTypeNames([
+        "Query".to_string(),
+        "Mutation".to_string(),
+        "Subscription".to_string(),
+    ]);
+    for definition in type_system_document.iter() {
+        if let GraphQLTypeSystemDefinition::SchemaDefinition(schema_definition) = &definition.item {
+            let root_types = [
+                &schema_definition.query,
+                &schema_definition.mutation,
+                &schema_definition.subscription,
+            ];
+            for (index, root_type) in root_types.into_iter().enumerate() {
+                if let Some(root_type) = root_type {
+                    root_type_names.0[index] = root_type.item.to_string();
+                }
+            }
+        }
+    }
+    root_type_names
+}
+
+fn type_definition_name(definition: &GraphQLTypeSystemDefinition) -> Option<String> {
+    match definition {
+        GraphQLTypeSystemDefinition::ObjectTypeDefinition(definition) => {
+            Some(definition.name.item.to_string())
+        }
+        GraphQLTypeSystemDefinition::ScalarTypeDefinition(definition) => {
+            Some(definition.name.item.to_string())
+        }
+        GraphQLTypeSystemDefinition::InterfaceTypeDefinition(definition) => {
+            Some(definition.name.item.to_string())
+        }
+        GraphQLTypeSystemDefinition::InputObjectTypeDefinition(definition) => {
+            Some(definition.name.item.to_string())
+        }
+        GraphQLTypeSystemDefinition::EnumDefinition(definition) => {
+            Some(definition.name.item.to_string())
+        }
+        GraphQLTypeSystemDefinition::UnionTypeDefinition(definition) => {
+            Some(definition.name.item.to_string())
+        }
+        GraphQLTypeSystemDefinition::DirectiveDefinition(_)
+        | GraphQLTypeSystemDefinition::SchemaDefinition(_) => None,
+    }
+}
+
+/// Renames the types defined in a stitched schema, and every reference to them.
+/// Types that are not defined in the stitched schema (i.e. the built-in scalars)
+/// are not renamed.
+struct TypeRenamer(HashMap<String, String>);
+
+impl TypeRenamer {
+    fn rename<T: Lookup + From<StringKey>>(&self, type_name: &mut T) {
+        if let Some(new_type_name) = self.0.get(type_name.lookup()) {
+            *type_name = new_type_name.as_str().intern().into();
+        }
+    }
+
+    fn rename_all<T: Lookup + From<StringKey>>(&self, type_names: &mut [WithLocation<T>]) {
+        for type_name in type_names.iter_mut() {
+            self.rename(&mut type_name.item);
+        }
+    }
+
+    fn rename_fields(&self, fields: &mut [WithLocation<GraphQLFieldDefinition>]) {
+        for field in fields.iter_mut() {
+            self.rename(field.item.type_.inner_mut());
+            self.rename_input_values(&mut field.item.arguments);
+        }
+    }
+
+    fn rename_input_values(&self, input_values: &mut [WithLocation<GraphQLInputValueDefinition>]) {
+        for input_value in input_values.iter_mut() {
+            self.rename(input_value.item.type_.inner_mut());
+        }
+    }
+}
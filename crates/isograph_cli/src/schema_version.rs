@@ -0,0 +1,188 @@
+use std::path::Path;
+
+use colored::Colorize;
+use graphql_lang_types::{print_type_system_document, print_type_system_extension_document};
+use isograph_config::CompilerConfig;
+
+use crate::{
+    batch_compile::{
+        parse_schema_with_config_path, read_and_parse_schema_extensions, BatchCompileError,
+    },
+    generate_artifacts::{content_hash, PathAndContent},
+    schema::read_schema_file,
+    schema_stitching::read_and_stitch_schemas,
+    write_artifacts::visit_files,
+};
+
+/// TypeScript artifacts start with this comment, followed by the schema hash.
+static SCHEMA_HASH_COMMENT: &str = "// schemaHash: ";
+/// JSON artifacts start with this property, whose value is the schema hash.
+static SCHEMA_HASH_PROPERTY: &str = "\"schemaHash\": \"";
+
+/// The keys of the persisted query manifest are query hashes, so it is not stamped.
+static PERSISTED_QUERIES_MANIFEST: &str = "persisted_queries.json";
+
+/// A hash of the schema, stitched schemas and schema extensions, which identifies
+/// the version of the schema from which artifacts are generated.
+///
+/// The schema documents are parsed and printed before they are hashed, so changes to
+/// comments or formatting, which do not affect the artifacts, do not change the hash.
+pub(crate) fn schema_hash(config: &CompilerConfig) -> Result<String, BatchCompileError> {
+    let content = read_schema_file(&config.schema, config.options.path_normalization)?;
+    let type_system_document = parse_schema_with_config_path(&content, config)?;
+    let mut printed_documents = vec![print_type_system_document(&type_system_document)];
+    printed_documents.extend(
+        read_and_stitch_schemas(config, &type_system_document)?
+            .iter()
+            .chain(read_and_parse_schema_extensions(config)?.iter())
+            .map(print_type_system_extension_document),
+    );
+
+    let mut fingerprints = String::new();
+    for printed_document in printed_documents {
+        fingerprints.push_str(&content_hash(printed_document.as_bytes()));
+    }
+    Ok(content_hash(fingerprints.as_bytes()))
+}
+
+/// Records the schema hash in the artifact: in a leading comment in TypeScript
+/// artifacts, and in a leading schemaHash property in JSON artifacts.
+pub(crate) fn stamp_schema_hash(
+    mut path_and_content: PathAndContent,
+    schema_hash: &str,
+) -> PathAndContent {
+    let relative_path = path_and_content.relative_directory.join(format!(
+        "{}.{}",
+        path_and_content.file_name_prefix, path_and_content.file_extension
+    ));
+    if !is_stamped(&relative_path) {
+        return path_and_content;
+    }
+
+    match path_and_content.file_extension {
+        "ts" => {
+            path_and_content.file_content = format!(
+                "{SCHEMA_HASH_COMMENT}{schema_hash}\n{}",
+                path_and_content.file_content
+            );
+        }
+        "json" => {
+            if let Some(properties) = path_and_content.file_content.strip_prefix("{\n") {
+                path_and_content.file_content =
+                    format!("{{\n  {SCHEMA_HASH_PROPERTY}{schema_hash}\",\n{properties}");
+            }
+        }
+        _ => {}
+    }
+    path_and_content
+}
+
+fn is_stamped(relative_path: &Path) -> bool {
+    relative_path != Path::new(PERSISTED_QUERIES_MANIFEST)
+}
+
+/// The schema hash recorded in the artifact by stamp_schema_hash, if any.
+fn read_schema_hash<'a>(file_content: &'a str, file_extension: &str) -> Option<&'a str> {
+    match file_extension {
+        "ts" => file_content
+            .lines()
+            .next()?
+            .strip_prefix(SCHEMA_HASH_COMMENT),
+        "json" => file_content
+            .lines()
+            .nth(1)?
+            .trim_start()
+            .strip_prefix(SCHEMA_HASH_PROPERTY)?
+            .strip_suffix("\","),
+        _ => None,
+    }
+}
+
+/// Checks that every artifact in the artifact directory was generated from the
+/// current version of the schema, without compiling. Iso literals are not read and
+/// the schema is only parsed, not validated, so this is fast enough to run e.g.
+/// before starting a dev server or in CI.
+pub fn check_schema_version_and_print(config: &CompilerConfig) -> Result<(), BatchCompileError> {
+    eprintln!(
+        "{}",
+        "Checking that artifacts were generated from the current schema.".cyan()
+    );
+
+    match check_schema_version(config) {
+        Ok(artifact_count) => {
+            eprintln!(
+                "{}",
+                format!(
+                    "All {} artifacts were generated from the current schema.\n",
+                    artifact_count
+                )
+                .bright_green()
+            );
+            Ok(())
+        }
+        Err(err) => {
+            eprintln!("{}\n{}", "Artifacts are out of date.\n".bright_red(), err);
+            Err(err)
+        }
+    }
+}
+
+/// Like check_schema_version_and_print, but returns the number of checked artifacts
+/// instead of printing it. Fails on the first artifact (in order of path) that was
+/// generated from a different schema.
+pub fn check_schema_version(config: &CompilerConfig) -> Result<usize, BatchCompileError> {
+    let schema_hash = schema_hash(config)?;
+
+    let mut artifact_paths = vec![];
+    if config.artifact_directory.exists() {
+        visit_files(&config.artifact_directory, &mut |path| {
+            artifact_paths.push(path);
+        })?;
+    }
+    artifact_paths.sort();
+
+    let mut artifact_count = 0;
+    for absolute_path in artifact_paths {
+        let relative_path = absolute_path
+            .strip_prefix(&config.artifact_directory)?
+            .to_path_buf();
+        let file_extension = match relative_path.extension().and_then(|e| e.to_str()) {
+            Some(file_extension @ ("ts" | "json")) => file_extension,
+            _ => continue,
+        };
+        if !is_stamped(&relative_path) {
+            continue;
+        }
+
+        let file_content = std::fs::read_to_string(&absolute_path).map_err(|message| {
+            BatchCompileError::UnableToReadFile {
+                path: absolute_path.clone(),
+                message,
+            }
+        })?;
+        match read_schema_hash(&file_content, file_extension) {
+            Some(artifact_schema_hash) if artifact_schema_hash == schema_hash => {
+                artifact_count += 1;
+            }
+            Some(artifact_schema_hash) => {
+                return Err(BatchCompileError::ArtifactGeneratedFromDifferentSchema {
+                    path: relative_path,
+                    artifact_schema_hash: artifact_schema_hash.to_string(),
+                    schema_hash,
+                });
+            }
+            None => {
+                return Err(BatchCompileError::ArtifactWithoutSchemaHash {
+                    path: relative_path,
+                })
+            }
+        }
+    }
+
+    if artifact_count == 0 {
+        return Err(BatchCompileError::NoArtifacts {
+            path: config.artifact_directory.clone(),
+        });
+    }
+    Ok(artifact_count)
+}
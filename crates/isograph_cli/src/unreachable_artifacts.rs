@@ -0,0 +1,187 @@
+use std::{
+    collections::{HashMap, HashSet},
+    path::{Component, Path, PathBuf},
+};
+
+use colored::Colorize;
+use isograph_config::{ArtifactFormat, CompilerConfig};
+
+use crate::{
+    batch_compile::{create_validated_schema, BatchCompileError},
+    generate_artifacts::{generate_path, get_artifact_path_and_contents, sorted_entrypoints},
+    progress::ProgressReporting,
+};
+
+/// These artifacts are imported by the app itself (e.g. for the iso function), so
+/// they are always reachable. The imports of iso.ts are only used in types, so they
+/// are not followed.
+static IMPORTED_BY_APP: [&str; 2] = ["iso.ts", "enums.ts"];
+
+/// Lists the artifacts that cannot be reached by following the imports of the
+/// entrypoint artifacts of the entrypoints that the app actually imports (as listed
+/// in the used_entrypoints option, or every entrypoint if it is absent), so that
+/// they can be pruned from the build. Only TypeScript artifacts import each other,
+/// so other artifacts (e.g. persisted_queries.json) are not listed.
+///
+/// Nothing is written or deleted.
+pub fn report_unreachable_artifacts_and_print(
+    config: &CompilerConfig,
+) -> Result<Vec<PathBuf>, BatchCompileError> {
+    eprintln!("{}", "Looking for unreachable artifacts.".cyan());
+
+    match find_unreachable_artifacts(config) {
+        Ok(unreachable_artifacts) => {
+            if unreachable_artifacts.is_empty() {
+                eprintln!("{}", "Found no unreachable artifacts.\n".bright_green());
+            } else {
+                eprintln!(
+                    "{}",
+                    format!(
+                        "Found {} unreachable artifact{}:",
+                        unreachable_artifacts.len(),
+                        if unreachable_artifacts.len() == 1 {
+                            ""
+                        } else {
+                            "s"
+                        }
+                    )
+                    .yellow()
+                );
+                for unreachable_artifact in unreachable_artifacts.iter() {
+                    println!("{}", unreachable_artifact.display());
+                }
+            }
+            Ok(unreachable_artifacts)
+        }
+        Err(err) => {
+            eprintln!(
+                "{}\n{}",
+                "Error when looking for unreachable artifacts.\n".bright_red(),
+                err
+            );
+            Err(err)
+        }
+    }
+}
+
+/// Like report_unreachable_artifacts_and_print, but returns the paths of the
+/// unreachable artifacts, relative to the artifact directory, sorted, instead of
+/// printing them.
+pub fn find_unreachable_artifacts(
+    config: &CompilerConfig,
+) -> Result<Vec<PathBuf>, BatchCompileError> {
+    if config.options.artifact_format != ArtifactFormat::TypeScript {
+        return Err(BatchCompileError::UnreachableArtifactsRequireTypeScript);
+    }
+    let (schema, _) = create_validated_schema(config, ProgressReporting::Hidden)?;
+
+    let entrypoints = sorted_entrypoints(&schema);
+    let used_entrypoints = match &config.used_entrypoints {
+        Some(used_entrypoints) => used_entrypoints
+            .read()
+            .map_err(|message| BatchCompileError::UnableToReadUsedEntrypoints { message })?
+            .into_iter()
+            .map(|used_entrypoint| {
+                entrypoints
+                    .iter()
+                    .find(|entrypoint| {
+                        format!(
+                            "{}.{}",
+                            entrypoint.type_and_field.type_name,
+                            entrypoint.type_and_field.field_name
+                        ) == used_entrypoint
+                    })
+                    .copied()
+                    .ok_or(BatchCompileError::UnknownUsedEntrypoint {
+                        entrypoint: used_entrypoint,
+                    })
+            })
+            .collect::<Result<Vec<_>, _>>()?,
+        None => entrypoints,
+    };
+
+    let artifacts = get_artifact_path_and_contents(
+        &schema,
+        &config.project_root,
+        &config.artifact_directory,
+        config.options,
+    )
+    .filter(|path_and_content| path_and_content.file_extension == "ts")
+    .map(|path_and_content| {
+        (
+            path_and_content.relative_directory.join(format!(
+                "{}.{}",
+                path_and_content.file_name_prefix, path_and_content.file_extension
+            )),
+            path_and_content.file_content,
+        )
+    })
+    .collect::<HashMap<_, _>>();
+
+    let mut reachable_artifacts = IMPORTED_BY_APP
+        .iter()
+        .map(PathBuf::from)
+        .collect::<HashSet<_>>();
+    let mut queue = used_entrypoints
+        .iter()
+        .map(|entrypoint| {
+            generate_path(
+                entrypoint.type_and_field.type_name,
+                entrypoint.type_and_field.field_name,
+                entrypoint.type_and_field.artifact_namespace,
+            )
+            .join("entrypoint.ts")
+        })
+        .collect::<Vec<_>>();
+    while let Some(path) = queue.pop() {
+        if !reachable_artifacts.insert(path.clone()) {
+            continue;
+        }
+        if let Some(file_content) = artifacts.get(&path) {
+            let directory = path.parent().unwrap_or(Path::new(""));
+            queue.extend(imported_artifacts(file_content, directory));
+        }
+    }
+
+    let mut unreachable_artifacts = artifacts
+        .into_keys()
+        .filter(|path| !reachable_artifacts.contains(path))
+        .collect::<Vec<_>>();
+    unreachable_artifacts.sort();
+    Ok(unreachable_artifacts)
+}
+
+/// The paths (relative to the artifact directory) of the artifacts that the artifact
+/// in directory imports at runtime. Type-only imports, and imports of packages and
+/// of files outside of the artifact directory (e.g. client field files), are
+/// skipped.
+fn imported_artifacts<'a>(
+    file_content: &'a str,
+    directory: &'a Path,
+) -> impl Iterator<Item = PathBuf> + 'a {
+    file_content.lines().filter_map(move |line| {
+        let line = line.trim_start();
+        if !line.starts_with("import ") || line.starts_with("import type ") {
+            return None;
+        }
+        let module_specifier = line.split(" from '").nth(1)?.split('\'').next()?;
+        if !module_specifier.starts_with('.') {
+            return None;
+        }
+
+        let mut path = directory.to_path_buf();
+        for component in Path::new(module_specifier).components() {
+            match component {
+                Component::CurDir => {}
+                Component::ParentDir => {
+                    if !path.pop() {
+                        return None;
+                    }
+                }
+                component => path.push(component),
+            }
+        }
+        path.set_extension("ts");
+        Some(path)
+    })
+}
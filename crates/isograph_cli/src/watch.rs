@@ -1,18 +1,26 @@
 use colored::Colorize;
 use isograph_config::CompilerConfig;
+use isograph_schema::LintRule;
 use notify::{Error, RecommendedWatcher, RecursiveMode, Watcher};
 use notify_debouncer_full::{
     new_debouncer, DebounceEventResult, DebouncedEvent, Debouncer, FileIdMap,
 };
-use std::{path::PathBuf, time::Duration};
+use std::{
+    path::{Path, PathBuf},
+    time::Duration,
+};
 use tokio::{runtime::Handle, sync::mpsc::Receiver, task::JoinError};
 
-use crate::batch_compile::compile_and_print;
+use crate::{
+    batch_compile::compile_and_print, compiler::Compiler,
+    post_emit_command::post_emit_manifest_path, schema::read_schema_file,
+};
 
-pub(crate) async fn handle_watch_command(
+pub async fn handle_watch_command(
     config: CompilerConfig,
+    lint_rules: Vec<Box<dyn LintRule>>,
 ) -> Result<Result<(), Vec<Error>>, JoinError> {
-    let _ = compile_and_print(&config);
+    let _ = compile_and_print(&config, &lint_rules);
 
     let (mut rx, mut watcher) = create_debounced_file_watcher();
 
@@ -31,17 +39,34 @@ pub(crate) async fn handle_watch_command(
             .watch(&extension, RecursiveMode::Recursive)
             .expect("Failing when watching schema extension");
     }
+    for stitched_schema in &config.stitched_schemas {
+        watcher
+            .watcher()
+            .watch(&stitched_schema.path, RecursiveMode::Recursive)
+            .expect("Failing when watching stitched schema");
+    }
 
     tokio::spawn(async move {
+        // Used to report which client fields are broken or fixed by schema changes,
+        // and kept up to date so that its index reflects the current project.
+        let mut compiler = Compiler::new(&config).ok();
+
         while let Some(res) = rx.recv().await {
             match res {
                 Ok(events) => {
-                    if any_modified_path_is_outside_artifact_directory(
+                    if any_modified_path_is_compiler_input(
                         &events,
                         &config.artifact_directory,
+                        config.build_report.as_deref(),
+                        &post_emit_manifest_path(&config),
                     ) {
                         eprintln!("{}", "File changes detected.".cyan());
-                        let _ = compile_and_print(&config);
+                        if any_modified_path_is_schema(&events, &config.schema) {
+                            report_schema_replacement(&mut compiler, &config);
+                        } else {
+                            refresh_compiler(&mut compiler, &config);
+                        }
+                        let _ = compile_and_print(&config, &lint_rules);
                     }
                 }
                 Err(errors) => return Err(errors),
@@ -52,15 +77,22 @@ pub(crate) async fn handle_watch_command(
     .await
 }
 
-fn any_modified_path_is_outside_artifact_directory(
+/// Whether any modified path is neither in the artifact directory, the build
+/// report nor the post emit manifest, all of which are written by the compiler.
+fn any_modified_path_is_compiler_input(
     events: &[DebouncedEvent],
     artifact_directory: &PathBuf,
+    build_report: Option<&Path>,
+    post_emit_manifest: &Path,
 ) -> bool {
     for event in events.iter() {
         for watched_path in event.paths.iter() {
             // These paths are canonicalized, so it is sufficient
             // to check starts_with
-            if !watched_path.starts_with(artifact_directory) {
+            if !watched_path.starts_with(artifact_directory)
+                && Some(watched_path.as_path()) != build_report
+                && watched_path != post_emit_manifest
+            {
                 return true;
             }
         }
@@ -68,6 +100,50 @@ fn any_modified_path_is_outside_artifact_directory(
     false
 }
 
+fn any_modified_path_is_schema(events: &[DebouncedEvent], schema: &Path) -> bool {
+    events
+        .iter()
+        .any(|event| event.paths.iter().any(|path| path == schema))
+}
+
+fn report_schema_replacement<'config>(
+    compiler: &mut Option<Compiler<'config>>,
+    config: &'config CompilerConfig,
+) {
+    match compiler {
+        Some(compiler) => {
+            let Ok(new_sdl) = read_schema_file(&config.schema, config.options.path_normalization)
+            else {
+                return;
+            };
+            // Errors are reported by the subsequent compilation
+            if let Ok(delta) = compiler.replace_schema(&new_sdl) {
+                eprintln!("{}", delta.to_string().cyan());
+            }
+        }
+        None => {
+            // The previous schema could not be processed, so there is nothing
+            // to compare against.
+            *compiler = Compiler::new(config).ok();
+        }
+    }
+}
+
+fn refresh_compiler<'config>(
+    compiler: &mut Option<Compiler<'config>>,
+    config: &'config CompilerConfig,
+) {
+    match compiler {
+        // Errors are reported by the subsequent compilation
+        Some(compiler) => {
+            let _ = compiler.refresh();
+        }
+        None => {
+            *compiler = Compiler::new(config).ok();
+        }
+    }
+}
+
 fn create_debounced_file_watcher() -> (
     Receiver<Result<Vec<DebouncedEvent>, Vec<Error>>>,
     Debouncer<RecommendedWatcher, FileIdMap>,
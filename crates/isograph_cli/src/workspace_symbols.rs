@@ -0,0 +1,94 @@
+use std::{collections::HashMap, path::PathBuf};
+
+use common_lang_types::Location;
+use isograph_config::CompilerConfig;
+
+use crate::{
+    batch_compile::{read_and_extract_iso_literals_with_unsaved_files, BatchCompileError},
+    compiler::ClientFieldKey,
+    progress::ProgressReporting,
+};
+
+#[derive(Debug, Clone, Copy)]
+pub struct ClientFieldSymbol {
+    pub key: ClientFieldKey,
+    /// The location of the client field's name in its iso literal
+    pub location: Location,
+}
+
+/// Finds the client fields declared in the project whose names (e.g. `User.avatar`)
+/// fuzzily match query, best matches first. A client field matches if the
+/// characters of query appear in order (ignoring case) in its name, and matches
+/// are ranked by how close together those characters are.
+///
+/// Client fields are read from the iso literals in the project, without
+/// processing the schema, so this works even if client fields fail validation.
+pub fn find_client_field_symbols(
+    config: &CompilerConfig,
+    query: &str,
+) -> Result<Vec<ClientFieldSymbol>, BatchCompileError> {
+    find_client_field_symbols_with_unsaved_files(config, query, &HashMap::new())
+}
+
+/// Like find_client_field_symbols, but the contents of the files in unsaved_files
+/// (by absolute path) are used instead of their contents on disk.
+pub(crate) fn find_client_field_symbols_with_unsaved_files(
+    config: &CompilerConfig,
+    query: &str,
+    unsaved_files: &HashMap<PathBuf, String>,
+) -> Result<Vec<ClientFieldSymbol>, BatchCompileError> {
+    let (client_field_declarations, _) = read_and_extract_iso_literals_with_unsaved_files(
+        config,
+        unsaved_files,
+        ProgressReporting::Hidden,
+    )?;
+
+    let mut symbols_and_scores = client_field_declarations
+        .into_iter()
+        .filter_map(|(client_field_declaration, text_source)| {
+            let key = ClientFieldKey {
+                parent_type_name: client_field_declaration.item.parent_type.item,
+                client_field_name: client_field_declaration.item.client_field_name.item,
+            };
+            let score = fuzzy_match_score(query, &key.to_string())?;
+            Some((
+                ClientFieldSymbol {
+                    key,
+                    location: Location::new(
+                        text_source,
+                        client_field_declaration.item.client_field_name.span,
+                    ),
+                },
+                score,
+            ))
+        })
+        .collect::<Vec<_>>();
+    symbols_and_scores.sort_by_key(|(symbol, score)| (*score, symbol.key));
+
+    Ok(symbols_and_scores
+        .into_iter()
+        .map(|(symbol, _)| symbol)
+        .collect())
+}
+
+/// If every character of query appears in candidate, in order and ignoring case,
+/// returns the number of characters of candidate that are skipped between the
+/// first and last matched characters. Lower scores are better matches.
+fn fuzzy_match_score(query: &str, candidate: &str) -> Option<usize> {
+    let mut candidate_chars = candidate.chars().map(|c| c.to_ascii_lowercase());
+    let mut score = 0;
+    let mut has_matched = false;
+    for query_char in query.chars().map(|c| c.to_ascii_lowercase()) {
+        loop {
+            let candidate_char = candidate_chars.next()?;
+            if candidate_char == query_char {
+                has_matched = true;
+                break;
+            }
+            if has_matched {
+                score += 1;
+            }
+        }
+    }
+    Some(score)
+}
@@ -1,23 +1,50 @@
 use std::{
-    fs::{self, File},
-    io::Write,
-    path::PathBuf,
+    collections::HashSet,
+    fs,
+    path::{Path, PathBuf},
 };
 
-use crate::generate_artifacts::{GenerateArtifactsError, PathAndContent};
+use crate::{
+    generate_artifacts::{content_hash, GenerateArtifactsError, PathAndContent},
+    post_emit_command::PostEmitManifest,
+    progress::{ProgressBar, ProgressReporting},
+};
+
+#[derive(Debug, Default)]
+pub(crate) struct WrittenArtifacts {
+    /// The absolute paths of the artifacts whose contents changed (or which did
+    /// not exist), and which were thus written
+    pub written_paths: Vec<PathBuf>,
+    /// The number of artifacts whose contents did not change, and which were thus
+    /// left untouched
+    pub unchanged_count: usize,
+    /// The number of files that were in the artifact directory, but that no
+    /// longer correspond to any artifact, and which were thus deleted
+    pub removed_count: usize,
+    /// The total size of the contents of the artifacts, whether or not they were
+    /// written
+    pub total_bytes: usize,
+}
 
+/// Writes the artifacts whose contents differ from the files in the artifact
+/// directory, and deletes the files (and then empty directories) that do not
+/// correspond to any artifact. Files that are already up to date are not touched,
+/// so that editors and bundlers that watch the artifact directory are not notified
+/// of changes that did not happen.
+///
+/// If post_emit_manifest is passed (i.e. if a post_emit_command rewrites the
+/// artifacts), artifacts are compared with the manifest instead of with the files,
+/// and the manifest is updated with the generated artifacts.
+///
+/// The files at retained_artifact_paths (relative to the artifact directory) were
+/// not generated, because they cannot have changed, and are left as they are.
 pub(crate) fn write_to_disk<'schema>(
     paths_and_contents: impl Iterator<Item = PathAndContent>,
     artifact_directory: &PathBuf,
-) -> Result<usize, GenerateArtifactsError> {
-    if artifact_directory.exists() {
-        fs::remove_dir_all(&artifact_directory).map_err(|e| {
-            GenerateArtifactsError::UnableToDeleteDirectory {
-                path: artifact_directory.clone(),
-                message: e,
-            }
-        })?;
-    }
+    retained_artifact_paths: &[PathBuf],
+    post_emit_manifest: Option<&mut PostEmitManifest>,
+    progress_reporting: ProgressReporting,
+) -> Result<WrittenArtifacts, GenerateArtifactsError> {
     fs::create_dir_all(&artifact_directory).map_err(|e| {
         GenerateArtifactsError::UnableToCreateDirectory {
             path: artifact_directory.clone(),
@@ -25,33 +52,181 @@ pub(crate) fn write_to_disk<'schema>(
         }
     })?;
 
-    let mut count = 0;
+    let mut existing_file_paths = HashSet::new();
+    visit_files(artifact_directory, &mut |path| {
+        existing_file_paths.insert(path);
+    })?;
+
+    // The contents of artifacts are generated lazily, as they are written, so
+    // this reports the progress of generating them, too.
+    let (min_artifact_count, max_artifact_count) = paths_and_contents.size_hint();
+    let mut progress_bar = ProgressBar::new(
+        "Generating artifacts",
+        max_artifact_count.unwrap_or(min_artifact_count),
+        progress_reporting,
+    );
+
+    let mut written_artifacts = WrittenArtifacts::default();
+    let mut retained_absolute_paths = vec![];
+    for retained_artifact_path in retained_artifact_paths {
+        let absolute_file_path = artifact_directory.join(retained_artifact_path);
+        if existing_file_paths.remove(&absolute_file_path) {
+            written_artifacts.unchanged_count += 1;
+            written_artifacts.total_bytes += fs::metadata(&absolute_file_path)
+                .map(|metadata| metadata.len() as usize)
+                .unwrap_or(0);
+            retained_absolute_paths.push(absolute_file_path);
+        }
+    }
+
+    let mut generated_hashes = vec![];
     for path_and_content in paths_and_contents {
-        // Is this better than materializing paths_and_contents sooner?
-        count += 1;
+        let absolute_directory = artifact_directory.join(&path_and_content.relative_directory);
+        let absolute_file_path = absolute_directory.join(artifact_file_name(&path_and_content));
+        written_artifacts.total_bytes += path_and_content.file_content.len();
 
-        let absolute_directory = artifact_directory.join(path_and_content.relative_directory);
-        fs::create_dir_all(&absolute_directory).map_err(|e| {
-            GenerateArtifactsError::UnableToCreateDirectory {
-                path: absolute_directory.clone(),
+        let generated_hash = post_emit_manifest
+            .is_some()
+            .then(|| content_hash(path_and_content.file_content.as_bytes()));
+        let is_unchanged = existing_file_paths.remove(&absolute_file_path)
+            && match (post_emit_manifest.as_deref(), &generated_hash) {
+                (Some(post_emit_manifest), Some(generated_hash)) => {
+                    post_emit_manifest.is_unchanged(&absolute_file_path, generated_hash)
+                }
+                _ => fs::read(&absolute_file_path)
+                    .map(|existing_content| {
+                        existing_content == path_and_content.file_content.as_bytes()
+                    })
+                    .unwrap_or(false),
+            };
+        if let Some(generated_hash) = generated_hash {
+            generated_hashes.push((absolute_file_path.clone(), generated_hash, is_unchanged));
+        }
+        if is_unchanged {
+            written_artifacts.unchanged_count += 1;
+        } else {
+            fs::create_dir_all(&absolute_directory).map_err(|e| {
+                GenerateArtifactsError::UnableToCreateDirectory {
+                    path: absolute_directory.clone(),
+                    message: e,
+                }
+            })?;
+            fs::write(
+                &absolute_file_path,
+                path_and_content.file_content.as_bytes(),
+            )
+            .map_err(|e| GenerateArtifactsError::UnableToWriteToArtifactFile {
+                path: absolute_file_path.clone(),
                 message: e,
-            }
-        })?;
+            })?;
+            written_artifacts.written_paths.push(absolute_file_path);
+        }
+        progress_bar.increment();
+    }
 
-        let absolute_file_path =
-            absolute_directory.join(&format!("{}.ts", path_and_content.file_name_prefix));
-        let mut file = File::create(&absolute_file_path).map_err(|e| {
-            GenerateArtifactsError::UnableToWriteToArtifactFile {
-                path: absolute_file_path.clone(),
+    // Whatever remains does not correspond to any artifact, e.g. the artifacts of
+    // client fields that have since been deleted or renamed.
+    for stale_file_path in existing_file_paths {
+        fs::remove_file(&stale_file_path).map_err(|e| {
+            GenerateArtifactsError::UnableToDeleteArtifactFile {
+                path: stale_file_path.clone(),
                 message: e,
             }
         })?;
+        written_artifacts.removed_count += 1;
+    }
+    remove_empty_directories(artifact_directory)?;
 
-        file.write(path_and_content.file_content.as_bytes())
-            .map_err(|e| GenerateArtifactsError::UnableToWriteToArtifactFile {
-                path: absolute_file_path.clone(),
-                message: e,
-            })?;
+    if let Some(post_emit_manifest) = post_emit_manifest {
+        post_emit_manifest
+            .replace_artifacts(generated_hashes.into_iter(), &retained_absolute_paths);
+    }
+
+    Ok(written_artifacts)
+}
+
+/// Removes the empty directories within dir, but not dir itself.
+fn remove_empty_directories(dir: &Path) -> Result<(), GenerateArtifactsError> {
+    let read_dir_error = |message| GenerateArtifactsError::UnableToReadDirectory {
+        path: dir.to_path_buf(),
+        message,
+    };
+    for entry in fs::read_dir(dir).map_err(read_dir_error)? {
+        let path = entry.map_err(read_dir_error)?.path();
+        if path.is_dir() {
+            remove_empty_directories(&path)?;
+            let is_empty = fs::read_dir(&path)
+                .map_err(|message| GenerateArtifactsError::UnableToReadDirectory {
+                    path: path.clone(),
+                    message,
+                })?
+                .next()
+                .is_none();
+            if is_empty {
+                fs::remove_dir(&path).map_err(|e| {
+                    GenerateArtifactsError::UnableToDeleteDirectory {
+                        path: path.clone(),
+                        message: e,
+                    }
+                })?;
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Files in the artifact directory which do not correspond to any of
+/// paths_and_contents, e.g. the artifacts of client fields that have since been
+/// deleted or renamed.
+pub(crate) fn find_orphaned_files(
+    paths_and_contents: impl Iterator<Item = PathAndContent>,
+    artifact_directory: &PathBuf,
+) -> Result<Vec<PathBuf>, GenerateArtifactsError> {
+    if !artifact_directory.exists() {
+        return Ok(vec![]);
+    }
+
+    let expected_file_paths = paths_and_contents
+        .map(|path_and_content| {
+            artifact_directory
+                .join(&path_and_content.relative_directory)
+                .join(artifact_file_name(&path_and_content))
+        })
+        .collect::<HashSet<_>>();
+
+    let mut orphaned_files = vec![];
+    visit_files(artifact_directory, &mut |path| {
+        if !expected_file_paths.contains(&path) {
+            orphaned_files.push(path);
+        }
+    })?;
+    orphaned_files.sort();
+
+    Ok(orphaned_files)
+}
+
+fn artifact_file_name(path_and_content: &PathAndContent) -> String {
+    format!(
+        "{}.{}",
+        path_and_content.file_name_prefix, path_and_content.file_extension
+    )
+}
+
+pub(crate) fn visit_files(
+    dir: &Path,
+    cb: &mut dyn FnMut(PathBuf),
+) -> Result<(), GenerateArtifactsError> {
+    let read_dir_error = |message| GenerateArtifactsError::UnableToReadDirectory {
+        path: dir.to_path_buf(),
+        message,
+    };
+    for entry in fs::read_dir(dir).map_err(read_dir_error)? {
+        let path = entry.map_err(read_dir_error)?.path();
+        if path.is_dir() {
+            visit_files(&path, cb)?;
+        } else {
+            cb(path);
+        }
     }
-    Ok(count)
+    Ok(())
 }
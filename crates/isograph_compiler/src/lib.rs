@@ -0,0 +1,66 @@
+//! The stable public API of the Isograph compiler.
+//!
+//! The compiler is split across many crates, whose layout is an implementation
+//! detail. External tooling should depend on this crate, which re-exports the
+//! parts of those crates that are meant to be used from the outside.
+
+// Reading the config file
+pub use isograph_config::{
+    apply_config_overrides, create_config, find_config_file, ArtifactFormat,
+    ClientFieldNamingConventions, CompilerConfig, ComplexityBudget, ConfigOptions, ConfigOverrides,
+    CustomScalarModule, CustomScalarType, EnumRepresentation, FieldOrdering, NameCase,
+    NormalizationAliasStrategy, OptionalValidationLevel, PathNormalization, PersistedQueries,
+    PostEmitCommand, StitchedSchema, UsedEntrypoints,
+};
+
+// Parsing GraphQL schemas and schema extensions
+pub use graphql_lang_types::{GraphQLTypeSystemDocument, GraphQLTypeSystemExtensionDocument};
+pub use graphql_schema_parser::{
+    parse_introspection_json, parse_schema, parse_schema_extensions, SchemaParseError,
+};
+
+// Parsing iso literals
+pub use isograph_lang_parser::{
+    format_iso_literal, parse_iso_literal, print_client_field_declaration,
+    print_entrypoint_declaration, semantic_tokens, IsoLiteralExtractionResult,
+    IsographLiteralParseError, SemanticTokenKind,
+};
+pub use isograph_lang_types::{ClientFieldDeclaration, EntrypointTypeAndField};
+
+// Building and validating the schema
+pub use isograph_schema::{
+    check_client_field_naming_conventions, check_forbidden_server_fields, check_lint_rules, Entity,
+    ForbiddenServerFieldError, LintRule, LintRuleError, NamingConventionError, OperationComplexity,
+    ProcessClientFieldDeclarationError, ProcessGraphQLDocumentOutcome, ProcessTypeDefinitionError,
+    Schema, UnvalidatedSchema, ValidateEntrypointDeclarationError, ValidateSchemaError,
+    ValidatedSchema,
+};
+
+// Analyzing merged selection sets, i.e. the selections of a client field and of the
+// client fields it selects, e.g. for bundle analyzers or data dependency visualizers.
+// The merged selection set of each client field is available on the validated
+// schema, and create_merged_selection_set_with_origins records which client fields
+// contributed each selection.
+pub use isograph_schema::{
+    create_merged_selection_set_with_origins, ClientFieldMergedSelectionSet,
+    MergedDeferredFragmentSelection, MergedInlineFragmentSelection, MergedLinkedFieldSelection,
+    MergedScalarFieldSelection, MergedSelectionOrigins, MergedSelectionSet,
+    MergedServerFieldSelection, MergedStreamDirective, RootRefetchedPath,
+};
+
+// Generating artifacts and driving compilation. Only entry points that return their
+// results without printing them are re-exported; the command line is the only
+// place where results are printed.
+pub use isograph_cli::{
+    check_schema_version, compile_projects, compile_with_cache, find_client_field_symbols,
+    find_unreachable_artifacts, format_iso_literals_in_file_content, generate_and_write_artifacts,
+    handle_compile_command, serve_language_client, BatchCompileError, ClientFieldKey,
+    ClientFieldSymbol, CompilationStats, Compiler, GenerateArtifactsError, GeneratedArtifactsStats,
+    IndexedClientField, MemoryUsage, OperationComplexityReport, OversizedArtifactReport,
+    ProjectIndex, SchemaReplacementDelta, ServerFieldKey, WithDuration,
+};
+
+// Diagnostics
+pub use common_lang_types::{
+    EmbeddedLocation, Location, Span, TextSource, WithEmbeddedLocation, WithLocation, WithSpan,
+};
@@ -1,5 +1,9 @@
-use std::path::PathBuf;
+use std::{
+    collections::HashMap,
+    path::{Component, Path, PathBuf},
+};
 
+use regex::Regex;
 use serde::Deserialize;
 
 pub static ISOGRAPH_FOLDER: &'static str = "__isograph";
@@ -8,8 +12,10 @@ use std::error::Error;
 
 use colorize::AnsiColor;
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct CompilerConfig {
+    /// The absolute path to the config file
+    pub config_file: PathBuf,
     /// The folder where the compiler should look for Isograph literals
     pub project_root: PathBuf,
     /// The folder where the compiler should create artifacts
@@ -18,14 +24,300 @@ pub struct CompilerConfig {
     pub schema: PathBuf,
     /// The absolute path to the schema extensions
     pub schema_extensions: Vec<PathBuf>,
+    /// The schemas of other services, which are stitched into the schema
+    pub stitched_schemas: Vec<StitchedSchema>,
+    /// The level at which each lint rule is reported, by name. Lint rules that
+    /// are not mentioned are reported as errors.
+    pub lint_rules: HashMap<String, OptionalValidationLevel>,
+    /// The extensions (e.g. `vue` or `svelte`) of single file components. Only
+    /// the `<script>` blocks of files with these extensions are searched for
+    /// Isograph literals.
+    pub single_file_component_extensions: Vec<String>,
+    /// The absolute paths to the roots of packages, by name. The artifacts of the
+    /// client fields declared in a package are namespaced by its name.
+    pub packages: HashMap<String, PathBuf>,
+    /// The conventions that the names of client fields must follow
+    pub client_field_naming: ClientFieldNamingConventions,
+    /// The TypeScript types of custom scalars, by scalar name. Scalars that are
+    /// not mentioned are typed as strings.
+    pub custom_scalar_types: HashMap<String, CustomScalarType>,
+    /// Server fields (e.g. `User.ssn`) that client fields must not select
+    pub forbidden_server_fields: Vec<String>,
+    /// The absolute path to which a report of every successful compilation (its
+    /// time and the fingerprints of its inputs) is written. Such information is
+    /// kept out of artifacts, so that artifacts are reproducible.
+    pub build_report: Option<PathBuf>,
+    /// A command that is run after every compilation that writes artifacts, e.g.
+    /// to format them
+    pub post_emit_command: Option<PostEmitCommand>,
+    /// The entrypoints that the app actually imports, from which the artifacts that
+    /// are reachable are determined. If None, every entrypoint is considered used.
+    pub used_entrypoints: Option<UsedEntrypoints>,
 
     /// Various options that are of lesser importance
     pub options: ConfigOptions,
 }
 
+/// The entrypoints (e.g. `Query.HomePage`) that the app actually imports.
+#[derive(Debug, Clone)]
+pub enum UsedEntrypoints {
+    /// Listed in the config
+    List(Vec<String>),
+    /// The absolute path to a JSON manifest containing an array of entrypoints,
+    /// e.g. written by a bundler plugin. It is read when it is needed, as it may be
+    /// written after the config is read.
+    Manifest(PathBuf),
+}
+
+impl UsedEntrypoints {
+    /// Returns the used entrypoints, reading the manifest if necessary, or a
+    /// message describing why they cannot be read.
+    pub fn read(&self) -> Result<Vec<String>, String> {
+        match self {
+            UsedEntrypoints::List(entrypoints) => Ok(entrypoints.clone()),
+            UsedEntrypoints::Manifest(path) => {
+                let contents = std::fs::read_to_string(path).map_err(|e| e.to_string())?;
+                serde_json::from_str(&contents).map_err(|e| e.to_string())
+            }
+        }
+    }
+}
+
+/// A command that is run with the absolute paths of the artifacts that were written
+/// appended to its arguments, e.g. `prettier --write <paths>`.
+#[derive(Debug, Clone)]
+pub struct PostEmitCommand {
+    pub program: String,
+    pub arguments: Vec<String>,
+    /// The directory of the config file, in which the command is run
+    pub working_directory: PathBuf,
+}
+
 #[derive(Default, Debug, Clone, Copy)]
 pub struct ConfigOptions {
     pub on_invalid_id_type: OptionalValidationLevel,
+    /// Whether repeated subtrees in generated query texts should be factored
+    /// out into named fragments
+    pub generate_named_fragments: bool,
+    /// Whether generated query texts should have the characters that GraphQL
+    /// ignores (whitespace, commas, etc.) stripped, producing minimal query texts
+    pub minify_query_text: bool,
+    /// How the aliases of fields with arguments are synthesized in generated
+    /// query texts
+    pub normalization_alias_strategy: NormalizationAliasStrategy,
+    /// Whether reader artifacts should contain a function that asserts that
+    /// the data read from the store has every field the reader AST selects.
+    /// Meant for development builds.
+    pub generate_dev_assertions: bool,
+    /// Whether a JSON file listing every server field that each client field
+    /// requires, directly or transitively, should be generated next to its
+    /// reader artifact
+    pub generate_data_requirements: bool,
+    /// Whether a refetch query (selecting node(id: $id)) should be generated for
+    /// each client field on a type with an id field, next to its reader artifact
+    pub generate_refetch_queries: bool,
+    /// Whether entrypoint and refetch query artifacts should contain a comment
+    /// with the complexity metrics of their operation
+    pub include_complexity_comments: bool,
+    /// Limits on the complexity of generated operations
+    pub complexity_budget: ComplexityBudget,
+    /// The format in which artifacts are generated
+    pub artifact_format: ArtifactFormat,
+    /// How GraphQL enums are represented in generated TypeScript types
+    pub enum_representation: EnumRepresentation,
+    /// The order of the properties of generated TypeScript types
+    pub field_ordering: FieldOrdering,
+    /// Whether every server field should be typed as possibly null or undefined
+    /// in generated types, regardless of its nullability in the schema. Meant for
+    /// servers that do not respect the nullability declared in their schema.
+    pub treat_server_fields_as_nullable: bool,
+    /// Whether the approximate memory usage of the compilation should be
+    /// printed after every compilation
+    pub report_memory_usage: bool,
+    /// If the peak memory usage of the compiler exceeds this many megabytes, a
+    /// warning is issued. Compilation does not fail.
+    pub memory_budget_megabytes: Option<usize>,
+    /// If a generated artifact is larger than this many bytes, a warning is
+    /// issued. Compilation does not fail.
+    pub max_artifact_size_bytes: Option<usize>,
+    /// How the paths in the config are made absolute
+    pub path_normalization: PathNormalization,
+    /// If present, a manifest mapping the SHA-256 hash of each generated query
+    /// text to the query text is written to the artifact directory
+    pub persisted_queries: Option<PersistedQueries>,
+}
+
+#[derive(Default, Debug, Clone, Copy)]
+pub struct ComplexityBudget {
+    /// The maximum number of fields an operation may select
+    pub max_field_count: Option<usize>,
+    /// The maximum depth of nested linked fields in an operation
+    pub max_depth: Option<usize>,
+    /// The maximum estimated number of fields in the response to an operation
+    pub max_estimated_response_size: Option<usize>,
+    /// What happens when an operation exceeds any of the limits
+    pub on_exceeded: OptionalValidationLevel,
+}
+
+#[derive(Default, Debug, Clone, Copy)]
+pub struct PersistedQueries {
+    /// Whether entrypoint and refetch query artifacts should contain only the
+    /// hash of their query text, for servers that only accept persisted queries
+    pub persist: bool,
+}
+
+#[derive(Debug, Clone)]
+pub struct StitchedSchema {
+    /// The absolute path to the schema
+    pub path: PathBuf,
+    /// If present, the names of the types defined in this schema (other than its
+    /// root types and the built-in scalars) are prefixed with it, so that they do
+    /// not clash with the types of other schemas
+    pub type_prefix: Option<String>,
+}
+
+#[derive(Debug, Clone)]
+pub struct CustomScalarType {
+    /// The TypeScript type, e.g. `unknown` or `DateTime`
+    pub typescript_type: String,
+    /// The module from which typescript_type is imported, if it is not a global
+    /// type
+    pub import_from: Option<CustomScalarModule>,
+    /// How literals of this scalar that are passed as arguments in iso literals
+    /// are canonicalized and validated
+    pub literals: CustomScalarLiterals,
+}
+
+#[derive(Default, Debug, Clone)]
+pub struct CustomScalarLiterals {
+    /// Whether leading and trailing whitespace is removed from string literals
+    /// before they are serialized into query texts
+    pub trim: bool,
+    /// If present, literals (after trimming, and as written, i.e. with escape
+    /// sequences) must match this pattern in full
+    pub pattern: Option<Regex>,
+}
+
+#[derive(Debug, Clone)]
+pub enum CustomScalarModule {
+    /// A module specifier that is not relative, e.g. `luxon`, which is used as is
+    Package(String),
+    /// The absolute path to a file in the project, which is imported with a
+    /// relative path from every artifact
+    Path(PathBuf),
+}
+
+#[derive(Default, Debug, Clone)]
+pub struct ClientFieldNamingConventions {
+    /// The case of the names of client fields, other than components
+    pub case: Option<NameCase>,
+    /// The case of the names of client fields with @component
+    pub component_case: Option<NameCase>,
+    /// Prefixes with which the names of client fields must not start
+    pub reserved_prefixes: Vec<String>,
+    /// Whether the name of a client field may differ from the name of a server
+    /// field of the same type only in case, e.g. `fullname` and `fullName`
+    pub forbid_case_insensitive_collisions: bool,
+    /// What happens when a client field violates any of the conventions
+    pub on_violation: OptionalValidationLevel,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum NameCase {
+    /// e.g. `fullName`
+    CamelCase,
+    /// e.g. `FullName`
+    PascalCase,
+    /// e.g. `full_name`
+    SnakeCase,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum NormalizationAliasStrategy {
+    /// Aliases contain the field name and arguments, e.g. `user____id___v_id`,
+    /// which makes network payloads easy to read
+    Readable,
+    /// Aliases are short hashes of the readable alias, which minimizes the size
+    /// of query texts and network payloads
+    Hashed,
+    /// Fields are not aliased, for servers that do not support aliases (e.g. in
+    /// persisted operations). Selecting a field with different arguments on the
+    /// same record is an error.
+    None,
+}
+
+impl Default for NormalizationAliasStrategy {
+    fn default() -> Self {
+        Self::Readable
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ArtifactFormat {
+    /// Artifacts are TypeScript modules, which are consumed by the JavaScript
+    /// runtime
+    TypeScript,
+    /// Artifacts are JSON files containing the reader ASTs, normalization ASTs
+    /// and query texts, which can be consumed by non-JavaScript runtimes. No
+    /// TypeScript is generated.
+    Json,
+}
+
+impl Default for ArtifactFormat {
+    fn default() -> Self {
+        Self::TypeScript
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EnumRepresentation {
+    /// Enums are unions of string literal types, e.g. `"RED" | "GREEN"`
+    StringLiteralUnion,
+    /// Enums are objects declared with `as const`, along with a type of the
+    /// same name that is the union of their values
+    ConstObject,
+    /// Enums are TypeScript enums
+    TypeScriptEnum,
+}
+
+impl Default for EnumRepresentation {
+    fn default() -> Self {
+        Self::StringLiteralUnion
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FieldOrdering {
+    /// Properties are in the order in which the fields are selected
+    Selection,
+    /// Properties are in the order in which the fields are defined in the
+    /// schema, followed by client fields
+    Schema,
+    /// Properties are sorted alphabetically by name (or alias, if aliased)
+    Alphabetical,
+}
+
+impl Default for FieldOrdering {
+    fn default() -> Self {
+        Self::Selection
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PathNormalization {
+    /// Paths are canonicalized, which resolves symlinks
+    Canonicalize,
+    /// `.` and `..` segments are removed without accessing the file system, so
+    /// symlinks are preserved. Meant for setups in which the project is a symlink
+    /// farm (e.g. pnpm or Bazel), where resolving symlinks would produce import
+    /// paths that point outside of the workspace.
+    Lexical,
+}
+
+impl Default for PathNormalization {
+    fn default() -> Self {
+        Self::Canonicalize
+    }
 }
 
 #[derive(Debug, Clone, Copy)]
@@ -76,12 +368,48 @@ struct ConfigFile {
     /// The relative path to schema extensions
     #[serde(default)]
     pub schema_extensions: Vec<PathBuf>,
+    /// The schemas of other services, which are stitched into the schema
+    #[serde(default)]
+    pub stitched_schemas: Vec<ConfigFileStitchedSchema>,
+    /// The level at which each lint rule is reported, by name
+    #[serde(default)]
+    pub lint_rules: HashMap<String, ConfigFileOptionalValidationLevel>,
+    /// The extensions of single file components, without the leading period
+    #[serde(default)]
+    pub single_file_component_extensions: Vec<String>,
+    /// The relative paths to the roots of packages, by name
+    #[serde(default)]
+    pub packages: HashMap<String, PathBuf>,
+    /// The conventions that the names of client fields must follow
+    #[serde(default)]
+    pub client_field_naming: ConfigFileClientFieldNamingConventions,
+    /// The TypeScript types of custom scalars, by scalar name
+    #[serde(default)]
+    pub custom_scalar_types: HashMap<String, ConfigFileCustomScalarType>,
+    /// Server fields (e.g. `User.ssn`) that client fields must not select
+    #[serde(default)]
+    pub forbidden_server_fields: Vec<String>,
+    /// The relative path to which a build report is written
+    pub build_report: Option<PathBuf>,
+    /// The program and arguments of a command that is run after every compilation
+    /// that writes artifacts, e.g. `["prettier", "--write"]`
+    pub post_emit_command: Option<Vec<String>>,
+    /// The entrypoints that the app actually imports, or the relative path to a
+    /// JSON manifest that lists them
+    pub used_entrypoints: Option<ConfigFileUsedEntrypoints>,
 
     /// Various that are of lesser importance
     #[serde(default = "Default::default")]
     pub options: ConfigFileOptions,
 }
 
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum ConfigFileUsedEntrypoints {
+    List(Vec<String>),
+    Manifest(PathBuf),
+}
+
 pub fn create_config(mut config_location: PathBuf) -> CompilerConfig {
     let config_contents = match std::fs::read_to_string(&config_location) {
         Ok(contents) => contents,
@@ -98,8 +426,11 @@ pub fn create_config(mut config_location: PathBuf) -> CompilerConfig {
     let config_parsed: ConfigFile = serde_json::from_str(&config_contents)
         .unwrap_or_else(|e| panic!("Error parsing config. Error: {}", e));
 
+    let config_file = config_location.clone();
     config_location.pop();
     let config_dir = config_location;
+    let options = create_options(config_parsed.options);
+    let normalize = |path: &Path| normalize_path(path, options.path_normalization);
 
     let artifact_dir = config_dir
         .join(
@@ -115,35 +446,267 @@ pub fn create_config(mut config_location: PathBuf) -> CompilerConfig {
     std::fs::create_dir_all(&project_root_dir).expect("Unable to create project root directory");
 
     CompilerConfig {
-        project_root: project_root_dir.canonicalize().expect(&format!(
+        config_file: normalize(&config_file).expect(&format!(
+            "Unable to canonicalize config file path at {:?}.",
+            config_file
+        )),
+        project_root: normalize(&project_root_dir).expect(&format!(
             "Unable to canonicalize project root at {:?}.",
             config_parsed.project_root
         )),
-        artifact_directory: artifact_dir.canonicalize().expect(&format!(
+        artifact_directory: normalize(&artifact_dir).expect(&format!(
             "Unable to canonicalize artifact directory at {:?}.",
             config_parsed.artifact_directory
         )),
-        schema: config_dir
-            .join(&config_parsed.schema)
-            .canonicalize()
-            .expect(&format!(
-                "Unable to canonicalize schema path. Does {:?} exist?",
+        // The schema need not exist yet, e.g. before it is pulled from an endpoint
+        schema: normalize_file_path(&config_dir.join(&config_parsed.schema), &normalize).expect(
+            &format!(
+                "Unable to canonicalize schema path. Does the directory of {:?} exist?",
                 config_parsed.schema
-            )),
+            ),
+        ),
         schema_extensions: config_parsed
             .schema_extensions
             .into_iter()
             .map(|schema_extension| {
-                config_dir
-                    .join(&schema_extension)
-                    .canonicalize()
-                    .expect(&format!(
-                        "Unable to canonicalize schema extension path. Does {:?} exist?",
-                        schema_extension
-                    ))
+                normalize(&config_dir.join(&schema_extension)).expect(&format!(
+                    "Unable to canonicalize schema extension path. Does {:?} exist?",
+                    schema_extension
+                ))
+            })
+            .collect(),
+        stitched_schemas: config_parsed
+            .stitched_schemas
+            .into_iter()
+            .map(|stitched_schema| StitchedSchema {
+                path: normalize(&config_dir.join(&stitched_schema.path)).expect(&format!(
+                    "Unable to canonicalize stitched schema path. Does {:?} exist?",
+                    stitched_schema.path
+                )),
+                type_prefix: stitched_schema.type_prefix,
+            })
+            .collect(),
+        lint_rules: config_parsed
+            .lint_rules
+            .into_iter()
+            .map(|(rule_name, level)| (rule_name, create_optional_validation_level(level)))
+            .collect(),
+        single_file_component_extensions: config_parsed.single_file_component_extensions,
+        packages: config_parsed
+            .packages
+            .into_iter()
+            .map(|(package_name, package_root)| {
+                let package_root = normalize(&config_dir.join(&package_root)).expect(&format!(
+                    "Unable to canonicalize the root of package {}. Does {:?} exist?",
+                    package_name, package_root
+                ));
+                (package_name, package_root)
             })
             .collect(),
-        options: create_options(config_parsed.options),
+        client_field_naming: create_client_field_naming_conventions(
+            config_parsed.client_field_naming,
+        ),
+        custom_scalar_types: config_parsed
+            .custom_scalar_types
+            .into_iter()
+            .map(|(scalar_name, custom_scalar_type)| {
+                let import_from = custom_scalar_type.import_from.map(|import_from| {
+                    if import_from.starts_with('.') {
+                        CustomScalarModule::Path(normalize(&config_dir.join(&import_from)).expect(
+                            &format!(
+                                "Unable to canonicalize the module of custom scalar {}. \
+                                Does {:?} exist?",
+                                scalar_name, import_from
+                            ),
+                        ))
+                    } else {
+                        CustomScalarModule::Package(import_from)
+                    }
+                });
+                let literals = CustomScalarLiterals {
+                    trim: custom_scalar_type.literals.trim,
+                    pattern: custom_scalar_type.literals.pattern.map(|pattern| {
+                        Regex::new(&format!("^(?:{pattern})$")).expect(&format!(
+                            "Invalid literal pattern for custom scalar {}: {:?}.",
+                            scalar_name, pattern
+                        ))
+                    }),
+                };
+                (
+                    scalar_name,
+                    CustomScalarType {
+                        typescript_type: custom_scalar_type.typescript_type,
+                        import_from,
+                        literals,
+                    },
+                )
+            })
+            .collect(),
+        forbidden_server_fields: config_parsed.forbidden_server_fields,
+        build_report: config_parsed.build_report.map(|build_report| {
+            let build_report = config_dir.join(&build_report);
+            let build_report_dir = build_report
+                .parent()
+                .expect("Expected build report to be a file");
+            std::fs::create_dir_all(build_report_dir)
+                .expect("Unable to create build report directory");
+            normalize(build_report_dir)
+                .expect(&format!(
+                    "Unable to canonicalize build report directory at {:?}.",
+                    build_report_dir
+                ))
+                .join(
+                    build_report
+                        .file_name()
+                        .expect("Expected build report to be a file"),
+                )
+        }),
+        post_emit_command: config_parsed
+            .post_emit_command
+            .map(|mut post_emit_command| {
+                if post_emit_command.is_empty() {
+                    panic!("Expected post_emit_command to contain at least the program to run.");
+                }
+                let program = post_emit_command.remove(0);
+                PostEmitCommand {
+                    program,
+                    arguments: post_emit_command,
+                    working_directory: normalize(&config_dir.join(".")).expect(&format!(
+                        "Unable to canonicalize the directory of the config at {:?}.",
+                        config_dir
+                    )),
+                }
+            }),
+        used_entrypoints: config_parsed.used_entrypoints.map(|used_entrypoints| {
+            match used_entrypoints {
+                ConfigFileUsedEntrypoints::List(entrypoints) => UsedEntrypoints::List(entrypoints),
+                ConfigFileUsedEntrypoints::Manifest(manifest) => UsedEntrypoints::Manifest(
+                    normalize_file_path(&config_dir.join(&manifest), &normalize).expect(&format!(
+                        "Unable to canonicalize the directory of the used entrypoints manifest \
+                        at {:?}.",
+                        manifest
+                    )),
+                ),
+            }
+        }),
+        options,
+    }
+}
+
+/// Normalizes the path of a file, which need not exist. If it does not, only its
+/// directory is normalized.
+fn normalize_file_path(
+    file_path: &Path,
+    normalize: impl Fn(&Path) -> std::io::Result<PathBuf>,
+) -> std::io::Result<PathBuf> {
+    if file_path.exists() {
+        return normalize(file_path);
+    }
+    let file_name = file_path
+        .file_name()
+        .ok_or_else(|| std::io::Error::new(std::io::ErrorKind::InvalidInput, "Expected a file"))?;
+    let directory = file_path.parent().unwrap_or(Path::new(""));
+    Ok(normalize(&directory.join("."))?.join(file_name))
+}
+
+pub static CONFIG_FILE_NAME: &'static str = "isograph.config.json";
+
+/// Looks for a config file in the given directory and then in each of its
+/// ancestors, and returns the path to the first one found.
+pub fn find_config_file(start_directory: &Path) -> Option<PathBuf> {
+    start_directory
+        .ancestors()
+        .map(|directory| directory.join(CONFIG_FILE_NAME))
+        .find(|config_location| config_location.is_file())
+}
+
+/// Values passed on the command line, which take precedence over the values in
+/// the config file. Relative paths are relative to the current working directory,
+/// not to the config file.
+#[derive(Debug, Default, Clone)]
+pub struct ConfigOverrides {
+    pub project_root: Option<PathBuf>,
+    pub artifact_directory: Option<PathBuf>,
+    pub schema: Option<PathBuf>,
+    /// If not empty, these replace (rather than add to) the schema extensions in
+    /// the config file
+    pub schema_extensions: Vec<PathBuf>,
+}
+
+pub fn apply_config_overrides(config: &mut CompilerConfig, overrides: ConfigOverrides) {
+    let current_dir = std::env::current_dir().expect("Unable to get current directory");
+    let path_normalization = config.options.path_normalization;
+    let normalize = |path: &Path| normalize_path(&current_dir.join(path), path_normalization);
+
+    if let Some(project_root) = overrides.project_root {
+        std::fs::create_dir_all(current_dir.join(&project_root))
+            .expect("Unable to create project root directory");
+        config.project_root = normalize(&project_root).expect(&format!(
+            "Unable to canonicalize project root at {:?}.",
+            project_root
+        ));
+    }
+    if let Some(artifact_directory) = overrides.artifact_directory {
+        let artifact_directory = artifact_directory.join(&*ISOGRAPH_FOLDER);
+        std::fs::create_dir_all(current_dir.join(&artifact_directory))
+            .expect("Unable to create artifact directory");
+        config.artifact_directory = normalize(&artifact_directory).expect(&format!(
+            "Unable to canonicalize artifact directory at {:?}.",
+            artifact_directory
+        ));
+    }
+    if let Some(schema) = overrides.schema {
+        config.schema = normalize(&schema).expect(&format!(
+            "Unable to canonicalize schema path. Does {:?} exist?",
+            schema
+        ));
+    }
+    if !overrides.schema_extensions.is_empty() {
+        config.schema_extensions = overrides
+            .schema_extensions
+            .iter()
+            .map(|schema_extension| {
+                normalize(schema_extension).expect(&format!(
+                    "Unable to canonicalize schema extension path. Does {:?} exist?",
+                    schema_extension
+                ))
+            })
+            .collect();
+    }
+}
+
+/// Makes path absolute according to path_normalization. Fails if nothing exists
+/// at the path. Paths that the compiler reads (e.g. of source files) must be
+/// normalized in the same way as the paths in the config, so that they can be
+/// compared with them.
+pub fn normalize_path(
+    path: &Path,
+    path_normalization: PathNormalization,
+) -> std::io::Result<PathBuf> {
+    match path_normalization {
+        PathNormalization::Canonicalize => path.canonicalize(),
+        PathNormalization::Lexical => {
+            // Joining an absolute path replaces the current directory
+            let absolute_path = std::env::current_dir()?.join(path);
+            let mut normalized_path = PathBuf::new();
+            for component in absolute_path.components() {
+                match component {
+                    Component::CurDir => {}
+                    Component::ParentDir => {
+                        normalized_path.pop();
+                    }
+                    component => normalized_path.push(component),
+                }
+            }
+            if normalized_path.exists() {
+                Ok(normalized_path)
+            } else {
+                Err(std::io::Error::new(
+                    std::io::ErrorKind::NotFound,
+                    format!("{:?} does not exist", normalized_path),
+                ))
+            }
+        }
     }
 }
 
@@ -151,6 +714,83 @@ pub fn create_config(mut config_location: PathBuf) -> CompilerConfig {
 #[serde(default, deny_unknown_fields)]
 struct ConfigFileOptions {
     on_invalid_id_type: ConfigFileOptionalValidationLevel,
+    generate_named_fragments: bool,
+    minify_query_text: bool,
+    normalization_alias_strategy: ConfigFileNormalizationAliasStrategy,
+    generate_dev_assertions: bool,
+    generate_data_requirements: bool,
+    generate_refetch_queries: bool,
+    include_complexity_comments: bool,
+    complexity_budget: ConfigFileComplexityBudget,
+    artifact_format: ConfigFileArtifactFormat,
+    enum_representation: ConfigFileEnumRepresentation,
+    field_ordering: ConfigFileFieldOrdering,
+    treat_server_fields_as_nullable: bool,
+    report_memory_usage: bool,
+    memory_budget_megabytes: Option<usize>,
+    max_artifact_size_bytes: Option<usize>,
+    path_normalization: ConfigFilePathNormalization,
+    persisted_queries: Option<ConfigFilePersistedQueries>,
+}
+
+#[derive(Deserialize, Default)]
+#[serde(default, deny_unknown_fields)]
+struct ConfigFileComplexityBudget {
+    max_field_count: Option<usize>,
+    max_depth: Option<usize>,
+    max_estimated_response_size: Option<usize>,
+    on_exceeded: ConfigFileOptionalValidationLevel,
+}
+
+#[derive(Deserialize, Default)]
+#[serde(default, deny_unknown_fields)]
+struct ConfigFilePersistedQueries {
+    persist: bool,
+}
+
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct ConfigFileCustomScalarType {
+    typescript_type: String,
+    /// A module specifier, or a path relative to the config if it starts with a
+    /// period
+    #[serde(default)]
+    import_from: Option<String>,
+    #[serde(default)]
+    literals: ConfigFileCustomScalarLiterals,
+}
+
+#[derive(Deserialize, Default)]
+#[serde(default, deny_unknown_fields)]
+struct ConfigFileCustomScalarLiterals {
+    trim: bool,
+    pattern: Option<String>,
+}
+
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct ConfigFileStitchedSchema {
+    path: PathBuf,
+    #[serde(default)]
+    type_prefix: Option<String>,
+}
+
+#[derive(Deserialize, Default)]
+#[serde(default, deny_unknown_fields)]
+struct ConfigFileClientFieldNamingConventions {
+    case: Option<ConfigFileNameCase>,
+    component_case: Option<ConfigFileNameCase>,
+    reserved_prefixes: Vec<String>,
+    forbid_case_insensitive_collisions: bool,
+    on_violation: ConfigFileOptionalValidationLevel,
+}
+
+#[derive(Deserialize, Debug, Clone, Copy)]
+#[serde(rename_all = "snake_case")]
+enum ConfigFileNameCase {
+    CamelCase,
+    PascalCase,
+    SnakeCase,
 }
 
 #[derive(Deserialize, Debug, Clone, Copy)]
@@ -170,9 +810,173 @@ impl Default for ConfigFileOptionalValidationLevel {
     }
 }
 
+#[derive(Deserialize, Debug, Clone, Copy)]
+#[serde(rename_all = "snake_case")]
+enum ConfigFileNormalizationAliasStrategy {
+    Readable,
+    Hashed,
+    None,
+}
+
+impl Default for ConfigFileNormalizationAliasStrategy {
+    fn default() -> Self {
+        Self::Readable
+    }
+}
+
+#[derive(Deserialize, Debug, Clone, Copy)]
+#[serde(rename_all = "snake_case")]
+enum ConfigFileArtifactFormat {
+    #[serde(rename = "typescript")]
+    TypeScript,
+    Json,
+}
+
+impl Default for ConfigFileArtifactFormat {
+    fn default() -> Self {
+        Self::TypeScript
+    }
+}
+
+#[derive(Deserialize, Debug, Clone, Copy)]
+#[serde(rename_all = "snake_case")]
+enum ConfigFileEnumRepresentation {
+    StringLiteralUnion,
+    ConstObject,
+    #[serde(rename = "typescript_enum")]
+    TypeScriptEnum,
+}
+
+impl Default for ConfigFileEnumRepresentation {
+    fn default() -> Self {
+        Self::StringLiteralUnion
+    }
+}
+
+#[derive(Deserialize, Debug, Clone, Copy)]
+#[serde(rename_all = "snake_case")]
+enum ConfigFilePathNormalization {
+    Canonicalize,
+    Lexical,
+}
+
+impl Default for ConfigFilePathNormalization {
+    fn default() -> Self {
+        Self::Canonicalize
+    }
+}
+
+#[derive(Deserialize, Debug, Clone, Copy)]
+#[serde(rename_all = "snake_case")]
+enum ConfigFileFieldOrdering {
+    Selection,
+    Schema,
+    Alphabetical,
+}
+
+impl Default for ConfigFileFieldOrdering {
+    fn default() -> Self {
+        Self::Selection
+    }
+}
+
 fn create_options(options: ConfigFileOptions) -> ConfigOptions {
     ConfigOptions {
         on_invalid_id_type: create_optional_validation_level(options.on_invalid_id_type),
+        generate_named_fragments: options.generate_named_fragments,
+        minify_query_text: options.minify_query_text,
+        normalization_alias_strategy: create_normalization_alias_strategy(
+            options.normalization_alias_strategy,
+        ),
+        generate_dev_assertions: options.generate_dev_assertions,
+        generate_data_requirements: options.generate_data_requirements,
+        generate_refetch_queries: options.generate_refetch_queries,
+        include_complexity_comments: options.include_complexity_comments,
+        complexity_budget: create_complexity_budget(options.complexity_budget),
+        artifact_format: create_artifact_format(options.artifact_format),
+        enum_representation: create_enum_representation(options.enum_representation),
+        field_ordering: create_field_ordering(options.field_ordering),
+        treat_server_fields_as_nullable: options.treat_server_fields_as_nullable,
+        report_memory_usage: options.report_memory_usage,
+        memory_budget_megabytes: options.memory_budget_megabytes,
+        max_artifact_size_bytes: options.max_artifact_size_bytes,
+        path_normalization: create_path_normalization(options.path_normalization),
+        persisted_queries: options
+            .persisted_queries
+            .map(|persisted_queries| PersistedQueries {
+                persist: persisted_queries.persist,
+            }),
+    }
+}
+
+fn create_client_field_naming_conventions(
+    client_field_naming: ConfigFileClientFieldNamingConventions,
+) -> ClientFieldNamingConventions {
+    ClientFieldNamingConventions {
+        case: client_field_naming.case.map(create_name_case),
+        component_case: client_field_naming.component_case.map(create_name_case),
+        reserved_prefixes: client_field_naming.reserved_prefixes,
+        forbid_case_insensitive_collisions: client_field_naming.forbid_case_insensitive_collisions,
+        on_violation: create_optional_validation_level(client_field_naming.on_violation),
+    }
+}
+
+fn create_name_case(name_case: ConfigFileNameCase) -> NameCase {
+    match name_case {
+        ConfigFileNameCase::CamelCase => NameCase::CamelCase,
+        ConfigFileNameCase::PascalCase => NameCase::PascalCase,
+        ConfigFileNameCase::SnakeCase => NameCase::SnakeCase,
+    }
+}
+
+fn create_path_normalization(path_normalization: ConfigFilePathNormalization) -> PathNormalization {
+    match path_normalization {
+        ConfigFilePathNormalization::Canonicalize => PathNormalization::Canonicalize,
+        ConfigFilePathNormalization::Lexical => PathNormalization::Lexical,
+    }
+}
+
+fn create_field_ordering(field_ordering: ConfigFileFieldOrdering) -> FieldOrdering {
+    match field_ordering {
+        ConfigFileFieldOrdering::Selection => FieldOrdering::Selection,
+        ConfigFileFieldOrdering::Schema => FieldOrdering::Schema,
+        ConfigFileFieldOrdering::Alphabetical => FieldOrdering::Alphabetical,
+    }
+}
+
+fn create_enum_representation(
+    enum_representation: ConfigFileEnumRepresentation,
+) -> EnumRepresentation {
+    match enum_representation {
+        ConfigFileEnumRepresentation::StringLiteralUnion => EnumRepresentation::StringLiteralUnion,
+        ConfigFileEnumRepresentation::ConstObject => EnumRepresentation::ConstObject,
+        ConfigFileEnumRepresentation::TypeScriptEnum => EnumRepresentation::TypeScriptEnum,
+    }
+}
+
+fn create_artifact_format(artifact_format: ConfigFileArtifactFormat) -> ArtifactFormat {
+    match artifact_format {
+        ConfigFileArtifactFormat::TypeScript => ArtifactFormat::TypeScript,
+        ConfigFileArtifactFormat::Json => ArtifactFormat::Json,
+    }
+}
+
+fn create_complexity_budget(complexity_budget: ConfigFileComplexityBudget) -> ComplexityBudget {
+    ComplexityBudget {
+        max_field_count: complexity_budget.max_field_count,
+        max_depth: complexity_budget.max_depth,
+        max_estimated_response_size: complexity_budget.max_estimated_response_size,
+        on_exceeded: create_optional_validation_level(complexity_budget.on_exceeded),
+    }
+}
+
+fn create_normalization_alias_strategy(
+    normalization_alias_strategy: ConfigFileNormalizationAliasStrategy,
+) -> NormalizationAliasStrategy {
+    match normalization_alias_strategy {
+        ConfigFileNormalizationAliasStrategy::Readable => NormalizationAliasStrategy::Readable,
+        ConfigFileNormalizationAliasStrategy::Hashed => NormalizationAliasStrategy::Hashed,
+        ConfigFileNormalizationAliasStrategy::None => NormalizationAliasStrategy::None,
     }
 }
 
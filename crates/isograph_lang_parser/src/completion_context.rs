@@ -0,0 +1,101 @@
+use logos::Logos;
+
+use crate::IsographLangTokenKind;
+
+/// Where a field can be selected, for completions in editors.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SelectionCompletionContext<'a> {
+    /// The parent type of the declared client field, e.g. `User` in
+    /// `field User.Avatar { ... }`
+    pub parent_type_name: &'a str,
+    /// The names (not aliases) of the linked fields whose selection sets enclose
+    /// the offset, outermost first. The fields that can be selected are those of
+    /// the type reached by following these fields from the parent type.
+    pub linked_field_names: Vec<&'a str>,
+}
+
+/// If a field can be selected at offset (relative to the start of
+/// iso_literal_text), i.e. offset is in a selection set of a client field
+/// declaration, but not in arguments or directives, returns the type and linked
+/// fields whose selection set it is in.
+///
+/// Like semantic_tokens, this only looks at the tokens before offset, and not at
+/// the parsed literal, because the literal is typically incomplete (e.g. a field
+/// is partially typed, or its selection set is not closed) while completions are
+/// requested.
+pub fn selection_completion_context(
+    iso_literal_text: &str,
+    offset: usize,
+) -> Option<SelectionCompletionContext<'_>> {
+    let text_before_offset = iso_literal_text.get(..offset)?;
+    let tokens = IsographLangTokenKind::lexer(text_before_offset)
+        .spanned()
+        .map(|(kind, range)| (kind, &text_before_offset[range]))
+        .collect::<Vec<_>>();
+    let kind_at = |index: usize| tokens.get(index).map(|(kind, _)| *kind);
+
+    let mut keyword = None;
+    let mut parent_type_name = None;
+    let mut brace_depth: usize = 0;
+    let mut paren_depth: usize = 0;
+    let mut linked_field_names = vec![];
+    // The last field selected in the innermost selection set, which is the linked
+    // field if a selection set is opened next.
+    let mut last_field_name = None;
+
+    for (index, (kind, source)) in tokens.iter().copied().enumerate() {
+        match kind {
+            IsographLangTokenKind::Identifier => {
+                let is_directive_name =
+                    index.checked_sub(1).and_then(kind_at) == Some(IsographLangTokenKind::At);
+                let is_alias = kind_at(index + 1) == Some(IsographLangTokenKind::Colon);
+                if keyword.is_none() {
+                    keyword = Some(source);
+                } else if parent_type_name.is_none() {
+                    parent_type_name = Some(source);
+                } else if brace_depth > 0 && paren_depth == 0 && !is_directive_name && !is_alias {
+                    last_field_name = Some(source);
+                }
+            }
+            IsographLangTokenKind::OpenParen => paren_depth += 1,
+            IsographLangTokenKind::CloseParen => paren_depth = paren_depth.saturating_sub(1),
+            IsographLangTokenKind::OpenBrace if paren_depth == 0 => {
+                // The outermost selection set is that of the client field itself.
+                if brace_depth > 0 {
+                    linked_field_names.push(last_field_name.take()?);
+                }
+                brace_depth += 1;
+            }
+            IsographLangTokenKind::CloseBrace if paren_depth == 0 => {
+                if brace_depth > 1 {
+                    linked_field_names.pop();
+                }
+                brace_depth = brace_depth.saturating_sub(1);
+                last_field_name = None;
+            }
+            _ => {}
+        }
+    }
+
+    // A directive or variable (rather than a field) is being typed.
+    let is_at_or_dollar = |kind: Option<IsographLangTokenKind>| {
+        kind == Some(IsographLangTokenKind::At) || kind == Some(IsographLangTokenKind::Dollar)
+    };
+    let last_index = tokens.len().checked_sub(1)?;
+    if is_at_or_dollar(kind_at(last_index))
+        || (kind_at(last_index) == Some(IsographLangTokenKind::Identifier)
+            && !text_before_offset.ends_with(char::is_whitespace)
+            && is_at_or_dollar(last_index.checked_sub(1).and_then(kind_at)))
+    {
+        return None;
+    }
+
+    if keyword != Some("field") || brace_depth == 0 || paren_depth > 0 {
+        return None;
+    }
+
+    Some(SelectionCompletionContext {
+        parent_type_name: parent_type_name?,
+        linked_field_names,
+    })
+}
@@ -24,6 +24,34 @@ pub(crate) fn parse_optional_description(
     }
 }
 
+/// The comments (e.g. `# text`) on the lines directly preceding the next token, i.e.
+/// not separated from it by a blank line, joined into a description. A comment on
+/// the line of the previous token (e.g. `name, # text`) belongs to that token, and is
+/// not included.
+pub(crate) fn parse_optional_comment_description(
+    tokens: &PeekableLexer,
+) -> Option<DescriptionValue> {
+    let white_space = tokens.source(tokens.white_space_span());
+
+    let mut comments = vec![];
+    // The first line is the line of the previous token, and the last line is the line
+    // of the next token.
+    let mut lines = white_space.split('\n').skip(1).collect::<Vec<_>>();
+    lines.pop();
+    for line in lines {
+        match line.trim().strip_prefix('#') {
+            Some(comment) => comments.push(comment.trim()),
+            None => comments.clear(),
+        }
+    }
+
+    if comments.is_empty() {
+        None
+    } else {
+        Some(comments.join("\n").intern().into())
+    }
+}
+
 fn parse_multiline_description(tokens: &mut PeekableLexer) -> Option<WithSpan<DescriptionValue>> {
     tokens
         .parse_source_of_kind(IsographLangTokenKind::BlockStringLiteral)
@@ -12,15 +12,15 @@ pub enum IsographLiteralParseError {
     #[error("{error}")]
     ParseError { error: LowLevelParseError },
 
+    #[error("Invalid token")]
+    InvalidToken,
+
     #[error("Expected a type (e.g. String, [String], or String!)")]
     ExpectedTypeAnnotation,
 
     #[error("Unparsed tokens remaining")]
     LeftoverTokens,
 
-    #[error("Isograph literals must be immediately called, and passed a function")]
-    ExpectedAssociatedJsFunction,
-
     #[error("Isograph literals must start with field or entrypoint")]
     ExpectedFieldOrEntrypoint,
 
@@ -32,7 +32,7 @@ pub enum IsographLiteralParseError {
         suggested_const_export_name: ScalarFieldName,
     },
 
-    #[error("Expected a valid value, like $foo or 42")]
+    #[error("Expected a valid value, like $foo, 42, true or \"foo\"")]
     ExpectedNonConstantValue,
 
     #[error("Descriptions are currently disallowed")]
@@ -1,11 +1,19 @@
+mod completion_context;
 mod description;
 mod isograph_literal_parse_error;
 mod parse_iso_literal;
+mod parse_iso_literal_recoverable;
 mod peekable_lexer;
+mod print_iso_literal;
+mod semantic_tokens;
 mod token_kind;
 
+pub use completion_context::*;
 pub(crate) use description::*;
 pub use isograph_literal_parse_error::*;
 pub use parse_iso_literal::*;
+pub use parse_iso_literal_recoverable::*;
 pub use peekable_lexer::*;
+pub use print_iso_literal::*;
+pub use semantic_tokens::*;
 pub use token_kind::*;
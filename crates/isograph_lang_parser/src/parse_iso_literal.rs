@@ -1,8 +1,8 @@
 use std::{collections::HashSet, ops::ControlFlow};
 
 use common_lang_types::{
-    FilePath, Location, ScalarFieldName, SelectableFieldName, Span, StringKeyNewtype, TextSource,
-    UnvalidatedTypeName, WithLocation, WithSpan,
+    DescriptionValue, FilePath, Location, ScalarFieldName, SelectableFieldName, Span,
+    StringKeyNewtype, TextSource, UnvalidatedTypeName, WithLocation, WithSpan,
 };
 use graphql_lang_types::{
     ListTypeAnnotation, NamedTypeAnnotation, NonNullTypeAnnotation, TypeAnnotation,
@@ -15,8 +15,8 @@ use isograph_lang_types::{
 };
 
 use crate::{
-    parse_optional_description, IsographLangTokenKind, IsographLiteralParseError,
-    ParseResultWithLocation, ParseResultWithSpan, PeekableLexer,
+    parse_optional_comment_description, parse_optional_description, IsographLangTokenKind,
+    IsographLiteralParseError, ParseResultWithLocation, ParseResultWithSpan, PeekableLexer,
 };
 
 pub enum IsoLiteralExtractionResult {
@@ -28,22 +28,50 @@ pub fn parse_iso_literal(
     iso_literal_text: &str,
     definition_file_path: FilePath,
     const_export_name: Option<&str>,
+    has_associated_js_function: bool,
     text_source: TextSource,
 ) -> Result<IsoLiteralExtractionResult, WithLocation<IsographLiteralParseError>> {
     let mut tokens = PeekableLexer::new(iso_literal_text);
+    let result = parse_iso_literal_inner(
+        &mut tokens,
+        definition_file_path,
+        const_export_name,
+        has_associated_js_function,
+        text_source,
+    );
+
+    // Invalid tokens are skipped by the lexer, so any errors around them are caused
+    // by the invalid token.
+    if let Some(span) = tokens.invalid_token_spans().first() {
+        return Err(WithLocation::new(
+            IsographLiteralParseError::InvalidToken,
+            Location::new(text_source, *span),
+        ));
+    }
+    result
+}
+
+fn parse_iso_literal_inner(
+    tokens: &mut PeekableLexer<'_>,
+    definition_file_path: FilePath,
+    const_export_name: Option<&str>,
+    has_associated_js_function: bool,
+    text_source: TextSource,
+) -> Result<IsoLiteralExtractionResult, WithLocation<IsographLiteralParseError>> {
     let discriminator = tokens
         .parse_source_of_kind(IsographLangTokenKind::Identifier)
         .map_err(|with_span| with_span.map(IsographLiteralParseError::from))
         .map_err(|err| err.to_with_location(text_source))?;
     match discriminator.item {
         "entrypoint" => Ok(IsoLiteralExtractionResult::EntrypointDeclaration(
-            parse_iso_entrypoint_declaration(&mut tokens, text_source)?,
+            parse_iso_entrypoint_declaration(tokens, text_source)?,
         )),
         "field" => Ok(IsoLiteralExtractionResult::ClientFieldDeclaration(
             parse_iso_client_field_declaration(
-                &mut tokens,
+                tokens,
                 definition_file_path,
                 const_export_name,
+                has_associated_js_function,
                 text_source,
             )?,
         )),
@@ -92,12 +120,14 @@ fn parse_iso_client_field_declaration(
     tokens: &mut PeekableLexer<'_>,
     definition_file_path: FilePath,
     const_export_name: Option<&str>,
+    has_associated_js_function: bool,
     text_source: TextSource,
 ) -> ParseResultWithLocation<WithSpan<ClientFieldDeclaration>> {
     let client_field_declaration = parse_client_field_declaration_inner(
         tokens,
         definition_file_path,
         const_export_name,
+        has_associated_js_function,
         text_source,
     )
     .map_err(|with_span| with_span.to_with_location(text_source))?;
@@ -116,11 +146,12 @@ fn parse_client_field_declaration_inner<'a>(
     tokens: &mut PeekableLexer<'a>,
     definition_file_path: FilePath,
     const_export_name: Option<&str>,
+    has_associated_js_function: bool,
     text_source: TextSource,
 ) -> ParseResultWithSpan<WithSpan<ClientFieldDeclaration>> {
     let client_field_declaration = tokens
         .with_span(|tokens| {
-            let _description = parse_optional_description(tokens)?;
+            let description = parse_optional_description(tokens)?;
             let parent_type = tokens
                 .parse_string_key_type(IsographLangTokenKind::Identifier)
                 .map_err(|with_span| with_span.map(IsographLiteralParseError::from))?;
@@ -135,18 +166,24 @@ fn parse_client_field_declaration_inner<'a>(
 
             let variable_definitions = parse_variable_definitions(tokens, text_source)?;
 
-            let directives = parse_directives(tokens)?;
+            let directives = parse_directives(tokens, text_source)?;
 
             let selection_set_and_unwraps = parse_selection_set_and_unwraps(tokens, text_source)?;
 
-            let const_export_name = const_export_name.ok_or_else(|| {
-                WithSpan::new(
-                    IsographLiteralParseError::ExpectedLiteralToBeExported {
-                        suggested_const_export_name: client_field_name.item.into(),
-                    },
-                    Span::todo_generated(),
-                )
-            })?;
+            // Literals without an associated js function do not need to be exported,
+            // since nothing imports them.
+            let const_export_name = if has_associated_js_function {
+                Some(const_export_name.ok_or_else(|| {
+                    WithSpan::new(
+                        IsographLiteralParseError::ExpectedLiteralToBeExported {
+                            suggested_const_export_name: client_field_name.item.into(),
+                        },
+                        Span::todo_generated(),
+                    )
+                })?)
+            } else {
+                None
+            };
 
             // --------------------
             // TODO: use directives to:
@@ -155,12 +192,13 @@ fn parse_client_field_declaration_inner<'a>(
             // --------------------
 
             Ok(ClientFieldDeclaration {
+                description,
                 parent_type,
                 client_field_name,
                 selection_set_and_unwraps,
                 definition_path: definition_file_path,
                 directives,
-                const_export_name: const_export_name.intern().into(),
+                const_export_name: const_export_name.map(|name| name.intern().into()),
                 variable_definitions,
             })
         })
@@ -250,7 +288,9 @@ fn parse_delimited_list<'a, TResult>(
     Ok(items)
 }
 
-fn parse_comma_or_line_break<'a>(tokens: &mut PeekableLexer<'a>) -> ParseResultWithSpan<()> {
+pub(crate) fn parse_comma_or_line_break<'a>(
+    tokens: &mut PeekableLexer<'a>,
+) -> ParseResultWithSpan<()> {
     let comma = tokens.parse_token_of_kind(IsographLangTokenKind::Comma);
     if comma.is_err() {
         let white_space_text = tokens.source(tokens.white_space_span());
@@ -271,6 +311,7 @@ fn parse_selection<'a>(
     tokens: &mut PeekableLexer<'a>,
     text_source: TextSource,
 ) -> ParseResultWithSpan<WithSpan<UnvalidatedSelection>> {
+    let description = parse_optional_comment_description(tokens);
     tokens
         .with_span(|tokens| {
             let (field_name, alias) = parse_optional_alias_and_field_name(tokens)?;
@@ -280,6 +321,8 @@ fn parse_selection<'a>(
             // TODO distinguish field groups
             let arguments = parse_optional_arguments(tokens, text_source)?;
 
+            let directives = parse_directives(tokens, text_source)?;
+
             // If we encounter a selection set, we are parsing a linked field. Otherwise, a scalar field.
             let selection_set = parse_optional_selection_set(tokens, text_source)?;
 
@@ -288,44 +331,62 @@ fn parse_selection<'a>(
             // commas are required
             parse_comma_or_line_break(tokens)?;
 
-            let selection = match selection_set {
-                Some(selection_set) => Selection::ServerField(ServerFieldSelection::LinkedField(
-                    LinkedFieldSelection {
-                        name: field_name.map(|string_key| string_key.into()),
-                        reader_alias: alias
-                            .map(|with_span| with_span.map(|string_key| string_key.into())),
-                        associated_data: (),
-                        selection_set,
-                        unwraps,
-                        normalization_alias:
-                            HACK_combine_name_and_variables_into_normalization_alias(
-                                field_name.map(|x| x.into()),
-                                &arguments,
-                            ),
-                        arguments,
-                    },
-                )),
-                None => Selection::ServerField(ServerFieldSelection::ScalarField(
-                    ScalarFieldSelection {
-                        name: field_name.map(|string_key| string_key.into()),
-                        reader_alias: alias
-                            .map(|with_span| with_span.map(|string_key| string_key.into())),
-                        associated_data: (),
-                        unwraps,
-                        normalization_alias:
-                            HACK_combine_name_and_variables_into_normalization_alias(
-                                field_name.map(|x| x.into()),
-                                &arguments,
-                            ),
-                        arguments,
-                    },
-                )),
-            };
-            Ok(selection)
+            Ok(selection_from_parts(
+                field_name,
+                alias,
+                arguments,
+                directives,
+                selection_set,
+                unwraps,
+                description,
+            ))
         })
         .transpose()
 }
 
+/// A linked field if there is a selection set, and a scalar field otherwise.
+pub(crate) fn selection_from_parts(
+    field_name: WithLocation<StringKey>,
+    alias: Option<WithLocation<StringKey>>,
+    arguments: Vec<WithLocation<SelectionFieldArgument>>,
+    directives: Vec<WithSpan<FragmentDirectiveUsage>>,
+    selection_set: Option<Vec<WithSpan<UnvalidatedSelection>>>,
+    unwraps: Vec<WithSpan<Unwrap>>,
+    description: Option<DescriptionValue>,
+) -> UnvalidatedSelection {
+    match selection_set {
+        Some(selection_set) => {
+            Selection::ServerField(ServerFieldSelection::LinkedField(LinkedFieldSelection {
+                name: field_name.map(|string_key| string_key.into()),
+                reader_alias: alias.map(|with_span| with_span.map(|string_key| string_key.into())),
+                associated_data: (),
+                selection_set,
+                unwraps,
+                normalization_alias: HACK_combine_name_and_variables_into_normalization_alias(
+                    field_name.map(|x| x.into()),
+                    &arguments,
+                ),
+                arguments,
+                directives,
+                description,
+            }))
+        }
+        None => Selection::ServerField(ServerFieldSelection::ScalarField(ScalarFieldSelection {
+            name: field_name.map(|string_key| string_key.into()),
+            reader_alias: alias.map(|with_span| with_span.map(|string_key| string_key.into())),
+            associated_data: (),
+            unwraps,
+            normalization_alias: HACK_combine_name_and_variables_into_normalization_alias(
+                field_name.map(|x| x.into()),
+                &arguments,
+            ),
+            arguments,
+            directives,
+            description,
+        })),
+    }
+}
+
 fn parse_optional_alias_and_field_name(
     tokens: &mut PeekableLexer,
 ) -> ParseResultWithSpan<(WithSpan<StringKey>, Option<WithSpan<StringKey>>)> {
@@ -346,7 +407,7 @@ fn parse_optional_alias_and_field_name(
     Ok((field_name, alias))
 }
 
-fn parse_unwraps(tokens: &mut PeekableLexer) -> Vec<WithSpan<Unwrap>> {
+pub(crate) fn parse_unwraps(tokens: &mut PeekableLexer) -> Vec<WithSpan<Unwrap>> {
     // TODO support _, etc.
     let mut unwraps = vec![];
     while let Ok(token) = tokens.parse_token_of_kind(IsographLangTokenKind::Exclamation) {
@@ -355,24 +416,26 @@ fn parse_unwraps(tokens: &mut PeekableLexer) -> Vec<WithSpan<Unwrap>> {
     unwraps
 }
 
-fn parse_directives(
+pub(crate) fn parse_directives(
     tokens: &mut PeekableLexer,
+    text_source: TextSource,
 ) -> ParseResultWithSpan<Vec<WithSpan<FragmentDirectiveUsage>>> {
     let mut directives = vec![];
     while let Ok(token) = tokens.parse_token_of_kind(IsographLangTokenKind::At) {
         let name = tokens
             .parse_string_key_type(IsographLangTokenKind::Identifier)
             .map_err(|with_span| with_span.map(IsographLiteralParseError::from))?;
+        let arguments = parse_optional_arguments(tokens, text_source)?;
         let directive_span = Span::join(token.span, name.span);
         directives.push(WithSpan::new(
-            FragmentDirectiveUsage { name },
+            FragmentDirectiveUsage { name, arguments },
             directive_span,
         ));
     }
     Ok(directives)
 }
 
-fn parse_optional_arguments(
+pub(crate) fn parse_optional_arguments(
     tokens: &mut PeekableLexer,
     text_source: TextSource,
 ) -> ParseResultWithSpan<Vec<WithLocation<SelectionFieldArgument>>> {
@@ -427,6 +490,20 @@ fn parse_non_constant_value(
             Ok(name.map(NonConstantValue::Variable))
         })?;
 
+        to_control_flow::<_, WithSpan<IsographLiteralParseError>>(|| {
+            let true_token = tokens
+                .parse_matching_identifier("true")
+                .map_err(|with_span| with_span.map(IsographLiteralParseError::from))?;
+            Ok(true_token.map(|_| NonConstantValue::Boolean(true)))
+        })?;
+
+        to_control_flow::<_, WithSpan<IsographLiteralParseError>>(|| {
+            let false_token = tokens
+                .parse_matching_identifier("false")
+                .map_err(|with_span| with_span.map(IsographLiteralParseError::from))?;
+            Ok(false_token.map(|_| NonConstantValue::Boolean(false)))
+        })?;
+
         to_control_flow::<_, WithSpan<IsographLiteralParseError>>(|| {
             let number = tokens
                 .parse_source_of_kind(IsographLangTokenKind::IntegerLiteral)
@@ -436,6 +513,17 @@ fn parse_non_constant_value(
             }))
         })?;
 
+        to_control_flow::<_, WithSpan<IsographLiteralParseError>>(|| {
+            let string = tokens
+                .parse_source_of_kind(IsographLangTokenKind::StringLiteral)
+                .map_err(|with_span| with_span.map(IsographLiteralParseError::from))?;
+            Ok(string.map(|source_with_quotes| {
+                NonConstantValue::String(
+                    source_with_quotes[1..source_with_quotes.len() - 1].intern(),
+                )
+            }))
+        })?;
+
         ControlFlow::Continue(WithSpan::new(
             IsographLiteralParseError::ExpectedNonConstantValue,
             Span::todo_generated(),
@@ -443,7 +531,7 @@ fn parse_non_constant_value(
     })
 }
 
-fn parse_variable_definitions(
+pub(crate) fn parse_variable_definitions(
     tokens: &mut PeekableLexer,
     text_source: TextSource,
 ) -> ParseResultWithSpan<Vec<WithSpan<VariableDefinition<UnvalidatedTypeName>>>> {
@@ -588,7 +676,32 @@ fn HACK_combine_name_and_variables_into_normalization_alias<T: StringKeyNewtype>
 
 #[cfg(test)]
 mod test {
-    use crate::{IsographLangTokenKind, PeekableLexer};
+    use common_lang_types::TextSource;
+    use intern::string_key::Intern;
+    use isograph_lang_types::ClientFieldDeclaration;
+
+    use crate::{
+        parse_iso_literal, IsoLiteralExtractionResult, IsographLangTokenKind, PeekableLexer,
+    };
+
+    fn parse_client_field_declaration(iso_literal_text: &str) -> ClientFieldDeclaration {
+        let text_source = TextSource {
+            path: "/path/to/Component.tsx".intern().into(),
+            span: None,
+        };
+        match parse_iso_literal(
+            iso_literal_text,
+            "/path/to/Component.tsx".intern().into(),
+            None,
+            false,
+            text_source,
+        ) {
+            Ok(IsoLiteralExtractionResult::ClientFieldDeclaration(client_field_declaration)) => {
+                client_field_declaration.item
+            }
+            _ => panic!("Expected a client field declaration"),
+        }
+    }
 
     #[test]
     fn parse_literal_tests() {
@@ -602,4 +715,32 @@ mod test {
             }
         }
     }
+
+    #[test]
+    fn parse_skip_and_include_conditions() {
+        let client_field_declaration = parse_client_field_declaration(
+            "field User.Avatar($compact: Boolean!) {\n  \
+            name @skip(if: true)\n  \
+            email @include(if: true)\n  \
+            avatar_url @skip(if: $compact)\n\
+            }",
+        );
+        let (selection_set, _) = client_field_declaration
+            .selection_set_and_unwraps
+            .expect("Expected a selection set");
+        let static_inclusions = selection_set
+            .iter()
+            .map(|selection| {
+                let directives = selection.item.directives();
+                assert_eq!(directives.len(), 1);
+                directives[0].item.static_inclusion()
+            })
+            .collect::<Vec<_>>();
+        // Conditions that depend on variables parse, but are not static, and are
+        // rejected during validation.
+        assert_eq!(static_inclusions, vec![Some(false), Some(true), None]);
+        assert!(selection_set[0].item.is_statically_excluded());
+        assert!(!selection_set[1].item.is_statically_excluded());
+        assert!(!selection_set[2].item.is_statically_excluded());
+    }
 }
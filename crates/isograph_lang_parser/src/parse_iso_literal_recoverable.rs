@@ -0,0 +1,518 @@
+use std::collections::HashSet;
+
+use common_lang_types::{FilePath, ScalarFieldName, Span, TextSource, WithLocation, WithSpan};
+use intern::string_key::{Intern, StringKey};
+use isograph_lang_types::{
+    ClientFieldDeclaration, EntrypointTypeAndField, Selection, UnvalidatedSelection,
+};
+
+use crate::{
+    parse_comma_or_line_break, parse_directives, parse_optional_arguments,
+    parse_optional_comment_description, parse_optional_description, parse_unwraps,
+    parse_variable_definitions, selection_from_parts, IsoLiteralExtractionResult,
+    IsographLangTokenKind, IsographLiteralParseError, LowLevelParseError, PeekableLexer,
+};
+
+/// The name of the type, client field or field that is missing where a name was
+/// expected, in literals parsed by parse_iso_literal_recoverable. Names starting
+/// with `__` are reserved by GraphQL, so this is never the name of an actual type
+/// or field.
+pub static MISSING_NAME: &'static str = "__missing";
+
+/// Parses an iso literal like parse_iso_literal, but instead of failing on the first
+/// error, returns a best-effort declaration along with every error that was
+/// encountered, for tools (such as editors) that need results while the literal is
+/// being edited. Recovers by:
+/// - using MISSING_NAME (with an empty span) for names that are missing,
+/// - skipping tokens that cannot start a selection, and arguments and directives
+///   that cannot be parsed,
+/// - closing selection sets that are not closed, and
+/// - using an empty selection set if the client field has none.
+///
+/// The declaration is None only if the literal does not start with `field` or
+/// `entrypoint`. Errors are sorted by location.
+pub fn parse_iso_literal_recoverable(
+    iso_literal_text: &str,
+    definition_file_path: FilePath,
+    const_export_name: Option<&str>,
+    has_associated_js_function: bool,
+    text_source: TextSource,
+) -> (
+    Option<IsoLiteralExtractionResult>,
+    Vec<WithLocation<IsographLiteralParseError>>,
+) {
+    let mut tokens = PeekableLexer::new(iso_literal_text);
+    let mut errors = vec![];
+
+    let result = match tokens.parse_source_of_kind(IsographLangTokenKind::Identifier) {
+        Ok(discriminator) => match discriminator.item {
+            "entrypoint" => Some(IsoLiteralExtractionResult::EntrypointDeclaration(
+                parse_entrypoint_declaration_recoverable(&mut tokens, &mut errors),
+            )),
+            "field" => Some(IsoLiteralExtractionResult::ClientFieldDeclaration(
+                parse_client_field_declaration_recoverable(
+                    &mut tokens,
+                    definition_file_path,
+                    const_export_name,
+                    has_associated_js_function,
+                    text_source,
+                    &mut errors,
+                ),
+            )),
+            _ => {
+                errors.push(WithSpan::new(
+                    IsographLiteralParseError::ExpectedFieldOrEntrypoint,
+                    discriminator.span,
+                ));
+                None
+            }
+        },
+        Err(error) => {
+            errors.push(error.map(IsographLiteralParseError::from));
+            None
+        }
+    };
+
+    if result.is_some() {
+        if let Some(span) = tokens.remaining_token_span() {
+            errors.push(WithSpan::new(
+                IsographLiteralParseError::LeftoverTokens,
+                span,
+            ));
+        }
+    }
+    errors.extend(
+        tokens
+            .invalid_token_spans()
+            .iter()
+            .map(|span| WithSpan::new(IsographLiteralParseError::InvalidToken, *span)),
+    );
+    errors.sort_by_key(|error| error.span.start);
+
+    (
+        result,
+        errors
+            .into_iter()
+            .map(|error| error.to_with_location(text_source))
+            .collect(),
+    )
+}
+
+fn parse_entrypoint_declaration_recoverable(
+    tokens: &mut PeekableLexer<'_>,
+    errors: &mut Vec<WithSpan<IsographLiteralParseError>>,
+) -> WithSpan<EntrypointTypeAndField> {
+    tokens.with_span(|tokens| {
+        let parent_type = parse_name(tokens, errors);
+        parse_token_of_kind(tokens, IsographLangTokenKind::Period, errors);
+        let client_field_name = parse_name(tokens, errors);
+        EntrypointTypeAndField {
+            parent_type,
+            client_field_name,
+        }
+    })
+}
+
+fn parse_client_field_declaration_recoverable(
+    tokens: &mut PeekableLexer<'_>,
+    definition_file_path: FilePath,
+    const_export_name: Option<&str>,
+    has_associated_js_function: bool,
+    text_source: TextSource,
+    errors: &mut Vec<WithSpan<IsographLiteralParseError>>,
+) -> WithSpan<ClientFieldDeclaration> {
+    tokens.with_span(|tokens| {
+        let description = parse_optional_description(tokens).unwrap_or_else(|error| {
+            errors.push(error);
+            None
+        });
+        let parent_type = parse_name(tokens, errors);
+        parse_token_of_kind(tokens, IsographLangTokenKind::Period, errors);
+        let client_field_name: WithSpan<ScalarFieldName> = parse_name(tokens, errors);
+
+        // If the variable definitions or directives cannot be parsed, everything up to
+        // the selection set is skipped.
+        let is_selection_set =
+            |kind: IsographLangTokenKind| kind == IsographLangTokenKind::OpenBrace;
+        let variable_definitions =
+            parse_variable_definitions(tokens, text_source).unwrap_or_else(|error| {
+                errors.push(error);
+                skip_until(tokens, is_selection_set);
+                vec![]
+            });
+        let directives = parse_directives(tokens, text_source).unwrap_or_else(|error| {
+            errors.push(error);
+            skip_until(tokens, is_selection_set);
+            vec![]
+        });
+
+        let selection_set = match tokens.parse_token_of_kind(IsographLangTokenKind::OpenBrace) {
+            Ok(_) => parse_selection_set_recoverable(tokens, text_source, errors),
+            Err(_) => {
+                errors.push(WithSpan::new(
+                    IsographLiteralParseError::ExpectedSelectionSet,
+                    tokens.peek().span,
+                ));
+                vec![]
+            }
+        };
+        let unwraps = parse_unwraps(tokens);
+
+        let const_export_name = if has_associated_js_function {
+            if const_export_name.is_none() {
+                errors.push(WithSpan::new(
+                    IsographLiteralParseError::ExpectedLiteralToBeExported {
+                        suggested_const_export_name: client_field_name.item,
+                    },
+                    Span::todo_generated(),
+                ));
+            }
+            const_export_name
+        } else {
+            None
+        };
+
+        ClientFieldDeclaration {
+            description,
+            parent_type,
+            client_field_name,
+            selection_set_and_unwraps: Some((selection_set, unwraps)),
+            definition_path: definition_file_path,
+            directives,
+            const_export_name: const_export_name.map(|name| name.intern().into()),
+            variable_definitions,
+        }
+    })
+}
+
+/// Parses the selections after an opening brace, up to and including the closing
+/// brace, if any.
+fn parse_selection_set_recoverable(
+    tokens: &mut PeekableLexer<'_>,
+    text_source: TextSource,
+    errors: &mut Vec<WithSpan<IsographLiteralParseError>>,
+) -> Vec<WithSpan<UnvalidatedSelection>> {
+    let mut encountered_names_or_aliases = HashSet::new();
+    let mut selections = vec![];
+    loop {
+        let next_token = tokens.peek();
+        match next_token.item {
+            IsographLangTokenKind::CloseBrace | IsographLangTokenKind::EndOfFile => {
+                parse_token_of_kind(tokens, IsographLangTokenKind::CloseBrace, errors);
+                break;
+            }
+            IsographLangTokenKind::Identifier => {
+                let selection = parse_selection_recoverable(tokens, text_source, errors);
+                match &selection.item {
+                    Selection::ServerField(server_field_selection) => {
+                        let selection_name_or_alias = server_field_selection.name_or_alias().item;
+                        if !encountered_names_or_aliases.insert(selection_name_or_alias) {
+                            errors.push(WithSpan::new(
+                                IsographLiteralParseError::DuplicateNameOrAlias {
+                                    name_or_alias: selection_name_or_alias,
+                                },
+                                selection.span,
+                            ));
+                            continue;
+                        }
+                    }
+                }
+                selections.push(selection);
+            }
+            found_kind => {
+                // Skip to the next selection, or the end of the selection set.
+                let span = skip_until(tokens, |kind| {
+                    kind == IsographLangTokenKind::Identifier
+                        || kind == IsographLangTokenKind::CloseBrace
+                });
+                errors.push(WithSpan::new(
+                    IsographLiteralParseError::from(LowLevelParseError::ParseTokenKindError {
+                        expected_kind: IsographLangTokenKind::Identifier,
+                        found_kind,
+                    }),
+                    span,
+                ));
+            }
+        }
+    }
+    selections
+}
+
+/// Parses a selection. The next token must be an identifier.
+fn parse_selection_recoverable(
+    tokens: &mut PeekableLexer<'_>,
+    text_source: TextSource,
+    errors: &mut Vec<WithSpan<IsographLiteralParseError>>,
+) -> WithSpan<UnvalidatedSelection> {
+    let description = parse_optional_comment_description(tokens);
+    tokens.with_span(|tokens| {
+        let field_name_or_alias: WithSpan<StringKey> = parse_name(tokens, errors);
+        let (field_name, alias) = if tokens
+            .parse_token_of_kind(IsographLangTokenKind::Colon)
+            .is_ok()
+        {
+            (parse_name(tokens, errors), Some(field_name_or_alias))
+        } else {
+            (field_name_or_alias, None)
+        };
+
+        // If the arguments or directives cannot be parsed, everything up to the
+        // selection set or the next selection is skipped.
+        let is_end_of_selection = |kind: IsographLangTokenKind| {
+            kind == IsographLangTokenKind::OpenBrace
+                || kind == IsographLangTokenKind::CloseBrace
+                || kind == IsographLangTokenKind::Comma
+        };
+        let arguments = parse_optional_arguments(tokens, text_source).unwrap_or_else(|error| {
+            errors.push(error);
+            skip_until(tokens, is_end_of_selection);
+            vec![]
+        });
+        let directives = parse_directives(tokens, text_source).unwrap_or_else(|error| {
+            errors.push(error);
+            skip_until(tokens, is_end_of_selection);
+            vec![]
+        });
+
+        let selection_set = tokens
+            .parse_token_of_kind(IsographLangTokenKind::OpenBrace)
+            .ok()
+            .map(|_| parse_selection_set_recoverable(tokens, text_source, errors));
+        let unwraps = parse_unwraps(tokens);
+
+        if let Err(error) = parse_comma_or_line_break(tokens) {
+            errors.push(error);
+        }
+
+        selection_from_parts(
+            field_name.to_with_location(text_source),
+            alias.map(|alias| alias.to_with_location(text_source)),
+            arguments,
+            directives,
+            selection_set,
+            unwraps,
+            description,
+        )
+    })
+}
+
+/// Parses a name, or returns MISSING_NAME with an empty span after the previous
+/// token if there is none.
+fn parse_name<T: From<StringKey>>(
+    tokens: &mut PeekableLexer<'_>,
+    errors: &mut Vec<WithSpan<IsographLiteralParseError>>,
+) -> WithSpan<T> {
+    tokens
+        .parse_string_key_type(IsographLangTokenKind::Identifier)
+        .unwrap_or_else(|error| {
+            errors.push(error.map(IsographLiteralParseError::from));
+            let position = tokens.white_space_span().start;
+            WithSpan::new(MISSING_NAME.intern().into(), Span::new(position, position))
+        })
+}
+
+fn parse_token_of_kind(
+    tokens: &mut PeekableLexer<'_>,
+    kind: IsographLangTokenKind,
+    errors: &mut Vec<WithSpan<IsographLiteralParseError>>,
+) {
+    if let Err(error) = tokens.parse_token_of_kind(kind) {
+        errors.push(error.map(IsographLiteralParseError::from));
+    }
+}
+
+/// Skips tokens until the next token that is_boundary (outside of any parentheses,
+/// braces or brackets that were opened while skipping), or the end of the literal.
+/// Returns the span of the skipped tokens.
+fn skip_until(
+    tokens: &mut PeekableLexer<'_>,
+    is_boundary: impl Fn(IsographLangTokenKind) -> bool,
+) -> Span {
+    let start = tokens.peek().span.start;
+    let mut depth: usize = 0;
+    loop {
+        let kind = tokens.peek().item;
+        if kind == IsographLangTokenKind::EndOfFile || (depth == 0 && is_boundary(kind)) {
+            break;
+        }
+        match kind {
+            IsographLangTokenKind::OpenParen
+            | IsographLangTokenKind::OpenBrace
+            | IsographLangTokenKind::OpenBracket => depth += 1,
+            IsographLangTokenKind::CloseParen
+            | IsographLangTokenKind::CloseBrace
+            | IsographLangTokenKind::CloseBracket => depth = depth.saturating_sub(1),
+            _ => {}
+        }
+        tokens.parse_token();
+    }
+    Span::new(start, tokens.white_space_span().start.max(start))
+}
+
+#[cfg(test)]
+mod test {
+    use common_lang_types::{Location, TextSource, WithLocation};
+    use intern::string_key::Intern;
+    use isograph_lang_types::{ClientFieldDeclaration, Selection, ServerFieldSelection};
+
+    use crate::{
+        parse_iso_literal_recoverable, IsoLiteralExtractionResult, IsographLangTokenKind,
+        IsographLiteralParseError, LowLevelParseError, MISSING_NAME,
+    };
+
+    fn parse_client_field_declaration(
+        iso_literal_text: &str,
+    ) -> (
+        ClientFieldDeclaration,
+        Vec<WithLocation<IsographLiteralParseError>>,
+    ) {
+        let text_source = TextSource {
+            path: "/path/to/Component.tsx".intern().into(),
+            span: None,
+        };
+        let (result, errors) = parse_iso_literal_recoverable(
+            iso_literal_text,
+            "/path/to/Component.tsx".intern().into(),
+            None,
+            false,
+            text_source,
+        );
+        let Some(IsoLiteralExtractionResult::ClientFieldDeclaration(client_field_declaration)) =
+            result
+        else {
+            panic!("Expected a client field declaration");
+        };
+        (client_field_declaration.item, errors)
+    }
+
+    fn names(client_field_declaration: &ClientFieldDeclaration) -> Vec<String> {
+        let (selection_set, _) = client_field_declaration
+            .selection_set_and_unwraps
+            .as_ref()
+            .expect("Expected a selection set");
+        selection_set
+            .iter()
+            .map(|selection| match &selection.item {
+                Selection::ServerField(ServerFieldSelection::ScalarField(scalar_field)) => {
+                    scalar_field.name.item.to_string()
+                }
+                Selection::ServerField(ServerFieldSelection::LinkedField(linked_field)) => {
+                    linked_field.name.item.to_string()
+                }
+            })
+            .collect()
+    }
+
+    fn selection_names(iso_literal_text: &str) -> (Vec<String>, usize) {
+        let (client_field_declaration, errors) = parse_client_field_declaration(iso_literal_text);
+        (names(&client_field_declaration), errors.len())
+    }
+
+    fn span_start(error: &WithLocation<IsographLiteralParseError>) -> u32 {
+        match &error.location {
+            Location::Embedded(location) => location.span.start,
+            Location::Generated => panic!("Expected error to have a location"),
+        }
+    }
+
+    fn is_unexpected_token(
+        error: &WithLocation<IsographLiteralParseError>,
+        expected: IsographLangTokenKind,
+        found: IsographLangTokenKind,
+    ) -> bool {
+        matches!(
+            error.item,
+            IsographLiteralParseError::ParseError {
+                error: LowLevelParseError::ParseTokenKindError {
+                    expected_kind,
+                    found_kind,
+                },
+            } if expected_kind == expected && found_kind == found
+        )
+    }
+
+    #[test]
+    fn closes_unclosed_selection_sets() {
+        let (names, error_count) = selection_names("field User.Avatar {\n  name\n  friends {\n");
+        assert_eq!(names, vec!["name", "friends"]);
+        // Each selection set that is not closed is an error.
+        assert_eq!(error_count, 2);
+    }
+
+    #[test]
+    fn uses_missing_names_and_skips_invalid_selections() {
+        let (names, error_count) =
+            selection_names("field User.Avatar {\n  alias:\n  ) ]\n  email(first: ,\n  id\n}");
+        assert_eq!(names, vec![MISSING_NAME, "email", "id"]);
+        assert_eq!(error_count, 3);
+    }
+
+    #[test]
+    fn returns_declaration_with_missing_client_field_name() {
+        let (client_field_declaration, errors) =
+            parse_client_field_declaration("field User. @component {\n  name\n}");
+
+        assert_eq!(
+            client_field_declaration.parent_type.item.to_string(),
+            "User"
+        );
+        assert_eq!(
+            client_field_declaration.client_field_name.item.to_string(),
+            MISSING_NAME
+        );
+        // Everything after the missing name is parsed as usual.
+        assert_eq!(client_field_declaration.directives.len(), 1);
+        assert_eq!(names(&client_field_declaration), vec!["name"]);
+
+        assert_eq!(errors.len(), 1);
+        assert!(is_unexpected_token(
+            &errors[0],
+            IsographLangTokenKind::Identifier,
+            IsographLangTokenKind::At
+        ));
+    }
+
+    #[test]
+    fn returns_declaration_without_selection_set() {
+        let (client_field_declaration, errors) =
+            parse_client_field_declaration("field User.Avatar($size: Int!)");
+
+        assert_eq!(
+            client_field_declaration.client_field_name.item.to_string(),
+            "Avatar"
+        );
+        assert_eq!(client_field_declaration.variable_definitions.len(), 1);
+        assert_eq!(names(&client_field_declaration), Vec::<String>::new());
+
+        assert_eq!(errors.len(), 1);
+        assert!(matches!(
+            errors[0].item,
+            IsographLiteralParseError::ExpectedSelectionSet
+        ));
+    }
+
+    #[test]
+    fn returns_every_error_sorted_by_location() {
+        let (client_field_declaration, errors) =
+            parse_client_field_declaration("field User.Avatar {\n  name(first: ,\n  ]\n  email\n");
+
+        assert_eq!(names(&client_field_declaration), vec!["name", "email"]);
+
+        // The invalid arguments of name, the unexpected bracket and the selection set
+        // that is not closed are each reported, in the order in which they appear.
+        assert_eq!(errors.len(), 3);
+        assert!(errors
+            .windows(2)
+            .all(|pair| span_start(&pair[0]) < span_start(&pair[1])));
+        assert!(is_unexpected_token(
+            &errors[1],
+            IsographLangTokenKind::Identifier,
+            IsographLangTokenKind::CloseBracket
+        ));
+        assert!(is_unexpected_token(
+            &errors[2],
+            IsographLangTokenKind::CloseBrace,
+            IsographLangTokenKind::EndOfFile
+        ));
+    }
+}
@@ -11,6 +11,8 @@ pub(crate) struct PeekableLexer<'source> {
     /// the byte offset of the *end* of the previous token
     end_index_of_last_parsed_token: u32,
     offset: u32,
+    /// The spans of the invalid tokens that were skipped
+    invalid_token_spans: Vec<Span>,
 }
 
 impl<'source> PeekableLexer<'source> {
@@ -30,6 +32,7 @@ impl<'source> PeekableLexer<'source> {
             source,
             end_index_of_last_parsed_token: 0,
             offset: 0,
+            invalid_token_spans: vec![],
         };
 
         // Advance to the first real token before doing any work
@@ -47,8 +50,8 @@ impl<'source> PeekableLexer<'source> {
                 .unwrap_or(IsographLangTokenKind::EndOfFile);
             match kind {
                 IsographLangTokenKind::Error => {
-                    // TODO propagate? continue?
-                    panic!("Encountered an error. This probably means you have an invalid token.")
+                    let span = self.lexer_span();
+                    self.invalid_token_spans.push(span);
                 }
                 _ => {
                     self.end_index_of_last_parsed_token = self.current.span.end;
@@ -78,6 +81,12 @@ impl<'source> PeekableLexer<'source> {
         }
     }
 
+    /// The spans of the invalid tokens that were skipped so far. Parsers must report
+    /// these, since the tokens around them may parse without errors.
+    pub fn invalid_token_spans(&self) -> &[Span] {
+        &self.invalid_token_spans
+    }
+
     pub fn reached_eof(&self) -> bool {
         self.current.item == IsographLangTokenKind::EndOfFile
     }
@@ -131,7 +140,6 @@ impl<'source> PeekableLexer<'source> {
         Ok(WithSpan::new(source.into(), kind.span))
     }
 
-    #[allow(dead_code)]
     pub fn parse_matching_identifier(
         &mut self,
         identifier: &'static str,
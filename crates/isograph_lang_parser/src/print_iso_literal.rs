@@ -0,0 +1,242 @@
+use common_lang_types::{TextSource, WithLocation, WithSpan};
+use intern::string_key::Intern;
+use isograph_lang_types::{
+    ClientFieldDeclaration, EntrypointTypeAndField, FragmentDirectiveUsage, NonConstantValue,
+    Selection, SelectionFieldArgument, ServerFieldSelection, UnvalidatedSelection, Unwrap,
+};
+
+use crate::{parse_iso_literal, IsoLiteralExtractionResult};
+
+static INDENT: &str = "  ";
+
+/// Returns the canonical formatting of the text of an iso literal (i.e. what is
+/// between the backticks), or None if the literal cannot be parsed.
+///
+/// The lexer discards comments, so literals that contain a comment are not
+/// formatted either, as formatting them would delete the comment.
+pub fn format_iso_literal(iso_literal_text: &str) -> Option<String> {
+    if iso_literal_text.contains('#') {
+        return None;
+    }
+
+    // The file path, export and associated function only affect validation, not
+    // the printed literal.
+    let text_source = TextSource {
+        path: "".intern().into(),
+        span: None,
+    };
+    let extraction_result = parse_iso_literal(
+        iso_literal_text,
+        "".intern().into(),
+        None,
+        false,
+        text_source,
+    )
+    .ok()?;
+
+    Some(match extraction_result {
+        IsoLiteralExtractionResult::ClientFieldDeclaration(client_field_declaration) => format!(
+            "\n{}{}\n",
+            INDENT,
+            print_client_field_declaration(&client_field_declaration.item, 1)
+        ),
+        IsoLiteralExtractionResult::EntrypointDeclaration(entrypoint_declaration) => {
+            print_entrypoint_declaration(&entrypoint_declaration.item)
+        }
+    })
+}
+
+pub fn print_entrypoint_declaration(entrypoint_declaration: &EntrypointTypeAndField) -> String {
+    format!(
+        "entrypoint {}.{}",
+        entrypoint_declaration.parent_type.item, entrypoint_declaration.client_field_name.item
+    )
+}
+
+/// Prints a client field declaration, with its selections on separate lines. The
+/// first line is not indented, and subsequent lines are indented as if the
+/// declaration were indentation_level levels deep.
+pub fn print_client_field_declaration(
+    client_field_declaration: &ClientFieldDeclaration,
+    indentation_level: usize,
+) -> String {
+    let mut s = String::new();
+    if let Some(description) = &client_field_declaration.description {
+        s.push_str(&format!(
+            "\"{}\"\n{}",
+            description.item,
+            INDENT.repeat(indentation_level)
+        ));
+    }
+    s.push_str(&format!(
+        "field {}.{}",
+        client_field_declaration.parent_type.item, client_field_declaration.client_field_name.item
+    ));
+    if !client_field_declaration.variable_definitions.is_empty() {
+        let variable_definitions = client_field_declaration
+            .variable_definitions
+            .iter()
+            .map(|variable_definition| {
+                format!(
+                    "${}: {}",
+                    variable_definition.item.name.item, variable_definition.item.type_
+                )
+            })
+            .collect::<Vec<_>>()
+            .join(", ");
+        s.push_str(&format!("({})", variable_definitions));
+    }
+    s.push_str(&print_directives(&client_field_declaration.directives));
+    if let Some((selection_set, unwraps)) = &client_field_declaration.selection_set_and_unwraps {
+        s.push(' ');
+        s.push_str(&print_selection_set(selection_set, indentation_level));
+        s.push_str(&print_unwraps(unwraps));
+    }
+    s
+}
+
+fn print_selection_set(
+    selection_set: &[WithSpan<UnvalidatedSelection>],
+    indentation_level: usize,
+) -> String {
+    let mut s = "{\n".to_string();
+    for selection in selection_set {
+        s.push_str(&INDENT.repeat(indentation_level + 1));
+        s.push_str(&print_selection(&selection.item, indentation_level + 1));
+        s.push('\n');
+    }
+    s.push_str(&INDENT.repeat(indentation_level));
+    s.push('}');
+    s
+}
+
+fn print_selection(selection: &UnvalidatedSelection, indentation_level: usize) -> String {
+    match selection {
+        Selection::ServerField(ServerFieldSelection::ScalarField(scalar_field)) => {
+            let mut s = String::new();
+            if let Some(alias) = scalar_field.reader_alias {
+                s.push_str(&format!("{}: ", alias.item));
+            }
+            s.push_str(&scalar_field.name.item.to_string());
+            s.push_str(&print_arguments(&scalar_field.arguments));
+            s.push_str(&print_directives(&scalar_field.directives));
+            s.push_str(&print_unwraps(&scalar_field.unwraps));
+            s
+        }
+        Selection::ServerField(ServerFieldSelection::LinkedField(linked_field)) => {
+            let mut s = String::new();
+            if let Some(alias) = linked_field.reader_alias {
+                s.push_str(&format!("{}: ", alias.item));
+            }
+            s.push_str(&linked_field.name.item.to_string());
+            s.push_str(&print_arguments(&linked_field.arguments));
+            s.push_str(&print_directives(&linked_field.directives));
+            s.push(' ');
+            s.push_str(&print_selection_set(
+                &linked_field.selection_set,
+                indentation_level,
+            ));
+            s.push_str(&print_unwraps(&linked_field.unwraps));
+            s
+        }
+    }
+}
+
+fn print_arguments(arguments: &[WithLocation<SelectionFieldArgument>]) -> String {
+    if arguments.is_empty() {
+        return "".to_string();
+    }
+    let arguments = arguments
+        .iter()
+        .map(|argument| {
+            format!(
+                "{}: {}",
+                argument.item.name.item,
+                print_non_constant_value(&argument.item.value.item)
+            )
+        })
+        .collect::<Vec<_>>()
+        .join(", ");
+    format!("({})", arguments)
+}
+
+fn print_non_constant_value(value: &NonConstantValue) -> String {
+    match value {
+        NonConstantValue::Variable(variable_name) => format!("${}", variable_name),
+        NonConstantValue::Integer(int_value) => int_value.to_string(),
+        NonConstantValue::Boolean(bool_value) => bool_value.to_string(),
+        NonConstantValue::String(string_value) => format!("\"{}\"", string_value),
+    }
+}
+
+fn print_directives(directives: &[WithSpan<FragmentDirectiveUsage>]) -> String {
+    directives
+        .iter()
+        .map(|directive| {
+            format!(
+                " @{}{}",
+                directive.item.name.item,
+                print_arguments(&directive.item.arguments)
+            )
+        })
+        .collect()
+}
+
+fn print_unwraps(unwraps: &[WithSpan<Unwrap>]) -> String {
+    unwraps
+        .iter()
+        .map(|unwrap| match unwrap.item {
+            Unwrap::ActualUnwrap => "!",
+            // Skipped unwraps cannot be parsed yet
+            Unwrap::SkippedUnwrap => "",
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod test {
+    use super::format_iso_literal;
+
+    fn format(iso_literal_text: &str) -> String {
+        format_iso_literal(iso_literal_text)
+            .unwrap_or_else(|| panic!("Expected {iso_literal_text} to be formatted"))
+    }
+
+    #[test]
+    fn formats_client_field_declaration() {
+        let formatted = format(
+            "\n    \"An avatar\" field   User.Avatar($size: Int!) @component {\n\
+            avatar: avatar_url(size: $size), name\n\
+            friends(first: 2) { name }\n\
+            }",
+        );
+        assert_eq!(
+            formatted,
+            "\n  \"An avatar\"\n  field User.Avatar($size: Int!) @component {\n    \
+            avatar: avatar_url(size: $size)\n    \
+            name\n    \
+            friends(first: 2) {\n      \
+            name\n    \
+            }\n  \
+            }\n"
+        );
+    }
+
+    #[test]
+    fn formatting_is_idempotent() {
+        for iso_literal_text in [
+            "field Query.Home { me { id, name @skip(if: false) }, viewer_count }",
+            "\n  field User.Avatar($size: Int!) {\n    avatar_url(size: $size)\n  }\n",
+            "  entrypoint   Query.Home ",
+        ] {
+            let formatted = format(iso_literal_text);
+            assert_eq!(format(&formatted), formatted, "{iso_literal_text}");
+        }
+    }
+
+    #[test]
+    fn does_not_format_literals_with_comments_or_errors() {
+        assert_eq!(format_iso_literal("field Query.Home { # me\n id }"), None);
+        assert_eq!(format_iso_literal("field Query.Home { id"), None);
+    }
+}
@@ -0,0 +1,133 @@
+use common_lang_types::{Span, WithSpan};
+use logos::Logos;
+
+use crate::IsographLangTokenKind;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum SemanticTokenKind {
+    /// `field` or `entrypoint` at the start of the literal, or a boolean literal
+    Keyword,
+    /// The parent type of the literal, or the type of a variable definition
+    TypeName,
+    /// A selected field, its alias, or the name of the declared client field
+    Field,
+    /// The name of an argument passed to a field or directive
+    Argument,
+    /// A variable, including its `$`
+    Variable,
+    /// A directive, including its `@`
+    Directive,
+    Number,
+    String,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum ParenKind {
+    VariableDefinitions,
+    Arguments,
+}
+
+/// Classifies the tokens of the text of an iso literal, for syntax highlighting.
+/// Spans are relative to the start of iso_literal_text.
+///
+/// This only looks at the tokens, and not at the parsed literal, so that
+/// incomplete literals (e.g. ones that are being edited) are highlighted too.
+/// Invalid tokens and punctuation are not classified.
+pub fn semantic_tokens(iso_literal_text: &str) -> Vec<WithSpan<SemanticTokenKind>> {
+    let tokens = IsographLangTokenKind::lexer(iso_literal_text)
+        .spanned()
+        .filter(|(kind, _)| *kind != IsographLangTokenKind::Error)
+        .map(|(kind, range)| {
+            (
+                kind,
+                Span::new(range.start as u32, range.end as u32),
+                &iso_literal_text[range],
+            )
+        })
+        .collect::<Vec<_>>();
+
+    let kind_at = |index: usize| tokens.get(index).map(|(kind, _, _)| *kind);
+
+    let mut semantic_tokens = vec![];
+    let mut brace_depth: usize = 0;
+    let mut paren_stack = vec![];
+    let mut has_seen_identifier = false;
+    let mut index = 0;
+    while let Some((kind, span, source)) = tokens.get(index).copied() {
+        let next_kind = kind_at(index + 1);
+
+        match kind {
+            IsographLangTokenKind::OpenBrace => brace_depth += 1,
+            IsographLangTokenKind::CloseBrace => brace_depth = brace_depth.saturating_sub(1),
+            IsographLangTokenKind::OpenParen => {
+                // Parentheses directly after the declared client field's name contain
+                // variable definitions. All others contain arguments.
+                let is_after_directive_name =
+                    index.checked_sub(2).and_then(kind_at) == Some(IsographLangTokenKind::At);
+                paren_stack.push(if brace_depth == 0 && !is_after_directive_name {
+                    ParenKind::VariableDefinitions
+                } else {
+                    ParenKind::Arguments
+                });
+            }
+            IsographLangTokenKind::CloseParen => {
+                paren_stack.pop();
+            }
+            IsographLangTokenKind::Dollar | IsographLangTokenKind::At
+                if next_kind == Some(IsographLangTokenKind::Identifier) =>
+            {
+                let (_, name_span, _) = tokens[index + 1];
+                semantic_tokens.push(WithSpan::new(
+                    if kind == IsographLangTokenKind::Dollar {
+                        SemanticTokenKind::Variable
+                    } else {
+                        SemanticTokenKind::Directive
+                    },
+                    Span::join(span, name_span),
+                ));
+                index += 2;
+                continue;
+            }
+            IsographLangTokenKind::Identifier => {
+                let semantic_token_kind = if !has_seen_identifier {
+                    Some(SemanticTokenKind::Keyword)
+                } else {
+                    match paren_stack.last() {
+                        Some(ParenKind::VariableDefinitions) => Some(SemanticTokenKind::TypeName),
+                        Some(ParenKind::Arguments) => {
+                            if next_kind == Some(IsographLangTokenKind::Colon) {
+                                Some(SemanticTokenKind::Argument)
+                            } else if source == "true" || source == "false" {
+                                Some(SemanticTokenKind::Keyword)
+                            } else {
+                                None
+                            }
+                        }
+                        None => {
+                            if brace_depth == 0 && next_kind == Some(IsographLangTokenKind::Period)
+                            {
+                                Some(SemanticTokenKind::TypeName)
+                            } else {
+                                Some(SemanticTokenKind::Field)
+                            }
+                        }
+                    }
+                };
+                has_seen_identifier = true;
+                if let Some(semantic_token_kind) = semantic_token_kind {
+                    semantic_tokens.push(WithSpan::new(semantic_token_kind, span));
+                }
+            }
+            IsographLangTokenKind::IntegerLiteral => {
+                semantic_tokens.push(WithSpan::new(SemanticTokenKind::Number, span));
+            }
+            IsographLangTokenKind::StringLiteral | IsographLangTokenKind::BlockStringLiteral => {
+                semantic_tokens.push(WithSpan::new(SemanticTokenKind::String, span));
+            }
+            _ => {}
+        }
+        index += 1;
+    }
+
+    semantic_tokens
+}
@@ -1,9 +1,10 @@
 use common_lang_types::{
-    ConstExportName, FieldArgumentName, FieldNameOrAlias, FilePath, HasName, IsographDirectiveName,
-    LinkedFieldAlias, LinkedFieldName, ScalarFieldAlias, ScalarFieldName, SelectableFieldName,
-    UnvalidatedTypeName, VariableName, WithLocation, WithSpan,
+    ConstExportName, DescriptionValue, FieldArgumentName, FieldNameOrAlias, FilePath, HasName,
+    IsographDirectiveName, LinkedFieldAlias, LinkedFieldName, ScalarFieldAlias, ScalarFieldName,
+    SelectableFieldName, UnvalidatedTypeName, VariableName, WithLocation, WithSpan,
 };
 use graphql_lang_types::TypeAnnotation;
+use intern::{string_key::StringKey, Lookup};
 
 pub type UnvalidatedSelection = Selection<
     // <UnvalidatedSchemaState as SchemaValidationState>::ClientFieldSelectionScalarFieldAssociatedData,
@@ -18,7 +19,10 @@ pub type UnvalidatedScalarFieldSelection = ScalarFieldSelection<
 
 #[derive(Clone, Eq, PartialEq, Ord, PartialOrd, Debug)]
 pub struct ClientFieldDeclaration {
-    pub const_export_name: ConstExportName,
+    pub description: Option<WithSpan<DescriptionValue>>,
+    /// None if the literal is not passed a js function, in which case the
+    /// client field evaluates to the data it reads.
+    pub const_export_name: Option<ConstExportName>,
     pub parent_type: WithSpan<UnvalidatedTypeName>,
     pub client_field_name: WithSpan<ScalarFieldName>,
     pub selection_set_and_unwraps:
@@ -32,7 +36,40 @@ pub struct ClientFieldDeclaration {
 /// Ugly name, but at least it makes clear this isn't a schema directive.
 pub struct FragmentDirectiveUsage {
     pub name: WithSpan<IsographDirectiveName>,
-    // TODO arguments and such
+    pub arguments: Vec<WithLocation<SelectionFieldArgument>>,
+}
+
+impl FragmentDirectiveUsage {
+    /// If this is an @skip or @include directive whose `if` argument is a literal
+    /// boolean, returns whether the selection it is applied to should be included.
+    /// Otherwise (e.g. if the condition is a variable), returns None.
+    pub fn static_inclusion(&self) -> Option<bool> {
+        let include_if_true = match self.name.item.lookup() {
+            "include" => true,
+            "skip" => false,
+            _ => return None,
+        };
+        self.arguments.iter().find_map(|argument| {
+            if argument.item.name.item.lookup() != "if" {
+                return None;
+            }
+            match argument.item.value.item {
+                NonConstantValue::Boolean(condition) => Some(condition == include_if_true),
+                _ => None,
+            }
+        })
+    }
+
+    /// The value passed as the argument with the given name, if any.
+    pub fn argument(&self, argument_name: &str) -> Option<&NonConstantValue> {
+        self.arguments.iter().find_map(|argument| {
+            if argument.item.name.item.lookup() == argument_name {
+                Some(&argument.item.value.item)
+            } else {
+                None
+            }
+        })
+    }
 }
 
 #[derive(Clone, Eq, PartialEq, Ord, PartialOrd, Debug)]
@@ -42,6 +79,37 @@ pub enum Selection<TScalarField, TLinkedField> {
 }
 
 impl<TScalarField, TLinkedField> Selection<TScalarField, TLinkedField> {
+    /// Whether this selection has a @skip or @include directive with a literal
+    /// condition that excludes it. Such selections are pruned at compile time,
+    /// i.e. they do not appear in the query text or in the reader AST.
+    pub fn is_statically_excluded(&self) -> bool {
+        self.directives()
+            .iter()
+            .any(|directive| directive.item.static_inclusion() == Some(false))
+    }
+
+    pub fn directives(&self) -> &[WithSpan<FragmentDirectiveUsage>] {
+        match self {
+            Selection::ServerField(ServerFieldSelection::ScalarField(scalar_field)) => {
+                &scalar_field.directives
+            }
+            Selection::ServerField(ServerFieldSelection::LinkedField(linked_field)) => {
+                &linked_field.directives
+            }
+        }
+    }
+
+    pub fn directives_mut(&mut self) -> &mut Vec<WithSpan<FragmentDirectiveUsage>> {
+        match self {
+            Selection::ServerField(ServerFieldSelection::ScalarField(scalar_field)) => {
+                &mut scalar_field.directives
+            }
+            Selection::ServerField(ServerFieldSelection::LinkedField(linked_field)) => {
+                &mut linked_field.directives
+            }
+        }
+    }
+
     pub fn map<TNewScalarField, TNewLinkedField>(
         self,
         map: &mut impl FnMut(
@@ -143,6 +211,10 @@ pub struct ScalarFieldSelection<TScalarField> {
     pub associated_data: TScalarField,
     pub unwraps: Vec<WithSpan<Unwrap>>,
     pub arguments: Vec<WithLocation<SelectionFieldArgument>>,
+    pub directives: Vec<WithSpan<FragmentDirectiveUsage>>,
+    /// The comments (e.g. `# text`) on the lines directly preceding the selection,
+    /// which are emitted as JSDoc on the corresponding property of generated types
+    pub description: Option<DescriptionValue>,
 }
 
 impl<TScalarField> ScalarFieldSelection<TScalarField> {
@@ -154,6 +226,8 @@ impl<TScalarField> ScalarFieldSelection<TScalarField> {
             unwraps: self.unwraps,
             arguments: self.arguments,
             normalization_alias: self.normalization_alias,
+            directives: self.directives,
+            description: self.description,
         }
     }
 
@@ -168,6 +242,8 @@ impl<TScalarField> ScalarFieldSelection<TScalarField> {
             unwraps: self.unwraps,
             arguments: self.arguments,
             normalization_alias: self.normalization_alias,
+            directives: self.directives,
+            description: self.description,
         })
     }
 
@@ -187,6 +263,10 @@ pub struct LinkedFieldSelection<TScalarField, TLinkedField> {
     pub selection_set: Vec<WithSpan<Selection<TScalarField, TLinkedField>>>,
     pub unwraps: Vec<WithSpan<Unwrap>>,
     pub arguments: Vec<WithLocation<SelectionFieldArgument>>,
+    pub directives: Vec<WithSpan<FragmentDirectiveUsage>>,
+    /// The comments (e.g. `# text`) on the lines directly preceding the selection,
+    /// which are emitted as JSDoc on the corresponding property of generated types
+    pub description: Option<DescriptionValue>,
 }
 
 impl<TScalarField, TLinkedField> LinkedFieldSelection<TScalarField, TLinkedField> {
@@ -226,6 +306,10 @@ impl SelectionFieldArgument {
 pub enum NonConstantValue {
     Variable(VariableName),
     Integer(u64),
+    Boolean(bool),
+    /// The contents of a string literal, without the quotes. Escape sequences are
+    /// kept as written.
+    String(StringKey),
 }
 
 impl NonConstantValue {
@@ -233,6 +317,8 @@ impl NonConstantValue {
         match self {
             NonConstantValue::Variable(name) => vec![*name],
             NonConstantValue::Integer(_) => vec![],
+            NonConstantValue::Boolean(_) => vec![],
+            NonConstantValue::String(_) => vec![],
         }
     }
 
@@ -241,6 +327,8 @@ impl NonConstantValue {
             NonConstantValue::Variable(name) => format!("v_{}", name),
             // l for literal, i.e. this is shared with others
             NonConstantValue::Integer(int_value) => format!("l_{}", int_value),
+            NonConstantValue::Boolean(bool_value) => format!("l_{}", bool_value),
+            NonConstantValue::String(string_value) => format!("l_{}", string_value),
         }
     }
 }
@@ -4,21 +4,23 @@ use std::collections::{
 };
 
 use common_lang_types::{
-    IsographObjectTypeName, LinkedFieldAlias, LinkedFieldName, Location, ScalarFieldAlias,
-    ScalarFieldName, SelectableFieldName, Span, VariableName, WithLocation, WithSpan,
+    ArtifactNamespace, IsographObjectTypeName, LinkedFieldAlias, LinkedFieldName, Location,
+    ScalarFieldAlias, ScalarFieldName, SelectableFieldName, Span, VariableName, WithLocation,
+    WithSpan,
 };
 use graphql_lang_types::GraphQLInputValueDefinition;
 use intern::{string_key::Intern, Lookup};
 use isograph_lang_types::{
-    ClientFieldId, ObjectId, ScalarFieldSelection, SelectableFieldId, Selection,
-    SelectionFieldArgument, ServerFieldSelection, VariableDefinition,
+    ClientFieldId, FragmentDirectiveUsage, NonConstantValue, ObjectId, ScalarFieldSelection,
+    SelectableFieldId, Selection, SelectionFieldArgument, ServerFieldSelection, VariableDefinition,
 };
 
 use crate::{
     expose_field_directive::RequiresRefinement, ArgumentKeyAndValue, ClientFieldVariant,
-    FieldDefinitionLocation, MutationFieldClientFieldVariant, NameAndArguments, PathToRefetchField,
-    ValidatedClientField, ValidatedFieldDefinitionLocation, ValidatedLinkedFieldSelection,
-    ValidatedSchema, ValidatedSchemaIdField, ValidatedSchemaObject, ValidatedSelection,
+    FieldDefinitionLocation, MutationFieldClientFieldVariant, NameAndArguments, OperationKind,
+    PathToRefetchField, ValidatedClientField, ValidatedFieldDefinitionLocation,
+    ValidatedLinkedFieldSelection, ValidatedSchema, ValidatedSchemaIdField, ValidatedSchemaObject,
+    ValidatedSelection,
 };
 
 type MergedSelectionMap = HashMap<NormalizationKey, WithSpan<MergedServerFieldSelection>>;
@@ -29,6 +31,7 @@ pub struct RootRefetchedPath {
     pub variables: Vec<VariableName>,
     // TODO This should not be an option
     pub field_name: SelectableFieldName,
+    pub operation_kind: OperationKind,
 }
 
 // TODO add id and typename variants, impl Ord, and get rid of the NormalizationKey enum
@@ -36,21 +39,46 @@ pub struct RootRefetchedPath {
 pub enum MergedServerFieldSelection {
     ScalarField(MergedScalarFieldSelection),
     LinkedField(MergedLinkedFieldSelection),
+    InlineFragment(MergedInlineFragmentSelection),
+    DeferredFragment(MergedDeferredFragmentSelection),
 }
 
 impl MergedServerFieldSelection {
     pub fn reachable_variables(&self) -> HashSet<VariableName> {
         match self {
             MergedServerFieldSelection::ScalarField(scalar_field) => {
-                get_variable_selections(&scalar_field.arguments)
+                let mut reachable_variables = get_variable_selections(&scalar_field.arguments);
+                if let Some(stream) = &scalar_field.stream {
+                    reachable_variables.extend(stream.reachable_variables());
+                }
+                reachable_variables
             }
             MergedServerFieldSelection::LinkedField(linked_field) => {
                 let mut reachable_variables = get_variable_selections(&linked_field.arguments);
+                if let Some(stream) = &linked_field.stream {
+                    reachable_variables.extend(stream.reachable_variables());
+                }
                 for selection in linked_field.selection_set.iter() {
                     reachable_variables.extend(selection.item.reachable_variables());
                 }
                 reachable_variables
             }
+            MergedServerFieldSelection::InlineFragment(inline_fragment) => inline_fragment
+                .selection_set
+                .iter()
+                .flat_map(|selection| selection.item.reachable_variables())
+                .collect(),
+            MergedServerFieldSelection::DeferredFragment(deferred_fragment) => {
+                let mut reachable_variables: HashSet<_> = deferred_fragment
+                    .selection_set
+                    .iter()
+                    .flat_map(|selection| selection.item.reachable_variables())
+                    .collect();
+                if let Some(if_condition) = &deferred_fragment.if_condition {
+                    reachable_variables.extend(if_condition.reachable_variables());
+                }
+                reachable_variables
+            }
         }
     }
 }
@@ -70,6 +98,8 @@ pub struct MergedScalarFieldSelection {
     // TODO calculate this when needed
     pub normalization_alias: Option<WithLocation<ScalarFieldAlias>>,
     pub arguments: Vec<WithLocation<SelectionFieldArgument>>,
+    /// Present if every selection of this (list) field is annotated with @stream.
+    pub stream: Option<MergedStreamDirective>,
 }
 
 #[derive(Clone, Eq, PartialEq, Ord, PartialOrd, Debug)]
@@ -79,6 +109,49 @@ pub struct MergedLinkedFieldSelection {
     pub normalization_alias: Option<WithLocation<LinkedFieldAlias>>,
     pub selection_set: Vec<WithSpan<MergedServerFieldSelection>>,
     pub arguments: Vec<WithLocation<SelectionFieldArgument>>,
+    /// The type of the linked field, i.e. the type on which the fields in
+    /// selection_set are selected.
+    pub concrete_type: IsographObjectTypeName,
+    /// Present if every selection of this field is annotated with @stream.
+    pub stream: Option<MergedStreamDirective>,
+}
+
+#[derive(Clone, Eq, PartialEq, Ord, PartialOrd, Debug)]
+pub struct MergedStreamDirective {
+    pub label: String,
+    pub initial_count: NonConstantValue,
+    /// The `if` argument, unless the directive unconditionally applies.
+    pub if_condition: Option<NonConstantValue>,
+}
+
+impl MergedStreamDirective {
+    fn reachable_variables(&self) -> HashSet<VariableName> {
+        let mut reachable_variables = self.initial_count.reachable_variables();
+        if let Some(if_condition) = &self.if_condition {
+            reachable_variables.extend(if_condition.reachable_variables());
+        }
+        reachable_variables
+    }
+}
+
+/// The merged selections of all refinement fields (e.g. asUser) to a given
+/// concrete type. These are selected on the same record as their parent,
+/// i.e. `... on User { ... }`.
+#[derive(Clone, Eq, PartialEq, Ord, PartialOrd, Debug)]
+pub struct MergedInlineFragmentSelection {
+    pub type_to_refine_to: IsographObjectTypeName,
+    pub selection_set: Vec<WithSpan<MergedServerFieldSelection>>,
+}
+
+/// The merged selections annotated with @defer (with the same condition) on a
+/// given record, i.e. `... @defer(label: "...") { ... }`. The server delivers
+/// these in a subsequent payload, identified by the label.
+#[derive(Clone, Eq, PartialEq, Ord, PartialOrd, Debug)]
+pub struct MergedDeferredFragmentSelection {
+    pub label: String,
+    /// The `if` argument, unless the directive unconditionally applies.
+    pub if_condition: Option<NonConstantValue>,
+    pub selection_set: Vec<WithSpan<MergedServerFieldSelection>>,
 }
 
 /// A merged selection set is an input for generating:
@@ -98,6 +171,12 @@ impl std::ops::Deref for MergedSelectionSet {
     }
 }
 
+impl std::ops::DerefMut for MergedSelectionSet {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.0
+    }
+}
+
 impl MergedSelectionSet {
     fn new(
         mut unsorted_vec: Vec<(NormalizationKey, WithSpan<MergedServerFieldSelection>)>,
@@ -106,7 +185,7 @@ impl MergedSelectionSet {
         MergedSelectionSet(unsorted_vec.into_iter().map(|(_, value)| value).collect())
     }
 
-    fn reachable_variables(&self) -> HashSet<VariableName> {
+    pub fn reachable_variables(&self) -> HashSet<VariableName> {
         self.0
             .iter()
             .flat_map(|x| x.item.reachable_variables())
@@ -118,19 +197,7 @@ fn find_by_path(
     path: &PathToRefetchField,
 ) -> MergedSelectionSet {
     for item in path.linked_fields.iter() {
-        let linked_field_selection = root
-            .iter()
-            .find_map(|linked_field_selection| {
-                if let MergedServerFieldSelection::LinkedField(linked_field) =
-                    &linked_field_selection.item
-                {
-                    let linked_field_name: SelectableFieldName = linked_field.name.item.into();
-                    if linked_field_name == item.name {
-                        return Some(linked_field);
-                    }
-                }
-                None
-            })
+        let linked_field_selection = find_linked_field(root, item.name)
             .expect("Linked field not found. This is indicative of a bug in Isograph.");
 
         root = &linked_field_selection.selection_set;
@@ -140,6 +207,130 @@ fn find_by_path(
     MergedSelectionSet(root.to_vec())
 }
 
+/// Finds a linked field in a selection set, including in the inline and deferred
+/// fragments of that selection set, which are selected on the same record.
+fn find_linked_field(
+    selection_set: &[WithSpan<MergedServerFieldSelection>],
+    name: SelectableFieldName,
+) -> Option<&MergedLinkedFieldSelection> {
+    selection_set
+        .iter()
+        .find_map(|selection| match &selection.item {
+            MergedServerFieldSelection::ScalarField(_) => None,
+            MergedServerFieldSelection::LinkedField(linked_field) => {
+                let linked_field_name: SelectableFieldName = linked_field.name.item.into();
+                if linked_field_name == name {
+                    Some(linked_field)
+                } else {
+                    None
+                }
+            }
+            MergedServerFieldSelection::InlineFragment(inline_fragment) => {
+                find_linked_field(&inline_fragment.selection_set, name)
+            }
+            MergedServerFieldSelection::DeferredFragment(deferred_fragment) => {
+                find_linked_field(&deferred_fragment.selection_set, name)
+            }
+        })
+}
+
+/// For each selection in a merged selection set, the client fields whose selection
+/// sets contained it. Used to explain why a field is part of a query.
+///
+/// Selections are identified by their path from the root of the merged selection
+/// set, including inline and deferred fragments. Selections that Isograph adds
+/// itself (e.g. id and __typename fields) have no origins.
+#[derive(Debug, Default)]
+pub struct MergedSelectionOrigins(HashMap<Vec<NormalizationKey>, Vec<ClientFieldId>>);
+
+impl MergedSelectionOrigins {
+    /// The client fields that contributed the last selection in path, in the order
+    /// in which they were encountered. path consists of the selections from the
+    /// root of the merged selection set to (and including) the selection.
+    pub fn client_field_ids(&self, path: &[&MergedServerFieldSelection]) -> &[ClientFieldId] {
+        let path: Vec<_> = path
+            .iter()
+            .map(|selection| normalization_key(selection))
+            .collect();
+        self.0.get(&path).map(Vec::as_slice).unwrap_or_default()
+    }
+
+    /// Walks selection_set (which must be the merged selection set for which these
+    /// origins were recorded) depth-first, and calls visit with the path to each
+    /// selection and the client fields that contributed it.
+    pub fn walk<'a>(
+        &self,
+        selection_set: &'a [WithSpan<MergedServerFieldSelection>],
+        visit: &mut impl FnMut(&[&'a MergedServerFieldSelection], &[ClientFieldId]),
+    ) {
+        self.walk_with_path(selection_set, &mut vec![], visit);
+    }
+
+    fn walk_with_path<'a>(
+        &self,
+        selection_set: &'a [WithSpan<MergedServerFieldSelection>],
+        path: &mut Vec<&'a MergedServerFieldSelection>,
+        visit: &mut impl FnMut(&[&'a MergedServerFieldSelection], &[ClientFieldId]),
+    ) {
+        for selection in selection_set.iter() {
+            path.push(&selection.item);
+            visit(path, self.client_field_ids(path));
+            let nested_selection_set = match &selection.item {
+                MergedServerFieldSelection::ScalarField(_) => None,
+                MergedServerFieldSelection::LinkedField(linked_field) => {
+                    Some(&linked_field.selection_set)
+                }
+                MergedServerFieldSelection::InlineFragment(inline_fragment) => {
+                    Some(&inline_fragment.selection_set)
+                }
+                MergedServerFieldSelection::DeferredFragment(deferred_fragment) => {
+                    Some(&deferred_fragment.selection_set)
+                }
+            };
+            if let Some(nested_selection_set) = nested_selection_set {
+                self.walk_with_path(nested_selection_set, path, visit);
+            }
+            path.pop();
+        }
+    }
+
+    /// For each client field, the paths to the selections of selection_set that it
+    /// contributed, in depth-first order. E.g. a bundle analyzer can use this to
+    /// attribute the size of a query to the client fields that caused it.
+    pub fn contributions_by_client_field<'a>(
+        &self,
+        selection_set: &'a [WithSpan<MergedServerFieldSelection>],
+    ) -> HashMap<ClientFieldId, Vec<Vec<&'a MergedServerFieldSelection>>> {
+        let mut contributions: HashMap<_, Vec<_>> = HashMap::new();
+        self.walk(selection_set, &mut |path, client_field_ids| {
+            for client_field_id in client_field_ids {
+                contributions
+                    .entry(*client_field_id)
+                    .or_default()
+                    .push(path.to_vec());
+            }
+        });
+        contributions
+    }
+}
+
+fn normalization_key(selection: &MergedServerFieldSelection) -> NormalizationKey {
+    match selection {
+        MergedServerFieldSelection::ScalarField(scalar_field) => NormalizationKey::ServerField(
+            name_and_arguments(scalar_field.name.item.into(), &scalar_field.arguments),
+        ),
+        MergedServerFieldSelection::LinkedField(linked_field) => NormalizationKey::ServerField(
+            name_and_arguments(linked_field.name.item.into(), &linked_field.arguments),
+        ),
+        MergedServerFieldSelection::InlineFragment(inline_fragment) => {
+            NormalizationKey::InlineFragment(inline_fragment.type_to_refine_to)
+        }
+        MergedServerFieldSelection::DeferredFragment(deferred_fragment) => {
+            NormalizationKey::DeferredFragment(deferred_fragment.label.clone())
+        }
+    }
+}
+
 impl Into<Vec<WithSpan<MergedServerFieldSelection>>> for MergedSelectionSet {
     fn into(self) -> Vec<WithSpan<MergedServerFieldSelection>> {
         self.0
@@ -151,9 +342,26 @@ enum NormalizationKey {
     // __typename,
     Id,
     ServerField(NameAndArguments),
+    InlineFragment(IsographObjectTypeName),
+    DeferredFragment(String),
 }
 
+/// The merged selection set of a client field, i.e. of its selections and of the
+/// selections of the client fields it selects, recursively. This is created once,
+/// when validating the schema, and shared by all artifacts that need it.
 #[derive(Debug)]
+pub struct ClientFieldMergedSelectionSet {
+    pub selection_set: MergedSelectionSet,
+    pub root_refetched_paths: Vec<RootRefetchedPath>,
+    /// The client fields that are selected, directly or transitively, sorted by id.
+    pub encountered_client_field_ids: Vec<ClientFieldId>,
+    /// The refetch queries and mutations reachable from this client field. These
+    /// are only collected for entrypoints (without @skipCodegen), as artifacts are
+    /// only generated for the queries reachable from entrypoints.
+    pub artifact_queue: Vec<ArtifactQueueItem>,
+}
+
+#[derive(Debug, Clone)]
 pub enum ArtifactQueueItem {
     RefetchField(RefetchFieldResolverInfo),
     MutationField(MutationFieldResolverInfo),
@@ -168,6 +376,9 @@ pub struct RefetchFieldResolverInfo {
     pub variable_definitions: Vec<WithSpan<VariableDefinition<SelectableFieldId>>>,
     pub root_parent_object: IsographObjectTypeName,
     pub root_fetchable_field: SelectableFieldName,
+    /// The artifact namespace of the root fetchable field, next to whose artifacts
+    /// the refetch query artifact is written
+    pub root_artifact_namespace: Option<ArtifactNamespace>,
     // TODO wrap in a newtype
     pub refetch_query_index: usize,
 }
@@ -181,6 +392,9 @@ pub struct MutationFieldResolverInfo {
     pub variable_definitions: Vec<WithSpan<VariableDefinition<SelectableFieldId>>>,
     pub root_parent_object: IsographObjectTypeName,
     pub root_fetchable_field: SelectableFieldName,
+    /// The artifact namespace of the root fetchable field, next to whose artifacts
+    /// the refetch query artifact is written
+    pub root_artifact_namespace: Option<ArtifactNamespace>,
     // TODO wrap in a newtype
     pub refetch_query_index: usize,
     // TODO make MutationFieldResolverInfo and RefetchFieldResolverInfo
@@ -211,6 +425,13 @@ struct MergeTraversalState<'a> {
     /// to generate the refetch query.
     current_path: PathToRefetchField,
     encountered_resolver_ids: Option<&'a mut HashSet<ClientFieldId>>,
+    /// The client field whose selection set is currently being merged.
+    current_client_field_id: ClientFieldId,
+    /// Present if we are recording which client fields contributed each selection.
+    /// In that case, current_origin_path contains the keys of the selections from
+    /// the root to the selection set currently being merged.
+    origins: Option<MergedSelectionOrigins>,
+    current_origin_path: Vec<NormalizationKey>,
 }
 
 impl<'a> MergeTraversalState<'a> {
@@ -223,6 +444,32 @@ impl<'a> MergeTraversalState<'a> {
             paths_to_refetch_fields: Default::default(),
             current_path: Default::default(),
             encountered_resolver_ids,
+            current_client_field_id: resolver.id,
+            origins: None,
+            current_origin_path: vec![],
+        }
+    }
+
+    fn record_origin(&mut self, normalization_key: &NormalizationKey) {
+        if let Some(origins) = &mut self.origins {
+            let mut path = self.current_origin_path.clone();
+            path.push(normalization_key.clone());
+            let client_field_ids = origins.0.entry(path).or_default();
+            if !client_field_ids.contains(&self.current_client_field_id) {
+                client_field_ids.push(self.current_client_field_id);
+            }
+        }
+    }
+
+    fn push_origin_path(&mut self, normalization_key: NormalizationKey) {
+        if self.origins.is_some() {
+            self.current_origin_path.push(normalization_key);
+        }
+    }
+
+    fn pop_origin_path(&mut self) {
+        if self.origins.is_some() {
+            self.current_origin_path.pop();
         }
     }
 }
@@ -282,7 +529,7 @@ pub fn create_merged_selection_set(
                             })
                             .collect();
 
-                        let field_name = match resolver_variant {
+                        let (field_name, operation_kind) = match resolver_variant {
                             ClientFieldVariant::RefetchField => {
                                 artifact_queue.push(ArtifactQueueItem::RefetchField(
                                     RefetchFieldResolverInfo {
@@ -294,10 +541,13 @@ pub fn create_merged_selection_set(
                                             .object(root_fetchable_resolver.parent_object_id)
                                             .name,
                                         root_fetchable_field: root_fetchable_resolver.name,
+                                        root_artifact_namespace: root_fetchable_resolver
+                                            .type_and_field
+                                            .artifact_namespace,
                                         refetch_query_index: index,
                                     },
                                 ));
-                                "__refetch".intern().into()
+                                ("__refetch".intern().into(), OperationKind::Query)
                             }
                             ClientFieldVariant::MutationField(
                                 MutationFieldClientFieldVariant {
@@ -329,6 +579,9 @@ pub fn create_merged_selection_set(
                                             .object(root_fetchable_resolver.parent_object_id)
                                             .name,
                                         root_fetchable_field: root_fetchable_resolver.name,
+                                        root_artifact_namespace: root_fetchable_resolver
+                                            .type_and_field
+                                            .artifact_namespace,
                                         refetch_query_index: index,
                                         mutation_field_name,
                                         mutation_primary_field_name,
@@ -336,7 +589,7 @@ pub fn create_merged_selection_set(
                                         requires_refinement,
                                     },
                                 ));
-                                mutation_field_name
+                                (mutation_field_name, OperationKind::Mutation)
                             }
                             _ => panic!("invalid resolver variant"),
                         };
@@ -349,6 +602,7 @@ pub fn create_merged_selection_set(
                             path: path_to_refetch_field,
                             variables: reachable_variables_vec,
                             field_name,
+                            operation_kind,
                         }
                     },
                 )
@@ -367,12 +621,14 @@ pub fn create_merged_selection_set(
                     // TODO we can pre-calculate this instead of re-iterating here
                     let reachable_variables = nested_merged_selection_set.reachable_variables();
 
-                    let field_name = match resolver_variant {
-                        ClientFieldVariant::RefetchField => "__refetch".intern().into(),
+                    let (field_name, operation_kind) = match resolver_variant {
+                        ClientFieldVariant::RefetchField => {
+                            ("__refetch".intern().into(), OperationKind::Query)
+                        }
                         ClientFieldVariant::MutationField(MutationFieldClientFieldVariant {
                             mutation_field_name,
                             ..
-                        }) => mutation_field_name,
+                        }) => (mutation_field_name, OperationKind::Mutation),
                         _ => panic!("invalid resolver variant"),
                     };
 
@@ -384,6 +640,7 @@ pub fn create_merged_selection_set(
                         path: path_to_refetch_field,
                         variables: reachable_variables_vec,
                         field_name,
+                        operation_kind,
                     }
                 })
                 .collect();
@@ -393,6 +650,127 @@ pub fn create_merged_selection_set(
     }
 }
 
+/// Creates the merged selection set of every client field with a selection set,
+/// and stores it on the client field.
+///
+/// Entrypoints are merged relative to the root type (e.g. Query or Mutation) that
+/// they are on. As when generating artifacts, we assume that other client fields are
+/// on the Query type. If there is no Query type, their merged selection sets are not
+/// created.
+pub(crate) fn merge_client_field_selection_sets(schema: &mut ValidatedSchema) {
+    let merged_selection_sets: Vec<_> = schema
+        .client_fields
+        .iter()
+        .map(|client_field| {
+            let (selection_set, _) = client_field.selection_set_and_unwraps.as_ref()?;
+            let is_entrypoint = schema.entrypoints.contains(&client_field.id);
+            let is_fetchable = !client_field.skip_codegen && is_entrypoint;
+            let parent_type_id = if is_entrypoint {
+                client_field.parent_object_id
+            } else {
+                schema.query_type_id?
+            };
+
+            let mut artifact_queue = vec![];
+            let mut encountered_client_field_ids = HashSet::new();
+            let (selection_set, root_refetched_paths) = create_merged_selection_set(
+                schema,
+                schema.schema_data.object(parent_type_id),
+                selection_set,
+                if is_fetchable {
+                    Some(&mut artifact_queue)
+                } else {
+                    None
+                },
+                Some(&mut encountered_client_field_ids),
+                client_field,
+            );
+
+            let mut encountered_client_field_ids: Vec<_> =
+                encountered_client_field_ids.into_iter().collect();
+            encountered_client_field_ids.sort();
+
+            Some(ClientFieldMergedSelectionSet {
+                selection_set,
+                root_refetched_paths,
+                encountered_client_field_ids,
+                artifact_queue,
+            })
+        })
+        .collect();
+
+    for (client_field, merged_selection_set) in
+        schema.client_fields.iter_mut().zip(merged_selection_sets)
+    {
+        client_field.merged_selection_set = merged_selection_set;
+    }
+}
+
+/// Creates the merged selection set of a fetchable client field, and records which
+/// client fields contributed each of its selections. This is slower than
+/// create_merged_selection_set, and is meant for tooling, e.g. for debugging why a
+/// field is part of a query, or for analyzing the data dependencies of client
+/// fields.
+///
+/// parent_type is the root type that the client field is on, e.g. Query.
+pub fn create_merged_selection_set_with_origins(
+    schema: &ValidatedSchema,
+    parent_type: &ValidatedSchemaObject,
+    validated_selections: &[WithSpan<ValidatedSelection>],
+    root_fetchable_resolver: &ValidatedClientField,
+) -> (
+    MergedSelectionSet,
+    Vec<RootRefetchedPath>,
+    MergedSelectionOrigins,
+) {
+    let mut merge_traversal_state = MergeTraversalState::new(root_fetchable_resolver, None);
+    merge_traversal_state.origins = Some(MergedSelectionOrigins::default());
+    let merged_selection_set = create_merged_selection_set_with_merge_traversal_state(
+        schema,
+        parent_type,
+        validated_selections,
+        &mut merge_traversal_state,
+    );
+
+    let root_refetched_paths = merge_traversal_state
+        .paths_to_refetch_fields
+        .into_iter()
+        .map(|(path_to_refetch_field, _, resolver_variant)| {
+            let nested_merged_selection_set =
+                find_by_path(&merged_selection_set, &path_to_refetch_field);
+            let mut variables: Vec<_> = nested_merged_selection_set
+                .reachable_variables()
+                .into_iter()
+                .collect();
+            variables.sort();
+
+            let (field_name, operation_kind) = match resolver_variant {
+                ClientFieldVariant::RefetchField => {
+                    ("__refetch".intern().into(), OperationKind::Query)
+                }
+                ClientFieldVariant::MutationField(MutationFieldClientFieldVariant {
+                    mutation_field_name,
+                    ..
+                }) => (mutation_field_name, OperationKind::Mutation),
+                _ => panic!("invalid resolver variant"),
+            };
+
+            RootRefetchedPath {
+                path: path_to_refetch_field,
+                variables,
+                field_name,
+                operation_kind,
+            }
+        })
+        .collect();
+
+    (
+        merged_selection_set,
+        root_refetched_paths,
+        merge_traversal_state.origins.unwrap_or_default(),
+    )
+}
+
 fn create_merged_selection_set_with_merge_traversal_state(
     schema: &ValidatedSchema,
     parent_type: &ValidatedSchemaObject,
@@ -427,14 +805,36 @@ fn merge_selections_into_set(
     validated_selections: &[WithSpan<ValidatedSelection>],
     merge_traversal_state: &mut MergeTraversalState<'_>,
 ) {
-    for validated_selection in validated_selections.iter().filter(filter_id_fields) {
+    for validated_selection in validated_selections
+        .iter()
+        .filter(filter_id_fields)
+        .filter(|selection| !selection.item.is_statically_excluded())
+    {
         let span = validated_selection.span;
+
+        if let Some(defer) = active_directive(validated_selection.item.directives(), "defer") {
+            merge_deferred_selection(
+                schema,
+                merged_selection_map,
+                parent_type,
+                validated_selection,
+                non_literal_if_condition(defer),
+                merge_traversal_state,
+            );
+            continue;
+        }
+
         match &validated_selection.item {
             Selection::ServerField(validated_server_field) => match validated_server_field {
                 ServerFieldSelection::ScalarField(scalar_field) => {
                     match &scalar_field.associated_data {
                         FieldDefinitionLocation::Server(_) => {
-                            merge_scalar_server_field(scalar_field, merged_selection_map, span);
+                            merge_scalar_server_field(
+                                scalar_field,
+                                merged_selection_map,
+                                span,
+                                merge_traversal_state,
+                            );
                         }
                         FieldDefinitionLocation::Client(resolver_field_id) => {
                             if let Some(ref mut encountered_resolver_ids) =
@@ -442,16 +842,35 @@ fn merge_selections_into_set(
                             {
                                 encountered_resolver_ids.insert(*resolver_field_id);
                             }
+                            let parent_client_field_id = std::mem::replace(
+                                &mut merge_traversal_state.current_client_field_id,
+                                *resolver_field_id,
+                            );
                             merge_scalar_resolver_field(
                                 parent_type,
                                 schema,
                                 merged_selection_map,
                                 merge_traversal_state,
                                 *resolver_field_id,
-                            )
+                                &scalar_field.arguments,
+                            );
+                            merge_traversal_state.current_client_field_id = parent_client_field_id;
                         }
                     };
                 }
+                ServerFieldSelection::LinkedField(new_linked_field)
+                    if new_linked_field.associated_data.is_refinement =>
+                {
+                    // Refinements do not change the record we are reading from, so they
+                    // are not part of the path to refetch fields.
+                    merge_inline_fragment(
+                        schema,
+                        merged_selection_map,
+                        new_linked_field,
+                        span,
+                        merge_traversal_state,
+                    );
+                }
                 ServerFieldSelection::LinkedField(new_linked_field) => {
                     let normalization_key = NormalizationKey::ServerField(name_and_arguments(
                         new_linked_field.name.item.into(),
@@ -471,6 +890,8 @@ fn merge_selections_into_set(
                                 })
                                 .collect(),
                         });
+                    merge_traversal_state.record_origin(&normalization_key);
+                    merge_traversal_state.push_origin_path(normalization_key.clone());
 
                     match merged_selection_map.entry(normalization_key) {
                         Entry::Vacant(vacant_entry) => merge_linked_field_into_vacant_entry(
@@ -488,6 +909,7 @@ fn merge_selections_into_set(
                         ),
                     };
 
+                    merge_traversal_state.pop_origin_path();
                     merge_traversal_state.current_path.linked_fields.pop();
                 }
             },
@@ -519,12 +941,12 @@ fn merge_linked_field_into_vacant_entry(
     span: Span,
     merge_traversal_state: &mut MergeTraversalState<'_>,
 ) {
+    let type_id = new_linked_field.associated_data.parent_object_id;
+    let linked_field_parent_type = schema.schema_data.object(type_id);
     vacant_entry.insert(WithSpan::new(
         MergedServerFieldSelection::LinkedField(MergedLinkedFieldSelection {
             name: new_linked_field.name,
             selection_set: {
-                let type_id = new_linked_field.associated_data.parent_object_id;
-                let linked_field_parent_type = schema.schema_data.object(type_id);
                 let merged_set = create_merged_selection_set_with_merge_traversal_state(
                     schema,
                     linked_field_parent_type,
@@ -535,6 +957,11 @@ fn merge_linked_field_into_vacant_entry(
             },
             arguments: new_linked_field.arguments.clone(),
             normalization_alias: new_linked_field.normalization_alias,
+            concrete_type: linked_field_parent_type.name,
+            stream: merged_stream_directive(
+                &new_linked_field.directives,
+                &merge_traversal_state.current_path,
+            ),
         }),
         span,
     ));
@@ -548,10 +975,16 @@ fn merge_linked_field_into_occupied_entry(
 ) {
     let existing_selection = occupied.get_mut();
     match &mut existing_selection.item {
-        MergedServerFieldSelection::ScalarField(_) => {
+        MergedServerFieldSelection::ScalarField(_)
+        | MergedServerFieldSelection::InlineFragment(_)
+        | MergedServerFieldSelection::DeferredFragment(_) => {
             panic!("expected linked, probably a bug in Isograph")
         }
         MergedServerFieldSelection::LinkedField(existing_linked_field) => {
+            // A field is only streamed if every selection of it asks for that.
+            if active_directive(&new_linked_field.directives, "stream").is_none() {
+                existing_linked_field.stream = None;
+            }
             let type_id = new_linked_field.associated_data.parent_object_id;
             let linked_field_parent_type = schema.schema_data.object(type_id);
             HACK__merge_linked_fields(
@@ -565,15 +998,208 @@ fn merge_linked_field_into_occupied_entry(
     }
 }
 
+fn merge_inline_fragment(
+    schema: &ValidatedSchema,
+    merged_selection_map: &mut MergedSelectionMap,
+    new_linked_field: &ValidatedLinkedFieldSelection,
+    span: Span,
+    merge_traversal_state: &mut MergeTraversalState<'_>,
+) {
+    let concrete_type = schema
+        .schema_data
+        .object(new_linked_field.associated_data.parent_object_id);
+
+    // Refining requires knowing the concrete type of the record, so we must
+    // select __typename on the abstract parent.
+    select_typename_in_merged_selection(merged_selection_map);
+
+    let normalization_key = NormalizationKey::InlineFragment(concrete_type.name);
+    merge_traversal_state.record_origin(&normalization_key);
+    merge_traversal_state.push_origin_path(normalization_key.clone());
+
+    match merged_selection_map.entry(normalization_key) {
+        Entry::Vacant(vacant_entry) => {
+            let merged_set = create_merged_selection_set_with_merge_traversal_state(
+                schema,
+                concrete_type,
+                &new_linked_field.selection_set,
+                merge_traversal_state,
+            );
+            vacant_entry.insert(WithSpan::new(
+                MergedServerFieldSelection::InlineFragment(MergedInlineFragmentSelection {
+                    type_to_refine_to: concrete_type.name,
+                    selection_set: merged_set.into(),
+                }),
+                span,
+            ));
+        }
+        Entry::Occupied(mut occupied) => match &mut occupied.get_mut().item {
+            MergedServerFieldSelection::InlineFragment(existing_inline_fragment) => {
+                HACK__merge_linked_fields(
+                    schema,
+                    &mut existing_inline_fragment.selection_set,
+                    &new_linked_field.selection_set,
+                    concrete_type,
+                    merge_traversal_state,
+                );
+            }
+            MergedServerFieldSelection::ScalarField(_)
+            | MergedServerFieldSelection::LinkedField(_)
+            | MergedServerFieldSelection::DeferredFragment(_) => {
+                panic!("expected inline fragment, probably a bug in Isograph")
+            }
+        },
+    }
+
+    merge_traversal_state.pop_origin_path();
+}
+
+/// Deferred selections are merged into a deferred fragment on the current record.
+/// All selections deferred with the same condition on the same record are delivered
+/// together.
+fn merge_deferred_selection(
+    schema: &ValidatedSchema,
+    merged_selection_map: &mut MergedSelectionMap,
+    parent_type: &ValidatedSchemaObject,
+    validated_selection: &WithSpan<ValidatedSelection>,
+    if_condition: Option<NonConstantValue>,
+    merge_traversal_state: &mut MergeTraversalState<'_>,
+) {
+    let label =
+        incremental_delivery_label("defer", &merge_traversal_state.current_path, &if_condition);
+
+    let mut selection_without_defer = validated_selection.clone();
+    selection_without_defer
+        .item
+        .directives_mut()
+        .retain(|directive| directive.item.name.item.lookup() != "defer");
+
+    let normalization_key = NormalizationKey::DeferredFragment(label.clone());
+    merge_traversal_state.record_origin(&normalization_key);
+    merge_traversal_state.push_origin_path(normalization_key.clone());
+
+    let deferred_fragment = merged_selection_map
+        .entry(normalization_key)
+        .or_insert_with(|| {
+            WithSpan::new(
+                MergedServerFieldSelection::DeferredFragment(MergedDeferredFragmentSelection {
+                    label,
+                    if_condition,
+                    selection_set: vec![],
+                }),
+                validated_selection.span,
+            )
+        });
+    match &mut deferred_fragment.item {
+        MergedServerFieldSelection::DeferredFragment(deferred_fragment) => {
+            HACK__merge_linked_fields(
+                schema,
+                &mut deferred_fragment.selection_set,
+                &[selection_without_defer],
+                parent_type,
+                merge_traversal_state,
+            );
+        }
+        MergedServerFieldSelection::ScalarField(_)
+        | MergedServerFieldSelection::LinkedField(_)
+        | MergedServerFieldSelection::InlineFragment(_) => {
+            panic!("expected deferred fragment, probably a bug in Isograph")
+        }
+    }
+
+    merge_traversal_state.pop_origin_path();
+}
+
+/// Returns the directive with the given name (i.e. @defer or @stream), unless
+/// it is disabled with a literal `if: false`.
+fn active_directive<'a>(
+    directives: &'a [WithSpan<FragmentDirectiveUsage>],
+    directive_name: &str,
+) -> Option<&'a FragmentDirectiveUsage> {
+    directives
+        .iter()
+        .map(|directive| &directive.item)
+        .find(|directive| directive.name.item.lookup() == directive_name)
+        .filter(|directive| {
+            !matches!(
+                directive.argument("if"),
+                Some(NonConstantValue::Boolean(false))
+            )
+        })
+}
+
+/// The @stream directive of a field, if it is active. path is the path to the field
+/// (including it), from which the label is derived.
+fn merged_stream_directive(
+    directives: &[WithSpan<FragmentDirectiveUsage>],
+    path: &PathToRefetchField,
+) -> Option<MergedStreamDirective> {
+    active_directive(directives, "stream").map(|stream| {
+        let if_condition = non_literal_if_condition(stream);
+        MergedStreamDirective {
+            label: incremental_delivery_label("stream", path, &if_condition),
+            initial_count: stream
+                .argument("initialCount")
+                .cloned()
+                .unwrap_or(NonConstantValue::Integer(0)),
+            if_condition,
+        }
+    })
+}
+
+fn non_literal_if_condition(directive: &FragmentDirectiveUsage) -> Option<NonConstantValue> {
+    match directive.argument("if") {
+        None | Some(NonConstantValue::Boolean(_)) => None,
+        Some(if_condition) => Some(if_condition.clone()),
+    }
+}
+
+/// Labels identify the subsequent payloads of @defer and @stream, and must be unique
+/// within an operation. So, they are derived from the path to the record (or for
+/// @stream, the field), and the condition.
+fn incremental_delivery_label(
+    directive_name: &str,
+    path: &PathToRefetchField,
+    if_condition: &Option<NonConstantValue>,
+) -> String {
+    let mut label = format!("{directive_name}$");
+    for (index, name_and_arguments) in path.linked_fields.iter().enumerate() {
+        if index != 0 {
+            label.push('.');
+        }
+        label.push_str(name_and_arguments.name.lookup());
+        for argument in name_and_arguments.arguments.iter() {
+            label.push_str(&format!(
+                "____{}___{}",
+                argument.key,
+                argument.value.to_alias_str_chunk()
+            ));
+        }
+    }
+    if let Some(if_condition) = if_condition {
+        label.push_str(&format!("$if_{}", if_condition.to_alias_str_chunk()));
+    }
+    label
+}
+
 fn merge_scalar_resolver_field(
     parent_type: &ValidatedSchemaObject,
     schema: &ValidatedSchema,
     merged_selection_map: &mut MergedSelectionMap,
     merge_traversal_state: &mut MergeTraversalState<'_>,
     resolver_field_id: ClientFieldId,
+    arguments: &[WithLocation<SelectionFieldArgument>],
 ) {
     let resolver_field = schema.resolver(resolver_field_id);
     if let Some((ref selection_set, _)) = resolver_field.selection_set_and_unwraps {
+        let selection_set_with_arguments;
+        let selection_set = if arguments.is_empty() {
+            selection_set
+        } else {
+            selection_set_with_arguments =
+                replace_variables_with_arguments(selection_set, arguments);
+            &selection_set_with_arguments
+        };
         merge_selections_into_set(
             schema,
             merged_selection_map,
@@ -615,33 +1241,93 @@ fn merge_scalar_resolver_field(
     }
 }
 
+/// Replaces the variables declared by a client field with the arguments passed by the
+/// selecting client field, e.g. selecting `avatar(size: 50)` replaces `$size` with `50`
+/// in the selections of `avatar`. Variables that are not passed are left as-is, and
+/// refer to the variables of the selecting client field.
+fn replace_variables_with_arguments(
+    selection_set: &[WithSpan<ValidatedSelection>],
+    arguments: &[WithLocation<SelectionFieldArgument>],
+) -> Vec<WithSpan<ValidatedSelection>> {
+    selection_set
+        .iter()
+        .map(|selection| {
+            selection.clone().map(|selection| match selection {
+                Selection::ServerField(ServerFieldSelection::ScalarField(mut scalar_field)) => {
+                    replace_variables_in_arguments(&mut scalar_field.arguments, arguments);
+                    for directive in scalar_field.directives.iter_mut() {
+                        replace_variables_in_arguments(&mut directive.item.arguments, arguments);
+                    }
+                    Selection::ServerField(ServerFieldSelection::ScalarField(scalar_field))
+                }
+                Selection::ServerField(ServerFieldSelection::LinkedField(mut linked_field)) => {
+                    replace_variables_in_arguments(&mut linked_field.arguments, arguments);
+                    for directive in linked_field.directives.iter_mut() {
+                        replace_variables_in_arguments(&mut directive.item.arguments, arguments);
+                    }
+                    linked_field.selection_set =
+                        replace_variables_with_arguments(&linked_field.selection_set, arguments);
+                    Selection::ServerField(ServerFieldSelection::LinkedField(linked_field))
+                }
+            })
+        })
+        .collect()
+}
+
+fn replace_variables_in_arguments(
+    field_arguments: &mut [WithLocation<SelectionFieldArgument>],
+    arguments: &[WithLocation<SelectionFieldArgument>],
+) {
+    for field_argument in field_arguments.iter_mut() {
+        if let NonConstantValue::Variable(variable_name) = field_argument.item.value.item {
+            if let Some(argument) = arguments
+                .iter()
+                .find(|argument| argument.item.name.item.lookup() == variable_name.lookup())
+            {
+                field_argument.item.value = argument.item.value.clone();
+            }
+        }
+    }
+}
+
 fn merge_scalar_server_field(
     scalar_field: &ScalarFieldSelection<ValidatedFieldDefinitionLocation>,
     merged_selection_set: &mut MergedSelectionMap,
     span: Span,
+    merge_traversal_state: &mut MergeTraversalState<'_>,
 ) {
-    let normalization_key = NormalizationKey::ServerField(name_and_arguments(
-        scalar_field.name.item.into(),
-        &scalar_field.arguments,
-    ));
+    let name_and_arguments =
+        name_and_arguments(scalar_field.name.item.into(), &scalar_field.arguments);
+    let normalization_key = NormalizationKey::ServerField(name_and_arguments.clone());
+    merge_traversal_state.record_origin(&normalization_key);
     match merged_selection_set.entry(normalization_key) {
-        Entry::Occupied(occupied) => {
-            match occupied.get().item {
-                MergedServerFieldSelection::ScalarField(_) => {
+        Entry::Occupied(mut occupied) => {
+            match &mut occupied.get_mut().item {
+                MergedServerFieldSelection::ScalarField(existing_scalar_field) => {
                     // TODO check that the existing server field matches the one we
                     // would create.
+
+                    // A field is only streamed if every selection of it asks for that.
+                    if active_directive(&scalar_field.directives, "stream").is_none() {
+                        existing_scalar_field.stream = None;
+                    }
                 }
-                MergedServerFieldSelection::LinkedField(_) => {
+                MergedServerFieldSelection::LinkedField(_)
+                | MergedServerFieldSelection::InlineFragment(_)
+                | MergedServerFieldSelection::DeferredFragment(_) => {
                     panic!("Unexpected linked field, probably a bug in Isograph")
                 }
             };
         }
         Entry::Vacant(vacant_entry) => {
+            let mut path = merge_traversal_state.current_path.clone();
+            path.linked_fields.push(name_and_arguments);
             vacant_entry.insert(WithSpan::new(
                 MergedServerFieldSelection::ScalarField(MergedScalarFieldSelection {
                     name: scalar_field.name,
                     arguments: scalar_field.arguments.clone(),
                     normalization_alias: scalar_field.normalization_alias,
+                    stream: merged_stream_directive(&scalar_field.directives, &path),
                 }),
                 span,
             ));
@@ -708,6 +1394,22 @@ fn HACK__merge_linked_fields(
                     ),
                 )
             }
+            MergedServerFieldSelection::InlineFragment(inline_fragment) => merged_selection_set
+                .insert(
+                    NormalizationKey::InlineFragment(inline_fragment.type_to_refine_to),
+                    WithSpan::new(
+                        MergedServerFieldSelection::InlineFragment(inline_fragment.clone()),
+                        span,
+                    ),
+                ),
+            MergedServerFieldSelection::DeferredFragment(deferred_fragment) => merged_selection_set
+                .insert(
+                    NormalizationKey::DeferredFragment(deferred_fragment.label.clone()),
+                    WithSpan::new(
+                        MergedServerFieldSelection::DeferredFragment(deferred_fragment.clone()),
+                        span,
+                    ),
+                ),
         };
     }
 
@@ -748,7 +1450,9 @@ fn select_typename_and_id_fields_in_merged_selection(
                         // TODO check that the existing server field matches the one we
                         // would create.
                     }
-                    MergedServerFieldSelection::LinkedField(_) => {
+                    MergedServerFieldSelection::LinkedField(_)
+                    | MergedServerFieldSelection::InlineFragment(_)
+                    | MergedServerFieldSelection::DeferredFragment(_) => {
                         panic!("Unexpected linked field for id, probably a bug in Isograph")
                     }
                 };
@@ -764,6 +1468,7 @@ fn select_typename_and_id_fields_in_merged_selection(
                         arguments: vec![],
                         // This indicates that there should be a separate MergedServerFieldSelection variant
                         normalization_alias: None,
+                        stream: None,
                     }),
                     Span::todo_generated(),
                 ));
@@ -771,3 +1476,25 @@ fn select_typename_and_id_fields_in_merged_selection(
         }
     }
 }
+
+fn select_typename_in_merged_selection(merged_selection_map: &mut MergedSelectionMap) {
+    let typename_name: SelectableFieldName = "__typename".intern().into();
+    // N.B. this uses the same key as a __typename field selected by the user, so that
+    // it is not selected twice.
+    merged_selection_map
+        .entry(NormalizationKey::ServerField(NameAndArguments {
+            name: typename_name,
+            arguments: vec![],
+        }))
+        .or_insert_with(|| {
+            WithSpan::new(
+                MergedServerFieldSelection::ScalarField(MergedScalarFieldSelection {
+                    name: WithLocation::new("__typename".intern().into(), Location::generated()),
+                    arguments: vec![],
+                    normalization_alias: None,
+                    stream: None,
+                }),
+                Span::todo_generated(),
+            )
+        });
+}
@@ -0,0 +1,173 @@
+use common_lang_types::{
+    FieldArgumentName, HasName, IsographObjectTypeName, SelectableFieldName, UnvalidatedTypeName,
+    WithSpan,
+};
+use graphql_lang_types::TypeAnnotation;
+use intern::Lookup;
+use isograph_lang_types::{NonConstantValue, ObjectId, SelectableFieldId};
+
+use crate::{MergedServerFieldSelection, ValidatedSchema};
+
+/// A server field that is required, directly or transitively (i.e. via the client
+/// fields that are selected), by a client field.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DataRequirement {
+    pub parent_type_name: IsographObjectTypeName,
+    pub field_name: SelectableFieldName,
+    /// The type of the field, as it is written in the schema, e.g. `[User!]!`
+    pub field_type: String,
+    pub arguments: Vec<DataRequirementArgument>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DataRequirementArgument {
+    pub name: FieldArgumentName,
+    /// The type of the argument, as it is written in the schema. None if the
+    /// argument is not defined in the schema.
+    pub argument_type: Option<String>,
+    pub value: NonConstantValue,
+}
+
+/// Collects the server fields that are selected in a merged selection set, sorted
+/// by type and field name. A field that is selected several times with the same
+/// arguments is only included once. Fields that are not defined in the schema
+/// (e.g. __typename) are not included.
+pub fn data_requirements(
+    schema: &ValidatedSchema,
+    parent_object_id: ObjectId,
+    merged_selection_set: &[WithSpan<MergedServerFieldSelection>],
+) -> Vec<DataRequirement> {
+    let mut data_requirements = vec![];
+    collect_data_requirements(
+        schema,
+        parent_object_id,
+        merged_selection_set,
+        &mut data_requirements,
+    );
+
+    data_requirements.sort_by(|requirement_1, requirement_2| {
+        sort_key(requirement_1).cmp(&sort_key(requirement_2))
+    });
+    data_requirements.dedup();
+    data_requirements
+}
+
+fn collect_data_requirements(
+    schema: &ValidatedSchema,
+    parent_object_id: ObjectId,
+    selections: &[WithSpan<MergedServerFieldSelection>],
+    data_requirements: &mut Vec<DataRequirement>,
+) {
+    for selection in selections {
+        match &selection.item {
+            MergedServerFieldSelection::ScalarField(scalar_field) => {
+                data_requirements.extend(data_requirement(
+                    schema,
+                    parent_object_id,
+                    scalar_field.name.item.into(),
+                    scalar_field
+                        .arguments
+                        .iter()
+                        .map(|argument| (argument.item.name.item, &argument.item.value.item)),
+                ));
+            }
+            MergedServerFieldSelection::LinkedField(linked_field) => {
+                data_requirements.extend(data_requirement(
+                    schema,
+                    parent_object_id,
+                    linked_field.name.item.into(),
+                    linked_field
+                        .arguments
+                        .iter()
+                        .map(|argument| (argument.item.name.item, &argument.item.value.item)),
+                ));
+                if let Some(object_id) = object_id(schema, linked_field.concrete_type.into()) {
+                    collect_data_requirements(
+                        schema,
+                        object_id,
+                        &linked_field.selection_set,
+                        data_requirements,
+                    );
+                }
+            }
+            MergedServerFieldSelection::InlineFragment(inline_fragment) => {
+                let refined_object_id = object_id(schema, inline_fragment.type_to_refine_to.into())
+                    .unwrap_or(parent_object_id);
+                collect_data_requirements(
+                    schema,
+                    refined_object_id,
+                    &inline_fragment.selection_set,
+                    data_requirements,
+                );
+            }
+            MergedServerFieldSelection::DeferredFragment(deferred_fragment) => {
+                collect_data_requirements(
+                    schema,
+                    parent_object_id,
+                    &deferred_fragment.selection_set,
+                    data_requirements,
+                );
+            }
+        }
+    }
+}
+
+fn data_requirement<'a>(
+    schema: &ValidatedSchema,
+    parent_object_id: ObjectId,
+    field_name: SelectableFieldName,
+    arguments: impl Iterator<Item = (FieldArgumentName, &'a NonConstantValue)>,
+) -> Option<DataRequirement> {
+    let parent_object = schema.schema_data.object(parent_object_id);
+    let server_field_id = parent_object
+        .encountered_fields
+        .get(&field_name)?
+        .as_server_field()?;
+    let server_field = schema.field(*server_field_id);
+
+    let field_type: TypeAnnotation<UnvalidatedTypeName> =
+        server_field
+            .associated_data
+            .clone()
+            .map(|selectable_field_id| {
+                schema
+                    .schema_data
+                    .lookup_unvalidated_type(selectable_field_id)
+                    .name()
+            });
+    let arguments = arguments
+        .map(|(name, value)| DataRequirementArgument {
+            name,
+            argument_type: server_field
+                .arguments
+                .iter()
+                .find(|argument_definition| {
+                    argument_definition.item.name.item.lookup() == name.lookup()
+                })
+                .map(|argument_definition| argument_definition.item.type_.to_string()),
+            value: value.clone(),
+        })
+        .collect();
+
+    Some(DataRequirement {
+        parent_type_name: parent_object.name,
+        field_name,
+        field_type: field_type.to_string(),
+        arguments,
+    })
+}
+
+fn sort_key(data_requirement: &DataRequirement) -> (&'static str, &'static str, String) {
+    (
+        data_requirement.parent_type_name.lookup(),
+        data_requirement.field_name.lookup(),
+        format!("{:?}", data_requirement.arguments),
+    )
+}
+
+fn object_id(schema: &ValidatedSchema, type_name: UnvalidatedTypeName) -> Option<ObjectId> {
+    match schema.schema_data.defined_types.get(&type_name) {
+        Some(SelectableFieldId::Object(object_id)) => Some(*object_id),
+        _ => None,
+    }
+}
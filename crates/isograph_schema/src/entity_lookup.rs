@@ -0,0 +1,122 @@
+use common_lang_types::{Location, SelectableFieldName, SourceFileName, WithSpan};
+use isograph_lang_types::{
+    ClientFieldId, ObjectId, SelectableFieldId, Selection, ServerFieldId, ServerFieldSelection,
+};
+
+use crate::{ValidatedFieldDefinitionLocation, ValidatedSchema, ValidatedSelection};
+
+/// An entity of a validated schema that is found at a position in a source file,
+/// e.g. for hovers and go to definition in editors.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Entity {
+    /// The name of a client field, where it is declared (i.e. in an iso literal, or
+    /// in a @resolver directive in a schema extension).
+    ClientFieldDeclaration(ClientFieldId),
+    /// The name of a selection in the selection set of a client field.
+    /// parent_object_id is the type on which the field is selected.
+    FieldSelection {
+        parent_object_id: ObjectId,
+        field: ValidatedFieldDefinitionLocation,
+    },
+    /// The name of a server field, where it is defined in the schema.
+    ServerFieldDefinition(ServerFieldId),
+    /// The name of a type, where it is defined in the schema.
+    SchemaType(SelectableFieldId),
+}
+
+impl ValidatedSchema {
+    /// Finds the entity whose name is at offset (relative to the start of the file,
+    /// not to an iso literal) in the file at path. path must be the path with which
+    /// the compiler read the file, i.e. an absolute path.
+    ///
+    /// Client fields and selections are found in iso literals, and types and server
+    /// fields are found in the schema and schema extensions. Entrypoint declarations
+    /// are not found.
+    pub fn lookup(&self, path: SourceFileName, offset: u32) -> Option<Entity> {
+        let contains_offset = |location: Location| match location {
+            Location::Embedded(embedded_location) => {
+                let span = embedded_location.span_in_file();
+                embedded_location.text_source.path == path
+                    && span.start <= offset
+                    && offset <= span.end
+            }
+            Location::Generated => false,
+        };
+
+        for client_field in self.client_fields.iter() {
+            if contains_offset(client_field.name_location) {
+                return Some(Entity::ClientFieldDeclaration(client_field.id));
+            }
+            if let Some((selection_set, _)) = &client_field.selection_set_and_unwraps {
+                if let Some(entity) = self.lookup_in_selection_set(
+                    client_field.parent_object_id,
+                    selection_set,
+                    &contains_offset,
+                ) {
+                    return Some(entity);
+                }
+            }
+        }
+
+        for server_field in self.server_fields.iter() {
+            if contains_offset(server_field.name.location) {
+                return Some(Entity::ServerFieldDefinition(server_field.id));
+            }
+        }
+
+        for object in self.schema_data.objects.iter() {
+            if contains_offset(object.name_location) {
+                return Some(Entity::SchemaType(SelectableFieldId::Object(object.id)));
+            }
+        }
+        for scalar in self.schema_data.scalars.iter() {
+            if contains_offset(scalar.name.location) {
+                return Some(Entity::SchemaType(SelectableFieldId::Scalar(scalar.id)));
+            }
+        }
+
+        None
+    }
+
+    fn lookup_in_selection_set(
+        &self,
+        parent_object_id: ObjectId,
+        selection_set: &[WithSpan<ValidatedSelection>],
+        contains_offset: &impl Fn(Location) -> bool,
+    ) -> Option<Entity> {
+        for selection in selection_set {
+            match &selection.item {
+                Selection::ServerField(ServerFieldSelection::ScalarField(scalar_field)) => {
+                    if contains_offset(scalar_field.name.location) {
+                        return Some(Entity::FieldSelection {
+                            parent_object_id,
+                            field: scalar_field.associated_data,
+                        });
+                    }
+                }
+                Selection::ServerField(ServerFieldSelection::LinkedField(linked_field)) => {
+                    if contains_offset(linked_field.name.location) {
+                        let field_name: SelectableFieldName = linked_field.name.item.into();
+                        let field = *self
+                            .schema_data
+                            .object(parent_object_id)
+                            .encountered_fields
+                            .get(&field_name)?;
+                        return Some(Entity::FieldSelection {
+                            parent_object_id,
+                            field,
+                        });
+                    }
+                    if let Some(entity) = self.lookup_in_selection_set(
+                        linked_field.associated_data.parent_object_id,
+                        &linked_field.selection_set,
+                        contains_offset,
+                    ) {
+                        return Some(entity);
+                    }
+                }
+            }
+        }
+        None
+    }
+}
@@ -15,10 +15,11 @@ use isograph_lang_types::{
 use serde::Deserialize;
 
 use crate::{
-    ArgumentMap, ClientField, ClientFieldActionKind, ClientFieldVariant, FieldDefinitionLocation,
-    FieldMapItem, MutationFieldClientFieldVariant, MutationFieldResolverActionKindInfo,
-    ObjectTypeAndFieldNames, ProcessTypeDefinitionError, ProcessTypeDefinitionResult,
-    ProcessedFieldMapItem, UnvalidatedSchema,
+    process_type_definition::schema_too_large_error, ArgumentMap, ClientField,
+    ClientFieldActionKind, ClientFieldVariant, FieldDefinitionLocation, FieldMapItem,
+    MutationFieldClientFieldVariant, MutationFieldResolverActionKindInfo, ObjectTypeAndFieldNames,
+    ProcessTypeDefinitionError, ProcessTypeDefinitionResult, ProcessedFieldMapItem,
+    UnvalidatedSchema,
 };
 use lazy_static::lazy_static;
 
@@ -119,6 +120,7 @@ impl UnvalidatedSchema {
         let mutation_field = self.field(field_id);
         let mutation_field_payload_type_name = *mutation_field.associated_data.inner();
         let mutation_field_name = mutation_field.name.item;
+        let mutation_field_location = mutation_field.name.location;
         let mutation_field_arguments = mutation_field.arguments.clone();
         let description = mutation_field.description.clone();
         let payload_id = self
@@ -196,6 +198,8 @@ impl UnvalidatedSchema {
                         unwraps: vec![],
                         // TODO what about arguments? How would we handle them?
                         arguments: vec![],
+                        directives: vec![],
+                        description: None,
                     };
 
                     WithSpan::new(
@@ -207,11 +211,15 @@ impl UnvalidatedSchema {
                 })
                 .collect::<Vec<_>>();
 
-            let mutation_field_client_field_id = self.client_fields.len().into();
+            let mutation_field_client_field_id =
+                ClientFieldId::try_from_usize(self.client_fields.len()).ok_or_else(|| {
+                    schema_too_large_error("client fields", mutation_field_location)
+                })?;
             let mutation_client_field = ClientField {
                 description,
                 // set_pet_best_friend
                 name: mutation_field_name,
+                name_location: Location::generated(),
                 id: mutation_field_client_field_id,
                 selection_set_and_unwraps: Some((fields.to_vec(), vec![])),
                 variant: ClientFieldVariant::MutationField(MutationFieldClientFieldVariant {
@@ -226,6 +234,7 @@ impl UnvalidatedSchema {
                     // TODO make this zero cost?
                     type_name: maybe_abstract_parent_type_name.lookup().intern().into(), // e.g. Pet
                     field_name: mutation_field_name, // set_pet_best_friend
+                    artifact_namespace: None,
                 },
                 parent_object_id: maybe_abstract_parent_object_id,
                 action_kind: ClientFieldActionKind::MutationField(
@@ -234,6 +243,10 @@ impl UnvalidatedSchema {
                         field_map: field_map.to_vec(),
                     },
                 ),
+                skip_codegen: false,
+                no_aliases: false,
+                fetchable_variant_of: None,
+                merged_selection_set: None,
             };
             self.client_fields.push(mutation_client_field);
 
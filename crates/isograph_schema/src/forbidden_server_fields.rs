@@ -0,0 +1,145 @@
+use common_lang_types::{
+    IsographObjectTypeName, Location, SelectableFieldName, UnvalidatedTypeName, WithLocation,
+    WithSpan,
+};
+use intern::string_key::Intern;
+use isograph_lang_types::{ObjectId, SelectableFieldId, Selection, ServerFieldSelection};
+use thiserror::Error;
+
+use crate::{FieldDefinitionLocation, ValidatedSchema, ValidatedSelection};
+
+/// Checks that no client field selects a server field that the config forbids, e.g.
+/// `User.ssn`. Violations are located at the selection. Generated client fields are
+/// not checked, as their selections are not chosen by the user.
+///
+/// A forbidden field is only detected when it is selected on the type named in the
+/// config, and not when it is selected on an interface that the type implements.
+pub fn check_forbidden_server_fields(
+    schema: &ValidatedSchema,
+    forbidden_server_fields: &[String],
+) -> Result<(), Vec<WithLocation<ForbiddenServerFieldError>>> {
+    let mut errors = vec![];
+
+    let mut forbidden_fields = vec![];
+    for forbidden_server_field in forbidden_server_fields {
+        match find_server_field(schema, forbidden_server_field) {
+            Some(forbidden_field) => forbidden_fields.push(forbidden_field),
+            None => errors.push(WithLocation::new(
+                ForbiddenServerFieldError::UnknownForbiddenServerField {
+                    name: forbidden_server_field.clone(),
+                },
+                Location::generated(),
+            )),
+        }
+    }
+
+    if !forbidden_fields.is_empty() {
+        for client_field in schema.client_fields.iter() {
+            if let Location::Generated = client_field.name_location {
+                continue;
+            }
+            if let Some((selection_set, _)) = &client_field.selection_set_and_unwraps {
+                check_selection_set(
+                    schema,
+                    client_field.parent_object_id,
+                    selection_set,
+                    &forbidden_fields,
+                    &mut errors,
+                );
+            }
+        }
+    }
+
+    if errors.is_empty() {
+        Ok(())
+    } else {
+        Err(errors)
+    }
+}
+
+/// Finds the object and field named by e.g. `User.ssn`, if the schema defines a
+/// server field with that name.
+fn find_server_field(
+    schema: &ValidatedSchema,
+    forbidden_server_field: &str,
+) -> Option<(ObjectId, SelectableFieldName)> {
+    let (type_name, field_name) = forbidden_server_field.split_once('.')?;
+    let type_name: UnvalidatedTypeName = type_name.intern().into();
+    let object_id = match schema.schema_data.defined_types.get(&type_name)? {
+        SelectableFieldId::Object(object_id) => *object_id,
+        _ => return None,
+    };
+    let field_name: SelectableFieldName = field_name.intern().into();
+    schema
+        .schema_data
+        .object(object_id)
+        .encountered_fields
+        .get(&field_name)?
+        .as_server_field()?;
+    Some((object_id, field_name))
+}
+
+fn check_selection_set(
+    schema: &ValidatedSchema,
+    parent_object_id: ObjectId,
+    selection_set: &[WithSpan<ValidatedSelection>],
+    forbidden_fields: &[(ObjectId, SelectableFieldName)],
+    errors: &mut Vec<WithLocation<ForbiddenServerFieldError>>,
+) {
+    let parent_type_name = schema.schema_data.object(parent_object_id).name;
+    for selection in selection_set {
+        match &selection.item {
+            Selection::ServerField(ServerFieldSelection::ScalarField(scalar_field)) => {
+                if let FieldDefinitionLocation::Server(_) = scalar_field.associated_data {
+                    let field_name = scalar_field.name.item.into();
+                    if forbidden_fields.contains(&(parent_object_id, field_name)) {
+                        errors.push(WithLocation::new(
+                            ForbiddenServerFieldError::ForbiddenServerFieldSelected {
+                                type_name: parent_type_name,
+                                field_name,
+                            },
+                            scalar_field.name.location,
+                        ));
+                    }
+                }
+            }
+            Selection::ServerField(ServerFieldSelection::LinkedField(linked_field)) => {
+                let field_name = linked_field.name.item.into();
+                if forbidden_fields.contains(&(parent_object_id, field_name)) {
+                    errors.push(WithLocation::new(
+                        ForbiddenServerFieldError::ForbiddenServerFieldSelected {
+                            type_name: parent_type_name,
+                            field_name,
+                        },
+                        linked_field.name.location,
+                    ));
+                }
+                check_selection_set(
+                    schema,
+                    linked_field.associated_data.parent_object_id,
+                    &linked_field.selection_set,
+                    forbidden_fields,
+                    errors,
+                );
+            }
+        }
+    }
+}
+
+#[derive(Debug, Error)]
+pub enum ForbiddenServerFieldError {
+    #[error(
+        "The server field `{type_name}.{field_name}` is forbidden by the \
+        forbidden_server_fields option in the config, and cannot be selected."
+    )]
+    ForbiddenServerFieldSelected {
+        type_name: IsographObjectTypeName,
+        field_name: SelectableFieldName,
+    },
+
+    #[error(
+        "The config forbids selecting `{name}`, but the schema defines no such server \
+        field. Server fields are named like `User.ssn`."
+    )]
+    UnknownForbiddenServerField { name: String },
+}
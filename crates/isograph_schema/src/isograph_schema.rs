@@ -1,9 +1,13 @@
-use std::{collections::HashMap, fmt::Debug};
+use std::{
+    collections::HashMap,
+    fmt::{self, Debug},
+};
 
 use common_lang_types::{
-    ConstExportName, DescriptionValue, FieldArgumentName, FilePath, GraphQLInterfaceTypeName,
-    GraphQLScalarTypeName, HasName, InputTypeName, IsographObjectTypeName, JavascriptName,
-    SelectableFieldName, UnvalidatedTypeName, WithLocation, WithSpan,
+    ArtifactNamespace, ConstExportName, DescriptionValue, EnumLiteralValue, FieldArgumentName,
+    FilePath, GraphQLInterfaceTypeName, GraphQLScalarTypeName, HasName, InputTypeName,
+    IsographObjectTypeName, JavascriptName, Location, SelectableFieldName, UnvalidatedTypeName,
+    WithLocation, WithSpan,
 };
 use graphql_lang_types::{
     ConstantValue, GraphQLDirective, GraphQLFieldDefinition, GraphQLInputObjectTypeDefinition,
@@ -11,13 +15,14 @@ use graphql_lang_types::{
     NamedTypeAnnotation, TypeAnnotation,
 };
 use intern::string_key::Intern;
+use isograph_config::CustomScalarLiterals;
 use isograph_lang_types::{
     ClientFieldId, LinkedFieldSelection, NonConstantValue, ObjectId, ScalarId, SelectableFieldId,
     Selection, ServerFieldId, ServerStrongIdFieldId, Unwrap, VariableDefinition,
 };
 use lazy_static::lazy_static;
 
-use crate::{ClientFieldVariant, FieldMapItem};
+use crate::{ClientFieldMergedSelectionSet, ClientFieldVariant, FieldMapItem};
 
 lazy_static! {
     pub static ref ID_GRAPHQL_TYPE: GraphQLScalarTypeName = "ID".intern().into();
@@ -98,8 +103,37 @@ pub struct Schema<TValidation: SchemaValidationState> {
     // typename
     // TODO name this root query type?
     pub query_type_id: Option<ObjectId>,
-    // Subscription
-    // Mutation
+    pub mutation_type_id: Option<ObjectId>,
+    pub subscription_type_id: Option<ObjectId>,
+}
+
+/// The kind of operation that the query text of an artifact contains.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OperationKind {
+    Query,
+    Mutation,
+    Subscription,
+}
+
+impl OperationKind {
+    /// The name of the operation kind, as used in the GraphQL syntax.
+    pub fn keyword(&self) -> &'static str {
+        match self {
+            OperationKind::Query => "query",
+            OperationKind::Mutation => "mutation",
+            OperationKind::Subscription => "subscription",
+        }
+    }
+}
+
+impl fmt::Display for OperationKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            OperationKind::Query => write!(f, "Query"),
+            OperationKind::Mutation => write!(f, "Mutation"),
+            OperationKind::Subscription => write!(f, "Subscription"),
+        }
+    }
 }
 
 /// Distinguishes between server-defined fields and locally-defined fields.
@@ -112,7 +146,7 @@ pub struct Schema<TValidation: SchemaValidationState> {
 /// Note that locally-defined fields do **not** only include fields defined in
 /// an iso field literal. Refetch fields and generated mutation fields are
 /// also local fields.
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum FieldDefinitionLocation<TServer, TClient> {
     Server(TServer),
     Client(TClient),
@@ -164,6 +198,26 @@ impl<TValidation: SchemaValidationState> Schema<TValidation> {
         &self.client_fields[client_field_id.as_usize()]
     }
 
+    /// Whether the schema defines a mutation or subscription root type, i.e. whether
+    /// operations other than queries can be generated.
+    pub fn has_non_query_root_types(&self) -> bool {
+        self.mutation_type_id.is_some() || self.subscription_type_id.is_some()
+    }
+
+    /// The kind of operation that fetches the given object, if it is a root type.
+    /// Only client fields on root types can be entrypoints.
+    pub fn root_operation_kind(&self, object_id: ObjectId) -> Option<OperationKind> {
+        if self.query_type_id == Some(object_id) {
+            Some(OperationKind::Query)
+        } else if self.mutation_type_id == Some(object_id) {
+            Some(OperationKind::Mutation)
+        } else if self.subscription_type_id == Some(object_id) {
+            Some(OperationKind::Subscription)
+        } else {
+            None
+        }
+    }
+
     /// Get a reference to the root query_object, if it's defined.
     pub fn query_object(&self) -> Option<&SchemaObject<TValidation::EncounteredField>> {
         self.query_type_id
@@ -215,6 +269,11 @@ impl<TEncounteredField> SchemaData<TEncounteredField> {
         &self.scalars[scalar_id.as_usize()]
     }
 
+    /// Get a mutable reference to a given scalar type by its id.
+    pub fn scalar_mut(&mut self, scalar_id: ScalarId) -> &mut SchemaScalar {
+        &mut self.scalars[scalar_id.as_usize()]
+    }
+
     pub fn lookup_unvalidated_type(
         &self,
         type_id: SelectableFieldId,
@@ -342,6 +401,8 @@ impl From<GraphQLInputObjectTypeDefinition> for IsographObjectTypeDefinition {
 pub struct SchemaObject<TEncounteredField> {
     pub description: Option<DescriptionValue>,
     pub name: IsographObjectTypeName,
+    /// Where the type is defined in the schema.
+    pub name_location: Location,
     pub id: ObjectId,
     // We probably don't want this
     pub directives: Vec<GraphQLDirective<ConstantValue>>,
@@ -351,11 +412,19 @@ pub struct SchemaObject<TEncounteredField> {
     pub server_fields: Vec<ServerFieldId>,
     pub resolvers: Vec<ClientFieldId>,
     pub encountered_fields: HashMap<SelectableFieldName, TEncounteredField>,
+    /// If this is an interface or union, the concrete types that implement it (or
+    /// are members of it). Client fields declared on an abstract type are also
+    /// available on each of these types.
+    pub valid_refinements: Vec<ValidRefinement>,
+    /// Whether this is an input object. Input objects are represented as objects,
+    /// but they can only be used as the types of variables and arguments, never
+    /// selected.
+    pub is_input_object: bool,
 }
 
 /// In GraphQL, ValidRefinement's are essentially the concrete types that an interface or
 /// union can be narrowed to. valid_refinements should be empty for concrete types.
-#[derive(Debug)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub struct ValidRefinement {
     pub target: ObjectId,
     // pub is_guaranteed_to_work: bool,
@@ -373,6 +442,15 @@ pub struct SchemaServerField<TData> {
     pub parent_type_id: ObjectId,
     // pub directives: Vec<Directive<ConstantValue>>,
     pub arguments: Vec<WithLocation<GraphQLInputValueDefinition>>,
+    /// If this is a generated refinement field (e.g. Node.asUser), the concrete
+    /// type that the parent is narrowed to. None for fields defined in the schema.
+    pub refinement: Option<ValidRefinement>,
+    /// Whether the field is annotated with @internal in the schema. Internal fields
+    /// remain in the schema (e.g. for other tooling), but cannot be selected in
+    /// iso literals, and thus never appear in generated artifacts.
+    pub is_internal: bool,
+    /// If the field is annotated with @deprecated in the schema, the reason
+    pub deprecation_reason: Option<DescriptionValue>,
 }
 
 impl<TData> SchemaServerField<TData> {
@@ -387,6 +465,9 @@ impl<TData> SchemaServerField<TData> {
             associated_data: convert(&self.associated_data)?,
             parent_type_id: self.parent_type_id,
             arguments: self.arguments.clone(),
+            refinement: self.refinement,
+            is_internal: self.is_internal,
+            deprecation_reason: self.deprecation_reason,
         })
     }
 }
@@ -432,6 +513,9 @@ impl<TData: Copy> TryFrom<SchemaServerField<TData>> for SchemaIdField<TData> {
 pub struct ObjectTypeAndFieldNames {
     pub type_name: IsographObjectTypeName,
     pub field_name: SelectableFieldName,
+    /// The package in which the client field is declared, if it is declared in
+    /// one of the packages in the config. Its artifacts are namespaced by it.
+    pub artifact_namespace: Option<ArtifactNamespace>,
 }
 
 impl ObjectTypeAndFieldNames {
@@ -439,23 +523,40 @@ impl ObjectTypeAndFieldNames {
         format!("{}__{}", self.type_name, self.field_name)
     }
 
-    pub fn relative_path(&self, current_file_type_name: IsographObjectTypeName) -> String {
-        let ObjectTypeAndFieldNames {
-            type_name,
-            field_name,
-        } = *self;
-        if type_name != current_file_type_name {
-            format!("../../{type_name}/{field_name}/{}", *READER)
+    pub fn relative_path(&self, current_file_type_directory: &str) -> String {
+        let type_directory = artifact_type_directory(self.type_name, self.artifact_namespace);
+        let field_name = self.field_name;
+        if type_directory != current_file_type_directory {
+            format!("../../{type_directory}/{field_name}/{}", *READER)
         } else {
             format!("../{field_name}/{}", *READER)
         }
     }
 }
 
+/// The directory (within the artifact directory) that contains the artifacts of the
+/// client fields of a type, e.g. `User`, or `design_system.User` for the client fields
+/// declared in the design_system package. Namespaced directories are siblings of the
+/// other type directories, so that every artifact remains two levels deep in the
+/// artifact directory.
+pub fn artifact_type_directory(
+    type_name: IsographObjectTypeName,
+    artifact_namespace: Option<ArtifactNamespace>,
+) -> String {
+    match artifact_namespace {
+        Some(artifact_namespace) => format!("{artifact_namespace}.{type_name}"),
+        None => type_name.to_string(),
+    }
+}
+
 #[derive(Debug, Clone)]
 pub enum ClientFieldActionKind {
     /// Associated js function
     NamedImport((ConstExportName, FilePath)),
+    /// No associated js function, so the client field evaluates to the data
+    /// it reads. Eager client fields of this kind are inlined into the reader
+    /// ASTs of the client fields that select them.
+    Identity,
     /// Refetch fields
     RefetchField,
     /// Mutation field
@@ -476,6 +577,9 @@ pub struct ClientField<
     pub description: Option<DescriptionValue>,
     // TODO make this a ResolverName that can be converted into a SelectableFieldName
     pub name: SelectableFieldName,
+    /// Where the client field's name is declared, i.e. in an iso literal, or in a
+    /// @resolver directive in the schema. Generated for generated client fields.
+    pub name_location: Location,
     pub id: ClientFieldId,
     // TODO it makes no sense for a resolver to not select fields!
     // Why not just make it a global function at that point? Who knows.
@@ -508,6 +612,26 @@ pub struct ClientField<
 
     // TODO should this be TypeWithFieldsId???
     pub parent_object_id: ObjectId,
+
+    /// Whether this client field is annotated with @skipCodegen, in which case
+    /// it is validated, but no artifacts are generated for it.
+    pub skip_codegen: bool,
+
+    /// Whether this client field is annotated with @noAliases, in which case the
+    /// operations generated for it (if it is an entrypoint) contain no aliases,
+    /// regardless of the normalization_alias_strategy option.
+    pub no_aliases: bool,
+
+    /// If this client field is a variant declared with @fetchable(name: "..."), the
+    /// name of the client field that it is a variant of, i.e. the name written in the
+    /// iso literal. The client field itself is named after the variant, so that its
+    /// operation, artifacts and exports are distinct from those of that client field.
+    pub fetchable_variant_of: Option<SelectableFieldName>,
+
+    /// The merged selection set of this client field, which is created when
+    /// validating the schema. None for unvalidated client fields and for client
+    /// fields without a selection set.
+    pub merged_selection_set: Option<ClientFieldMergedSelectionSet>,
 }
 
 #[derive(Default, Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
@@ -552,6 +676,9 @@ impl<T> SchemaServerField<T> {
             associated_data,
             parent_type_id,
             arguments,
+            refinement,
+            is_internal,
+            deprecation_reason,
         } = self;
         (
             SchemaServerField {
@@ -561,6 +688,9 @@ impl<T> SchemaServerField<T> {
                 associated_data: (),
                 parent_type_id,
                 arguments,
+                refinement,
+                is_internal,
+                deprecation_reason,
             },
             associated_data,
         )
@@ -574,4 +704,18 @@ pub struct SchemaScalar {
     pub name: WithLocation<GraphQLScalarTypeName>,
     pub id: ScalarId,
     pub javascript_name: JavascriptName,
+    /// If this scalar is a GraphQL enum (which Isograph currently treats as a
+    /// scalar), its values
+    pub enum_values: Option<Vec<SchemaEnumValue>>,
+    /// How literals of this scalar are canonicalized and validated, as configured
+    /// in custom_scalar_types
+    pub literals: CustomScalarLiterals,
+}
+
+/// A value of an enum in the schema.
+#[derive(Debug, Clone, Copy)]
+pub struct SchemaEnumValue {
+    pub value: EnumLiteralValue,
+    /// If the value is annotated with @deprecated in the schema, the reason
+    pub deprecation_reason: Option<DescriptionValue>,
 }
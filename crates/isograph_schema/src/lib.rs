@@ -1,24 +1,40 @@
 mod add_fields_to_subtypes;
 mod argument_map;
 mod create_merged_selection_set;
+mod data_requirements;
+mod entity_lookup;
 mod expose_field_directive;
+mod forbidden_server_fields;
 mod isograph_schema;
+mod lint_rule;
+mod naming_conventions;
+mod operation_complexity;
 mod process_client_field_declaration;
 mod process_type_definition;
 pub(crate) mod refetched_paths;
+mod resolver_directive;
 mod root_types;
 mod unvalidated_schema;
+mod validate_directives;
 mod validate_entrypoint;
 mod validate_schema;
 
 use argument_map::*;
 
 pub use create_merged_selection_set::*;
+pub use data_requirements::*;
+pub use entity_lookup::*;
 pub use expose_field_directive::*;
+pub use forbidden_server_fields::*;
 pub use isograph_schema::*;
+pub use lint_rule::*;
+pub use naming_conventions::*;
+pub use operation_complexity::*;
 pub use process_client_field_declaration::*;
 pub use process_type_definition::*;
+pub use resolver_directive::*;
 use root_types::*;
 pub use unvalidated_schema::*;
+pub use validate_directives::*;
 pub use validate_entrypoint::*;
 pub use validate_schema::*;
@@ -0,0 +1,102 @@
+use std::collections::HashMap;
+
+use common_lang_types::{Location, WithLocation};
+use isograph_config::OptionalValidationLevel;
+use thiserror::Error;
+
+use crate::{ValidatedClientField, ValidatedSchema};
+
+/// A house rule (e.g. "always select id") that is checked against the selections
+/// of every client field, once the schema has been validated.
+///
+/// Lint rules are provided by tools that embed the compiler, and are enabled,
+/// disabled or downgraded to warnings by name in the config file.
+pub trait LintRule: Send + Sync {
+    /// The name by which this rule is referred to in the config file,
+    /// e.g. `always_select_id`
+    fn name(&self) -> &'static str;
+
+    /// Returns a message for every violation of this rule in client_field,
+    /// located at the offending selection.
+    fn check_client_field(
+        &self,
+        schema: &ValidatedSchema,
+        client_field: &ValidatedClientField,
+    ) -> Vec<WithLocation<String>>;
+}
+
+/// Runs every lint rule against every client field. Rules that are not mentioned
+/// in lint_rule_levels are treated as errors.
+pub fn check_lint_rules(
+    schema: &ValidatedSchema,
+    lint_rules: &[Box<dyn LintRule>],
+    lint_rule_levels: &HashMap<String, OptionalValidationLevel>,
+) -> Result<(), Vec<WithLocation<LintRuleError>>> {
+    let mut errors = vec![];
+
+    for configured_rule_name in lint_rule_levels.keys() {
+        if !lint_rules
+            .iter()
+            .any(|lint_rule| lint_rule.name() == configured_rule_name)
+        {
+            errors.push(WithLocation::new(
+                LintRuleError::UnknownLintRule {
+                    rule_name: configured_rule_name.clone(),
+                    known_rule_names: lint_rules
+                        .iter()
+                        .map(|lint_rule| lint_rule.name())
+                        .collect::<Vec<_>>()
+                        .join(", "),
+                },
+                Location::generated(),
+            ));
+        }
+    }
+
+    for lint_rule in lint_rules {
+        let level = lint_rule_levels
+            .get(lint_rule.name())
+            .copied()
+            .unwrap_or(OptionalValidationLevel::Error);
+        for client_field in schema.client_fields.iter() {
+            for violation in lint_rule.check_client_field(schema, client_field) {
+                let result = level.on_failure(|| {
+                    WithLocation::new(
+                        LintRuleError::LintRuleViolated {
+                            rule_name: lint_rule.name(),
+                            message: violation.item,
+                        },
+                        violation.location,
+                    )
+                });
+                if let Err(e) = result {
+                    errors.push(e);
+                }
+            }
+        }
+    }
+
+    if errors.is_empty() {
+        Ok(())
+    } else {
+        Err(errors)
+    }
+}
+
+#[derive(Debug, Error)]
+pub enum LintRuleError {
+    #[error("{message} (lint rule `{rule_name}`)")]
+    LintRuleViolated {
+        rule_name: &'static str,
+        message: String,
+    },
+
+    #[error(
+        "The lint rule `{rule_name}` is configured in the config file, but no such \
+        rule is registered. Registered rules: {known_rule_names}"
+    )]
+    UnknownLintRule {
+        rule_name: String,
+        known_rule_names: String,
+    },
+}
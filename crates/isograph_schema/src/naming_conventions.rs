@@ -0,0 +1,150 @@
+use common_lang_types::{IsographObjectTypeName, Location, SelectableFieldName, WithLocation};
+use intern::Lookup;
+use isograph_config::{ClientFieldNamingConventions, NameCase};
+use thiserror::Error;
+
+use crate::{ClientFieldVariant, ValidatedClientField, ValidatedSchema};
+
+/// Checks the name of every client field against the naming conventions in the
+/// config. Violations are located at the name of the client field. Generated client
+/// fields (e.g. __refetch fields) are not checked, as their names are not chosen by
+/// the user.
+pub fn check_client_field_naming_conventions(
+    schema: &ValidatedSchema,
+    naming_conventions: &ClientFieldNamingConventions,
+) -> Result<(), Vec<WithLocation<NamingConventionError>>> {
+    let mut errors = vec![];
+
+    for client_field in schema.client_fields.iter() {
+        if let Location::Generated = client_field.name_location {
+            continue;
+        }
+        for violation in naming_convention_violations(schema, client_field, naming_conventions) {
+            let result = naming_conventions
+                .on_violation
+                .on_failure(|| WithLocation::new(violation, client_field.name_location));
+            if let Err(e) = result {
+                errors.push(e);
+            }
+        }
+    }
+
+    if errors.is_empty() {
+        Ok(())
+    } else {
+        Err(errors)
+    }
+}
+
+fn naming_convention_violations(
+    schema: &ValidatedSchema,
+    client_field: &ValidatedClientField,
+    naming_conventions: &ClientFieldNamingConventions,
+) -> Vec<NamingConventionError> {
+    let type_name = client_field.type_and_field.type_name;
+    let field_name = client_field.name;
+    let mut violations = vec![];
+
+    let expected_case = match client_field.variant {
+        ClientFieldVariant::Component => naming_conventions.component_case,
+        _ => naming_conventions.case,
+    };
+    if let Some(expected_case) = expected_case {
+        if !is_in_case(field_name.lookup(), expected_case) {
+            violations.push(NamingConventionError::InvalidCase {
+                type_name,
+                field_name,
+                expected_case: case_name(expected_case),
+            });
+        }
+    }
+
+    for reserved_prefix in naming_conventions.reserved_prefixes.iter() {
+        if field_name.lookup().starts_with(reserved_prefix.as_str()) {
+            violations.push(NamingConventionError::ReservedPrefix {
+                type_name,
+                field_name,
+                reserved_prefix: reserved_prefix.clone(),
+            });
+        }
+    }
+
+    if naming_conventions.forbid_case_insensitive_collisions {
+        let parent_object = schema.schema_data.object(client_field.parent_object_id);
+        for server_field_id in parent_object.server_fields.iter() {
+            let server_field_name = schema.field(*server_field_id).name.item;
+            if server_field_name != field_name
+                && server_field_name
+                    .lookup()
+                    .eq_ignore_ascii_case(field_name.lookup())
+            {
+                violations.push(NamingConventionError::CaseInsensitiveCollision {
+                    type_name,
+                    field_name,
+                    server_field_name,
+                });
+            }
+        }
+    }
+
+    violations
+}
+
+fn is_in_case(name: &str, case: NameCase) -> bool {
+    let mut chars = name.chars();
+    let first_char = match chars.next() {
+        Some(first_char) => first_char,
+        None => return false,
+    };
+    match case {
+        NameCase::CamelCase => {
+            first_char.is_ascii_lowercase() && chars.all(|char| char.is_ascii_alphanumeric())
+        }
+        NameCase::PascalCase => {
+            first_char.is_ascii_uppercase() && chars.all(|char| char.is_ascii_alphanumeric())
+        }
+        NameCase::SnakeCase => {
+            first_char.is_ascii_lowercase()
+                && chars
+                    .all(|char| char.is_ascii_lowercase() || char.is_ascii_digit() || char == '_')
+        }
+    }
+}
+
+fn case_name(case: NameCase) -> &'static str {
+    match case {
+        NameCase::CamelCase => "camelCase",
+        NameCase::PascalCase => "PascalCase",
+        NameCase::SnakeCase => "snake_case",
+    }
+}
+
+#[derive(Debug, Error)]
+pub enum NamingConventionError {
+    #[error("The name of the client field `{type_name}.{field_name}` is not in {expected_case}.")]
+    InvalidCase {
+        type_name: IsographObjectTypeName,
+        field_name: SelectableFieldName,
+        expected_case: &'static str,
+    },
+
+    #[error(
+        "The name of the client field `{type_name}.{field_name}` starts with \
+        `{reserved_prefix}`, which is reserved."
+    )]
+    ReservedPrefix {
+        type_name: IsographObjectTypeName,
+        field_name: SelectableFieldName,
+        reserved_prefix: String,
+    },
+
+    #[error(
+        "The name of the client field `{type_name}.{field_name}` differs from the \
+        name of the server field `{type_name}.{server_field_name}` only in case."
+    )]
+    CaseInsensitiveCollision {
+        type_name: IsographObjectTypeName,
+        field_name: SelectableFieldName,
+        server_field_name: SelectableFieldName,
+    },
+}
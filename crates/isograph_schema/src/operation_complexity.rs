@@ -0,0 +1,139 @@
+use std::fmt;
+
+use common_lang_types::{SelectableFieldName, UnvalidatedTypeName, WithSpan};
+use isograph_lang_types::{ObjectId, SelectableFieldId};
+
+use crate::{MergedServerFieldSelection, ValidatedSchema};
+
+/// When estimating the size of a response, every list field is assumed to
+/// contain this many items.
+pub const ESTIMATED_LIST_LENGTH: usize = 10;
+
+/// Metrics describing the size of an operation, derived from its merged
+/// selection set.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct OperationComplexity {
+    /// The number of fields selected in the operation
+    pub field_count: usize,
+    /// The maximum number of nested linked fields, where top-level fields have a
+    /// depth of 1
+    pub max_depth: usize,
+    /// The estimated number of fields in the response, assuming that every list
+    /// contains ESTIMATED_LIST_LENGTH items
+    pub estimated_response_size: usize,
+}
+
+impl OperationComplexity {
+    pub fn new(
+        schema: &ValidatedSchema,
+        root_object_id: ObjectId,
+        merged_selection_set: &[WithSpan<MergedServerFieldSelection>],
+    ) -> Self {
+        let mut complexity = OperationComplexity::default();
+        let estimated_response_size =
+            complexity.visit_selections(schema, root_object_id, merged_selection_set, 1);
+        OperationComplexity {
+            estimated_response_size,
+            ..complexity
+        }
+    }
+
+    /// Visits the selections and returns the estimated number of fields in the
+    /// response for a single record of type parent_object_id.
+    fn visit_selections(
+        &mut self,
+        schema: &ValidatedSchema,
+        parent_object_id: ObjectId,
+        selections: &[WithSpan<MergedServerFieldSelection>],
+        depth: usize,
+    ) -> usize {
+        let mut estimated_response_size = 0;
+        for selection in selections {
+            match &selection.item {
+                MergedServerFieldSelection::ScalarField(scalar_field) => {
+                    self.field_count += 1;
+                    self.max_depth = self.max_depth.max(depth);
+                    let list_length =
+                        if is_list_field(schema, parent_object_id, scalar_field.name.item.into()) {
+                            ESTIMATED_LIST_LENGTH
+                        } else {
+                            1
+                        };
+                    estimated_response_size += list_length;
+                }
+                MergedServerFieldSelection::LinkedField(linked_field) => {
+                    self.field_count += 1;
+                    self.max_depth = self.max_depth.max(depth);
+                    let list_length =
+                        if is_list_field(schema, parent_object_id, linked_field.name.item.into()) {
+                            ESTIMATED_LIST_LENGTH
+                        } else {
+                            1
+                        };
+                    let nested_size = match object_id(schema, linked_field.concrete_type.into()) {
+                        Some(object_id) => self.visit_selections(
+                            schema,
+                            object_id,
+                            &linked_field.selection_set,
+                            depth + 1,
+                        ),
+                        None => 0,
+                    };
+                    estimated_response_size += list_length * (1 + nested_size);
+                }
+                MergedServerFieldSelection::InlineFragment(inline_fragment) => {
+                    let refined_object_id =
+                        object_id(schema, inline_fragment.type_to_refine_to.into())
+                            .unwrap_or(parent_object_id);
+                    estimated_response_size += self.visit_selections(
+                        schema,
+                        refined_object_id,
+                        &inline_fragment.selection_set,
+                        depth,
+                    );
+                }
+                MergedServerFieldSelection::DeferredFragment(deferred_fragment) => {
+                    estimated_response_size += self.visit_selections(
+                        schema,
+                        parent_object_id,
+                        &deferred_fragment.selection_set,
+                        depth,
+                    );
+                }
+            }
+        }
+        estimated_response_size
+    }
+}
+
+impl fmt::Display for OperationComplexity {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} fields, max depth {}, estimated response size {}",
+            self.field_count, self.max_depth, self.estimated_response_size
+        )
+    }
+}
+
+fn object_id(schema: &ValidatedSchema, type_name: UnvalidatedTypeName) -> Option<ObjectId> {
+    match schema.schema_data.defined_types.get(&type_name) {
+        Some(SelectableFieldId::Object(object_id)) => Some(*object_id),
+        _ => None,
+    }
+}
+
+fn is_list_field(
+    schema: &ValidatedSchema,
+    parent_object_id: ObjectId,
+    field_name: SelectableFieldName,
+) -> bool {
+    schema
+        .schema_data
+        .object(parent_object_id)
+        .encountered_fields
+        .get(&field_name)
+        .and_then(|field| field.as_server_field())
+        .map(|server_field_id| schema.field(*server_field_id).associated_data.is_list())
+        .unwrap_or(false)
+}
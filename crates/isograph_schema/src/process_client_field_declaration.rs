@@ -1,13 +1,14 @@
 use std::fmt;
 
 use common_lang_types::{
-    IsographDirectiveName, IsographObjectTypeName, Location, SelectableFieldName, TextSource,
-    UnvalidatedTypeName, WithLocation, WithSpan,
+    ArtifactNamespace, IsographDirectiveName, IsographObjectTypeName, Location,
+    SelectableFieldName, TextSource, UnvalidatedTypeName, WithLocation, WithSpan,
 };
 use graphql_lang_types::GraphQLInputValueDefinition;
 use intern::string_key::Intern;
 use isograph_lang_types::{
-    ClientFieldDeclaration, FragmentDirectiveUsage, ObjectId, SelectableFieldId,
+    ClientFieldDeclaration, ClientFieldId, FragmentDirectiveUsage, NonConstantValue, ObjectId,
+    SelectableFieldId,
 };
 use lazy_static::lazy_static;
 use thiserror::Error;
@@ -22,6 +23,7 @@ impl UnvalidatedSchema {
         &mut self,
         client_field_declaration: WithSpan<ClientFieldDeclaration>,
         text_source: TextSource,
+        artifact_namespace: Option<ArtifactNamespace>,
     ) -> Result<(), WithLocation<ProcessClientFieldDeclarationError>> {
         let parent_type_id = self
             .schema_data
@@ -36,8 +38,13 @@ impl UnvalidatedSchema {
 
         match parent_type_id {
             SelectableFieldId::Object(object_id) => {
-                self.add_resolver_field_to_object(*object_id, client_field_declaration)
-                    .map_err(|e| WithLocation::new(e.item, Location::new(text_source, e.span)))?;
+                self.add_resolver_field_to_object(
+                    *object_id,
+                    client_field_declaration,
+                    text_source,
+                    artifact_namespace,
+                )
+                .map_err(|e| WithLocation::new(e.item, Location::new(text_source, e.span)))?;
             }
             SelectableFieldId::Scalar(scalar_id) => {
                 let scalar_name = self.schema_data.scalars[scalar_id.as_usize()].name;
@@ -57,18 +64,36 @@ impl UnvalidatedSchema {
         &mut self,
         parent_object_id: ObjectId,
         client_field_declaration: WithSpan<ClientFieldDeclaration>,
+        text_source: TextSource,
+        artifact_namespace: Option<ArtifactNamespace>,
     ) -> ProcessResolverDeclarationResult<()> {
         let object = &mut self.schema_data.objects[parent_object_id.as_usize()];
         let resolver_field_name_ws = client_field_declaration.item.client_field_name;
-        let resolver_field_name = resolver_field_name_ws.item;
         let resolver_field_name_span = resolver_field_name_ws.span;
 
-        let next_resolver_id = self.client_fields.len().into();
+        // A variant declared with @fetchable(name: "...") is added under the name of
+        // the variant, next to the client field that it is a variant of.
+        let fetchable_variant_name =
+            get_fetchable_variant_name(&client_field_declaration.item.directives)?;
+        let resolver_field_name: SelectableFieldName = match fetchable_variant_name {
+            Some(fetchable_variant_name) => fetchable_variant_name,
+            None => resolver_field_name_ws.item.into(),
+        };
+        let fetchable_variant_of =
+            fetchable_variant_name.map(|_| resolver_field_name_ws.item.into());
+
+        let next_resolver_id =
+            ClientFieldId::try_from_usize(self.client_fields.len()).ok_or_else(|| {
+                WithSpan::new(
+                    ProcessClientFieldDeclarationError::TooManyClientFields,
+                    resolver_field_name_span,
+                )
+            })?;
 
         if object
             .encountered_fields
             .insert(
-                resolver_field_name.into(),
+                resolver_field_name,
                 FieldDefinitionLocation::Client(next_resolver_id),
             )
             .is_some()
@@ -77,7 +102,7 @@ impl UnvalidatedSchema {
             return Err(WithSpan::new(
                 ProcessClientFieldDeclarationError::ParentAlreadyHasField {
                     parent_type_name: object.name.into(),
-                    resolver_field_name: resolver_field_name.into(),
+                    resolver_field_name,
                 },
                 resolver_field_name_span,
             ));
@@ -85,12 +110,25 @@ impl UnvalidatedSchema {
 
         object.resolvers.push(next_resolver_id);
 
-        let name = client_field_declaration.item.client_field_name.item.into();
+        let name = resolver_field_name;
         let variant = get_resolver_variant(&client_field_declaration.item.directives);
-        let action_kind = ClientFieldActionKind::NamedImport((
-            client_field_declaration.item.const_export_name,
-            client_field_declaration.item.definition_path,
-        ));
+        let skip_codegen = client_field_declaration
+            .item
+            .directives
+            .iter()
+            .any(|directive| directive.item.name.item == *SKIP_CODEGEN);
+        let no_aliases = client_field_declaration
+            .item
+            .directives
+            .iter()
+            .any(|directive| directive.item.name.item == *NO_ALIASES);
+        let action_kind = match client_field_declaration.item.const_export_name {
+            Some(const_export_name) => ClientFieldActionKind::NamedImport((
+                const_export_name,
+                client_field_declaration.item.definition_path,
+            )),
+            None => ClientFieldActionKind::Identity,
+        };
 
         // TODO variant should carry payloads, instead of this check
         if variant == ClientFieldVariant::Component {
@@ -103,8 +141,9 @@ impl UnvalidatedSchema {
         }
 
         self.client_fields.push(ClientField {
-            description: None,
+            description: client_field_declaration.item.description.map(|d| d.item),
             name,
+            name_location: Location::new(text_source, resolver_field_name_span),
             id: next_resolver_id,
             selection_set_and_unwraps: client_field_declaration.item.selection_set_and_unwraps,
             variant,
@@ -112,10 +151,15 @@ impl UnvalidatedSchema {
             type_and_field: ObjectTypeAndFieldNames {
                 type_name: object.name,
                 field_name: name,
+                artifact_namespace,
             },
 
             parent_object_id,
             action_kind,
+            skip_codegen,
+            no_aliases,
+            fetchable_variant_of,
+            merged_selection_set: None,
         });
         Ok(())
     }
@@ -149,6 +193,19 @@ pub enum ProcessClientFieldDeclarationError {
     )]
     // TODO add parent type and resolver field name
     ComponentResolverMissingJsFunction,
+
+    #[error(
+        "@fetchable must be passed the name of the variant as a string, e.g. \
+        @fetchable(name: \"UserDetailCompact\")."
+    )]
+    FetchableDirectiveMissingName,
+
+    // Ids are the indexes 0 through u32::MAX, inclusive.
+    #[error(
+        "There are too many client fields. Isograph supports at most {} client fields.",
+        u32::MAX as u64 + 1
+    )]
+    TooManyClientFields,
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
@@ -181,6 +238,29 @@ impl fmt::Display for ClientFieldVariant {
 
 lazy_static! {
     static ref COMPONENT: IsographDirectiveName = "component".intern().into();
+    static ref SKIP_CODEGEN: IsographDirectiveName = "skipCodegen".intern().into();
+    static ref NO_ALIASES: IsographDirectiveName = "noAliases".intern().into();
+    static ref FETCHABLE: IsographDirectiveName = "fetchable".intern().into();
+}
+
+/// The name passed to @fetchable, if the client field is annotated with it.
+fn get_fetchable_variant_name(
+    directives: &[WithSpan<FragmentDirectiveUsage>],
+) -> ProcessResolverDeclarationResult<Option<SelectableFieldName>> {
+    let fetchable = match directives
+        .iter()
+        .find(|directive| directive.item.name.item == *FETCHABLE)
+    {
+        Some(fetchable) => fetchable,
+        None => return Ok(None),
+    };
+    match fetchable.item.argument("name") {
+        Some(NonConstantValue::String(name)) => Ok(Some((*name).into())),
+        _ => Err(WithSpan::new(
+            ProcessClientFieldDeclarationError::FetchableDirectiveMissingName,
+            fetchable.span,
+        )),
+    }
 }
 
 fn get_resolver_variant(directives: &[WithSpan<FragmentDirectiveUsage>]) -> ClientFieldVariant {
@@ -3,25 +3,29 @@ use std::collections::{hash_map::Entry, HashMap};
 use crate::{
     ClientField, ClientFieldActionKind, ClientFieldVariant, EncounteredRootTypes,
     FieldDefinitionLocation, IsographObjectTypeDefinition, ObjectTypeAndFieldNames,
-    ProcessedRootTypes, RootTypes, Schema, SchemaObject, SchemaScalar, SchemaServerField,
-    UnvalidatedClientField, UnvalidatedObjectFieldInfo, UnvalidatedSchema, UnvalidatedSchemaField,
+    ProcessClientFieldDeclarationError, ProcessedRootTypes, RootTypes, Schema, SchemaEnumValue,
+    SchemaObject, SchemaScalar, SchemaServerField, UnvalidatedClientField,
+    UnvalidatedObjectFieldInfo, UnvalidatedSchema, UnvalidatedSchemaField, ValidRefinement,
     ID_GRAPHQL_TYPE, STRING_JAVASCRIPT_TYPE,
 };
 use common_lang_types::{
-    GraphQLObjectTypeName, GraphQLScalarTypeName, IsographObjectTypeName, Location,
-    SelectableFieldName, Span, StringLiteralValue, UnvalidatedTypeName, WithLocation, WithSpan,
+    DescriptionValue, DirectiveArgumentName, DirectiveName, EnumLiteralValue,
+    GraphQLInterfaceTypeName, GraphQLObjectTypeName, GraphQLScalarTypeName, InputTypeName,
+    InputValueName, IsographObjectTypeName, Location, SelectableFieldName, Span,
+    StringLiteralValue, UnvalidatedTypeName, ValueKeyName, WithLocation, WithSpan,
 };
 use graphql_lang_types::{
+    ConstantValue, DirectiveLocation, GraphQLDirective, GraphQLEnumValueDefinition,
     GraphQLFieldDefinition, GraphQLScalarTypeDefinition, GraphQLTypeSystemDefinition,
     GraphQLTypeSystemDocument, GraphQLTypeSystemExtension, GraphQLTypeSystemExtensionDocument,
     GraphQLTypeSystemExtensionOrDefinition, NamedTypeAnnotation, NonNullTypeAnnotation,
     RootOperationKind, TypeAnnotation,
 };
 use intern::{string_key::Intern, Lookup};
-use isograph_config::ConfigOptions;
+use isograph_config::{ConfigOptions, CustomScalarLiterals};
 use isograph_lang_types::{
-    ClientFieldId, ObjectId, ScalarFieldSelection, SelectableFieldId, Selection, ServerFieldId,
-    ServerFieldSelection, ServerStrongIdFieldId,
+    ClientFieldId, ObjectId, ScalarFieldSelection, ScalarId, SelectableFieldId, Selection,
+    ServerFieldId, ServerFieldSelection, ServerStrongIdFieldId,
 };
 use lazy_static::lazy_static;
 use serde::Deserialize;
@@ -30,6 +34,40 @@ use thiserror::Error;
 lazy_static! {
     static ref QUERY_TYPE: IsographObjectTypeName = "Query".intern().into();
     static ref MUTATION_TYPE: IsographObjectTypeName = "Mutation".intern().into();
+    static ref SUBSCRIPTION_TYPE: IsographObjectTypeName = "Subscription".intern().into();
+    static ref INTERNAL_DIRECTIVE: DirectiveName = "internal".intern().into();
+    static ref DEPRECATED_DIRECTIVE: DirectiveName = "deprecated".intern().into();
+    static ref REASON_ARGUMENT: DirectiveArgumentName = "reason".intern().into();
+    static ref DEFAULT_DEPRECATION_REASON: DescriptionValue = "No longer supported".intern().into();
+}
+
+/// If the directives contain @deprecated, its reason, which defaults to "No longer
+/// supported", as in the GraphQL spec.
+fn deprecation_reason(directives: &[GraphQLDirective<ConstantValue>]) -> Option<DescriptionValue> {
+    let directive = directives
+        .iter()
+        .find(|directive| directive.name.item == *DEPRECATED_DIRECTIVE)?;
+    Some(
+        directive
+            .arguments
+            .iter()
+            .find_map(|argument| match &argument.value.item {
+                ConstantValue::String(reason) if argument.name.item == *REASON_ARGUMENT => {
+                    Some(reason.lookup().intern().into())
+                }
+                _ => None,
+            })
+            .unwrap_or(*DEFAULT_DEPRECATION_REASON),
+    )
+}
+
+fn schema_enum_value(
+    enum_value_definition: &WithLocation<GraphQLEnumValueDefinition>,
+) -> SchemaEnumValue {
+    SchemaEnumValue {
+        value: enum_value_definition.item.value.item,
+        deprecation_reason: deprecation_reason(&enum_value_definition.item.directives),
+    }
 }
 
 // When parsing, we have the subtype's ObjectId, but only the Supertype's name
@@ -79,6 +117,7 @@ impl UnvalidatedSchema {
             subscription: None,
         };
         let mut processed_root_types = None;
+        let mut encountered_union_members = vec![];
 
         for with_location in type_system_document.0 {
             let WithLocation {
@@ -102,7 +141,7 @@ impl UnvalidatedSchema {
                     }
                 }
                 GraphQLTypeSystemDefinition::ScalarTypeDefinition(scalar_type_definition) => {
-                    self.process_scalar_definition(scalar_type_definition)?;
+                    self.process_scalar_definition(scalar_type_definition, None)?;
                     // N.B. we assume that Mutation will be an object, not a scalar
                 }
                 GraphQLTypeSystemDefinition::InterfaceTypeDefinition(interface_type_definition) => {
@@ -118,13 +157,16 @@ impl UnvalidatedSchema {
                 GraphQLTypeSystemDefinition::InputObjectTypeDefinition(
                     input_object_type_definition,
                 ) => {
-                    self.process_object_type_definition(
+                    let outcome = self.process_object_type_definition(
                         input_object_type_definition.into(),
                         &mut supertype_to_subtype_map,
                         &mut subtype_to_supertype_map,
                         false,
                         options,
                     )?;
+                    self.schema_data
+                        .object_mut(outcome.object_id)
+                        .is_input_object = true;
                 }
                 GraphQLTypeSystemDefinition::DirectiveDefinition(_) => {
                     // For now, Isograph ignores directive definitions,
@@ -132,14 +174,30 @@ impl UnvalidatedSchema {
                 }
                 GraphQLTypeSystemDefinition::EnumDefinition(enum_definition) => {
                     // TODO Do not do this
-                    self.process_scalar_definition(GraphQLScalarTypeDefinition {
-                        description: enum_definition.description,
-                        name: enum_definition.name.map(|x| x.lookup().intern().into()),
-                        directives: enum_definition.directives,
-                    })?;
+                    let enum_values = enum_definition
+                        .enum_value_definitions
+                        .iter()
+                        .map(schema_enum_value)
+                        .collect();
+                    self.process_scalar_definition(
+                        GraphQLScalarTypeDefinition {
+                            description: enum_definition.description,
+                            name: enum_definition.name.map(|x| x.lookup().intern().into()),
+                            directives: enum_definition.directives,
+                        },
+                        Some(enum_values),
+                    )?;
                 }
                 GraphQLTypeSystemDefinition::UnionTypeDefinition(union_definition) => {
-                    // TODO do something reasonable here, once we add support for type refinements.
+                    // Union members may be defined later in the document, so we record them
+                    // now, and add them to the refinement maps once all types are defined.
+                    let union_name: IsographObjectTypeName = union_definition.name.item.into();
+                    encountered_union_members.extend(
+                        union_definition
+                            .union_member_types
+                            .into_iter()
+                            .map(|member| (union_name, member)),
+                    );
                     self.process_object_type_definition(
                         IsographObjectTypeDefinition {
                             description: union_definition.description,
@@ -170,9 +228,17 @@ impl UnvalidatedSchema {
             }
         }
 
+        self.add_union_members_to_refinement_maps(
+            encountered_union_members,
+            &mut supertype_to_subtype_map,
+            &mut subtype_to_supertype_map,
+        )?;
+
         let type_refinement_map =
             self.get_type_refinement_map(supertype_to_subtype_map, subtype_to_supertype_map)?;
 
+        self.add_refinement_fields(&type_refinement_map.supertype_to_subtype_map)?;
+
         let root_types = self.process_root_types(processed_root_types, encountered_root_types)?;
 
         if let Some(query_type_id) = root_types.query {
@@ -182,6 +248,20 @@ impl UnvalidatedSchema {
             );
             self.query_type_id = Some(query_type_id);
         }
+        if let Some(mutation_type_id) = root_types.mutation {
+            debug_assert!(
+                self.mutation_type_id.is_none(),
+                "Expected mutation not to be already defined."
+            );
+            self.mutation_type_id = Some(mutation_type_id);
+        }
+        if let Some(subscription_type_id) = root_types.subscription {
+            debug_assert!(
+                self.subscription_type_id.is_none(),
+                "Expected subscription not to be already defined."
+            );
+            self.subscription_type_id = Some(subscription_type_id);
+        }
 
         Ok(ProcessGraphQLDocumentOutcome {
             root_types,
@@ -189,9 +269,46 @@ impl UnvalidatedSchema {
         })
     }
 
-    // TODO This is currently a completely useless function, serving only to surface
-    // some validation errors. It might be necessary once we handle __asNode etc.
-    // style fields.
+    fn add_union_members_to_refinement_maps(
+        &self,
+        union_members: Vec<(IsographObjectTypeName, WithLocation<GraphQLObjectTypeName>)>,
+        supertype_to_subtype_map: &mut UnvalidatedSupertypeToSubtypeMap,
+        subtype_to_supertype_map: &mut UnvalidatedSubtypeToSupertypeMap,
+    ) -> ProcessTypeDefinitionResult<()> {
+        for (union_name, member) in union_members {
+            let member_id = match self.schema_data.defined_types.get(&member.item.into()) {
+                Some(SelectableFieldId::Object(object_id)) => *object_id,
+                Some(SelectableFieldId::Scalar(_)) => {
+                    return Err(WithLocation::new(
+                        ProcessTypeDefinitionError::UnionMemberIsScalar {
+                            union_name,
+                            member_name: member.item.into(),
+                        },
+                        member.location,
+                    ))
+                }
+                None => {
+                    return Err(WithLocation::new(
+                        ProcessTypeDefinitionError::IsographObjectTypeNameNotDefined {
+                            type_name: member.item.into(),
+                        },
+                        member.location,
+                    ))
+                }
+            };
+
+            supertype_to_subtype_map
+                .entry(union_name)
+                .or_default()
+                .push(member_id);
+            subtype_to_supertype_map
+                .entry(member_id)
+                .or_default()
+                .push(WithLocation::new(union_name, member.location));
+        }
+        Ok(())
+    }
+
     fn get_type_refinement_map(
         &mut self,
         unvalidated_supertype_to_subtype_map: UnvalidatedSupertypeToSubtypeMap,
@@ -287,6 +404,87 @@ impl UnvalidatedSchema {
         })
     }
 
+    /// For each abstract type (e.g. Node), add a server field that narrows it to each
+    /// of its concrete subtypes (e.g. creating Node.asUser). These fields are resolved
+    /// on the client by comparing the record's __typename to the concrete type's name.
+    fn add_refinement_fields(
+        &mut self,
+        supertype_to_subtype_map: &TypeRefinementMap,
+    ) -> ProcessTypeDefinitionResult<()> {
+        // Iterate in a stable order, so that server field ids do not depend on
+        // hash map ordering.
+        let mut supertype_ids: Vec<_> = supertype_to_subtype_map.keys().copied().collect();
+        supertype_ids.sort();
+
+        for supertype_id in supertype_ids {
+            for subtype_id in &supertype_to_subtype_map[&supertype_id] {
+                let subtype_name = self.schema_data.object(*subtype_id).name;
+                let field_name: SelectableFieldName = format!("as{}", subtype_name).intern().into();
+                let field_type: TypeAnnotation<UnvalidatedTypeName> = TypeAnnotation::Named(
+                    NamedTypeAnnotation(WithSpan::new(subtype_name.into(), Span::todo_generated())),
+                );
+                let next_server_field_id = ServerFieldId::try_from_usize(self.server_fields.len())
+                    .ok_or_else(|| {
+                        schema_too_large_error("server fields", Location::generated())
+                    })?;
+
+                let supertype = self.schema_data.object_mut(supertype_id);
+                let supertype_name = supertype.name;
+                if supertype
+                    .encountered_fields
+                    .insert(
+                        field_name,
+                        FieldDefinitionLocation::Server(field_type.clone()),
+                    )
+                    .is_some()
+                {
+                    // Point at the field that is already defined with that name
+                    let existing_field_location = supertype
+                        .server_fields
+                        .iter()
+                        .map(|server_field_id| &self.server_fields[server_field_id.as_usize()])
+                        .find(|server_field| server_field.name.item == field_name)
+                        .map(|server_field| server_field.name.location)
+                        .unwrap_or_else(Location::generated);
+                    return Err(WithLocation::new(
+                        ProcessTypeDefinitionError::RefinementFieldExists {
+                            field_name,
+                            parent_type: supertype_name,
+                            concrete_type: subtype_name,
+                        },
+                        existing_field_location,
+                    ));
+                }
+                supertype.server_fields.push(next_server_field_id);
+                supertype.valid_refinements.push(ValidRefinement {
+                    target: *subtype_id,
+                });
+
+                self.server_fields.push(SchemaServerField {
+                    description: Some(
+                        format!(
+                            "Narrows {} to {}, if it is one.",
+                            supertype_name, subtype_name
+                        )
+                        .intern()
+                        .into(),
+                    ),
+                    name: WithLocation::new(field_name, Location::generated()),
+                    id: next_server_field_id,
+                    associated_data: field_type,
+                    parent_type_id: supertype_id,
+                    arguments: vec![],
+                    refinement: Some(ValidRefinement {
+                        target: *subtype_id,
+                    }),
+                    is_internal: false,
+                    deprecation_reason: None,
+                });
+            }
+        }
+        Ok(())
+    }
+
     pub fn process_graphql_type_extension_document(
         &mut self,
         extension_document: GraphQLTypeSystemExtensionDocument,
@@ -309,15 +507,40 @@ impl UnvalidatedSchema {
 
         // N.B. we should probably restructure this...?
         // Like, we could discover the mutation type right now!
-        let outcome = self.process_graphql_type_system_document(
+        let mut outcome = self.process_graphql_type_system_document(
             GraphQLTypeSystemDocument(definitions),
             options,
         )?;
 
+        // (supertype, subtype) pairs, i.e. interfaces implemented and union members
+        // added by extensions
+        let mut new_refinements = vec![];
         for extension in extensions.into_iter() {
             // TODO collect errors into vec
-            // TODO we can encounter new interface implementations; we should account for that
-            self.process_graphql_type_system_extension(extension)?;
+            self.process_graphql_type_system_extension(extension, options, &mut new_refinements)?;
+        }
+
+        let mut new_supertype_to_subtype_map: TypeRefinementMap = HashMap::new();
+        for (supertype_id, subtype_id) in new_refinements {
+            new_supertype_to_subtype_map
+                .entry(supertype_id)
+                .or_default()
+                .push(subtype_id);
+            outcome
+                .type_refinement_maps
+                .subtype_to_supertype_map
+                .entry(subtype_id)
+                .or_default()
+                .push(supertype_id);
+        }
+        self.add_refinement_fields(&new_supertype_to_subtype_map)?;
+        for (supertype_id, subtype_ids) in new_supertype_to_subtype_map {
+            outcome
+                .type_refinement_maps
+                .supertype_to_subtype_map
+                .entry(supertype_id)
+                .or_default()
+                .extend(subtype_ids);
         }
 
         Ok(outcome)
@@ -326,45 +549,323 @@ impl UnvalidatedSchema {
     fn process_graphql_type_system_extension(
         &mut self,
         extension: WithLocation<GraphQLTypeSystemExtension>,
+        options: ConfigOptions,
+        new_refinements: &mut Vec<(ObjectId, ObjectId)>,
     ) -> ProcessTypeDefinitionResult<()> {
         match extension.item {
             GraphQLTypeSystemExtension::ObjectTypeExtension(object_extension) => {
-                let name = object_extension.name.item;
-
-                let id = self.schema_data.defined_types.get(&name.into()).expect(
-                    "TODO why does this id not exist. This probably indicates a bug in Isograph.",
-                );
-
-                match *id {
-                    SelectableFieldId::Object(object_id) => {
-                        let schema_object = self.schema_data.object_mut(object_id);
-
-                        if !object_extension.fields.is_empty() {
-                            panic!("Adding fields in schema extensions is not allowed, yet.");
+                let object_id = self.process_object_type_extension(
+                    object_extension.name.map(|name| name.into()),
+                    "an object",
+                    object_extension.directives,
+                    object_extension.fields,
+                    true,
+                    options,
+                )?;
+                self.add_implemented_interfaces(
+                    object_id,
+                    object_extension.interfaces,
+                    new_refinements,
+                )
+            }
+            GraphQLTypeSystemExtension::InterfaceTypeExtension(interface_extension) => {
+                let interface_id = self.process_object_type_extension(
+                    interface_extension.name.map(|name| name.into()),
+                    "an interface",
+                    interface_extension.directives,
+                    interface_extension.fields,
+                    true,
+                    options,
+                )?;
+                self.add_implemented_interfaces(
+                    interface_id,
+                    interface_extension.interfaces,
+                    new_refinements,
+                )
+            }
+            GraphQLTypeSystemExtension::InputObjectTypeExtension(input_object_extension) => self
+                .process_object_type_extension(
+                    input_object_extension.name.map(|name| name.into()),
+                    "an input object",
+                    input_object_extension.directives,
+                    input_object_extension
+                        .fields
+                        .into_iter()
+                        .map(|field| field.map(GraphQLFieldDefinition::from))
+                        .collect(),
+                    false,
+                    options,
+                )
+                .map(|_| ()),
+            GraphQLTypeSystemExtension::UnionTypeExtension(union_extension) => {
+                let union_id = self.process_object_type_extension(
+                    union_extension.name.map(|name| name.into()),
+                    "a union",
+                    union_extension.directives,
+                    vec![],
+                    true,
+                    options,
+                )?;
+                let union_name = self.schema_data.object(union_id).name;
+                for member in union_extension.union_member_types {
+                    let member_id = match self.schema_data.defined_types.get(&member.item.into()) {
+                        Some(SelectableFieldId::Object(object_id)) => *object_id,
+                        Some(SelectableFieldId::Scalar(_)) => {
+                            return Err(WithLocation::new(
+                                ProcessTypeDefinitionError::UnionMemberIsScalar {
+                                    union_name,
+                                    member_name: member.item.into(),
+                                },
+                                member.location,
+                            ))
                         }
-                        if !object_extension.interfaces.is_empty() {
-                            panic!("Adding interfaces in schema extensions is not allowed, yet.");
+                        None => {
+                            return Err(WithLocation::new(
+                                ProcessTypeDefinitionError::IsographObjectTypeNameNotDefined {
+                                    type_name: member.item.into(),
+                                },
+                                member.location,
+                            ))
                         }
-
-                        schema_object
-                            .directives
-                            .extend(object_extension.directives.into_iter());
-
+                    };
+                    new_refinements.push((union_id, member_id));
+                }
+                Ok(())
+            }
+            GraphQLTypeSystemExtension::ScalarTypeExtension(scalar_extension) => {
+                let scalar_id = self.scalar_id_for_extension(
+                    scalar_extension.name.map(|name| name.into()),
+                    "a scalar",
+                )?;
+                if self.schema_data.scalar(scalar_id).enum_values.is_some() {
+                    return Err(WithLocation::new(
+                        ProcessTypeDefinitionError::TypeExtensionMismatch {
+                            type_name: scalar_extension.name.item.into(),
+                            is_type: "an enum",
+                            extended_as_type: "a scalar",
+                        },
+                        scalar_extension.name.location,
+                    ));
+                }
+                // Isograph does not keep track of the directives of scalars, so there is
+                // nothing to extend.
+                Ok(())
+            }
+            GraphQLTypeSystemExtension::EnumTypeExtension(enum_extension) => {
+                let scalar_id = self.scalar_id_for_extension(
+                    enum_extension.name.map(|name| name.into()),
+                    "an enum",
+                )?;
+                // Enums are (currently) scalars with enum values
+                match &mut self.schema_data.scalar_mut(scalar_id).enum_values {
+                    Some(enum_values) => {
+                        enum_values.extend(
+                            enum_extension
+                                .enum_value_definitions
+                                .iter()
+                                .map(schema_enum_value),
+                        );
                         Ok(())
                     }
-                    SelectableFieldId::Scalar(_) => Err(WithLocation::new(
+                    None => Err(WithLocation::new(
                         ProcessTypeDefinitionError::TypeExtensionMismatch {
-                            type_name: name.into(),
+                            type_name: enum_extension.name.item.into(),
                             is_type: "a scalar",
-                            extended_as_type: "an object",
+                            extended_as_type: "an enum",
                         },
-                        object_extension.name.location,
+                        enum_extension.name.location,
                     )),
                 }
             }
         }
     }
 
+    /// Objects, interfaces, unions and input objects are all represented as objects,
+    /// so they are extended in the same way: fields (other than client fields declared
+    /// with @resolver) are added to the object as server fields, as if they had been
+    /// part of its definition, and directives are appended to its directives.
+    ///
+    /// Returns the id of the extended object.
+    fn process_object_type_extension(
+        &mut self,
+        name: WithLocation<UnvalidatedTypeName>,
+        extended_as_type: &'static str,
+        directives: Vec<GraphQLDirective<ConstantValue>>,
+        fields: Vec<WithLocation<GraphQLFieldDefinition>>,
+        may_have_id_field: bool,
+        options: ConfigOptions,
+    ) -> ProcessTypeDefinitionResult<ObjectId> {
+        let object_id = match self.schema_data.defined_types.get(&name.item) {
+            Some(SelectableFieldId::Object(object_id)) => *object_id,
+            Some(SelectableFieldId::Scalar(_)) => {
+                return Err(WithLocation::new(
+                    ProcessTypeDefinitionError::TypeExtensionMismatch {
+                        type_name: name.item,
+                        is_type: "a scalar",
+                        extended_as_type,
+                    },
+                    name.location,
+                ))
+            }
+            None => {
+                return Err(WithLocation::new(
+                    ProcessTypeDefinitionError::ExtendedTypeNotDefined {
+                        type_name: name.item,
+                        extended_as_type,
+                    },
+                    name.location,
+                ))
+            }
+        };
+
+        let object_name = self.schema_data.object(object_id).name;
+        for field in fields {
+            // Fields with a @resolver directive are client fields
+            if let Some(field) = self.process_resolver_directive_field(object_name, field)? {
+                self.add_server_field_from_extension(object_id, field, may_have_id_field, options)?;
+            }
+        }
+
+        self.schema_data
+            .object_mut(object_id)
+            .directives
+            .extend(directives.into_iter());
+
+        Ok(object_id)
+    }
+
+    fn add_server_field_from_extension(
+        &mut self,
+        object_id: ObjectId,
+        field: WithLocation<GraphQLFieldDefinition>,
+        may_have_id_field: bool,
+        options: ConfigOptions,
+    ) -> ProcessTypeDefinitionResult<()> {
+        let next_server_field_id = ServerFieldId::try_from_usize(self.server_fields.len())
+            .ok_or_else(|| schema_too_large_error("server fields", field.location))?;
+
+        let object = self.schema_data.object_mut(object_id);
+        let object_name = object.name;
+        if object
+            .encountered_fields
+            .contains_key(&field.item.name.item)
+        {
+            return Err(WithLocation::new(
+                ProcessTypeDefinitionError::DuplicateField {
+                    field_name: field.item.name.item,
+                    parent_type: object_name,
+                },
+                field.item.name.location,
+            ));
+        }
+
+        // TODO the __refetch field is only added to objects whose definition has an
+        // id field, so objects whose id field is added by an extension cannot be
+        // refetched.
+        let id_name: SelectableFieldName = "id".intern().into();
+        if may_have_id_field && field.item.name.item == id_name {
+            set_and_validate_id_field(
+                &mut object.id_field,
+                next_server_field_id.as_usize(),
+                &field,
+                object_name,
+                options,
+            )?;
+        }
+
+        object.encountered_fields.insert(
+            field.item.name.item,
+            FieldDefinitionLocation::Server(field.item.type_.clone()),
+        );
+        object.server_fields.push(next_server_field_id);
+
+        let is_internal = field
+            .item
+            .directives
+            .iter()
+            .any(|directive| directive.name.item == *INTERNAL_DIRECTIVE);
+        self.server_fields.push(SchemaServerField {
+            description: field.item.description.map(|d| d.item),
+            name: field.item.name,
+            id: next_server_field_id,
+            associated_data: field.item.type_,
+            parent_type_id: object_id,
+            arguments: field.item.arguments,
+            refinement: None,
+            is_internal,
+            deprecation_reason: deprecation_reason(&field.item.directives),
+        });
+        Ok(())
+    }
+
+    /// Records that subtype_id implements the interfaces, which are added to the
+    /// type refinement maps once every extension has been processed.
+    fn add_implemented_interfaces(
+        &self,
+        subtype_id: ObjectId,
+        interfaces: Vec<WithLocation<GraphQLInterfaceTypeName>>,
+        new_refinements: &mut Vec<(ObjectId, ObjectId)>,
+    ) -> ProcessTypeDefinitionResult<()> {
+        for interface in interfaces {
+            let interface_name: IsographObjectTypeName = interface.item.into();
+            match self.schema_data.defined_types.get(&interface_name.into()) {
+                Some(SelectableFieldId::Object(interface_id)) => {
+                    new_refinements.push((*interface_id, subtype_id));
+                }
+                Some(SelectableFieldId::Scalar(_)) => {
+                    return Err(WithLocation::new(
+                        ProcessTypeDefinitionError::ObjectIsScalar {
+                            type_name: interface_name,
+                            implementing_object: self.schema_data.object(subtype_id).name,
+                        },
+                        interface.location,
+                    ))
+                }
+                None => {
+                    return Err(WithLocation::new(
+                        ProcessTypeDefinitionError::IsographObjectTypeNameNotDefined {
+                            type_name: interface_name,
+                        },
+                        interface.location,
+                    ))
+                }
+            }
+        }
+        Ok(())
+    }
+
+    fn scalar_id_for_extension(
+        &self,
+        name: WithLocation<UnvalidatedTypeName>,
+        extended_as_type: &'static str,
+    ) -> ProcessTypeDefinitionResult<ScalarId> {
+        let id = self
+            .schema_data
+            .defined_types
+            .get(&name.item)
+            .ok_or_else(|| {
+                WithLocation::new(
+                    ProcessTypeDefinitionError::ExtendedTypeNotDefined {
+                        type_name: name.item,
+                        extended_as_type,
+                    },
+                    name.location,
+                )
+            })?;
+
+        match *id {
+            SelectableFieldId::Scalar(scalar_id) => Ok(scalar_id),
+            SelectableFieldId::Object(_) => Err(WithLocation::new(
+                ProcessTypeDefinitionError::TypeExtensionMismatch {
+                    type_name: name.item,
+                    is_type: "an object",
+                    extended_as_type,
+                },
+                name.location,
+            )),
+        }
+    }
+
     pub(crate) fn process_object_type_definition(
         &mut self,
         object_type_definition: IsographObjectTypeDefinition,
@@ -380,7 +881,10 @@ impl UnvalidatedSchema {
             client_fields: ref mut schema_resolvers,
             ..
         } = self;
-        let next_object_id = schema_data.objects.len().into();
+        let next_object_id =
+            ObjectId::try_from_usize(schema_data.objects.len()).ok_or_else(|| {
+                schema_too_large_error("objects", object_type_definition.name.location)
+            })?;
         let string_type_for_typename = schema_data.scalar(self.string_type_id).name;
         let ref mut type_names = schema_data.defined_types;
         let ref mut objects = schema_data.objects;
@@ -399,6 +903,15 @@ impl UnvalidatedSchema {
             Entry::Vacant(vacant) => {
                 // TODO avoid this
                 let type_def_2 = object_type_definition.clone();
+                // Every field, and __typename, is given an id
+                if ServerFieldId::try_from_usize(schema_fields.len() + type_def_2.fields.len())
+                    .is_none()
+                {
+                    return Err(schema_too_large_error(
+                        "server fields",
+                        object_type_definition.name.location,
+                    ));
+                }
                 let FieldObjectIdsEtc {
                     unvalidated_schema_fields,
                     server_fields,
@@ -420,29 +933,35 @@ impl UnvalidatedSchema {
                     schema_resolvers,
                     next_object_id,
                     &object_type_definition,
-                );
+                )?;
 
                 objects.push(SchemaObject {
                     description: object_type_definition.description.map(|d| d.item),
                     name: object_type_definition.name.item,
+                    name_location: object_type_definition.name.location,
                     id: next_object_id,
                     server_fields,
                     resolvers: object_resolvers,
                     encountered_fields,
                     id_field,
                     directives: object_type_definition.directives,
+                    valid_refinements: vec![],
+                    is_input_object: false,
                 });
 
                 schema_fields.extend(unvalidated_schema_fields);
                 vacant.insert(SelectableFieldId::Object(next_object_id));
 
+                // These are the default root types, which are used if the schema has no
+                // schema definition (i.e. `schema { query: ... }`).
                 // TODO default types are a GraphQL-land concept, but this is Isograph-land
                 if object_type_definition.name.item == *QUERY_TYPE {
                     Some(RootOperationKind::Query)
                 } else if object_type_definition.name.item == *MUTATION_TYPE {
                     Some(RootOperationKind::Mutation)
+                } else if object_type_definition.name.item == *SUBSCRIPTION_TYPE {
+                    Some(RootOperationKind::Subscription)
                 } else {
-                    // TODO subscription
                     None
                 }
             }
@@ -484,12 +1003,16 @@ impl UnvalidatedSchema {
     fn process_scalar_definition(
         &mut self,
         scalar_type_definition: GraphQLScalarTypeDefinition,
+        enum_values: Option<Vec<SchemaEnumValue>>,
     ) -> ProcessTypeDefinitionResult<()> {
         let &mut Schema {
             ref mut schema_data,
             ..
         } = self;
-        let next_scalar_id = schema_data.scalars.len().into();
+        let next_scalar_id =
+            ScalarId::try_from_usize(schema_data.scalars.len()).ok_or_else(|| {
+                schema_too_large_error("scalars", scalar_type_definition.name.location)
+            })?;
         let ref mut type_names = schema_data.defined_types;
         let ref mut scalars = schema_data.scalars;
         match type_names.entry(scalar_type_definition.name.item.into()) {
@@ -508,6 +1031,8 @@ impl UnvalidatedSchema {
                     name: scalar_type_definition.name,
                     id: next_scalar_id,
                     javascript_name: *STRING_JAVASCRIPT_TYPE,
+                    enum_values,
+                    literals: CustomScalarLiterals::default(),
                 });
 
                 vacant.insert(SelectableFieldId::Scalar(next_scalar_id));
@@ -606,9 +1131,12 @@ fn get_resolvers_for_schema_object(
     schema_resolvers: &mut Vec<UnvalidatedClientField>,
     parent_object_id: ObjectId,
     type_definition: &IsographObjectTypeDefinition,
-) -> Vec<ClientFieldId> {
+) -> ProcessTypeDefinitionResult<Vec<ClientFieldId>> {
     if let Some(_id_field_id) = id_field_id {
-        let next_resolver_id = schema_resolvers.len().into();
+        let next_resolver_id =
+            ClientFieldId::try_from_usize(schema_resolvers.len()).ok_or_else(|| {
+                schema_too_large_error("client fields", type_definition.name.location)
+            })?;
         let id_field_selection = WithSpan::new(
             Selection::ServerField(ServerFieldSelection::ScalarField(ScalarFieldSelection {
                 name: WithLocation::new("id".intern().into(), Location::generated()),
@@ -617,12 +1145,15 @@ fn get_resolvers_for_schema_object(
                 associated_data: (),
                 unwraps: vec![],
                 arguments: vec![],
+                directives: vec![],
+                description: None,
             })),
             Span::todo_generated(),
         );
         schema_resolvers.push(ClientField {
             description: Some("A refetch field for this object.".intern().into()),
             name: "__refetch".intern().into(),
+            name_location: Location::generated(),
             id: next_resolver_id,
             selection_set_and_unwraps: Some((vec![id_field_selection], vec![])),
             variant: ClientFieldVariant::RefetchField,
@@ -630,22 +1161,39 @@ fn get_resolvers_for_schema_object(
             type_and_field: ObjectTypeAndFieldNames {
                 type_name: type_definition.name.item,
                 field_name: "__refetch".intern().into(),
+                artifact_namespace: None,
             },
             parent_object_id,
             // N.B. __refetch fields are non-fetchable, but they do execute queries which
             // have normalization ASTs.
             action_kind: ClientFieldActionKind::RefetchField,
+            skip_codegen: false,
+            no_aliases: false,
+            fetchable_variant_of: None,
+            merged_selection_set: None,
         });
         encountered_fields.insert(
             "__refetch".intern().into(),
             FieldDefinitionLocation::Client(next_resolver_id),
         );
-        vec![next_resolver_id]
+        Ok(vec![next_resolver_id])
     } else {
-        vec![]
+        Ok(vec![])
     }
 }
 
+/// Ids are u32 indexes into the schema, so the schema cannot contain more than
+/// u32::MAX + 1 items of any kind.
+pub(crate) fn schema_too_large_error(
+    item_kind: &'static str,
+    location: Location,
+) -> WithLocation<ProcessTypeDefinitionError> {
+    WithLocation::new(
+        ProcessTypeDefinitionError::SchemaTooLarge { item_kind },
+        location,
+    )
+}
+
 fn get_typename_type(
     string_type_for_typename: GraphQLScalarTypeName,
 ) -> TypeAnnotation<UnvalidatedTypeName> {
@@ -669,6 +1217,9 @@ struct FieldObjectIdsEtc {
 
 /// Given a vector of fields from the schema AST all belonging to the same object/interface,
 /// return a vector of unvalidated fields and a set of field names.
+///
+/// The caller must ensure that next_field_id + new_fields.len() (the id of __typename)
+/// fits in a ServerFieldId, so that converting the ids of the fields cannot panic.
 fn get_field_objects_ids_and_names(
     new_fields: Vec<WithLocation<GraphQLFieldDefinition>>,
     next_field_id: usize,
@@ -705,6 +1256,11 @@ fn get_field_objects_ids_and_names(
                     )?;
                 }
 
+                let is_internal = field
+                    .item
+                    .directives
+                    .iter()
+                    .any(|directive| directive.name.item == *INTERNAL_DIRECTIVE);
                 unvalidated_fields.push(SchemaServerField {
                     description: field.item.description.map(|d| d.item),
                     name: field.item.name,
@@ -712,6 +1268,9 @@ fn get_field_objects_ids_and_names(
                     associated_data: field.item.type_,
                     parent_type_id,
                     arguments: field.item.arguments,
+                    refinement: None,
+                    is_internal,
+                    deprecation_reason: deprecation_reason(&field.item.directives),
                 });
                 field_ids.push(current_field_id.into());
             }
@@ -733,6 +1292,7 @@ fn get_field_objects_ids_and_names(
     // TODO: the only way to determine that a field is a magic __typename field is
     // to check the name! That's a bit unfortunate. We should model these differently,
     // perhaps fields should contain an enum (IdField, TypenameField, ActualField)
+    // N.B. this is at most next_field_id + new_field_count, which the caller checked.
     let typename_field_id = (next_field_id + field_ids.len()).into();
     let typename_name = WithLocation::new("__typename".intern().into(), Location::generated());
     field_ids.push(typename_field_id);
@@ -743,6 +1303,9 @@ fn get_field_objects_ids_and_names(
         associated_data: typename_type.clone(),
         parent_type_id,
         arguments: vec![],
+        refinement: None,
+        is_internal: false,
+        deprecation_reason: None,
     });
 
     if encountered_fields
@@ -752,13 +1315,16 @@ fn get_field_objects_ids_and_names(
         )
         .is_some()
     {
+        let defined_typename_location = unvalidated_fields
+            .iter()
+            .find(|field| field.name.item == typename_name.item && field.id != typename_field_id)
+            .map(|field| field.name.location)
+            .unwrap_or_else(Location::generated);
         return Err(WithLocation::new(
             ProcessTypeDefinitionError::TypenameCannotBeDefined {
                 parent_type: parent_type_name,
             },
-            // This is blatantly incorrect, we should have the location
-            // of the previously defined typename
-            Location::generated(),
+            defined_typename_location,
         ));
     }
     // ----- END HACK -----
@@ -787,6 +1353,8 @@ fn set_and_validate_id_field(
 
     // We should change the type here! It should not be ID! It should be a
     // type specific to the concrete type, e.g. UserID.
+    // N.B. current_field_id is less than the id of __typename, which the caller of
+    // get_field_objects_ids_and_names checked fits in a ServerFieldId.
     *id_field = Some(current_field_id.into());
 
     match field.item.type_.inner_non_null_named_type() {
@@ -830,6 +1398,13 @@ pub(crate) type ProcessTypeDefinitionResult<T> =
 /// Errors that make semantic sense when referring to creating a GraphQL schema in-memory representation
 #[derive(Error, Debug)]
 pub enum ProcessTypeDefinitionError {
+    // Ids are the indexes 0 through u32::MAX, inclusive.
+    #[error(
+        "The schema contains too many {item_kind}. Isograph supports at most {} {item_kind}.",
+        u32::MAX as u64 + 1
+    )]
+    SchemaTooLarge { item_kind: &'static str },
+
     // TODO include info about where the type was previously defined
     // TODO the type_definition_name refers to the second object being defined, which isn't
     // all that helpful
@@ -870,6 +1445,23 @@ pub enum ProcessTypeDefinitionError {
         implementing_object: IsographObjectTypeName,
     },
 
+    #[error("\"{member_name}\" is a member of the union \"{union_name}\". However, \"{member_name}\" is a scalar, but only object types can be union members.")]
+    UnionMemberIsScalar {
+        union_name: IsographObjectTypeName,
+        member_name: IsographObjectTypeName,
+    },
+
+    #[error(
+        "The Isograph compiler attempted to create a field named \
+    \"{field_name}\" on type \"{parent_type}\", which narrows it to \"{concrete_type}\", \
+    but a field with that name already exists."
+    )]
+    RefinementFieldExists {
+        field_name: SelectableFieldName,
+        parent_type: IsographObjectTypeName,
+        concrete_type: IsographObjectTypeName,
+    },
+
     #[error(
         "You cannot manually defined the \"__typename\" field, which is defined in \"{parent_type}\"."
     )]
@@ -956,6 +1548,15 @@ pub enum ProcessTypeDefinitionError {
         extended_as_type: &'static str,
     },
 
+    #[error(
+        "The type `{type_name}` is being extended as {extended_as_type}, but it is not \
+        defined. Only types defined in the schema or in schema extensions can be extended."
+    )]
+    ExtendedTypeNotDefined {
+        type_name: UnvalidatedTypeName,
+        extended_as_type: &'static str,
+    },
+
     #[error("Duplicate schema definition")]
     DuplicateSchemaDefinition,
 
@@ -964,4 +1565,71 @@ pub enum ProcessTypeDefinitionError {
 
     #[error("Failed to deserialize {0}")]
     FailedToDeserialize(String),
+
+    #[error("{0}")]
+    ProcessClientFieldDeclarationError(ProcessClientFieldDeclarationError),
+
+    #[error("The directive `@{directive_name}` is not defined.")]
+    UnknownDirective { directive_name: DirectiveName },
+
+    #[error("The directive `@{directive_name}` cannot be used at location {location}.")]
+    DirectiveNotAllowedAtLocation {
+        directive_name: DirectiveName,
+        location: DirectiveLocation,
+    },
+
+    #[error(
+        "The directive `@{directive_name}` does not accept an argument named `{argument_name}`."
+    )]
+    UnknownDirectiveArgument {
+        directive_name: DirectiveName,
+        argument_name: DirectiveArgumentName,
+    },
+
+    #[error("The directive `@{directive_name}` requires an argument named `{argument_name}`.")]
+    MissingDirectiveArgument {
+        directive_name: DirectiveName,
+        argument_name: InputValueName,
+    },
+
+    #[error(
+        "The directive `@{directive_name}` is not repeatable, \
+        but it is used more than once on the same element."
+    )]
+    DirectiveIsNotRepeatable { directive_name: DirectiveName },
+
+    #[error("Expected a value of type `{expected_type}`.")]
+    InvalidValueType { expected_type: String },
+
+    #[error(
+        "The enum `{enum_name}` has no value named `{value}`.{}",
+        suggestion.map(|suggestion| format!(" Did you mean `{suggestion}`?")).unwrap_or_default()
+    )]
+    UnknownEnumValue {
+        enum_name: InputTypeName,
+        value: EnumLiteralValue,
+        suggestion: Option<EnumLiteralValue>,
+    },
+
+    #[error(
+        "The input object `{input_object_name}` has no field named `{field_name}`.{}",
+        suggestion.map(|suggestion| format!(" Did you mean `{suggestion}`?")).unwrap_or_default()
+    )]
+    UnknownInputObjectField {
+        input_object_name: InputTypeName,
+        field_name: ValueKeyName,
+        suggestion: Option<InputValueName>,
+    },
+
+    #[error("The field `{field_name}` of the input object `{input_object_name}` is specified more than once.")]
+    DuplicateInputObjectField {
+        input_object_name: InputTypeName,
+        field_name: ValueKeyName,
+    },
+
+    #[error("The input object `{input_object_name}` requires a field named `{field_name}`.")]
+    MissingInputObjectField {
+        input_object_name: InputTypeName,
+        field_name: InputValueName,
+    },
 }
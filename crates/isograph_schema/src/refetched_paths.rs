@@ -15,7 +15,10 @@ pub fn refetched_paths_with_path(
 ) -> HashSet<PathToRefetchField> {
     let mut paths = HashSet::default();
 
-    for selection in selection_set {
+    for selection in selection_set
+        .iter()
+        .filter(|selection| !selection.item.is_statically_excluded())
+    {
         match &selection.item {
             Selection::ServerField(field) => match field {
                 ServerFieldSelection::ScalarField(scalar) => match scalar.associated_data {
@@ -47,6 +50,18 @@ pub fn refetched_paths_with_path(
                         }
                     }
                 },
+                ServerFieldSelection::LinkedField(linked_field_selection)
+                    if linked_field_selection.associated_data.is_refinement =>
+                {
+                    // Refinements read from the same record, so they do not add to the path.
+                    let new_paths = refetched_paths_with_path(
+                        &linked_field_selection.selection_set,
+                        schema,
+                        path,
+                    );
+
+                    paths.extend(new_paths.into_iter());
+                }
                 ServerFieldSelection::LinkedField(linked_field_selection) => {
                     path.push(NameAndArguments {
                         name: linked_field_selection.name.item.into(),
@@ -0,0 +1,121 @@
+use common_lang_types::{
+    DirectiveName, EmbeddedLocation, IsographObjectTypeName, Location, StringLiteralValue,
+    WithLocation, WithSpan,
+};
+use graphql_lang_types::{
+    from_graph_ql_directive, ConstantValue, DeserializationError, GraphQLDirective,
+    GraphQLFieldDefinition,
+};
+use intern::{string_key::Intern, Lookup};
+use isograph_lang_types::{ClientFieldDeclaration, VariableDefinition};
+use lazy_static::lazy_static;
+use serde::Deserialize;
+
+use crate::{ProcessTypeDefinitionError, ProcessTypeDefinitionResult, UnvalidatedSchema};
+
+lazy_static! {
+    static ref RESOLVER_DIRECTIVE: DirectiveName = "resolver".intern().into();
+}
+
+/// A client field declared in a schema extension, instead of in an iso literal, e.g.
+///
+/// extend type User {
+///   fullName: String @resolver(path: "src/User/fullName.ts", export: "fullName")
+/// }
+///
+/// The path is relative to the project root.
+#[derive(Deserialize, Eq, PartialEq, Debug)]
+#[serde(deny_unknown_fields)]
+pub struct ResolverDirective {
+    path: StringLiteralValue,
+    export: StringLiteralValue,
+}
+
+impl ResolverDirective {
+    pub fn new(path: StringLiteralValue, export: StringLiteralValue) -> Self {
+        Self { path, export }
+    }
+}
+
+impl UnvalidatedSchema {
+    /// Process a field in an object type extension that has a @resolver directive
+    /// as if it were declared in an iso literal. Such client fields do not select
+    /// any fields, and their arguments become the client field's variables.
+    ///
+    /// Returns the field back if it has no @resolver directive.
+    pub(crate) fn process_resolver_directive_field(
+        &mut self,
+        parent_type_name: IsographObjectTypeName,
+        field: WithLocation<GraphQLFieldDefinition>,
+    ) -> ProcessTypeDefinitionResult<Option<WithLocation<GraphQLFieldDefinition>>> {
+        let resolver_directive = match find_resolver_directive(&field.item.directives)? {
+            Some(resolver_directive) => resolver_directive,
+            None => return Ok(Some(field)),
+        };
+
+        let (text_source, name_span) = match field.item.name.location {
+            Location::Embedded(EmbeddedLocation { text_source, span }) => (text_source, span),
+            Location::Generated => panic!(
+                "Fields in schema extensions should have an embedded location. \
+                This is indicative of a bug in Isograph."
+            ),
+        };
+
+        let client_field_declaration = ClientFieldDeclaration {
+            description: field.item.description,
+            const_export_name: Some(resolver_directive.export.lookup().intern().into()),
+            parent_type: WithSpan::new(parent_type_name.into(), name_span),
+            client_field_name: WithSpan::new(
+                field.item.name.item.lookup().intern().into(),
+                name_span,
+            ),
+            selection_set_and_unwraps: Some((vec![], vec![])),
+            directives: vec![],
+            variable_definitions: field
+                .item
+                .arguments
+                .into_iter()
+                .map(|argument| {
+                    let argument = argument.hack_to_with_span();
+                    argument.map(|argument| VariableDefinition {
+                        name: argument.name.map(|name| name.into()),
+                        type_: argument.type_.map(|type_name| type_name.into()),
+                    })
+                })
+                .collect(),
+            definition_path: resolver_directive.path.lookup().intern().into(),
+        };
+
+        self.process_client_field_declaration(
+            WithSpan::new(client_field_declaration, name_span),
+            text_source,
+            None,
+        )
+        .map_err(|e| {
+            WithLocation::new(
+                ProcessTypeDefinitionError::ProcessClientFieldDeclarationError(e.item),
+                e.location,
+            )
+        })?;
+
+        Ok(None)
+    }
+}
+
+fn find_resolver_directive(
+    directives: &[GraphQLDirective<ConstantValue>],
+) -> ProcessTypeDefinitionResult<Option<ResolverDirective>> {
+    for directive in directives {
+        if directive.name.item == *RESOLVER_DIRECTIVE {
+            let resolver_directive =
+                from_graph_ql_directive(directive).map_err(|err| match err {
+                    DeserializationError::Custom(err) => WithLocation::new(
+                        ProcessTypeDefinitionError::FailedToDeserialize(err),
+                        directive.name.location.into(),
+                    ),
+                })?;
+            return Ok(Some(resolver_directive));
+        }
+    }
+    Ok(None)
+}
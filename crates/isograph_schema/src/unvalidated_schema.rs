@@ -5,6 +5,7 @@ use common_lang_types::{
 };
 use graphql_lang_types::TypeAnnotation;
 use intern::string_key::Intern;
+use isograph_config::CustomScalarLiterals;
 use isograph_lang_types::{
     ClientFieldId, EntrypointTypeAndField, LinkedFieldSelection, ScalarId, SelectableFieldId,
 };
@@ -119,6 +120,8 @@ impl UnvalidatedSchema {
             boolean_type_id,
 
             query_type_id: None,
+            mutation_type_id: None,
+            subscription_type_id: None,
         }
     }
 }
@@ -129,6 +132,8 @@ fn add_schema_defined_scalar_type(
     field_name: &'static str,
     javascript_name: JavascriptName,
 ) -> ScalarId {
+    // N.B. this is only called for the handful of built-in scalars, before any
+    // scalars from the schema are added, so this cannot overflow.
     let scalar_id = scalars.len().into();
 
     // TODO this is problematic, we have no span (or really, no location) associated with this
@@ -140,6 +145,8 @@ fn add_schema_defined_scalar_type(
         name: typename,
         id: scalar_id,
         javascript_name,
+        enum_values: None,
+        literals: CustomScalarLiterals::default(),
     });
     defined_types.insert(
         typename.item.into(),
@@ -0,0 +1,823 @@
+use std::collections::{HashMap, HashSet};
+
+use common_lang_types::{DirectiveName, InputTypeName, WithLocation};
+use graphql_lang_types::{
+    ConstantValue, DirectiveLocation, GraphQLDirective, GraphQLDirectiveDefinition,
+    GraphQLEnumDefinition, GraphQLFieldDefinition, GraphQLInputObjectTypeDefinition,
+    GraphQLInputValueDefinition, GraphQLTypeSystemDefinition, GraphQLTypeSystemDocument,
+    GraphQLTypeSystemExtension, GraphQLTypeSystemExtensionDocument,
+    GraphQLTypeSystemExtensionOrDefinition, NonNullTypeAnnotation, TypeAnnotation,
+};
+use intern::{string_key::Intern, Lookup};
+use lazy_static::lazy_static;
+
+use crate::{ProcessTypeDefinitionError, ProcessTypeDefinitionResult};
+
+lazy_static! {
+    /// Directives that can be used without being defined in the schema, and whether
+    /// they are repeatable. The arguments of Isograph's own directives (@exposeField
+    /// and @resolver) are validated when they are processed.
+    static ref BUILT_IN_DIRECTIVES: Vec<(DirectiveName, bool)> = [
+        ("skip", false),
+        ("include", false),
+        ("deprecated", false),
+        ("specifiedBy", false),
+        ("oneOf", false),
+        ("exposeField", true),
+        ("resolver", false),
+        ("internal", false),
+    ]
+    .into_iter()
+    .map(|(name, is_repeatable)| (name.intern().into(), is_repeatable))
+    .collect();
+}
+
+#[derive(Debug, Clone, Copy)]
+enum InputTypeKind<'a> {
+    Scalar,
+    Enum(&'a GraphQLEnumDefinition),
+    InputObject(&'a GraphQLInputObjectTypeDefinition),
+}
+
+/// Validate every directive applied in the schema and in the schema extensions against
+/// the directive definitions in those documents, i.e. that the directive is defined,
+/// can be used at that location, is passed arguments of the correct types, and is
+/// not used more than once on the same element unless it is repeatable.
+///
+/// The default values of arguments and input fields are validated against their
+/// types as well.
+pub fn validate_directives_and_default_values(
+    type_system_document: &GraphQLTypeSystemDocument,
+    type_extension_documents: &[GraphQLTypeSystemExtensionDocument],
+) -> ProcessTypeDefinitionResult<()> {
+    let definitions = type_system_document.0.iter().map(|item| &item.item).chain(
+        type_extension_documents
+            .iter()
+            .flat_map(|document| document.0.iter())
+            .filter_map(|item| match &item.item {
+                GraphQLTypeSystemExtensionOrDefinition::Definition(definition) => Some(definition),
+                GraphQLTypeSystemExtensionOrDefinition::Extension(_) => None,
+            }),
+    );
+
+    let mut validator = DirectiveValidator::default();
+    for definition in definitions.clone() {
+        validator.add_definition(definition);
+    }
+
+    for definition in definitions {
+        validator.validate_definition(definition)?;
+    }
+
+    for item in type_extension_documents
+        .iter()
+        .flat_map(|document| document.0.iter())
+    {
+        if let GraphQLTypeSystemExtensionOrDefinition::Extension(extension) = &item.item {
+            validator.validate_extension(extension)?;
+        }
+    }
+
+    Ok(())
+}
+
+#[derive(Default)]
+struct DirectiveValidator<'a> {
+    directive_definitions: HashMap<DirectiveName, &'a GraphQLDirectiveDefinition>,
+    input_types: HashMap<InputTypeName, InputTypeKind<'a>>,
+}
+
+impl<'a> DirectiveValidator<'a> {
+    fn add_definition(&mut self, definition: &'a GraphQLTypeSystemDefinition) {
+        match definition {
+            GraphQLTypeSystemDefinition::DirectiveDefinition(directive_definition) => {
+                self.directive_definitions
+                    .insert(directive_definition.name.item, directive_definition);
+            }
+            GraphQLTypeSystemDefinition::ScalarTypeDefinition(scalar_definition) => {
+                self.input_types
+                    .insert(scalar_definition.name.item.into(), InputTypeKind::Scalar);
+            }
+            GraphQLTypeSystemDefinition::EnumDefinition(enum_definition) => {
+                self.input_types.insert(
+                    enum_definition.name.item.lookup().intern().into(),
+                    InputTypeKind::Enum(enum_definition),
+                );
+            }
+            GraphQLTypeSystemDefinition::InputObjectTypeDefinition(input_object_definition) => {
+                self.input_types.insert(
+                    input_object_definition.name.item.lookup().intern().into(),
+                    InputTypeKind::InputObject(input_object_definition),
+                );
+            }
+            GraphQLTypeSystemDefinition::ObjectTypeDefinition(_)
+            | GraphQLTypeSystemDefinition::InterfaceTypeDefinition(_)
+            | GraphQLTypeSystemDefinition::UnionTypeDefinition(_)
+            | GraphQLTypeSystemDefinition::SchemaDefinition(_) => {}
+        }
+    }
+
+    fn validate_definition(
+        &self,
+        definition: &GraphQLTypeSystemDefinition,
+    ) -> ProcessTypeDefinitionResult<()> {
+        match definition {
+            GraphQLTypeSystemDefinition::ObjectTypeDefinition(object_definition) => {
+                self.validate_directives(&object_definition.directives, DirectiveLocation::Object)?;
+                self.validate_fields(&object_definition.fields)
+            }
+            GraphQLTypeSystemDefinition::InterfaceTypeDefinition(interface_definition) => {
+                self.validate_directives(
+                    &interface_definition.directives,
+                    DirectiveLocation::Interface,
+                )?;
+                self.validate_fields(&interface_definition.fields)
+            }
+            GraphQLTypeSystemDefinition::InputObjectTypeDefinition(input_object_definition) => {
+                self.validate_directives(
+                    &input_object_definition.directives,
+                    DirectiveLocation::InputObject,
+                )?;
+                self.validate_input_values(
+                    &input_object_definition.fields,
+                    DirectiveLocation::InputFieldDefinition,
+                )
+            }
+            GraphQLTypeSystemDefinition::EnumDefinition(enum_definition) => {
+                self.validate_directives(&enum_definition.directives, DirectiveLocation::Enum)?;
+                for enum_value_definition in enum_definition.enum_value_definitions.iter() {
+                    self.validate_directives(
+                        &enum_value_definition.item.directives,
+                        DirectiveLocation::EnumValue,
+                    )?;
+                }
+                Ok(())
+            }
+            GraphQLTypeSystemDefinition::ScalarTypeDefinition(scalar_definition) => {
+                self.validate_directives(&scalar_definition.directives, DirectiveLocation::Scalar)
+            }
+            GraphQLTypeSystemDefinition::UnionTypeDefinition(union_definition) => {
+                self.validate_directives(&union_definition.directives, DirectiveLocation::Union)
+            }
+            GraphQLTypeSystemDefinition::SchemaDefinition(schema_definition) => {
+                self.validate_directives(&schema_definition.directives, DirectiveLocation::Schema)
+            }
+            GraphQLTypeSystemDefinition::DirectiveDefinition(directive_definition) => self
+                .validate_input_values(
+                    &directive_definition.arguments,
+                    DirectiveLocation::ArgumentDefinition,
+                ),
+        }
+    }
+
+    fn validate_extension(
+        &self,
+        extension: &GraphQLTypeSystemExtension,
+    ) -> ProcessTypeDefinitionResult<()> {
+        match extension {
+            GraphQLTypeSystemExtension::ObjectTypeExtension(object_extension) => {
+                self.validate_directives(&object_extension.directives, DirectiveLocation::Object)?;
+                self.validate_fields(&object_extension.fields)
+            }
+            GraphQLTypeSystemExtension::InterfaceTypeExtension(interface_extension) => {
+                self.validate_directives(
+                    &interface_extension.directives,
+                    DirectiveLocation::Interface,
+                )?;
+                self.validate_fields(&interface_extension.fields)
+            }
+            GraphQLTypeSystemExtension::InputObjectTypeExtension(input_object_extension) => {
+                self.validate_directives(
+                    &input_object_extension.directives,
+                    DirectiveLocation::InputObject,
+                )?;
+                self.validate_input_values(
+                    &input_object_extension.fields,
+                    DirectiveLocation::InputFieldDefinition,
+                )
+            }
+            GraphQLTypeSystemExtension::EnumTypeExtension(enum_extension) => {
+                self.validate_directives(&enum_extension.directives, DirectiveLocation::Enum)?;
+                for enum_value_definition in enum_extension.enum_value_definitions.iter() {
+                    self.validate_directives(
+                        &enum_value_definition.item.directives,
+                        DirectiveLocation::EnumValue,
+                    )?;
+                }
+                Ok(())
+            }
+            GraphQLTypeSystemExtension::ScalarTypeExtension(scalar_extension) => {
+                self.validate_directives(&scalar_extension.directives, DirectiveLocation::Scalar)
+            }
+            GraphQLTypeSystemExtension::UnionTypeExtension(union_extension) => {
+                self.validate_directives(&union_extension.directives, DirectiveLocation::Union)
+            }
+        }
+    }
+
+    fn validate_fields(
+        &self,
+        fields: &[WithLocation<GraphQLFieldDefinition>],
+    ) -> ProcessTypeDefinitionResult<()> {
+        for field in fields {
+            self.validate_directives(&field.item.directives, DirectiveLocation::FieldDefinition)?;
+            self.validate_input_values(
+                &field.item.arguments,
+                DirectiveLocation::ArgumentDefinition,
+            )?;
+        }
+        Ok(())
+    }
+
+    fn validate_input_values(
+        &self,
+        input_values: &[WithLocation<GraphQLInputValueDefinition>],
+        location: DirectiveLocation,
+    ) -> ProcessTypeDefinitionResult<()> {
+        for input_value in input_values {
+            self.validate_directives(&input_value.item.directives, location)?;
+            if let Some(default_value) = &input_value.item.default_value {
+                self.validate_value(default_value, &input_value.item.type_)?;
+            }
+        }
+        Ok(())
+    }
+
+    fn validate_directives(
+        &self,
+        directives: &[GraphQLDirective<ConstantValue>],
+        location: DirectiveLocation,
+    ) -> ProcessTypeDefinitionResult<()> {
+        let mut seen_directive_names = HashSet::new();
+        for directive in directives {
+            self.validate_directive(directive, location)?;
+
+            let directive_name = directive.name.item;
+            if !seen_directive_names.insert(directive_name) && !self.is_repeatable(directive_name) {
+                return Err(WithLocation::new(
+                    ProcessTypeDefinitionError::DirectiveIsNotRepeatable { directive_name },
+                    directive.name.location.into(),
+                ));
+            }
+        }
+        Ok(())
+    }
+
+    fn is_repeatable(&self, directive_name: DirectiveName) -> bool {
+        match self.directive_definitions.get(&directive_name) {
+            Some(directive_definition) => directive_definition.repeatable.is_some(),
+            None => BUILT_IN_DIRECTIVES
+                .iter()
+                .find(|(name, _)| *name == directive_name)
+                .map(|(_, is_repeatable)| *is_repeatable)
+                .unwrap_or(true),
+        }
+    }
+
+    fn validate_directive(
+        &self,
+        directive: &GraphQLDirective<ConstantValue>,
+        location: DirectiveLocation,
+    ) -> ProcessTypeDefinitionResult<()> {
+        let directive_name = directive.name.item;
+        let directive_definition = match self.directive_definitions.get(&directive_name) {
+            Some(directive_definition) => directive_definition,
+            None => {
+                if BUILT_IN_DIRECTIVES
+                    .iter()
+                    .any(|(name, _)| *name == directive_name)
+                {
+                    return Ok(());
+                }
+                return Err(WithLocation::new(
+                    ProcessTypeDefinitionError::UnknownDirective { directive_name },
+                    directive.name.location.into(),
+                ));
+            }
+        };
+
+        if !directive_definition
+            .locations
+            .iter()
+            .any(|allowed_location| allowed_location.item == location)
+        {
+            return Err(WithLocation::new(
+                ProcessTypeDefinitionError::DirectiveNotAllowedAtLocation {
+                    directive_name,
+                    location,
+                },
+                directive.name.location.into(),
+            ));
+        }
+
+        for argument in directive.arguments.iter() {
+            let argument_definition = directive_definition
+                .arguments
+                .iter()
+                .find(|argument_definition| {
+                    argument_definition.item.name.item.lookup() == argument.name.item.lookup()
+                })
+                .ok_or_else(|| {
+                    WithLocation::new(
+                        ProcessTypeDefinitionError::UnknownDirectiveArgument {
+                            directive_name,
+                            argument_name: argument.name.item,
+                        },
+                        argument.name.location,
+                    )
+                })?;
+
+            self.validate_value(&argument.value, &argument_definition.item.type_)?;
+        }
+
+        for argument_definition in directive_definition.arguments.iter() {
+            let is_required = matches!(argument_definition.item.type_, TypeAnnotation::NonNull(_))
+                && argument_definition.item.default_value.is_none();
+            if is_required
+                && !directive.arguments.iter().any(|argument| {
+                    argument.name.item.lookup() == argument_definition.item.name.item.lookup()
+                })
+            {
+                return Err(WithLocation::new(
+                    ProcessTypeDefinitionError::MissingDirectiveArgument {
+                        directive_name,
+                        argument_name: argument_definition.item.name.item,
+                    },
+                    directive.name.location.into(),
+                ));
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Validate that value can be coerced to type_. The returned error points at the
+    /// part of the value (e.g. a list item) that cannot be coerced.
+    fn validate_value(
+        &self,
+        value: &WithLocation<ConstantValue>,
+        type_: &TypeAnnotation<InputTypeName>,
+    ) -> ProcessTypeDefinitionResult<()> {
+        match type_ {
+            TypeAnnotation::Named(named_type) => match value.item {
+                ConstantValue::Null => Ok(()),
+                _ => self.validate_named_type_value(value, type_, named_type.item),
+            },
+            TypeAnnotation::List(list_type) => match value.item {
+                ConstantValue::Null => Ok(()),
+                _ => self.validate_list_value(value, &list_type.0),
+            },
+            TypeAnnotation::NonNull(non_null_type) => match value.item {
+                ConstantValue::Null => Err(invalid_value_type(value, type_)),
+                _ => match non_null_type.as_ref() {
+                    NonNullTypeAnnotation::Named(named_type) => {
+                        self.validate_named_type_value(value, type_, named_type.item)
+                    }
+                    NonNullTypeAnnotation::List(list_type) => {
+                        self.validate_list_value(value, &list_type.0)
+                    }
+                },
+            },
+        }
+    }
+
+    fn validate_list_value(
+        &self,
+        value: &WithLocation<ConstantValue>,
+        item_type: &TypeAnnotation<InputTypeName>,
+    ) -> ProcessTypeDefinitionResult<()> {
+        match &value.item {
+            ConstantValue::List(items) => {
+                for item in items {
+                    self.validate_value(item, item_type)?;
+                }
+                Ok(())
+            }
+            // A single value is coerced to a list containing that value
+            _ => self.validate_value(value, item_type),
+        }
+    }
+
+    fn validate_named_type_value(
+        &self,
+        value: &WithLocation<ConstantValue>,
+        type_: &TypeAnnotation<InputTypeName>,
+        type_name: InputTypeName,
+    ) -> ProcessTypeDefinitionResult<()> {
+        let is_valid = match type_name.lookup() {
+            "String" => matches!(value.item, ConstantValue::String(_)),
+            "ID" => matches!(value.item, ConstantValue::String(_) | ConstantValue::Int(_)),
+            "Int" => matches!(value.item, ConstantValue::Int(_)),
+            "Float" => matches!(value.item, ConstantValue::Float(_) | ConstantValue::Int(_)),
+            "Boolean" => matches!(value.item, ConstantValue::Boolean(_)),
+            _ => match self.input_types.get(&type_name) {
+                Some(InputTypeKind::Enum(enum_definition)) => {
+                    return self.validate_enum_value(value, type_, type_name, enum_definition);
+                }
+                Some(InputTypeKind::InputObject(input_object_definition)) => {
+                    return self.validate_input_object_value(
+                        value,
+                        type_,
+                        type_name,
+                        input_object_definition,
+                    );
+                }
+                // Custom scalars can be represented by any value, and types that are
+                // never defined are reported elsewhere.
+                Some(InputTypeKind::Scalar) | None => true,
+            },
+        };
+
+        if is_valid {
+            Ok(())
+        } else {
+            Err(invalid_value_type(value, type_))
+        }
+    }
+
+    fn validate_enum_value(
+        &self,
+        value: &WithLocation<ConstantValue>,
+        type_: &TypeAnnotation<InputTypeName>,
+        enum_name: InputTypeName,
+        enum_definition: &GraphQLEnumDefinition,
+    ) -> ProcessTypeDefinitionResult<()> {
+        let enum_literal = match value.item {
+            ConstantValue::Enum(enum_literal) => enum_literal,
+            _ => return Err(invalid_value_type(value, type_)),
+        };
+
+        let enum_values = enum_definition
+            .enum_value_definitions
+            .iter()
+            .map(|enum_value_definition| enum_value_definition.item.value.item);
+        if enum_values
+            .clone()
+            .any(|enum_value| enum_value == enum_literal)
+        {
+            return Ok(());
+        }
+
+        Err(WithLocation::new(
+            ProcessTypeDefinitionError::UnknownEnumValue {
+                enum_name,
+                value: enum_literal,
+                suggestion: closest_match(enum_literal.lookup(), enum_values),
+            },
+            value.location,
+        ))
+    }
+
+    fn validate_input_object_value(
+        &self,
+        value: &WithLocation<ConstantValue>,
+        type_: &TypeAnnotation<InputTypeName>,
+        input_object_name: InputTypeName,
+        input_object_definition: &GraphQLInputObjectTypeDefinition,
+    ) -> ProcessTypeDefinitionResult<()> {
+        let object_fields = match &value.item {
+            ConstantValue::Object(object_fields) => object_fields,
+            _ => return Err(invalid_value_type(value, type_)),
+        };
+
+        for (index, object_field) in object_fields.iter().enumerate() {
+            let field_name = object_field.name.item;
+            if object_fields[..index]
+                .iter()
+                .any(|previous_field| previous_field.name.item == field_name)
+            {
+                return Err(WithLocation::new(
+                    ProcessTypeDefinitionError::DuplicateInputObjectField {
+                        input_object_name,
+                        field_name,
+                    },
+                    object_field.name.location,
+                ));
+            }
+
+            let field_definition = input_object_definition
+                .fields
+                .iter()
+                .find(|field_definition| {
+                    field_definition.item.name.item.lookup() == field_name.lookup()
+                })
+                .ok_or_else(|| {
+                    WithLocation::new(
+                        ProcessTypeDefinitionError::UnknownInputObjectField {
+                            input_object_name,
+                            field_name,
+                            suggestion: closest_match(
+                                field_name.lookup(),
+                                input_object_definition
+                                    .fields
+                                    .iter()
+                                    .map(|field_definition| field_definition.item.name.item),
+                            ),
+                        },
+                        object_field.name.location,
+                    )
+                })?;
+
+            self.validate_value(&object_field.value, &field_definition.item.type_)?;
+        }
+
+        for field_definition in input_object_definition.fields.iter() {
+            let is_required = matches!(field_definition.item.type_, TypeAnnotation::NonNull(_))
+                && field_definition.item.default_value.is_none();
+            if is_required
+                && !object_fields.iter().any(|object_field| {
+                    object_field.name.item.lookup() == field_definition.item.name.item.lookup()
+                })
+            {
+                return Err(WithLocation::new(
+                    ProcessTypeDefinitionError::MissingInputObjectField {
+                        input_object_name,
+                        field_name: field_definition.item.name.item,
+                    },
+                    value.location,
+                ));
+            }
+        }
+
+        Ok(())
+    }
+}
+
+fn invalid_value_type(
+    value: &WithLocation<ConstantValue>,
+    type_: &TypeAnnotation<InputTypeName>,
+) -> WithLocation<ProcessTypeDefinitionError> {
+    WithLocation::new(
+        ProcessTypeDefinitionError::InvalidValueType {
+            expected_type: type_.to_string(),
+        },
+        value.location,
+    )
+}
+
+/// Find the candidate that is most similar to name, if any candidate is similar
+/// enough that name is likely a typo of it.
+fn closest_match<T: Lookup + Copy>(name: &str, candidates: impl Iterator<Item = T>) -> Option<T> {
+    let max_distance = (name.len() / 3).max(1);
+    candidates
+        .map(|candidate| (edit_distance(name, candidate.lookup()), candidate))
+        .filter(|(distance, _)| *distance <= max_distance)
+        .min_by_key(|(distance, _)| *distance)
+        .map(|(_, candidate)| candidate)
+}
+
+/// The Levenshtein distance between a and b, ignoring case.
+fn edit_distance(a: &str, b: &str) -> usize {
+    let a = a.to_lowercase().chars().collect::<Vec<_>>();
+    let b = b.to_lowercase().chars().collect::<Vec<_>>();
+
+    let mut previous_row = (0..=b.len()).collect::<Vec<_>>();
+    for (i, a_char) in a.iter().enumerate() {
+        let mut current_row = vec![i + 1];
+        for (j, b_char) in b.iter().enumerate() {
+            let substitution_cost = if a_char == b_char { 0 } else { 1 };
+            current_row.push(
+                (previous_row[j] + substitution_cost)
+                    .min(previous_row[j + 1] + 1)
+                    .min(current_row[j] + 1),
+            );
+        }
+        previous_row = current_row;
+    }
+    previous_row[b.len()]
+}
+
+#[cfg(test)]
+mod test {
+    use common_lang_types::{EmbeddedLocation, Location, TextSource};
+    use graphql_schema_parser::parse_schema;
+    use intern::{string_key::Intern, Lookup};
+
+    use crate::{ProcessTypeDefinitionError, ProcessTypeDefinitionResult};
+
+    use super::{edit_distance, validate_directives_and_default_values};
+
+    static SORT_ENUM: &str = "enum Sort { NAME AGE }\n";
+
+    static FILTER_INPUT_OBJECT: &str = "input Filter { name: String! age: Int sort: Sort }\n";
+
+    fn validate(schema: &str) -> ProcessTypeDefinitionResult<()> {
+        let text_source = TextSource {
+            path: "/path/to/schema.graphql".intern().into(),
+            span: None,
+        };
+        let document = parse_schema(schema, text_source).expect("Expected the schema to parse");
+        validate_directives_and_default_values(&document, &[])
+    }
+
+    fn validation_error(schema: &str) -> Option<ProcessTypeDefinitionError> {
+        validate(schema).err().map(|error| error.item)
+    }
+
+    /// The error, and the text of the schema that it points at
+    fn validation_error_and_text(schema: &str) -> (ProcessTypeDefinitionError, &str) {
+        let error = validate(schema).expect_err("Expected the schema to be invalid");
+        match error.location {
+            Location::Embedded(EmbeddedLocation { span, .. }) => {
+                (error.item, &schema[span.start as usize..span.end as usize])
+            }
+            Location::Generated => panic!("Expected the error to point at the schema"),
+        }
+    }
+
+    #[test]
+    fn valid_enum_default_value() {
+        assert!(validation_error(&format!(
+            "{SORT_ENUM}type Query {{ users(sort: Sort = AGE): String }}"
+        ))
+        .is_none());
+    }
+
+    #[test]
+    fn unknown_enum_default_value_with_suggestion() {
+        match validation_error(&format!(
+            "{SORT_ENUM}type Query {{ users(sort: Sort = NAMEE): String }}"
+        )) {
+            Some(ProcessTypeDefinitionError::UnknownEnumValue {
+                enum_name,
+                value,
+                suggestion,
+            }) => {
+                assert_eq!(enum_name.lookup(), "Sort");
+                assert_eq!(value.lookup(), "NAMEE");
+                assert_eq!(
+                    suggestion.map(|suggestion| suggestion.lookup()),
+                    Some("NAME")
+                );
+            }
+            error => panic!("Expected an unknown enum value, got {error:?}"),
+        }
+    }
+
+    #[test]
+    fn unknown_enum_default_value_without_suggestion() {
+        match validation_error(&format!(
+            "{SORT_ENUM}type Query {{ users(sort: Sort = CREATED_AT): String }}"
+        )) {
+            Some(ProcessTypeDefinitionError::UnknownEnumValue { suggestion, .. }) => {
+                assert!(suggestion.is_none());
+            }
+            error => panic!("Expected an unknown enum value, got {error:?}"),
+        }
+    }
+
+    #[test]
+    fn unknown_enum_directive_argument() {
+        match validation_error(&format!(
+            "{SORT_ENUM}directive @sorted(by: Sort!) on FIELD_DEFINITION\n\
+            type Query {{ users: String @sorted(by: AGGE) }}"
+        )) {
+            Some(ProcessTypeDefinitionError::UnknownEnumValue {
+                value, suggestion, ..
+            }) => {
+                assert_eq!(value.lookup(), "AGGE");
+                assert_eq!(
+                    suggestion.map(|suggestion| suggestion.lookup()),
+                    Some("AGE")
+                );
+            }
+            error => panic!("Expected an unknown enum value, got {error:?}"),
+        }
+    }
+
+    #[test]
+    fn non_enum_value_for_enum() {
+        match validation_error(&format!(
+            "{SORT_ENUM}type Query {{ users(sort: Sort = \"NAME\"): String }}"
+        )) {
+            Some(ProcessTypeDefinitionError::InvalidValueType { expected_type }) => {
+                assert_eq!(expected_type, "Sort");
+            }
+            error => panic!("Expected an invalid value type, got {error:?}"),
+        }
+    }
+
+    #[test]
+    fn edit_distance_ignores_case() {
+        assert_eq!(edit_distance("kitten", "sitting"), 3);
+        assert_eq!(edit_distance("NAME", "name"), 0);
+        assert_eq!(edit_distance("NMAE", "NAME"), 2);
+        assert_eq!(edit_distance("", "AGE"), 3);
+    }
+
+    #[test]
+    fn valid_input_object_default_value() {
+        assert!(validation_error(&format!(
+            "{SORT_ENUM}{FILTER_INPUT_OBJECT}\
+            type Query {{ users(filter: Filter = {{ name: \"Jane\", age: 30, sort: AGE }}): String }}"
+        ))
+        .is_none());
+    }
+
+    #[test]
+    fn unknown_input_object_field() {
+        let (error, text) = validation_error_and_text(&format!(
+            "{SORT_ENUM}{FILTER_INPUT_OBJECT}\
+            type Query {{ users(filter: Filter = {{ nmae: \"Jane\" }}): String }}"
+        ));
+        match error {
+            ProcessTypeDefinitionError::UnknownInputObjectField {
+                input_object_name,
+                field_name,
+                suggestion,
+            } => {
+                assert_eq!(input_object_name.lookup(), "Filter");
+                assert_eq!(field_name.lookup(), "nmae");
+                assert!(suggestion.is_none());
+            }
+            error => panic!("Expected an unknown input object field, got {error:?}"),
+        }
+        assert_eq!(text, "nmae");
+    }
+
+    #[test]
+    fn unknown_input_object_field_with_suggestion() {
+        let (error, text) = validation_error_and_text(&format!(
+            "{SORT_ENUM}{FILTER_INPUT_OBJECT}\
+            type Query {{ users(filter: Filter = {{ name: \"Jane\", ages: 30 }}): String }}"
+        ));
+        match error {
+            ProcessTypeDefinitionError::UnknownInputObjectField { suggestion, .. } => {
+                assert_eq!(
+                    suggestion.map(|suggestion| suggestion.lookup()),
+                    Some("age")
+                );
+            }
+            error => panic!("Expected an unknown input object field, got {error:?}"),
+        }
+        assert_eq!(text, "ages");
+    }
+
+    #[test]
+    fn duplicate_input_object_field() {
+        let (error, text) = validation_error_and_text(&format!(
+            "{SORT_ENUM}{FILTER_INPUT_OBJECT}\
+            type Query {{ users(filter: Filter = {{ name: \"Jane\", age: 30, age: 31 }}): String }}"
+        ));
+        match error {
+            ProcessTypeDefinitionError::DuplicateInputObjectField { field_name, .. } => {
+                assert_eq!(field_name.lookup(), "age");
+            }
+            error => panic!("Expected a duplicate input object field, got {error:?}"),
+        }
+        assert_eq!(text, "age");
+    }
+
+    #[test]
+    fn missing_input_object_field() {
+        let (error, text) = validation_error_and_text(&format!(
+            "{SORT_ENUM}{FILTER_INPUT_OBJECT}\
+            type Query {{ users(filter: Filter = {{ age: 30 }}): String }}"
+        ));
+        match error {
+            ProcessTypeDefinitionError::MissingInputObjectField {
+                input_object_name,
+                field_name,
+            } => {
+                assert_eq!(input_object_name.lookup(), "Filter");
+                assert_eq!(field_name.lookup(), "name");
+            }
+            error => panic!("Expected a missing input object field, got {error:?}"),
+        }
+        assert_eq!(text, "{ age: 30 }");
+    }
+
+    #[test]
+    fn invalid_nested_input_object_field_values() {
+        let (error, text) = validation_error_and_text(&format!(
+            "{SORT_ENUM}{FILTER_INPUT_OBJECT}\
+            type Query {{ users(filter: Filter = {{ name: \"Jane\", age: \"thirty\" }}): String }}"
+        ));
+        match error {
+            ProcessTypeDefinitionError::InvalidValueType { expected_type } => {
+                assert_eq!(expected_type, "Int");
+            }
+            error => panic!("Expected an invalid value type, got {error:?}"),
+        }
+        assert_eq!(text, "\"thirty\"");
+
+        assert!(matches!(
+            validation_error(&format!(
+                "{SORT_ENUM}{FILTER_INPUT_OBJECT}\
+                type Query {{ users(filter: Filter = {{ name: \"Jane\", sort: AGGE }}): String }}"
+            )),
+            Some(ProcessTypeDefinitionError::UnknownEnumValue { .. })
+        ));
+    }
+
+    #[test]
+    fn non_object_value_for_input_object() {
+        assert!(matches!(
+            validation_error(&format!(
+                "{SORT_ENUM}{FILTER_INPUT_OBJECT}\
+                type Query {{ users(filter: Filter = \"Jane\"): String }}"
+            )),
+            Some(ProcessTypeDefinitionError::InvalidValueType { .. })
+        ));
+    }
+}
@@ -42,26 +42,27 @@ impl UnvalidatedSchema {
 
         match parent_type_id {
             SelectableFieldId::Object(object_id) => {
-                // For now, only the query object is fetchable, and thus
-                // can be used as a parent type in an iso entrypoint declaration.
+                // For now, only root objects (i.e. Query, Mutation and Subscription) are
+                // fetchable, and thus can be used as a parent type in an iso entrypoint
+                // declaration.
                 //
                 // This requirement should be loosened — anything that we
                 // know how to fetch (e.g. viewer, an item implementing Node, etc.)
                 // should be fetchable.
-                let query_id = self.query_type_id.ok_or(WithLocation::new(
-                    ValidateEntrypointDeclarationError::RootQueryTypeMustExist,
-                    Location::generated(),
-                ))?;
-
-                if query_id != *object_id {
+                if self.root_operation_kind(*object_id).is_some() {
+                    Ok(*object_id)
+                } else if self.query_type_id.is_none() {
+                    Err(WithLocation::new(
+                        ValidateEntrypointDeclarationError::RootQueryTypeMustExist,
+                        Location::generated(),
+                    ))
+                } else {
                     Err(WithLocation::new(
                         ValidateEntrypointDeclarationError::NonFetchableParentType {
                             parent_type_name: parent_type.item,
                         },
                         Location::new(text_source, parent_type.span),
                     ))
-                } else {
-                    Ok(*object_id)
                 }
             }
             SelectableFieldId::Scalar(scalar_id) => {
@@ -127,7 +128,7 @@ pub enum ValidateEntrypointDeclarationError {
     RootQueryTypeMustExist,
 
     #[error(
-        "The type `{parent_type_name}` is not fetchable. (Currently, only Query is fetchable.)"
+        "The type `{parent_type_name}` is not fetchable. (Currently, only Query, Mutation and Subscription are fetchable.)"
     )]
     NonFetchableParentType {
         parent_type_name: UnvalidatedTypeName,
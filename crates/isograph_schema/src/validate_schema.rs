@@ -1,16 +1,26 @@
+use std::collections::{HashMap, HashSet};
+
 use common_lang_types::{
-    InputTypeName, InputValueName, IsographObjectTypeName, ScalarFieldName, SelectableFieldName,
+    FieldArgumentName, FieldNameOrAlias, HasName, InputTypeName, InputValueName,
+    IsographDirectiveName, IsographObjectTypeName, Location, ScalarFieldName, SelectableFieldName,
     UnvalidatedTypeName, VariableName, WithLocation, WithSpan,
 };
 use graphql_lang_types::{GraphQLInputValueDefinition, NamedTypeAnnotation, TypeAnnotation};
+use intern::{
+    string_key::{Intern, StringKey},
+    Lookup,
+};
 use isograph_lang_types::{
-    ClientFieldId, LinkedFieldSelection, ObjectId, ScalarFieldSelection, ScalarId,
-    SelectableFieldId, Selection, ServerFieldId, UnvalidatedScalarFieldSelection,
-    UnvalidatedSelection, VariableDefinition,
+    ClientFieldId, FragmentDirectiveUsage, LinkedFieldSelection, NonConstantValue, ObjectId,
+    ScalarFieldSelection, ScalarId, SelectableFieldId, Selection, SelectionFieldArgument,
+    ServerFieldId, ServerFieldSelection, UnvalidatedScalarFieldSelection, UnvalidatedSelection,
+    VariableDefinition,
 };
+use lazy_static::lazy_static;
 use thiserror::Error;
 
 use crate::{
+    create_merged_selection_set::merge_client_field_selection_sets,
     refetched_paths::refetched_paths_with_path, ClientField, FieldDefinitionLocation,
     NameAndArguments, PathToRefetchField, Schema, SchemaData, SchemaIdField, SchemaObject,
     SchemaServerField, SchemaValidationState, UnvalidatedClientField,
@@ -19,6 +29,16 @@ use crate::{
     ValidateEntrypointDeclarationError,
 };
 
+lazy_static! {
+    static ref TYPENAME_FIELD_NAME: SelectableFieldName = "__typename".intern().into();
+    // These can only be selected on the query type, and we do not support introspection queries
+    static ref INTROSPECTION_FIELD_NAMES: [SelectableFieldName; 2] =
+        ["__schema".intern().into(), "__type".intern().into()];
+}
+
+/// The directives that can be used on selections in iso literals.
+static SELECTION_DIRECTIVE_NAMES: [&str; 5] = ["skip", "include", "defer", "stream", "rename"];
+
 pub type ValidatedSchemaServerField = SchemaServerField<TypeAnnotation<SelectableFieldId>>;
 
 pub type ValidatedSelection = Selection<
@@ -33,8 +53,13 @@ pub type ValidatedLinkedFieldSelection = LinkedFieldSelection<
 pub type ValidatedScalarFieldSelection = ScalarFieldSelection<
     <ValidatedSchemaState as SchemaValidationState>::ClientFieldSelectionScalarFieldAssociatedData,
 >;
+pub type ValidatedServerFieldSelection = ServerFieldSelection<
+    <ValidatedSchemaState as SchemaValidationState>::ClientFieldSelectionScalarFieldAssociatedData,
+    <ValidatedSchemaState as SchemaValidationState>::ClientFieldSelectionLinkedFieldAssociatedData,
+>;
 
 pub type ValidatedVariableDefinition = VariableDefinition<SelectableFieldId>;
+type UnvalidatedVariableDefinition = VariableDefinition<UnvalidatedTypeName>;
 pub type ValidatedClientField = ClientField<
     <ValidatedSchemaState as SchemaValidationState>::ClientFieldSelectionScalarFieldAssociatedData,
     <ValidatedSchemaState as SchemaValidationState>::ClientFieldSelectionLinkedFieldAssociatedData,
@@ -49,9 +74,13 @@ pub type ValidatedSchemaObject =
 
 pub type ValidatedSchemaIdField = SchemaIdField<NamedTypeAnnotation<ScalarId>>;
 
-#[derive(Debug)]
+#[derive(Debug, Clone, Copy)]
 pub struct ValidatedLinkedFieldAssociatedData {
     pub parent_object_id: ObjectId,
+    /// Whether this is a refinement field (e.g. Node.asUser), which narrows the
+    /// parent record to the concrete type parent_object_id, instead of following
+    /// a link to another record.
+    pub is_refinement: bool,
 }
 
 #[derive(Debug)]
@@ -98,6 +127,8 @@ impl ValidatedSchema {
             id_type_id: id_type,
             string_type_id: string_type,
             query_type_id,
+            mutation_type_id,
+            subscription_type_id,
             float_type_id,
             boolean_type_id,
             int_type_id,
@@ -124,6 +155,14 @@ impl ValidatedSchema {
                 }
             };
 
+        errors.extend(validate_skipped_client_fields_are_not_selected(
+            &updated_resolvers,
+        ));
+        errors.extend(validate_fetchable_variants(
+            &updated_resolvers,
+            &schema_data,
+        ));
+
         let SchemaData {
             objects,
             scalars,
@@ -138,7 +177,7 @@ impl ValidatedSchema {
                 })
                 .collect();
 
-            Ok(Self {
+            let mut validated_schema = Self {
                 server_fields: updated_fields,
                 client_fields: updated_resolvers,
                 entrypoints: updated_entrypoints,
@@ -150,16 +189,120 @@ impl ValidatedSchema {
                 id_type_id: id_type,
                 string_type_id: string_type,
                 query_type_id,
+                mutation_type_id,
+                subscription_type_id,
                 float_type_id,
                 boolean_type_id,
                 int_type_id,
-            })
+            };
+            merge_client_field_selection_sets(&mut validated_schema);
+            Ok(validated_schema)
         } else {
             Err(errors)
         }
     }
 }
 
+/// A client field declared with @fetchable(name: "...") is a variant of the client
+/// field with the name written in its iso literal, which must exist and must not be
+/// a variant itself.
+fn validate_fetchable_variants(
+    client_fields: &[ValidatedClientField],
+    schema_data: &UnvalidatedSchemaData,
+) -> Vec<WithLocation<ValidateSchemaError>> {
+    client_fields
+        .iter()
+        .filter_map(|client_field| {
+            let fetchable_variant_of = client_field.fetchable_variant_of?;
+            let parent_object = schema_data.object(client_field.parent_object_id);
+            let is_variant_of_client_field =
+                match parent_object.encountered_fields.get(&fetchable_variant_of) {
+                    Some(FieldDefinitionLocation::Client(client_field_id)) => client_fields
+                        [client_field_id.as_usize()]
+                    .fetchable_variant_of
+                    .is_none(),
+                    _ => false,
+                };
+            if is_variant_of_client_field {
+                None
+            } else {
+                Some(WithLocation::new(
+                    ValidateSchemaError::FetchableVariantOfUnknownClientField {
+                        client_field_parent_type_name: parent_object.name,
+                        client_field_name: client_field.name,
+                        fetchable_variant_of,
+                    },
+                    client_field.name_location,
+                ))
+            }
+        })
+        .collect()
+}
+
+/// Client fields with @skipCodegen have no artifacts, so they cannot be selected
+/// by client fields that do.
+fn validate_skipped_client_fields_are_not_selected(
+    client_fields: &[ValidatedClientField],
+) -> Vec<WithLocation<ValidateSchemaError>> {
+    let mut errors = vec![];
+    for client_field in client_fields
+        .iter()
+        .filter(|client_field| !client_field.skip_codegen)
+    {
+        if let Some((selection_set, _)) = &client_field.selection_set_and_unwraps {
+            validate_selections_do_not_select_skipped_client_fields(
+                client_field,
+                selection_set,
+                client_fields,
+                &mut errors,
+            );
+        }
+    }
+    errors
+}
+
+fn validate_selections_do_not_select_skipped_client_fields(
+    selecting_client_field: &ValidatedClientField,
+    selection_set: &[WithSpan<ValidatedSelection>],
+    client_fields: &[ValidatedClientField],
+    errors: &mut Vec<WithLocation<ValidateSchemaError>>,
+) {
+    for selection in selection_set {
+        match &selection.item {
+            Selection::ServerField(ServerFieldSelection::ScalarField(scalar_field)) => {
+                if let FieldDefinitionLocation::Client(client_field_id) =
+                    scalar_field.associated_data
+                {
+                    let selected_client_field = &client_fields[client_field_id.as_usize()];
+                    if selected_client_field.skip_codegen {
+                        errors.push(WithLocation::new(
+                            ValidateSchemaError::SkippedClientFieldSelected {
+                                client_field_parent_type_name: selecting_client_field
+                                    .type_and_field
+                                    .type_name,
+                                client_field_name: selecting_client_field.name,
+                                field_parent_type_name: selected_client_field
+                                    .type_and_field
+                                    .type_name,
+                                field_name: selected_client_field.name,
+                            },
+                            scalar_field.name.location,
+                        ));
+                    }
+                }
+            }
+            Selection::ServerField(ServerFieldSelection::LinkedField(linked_field)) => {
+                validate_selections_do_not_select_skipped_client_fields(
+                    selecting_client_field,
+                    &linked_field.selection_set,
+                    client_fields,
+                    errors,
+                );
+            }
+        }
+    }
+}
+
 fn transform_object_field_ids(
     schema_fields: &[ValidatedSchemaServerField],
     schema_resolvers: &[ValidatedClientField],
@@ -167,6 +310,7 @@ fn transform_object_field_ids(
 ) -> ValidatedSchemaObject {
     let SchemaObject {
         name,
+        name_location,
         server_fields,
         description,
         id,
@@ -174,6 +318,8 @@ fn transform_object_field_ids(
         resolvers,
         id_field,
         directives,
+        valid_refinements,
+        is_input_object,
     } = object;
 
     let validated_encountered_fields = unvalidated_encountered_fields
@@ -207,12 +353,15 @@ fn transform_object_field_ids(
     SchemaObject {
         description,
         name,
+        name_location,
         id,
         server_fields,
         encountered_fields: validated_encountered_fields,
         resolvers,
         id_field,
         directives,
+        valid_refinements,
+        is_input_object,
     }
 }
 
@@ -311,6 +460,9 @@ fn validate_and_transform_field(
                 associated_data: field_type,
                 parent_type_id: empty_field.parent_type_id,
                 arguments: valid_arguments,
+                refinement: empty_field.refinement,
+                is_internal: empty_field.is_internal,
+                deprecation_reason: empty_field.deprecation_reason,
             });
         }
     }
@@ -370,18 +522,39 @@ fn validate_and_transform_resolvers(
     schema_data: &UnvalidatedSchemaData,
     server_fields: &[UnvalidatedSchemaServerField],
 ) -> Result<Vec<ValidatedClientField>, Vec<WithLocation<ValidateSchemaError>>> {
-    get_all_errors_or_all_ok(
-        resolvers
-            .into_iter()
-            .map(|resolver| validate_resolver_fragment(schema_data, resolver, server_fields)),
-    )
+    let variable_definitions_by_client_field: Vec<_> = resolvers
+        .iter()
+        .map(|resolver| resolver.variable_definitions.clone())
+        .collect();
+
+    get_all_errors_or_all_ok(resolvers.into_iter().map(|resolver| {
+        validate_resolver_fragment(
+            schema_data,
+            resolver,
+            server_fields,
+            &variable_definitions_by_client_field,
+        )
+    }))
+}
+
+/// The variables declared by each client field (indexed by ClientFieldId), and by
+/// the client field whose selections are being validated.
+struct ClientFieldVariableDefinitions<'a> {
+    by_client_field: &'a [Vec<WithSpan<UnvalidatedVariableDefinition>>],
+    selecting_client_field: &'a [WithSpan<UnvalidatedVariableDefinition>],
 }
 
 fn validate_resolver_fragment(
     schema_data: &UnvalidatedSchemaData,
     unvalidated_resolver: UnvalidatedClientField,
     server_fields: &[UnvalidatedSchemaServerField],
+    variable_definitions_by_client_field: &[Vec<WithSpan<UnvalidatedVariableDefinition>>],
 ) -> ValidateSchemaResult<ValidatedClientField> {
+    let client_field_variable_definitions = ClientFieldVariableDefinitions {
+        by_client_field: variable_definitions_by_client_field,
+        selecting_client_field: &variable_definitions_by_client_field
+            [unvalidated_resolver.id.as_usize()],
+    };
     let variable_definitions =
         validate_variable_definitions(schema_data, unvalidated_resolver.variable_definitions)?;
 
@@ -393,6 +566,7 @@ fn validate_resolver_fragment(
                 selection_set,
                 parent_object,
                 server_fields,
+                &client_field_variable_definitions,
             )
             .map_err(|err| {
                 validate_selections_error_to_validate_schema_error(
@@ -401,9 +575,17 @@ fn validate_resolver_fragment(
                     unvalidated_resolver.name,
                 )
             })?;
+            validate_variable_usage(
+                &selection_set,
+                client_field_variable_definitions.selecting_client_field,
+                variable_definitions_by_client_field,
+                parent_object.name,
+                unvalidated_resolver.name,
+            )?;
             Ok(ClientField {
                 description: unvalidated_resolver.description,
                 name: unvalidated_resolver.name,
+                name_location: unvalidated_resolver.name_location,
                 id: unvalidated_resolver.id,
                 selection_set_and_unwraps: Some((selection_set, unwraps)),
                 variant: unvalidated_resolver.variant,
@@ -411,11 +593,17 @@ fn validate_resolver_fragment(
                 type_and_field: unvalidated_resolver.type_and_field,
                 parent_object_id: unvalidated_resolver.parent_object_id,
                 action_kind: unvalidated_resolver.action_kind,
+                skip_codegen: unvalidated_resolver.skip_codegen,
+                no_aliases: unvalidated_resolver.no_aliases,
+                fetchable_variant_of: unvalidated_resolver.fetchable_variant_of,
+                // Set once the schema is validated, see merge_client_field_selection_sets
+                merged_selection_set: None,
             })
         }
         None => Ok(ClientField {
             description: unvalidated_resolver.description,
             name: unvalidated_resolver.name,
+            name_location: unvalidated_resolver.name_location,
             id: unvalidated_resolver.id,
             selection_set_and_unwraps: None,
             variant: unvalidated_resolver.variant,
@@ -423,13 +611,130 @@ fn validate_resolver_fragment(
             type_and_field: unvalidated_resolver.type_and_field,
             parent_object_id: unvalidated_resolver.parent_object_id,
             action_kind: unvalidated_resolver.action_kind,
+            skip_codegen: unvalidated_resolver.skip_codegen,
+            no_aliases: unvalidated_resolver.no_aliases,
+            fetchable_variant_of: unvalidated_resolver.fetchable_variant_of,
+            // Set once the schema is validated, see merge_client_field_selection_sets
+            merged_selection_set: None,
         }),
     }
 }
 
+/// Every variable that is used in the selection set of a client field (in the
+/// arguments of fields and directives) must be declared by that client field, and
+/// every variable that it declares must be used, either explicitly, or implicitly by
+/// selecting a client field that declares a variable with the same name (to which
+/// it is forwarded).
+fn validate_variable_usage(
+    selection_set: &[WithSpan<ValidatedSelection>],
+    variable_definitions: &[WithSpan<UnvalidatedVariableDefinition>],
+    variable_definitions_by_client_field: &[Vec<WithSpan<UnvalidatedVariableDefinition>>],
+    client_field_parent_type_name: IsographObjectTypeName,
+    client_field_name: SelectableFieldName,
+) -> ValidateSchemaResult<()> {
+    let mut used_variables = vec![];
+    let mut forwarded_variables = HashSet::new();
+    collect_variable_usages(
+        selection_set,
+        variable_definitions_by_client_field,
+        &mut used_variables,
+        &mut forwarded_variables,
+    );
+
+    for used_variable in used_variables.iter() {
+        if !variable_definitions
+            .iter()
+            .any(|definition| definition.item.name.item == used_variable.item)
+        {
+            return Err(WithLocation::new(
+                ValidateSchemaError::ClientFieldUndefinedVariable {
+                    client_field_parent_type_name,
+                    client_field_name,
+                    variable_name: used_variable.item,
+                },
+                used_variable.location,
+            ));
+        }
+    }
+
+    for definition in variable_definitions {
+        let variable_name = definition.item.name.item;
+        let is_used = used_variables
+            .iter()
+            .any(|used_variable| used_variable.item == variable_name)
+            || forwarded_variables.contains(&variable_name);
+        if !is_used {
+            return Err(WithLocation::new(
+                ValidateSchemaError::ClientFieldUnusedVariable {
+                    client_field_parent_type_name,
+                    client_field_name,
+                    variable_name,
+                },
+                definition.item.name.location,
+            ));
+        }
+    }
+
+    Ok(())
+}
+
+/// Collects the variables that are used in the arguments of the selections (and
+/// their directives), with the locations of those arguments, and the variables that
+/// are implicitly forwarded to the selected client fields, because they declare
+/// them and are not passed them.
+fn collect_variable_usages(
+    selection_set: &[WithSpan<ValidatedSelection>],
+    variable_definitions_by_client_field: &[Vec<WithSpan<UnvalidatedVariableDefinition>>],
+    used_variables: &mut Vec<WithLocation<VariableName>>,
+    forwarded_variables: &mut HashSet<VariableName>,
+) {
+    for selection in selection_set {
+        let server_field = match &selection.item {
+            Selection::ServerField(server_field) => server_field,
+        };
+        let (arguments, directives) = match server_field {
+            ServerFieldSelection::ScalarField(scalar_field) => {
+                if let FieldDefinitionLocation::Client(client_field_id) =
+                    scalar_field.associated_data
+                {
+                    forwarded_variables.extend(
+                        variable_definitions_by_client_field[client_field_id.as_usize()]
+                            .iter()
+                            .map(|definition| definition.item.name.item)
+                            .filter(|variable_name| {
+                                !scalar_field.arguments.iter().any(|argument| {
+                                    argument.item.name.item.lookup() == variable_name.lookup()
+                                })
+                            }),
+                    );
+                }
+                (&scalar_field.arguments, &scalar_field.directives)
+            }
+            ServerFieldSelection::LinkedField(linked_field) => {
+                collect_variable_usages(
+                    &linked_field.selection_set,
+                    variable_definitions_by_client_field,
+                    used_variables,
+                    forwarded_variables,
+                );
+                (&linked_field.arguments, &linked_field.directives)
+            }
+        };
+
+        let directive_arguments = directives
+            .iter()
+            .flat_map(|directive| directive.item.arguments.iter());
+        for argument in arguments.iter().chain(directive_arguments) {
+            for variable_name in argument.item.value.item.reachable_variables() {
+                used_variables.push(WithLocation::new(variable_name, argument.location));
+            }
+        }
+    }
+}
+
 fn validate_variable_definitions(
     schema_data: &UnvalidatedSchemaData,
-    variable_definitions: Vec<WithSpan<VariableDefinition<UnvalidatedTypeName>>>,
+    variable_definitions: Vec<WithSpan<UnvalidatedVariableDefinition>>,
 ) -> ValidateSchemaResult<Vec<WithSpan<ValidatedVariableDefinition>>> {
     variable_definitions
         .into_iter()
@@ -442,6 +747,19 @@ fn validate_variable_definitions(
                     name: vd.name,
                     type_: vd.type_.and_then(|type_name| {
                         match schema_data.defined_types.get(&type_name) {
+                            // Input objects are the only objects that variables can have
+                            Some(SelectableFieldId::Object(object_id))
+                                if !schema_data.object(*object_id).is_input_object =>
+                            {
+                                Err(WithLocation::new(
+                                    ValidateSchemaError::VariableDefinitionInnerTypeIsNotAnInputType {
+                                        variable_name: vd.name.item,
+                                        type_: type_string.clone(),
+                                        inner_type,
+                                    },
+                                    vd.name.location,
+                                ))
+                            }
                             Some(type_id) => Ok(*type_id),
                             None => Err(WithLocation::new(
                                 ValidateSchemaError::VariableDefinitionInnerTypeDoesNotExist {
@@ -478,6 +796,8 @@ fn validate_selections_error_to_validate_schema_error(
             field_name,
             target_type,
             target_type_name,
+            declared_type,
+            field_definition_location,
         } => ValidateSchemaError::ClientFieldSelectionFieldIsNotScalar {
             client_field_parent_type_name: parent_object.name,
             client_field_name: resolver_field_name,
@@ -485,12 +805,16 @@ fn validate_selections_error_to_validate_schema_error(
             field_name,
             field_type: target_type,
             target_type_name,
+            declared_type,
+            field_definition_location,
         },
         ValidateSelectionsError::FieldSelectedAsLinkedButTypeIsScalar {
             field_parent_type_name,
             field_name,
             target_type,
             target_type_name,
+            declared_type,
+            field_definition_location,
         } => ValidateSchemaError::ClientFieldSelectionFieldIsScalar {
             client_field_parent_type_name: parent_object.name,
             client_field_name: resolver_field_name,
@@ -498,6 +822,8 @@ fn validate_selections_error_to_validate_schema_error(
             field_name,
             field_type: target_type,
             target_type_name,
+            declared_type,
+            field_definition_location,
         },
         ValidateSelectionsError::FieldSelectedAsLinkedButTypeIsResolver {
             field_parent_type_name,
@@ -508,11 +834,210 @@ fn validate_selections_error_to_validate_schema_error(
             field_parent_type_name,
             field_name,
         },
+        ValidateSelectionsError::IntrospectionFieldSelected { field_name } => {
+            ValidateSchemaError::ClientFieldSelectionIntrospectionField {
+                client_field_parent_type_name: parent_object.name,
+                client_field_name: resolver_field_name,
+                field_name,
+            }
+        }
+        ValidateSelectionsError::TypenameFieldHasArguments {
+            field_parent_type_name,
+        } => ValidateSchemaError::ClientFieldSelectionTypenameHasArguments {
+            client_field_parent_type_name: parent_object.name,
+            client_field_name: resolver_field_name,
+            field_parent_type_name,
+        },
+        ValidateSelectionsError::RefinementFieldHasArguments {
+            field_parent_type_name,
+            field_name,
+        } => ValidateSchemaError::ClientFieldSelectionRefinementHasArguments {
+            client_field_parent_type_name: parent_object.name,
+            client_field_name: resolver_field_name,
+            field_parent_type_name,
+            field_name,
+        },
+        ValidateSelectionsError::ClientFieldArgumentDoesNotExist {
+            field_parent_type_name,
+            field_name,
+            argument_name,
+        } => ValidateSchemaError::ClientFieldSelectionArgumentDoesNotExist {
+            client_field_parent_type_name: parent_object.name,
+            client_field_name: resolver_field_name,
+            field_parent_type_name,
+            field_name,
+            argument_name,
+        },
+        ValidateSelectionsError::ClientFieldArgumentMissing {
+            field_parent_type_name,
+            field_name,
+            variable_name,
+        } => ValidateSchemaError::ClientFieldSelectionArgumentMissing {
+            client_field_parent_type_name: parent_object.name,
+            client_field_name: resolver_field_name,
+            field_parent_type_name,
+            field_name,
+            variable_name,
+        },
+        ValidateSelectionsError::ClientFieldArgumentHasInvalidValue {
+            field_parent_type_name,
+            field_name,
+            argument_name,
+            argument_type,
+        } => ValidateSchemaError::ClientFieldSelectionArgumentHasInvalidValue {
+            client_field_parent_type_name: parent_object.name,
+            client_field_name: resolver_field_name,
+            field_parent_type_name,
+            field_name,
+            argument_name,
+            argument_type,
+        },
+        ValidateSelectionsError::ServerFieldArgumentDoesNotExist {
+            field_parent_type_name,
+            field_name,
+            argument_name,
+            field_definition_location,
+        } => ValidateSchemaError::ClientFieldSelectionServerFieldArgumentDoesNotExist {
+            client_field_parent_type_name: parent_object.name,
+            client_field_name: resolver_field_name,
+            field_parent_type_name,
+            field_name,
+            argument_name,
+            field_definition_location,
+        },
+        ValidateSelectionsError::ServerFieldArgumentMissing {
+            field_parent_type_name,
+            field_name,
+            argument_name,
+            argument_type,
+        } => ValidateSchemaError::ClientFieldSelectionServerFieldArgumentMissing {
+            client_field_parent_type_name: parent_object.name,
+            client_field_name: resolver_field_name,
+            field_parent_type_name,
+            field_name,
+            argument_name,
+            argument_type,
+        },
+        ValidateSelectionsError::ServerFieldArgumentHasInvalidValue {
+            field_parent_type_name,
+            field_name,
+            argument_name,
+            argument_type,
+        } => ValidateSchemaError::ClientFieldSelectionServerFieldArgumentHasInvalidValue {
+            client_field_parent_type_name: parent_object.name,
+            client_field_name: resolver_field_name,
+            field_parent_type_name,
+            field_name,
+            argument_name,
+            argument_type,
+        },
+        ValidateSelectionsError::ServerFieldArgumentDoesNotMatchPattern {
+            field_parent_type_name,
+            field_name,
+            argument_name,
+            scalar_name,
+            pattern,
+        } => ValidateSchemaError::ClientFieldSelectionServerFieldArgumentDoesNotMatchPattern {
+            client_field_parent_type_name: parent_object.name,
+            client_field_name: resolver_field_name,
+            field_parent_type_name,
+            field_name,
+            argument_name,
+            scalar_name,
+            pattern,
+        },
+        ValidateSelectionsError::StreamOnNonListField {
+            field_parent_type_name,
+            field_name,
+        } => ValidateSchemaError::ClientFieldSelectionStreamOnNonListField {
+            client_field_parent_type_name: parent_object.name,
+            client_field_name: resolver_field_name,
+            field_parent_type_name,
+            field_name,
+        },
+        ValidateSelectionsError::InternalFieldSelected {
+            field_parent_type_name,
+            field_name,
+            field_definition_location,
+        } => ValidateSchemaError::ClientFieldSelectionInternalField {
+            client_field_parent_type_name: parent_object.name,
+            client_field_name: resolver_field_name,
+            field_parent_type_name,
+            field_name,
+            field_definition_location,
+        },
+        ValidateSelectionsError::RenameDirectiveOnAliasedField {
+            field_parent_type_name,
+            field_name,
+        } => ValidateSchemaError::ClientFieldSelectionRenameDirectiveOnAliasedField {
+            client_field_parent_type_name: parent_object.name,
+            client_field_name: resolver_field_name,
+            field_parent_type_name,
+            field_name,
+        },
+        ValidateSelectionsError::InvalidRenameDirective {
+            field_parent_type_name,
+            field_name,
+        } => ValidateSchemaError::ClientFieldSelectionInvalidRenameDirective {
+            client_field_parent_type_name: parent_object.name,
+            client_field_name: resolver_field_name,
+            field_parent_type_name,
+            field_name,
+        },
+        ValidateSelectionsError::ConflictingReaderAliases {
+            field_parent_type_name,
+            name_or_alias,
+            previous_location,
+        } => ValidateSchemaError::ClientFieldSelectionConflictingAliases {
+            client_field_parent_type_name: parent_object.name,
+            client_field_name: resolver_field_name,
+            field_parent_type_name,
+            name_or_alias,
+            previous_location,
+        },
+        ValidateSelectionsError::UnknownSelectionDirective {
+            field_parent_type_name,
+            field_name,
+            directive_name,
+        } => ValidateSchemaError::ClientFieldSelectionUnknownDirective {
+            client_field_parent_type_name: parent_object.name,
+            client_field_name: resolver_field_name,
+            field_parent_type_name,
+            field_name,
+            directive_name,
+        },
+        ValidateSelectionsError::NonLiteralSkipOrIncludeCondition {
+            field_parent_type_name,
+            field_name,
+            directive_name,
+        } => ValidateSchemaError::ClientFieldSelectionNonLiteralSkipOrIncludeCondition {
+            client_field_parent_type_name: parent_object.name,
+            client_field_name: resolver_field_name,
+            field_parent_type_name,
+            field_name,
+            directive_name,
+        },
     })
 }
 
 type ValidateSelectionsResult<T> = Result<T, WithLocation<ValidateSelectionsError>>;
 
+/// __typename is a regular (if magic) server field, but introspection fields like
+/// __schema and __type are not fields on any object, and should not be reported
+/// as merely missing.
+fn validate_not_introspection_field(
+    field_name: SelectableFieldName,
+    location: Location,
+) -> ValidateSelectionsResult<()> {
+    if INTROSPECTION_FIELD_NAMES.contains(&field_name) {
+        return Err(WithLocation::new(
+            ValidateSelectionsError::IntrospectionFieldSelected { field_name },
+            location,
+        ));
+    }
+    Ok(())
+}
+
 #[allow(unused)]
 #[derive(Debug)]
 enum ValidateSelectionsError {
@@ -522,17 +1047,104 @@ enum ValidateSelectionsError {
         field_name: SelectableFieldName,
         target_type: &'static str,
         target_type_name: UnvalidatedTypeName,
+        declared_type: String,
+        field_definition_location: Location,
     },
     FieldSelectedAsLinkedButTypeIsScalar {
         field_parent_type_name: IsographObjectTypeName,
         field_name: SelectableFieldName,
         target_type: &'static str,
         target_type_name: UnvalidatedTypeName,
+        declared_type: String,
+        field_definition_location: Location,
     },
     FieldSelectedAsLinkedButTypeIsResolver {
         field_parent_type_name: IsographObjectTypeName,
         field_name: SelectableFieldName,
     },
+    IntrospectionFieldSelected {
+        field_name: SelectableFieldName,
+    },
+    TypenameFieldHasArguments {
+        field_parent_type_name: IsographObjectTypeName,
+    },
+    RefinementFieldHasArguments {
+        field_parent_type_name: IsographObjectTypeName,
+        field_name: SelectableFieldName,
+    },
+    ClientFieldArgumentDoesNotExist {
+        field_parent_type_name: IsographObjectTypeName,
+        field_name: SelectableFieldName,
+        argument_name: FieldArgumentName,
+    },
+    ClientFieldArgumentMissing {
+        field_parent_type_name: IsographObjectTypeName,
+        field_name: SelectableFieldName,
+        variable_name: VariableName,
+    },
+    ClientFieldArgumentHasInvalidValue {
+        field_parent_type_name: IsographObjectTypeName,
+        field_name: SelectableFieldName,
+        argument_name: FieldArgumentName,
+        argument_type: String,
+    },
+    ServerFieldArgumentDoesNotExist {
+        field_parent_type_name: IsographObjectTypeName,
+        field_name: SelectableFieldName,
+        argument_name: FieldArgumentName,
+        field_definition_location: Location,
+    },
+    ServerFieldArgumentMissing {
+        field_parent_type_name: IsographObjectTypeName,
+        field_name: SelectableFieldName,
+        argument_name: InputValueName,
+        argument_type: String,
+    },
+    ServerFieldArgumentHasInvalidValue {
+        field_parent_type_name: IsographObjectTypeName,
+        field_name: SelectableFieldName,
+        argument_name: FieldArgumentName,
+        argument_type: String,
+    },
+    ServerFieldArgumentDoesNotMatchPattern {
+        field_parent_type_name: IsographObjectTypeName,
+        field_name: SelectableFieldName,
+        argument_name: FieldArgumentName,
+        scalar_name: InputTypeName,
+        pattern: String,
+    },
+    StreamOnNonListField {
+        field_parent_type_name: IsographObjectTypeName,
+        field_name: SelectableFieldName,
+    },
+    InternalFieldSelected {
+        field_parent_type_name: IsographObjectTypeName,
+        field_name: SelectableFieldName,
+        field_definition_location: Location,
+    },
+    RenameDirectiveOnAliasedField {
+        field_parent_type_name: IsographObjectTypeName,
+        field_name: SelectableFieldName,
+    },
+    InvalidRenameDirective {
+        field_parent_type_name: IsographObjectTypeName,
+        field_name: SelectableFieldName,
+    },
+    ConflictingReaderAliases {
+        field_parent_type_name: IsographObjectTypeName,
+        name_or_alias: FieldNameOrAlias,
+        previous_location: Location,
+    },
+    UnknownSelectionDirective {
+        field_parent_type_name: IsographObjectTypeName,
+        field_name: SelectableFieldName,
+        directive_name: IsographDirectiveName,
+    },
+    NonLiteralSkipOrIncludeCondition {
+        field_parent_type_name: IsographObjectTypeName,
+        field_name: SelectableFieldName,
+        directive_name: IsographDirectiveName,
+    },
 }
 
 fn validate_resolver_definition_selections_exist_and_types_match(
@@ -540,11 +1152,9 @@ fn validate_resolver_definition_selections_exist_and_types_match(
     selection_set: Vec<WithSpan<UnvalidatedSelection>>,
     parent_object: &UnvalidatedSchemaObject,
     server_fields: &[UnvalidatedSchemaServerField],
+    variable_definitions: &ClientFieldVariableDefinitions,
 ) -> ValidateSelectionsResult<Vec<WithSpan<ValidatedSelection>>> {
-    // Currently, we only check that each field exists and has an appropriate type, not that
-    // there are no selection conflicts due to aliases or parameters.
-
-    Ok(selection_set
+    let selection_set = selection_set
         .into_iter()
         .map(|selection| {
             validate_resolver_definition_selection_exists_and_type_matches(
@@ -552,9 +1162,64 @@ fn validate_resolver_definition_selections_exist_and_types_match(
                 parent_object,
                 schema_data,
                 server_fields,
+                variable_definitions,
             )
         })
-        .collect::<Result<_, _>>()?)
+        .collect::<Result<Vec<_>, _>>()?;
+    validate_no_conflicting_reader_aliases(parent_object, &selection_set)?;
+    Ok(selection_set)
+}
+
+/// Each selection is read into the property named by its alias (or its name), so
+/// two selections with the same alias must select the same field with the same
+/// arguments. Otherwise, the reader AST would contain two entries for the same key.
+fn validate_no_conflicting_reader_aliases(
+    parent_object: &UnvalidatedSchemaObject,
+    selection_set: &[WithSpan<ValidatedSelection>],
+) -> ValidateSelectionsResult<()> {
+    let mut selections_by_alias: HashMap<FieldNameOrAlias, &ValidatedServerFieldSelection> =
+        HashMap::new();
+    for selection in selection_set {
+        let server_field = match &selection.item {
+            Selection::ServerField(server_field) => server_field,
+        };
+        let name_or_alias = server_field.name_or_alias();
+        match selections_by_alias.get(&name_or_alias.item) {
+            Some(previous_selection) => {
+                if previous_selection.name() != server_field.name()
+                    || sorted_arguments(previous_selection) != sorted_arguments(server_field)
+                {
+                    return Err(WithLocation::new(
+                        ValidateSelectionsError::ConflictingReaderAliases {
+                            field_parent_type_name: parent_object.name,
+                            name_or_alias: name_or_alias.item,
+                            previous_location: previous_selection.name_or_alias().location,
+                        },
+                        name_or_alias.location,
+                    ));
+                }
+            }
+            None => {
+                selections_by_alias.insert(name_or_alias.item, server_field);
+            }
+        }
+    }
+    Ok(())
+}
+
+fn sorted_arguments(
+    server_field: &ValidatedServerFieldSelection,
+) -> Vec<(FieldArgumentName, &NonConstantValue)> {
+    let arguments = match server_field {
+        ServerFieldSelection::ScalarField(scalar_field) => &scalar_field.arguments,
+        ServerFieldSelection::LinkedField(linked_field) => &linked_field.arguments,
+    };
+    let mut arguments = arguments
+        .iter()
+        .map(|argument| (argument.item.name.item, &argument.item.value.item))
+        .collect::<Vec<_>>();
+    arguments.sort();
+    arguments
 }
 
 fn validate_resolver_definition_selection_exists_and_type_matches(
@@ -562,6 +1227,7 @@ fn validate_resolver_definition_selection_exists_and_type_matches(
     parent_object: &UnvalidatedSchemaObject,
     schema_data: &UnvalidatedSchemaData,
     server_fields: &[UnvalidatedSchemaServerField],
+    variable_definitions: &ClientFieldVariableDefinitions,
 ) -> ValidateSelectionsResult<WithSpan<ValidatedSelection>> {
     selection.and_then(|selection| {
         selection.and_then(&mut |field_selection| {
@@ -572,6 +1238,7 @@ fn validate_resolver_definition_selection_exists_and_type_matches(
                         parent_object,
                         scalar_field_selection,
                         server_fields,
+                        variable_definitions,
                     )
                 },
                 &mut |linked_field_selection| {
@@ -580,6 +1247,7 @@ fn validate_resolver_definition_selection_exists_and_type_matches(
                         parent_object,
                         linked_field_selection,
                         server_fields,
+                        variable_definitions,
                     )
                 },
             )
@@ -594,56 +1262,127 @@ fn validate_field_type_exists_and_is_scalar(
     parent_object: &UnvalidatedSchemaObject,
     scalar_field_selection: UnvalidatedScalarFieldSelection,
     server_fields: &[UnvalidatedSchemaServerField],
+    variable_definitions: &ClientFieldVariableDefinitions,
 ) -> ValidateSelectionsResult<ValidatedScalarFieldSelection> {
     let scalar_field_name = scalar_field_selection.name.item.into();
-    match parent_object.encountered_fields.get(&scalar_field_name) {
-        Some(defined_field_type) => match defined_field_type {
-            FieldDefinitionLocation::Server(server_field_name) => {
-                let field_type_id = *schema_data
-                    .defined_types
-                    .get(server_field_name.inner())
+    validate_not_introspection_field(scalar_field_name, scalar_field_selection.name.location)?;
+    validate_selection_directives(
+        parent_object.name,
+        scalar_field_name,
+        &scalar_field_selection.directives,
+        scalar_field_selection.name.location,
+    )?;
+    // Client fields are never lists, so they cannot be streamed either.
+    if has_stream_directive(&scalar_field_selection.directives)
+        && !is_list_field(
+            server_fields,
+            scalar_field_name,
+            &parent_object.server_fields,
+        )
+    {
+        return Err(WithLocation::new(
+            ValidateSelectionsError::StreamOnNonListField {
+                field_parent_type_name: parent_object.name,
+                field_name: scalar_field_name,
+            },
+            scalar_field_selection.name.location,
+        ));
+    }
+    let reader_alias = reader_alias_with_rename_directive(
+        parent_object.name,
+        scalar_field_name,
+        scalar_field_selection.reader_alias,
+        &scalar_field_selection.directives,
+        scalar_field_selection.name.location,
+    )?;
+    if scalar_field_name == *TYPENAME_FIELD_NAME && !scalar_field_selection.arguments.is_empty() {
+        return Err(WithLocation::new(
+            ValidateSelectionsError::TypenameFieldHasArguments {
+                field_parent_type_name: parent_object.name,
+            },
+            scalar_field_selection.name.location,
+        ));
+    }
+    match parent_object.encountered_fields.get(&scalar_field_name) {
+        Some(defined_field_type) => match defined_field_type {
+            FieldDefinitionLocation::Server(server_field_name) => {
+                validate_server_field_is_not_internal(
+                    server_fields,
+                    parent_object,
+                    scalar_field_name,
+                    scalar_field_selection.name.location,
+                )?;
+                let field_type_id = *schema_data
+                    .defined_types
+                    .get(server_field_name.inner())
                     .expect(
                         "Expected field type to be defined, which I think \
                         was validated earlier, probably indicates a bug in Isograph",
                     );
                 match field_type_id {
-                    SelectableFieldId::Scalar(_scalar_id) => Ok(ScalarFieldSelection {
-                        name: scalar_field_selection.name,
-                        associated_data: FieldDefinitionLocation::Server(
-                            find_server_field_id(
+                    SelectableFieldId::Scalar(_scalar_id) => {
+                        let server_field_id = find_server_field_id(
+                            server_fields,
+                            scalar_field_selection.name.item,
+                            &parent_object.server_fields,
+                        )
+                        .expect("Expected to find scalar field, this probably indicates a bug in Isograph");
+                        let arguments = validate_server_field_arguments(
+                            schema_data,
+                            parent_object.name,
+                            &server_fields[server_field_id.as_usize()],
+                            scalar_field_selection.arguments,
+                            variable_definitions.selecting_client_field,
+                            scalar_field_selection.name.location,
+                        )?;
+                        Ok(ScalarFieldSelection {
+                            name: scalar_field_selection.name,
+                            associated_data: FieldDefinitionLocation::Server(server_field_id),
+                            reader_alias,
+                            normalization_alias: scalar_field_selection.normalization_alias,
+                            unwraps: scalar_field_selection.unwraps,
+                            arguments,
+                            directives: scalar_field_selection.directives,
+                            description: scalar_field_selection.description,
+                        })
+                    }
+                    SelectableFieldId::Object(_) => Err(WithLocation::new(
+                        ValidateSelectionsError::FieldSelectedAsScalarButTypeIsNotScalar {
+                            field_parent_type_name: parent_object.name,
+                            field_name: scalar_field_name,
+                            target_type: "an object",
+                            target_type_name: *server_field_name.inner(),
+                            declared_type: server_field_name.to_string(),
+                            field_definition_location: server_field_definition_location(
                                 server_fields,
-                                scalar_field_selection.name.item,
+                                scalar_field_name,
                                 &parent_object.server_fields,
-                            )
-                            .expect("Expected to find scalar field, this probably indicates a bug in Isograph"),
-                        ),
-                        reader_alias: scalar_field_selection.reader_alias,
-                        normalization_alias: scalar_field_selection.normalization_alias,
-                        unwraps: scalar_field_selection.unwraps,
-                        arguments: scalar_field_selection.arguments,
-                    }),
-                    SelectableFieldId::Object(_) => Err(
-                        WithLocation::new(
-                            ValidateSelectionsError::FieldSelectedAsScalarButTypeIsNotScalar {
-                                field_parent_type_name: parent_object.name,
-                                field_name: scalar_field_name,
-                                target_type: "an object",
-                                target_type_name: *server_field_name.inner(),
-                            },
-                            scalar_field_selection.name.location
-                        ),
-                    ),
+                            ),
+                        },
+                        scalar_field_selection.name.location,
+                    )),
                 }
             }
             FieldDefinitionLocation::Client(resolver_field_id) => {
+                validate_client_field_arguments(
+                    schema_data,
+                    parent_object.name,
+                    scalar_field_name,
+                    &scalar_field_selection.arguments,
+                    &variable_definitions.by_client_field[resolver_field_id.as_usize()],
+                    variable_definitions.selecting_client_field,
+                    scalar_field_selection.name.location,
+                )?;
                 // TODO confirm this works if resolver_name is an alias
                 Ok(ScalarFieldSelection {
                     name: scalar_field_selection.name,
-                    reader_alias: scalar_field_selection.reader_alias,
+                    reader_alias,
                     unwraps: scalar_field_selection.unwraps,
                     associated_data: FieldDefinitionLocation::Client(*resolver_field_id),
                     arguments: scalar_field_selection.arguments,
                     normalization_alias: scalar_field_selection.normalization_alias,
+                    directives: scalar_field_selection.directives,
+                    description: scalar_field_selection.description,
                 })
             }
         },
@@ -661,63 +1400,134 @@ fn validate_field_type_exists_and_is_linked(
     parent_object: &UnvalidatedSchemaObject,
     linked_field_selection: UnvalidatedLinkedFieldSelection,
     server_fields: &[UnvalidatedSchemaServerField],
+    variable_definitions: &ClientFieldVariableDefinitions,
 ) -> ValidateSelectionsResult<ValidatedLinkedFieldSelection> {
     let linked_field_name = linked_field_selection.name.item.into();
+    validate_not_introspection_field(linked_field_name, linked_field_selection.name.location)?;
+    validate_selection_directives(
+        parent_object.name,
+        linked_field_name,
+        &linked_field_selection.directives,
+        linked_field_selection.name.location,
+    )?;
+    let reader_alias = reader_alias_with_rename_directive(
+        parent_object.name,
+        linked_field_name,
+        linked_field_selection.reader_alias,
+        &linked_field_selection.directives,
+        linked_field_selection.name.location,
+    )?;
     match (&parent_object.encountered_fields).get(&linked_field_name) {
-        Some(defined_field_type) => {
-            match defined_field_type {
-                FieldDefinitionLocation::Server(server_field_name) => {
-                    let field_type_id = *schema_data
-                        .defined_types
-                        .get(server_field_name.inner())
-                        .expect(
-                            "Expected field type to be defined, which I \
+        Some(defined_field_type) => match defined_field_type {
+            FieldDefinitionLocation::Server(server_field_name) => {
+                validate_server_field_is_not_internal(
+                    server_fields,
+                    parent_object,
+                    linked_field_name,
+                    linked_field_selection.name.location,
+                )?;
+                let field_type_id = *schema_data
+                    .defined_types
+                    .get(server_field_name.inner())
+                    .expect(
+                        "Expected field type to be defined, which I \
                             think was validated earlier, probably indicates a bug in Isograph",
+                    );
+                match field_type_id {
+                    SelectableFieldId::Scalar(_) => Err(WithLocation::new(
+                        ValidateSelectionsError::FieldSelectedAsLinkedButTypeIsScalar {
+                            field_parent_type_name: parent_object.name,
+                            field_name: linked_field_name,
+                            target_type: "a scalar",
+                            target_type_name: *server_field_name.inner(),
+                            declared_type: server_field_name.to_string(),
+                            field_definition_location: server_field_definition_location(
+                                server_fields,
+                                linked_field_name,
+                                &parent_object.server_fields,
+                            ),
+                        },
+                        linked_field_selection.name.location,
+                    )),
+                    SelectableFieldId::Object(object_id) => {
+                        let object = schema_data.objects.get(object_id.as_usize()).unwrap();
+                        let is_refinement = is_refinement_field(
+                            server_fields,
+                            linked_field_name,
+                            &parent_object.server_fields,
                         );
-                    match field_type_id {
-                        SelectableFieldId::Scalar(_) => Err(WithLocation::new(
-                            ValidateSelectionsError::FieldSelectedAsLinkedButTypeIsScalar {
-                                field_parent_type_name: parent_object.name,
-                                field_name: linked_field_name,
-                                target_type: "a scalar",
-                                target_type_name: *server_field_name.inner(),
-                            },
-                            linked_field_selection.name.location,
-                        )),
-                        SelectableFieldId::Object(object_id) => {
-                            let object = schema_data.objects.get(object_id.as_usize()).unwrap();
-                            Ok(LinkedFieldSelection {
-                                name: linked_field_selection.name,
-                                reader_alias: linked_field_selection.reader_alias,
-                                normalization_alias: linked_field_selection.normalization_alias,
-                                selection_set: linked_field_selection.selection_set.into_iter().map(
-                                    |selection| {
-                                        validate_resolver_definition_selection_exists_and_type_matches(
-                                            selection,
-                                            object,
-                                            schema_data,
-                                            server_fields
-                                        )
-                                    },
-                                ).collect::<Result<Vec<_>, _>>()?,
-                                unwraps: linked_field_selection.unwraps,
-                                associated_data: ValidatedLinkedFieldAssociatedData {
-                                    parent_object_id: object_id,
+                        if is_refinement && !linked_field_selection.arguments.is_empty() {
+                            return Err(WithLocation::new(
+                                ValidateSelectionsError::RefinementFieldHasArguments {
+                                    field_parent_type_name: parent_object.name,
+                                    field_name: linked_field_name,
+                                },
+                                linked_field_selection.name.location,
+                            ));
+                        }
+                        let server_field = parent_object
+                            .server_fields
+                            .iter()
+                            .map(|server_field_id| &server_fields[server_field_id.as_usize()])
+                            .find(|server_field| server_field.name.item == linked_field_name);
+                        let arguments = match server_field {
+                            Some(server_field) => validate_server_field_arguments(
+                                schema_data,
+                                parent_object.name,
+                                server_field,
+                                linked_field_selection.arguments,
+                                variable_definitions.selecting_client_field,
+                                linked_field_selection.name.location,
+                            )?,
+                            None => linked_field_selection.arguments,
+                        };
+                        if has_stream_directive(&linked_field_selection.directives)
+                            && !is_list_field(
+                                server_fields,
+                                linked_field_name,
+                                &parent_object.server_fields,
+                            )
+                        {
+                            return Err(WithLocation::new(
+                                ValidateSelectionsError::StreamOnNonListField {
+                                    field_parent_type_name: parent_object.name,
+                                    field_name: linked_field_name,
                                 },
-                                arguments: linked_field_selection.arguments,
-                            })
+                                linked_field_selection.name.location,
+                            ));
                         }
+                        Ok(LinkedFieldSelection {
+                            name: linked_field_selection.name,
+                            reader_alias,
+                            normalization_alias: linked_field_selection.normalization_alias,
+                            selection_set:
+                                validate_resolver_definition_selections_exist_and_types_match(
+                                    schema_data,
+                                    linked_field_selection.selection_set,
+                                    object,
+                                    server_fields,
+                                    variable_definitions,
+                                )?,
+                            unwraps: linked_field_selection.unwraps,
+                            associated_data: ValidatedLinkedFieldAssociatedData {
+                                parent_object_id: object_id,
+                                is_refinement,
+                            },
+                            arguments,
+                            directives: linked_field_selection.directives,
+                            description: linked_field_selection.description,
+                        })
                     }
                 }
-                FieldDefinitionLocation::Client(_) => Err(WithLocation::new(
-                    ValidateSelectionsError::FieldSelectedAsLinkedButTypeIsResolver {
-                        field_parent_type_name: parent_object.name,
-                        field_name: linked_field_name,
-                    },
-                    linked_field_selection.name.location,
-                )),
             }
-        }
+            FieldDefinitionLocation::Client(_) => Err(WithLocation::new(
+                ValidateSelectionsError::FieldSelectedAsLinkedButTypeIsResolver {
+                    field_parent_type_name: parent_object.name,
+                    field_name: linked_field_name,
+                },
+                linked_field_selection.name.location,
+            )),
+        },
         None => Err(WithLocation::new(
             ValidateSelectionsError::FieldDoesNotExist(parent_object.name, linked_field_name),
             linked_field_selection.name.location,
@@ -740,6 +1550,415 @@ fn find_server_field_id(
     })
 }
 
+/// The location of the definition of a server field, so that errors about how the
+/// field is selected can point at its declared type.
+fn server_field_definition_location(
+    server_fields: &[UnvalidatedSchemaServerField],
+    field_name: SelectableFieldName,
+    parent_server_fields: &[ServerFieldId],
+) -> Location {
+    parent_server_fields
+        .iter()
+        .map(|server_field_id| &server_fields[server_field_id.as_usize()])
+        .find(|server_field| server_field.name.item == field_name)
+        .map(|server_field| server_field.name.location)
+        .unwrap_or_else(Location::generated)
+}
+
+/// Arguments passed to a client field must be declared as variables by that client
+/// field. Non-null variables must either be passed, or also be declared by the selecting
+/// client field, in which case they are forwarded implicitly.
+fn validate_client_field_arguments(
+    schema_data: &UnvalidatedSchemaData,
+    field_parent_type_name: IsographObjectTypeName,
+    field_name: SelectableFieldName,
+    arguments: &[WithLocation<SelectionFieldArgument>],
+    client_field_variable_definitions: &[WithSpan<UnvalidatedVariableDefinition>],
+    selecting_client_field_variable_definitions: &[WithSpan<UnvalidatedVariableDefinition>],
+    location: Location,
+) -> ValidateSelectionsResult<()> {
+    for argument in arguments {
+        let argument_name = argument.item.name.item;
+        let definition = client_field_variable_definitions
+            .iter()
+            .find(|definition| definition.item.name.item.lookup() == argument_name.lookup())
+            .ok_or_else(|| {
+                WithLocation::new(
+                    ValidateSelectionsError::ClientFieldArgumentDoesNotExist {
+                        field_parent_type_name,
+                        field_name,
+                        argument_name,
+                    },
+                    argument.location,
+                )
+            })?;
+        // Variables of client fields have no default values
+        if !is_valid_argument_value(
+            schema_data,
+            &argument.item.value.item,
+            &definition.item.type_,
+            false,
+            selecting_client_field_variable_definitions,
+        ) {
+            return Err(WithLocation::new(
+                ValidateSelectionsError::ClientFieldArgumentHasInvalidValue {
+                    field_parent_type_name,
+                    field_name,
+                    argument_name,
+                    argument_type: definition.item.type_.to_string(),
+                },
+                argument.location,
+            ));
+        }
+    }
+
+    for definition in client_field_variable_definitions {
+        let variable_name = definition.item.name.item;
+        let is_required = matches!(definition.item.type_, TypeAnnotation::NonNull(_));
+        let is_passed = arguments
+            .iter()
+            .any(|argument| argument.item.name.item.lookup() == variable_name.lookup());
+        let is_forwarded = selecting_client_field_variable_definitions
+            .iter()
+            .any(|definition| definition.item.name.item == variable_name);
+        if is_required && !is_passed && !is_forwarded {
+            return Err(WithLocation::new(
+                ValidateSelectionsError::ClientFieldArgumentMissing {
+                    field_parent_type_name,
+                    field_name,
+                    variable_name,
+                },
+                location,
+            ));
+        }
+    }
+
+    Ok(())
+}
+
+/// Arguments passed to a server field must be declared by that field in the schema,
+/// and literal values must be valid for the declared type. Arguments of non-null
+/// type without a default value must be passed.
+fn validate_server_field_arguments(
+    schema_data: &UnvalidatedSchemaData,
+    field_parent_type_name: IsographObjectTypeName,
+    server_field: &UnvalidatedSchemaServerField,
+    mut arguments: Vec<WithLocation<SelectionFieldArgument>>,
+    selecting_client_field_variable_definitions: &[WithSpan<UnvalidatedVariableDefinition>],
+    location: Location,
+) -> ValidateSelectionsResult<Vec<WithLocation<SelectionFieldArgument>>> {
+    let field_name = server_field.name.item;
+    for argument in arguments.iter_mut() {
+        let argument_name = argument.item.name.item;
+        let definition = server_field
+            .arguments
+            .iter()
+            .find(|definition| definition.item.name.item.lookup() == argument_name.lookup())
+            .ok_or_else(|| {
+                WithLocation::new(
+                    ValidateSelectionsError::ServerFieldArgumentDoesNotExist {
+                        field_parent_type_name,
+                        field_name,
+                        argument_name,
+                        field_definition_location: server_field.name.location,
+                    },
+                    argument.location,
+                )
+            })?;
+        if !is_valid_argument_value(
+            schema_data,
+            &argument.item.value.item,
+            &definition.item.type_,
+            definition.item.default_value.is_some(),
+            selecting_client_field_variable_definitions,
+        ) {
+            return Err(WithLocation::new(
+                ValidateSelectionsError::ServerFieldArgumentHasInvalidValue {
+                    field_parent_type_name,
+                    field_name,
+                    argument_name,
+                    argument_type: definition.item.type_.to_string(),
+                },
+                argument.location,
+            ));
+        }
+        let scalar_name = *definition.item.type_.inner();
+        canonicalize_custom_scalar_literal(schema_data, &mut argument.item.value.item, scalar_name)
+            .map_err(|pattern| {
+                WithLocation::new(
+                    ValidateSelectionsError::ServerFieldArgumentDoesNotMatchPattern {
+                        field_parent_type_name,
+                        field_name,
+                        argument_name,
+                        scalar_name,
+                        pattern,
+                    },
+                    argument.location,
+                )
+            })?;
+    }
+
+    for definition in server_field.arguments.iter() {
+        let argument_name = definition.item.name.item;
+        let is_required = matches!(definition.item.type_, TypeAnnotation::NonNull(_))
+            && definition.item.default_value.is_none();
+        let is_passed = arguments
+            .iter()
+            .any(|argument| argument.item.name.item.lookup() == argument_name.lookup());
+        if is_required && !is_passed {
+            return Err(WithLocation::new(
+                ValidateSelectionsError::ServerFieldArgumentMissing {
+                    field_parent_type_name,
+                    field_name,
+                    argument_name,
+                    argument_type: definition.item.type_.to_string(),
+                },
+                location,
+            ));
+        }
+    }
+
+    Ok(arguments)
+}
+
+/// Literals are checked against the (innermost) named type of the argument, since a
+/// single value is coerced to a list. Variables declared by the selecting client
+/// field must have the same named type, and be non-null if the argument is non-null
+/// and has no default value. Other variables (e.g. those of refetch queries) are not
+/// checked. The argument is that of a server field, or the variable of a client field.
+fn is_valid_argument_value<TTypeName: Lookup + Copy>(
+    schema_data: &UnvalidatedSchemaData,
+    value: &NonConstantValue,
+    argument_type: &TypeAnnotation<TTypeName>,
+    has_default_value: bool,
+    selecting_client_field_variable_definitions: &[WithSpan<UnvalidatedVariableDefinition>],
+) -> bool {
+    let type_name: InputTypeName = argument_type.inner().lookup().intern().into();
+    match value {
+        NonConstantValue::Variable(variable_name) => {
+            match selecting_client_field_variable_definitions
+                .iter()
+                .find(|definition| definition.item.name.item == *variable_name)
+            {
+                Some(variable_definition) => {
+                    let accepts_null =
+                        !matches!(argument_type, TypeAnnotation::NonNull(_)) || has_default_value;
+                    variable_definition.item.type_.inner().lookup() == type_name.lookup()
+                        && (accepts_null
+                            || matches!(variable_definition.item.type_, TypeAnnotation::NonNull(_)))
+                }
+                None => true,
+            }
+        }
+        NonConstantValue::Integer(_) => match type_name.lookup() {
+            "Int" | "Float" | "ID" => true,
+            "String" | "Boolean" => false,
+            _ => is_custom_scalar(schema_data, type_name),
+        },
+        NonConstantValue::Boolean(_) => match type_name.lookup() {
+            "Boolean" => true,
+            "String" | "ID" | "Int" | "Float" => false,
+            _ => is_custom_scalar(schema_data, type_name),
+        },
+        NonConstantValue::String(_) => match type_name.lookup() {
+            "String" | "ID" => true,
+            "Int" | "Float" | "Boolean" => false,
+            _ => is_custom_scalar(schema_data, type_name),
+        },
+    }
+}
+
+/// Custom scalars may declare in the config how their literals are canonicalized
+/// (e.g. trimmed) and which literals are valid, so that e.g. a malformed DateTime
+/// literal is reported by the compiler rather than by the server. Returns the
+/// pattern if the literal does not match it.
+fn canonicalize_custom_scalar_literal(
+    schema_data: &UnvalidatedSchemaData,
+    value: &mut NonConstantValue,
+    type_name: InputTypeName,
+) -> Result<(), String> {
+    let literals = match schema_data.defined_types.get(&type_name.into()) {
+        Some(SelectableFieldId::Scalar(scalar_id)) => &schema_data.scalar(*scalar_id).literals,
+        _ => return Ok(()),
+    };
+    let literal = match value {
+        NonConstantValue::Variable(_) => return Ok(()),
+        NonConstantValue::String(string) => {
+            if literals.trim {
+                *string = string.lookup().trim().intern();
+            }
+            string.lookup().to_string()
+        }
+        NonConstantValue::Integer(integer) => integer.to_string(),
+        NonConstantValue::Boolean(boolean) => boolean.to_string(),
+    };
+    match &literals.pattern {
+        Some(pattern) if !pattern.is_match(&literal) => Err(pattern.as_str().to_string()),
+        _ => Ok(()),
+    }
+}
+
+/// Custom scalars can be represented by any literal. Enums (which Isograph treats
+/// as scalars) and input objects cannot be written as literals in iso literals, so
+/// they must be passed as variables. Types that are never defined are reported
+/// elsewhere.
+fn is_custom_scalar(schema_data: &UnvalidatedSchemaData, type_name: InputTypeName) -> bool {
+    match schema_data.defined_types.get(&type_name.into()) {
+        Some(SelectableFieldId::Scalar(scalar_id)) => {
+            schema_data.scalar(*scalar_id).enum_values.is_none()
+        }
+        Some(SelectableFieldId::Object(_)) => false,
+        None => true,
+    }
+}
+
+/// Fields annotated with @internal in the schema cannot be selected.
+fn validate_server_field_is_not_internal(
+    server_fields: &[UnvalidatedSchemaServerField],
+    parent_object: &UnvalidatedSchemaObject,
+    field_name: SelectableFieldName,
+    location: Location,
+) -> ValidateSelectionsResult<()> {
+    let internal_server_field = parent_object
+        .server_fields
+        .iter()
+        .map(|server_field_id| &server_fields[server_field_id.as_usize()])
+        .find(|server_field| server_field.name.item == field_name && server_field.is_internal);
+    if let Some(internal_server_field) = internal_server_field {
+        return Err(WithLocation::new(
+            ValidateSelectionsError::InternalFieldSelected {
+                field_parent_type_name: parent_object.name,
+                field_name,
+                field_definition_location: internal_server_field.name.location,
+            },
+            location,
+        ));
+    }
+    Ok(())
+}
+
+fn is_refinement_field(
+    server_fields: &[UnvalidatedSchemaServerField],
+    field_name: SelectableFieldName,
+    parent_server_fields: &[ServerFieldId],
+) -> bool {
+    parent_server_fields.iter().any(|server_field_id| {
+        let server_field = &server_fields[server_field_id.as_usize()];
+        server_field.name.item == field_name && server_field.refinement.is_some()
+    })
+}
+
+fn is_list_field(
+    server_fields: &[UnvalidatedSchemaServerField],
+    field_name: SelectableFieldName,
+    parent_server_fields: &[ServerFieldId],
+) -> bool {
+    parent_server_fields.iter().any(|server_field_id| {
+        let server_field = &server_fields[server_field_id.as_usize()];
+        server_field.name.item == field_name && server_field.associated_data.is_list()
+    })
+}
+
+/// Selections can only be annotated with the directives that the compiler acts on.
+/// In particular, @skip and @include must be passed a literal condition, because
+/// they are applied at compile time (see is_statically_excluded). A condition that
+/// depends on a variable would otherwise be silently ignored, i.e. the field would
+/// always be fetched and read.
+fn validate_selection_directives(
+    field_parent_type_name: IsographObjectTypeName,
+    field_name: SelectableFieldName,
+    directives: &[WithSpan<FragmentDirectiveUsage>],
+    field_location: Location,
+) -> ValidateSelectionsResult<()> {
+    for directive in directives {
+        let directive_name = directive.item.name.item;
+        let location = match field_location {
+            Location::Embedded(embedded_location) => {
+                Location::new(embedded_location.text_source, directive.item.name.span)
+            }
+            Location::Generated => Location::Generated,
+        };
+        if !SELECTION_DIRECTIVE_NAMES.contains(&directive_name.lookup()) {
+            return Err(WithLocation::new(
+                ValidateSelectionsError::UnknownSelectionDirective {
+                    field_parent_type_name,
+                    field_name,
+                    directive_name,
+                },
+                location,
+            ));
+        }
+        if matches!(directive_name.lookup(), "skip" | "include")
+            && directive.item.static_inclusion().is_none()
+        {
+            return Err(WithLocation::new(
+                ValidateSelectionsError::NonLiteralSkipOrIncludeCondition {
+                    field_parent_type_name,
+                    field_name,
+                    directive_name,
+                },
+                location,
+            ));
+        }
+    }
+    Ok(())
+}
+
+/// @stream delivers the items of a list incrementally, so it can only be used
+/// on server fields whose type is a list (of objects or of scalars).
+fn has_stream_directive(directives: &[WithSpan<FragmentDirectiveUsage>]) -> bool {
+    directives
+        .iter()
+        .any(|directive| directive.item.name.item.lookup() == "stream")
+}
+
+/// @rename(to: "...") changes the name of the property that a selection is read
+/// into (and its name in the generated types), like an alias in an iso literal.
+/// The field is still requested from the server under its own name.
+fn reader_alias_with_rename_directive<TAlias: From<StringKey>>(
+    field_parent_type_name: IsographObjectTypeName,
+    field_name: SelectableFieldName,
+    reader_alias: Option<WithLocation<TAlias>>,
+    directives: &[WithSpan<FragmentDirectiveUsage>],
+    location: Location,
+) -> ValidateSelectionsResult<Option<WithLocation<TAlias>>> {
+    let rename_directive = match directives
+        .iter()
+        .find(|directive| directive.item.name.item.lookup() == "rename")
+    {
+        Some(rename_directive) => &rename_directive.item,
+        None => return Ok(reader_alias),
+    };
+    if reader_alias.is_some() {
+        return Err(WithLocation::new(
+            ValidateSelectionsError::RenameDirectiveOnAliasedField {
+                field_parent_type_name,
+                field_name,
+            },
+            location,
+        ));
+    }
+    rename_directive
+        .arguments
+        .iter()
+        .find(|argument| argument.item.name.item.lookup() == "to")
+        .and_then(|argument| match argument.item.value.item {
+            NonConstantValue::String(name) => {
+                Some(Some(WithLocation::new(name.into(), argument.location)))
+            }
+            _ => None,
+        })
+        .ok_or_else(|| {
+            WithLocation::new(
+                ValidateSelectionsError::InvalidRenameDirective {
+                    field_parent_type_name,
+                    field_name,
+                },
+                location,
+            )
+        })
+}
+
 type ValidateSchemaResult<T> = Result<T, WithLocation<ValidateSchemaError>>;
 
 #[derive(Debug, Error)]
@@ -777,8 +1996,12 @@ pub enum ValidateSchemaError {
 
     #[error(
         "In the client field `{client_field_parent_type_name}.{client_field_name}`, \
-        the field `{field_parent_type_name}.{field_name}` is selected as a scalar, \
-        but that field's type is `{target_type_name}`, which is {field_type}."
+        the field `{field_parent_type_name}.{field_name}` is selected without a selection set, \
+        but its type is `{declared_type}`, and `{target_type_name}` is {field_type}. \
+        Fields whose type is an object must be selected with a selection set, \
+        e.g. `{field_name} {{ ... }}`.\n\
+        `{field_parent_type_name}.{field_name}` is declared with type `{declared_type}` here:\n\
+        {field_definition_location}"
     )]
     ClientFieldSelectionFieldIsNotScalar {
         client_field_parent_type_name: IsographObjectTypeName,
@@ -787,12 +2010,17 @@ pub enum ValidateSchemaError {
         field_name: SelectableFieldName,
         field_type: &'static str,
         target_type_name: UnvalidatedTypeName,
+        declared_type: String,
+        field_definition_location: Location,
     },
 
     #[error(
         "In the client field `{client_field_parent_type_name}.{client_field_name}`, \
-        the field `{field_parent_type_name}.{field_name}` is selected as a linked field, \
-        but that field's type is `{target_type_name}`, which is {field_type}."
+        the field `{field_parent_type_name}.{field_name}` is selected with a selection set, \
+        but its type is `{declared_type}`, and `{target_type_name}` is {field_type}, \
+        which has no fields that can be selected.\n\
+        `{field_parent_type_name}.{field_name}` is declared with type `{declared_type}` here:\n\
+        {field_definition_location}"
     )]
     ClientFieldSelectionFieldIsScalar {
         client_field_parent_type_name: IsographObjectTypeName,
@@ -801,6 +2029,8 @@ pub enum ValidateSchemaError {
         field_name: SelectableFieldName,
         field_type: &'static str,
         target_type_name: UnvalidatedTypeName,
+        declared_type: String,
+        field_definition_location: Location,
     },
 
     #[error(
@@ -815,6 +2045,261 @@ pub enum ValidateSchemaError {
         field_name: SelectableFieldName,
     },
 
+    #[error(
+        "In the client field `{client_field_parent_type_name}.{client_field_name}`, \
+        the introspection field `{field_name}` is selected. Introspection fields \
+        cannot be selected in client fields."
+    )]
+    ClientFieldSelectionIntrospectionField {
+        client_field_parent_type_name: IsographObjectTypeName,
+        client_field_name: SelectableFieldName,
+        field_name: SelectableFieldName,
+    },
+
+    #[error(
+        "In the client field `{client_field_parent_type_name}.{client_field_name}`, \
+        the field `{field_parent_type_name}.__typename` is selected with arguments, \
+        but __typename does not accept arguments."
+    )]
+    ClientFieldSelectionTypenameHasArguments {
+        client_field_parent_type_name: IsographObjectTypeName,
+        client_field_name: SelectableFieldName,
+        field_parent_type_name: IsographObjectTypeName,
+    },
+
+    #[error(
+        "In the client field `{client_field_parent_type_name}.{client_field_name}`, \
+        the refinement field `{field_parent_type_name}.{field_name}` is selected with \
+        arguments, but refinement fields do not accept arguments."
+    )]
+    ClientFieldSelectionRefinementHasArguments {
+        client_field_parent_type_name: IsographObjectTypeName,
+        client_field_name: SelectableFieldName,
+        field_parent_type_name: IsographObjectTypeName,
+        field_name: SelectableFieldName,
+    },
+
+    #[error(
+        "In the client field `{client_field_parent_type_name}.{client_field_name}`, \
+        the client field `{field_parent_type_name}.{field_name}` is passed the argument \
+        `{argument_name}`, but `{field_parent_type_name}.{field_name}` does not declare \
+        a variable with that name."
+    )]
+    ClientFieldSelectionArgumentDoesNotExist {
+        client_field_parent_type_name: IsographObjectTypeName,
+        client_field_name: SelectableFieldName,
+        field_parent_type_name: IsographObjectTypeName,
+        field_name: SelectableFieldName,
+        argument_name: FieldArgumentName,
+    },
+
+    #[error(
+        "In the client field `{client_field_parent_type_name}.{client_field_name}`, \
+        the client field `{field_parent_type_name}.{field_name}` is selected without \
+        the required argument `{variable_name}`. Either pass it, or declare the \
+        variable `${variable_name}` on `{client_field_parent_type_name}.{client_field_name}`."
+    )]
+    ClientFieldSelectionArgumentMissing {
+        client_field_parent_type_name: IsographObjectTypeName,
+        client_field_name: SelectableFieldName,
+        field_parent_type_name: IsographObjectTypeName,
+        field_name: SelectableFieldName,
+        variable_name: VariableName,
+    },
+
+    #[error(
+        "In the client field `{client_field_parent_type_name}.{client_field_name}`, \
+        the client field `{field_parent_type_name}.{field_name}` is passed a value for the \
+        argument `{argument_name}` that is not valid for the type of its variable, \
+        `{argument_type}`."
+    )]
+    ClientFieldSelectionArgumentHasInvalidValue {
+        client_field_parent_type_name: IsographObjectTypeName,
+        client_field_name: SelectableFieldName,
+        field_parent_type_name: IsographObjectTypeName,
+        field_name: SelectableFieldName,
+        argument_name: FieldArgumentName,
+        argument_type: String,
+    },
+
+    #[error(
+        "In the client field `{client_field_parent_type_name}.{client_field_name}`, \
+        the field `{field_parent_type_name}.{field_name}` is passed the argument \
+        `{argument_name}`, but `{field_parent_type_name}.{field_name}` does not declare \
+        an argument with that name.\n\
+        The field is defined here:\n\
+        {field_definition_location}"
+    )]
+    ClientFieldSelectionServerFieldArgumentDoesNotExist {
+        client_field_parent_type_name: IsographObjectTypeName,
+        client_field_name: SelectableFieldName,
+        field_parent_type_name: IsographObjectTypeName,
+        field_name: SelectableFieldName,
+        argument_name: FieldArgumentName,
+        field_definition_location: Location,
+    },
+
+    #[error(
+        "In the client field `{client_field_parent_type_name}.{client_field_name}`, \
+        the field `{field_parent_type_name}.{field_name}` is selected without the \
+        required argument `{argument_name}` (of type `{argument_type}`)."
+    )]
+    ClientFieldSelectionServerFieldArgumentMissing {
+        client_field_parent_type_name: IsographObjectTypeName,
+        client_field_name: SelectableFieldName,
+        field_parent_type_name: IsographObjectTypeName,
+        field_name: SelectableFieldName,
+        argument_name: InputValueName,
+        argument_type: String,
+    },
+
+    #[error(
+        "In the client field `{client_field_parent_type_name}.{client_field_name}`, \
+        the field `{field_parent_type_name}.{field_name}` is passed a value for the \
+        argument `{argument_name}` that is not valid for its type, `{argument_type}`."
+    )]
+    ClientFieldSelectionServerFieldArgumentHasInvalidValue {
+        client_field_parent_type_name: IsographObjectTypeName,
+        client_field_name: SelectableFieldName,
+        field_parent_type_name: IsographObjectTypeName,
+        field_name: SelectableFieldName,
+        argument_name: FieldArgumentName,
+        argument_type: String,
+    },
+
+    #[error(
+        "In the client field `{client_field_parent_type_name}.{client_field_name}`, \
+        the field `{field_parent_type_name}.{field_name}` is passed a literal for the \
+        argument `{argument_name}` that does not match the pattern `{pattern}`, which \
+        the config requires of `{scalar_name}` literals."
+    )]
+    ClientFieldSelectionServerFieldArgumentDoesNotMatchPattern {
+        client_field_parent_type_name: IsographObjectTypeName,
+        client_field_name: SelectableFieldName,
+        field_parent_type_name: IsographObjectTypeName,
+        field_name: SelectableFieldName,
+        argument_name: FieldArgumentName,
+        scalar_name: InputTypeName,
+        pattern: String,
+    },
+
+    #[error(
+        "In the client field `{client_field_parent_type_name}.{client_field_name}`, \
+        the field `{field_parent_type_name}.{field_name}` is selected with @stream, \
+        but @stream can only be used on fields whose type is a list."
+    )]
+    ClientFieldSelectionStreamOnNonListField {
+        client_field_parent_type_name: IsographObjectTypeName,
+        client_field_name: SelectableFieldName,
+        field_parent_type_name: IsographObjectTypeName,
+        field_name: SelectableFieldName,
+    },
+
+    #[error(
+        "In the client field `{client_field_parent_type_name}.{client_field_name}`, the \
+        field `{field_parent_type_name}.{field_name}` is selected, but that field is \
+        annotated with @internal. Internal fields remain in the schema for other tooling, \
+        but cannot be selected in iso literals.\n\
+        `{field_parent_type_name}.{field_name}` is declared here:\n\
+        {field_definition_location}"
+    )]
+    ClientFieldSelectionInternalField {
+        client_field_parent_type_name: IsographObjectTypeName,
+        client_field_name: SelectableFieldName,
+        field_parent_type_name: IsographObjectTypeName,
+        field_name: SelectableFieldName,
+        field_definition_location: Location,
+    },
+
+    #[error(
+        "In the client field `{client_field_parent_type_name}.{client_field_name}`, the \
+        field `{field_parent_type_name}.{field_name}` is both aliased and selected with \
+        @rename. Use one or the other."
+    )]
+    ClientFieldSelectionRenameDirectiveOnAliasedField {
+        client_field_parent_type_name: IsographObjectTypeName,
+        client_field_name: SelectableFieldName,
+        field_parent_type_name: IsographObjectTypeName,
+        field_name: SelectableFieldName,
+    },
+
+    #[error(
+        "In the client field `{client_field_parent_type_name}.{client_field_name}`, the \
+        field `{field_parent_type_name}.{field_name}` is selected with @rename, but @rename \
+        must be passed the new name as a string, e.g. @rename(to: \"newName\")."
+    )]
+    ClientFieldSelectionInvalidRenameDirective {
+        client_field_parent_type_name: IsographObjectTypeName,
+        client_field_name: SelectableFieldName,
+        field_parent_type_name: IsographObjectTypeName,
+        field_name: SelectableFieldName,
+    },
+
+    #[error(
+        "In the client field `{client_field_parent_type_name}.{client_field_name}`, the \
+        field `{field_parent_type_name}.{field_name}` is selected with @{directive_name}, \
+        but that directive does not exist. Selections can be annotated with @skip, @include, \
+        @defer, @stream and @rename."
+    )]
+    ClientFieldSelectionUnknownDirective {
+        client_field_parent_type_name: IsographObjectTypeName,
+        client_field_name: SelectableFieldName,
+        field_parent_type_name: IsographObjectTypeName,
+        field_name: SelectableFieldName,
+        directive_name: IsographDirectiveName,
+    },
+
+    #[error(
+        "In the client field `{client_field_parent_type_name}.{client_field_name}`, the \
+        field `{field_parent_type_name}.{field_name}` is selected with @{directive_name}, \
+        but the `if` argument of @{directive_name} must be a literal boolean, e.g. \
+        @{directive_name}(if: true). Conditions that depend on variables are not supported."
+    )]
+    ClientFieldSelectionNonLiteralSkipOrIncludeCondition {
+        client_field_parent_type_name: IsographObjectTypeName,
+        client_field_name: SelectableFieldName,
+        field_parent_type_name: IsographObjectTypeName,
+        field_name: SelectableFieldName,
+        directive_name: IsographDirectiveName,
+    },
+
+    #[error(
+        "In the client field `{client_field_parent_type_name}.{client_field_name}`, \
+        `{name_or_alias}` is used as the name (or alias) of two different selections on \
+        `{field_parent_type_name}`. Selections with the same name or alias must select the \
+        same field with the same arguments.\n\
+        The other selection is here:\n\
+        {previous_location}"
+    )]
+    ClientFieldSelectionConflictingAliases {
+        client_field_parent_type_name: IsographObjectTypeName,
+        client_field_name: SelectableFieldName,
+        field_parent_type_name: IsographObjectTypeName,
+        name_or_alias: FieldNameOrAlias,
+        previous_location: Location,
+    },
+
+    #[error(
+        "The client field `{client_field_parent_type_name}.{client_field_name}` uses \
+        the variable `${variable_name}`, but does not declare it."
+    )]
+    ClientFieldUndefinedVariable {
+        client_field_parent_type_name: IsographObjectTypeName,
+        client_field_name: SelectableFieldName,
+        variable_name: VariableName,
+    },
+
+    #[error(
+        "The client field `{client_field_parent_type_name}.{client_field_name}` declares \
+        the variable `${variable_name}`, but never uses it (or forwards it to a client \
+        field that it selects)."
+    )]
+    ClientFieldUnusedVariable {
+        client_field_parent_type_name: IsographObjectTypeName,
+        client_field_name: SelectableFieldName,
+        variable_name: VariableName,
+    },
+
     #[error(
         "The variable `{variable_name}` has type `{type_}`, but the inner type \
         `{inner_type}` does not exist."
@@ -825,6 +2310,42 @@ pub enum ValidateSchemaError {
         inner_type: UnvalidatedTypeName,
     },
 
+    #[error(
+        "The variable `{variable_name}` has type `{type_}`, but the inner type \
+        `{inner_type}` is an object type. Variables must have scalar, enum or \
+        input object types."
+    )]
+    VariableDefinitionInnerTypeIsNotAnInputType {
+        variable_name: VariableName,
+        type_: String,
+        inner_type: UnvalidatedTypeName,
+    },
+
+    #[error(
+        "The client field `{client_field_parent_type_name}.{fetchable_variant_of}` is \
+        declared with @fetchable(name: \"{client_field_name}\"), but \
+        `{client_field_parent_type_name}.{fetchable_variant_of}` is not declared without \
+        @fetchable. A variant must be declared with the name of the client field that it \
+        is a variant of."
+    )]
+    FetchableVariantOfUnknownClientField {
+        client_field_parent_type_name: IsographObjectTypeName,
+        client_field_name: SelectableFieldName,
+        fetchable_variant_of: SelectableFieldName,
+    },
+
+    #[error(
+        "In the client field `{client_field_parent_type_name}.{client_field_name}`, \
+        the client field `{field_parent_type_name}.{field_name}` is selected, but it is \
+        annotated with @skipCodegen, so no artifacts are generated for it."
+    )]
+    SkippedClientFieldSelected {
+        client_field_parent_type_name: IsographObjectTypeName,
+        client_field_name: SelectableFieldName,
+        field_parent_type_name: IsographObjectTypeName,
+        field_name: SelectableFieldName,
+    },
+
     #[error("Error when validating iso entrypoint calls.\nMessage: {message}")]
     ErrorValidatingEntrypointDeclaration {
         message: ValidateEntrypointDeclarationError,
@@ -844,3 +2365,243 @@ pub fn refetched_paths_for_resolver(
     paths.sort();
     paths
 }
+
+#[cfg(test)]
+mod test {
+    use common_lang_types::{EmbeddedLocation, Location, TextSource, WithLocation};
+    use graphql_schema_parser::parse_schema;
+    use intern::{string_key::Intern, Lookup};
+    use isograph_config::ConfigOptions;
+    use isograph_lang_parser::{parse_iso_literal, IsoLiteralExtractionResult};
+
+    use crate::{Schema, UnvalidatedSchema};
+
+    use super::ValidateSchemaError;
+
+    static SCHEMA: &str = "type Query { me: User }\n\
+        type User {\n  \
+        id: ID!\n  \
+        name: String\n  \
+        nickname: String\n  \
+        avatar_url(size: Int): String\n  \
+        thumbnail_url(size: Int!): String\n\
+        }";
+
+    /// Processes the iso literal against SCHEMA, and returns the validation errors.
+    fn validation_errors(iso_literal_text: &str) -> Vec<WithLocation<ValidateSchemaError>> {
+        let schema_text_source = TextSource {
+            path: "/path/to/schema.graphql".intern().into(),
+            span: None,
+        };
+        let type_system_document =
+            parse_schema(SCHEMA, schema_text_source).expect("Expected the schema to parse");
+        let mut schema = UnvalidatedSchema::new();
+        let outcome = schema
+            .process_graphql_type_system_document(type_system_document, ConfigOptions::default())
+            .expect("Expected the schema to be processed");
+
+        let text_source = TextSource {
+            path: "/path/to/Component.tsx".intern().into(),
+            span: None,
+        };
+        let client_field_declaration = match parse_iso_literal(
+            iso_literal_text,
+            "/path/to/Component.tsx".intern().into(),
+            None,
+            false,
+            text_source,
+        ) {
+            Ok(IsoLiteralExtractionResult::ClientFieldDeclaration(client_field_declaration)) => {
+                client_field_declaration
+            }
+            _ => panic!("Expected a client field declaration"),
+        };
+        schema
+            .process_client_field_declaration(client_field_declaration, text_source, None)
+            .expect("Expected the client field declaration to be processed");
+        schema
+            .add_fields_to_subtypes(&outcome.type_refinement_maps.supertype_to_subtype_map)
+            .expect("Expected the fields of supertypes to be added");
+
+        Schema::validate_and_construct(schema)
+            .err()
+            .unwrap_or_default()
+    }
+
+    /// The single validation error, and the text of the iso literal that it and its
+    /// previous_location (if any) point at.
+    fn validation_error(iso_literal_text: &str) -> (ValidateSchemaError, &str) {
+        let mut errors = validation_errors(iso_literal_text);
+        assert_eq!(errors.len(), 1, "{errors:?}");
+        let error = errors.remove(0);
+        (error.item, location_text(iso_literal_text, error.location))
+    }
+
+    fn location_text(iso_literal_text: &str, location: Location) -> &str {
+        match location {
+            Location::Embedded(EmbeddedLocation { span, .. }) => {
+                &iso_literal_text[span.start as usize..span.end as usize]
+            }
+            Location::Generated => panic!("Expected the location to point at the iso literal"),
+        }
+    }
+
+    #[test]
+    fn conflicting_reader_aliases() {
+        let iso_literal_text = "field User.Profile {\n  \
+            name\n  \
+            nickname @rename(to: \"name\")\n\
+            }";
+        let (error, text) = validation_error(iso_literal_text);
+        match error {
+            ValidateSchemaError::ClientFieldSelectionConflictingAliases {
+                client_field_name,
+                name_or_alias,
+                previous_location,
+                ..
+            } => {
+                assert_eq!(client_field_name.lookup(), "Profile");
+                assert_eq!(name_or_alias.lookup(), "name");
+                assert_eq!(location_text(iso_literal_text, previous_location), "name");
+            }
+            error => panic!("Expected conflicting aliases, got {error:?}"),
+        }
+        // The error points at the argument of the rename directive of the second
+        // selection
+        assert_eq!(text, "to: \"name\"");
+    }
+
+    #[test]
+    fn conflicting_reader_aliases_with_different_arguments() {
+        let (error, _) = validation_error(
+            "field User.Profile {\n  \
+            avatar_url(size: 48) @rename(to: \"avatar\")\n  \
+            avatar: avatar_url(size: 24)\n\
+            }",
+        );
+        assert!(
+            matches!(
+                error,
+                ValidateSchemaError::ClientFieldSelectionConflictingAliases { .. }
+            ),
+            "{error:?}"
+        );
+    }
+
+    #[test]
+    fn same_field_read_into_the_same_key() {
+        let errors = validation_errors(
+            "field User.Profile {\n  \
+            name @rename(to: \"displayName\")\n  \
+            displayName: name\n\
+            }",
+        );
+        assert!(errors.is_empty(), "{errors:?}");
+    }
+
+    #[test]
+    fn valid_server_field_arguments() {
+        let errors = validation_errors(
+            "field User.Avatar($size: Int!) {\n  \
+            avatar_url(size: $size)\n  \
+            thumbnail_url(size: 48)\n\
+            }",
+        );
+        assert!(errors.is_empty(), "{errors:?}");
+    }
+
+    #[test]
+    fn server_field_argument_does_not_exist() {
+        let (error, text) = validation_error(
+            "field User.Avatar {\n  \
+            avatar_url(sizee: 48)\n\
+            }",
+        );
+        match error {
+            ValidateSchemaError::ClientFieldSelectionServerFieldArgumentDoesNotExist {
+                field_name,
+                argument_name,
+                ..
+            } => {
+                assert_eq!(field_name.lookup(), "avatar_url");
+                assert_eq!(argument_name.lookup(), "sizee");
+            }
+            error => panic!("Expected an argument that does not exist, got {error:?}"),
+        }
+        assert_eq!(text, "sizee: 48");
+    }
+
+    #[test]
+    fn server_field_argument_missing() {
+        let (error, _) = validation_error(
+            "field User.Avatar {\n  \
+            thumbnail_url\n\
+            }",
+        );
+        match error {
+            ValidateSchemaError::ClientFieldSelectionServerFieldArgumentMissing {
+                field_name,
+                argument_name,
+                argument_type,
+                ..
+            } => {
+                assert_eq!(field_name.lookup(), "thumbnail_url");
+                assert_eq!(argument_name.lookup(), "size");
+                assert_eq!(argument_type, "Int!");
+            }
+            error => panic!("Expected a missing argument, got {error:?}"),
+        }
+    }
+
+    #[test]
+    fn server_field_argument_has_invalid_literal() {
+        let (error, text) = validation_error(
+            "field User.Avatar {\n  \
+            avatar_url(size: \"large\")\n\
+            }",
+        );
+        match error {
+            ValidateSchemaError::ClientFieldSelectionServerFieldArgumentHasInvalidValue {
+                argument_name,
+                argument_type,
+                ..
+            } => {
+                assert_eq!(argument_name.lookup(), "size");
+                assert_eq!(argument_type, "Int");
+            }
+            error => panic!("Expected an invalid argument value, got {error:?}"),
+        }
+        assert_eq!(text, "size: \"large\"");
+    }
+
+    #[test]
+    fn server_field_argument_has_invalid_variable() {
+        // The variable has a different type
+        let (error, _) = validation_error(
+            "field User.Avatar($size: String) {\n  \
+            avatar_url(size: $size)\n\
+            }",
+        );
+        assert!(
+            matches!(
+                error,
+                ValidateSchemaError::ClientFieldSelectionServerFieldArgumentHasInvalidValue { .. }
+            ),
+            "{error:?}"
+        );
+
+        // The variable is nullable, but the argument is not
+        let (error, _) = validation_error(
+            "field User.Avatar($size: Int) {\n  \
+            thumbnail_url(size: $size)\n\
+            }",
+        );
+        assert!(
+            matches!(
+                error,
+                ValidateSchemaError::ClientFieldSelectionServerFieldArgumentHasInvalidValue { .. }
+            ),
+            "{error:?}"
+        );
+    }
+}
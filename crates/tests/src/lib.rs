@@ -1 +1,75 @@
+//! Helpers for the integration tests in tests/, which compile copies of the fixture
+//! projects in tests/fixtures.
 
+use std::{
+    error::Error,
+    fs,
+    path::{Path, PathBuf},
+};
+
+use isograph_compiler::{create_config, handle_compile_command, CompilerConfig};
+
+/// The config of most fixture projects: the schema is next to the config, the iso
+/// literals are in src/, and the artifacts are generated next to the config.
+pub static DEFAULT_CONFIG: &str = r#"{
+  "project_root": "./src",
+  "artifact_directory": "./",
+  "schema": "./schema.graphql"
+}"#;
+
+/// Copies the given files of the fixture project in fixture_directory into a fresh
+/// directory, so that compiling it does not write into the source tree, and writes
+/// config next to them. Returns the path to that config.
+///
+/// Each file is a pair of paths, relative to fixture_directory and to the fresh
+/// directory. Tests run concurrently, so each test must pass a different name.
+pub fn copy_fixture_project(
+    name: &str,
+    fixture_directory: &str,
+    files: &[(&str, &str)],
+    config: &str,
+) -> Result<PathBuf, Box<dyn Error>> {
+    let project_directory =
+        std::env::temp_dir().join(format!("isograph_{name}_{}", std::process::id()));
+    if project_directory.exists() {
+        fs::remove_dir_all(&project_directory)?;
+    }
+    fs::create_dir_all(project_directory.join("src"))?;
+    for (fixture_file, project_file) in files {
+        let project_file = project_directory.join(project_file);
+        if let Some(parent_directory) = project_file.parent() {
+            fs::create_dir_all(parent_directory)?;
+        }
+        fs::copy(
+            Path::new(fixture_directory).join(fixture_file),
+            project_file,
+        )?;
+    }
+
+    let config_path = project_directory.join("isograph.config.json");
+    fs::write(&config_path, config)?;
+    Ok(config_path)
+}
+
+/// The directory into which copy_fixture_project copied the project, which tests
+/// remove when they are done with it.
+pub fn project_directory(config: &CompilerConfig) -> PathBuf {
+    config
+        .project_root
+        .parent()
+        .expect("Expected project root to have a parent")
+        .to_path_buf()
+}
+
+/// Compiles the project, which is expected to be invalid, removes it, and returns
+/// the error.
+pub fn compile_error(config_path: PathBuf) -> Result<String, Box<dyn Error>> {
+    let config = create_config(config_path);
+    let result = handle_compile_command(&config, &[]).item;
+    let project_directory = project_directory(&config);
+    fs::remove_dir_all(&project_directory)?;
+    match result {
+        Ok(_) => panic!("Expected the project in {project_directory:?} to be invalid"),
+        Err(error) => Ok(error.to_string()),
+    }
+}
@@ -0,0 +1,81 @@
+use std::{
+    error::Error,
+    fs,
+    path::{Path, PathBuf},
+};
+
+use isograph_compiler::{create_config, handle_compile_command};
+use tests::project_directory;
+
+static FIXTURE_DIRECTORY: &str = concat!(
+    env!("CARGO_MANIFEST_DIR"),
+    "/tests/fixtures/portable_project"
+);
+
+/// Copies the fixture project into a fresh directory, and returns the path to its
+/// config.
+fn copy_fixture_project(name: &str) -> Result<PathBuf, Box<dyn Error>> {
+    tests::copy_fixture_project(
+        name,
+        FIXTURE_DIRECTORY,
+        &[
+            ("schema.graphql", "schema.graphql"),
+            ("src/HomeRoute.tsx", "src/HomeRoute.tsx"),
+        ],
+        r#"{
+  "project_root": "./src",
+  "artifact_directory": "./",
+  "schema": "./schema.graphql",
+  "build_report": "./build/isograph-report.json",
+  "options": {
+    "generate_data_requirements": true,
+    "include_complexity_comments": true
+  }
+}"#,
+    )
+}
+
+fn read_files_recursively(directory: &Path, files: &mut Vec<(PathBuf, String)>) {
+    for entry in fs::read_dir(directory).expect("Expected artifact directory to be readable") {
+        let path = entry.expect("Expected directory entry").path();
+        if path.is_dir() {
+            read_files_recursively(&path, files);
+        } else {
+            let contents = fs::read_to_string(&path).expect("Expected artifact to be readable");
+            files.push((path, contents));
+        }
+    }
+}
+
+#[test]
+fn test_artifacts_contain_no_absolute_paths() -> Result<(), Box<dyn Error>> {
+    let config_path = copy_fixture_project("artifact_portability")?;
+    let config = create_config(config_path);
+    let project_directory = project_directory(&config);
+
+    handle_compile_command(&config, &[]).item?;
+
+    let mut artifacts = vec![];
+    read_files_recursively(&config.artifact_directory, &mut artifacts);
+    assert!(!artifacts.is_empty());
+
+    let project_directory_str = project_directory.to_string_lossy();
+    for (path, contents) in &artifacts {
+        assert!(
+            !contents.contains(project_directory_str.as_ref()),
+            "The artifact at {path:?} contains the absolute path of the project"
+        );
+    }
+
+    // The absolute paths of the inputs belong in the build report instead.
+    let build_report = fs::read_to_string(
+        config
+            .build_report
+            .as_ref()
+            .expect("Expected build report to be configured"),
+    )?;
+    assert!(build_report.contains(project_directory_str.as_ref()));
+
+    fs::remove_dir_all(&project_directory)?;
+    Ok(())
+}
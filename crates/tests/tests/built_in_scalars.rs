@@ -0,0 +1,49 @@
+use std::{error::Error, fs, path::PathBuf};
+
+use isograph_compiler::{create_config, handle_compile_command};
+use tests::{project_directory, DEFAULT_CONFIG};
+
+static FIXTURE_DIRECTORY: &str = concat!(
+    env!("CARGO_MANIFEST_DIR"),
+    "/tests/fixtures/built_in_scalars_project"
+);
+
+/// Copies the fixture project into a fresh directory, and returns the path to its
+/// config.
+fn copy_fixture_project(name: &str) -> Result<PathBuf, Box<dyn Error>> {
+    tests::copy_fixture_project(
+        name,
+        FIXTURE_DIRECTORY,
+        &[
+            ("schema.graphql", "schema.graphql"),
+            ("src/PetSummary.tsx", "src/PetSummary.tsx"),
+        ],
+        DEFAULT_CONFIG,
+    )
+}
+
+#[test]
+fn test_built_in_scalars_have_javascript_types() -> Result<(), Box<dyn Error>> {
+    let config_path = copy_fixture_project("built_in_scalars")?;
+    let config = create_config(config_path);
+    let project_directory = project_directory(&config);
+
+    handle_compile_command(&config, &[]).item?;
+
+    let reader = fs::read_to_string(config.artifact_directory.join("Pet/PetSummary/reader.ts"))?;
+    fs::remove_dir_all(&project_directory)?;
+
+    for property in [
+        "  id: string,\n",
+        "  name: (string | null),\n",
+        "  age: number,\n",
+        "  weight: (number | null),\n",
+        "  is_adopted: boolean,\n",
+    ] {
+        assert!(
+            reader.contains(property),
+            "Expected the reader artifact to contain {property:?}, but it was:\n{reader}"
+        );
+    }
+    Ok(())
+}
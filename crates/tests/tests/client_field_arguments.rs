@@ -0,0 +1,86 @@
+use std::{error::Error, fs, path::PathBuf};
+
+use isograph_compiler::{create_config, handle_compile_command};
+use tests::{compile_error, project_directory, DEFAULT_CONFIG};
+
+static FIXTURE_DIRECTORY: &str = concat!(
+    env!("CARGO_MANIFEST_DIR"),
+    "/tests/fixtures/client_field_arguments_project"
+);
+
+/// Copies the schema, the Avatar client field and the source file in the given
+/// directory of the fixture project into a fresh directory, and returns the path to
+/// its config.
+fn copy_fixture_project(name: &str, source_directory: &str) -> Result<PathBuf, Box<dyn Error>> {
+    let source_file = format!("{source_directory}/UserProfile.tsx");
+    tests::copy_fixture_project(
+        name,
+        FIXTURE_DIRECTORY,
+        &[
+            ("schema.graphql", "schema.graphql"),
+            ("Avatar.tsx", "src/Avatar.tsx"),
+            (&source_file, "src/UserProfile.tsx"),
+        ],
+        DEFAULT_CONFIG,
+    )
+}
+
+#[test]
+fn test_literal_of_matching_type_is_accepted() -> Result<(), Box<dyn Error>> {
+    let config = create_config(copy_fixture_project(
+        "client_field_arguments_valid",
+        "valid",
+    )?);
+    let result = handle_compile_command(&config, &[]).item;
+    fs::remove_dir_all(project_directory(&config))?;
+    result?;
+    Ok(())
+}
+
+#[test]
+fn test_literal_of_wrong_type_is_rejected() -> Result<(), Box<dyn Error>> {
+    let error = compile_error(copy_fixture_project(
+        "client_field_arguments_literal_of_wrong_type",
+        "literal_of_wrong_type",
+    )?)?;
+    assert!(
+        error.contains(
+            "the client field `User.Avatar` is passed a value for the argument `size` that \
+            is not valid for the type of its variable, `Int!`"
+        ),
+        "{error}"
+    );
+    Ok(())
+}
+
+#[test]
+fn test_variable_of_wrong_type_is_rejected() -> Result<(), Box<dyn Error>> {
+    let error = compile_error(copy_fixture_project(
+        "client_field_arguments_variable_of_wrong_type",
+        "variable_of_wrong_type",
+    )?)?;
+    assert!(
+        error.contains(
+            "the client field `User.Avatar` is passed a value for the argument `size` that \
+            is not valid for the type of its variable, `Int!`"
+        ),
+        "{error}"
+    );
+    Ok(())
+}
+
+#[test]
+fn test_nullable_variable_for_non_null_variable_is_rejected() -> Result<(), Box<dyn Error>> {
+    let error = compile_error(copy_fixture_project(
+        "client_field_arguments_nullable_variable",
+        "nullable_variable",
+    )?)?;
+    assert!(
+        error.contains(
+            "the client field `User.Avatar` is passed a value for the argument `size` that \
+            is not valid for the type of its variable, `Int!`"
+        ),
+        "{error}"
+    );
+    Ok(())
+}
@@ -0,0 +1,111 @@
+use std::{error::Error, fs, path::PathBuf};
+
+use isograph_compiler::{compile_with_cache, create_config};
+use tests::{project_directory, DEFAULT_CONFIG};
+
+static FIXTURE_DIRECTORY: &str = concat!(
+    env!("CARGO_MANIFEST_DIR"),
+    "/tests/fixtures/compilation_cache_project"
+);
+
+static FIXTURE_FILES: [&str; 4] = [
+    "schema.graphql",
+    "src/HomeRoute.tsx",
+    "src/ProfileRoute.tsx",
+    "src/UserName.tsx",
+];
+
+/// Copies the fixture project into a fresh directory, and returns the path to its
+/// config.
+fn copy_fixture_project(name: &str) -> Result<PathBuf, Box<dyn Error>> {
+    tests::copy_fixture_project(
+        name,
+        FIXTURE_DIRECTORY,
+        &FIXTURE_FILES.map(|fixture_file| (fixture_file, fixture_file)),
+        DEFAULT_CONFIG,
+    )
+}
+
+#[test]
+fn test_only_artifacts_of_edited_file_are_rewritten() -> Result<(), Box<dyn Error>> {
+    let config = create_config(copy_fixture_project("compilation_cache")?);
+    let project_directory = project_directory(&config);
+
+    let first_compilation = compile_with_cache(&config)?;
+    // Nothing changed, so the second compilation is skipped.
+    let second_compilation = compile_with_cache(&config)?;
+
+    let profile_route = config.project_root.join("ProfileRoute.tsx");
+    fs::write(
+        &profile_route,
+        fs::read_to_string(&profile_route)?.replace("      name\n", "      name\n      bio\n"),
+    )?;
+    let third_compilation = compile_with_cache(&config)?;
+    let user_name_reader_exists = config
+        .artifact_directory
+        .join("User/UserName/reader.ts")
+        .exists();
+    fs::remove_dir_all(&project_directory)?;
+
+    assert!(first_compilation.is_some());
+    assert!(second_compilation.is_none());
+    let stats = third_compilation.expect("Expected the edited project to be compiled");
+
+    // Only the artifacts of ProfileRoute and of the entrypoints are regenerated, and
+    // of those, only the artifacts of ProfileRoute changed, so only those are
+    // written. The reader artifact of UserName, which is unaffected by the edit, is
+    // neither regenerated nor deleted.
+    let profile_route_artifacts = config.artifact_directory.join("Query/ProfileRoute");
+    assert!(
+        !stats.written_artifact_paths.is_empty(),
+        "{:?}",
+        stats.written_artifact_paths
+    );
+    assert!(
+        stats
+            .written_artifact_paths
+            .iter()
+            .all(|path| path.starts_with(&profile_route_artifacts)),
+        "{:?}",
+        stats.written_artifact_paths
+    );
+    assert!(stats.total_artifacts_unchanged > 0);
+    assert!(user_name_reader_exists);
+    Ok(())
+}
+
+#[test]
+fn test_edits_outside_of_iso_literals_are_not_compiled() -> Result<(), Box<dyn Error>> {
+    let config = create_config(copy_fixture_project(
+        "compilation_cache_outside_iso_literals",
+    )?);
+    let project_directory = project_directory(&config);
+
+    let first_compilation = compile_with_cache(&config)?;
+
+    let home_route = config.project_root.join("HomeRoute.tsx");
+    fs::write(
+        &home_route,
+        fs::read_to_string(&home_route)?.replace(
+            "((data) => data.viewer_count)",
+            "((data) => data.viewer_count ?? 0)",
+        ),
+    )?;
+    let second_compilation = compile_with_cache(&config)?;
+
+    fs::write(
+        &home_route,
+        fs::read_to_string(&home_route)?.replace(
+            "    viewer_count\n",
+            "    viewer_count\n    me {\n      name\n    }\n",
+        ),
+    )?;
+    let third_compilation = compile_with_cache(&config)?;
+    fs::remove_dir_all(&project_directory)?;
+
+    assert!(first_compilation.is_some());
+    assert!(second_compilation.is_none());
+    // Only now does an iso literal change.
+    assert!(third_compilation.is_some());
+    Ok(())
+}
@@ -0,0 +1,132 @@
+use std::{
+    error::Error,
+    fs,
+    path::{Path, PathBuf},
+};
+
+use isograph_compiler::{create_config, handle_compile_command};
+use tests::{project_directory, DEFAULT_CONFIG};
+
+static FIXTURE_DIRECTORY: &str = concat!(
+    env!("CARGO_MANIFEST_DIR"),
+    "/tests/fixtures/deterministic_project"
+);
+
+/// Copies the fixture project into a fresh directory, and returns the path to its
+/// config.
+fn copy_fixture_project(name: &str) -> Result<PathBuf, Box<dyn Error>> {
+    tests::copy_fixture_project(
+        name,
+        FIXTURE_DIRECTORY,
+        &[
+            ("schema.graphql", "schema.graphql"),
+            ("src/HomeRoute.tsx", "src/HomeRoute.tsx"),
+        ],
+        DEFAULT_CONFIG,
+    )
+}
+
+/// Returns the paths (relative to directory) and contents of the files in directory,
+/// sorted by path.
+fn read_files_recursively(directory: &Path) -> Vec<(PathBuf, Vec<u8>)> {
+    fn visit(root: &Path, directory: &Path, files: &mut Vec<(PathBuf, Vec<u8>)>) {
+        for entry in fs::read_dir(directory).expect("Expected artifact directory to be readable") {
+            let path = entry.expect("Expected directory entry").path();
+            if path.is_dir() {
+                visit(root, &path, files);
+            } else {
+                let contents = fs::read(&path).expect("Expected artifact to be readable");
+                let relative_path = path
+                    .strip_prefix(root)
+                    .expect("Expected artifact to be within the artifact directory")
+                    .to_path_buf();
+                files.push((relative_path, contents));
+            }
+        }
+    }
+
+    let mut files = vec![];
+    visit(directory, directory, &mut files);
+    files.sort();
+    files
+}
+
+/// Compiles the fixture project and returns its artifacts.
+fn compile_fixture_project(name: &str) -> Result<Vec<(PathBuf, Vec<u8>)>, Box<dyn Error>> {
+    let config_path = copy_fixture_project(name)?;
+    let config = create_config(config_path);
+    let project_directory = project_directory(&config);
+
+    handle_compile_command(&config, &[]).item?;
+
+    let artifacts = read_files_recursively(&config.artifact_directory);
+    fs::remove_dir_all(&project_directory)?;
+    Ok(artifacts)
+}
+
+#[test]
+fn test_artifacts_are_identical_across_compilations() -> Result<(), Box<dyn Error>> {
+    let first_artifacts = compile_fixture_project("deterministic_artifacts_1")?;
+    assert!(!first_artifacts.is_empty());
+
+    // Hash maps are seeded randomly, so if the order of their items leaked into
+    // artifacts, it would (very likely) differ between these compilations.
+    for index in 2..=5 {
+        let artifacts = compile_fixture_project(&format!("deterministic_artifacts_{index}"))?;
+        assert_eq!(
+            first_artifacts
+                .iter()
+                .map(|(path, _)| path)
+                .collect::<Vec<_>>(),
+            artifacts.iter().map(|(path, _)| path).collect::<Vec<_>>(),
+        );
+        for ((path, first_contents), (_, contents)) in first_artifacts.iter().zip(artifacts.iter())
+        {
+            assert!(
+                first_contents == contents,
+                "The artifact at {path:?} differs between compilations"
+            );
+        }
+    }
+    Ok(())
+}
+
+#[test]
+fn test_schema_hash_ignores_comments_and_formatting() -> Result<(), Box<dyn Error>> {
+    let config = create_config(copy_fixture_project("deterministic_schema_hash")?);
+    let project_directory = project_directory(&config);
+    let schema = fs::read_to_string(&config.schema)?;
+    let reader_path = config.artifact_directory.join("User/UserName/reader.ts");
+
+    handle_compile_command(&config, &[]).item?;
+    let original_reader = fs::read_to_string(&reader_path)?;
+
+    fs::write(
+        &config.schema,
+        format!(
+            "# The schema of the fixture project\n\n{}",
+            schema.replace("  ", "    ")
+        ),
+    )?;
+    handle_compile_command(&config, &[]).item?;
+    let reformatted_reader = fs::read_to_string(&reader_path)?;
+
+    fs::write(
+        &config.schema,
+        schema.replace("  bio: String\n", "  bio: String\n  website: String\n"),
+    )?;
+    handle_compile_command(&config, &[]).item?;
+    let changed_reader = fs::read_to_string(&reader_path)?;
+    fs::remove_dir_all(&project_directory)?;
+
+    assert!(
+        original_reader.starts_with("// schemaHash: "),
+        "{original_reader}"
+    );
+    assert_eq!(original_reader, reformatted_reader);
+    assert_ne!(
+        original_reader.lines().next(),
+        changed_reader.lines().next()
+    );
+    Ok(())
+}
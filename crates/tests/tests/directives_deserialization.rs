@@ -3,7 +3,7 @@ use graphql_lang_types::{
     from_graph_ql_directive, ConstantValue, DeserializationError, GraphQLDirective,
 };
 use intern::string_key::Intern;
-use isograph_schema::{ExposeFieldDirective, FieldMapItem};
+use isograph_schema::{ExposeFieldDirective, FieldMapItem, ResolverDirective};
 use std::error::Error;
 
 use graphql_lang_types::{GraphQLTypeSystemExtension, GraphQLTypeSystemExtensionOrDefinition};
@@ -12,8 +12,9 @@ fn unwrap_directive(
     extension_or_definition: GraphQLTypeSystemExtensionOrDefinition,
 ) -> Result<Vec<GraphQLDirective<ConstantValue>>, Box<dyn Error>> {
     if let GraphQLTypeSystemExtensionOrDefinition::Extension(extension) = extension_or_definition {
-        let GraphQLTypeSystemExtension::ObjectTypeExtension(object_type_extension) = extension;
-        return Ok(object_type_extension.directives.clone());
+        if let GraphQLTypeSystemExtension::ObjectTypeExtension(object_type_extension) = extension {
+            return Ok(object_type_extension.directives.clone());
+        }
     }
     Err("unexpected structure of directive".into())
 }
@@ -134,3 +135,43 @@ fn test_mutation_extension_missing_nestedfield_parsing_failure() -> Result<(), B
     match_failure_message(expose_field_directives, "missing field `from`");
     Ok(())
 }
+
+fn parse_resolver_fields(source: &str) -> Result<Vec<ResolverDirective>, Box<dyn Error>> {
+    let text_source = TextSource {
+        path: "dummy".intern().into(),
+        span: None,
+    };
+    let document =
+        graphql_schema_parser::parse_schema_extensions(source, text_source).map_err(|e| e.item)?;
+    let mut resolver_directives = vec![];
+    for extension_or_definition in document.0 {
+        if let GraphQLTypeSystemExtensionOrDefinition::Extension(extension) =
+            extension_or_definition.item
+        {
+            let GraphQLTypeSystemExtension::ObjectTypeExtension(object_type_extension) = extension
+            else {
+                return Err("unexpected structure of directive".into());
+            };
+            for field in object_type_extension.fields {
+                for directive in field.item.directives.iter() {
+                    resolver_directives
+                        .push(from_graph_ql_directive::<ResolverDirective>(directive)?);
+                }
+            }
+        }
+    }
+    Ok(resolver_directives)
+}
+
+#[test]
+fn test_resolver_extension_parsing() -> Result<(), Box<dyn Error>> {
+    let resolver_directives = parse_resolver_fields(include_str!(
+        "fixtures/directives/resolver_extension_valid.graphql"
+    ))?;
+    let full_name_resolver = ResolverDirective::new(
+        StringLiteralValue::from("src/Pet/fullName.ts".intern()),
+        StringLiteralValue::from("fullName".intern()),
+    );
+    assert_eq!(resolver_directives, vec![full_name_resolver]);
+    Ok(())
+}
@@ -0,0 +1,100 @@
+use std::{error::Error, fs, path::PathBuf};
+
+use isograph_compiler::{create_config, handle_compile_command};
+use tests::{compile_error, project_directory, DEFAULT_CONFIG};
+
+static FIXTURE_DIRECTORY: &str = concat!(
+    env!("CARGO_MANIFEST_DIR"),
+    "/tests/fixtures/fetchable_variants_project"
+);
+
+/// Copies the schema and the source file in the given directory of the fixture project
+/// into a fresh directory, and returns the path to its config.
+fn copy_fixture_project(name: &str, source_directory: &str) -> Result<PathBuf, Box<dyn Error>> {
+    let source_file = format!("{source_directory}/UserDetail.tsx");
+    tests::copy_fixture_project(
+        name,
+        FIXTURE_DIRECTORY,
+        &[
+            ("schema.graphql", "schema.graphql"),
+            (&source_file, "src/UserDetail.tsx"),
+        ],
+        DEFAULT_CONFIG,
+    )
+}
+
+#[test]
+fn test_variants_have_their_own_operations_and_artifacts() -> Result<(), Box<dyn Error>> {
+    let config = create_config(copy_fixture_project("fetchable_variants", "valid")?);
+    handle_compile_command(&config, &[]).item?;
+
+    let read_artifact = |path: &str| fs::read_to_string(config.artifact_directory.join(path));
+    let entrypoint = read_artifact("Query/UserDetail/entrypoint.ts")?;
+    let variant_entrypoint = read_artifact("Query/UserDetailCompact/entrypoint.ts")?;
+    let variant_reader = read_artifact("Query/UserDetailCompact/reader.ts")?;
+    let iso = read_artifact("iso.ts")?;
+    fs::remove_dir_all(project_directory(&config))?;
+
+    assert!(entrypoint.contains("query UserDetail "), "{entrypoint}");
+    assert!(entrypoint.contains("email"), "{entrypoint}");
+    assert!(
+        variant_entrypoint.contains("query UserDetailCompact "),
+        "{variant_entrypoint}"
+    );
+    assert!(
+        !variant_entrypoint.contains("email"),
+        "{variant_entrypoint}"
+    );
+    // The reader of the variant reads the selections of the variant, and renders the
+    // component exported for the variant.
+    assert!(
+        variant_reader.contains("UserDetailCompact"),
+        "{variant_reader}"
+    );
+    assert!(!variant_reader.contains("email"), "{variant_reader}");
+
+    // The overload of the variant is more specific than that of the client field, so
+    // it must come first.
+    let variant_overload = iso
+        .find("`field Query.UserDetail${string}@fetchable(name: \"UserDetailCompact\")`")
+        .unwrap_or_else(|| panic!("Expected an overload for the variant in {iso}"));
+    let overload = iso
+        .find("'field Query.UserDetail'")
+        .unwrap_or_else(|| panic!("Expected an overload for the client field in {iso}"));
+    assert!(variant_overload < overload, "{iso}");
+    assert!(
+        iso.contains("'entrypoint Query.UserDetailCompact'"),
+        "{iso}"
+    );
+    Ok(())
+}
+
+#[test]
+fn test_variant_of_unknown_client_field_is_rejected() -> Result<(), Box<dyn Error>> {
+    let error = compile_error(copy_fixture_project(
+        "fetchable_variants_unknown_client_field",
+        "unknown_client_field",
+    )?)?;
+    assert!(
+        error.contains(
+            "The client field `Query.UserDetail` is declared with \
+            @fetchable(name: \"UserDetailCompact\"), but `Query.UserDetail` is not \
+            declared without @fetchable."
+        ),
+        "{error}"
+    );
+    Ok(())
+}
+
+#[test]
+fn test_fetchable_without_name_is_rejected() -> Result<(), Box<dyn Error>> {
+    let error = compile_error(copy_fixture_project(
+        "fetchable_variants_missing_name",
+        "missing_name",
+    )?)?;
+    assert!(
+        error.contains("@fetchable must be passed the name of the variant as a string"),
+        "{error}"
+    );
+    Ok(())
+}
@@ -0,0 +1,11 @@
+import { iso } from '@iso';
+
+export const PetSummary = iso(`
+  field Pet.PetSummary {
+    id
+    name
+    age
+    weight
+    is_adopted
+  }
+`)((data) => data);
@@ -0,0 +1,7 @@
+import { iso } from '@iso';
+
+export const Avatar = iso(`
+  field User.Avatar($size: Int!) @component {
+    avatar_url(size: $size)
+  }
+`)((data) => data.avatar_url);
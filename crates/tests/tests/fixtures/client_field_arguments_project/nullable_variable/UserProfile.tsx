@@ -0,0 +1,12 @@
+import { iso } from '@iso';
+
+export const UserProfile = iso(`
+  field Query.UserProfile($size: Int) @component {
+    me {
+      name
+      Avatar(size: $size)
+    }
+  }
+`)((data) => data.me?.name);
+
+iso(`entrypoint Query.UserProfile`);
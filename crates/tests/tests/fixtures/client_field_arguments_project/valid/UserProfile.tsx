@@ -0,0 +1,12 @@
+import { iso } from '@iso';
+
+export const UserProfile = iso(`
+  field Query.UserProfile @component {
+    me {
+      name
+      Avatar(size: 48)
+    }
+  }
+`)((data) => data.me?.name);
+
+iso(`entrypoint Query.UserProfile`);
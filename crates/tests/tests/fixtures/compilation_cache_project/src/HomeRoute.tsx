@@ -0,0 +1,9 @@
+import { iso } from '@iso';
+
+export const HomeRoute = iso(`
+  field Query.HomeRoute {
+    viewer_count
+  }
+`)((data) => data.viewer_count);
+
+iso(`entrypoint Query.HomeRoute`);
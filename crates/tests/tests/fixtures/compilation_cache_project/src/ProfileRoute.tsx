@@ -0,0 +1,11 @@
+import { iso } from '@iso';
+
+export const ProfileRoute = iso(`
+  field Query.ProfileRoute {
+    me {
+      name
+    }
+  }
+`)((data) => data.me);
+
+iso(`entrypoint Query.ProfileRoute`);
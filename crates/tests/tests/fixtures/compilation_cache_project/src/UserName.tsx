@@ -0,0 +1,7 @@
+import { iso } from '@iso';
+
+export const UserName = iso(`
+  field User.UserName {
+    name
+  }
+`)((data) => data.name);
@@ -0,0 +1,60 @@
+import { iso } from '@iso';
+
+export const UserName = iso(`
+  field User.UserName {
+    name
+  }
+`)((data) => data.name);
+
+export const UserEmail = iso(`
+  field User.UserEmail {
+    email
+  }
+`)((data) => data.email);
+
+export const UserAvatar = iso(`
+  field User.UserAvatar @component {
+    avatar_url
+  }
+`)((data) => data.avatar_url);
+
+export const UserBio = iso(`
+  field User.UserBio {
+    bio
+  }
+`)((data) => data.bio);
+
+export const UserLocation = iso(`
+  field User.UserLocation {
+    location
+  }
+`)((data) => data.location);
+
+export const UserSummary = iso(`
+  field User.UserSummary @component {
+    UserName
+    UserEmail
+    UserAvatar
+    UserBio
+    UserLocation
+    best_friend {
+      UserName
+      UserAvatar
+    }
+  }
+`)((data) => data.UserName);
+
+export const HomeRoute = iso(`
+  field Query.HomeRoute @component {
+    me {
+      UserSummary
+      UserName
+    }
+    viewer {
+      UserLocation
+      UserBio
+    }
+  }
+`)((data) => data.me?.UserSummary);
+
+iso(`entrypoint Query.HomeRoute`);
@@ -0,0 +1,17 @@
+import { iso } from '@iso';
+
+export const UserDetail = iso(`
+  field Query.UserDetail @component {
+    me {
+      name
+    }
+  }
+`)((data) => data.me?.name);
+
+export const UserDetailCompact = iso(`
+  field Query.UserDetail @component @fetchable {
+    me {
+      name
+    }
+  }
+`)((data) => data.me?.name);
@@ -0,0 +1,9 @@
+import { iso } from '@iso';
+
+export const UserDetailCompact = iso(`
+  field Query.UserDetail @component @fetchable(name: "UserDetailCompact") {
+    me {
+      name
+    }
+  }
+`)((data) => data.me?.name);
@@ -0,0 +1,22 @@
+import { iso } from '@iso';
+
+export const UserDetail = iso(`
+  field Query.UserDetail @component {
+    me {
+      name
+      email
+      bio
+    }
+  }
+`)((data) => data.me?.name);
+
+export const UserDetailCompact = iso(`
+  field Query.UserDetail @component @fetchable(name: "UserDetailCompact") {
+    me {
+      name
+    }
+  }
+`)((data) => data.me?.name);
+
+iso(`entrypoint Query.UserDetail`);
+iso(`entrypoint Query.UserDetailCompact`);
@@ -0,0 +1,16 @@
+import { iso } from '@iso';
+
+export const UserProfile = iso(`
+  field Query.UserProfile {
+    me {
+      name
+      bio @defer
+      tags @stream(initialCount: 1)
+      friends @stream(initialCount: 2) {
+        name
+      }
+    }
+  }
+`)((data) => data.me);
+
+iso(`entrypoint Query.UserProfile`);
@@ -0,0 +1,11 @@
+import { iso } from '@iso';
+
+export const UserProfile = iso(`
+  field Query.UserProfile {
+    me {
+      name
+    }
+  }
+`)((data) => data.me);
+
+iso(`entrypoint Query.UserProfile`);
@@ -0,0 +1,16 @@
+import { iso } from '@iso';
+
+export const UserCard = iso(`
+  field User.UserCard {
+    best_friend {
+      name
+      email
+    }
+    worst_enemy {
+      name
+      email
+    }
+    __refetch
+    set_user_name
+  }
+`)((data) => data);
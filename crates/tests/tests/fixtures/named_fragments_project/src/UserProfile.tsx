@@ -0,0 +1,11 @@
+import { iso } from '@iso';
+
+export const UserProfile = iso(`
+  field Query.UserProfile {
+    me {
+      UserCard
+    }
+  }
+`)((data) => data.me?.UserCard);
+
+iso(`entrypoint Query.UserProfile`);
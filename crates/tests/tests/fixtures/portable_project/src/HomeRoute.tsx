@@ -0,0 +1,17 @@
+import { iso } from '@iso';
+
+export const UserName = iso(`
+  field User.UserName {
+    name
+  }
+`)((data) => data.name);
+
+export const HomeRoute = iso(`
+  field Query.HomeRoute @component {
+    me {
+      UserName
+    }
+  }
+`)((data) => data.me?.UserName);
+
+iso(`entrypoint Query.HomeRoute`);
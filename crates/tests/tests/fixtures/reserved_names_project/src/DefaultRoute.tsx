@@ -0,0 +1,11 @@
+import { iso } from '@iso';
+
+export const DefaultRoute = iso(`
+  field Query.default {
+    me {
+      resolver
+    }
+  }
+`)((data) => data.me?.resolver);
+
+iso(`entrypoint Query.default`);
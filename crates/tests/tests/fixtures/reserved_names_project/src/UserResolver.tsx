@@ -0,0 +1,8 @@
+import { iso } from '@iso';
+
+export const resolver = iso(`
+  field User.resolver {
+    default
+    artifact: name
+  }
+`)((data) => data.default ?? data.artifact);
@@ -0,0 +1,20 @@
+import { iso } from '@iso';
+
+export const UserProfile = iso(`
+  field Query.UserProfile {
+    me {
+      name
+      reviews {
+        body
+      }
+    }
+    top_reviews {
+      body
+      author {
+        name
+      }
+    }
+  }
+`)((data) => data);
+
+iso(`entrypoint Query.UserProfile`);
@@ -0,0 +1,11 @@
+import { iso } from '@iso';
+
+export const HomeRoute = iso(`
+  field Query.HomeRoute {
+    me {
+      UserCard
+    }
+  }
+`)((data) => data.me?.UserCard);
+
+iso(`entrypoint Query.HomeRoute`);
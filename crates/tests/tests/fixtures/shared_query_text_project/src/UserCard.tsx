@@ -0,0 +1,8 @@
+import { iso } from '@iso';
+
+export const UserCard = iso(`
+  field User.UserCard {
+    name
+    __refetch
+  }
+`)((data) => data.name);
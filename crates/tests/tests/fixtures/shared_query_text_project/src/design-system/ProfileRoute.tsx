@@ -0,0 +1,11 @@
+import { iso } from '@iso';
+
+export const ProfileRoute = iso(`
+  field Query.ProfileRoute {
+    me {
+      UserCard
+    }
+  }
+`)((data) => data.me?.UserCard);
+
+iso(`entrypoint Query.ProfileRoute`);
@@ -0,0 +1,14 @@
+import { iso } from '@iso';
+
+export const UserProfile = iso(`
+  field Query.UserProfile {
+    me {
+      name
+      email @include(if: true)
+      phone_number @include(if: false)
+      avatar_url @skip(if: true)
+    }
+  }
+`)((data) => data.me);
+
+iso(`entrypoint Query.UserProfile`);
@@ -0,0 +1,12 @@
+import { iso } from '@iso';
+
+export const UserProfile = iso(`
+  field Query.UserProfile($compact: Boolean!) {
+    me {
+      name
+      avatar_url @skip(if: $compact)
+    }
+  }
+`)((data) => data.me);
+
+iso(`entrypoint Query.UserProfile`);
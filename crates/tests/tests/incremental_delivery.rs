@@ -0,0 +1,130 @@
+use std::{error::Error, fs, path::PathBuf};
+
+use isograph_compiler::{create_config, handle_compile_command};
+use tests::{project_directory, DEFAULT_CONFIG};
+
+static FIXTURE_DIRECTORY: &str = concat!(
+    env!("CARGO_MANIFEST_DIR"),
+    "/tests/fixtures/incremental_delivery_project"
+);
+
+/// Copies the schema and the source file in the given directory of the fixture project
+/// into a fresh directory, and returns the path to its config.
+fn copy_fixture_project(name: &str, source_directory: &str) -> Result<PathBuf, Box<dyn Error>> {
+    let source_file = format!("{source_directory}/UserProfile.tsx");
+    tests::copy_fixture_project(
+        name,
+        FIXTURE_DIRECTORY,
+        &[
+            ("schema.graphql", "schema.graphql"),
+            (&source_file, "src/UserProfile.tsx"),
+        ],
+        DEFAULT_CONFIG,
+    )
+}
+
+fn compile_entrypoint(name: &str) -> Result<String, Box<dyn Error>> {
+    let config = create_config(copy_fixture_project(name, "valid")?);
+    handle_compile_command(&config, &[]).item?;
+    let entrypoint = fs::read_to_string(
+        config
+            .artifact_directory
+            .join("Query/UserProfile/entrypoint.ts"),
+    )?;
+    fs::remove_dir_all(project_directory(&config))?;
+    Ok(entrypoint)
+}
+
+/// Returns the part of s up to (and including) the bracket that closes its first `[`.
+fn bracketed(s: &str) -> &str {
+    let mut depth = 0;
+    for (index, character) in s.char_indices() {
+        match character {
+            '[' => depth += 1,
+            ']' => {
+                depth -= 1;
+                if depth == 0 {
+                    return &s[..=index];
+                }
+            }
+            _ => {}
+        }
+    }
+    panic!("Expected brackets to be balanced in {s}")
+}
+
+#[test]
+fn test_query_text_contains_defer_and_stream() -> Result<(), Box<dyn Error>> {
+    let entrypoint = compile_entrypoint("incremental_delivery_query_text")?;
+
+    // Labels are derived from the path to the deferred selection (or streamed field),
+    // so that the runtime can tell which part of the response a payload belongs to.
+    assert!(
+        entrypoint.contains("... @defer(label: \"defer$me\") {"),
+        "{entrypoint}"
+    );
+    assert!(
+        entrypoint.contains("tags @stream(label: \"stream$me.tags\", initialCount: 1),"),
+        "{entrypoint}"
+    );
+    assert!(
+        entrypoint.contains("friends @stream(label: \"stream$me.friends\", initialCount: 2) {"),
+        "{entrypoint}"
+    );
+    Ok(())
+}
+
+#[test]
+fn test_normalization_ast_contains_defer_boundaries() -> Result<(), Box<dyn Error>> {
+    let entrypoint = compile_entrypoint("incremental_delivery_normalization_ast")?;
+    let normalization_ast = &entrypoint[entrypoint
+        .find("normalizationAst")
+        .expect("Expected entrypoint to contain a normalization AST")..];
+
+    let defer_start = normalization_ast
+        .find("kind: \"Defer\",")
+        .unwrap_or_else(|| panic!("Expected a Defer node in {normalization_ast}"));
+    let defer_node = &normalization_ast[defer_start..];
+    assert!(
+        defer_node.contains("label: \"defer$me\","),
+        "{normalization_ast}"
+    );
+
+    // bio is only normalized as part of the deferred payload, and name is not.
+    let deferred_selections = bracketed(
+        &defer_node[defer_node
+            .find("selections: [")
+            .expect("Expected the Defer node to have selections")..],
+    );
+    assert!(
+        deferred_selections.contains("fieldName: \"bio\""),
+        "{normalization_ast}"
+    );
+    assert!(
+        !deferred_selections.contains("fieldName: \"name\""),
+        "{normalization_ast}"
+    );
+    Ok(())
+}
+
+#[test]
+fn test_stream_on_non_list_field_is_rejected() -> Result<(), Box<dyn Error>> {
+    let config = create_config(copy_fixture_project(
+        "incremental_delivery_stream_on_non_list_field",
+        "stream_on_non_list_field",
+    )?);
+    let result = handle_compile_command(&config, &[]).item;
+    fs::remove_dir_all(project_directory(&config))?;
+    let error = match result {
+        Ok(_) => panic!("Expected @stream on a non-list field to be rejected"),
+        Err(error) => error.to_string(),
+    };
+    assert!(
+        error.contains(
+            "the field `User.name` is selected with @stream, but @stream can only be used on \
+            fields whose type is a list"
+        ),
+        "{error}"
+    );
+    Ok(())
+}
@@ -0,0 +1,108 @@
+use common_lang_types::TextSource;
+use graphql_lang_types::print_type_system_document;
+use graphql_schema_parser::{parse_introspection_json, parse_schema};
+use intern::string_key::Intern;
+use std::error::Error;
+
+fn text_source() -> TextSource {
+    TextSource {
+        path: "dummy".intern().into(),
+        span: None,
+    }
+}
+
+/// Converts an introspection result to SDL, as `isograph pull-schema` does when the
+/// schema is not written as JSON.
+fn introspection_to_sdl(source: &str) -> Result<String, Box<dyn Error>> {
+    let document = parse_introspection_json(source, text_source()).map_err(|e| e.item)?;
+    Ok(print_type_system_document(&document))
+}
+
+#[test]
+fn test_introspection_result_is_converted_to_sdl() -> Result<(), Box<dyn Error>> {
+    let sdl = introspection_to_sdl(include_str!("fixtures/introspection/introspection.json"))?;
+
+    // Built-in scalars and directives, and introspection types, are not printed.
+    assert_eq!(
+        sdl,
+        include_str!("fixtures/introspection/schema.graphql"),
+        "{sdl}"
+    );
+    Ok(())
+}
+
+#[test]
+fn test_converted_sdl_can_be_parsed() -> Result<(), Box<dyn Error>> {
+    let sdl = introspection_to_sdl(include_str!("fixtures/introspection/introspection.json"))?;
+
+    let document = parse_schema(&sdl, text_source()).map_err(|e| e.item)?;
+    assert_eq!(print_type_system_document(&document), sdl);
+    Ok(())
+}
+
+#[test]
+fn test_result_without_data_is_converted() -> Result<(), Box<dyn Error>> {
+    let sdl = introspection_to_sdl(
+        r#"{
+  "__schema": {
+    "queryType": { "name": "Query" },
+    "types": [
+      {
+        "kind": "OBJECT",
+        "name": "Query",
+        "fields": [
+          {
+            "name": "tags",
+            "type": {
+              "kind": "LIST",
+              "ofType": { "kind": "SCALAR", "name": "String" }
+            }
+          }
+        ]
+      }
+    ]
+  }
+}"#,
+    )?;
+    assert_eq!(
+        sdl,
+        "schema {\n  query: Query\n}\n\ntype Query {\n  tags: [String]\n}\n"
+    );
+    Ok(())
+}
+
+#[test]
+fn test_doubly_non_null_type_is_rejected() {
+    let result = introspection_to_sdl(
+        r#"{
+  "__schema": {
+    "queryType": { "name": "Query" },
+    "types": [
+      {
+        "kind": "OBJECT",
+        "name": "Query",
+        "fields": [
+          {
+            "name": "id",
+            "type": {
+              "kind": "NON_NULL",
+              "ofType": {
+                "kind": "NON_NULL",
+                "ofType": { "kind": "SCALAR", "name": "ID" }
+              }
+            }
+          }
+        ]
+      }
+    ]
+  }
+}"#,
+    );
+    let error = result
+        .expect_err("Expected a non-null type wrapping a non-null type to be rejected")
+        .to_string();
+    assert!(
+        error.contains("The type of `id` in the introspection result is invalid"),
+        "{error}"
+    );
+}
@@ -0,0 +1,137 @@
+use std::{error::Error, fs, path::PathBuf};
+
+use isograph_compiler::{create_config, handle_compile_command, CompilerConfig};
+use serde_json::{json, Value};
+use tests::project_directory;
+
+static FIXTURE_DIRECTORY: &str = concat!(
+    env!("CARGO_MANIFEST_DIR"),
+    "/tests/fixtures/client_field_arguments_project"
+);
+
+static CONFIG: &str = r#"{
+  "project_root": "./src",
+  "artifact_directory": "./",
+  "schema": "./schema.graphql",
+  "options": {
+    "artifact_format": "json"
+  }
+}"#;
+
+/// Copies the schema, the Avatar client field and the UserProfile entrypoint of the
+/// fixture project into a fresh directory, and returns the path to its config.
+fn copy_fixture_project(name: &str) -> Result<PathBuf, Box<dyn Error>> {
+    tests::copy_fixture_project(
+        name,
+        FIXTURE_DIRECTORY,
+        &[
+            ("schema.graphql", "schema.graphql"),
+            ("Avatar.tsx", "src/Avatar.tsx"),
+            ("valid/UserProfile.tsx", "src/UserProfile.tsx"),
+        ],
+        CONFIG,
+    )
+}
+
+fn read_artifact(config: &CompilerConfig, path: &str) -> Result<Value, Box<dyn Error>> {
+    let contents = fs::read_to_string(config.artifact_directory.join(path))?;
+    Ok(serde_json::from_str(&contents)?)
+}
+
+#[test]
+fn test_json_entrypoint_artifact() -> Result<(), Box<dyn Error>> {
+    let config = create_config(copy_fixture_project("json_artifacts_entrypoint")?);
+    handle_compile_command(&config, &[]).item?;
+    let entrypoint = read_artifact(&config, "Query/UserProfile/entrypoint.json");
+    let typescript_artifact_exists = config
+        .artifact_directory
+        .join("Query/UserProfile/entrypoint.ts")
+        .exists();
+    fs::remove_dir_all(project_directory(&config))?;
+    let entrypoint = entrypoint?;
+
+    assert!(!typescript_artifact_exists);
+    assert_eq!(entrypoint["kind"], "Entrypoint");
+    assert_eq!(entrypoint["operationKind"], "Query");
+    assert!(
+        entrypoint["queryText"]
+            .as_str()
+            .map_or(false, |query_text| query_text.contains("avatar_url")),
+        "{entrypoint}"
+    );
+    assert_eq!(entrypoint["nestedRefetchQueries"], json!([]));
+
+    let normalization_ast = entrypoint["normalizationAst"]
+        .as_array()
+        .expect("Expected the normalization AST to be an array");
+    assert_eq!(normalization_ast.len(), 1, "{entrypoint}");
+    assert_eq!(normalization_ast[0]["kind"], "Linked");
+    assert_eq!(normalization_ast[0]["fieldName"], "me");
+    assert_eq!(normalization_ast[0]["arguments"], Value::Null);
+    let me_selections = normalization_ast[0]["selections"]
+        .as_array()
+        .expect("Expected the selections of me to be an array");
+    let avatar_url = me_selections
+        .iter()
+        .find(|selection| selection["fieldName"] == "avatar_url")
+        .expect("Expected avatar_url to be normalized");
+    assert_eq!(avatar_url["kind"], "Scalar");
+    assert_eq!(
+        avatar_url["arguments"],
+        json!([["size", { "kind": "Literal", "value": "48" }]])
+    );
+    Ok(())
+}
+
+#[test]
+fn test_json_reader_artifacts() -> Result<(), Box<dyn Error>> {
+    let config = create_config(copy_fixture_project("json_artifacts_reader")?);
+    handle_compile_command(&config, &[]).item?;
+    let user_profile = read_artifact(&config, "Query/UserProfile/reader.json");
+    let avatar = read_artifact(&config, "User/Avatar/reader.json");
+    fs::remove_dir_all(project_directory(&config))?;
+    let (user_profile, avatar) = (user_profile?, avatar?);
+
+    assert_eq!(user_profile["kind"], "ReaderArtifact");
+    assert_eq!(
+        user_profile["variant"],
+        json!({ "kind": "Component", "componentName": "Query.UserProfile" })
+    );
+    // Client fields refer to the reader artifacts of the client fields they select
+    // by name.
+    assert_eq!(
+        user_profile["readerAst"],
+        json!([{
+            "kind": "Linked",
+            "fieldName": "me",
+            "alias": null,
+            "arguments": null,
+            "selections": [
+                {
+                    "kind": "Scalar",
+                    "fieldName": "name",
+                    "alias": null,
+                    "arguments": null,
+                },
+                {
+                    "kind": "Resolver",
+                    "alias": "Avatar",
+                    "arguments": [["size", { "kind": "Literal", "value": "48" }]],
+                    "readerArtifact": "User__Avatar",
+                    "usedRefetchQueries": [],
+                },
+            ],
+        }])
+    );
+
+    assert_eq!(
+        avatar["readerAst"],
+        json!([{
+            "kind": "Scalar",
+            "fieldName": "avatar_url",
+            "alias": null,
+            "arguments": [["size", { "kind": "Variable", "name": "size" }]],
+        }])
+    );
+    Ok(())
+}
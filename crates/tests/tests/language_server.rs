@@ -0,0 +1,481 @@
+use std::{error::Error, fs, io::Cursor, path::PathBuf};
+
+use isograph_compiler::{create_config, serve_language_client, CompilerConfig};
+use serde_json::{json, Value};
+use tests::{project_directory, DEFAULT_CONFIG};
+
+static FIXTURE_DIRECTORY: &str = concat!(
+    env!("CARGO_MANIFEST_DIR"),
+    "/tests/fixtures/language_server_project"
+);
+
+static FIXTURE_FILES: [&str; 2] = ["schema.graphql", "src/UserProfile.tsx"];
+
+/// Copies the fixture project into a fresh directory, so that the language server
+/// does not read files that other tests write, and returns the path to its config.
+fn copy_fixture_project(name: &str) -> Result<PathBuf, Box<dyn Error>> {
+    tests::copy_fixture_project(
+        name,
+        FIXTURE_DIRECTORY,
+        &FIXTURE_FILES.map(|fixture_file| (fixture_file, fixture_file)),
+        DEFAULT_CONFIG,
+    )
+}
+
+/// The uri of UserProfile.tsx. The project is in the temp directory, whose path
+/// does not contain characters that must be percent-encoded.
+fn user_profile_uri(config: &CompilerConfig) -> String {
+    format!(
+        "file://{}",
+        config.project_root.join("UserProfile.tsx").display()
+    )
+}
+
+/// Sends the messages to a language server for the project, each framed with a
+/// Content-Length header, followed by shutdown and exit, and returns the messages
+/// that the language server sent back, in order.
+fn exchange_messages(
+    config: &CompilerConfig,
+    messages: &[Value],
+) -> Result<Vec<Value>, Box<dyn Error>> {
+    let mut input = vec![];
+    for message in messages.iter().chain(&[
+        json!({ "jsonrpc": "2.0", "id": "shutdown", "method": "shutdown" }),
+        json!({ "jsonrpc": "2.0", "method": "exit" }),
+    ]) {
+        let content = message.to_string();
+        input.extend(format!("Content-Length: {}\r\n\r\n{content}", content.len()).bytes());
+    }
+
+    let mut output = vec![];
+    serve_language_client(config, &mut Cursor::new(input), &mut output)?;
+
+    let mut output = output.as_slice();
+    let mut responses = vec![];
+    while !output.is_empty() {
+        let header_end = output
+            .windows(4)
+            .position(|window| window == b"\r\n\r\n")
+            .expect("Expected every message to have a header");
+        let header = std::str::from_utf8(&output[..header_end])?;
+        let content_length: usize = header
+            .strip_prefix("Content-Length: ")
+            .expect("Expected a Content-Length header")
+            .parse()?;
+        let content_start = header_end + 4;
+        responses.push(serde_json::from_slice(
+            &output[content_start..content_start + content_length],
+        )?);
+        output = &output[content_start + content_length..];
+    }
+
+    // The last message is always the response to shutdown.
+    assert_eq!(
+        responses.pop(),
+        Some(json!({ "jsonrpc": "2.0", "id": "shutdown", "result": null }))
+    );
+    Ok(responses)
+}
+
+fn initialize() -> Value {
+    json!({ "jsonrpc": "2.0", "id": 1, "method": "initialize", "params": { "capabilities": {} } })
+}
+
+fn initialized() -> Value {
+    json!({ "jsonrpc": "2.0", "method": "initialized", "params": {} })
+}
+
+fn did_change(uri: &str, version: u64, text: &str) -> Value {
+    json!({
+        "jsonrpc": "2.0",
+        "method": "textDocument/didChange",
+        "params": {
+            "textDocument": { "uri": uri, "version": version },
+            "contentChanges": [{ "text": text }],
+        },
+    })
+}
+
+/// The zero-based line and UTF-16 character of the first occurrence of needle in
+/// content, which is ASCII.
+fn position_of(content: &str, needle: &str) -> Value {
+    let offset = content
+        .find(needle)
+        .unwrap_or_else(|| panic!("Expected {needle} to occur in {content}"));
+    let line_start = content[..offset]
+        .rfind('\n')
+        .map(|index| index + 1)
+        .unwrap_or(0);
+    json!({
+        "line": content[..offset].matches('\n').count(),
+        "character": offset - line_start,
+    })
+}
+
+#[test]
+fn test_initialize_advertises_capabilities() -> Result<(), Box<dyn Error>> {
+    let config = create_config(copy_fixture_project("language_server_initialize")?);
+    let responses = exchange_messages(&config, &[initialize()]);
+    fs::remove_dir_all(project_directory(&config))?;
+    let responses = responses?;
+
+    assert_eq!(responses.len(), 1, "{responses:?}");
+    assert_eq!(responses[0]["id"], json!(1));
+    let capabilities = &responses[0]["result"]["capabilities"];
+    assert_eq!(
+        capabilities["textDocumentSync"],
+        json!({ "openClose": true, "change": 1, "save": true })
+    );
+    assert_eq!(capabilities["hoverProvider"], json!(true));
+    assert_eq!(capabilities["definitionProvider"], json!(true));
+    assert_eq!(capabilities["documentFormattingProvider"], json!(true));
+    assert_eq!(capabilities["workspaceSymbolProvider"], json!(true));
+    assert_eq!(
+        capabilities["semanticTokensProvider"]["legend"]["tokenTypes"],
+        json!([
+            "keyword",
+            "type",
+            "property",
+            "parameter",
+            "variable",
+            "decorator",
+            "number",
+            "string"
+        ])
+    );
+    assert_eq!(
+        responses[0]["result"]["serverInfo"]["name"],
+        json!("isograph")
+    );
+    Ok(())
+}
+
+fn did_open(uri: &str, text: &str) -> Value {
+    json!({
+        "jsonrpc": "2.0",
+        "method": "textDocument/didOpen",
+        "params": {
+            "textDocument": {
+                "uri": uri,
+                "languageId": "typescriptreact",
+                "version": 1,
+                "text": text,
+            },
+        },
+    })
+}
+
+#[test]
+fn test_diagnostics_are_published_for_unsaved_changes() -> Result<(), Box<dyn Error>> {
+    let config = create_config(copy_fixture_project("language_server_diagnostics")?);
+    let uri = user_profile_uri(&config);
+    let content = fs::read_to_string(config.project_root.join("UserProfile.tsx"))?;
+    let responses = exchange_messages(
+        &config,
+        &[
+            initialize(),
+            initialized(),
+            did_open(&uri, &content),
+            did_change(&uri, 2, &content.replace("      name\n", "      nme\n")),
+            did_change(&uri, 3, &content),
+        ],
+    );
+    fs::remove_dir_all(project_directory(&config))?;
+    let responses = responses?;
+
+    // The project is valid on disk, so nothing is published until the unsaved
+    // change, which selects a field that does not exist.
+    assert_eq!(responses.len(), 3, "{responses:?}");
+    assert_eq!(
+        responses[1]["method"],
+        json!("textDocument/publishDiagnostics")
+    );
+    assert_eq!(responses[1]["params"]["uri"], json!(uri));
+    let diagnostics = responses[1]["params"]["diagnostics"]
+        .as_array()
+        .expect("Expected diagnostics to be an array");
+    assert_eq!(diagnostics.len(), 1, "{diagnostics:?}");
+    let message = diagnostics[0]["message"].as_str().unwrap_or_default();
+    assert!(
+        message
+            .contains("the field `User.nme` is selected, but that field does not exist on `User`"),
+        "{message}"
+    );
+    assert_eq!(diagnostics[0]["range"]["start"]["line"], json!(5));
+
+    // Once the change is undone, the diagnostics are cleared.
+    assert_eq!(
+        responses[2],
+        json!({
+            "jsonrpc": "2.0",
+            "method": "textDocument/publishDiagnostics",
+            "params": { "uri": uri, "diagnostics": [] },
+        })
+    );
+    Ok(())
+}
+
+#[test]
+fn test_hover_shows_server_field() -> Result<(), Box<dyn Error>> {
+    let config = create_config(copy_fixture_project("language_server_hover")?);
+    let uri = user_profile_uri(&config);
+    let content = fs::read_to_string(config.project_root.join("UserProfile.tsx"))?;
+    let responses = exchange_messages(
+        &config,
+        &[
+            initialize(),
+            initialized(),
+            json!({
+                "jsonrpc": "2.0",
+                "id": 2,
+                "method": "textDocument/hover",
+                "params": {
+                    "textDocument": { "uri": uri },
+                    "position": position_of(&content, "name"),
+                },
+            }),
+        ],
+    );
+    fs::remove_dir_all(project_directory(&config))?;
+    let responses = responses?;
+
+    assert_eq!(responses.len(), 2, "{responses:?}");
+    assert_eq!(
+        responses[1],
+        json!({
+            "jsonrpc": "2.0",
+            "id": 2,
+            "result": {
+                "contents": {
+                    "kind": "markdown",
+                    "value": "```graphql\nUser.name: String\n```\n\nThe name that the user chose",
+                },
+            },
+        })
+    );
+    Ok(())
+}
+
+#[test]
+fn test_formatting_formats_unsaved_iso_literals() -> Result<(), Box<dyn Error>> {
+    let config = create_config(copy_fixture_project("language_server_formatting")?);
+    let uri = user_profile_uri(&config);
+    let content = fs::read_to_string(config.project_root.join("UserProfile.tsx"))?;
+    let unformatted_content = content.replace("    me {\n      name\n    }", "me { name }");
+    let formatting = |id: u64| {
+        json!({
+            "jsonrpc": "2.0",
+            "id": id,
+            "method": "textDocument/formatting",
+            "params": {
+                "textDocument": { "uri": uri },
+                "options": { "tabSize": 2, "insertSpaces": true },
+            },
+        })
+    };
+    let responses = exchange_messages(
+        &config,
+        &[
+            initialize(),
+            did_open(&uri, &unformatted_content),
+            formatting(2),
+            did_change(&uri, 2, &content),
+            formatting(3),
+        ],
+    );
+    fs::remove_dir_all(project_directory(&config))?;
+    let responses = responses?;
+
+    // The fixture is formatted, so formatting the unformatted literal restores it,
+    // and formatting it again changes nothing.
+    assert_eq!(responses.len(), 3, "{responses:?}");
+    let edits = responses[1]["result"]
+        .as_array()
+        .expect("Expected the result to be an array of edits");
+    assert_eq!(edits.len(), 1, "{edits:?}");
+    assert_eq!(edits[0]["newText"], json!(content));
+    assert_eq!(
+        edits[0]["range"]["start"],
+        json!({ "line": 0, "character": 0 })
+    );
+    assert_eq!(
+        edits[0]["range"]["end"],
+        json!({ "line": unformatted_content.matches('\n').count(), "character": 0 })
+    );
+    assert_eq!(
+        responses[2],
+        json!({ "jsonrpc": "2.0", "id": 3, "result": [] })
+    );
+    Ok(())
+}
+
+#[test]
+fn test_workspace_symbols_are_found_by_fuzzy_name() -> Result<(), Box<dyn Error>> {
+    let config = create_config(copy_fixture_project("language_server_workspace_symbols")?);
+    let uri = user_profile_uri(&config);
+    let content = fs::read_to_string(config.project_root.join("UserProfile.tsx"))?;
+    let workspace_symbol = |id: u64, query: &str| {
+        json!({
+            "jsonrpc": "2.0",
+            "id": id,
+            "method": "workspace/symbol",
+            "params": { "query": query },
+        })
+    };
+    let responses = exchange_messages(
+        &config,
+        &[
+            initialize(),
+            workspace_symbol(2, "qusrpro"),
+            workspace_symbol(3, "avatar"),
+        ],
+    );
+    fs::remove_dir_all(project_directory(&config))?;
+    let responses = responses?;
+
+    assert_eq!(responses.len(), 3, "{responses:?}");
+    let start = position_of(&content, "UserProfile {");
+    let mut end = start.clone();
+    end["character"] = json!(start["character"].as_u64().unwrap_or_default() + 11);
+    assert_eq!(
+        responses[1],
+        json!({
+            "jsonrpc": "2.0",
+            "id": 2,
+            "result": [{
+                "name": "Query.UserProfile",
+                "kind": 6,
+                "location": { "uri": uri, "range": { "start": start, "end": end } },
+            }],
+        })
+    );
+    assert_eq!(
+        responses[2],
+        json!({ "jsonrpc": "2.0", "id": 3, "result": [] })
+    );
+    Ok(())
+}
+
+#[test]
+fn test_semantic_tokens_are_encoded_relative_to_each_other() -> Result<(), Box<dyn Error>> {
+    let config = create_config(copy_fixture_project("language_server_semantic_tokens")?);
+    let uri = user_profile_uri(&config);
+    let content = "export const UserProfile = iso(`\n  \"\"\"\n  A user\n  \"\"\"\n  \
+        field User.UserProfile($size: Int) {\n    \
+        avatar_url(size: $size) @loadable\n  \
+        }\n\
+        `);\n";
+    let responses = exchange_messages(
+        &config,
+        &[
+            initialize(),
+            did_open(&uri, content),
+            json!({
+                "jsonrpc": "2.0",
+                "id": 2,
+                "method": "textDocument/semanticTokens/full",
+                "params": { "textDocument": { "uri": uri } },
+            }),
+        ],
+    );
+    fs::remove_dir_all(project_directory(&config))?;
+    let responses = responses?;
+
+    assert_eq!(responses.len(), 2, "{responses:?}");
+    // Each token is its line and start character, relative to the previous token
+    // (the start character only if it is on the same line), its length, its type
+    // (an index into the legend) and its modifiers.
+    assert_eq!(
+        responses[1]["result"]["data"],
+        json!([
+            // The block string is split into one token per line
+            1, 2, 3, 7, 0, //
+            1, 0, 8, 7, 0, //
+            1, 0, 5, 7, 0, //
+            // field User.UserProfile($size: Int)
+            1, 2, 5, 0, 0, //
+            0, 6, 4, 1, 0, //
+            0, 5, 11, 2, 0, //
+            0, 12, 5, 4, 0, //
+            0, 7, 3, 1, 0, //
+            // avatar_url(size: $size) @loadable
+            1, 4, 10, 2, 0, //
+            0, 11, 4, 3, 0, //
+            0, 6, 5, 4, 0, //
+            0, 7, 9, 5, 0, //
+        ])
+    );
+    Ok(())
+}
+
+#[test]
+fn test_progress_of_revalidation_is_reported() -> Result<(), Box<dyn Error>> {
+    let config = create_config(copy_fixture_project("language_server_progress")?);
+    let uri = user_profile_uri(&config);
+    let content = fs::read_to_string(config.project_root.join("UserProfile.tsx"))?;
+    // The responses of the editor to the requests to create progress tokens, which
+    // the language server waits for before reporting progress.
+    let create_response = |id: u64| json!({ "jsonrpc": "2.0", "id": id, "result": null });
+    let responses = exchange_messages(
+        &config,
+        &[
+            json!({
+                "jsonrpc": "2.0",
+                "id": "initialize",
+                "method": "initialize",
+                "params": { "capabilities": { "window": { "workDoneProgress": true } } },
+            }),
+            initialized(),
+            create_response(1),
+            did_open(&uri, &content),
+            did_change(&uri, 2, &content.replace("      name\n", "      nme\n")),
+            create_response(2),
+        ],
+    );
+    fs::remove_dir_all(project_directory(&config))?;
+    let responses = responses?;
+
+    let progress = |token: &str, value: Value| {
+        json!({
+            "jsonrpc": "2.0",
+            "method": "$/progress",
+            "params": { "token": token, "value": value },
+        })
+    };
+    let create_request = |id: u64, token: &str| {
+        json!({
+            "jsonrpc": "2.0",
+            "id": id,
+            "method": "window/workDoneProgress/create",
+            "params": { "token": token },
+        })
+    };
+    let begin = json!({ "kind": "begin", "title": "Validating the project" });
+
+    assert_eq!(responses.len(), 8, "{responses:?}");
+    assert_eq!(responses[0]["id"], json!("initialize"));
+    assert_eq!(responses[1], create_request(1, "isograph/1"));
+    assert_eq!(responses[2], progress("isograph/1", begin.clone()));
+    assert_eq!(
+        responses[3],
+        progress(
+            "isograph/1",
+            json!({ "kind": "end", "message": "No errors" })
+        )
+    );
+    // The diagnostics are published between the start and end of the progress.
+    assert_eq!(responses[4], create_request(2, "isograph/2"));
+    assert_eq!(responses[5], progress("isograph/2", begin));
+    assert_eq!(
+        responses[6]["method"],
+        json!("textDocument/publishDiagnostics")
+    );
+    assert_eq!(
+        responses[7],
+        progress(
+            "isograph/2",
+            json!({ "kind": "end", "message": "Found 1 error" })
+        )
+    );
+    Ok(())
+}
@@ -0,0 +1,112 @@
+use std::{error::Error, fs, path::PathBuf};
+
+use isograph_compiler::{create_config, handle_compile_command};
+use tests::project_directory;
+
+static FIXTURE_DIRECTORY: &str = concat!(
+    env!("CARGO_MANIFEST_DIR"),
+    "/tests/fixtures/named_fragments_project"
+);
+
+static FIXTURE_FILES: [&str; 4] = [
+    "schema.graphql",
+    "schema-extension.graphql",
+    "src/UserCard.tsx",
+    "src/UserProfile.tsx",
+];
+
+/// Copies the fixture project into a fresh directory, and returns the path to its
+/// config.
+fn copy_fixture_project(
+    name: &str,
+    generate_named_fragments: bool,
+) -> Result<PathBuf, Box<dyn Error>> {
+    tests::copy_fixture_project(
+        name,
+        FIXTURE_DIRECTORY,
+        &FIXTURE_FILES.map(|fixture_file| (fixture_file, fixture_file)),
+        &format!(
+            r#"{{
+  "project_root": "./src",
+  "artifact_directory": "./",
+  "schema": "./schema.graphql",
+  "schema_extensions": ["./schema-extension.graphql"],
+  "options": {{
+    "generate_named_fragments": {generate_named_fragments}
+  }}
+}}"#
+        ),
+    )
+}
+
+/// Compiles the fixture project and returns the query texts of the entrypoint, of
+/// the refetch query and of the mutation, in that order.
+fn compile_operations(
+    name: &str,
+    generate_named_fragments: bool,
+) -> Result<[String; 3], Box<dyn Error>> {
+    let config = create_config(copy_fixture_project(name, generate_named_fragments)?);
+    let project_directory = project_directory(&config);
+    handle_compile_command(&config, &[]).item?;
+
+    let entrypoint_directory = config.artifact_directory.join("Query/UserProfile");
+    let entrypoint = fs::read_to_string(entrypoint_directory.join("entrypoint.ts"))?;
+    let mut refetch_query = None;
+    let mut mutation = None;
+    for entry in fs::read_dir(&entrypoint_directory)? {
+        let path = entry?.path();
+        let contents = fs::read_to_string(&path)?;
+        if contents.contains("query User_refetch") {
+            refetch_query = Some(contents);
+        } else if contents.contains("mutation Userset_user_name") {
+            mutation = Some(contents);
+        }
+    }
+    fs::remove_dir_all(&project_directory)?;
+
+    Ok([
+        entrypoint,
+        refetch_query.expect("Expected a refetch query artifact"),
+        mutation.expect("Expected a mutation artifact"),
+    ])
+}
+
+#[test]
+fn test_repeated_subtrees_are_spread_in_every_operation() -> Result<(), Box<dyn Error>> {
+    let operations = compile_operations("named_fragments", true)?;
+
+    // best_friend and worst_enemy select the same fields, so their selection sets
+    // are printed once, as a fragment that each of them spreads.
+    for (operation, operation_name) in
+        operations
+            .iter()
+            .zip(["UserProfile", "User_refetch", "Userset_user_name"])
+    {
+        let fragment_name = format!("{operation_name}__User__0");
+        assert_eq!(
+            operation.matches(&format!("...{fragment_name}")).count(),
+            2,
+            "{operation}"
+        );
+        assert_eq!(
+            operation
+                .matches(&format!("fragment {fragment_name} on User {{"))
+                .count(),
+            1,
+            "{operation}"
+        );
+        assert!(!operation.contains("__User__1"), "{operation}");
+    }
+    Ok(())
+}
+
+#[test]
+fn test_subtrees_are_inlined_without_generate_named_fragments() -> Result<(), Box<dyn Error>> {
+    let operations = compile_operations("named_fragments_disabled", false)?;
+
+    for operation in &operations {
+        assert!(!operation.contains("fragment "), "{operation}");
+        assert!(!operation.contains("...User"), "{operation}");
+    }
+    Ok(())
+}
@@ -0,0 +1,90 @@
+// The post_emit_command of these tests is a shell script
+#![cfg(unix)]
+
+use std::{error::Error, fs, path::PathBuf};
+
+use isograph_compiler::{create_config, handle_compile_command};
+use tests::project_directory;
+
+static FIXTURE_DIRECTORY: &str = concat!(
+    env!("CARGO_MANIFEST_DIR"),
+    "/tests/fixtures/compilation_cache_project"
+);
+
+static FIXTURE_FILES: [&str; 3] = [
+    "schema.graphql",
+    "src/HomeRoute.tsx",
+    "src/ProfileRoute.tsx",
+];
+
+/// Appends a comment to every file it is passed, like a formatter that rewrites
+/// the artifacts.
+static CONFIG: &str = r#"{
+  "project_root": "./src",
+  "artifact_directory": "./",
+  "schema": "./schema.graphql",
+  "post_emit_command": ["sh", "-c", "for file; do echo '// formatted' >> \"$file\"; done", "sh"]
+}"#;
+
+/// Copies the fixture project into a fresh directory, and returns the path to its
+/// config.
+fn copy_fixture_project(name: &str) -> Result<PathBuf, Box<dyn Error>> {
+    tests::copy_fixture_project(
+        name,
+        FIXTURE_DIRECTORY,
+        &FIXTURE_FILES.map(|fixture_file| (fixture_file, fixture_file)),
+        CONFIG,
+    )
+}
+
+#[test]
+fn test_artifacts_rewritten_by_post_emit_command_are_not_written_again(
+) -> Result<(), Box<dyn Error>> {
+    let config = create_config(copy_fixture_project("post_emit_command")?);
+    let project_directory = project_directory(&config);
+
+    let first_compilation = handle_compile_command(&config, &[]).item?;
+    let second_compilation = handle_compile_command(&config, &[]).item?;
+
+    let profile_route = config.project_root.join("ProfileRoute.tsx");
+    fs::write(
+        &profile_route,
+        fs::read_to_string(&profile_route)?.replace("      name\n", "      name\n      bio\n"),
+    )?;
+    let third_compilation = handle_compile_command(&config, &[]).item?;
+
+    let home_route_reader =
+        fs::read_to_string(config.artifact_directory.join("Query/HomeRoute/reader.ts"))?;
+    fs::remove_dir_all(&project_directory)?;
+
+    assert!(!first_compilation.written_artifact_paths.is_empty());
+
+    // The artifacts differ from the generated contents, since the post_emit_command
+    // rewrote them, but the generated contents did not change, so nothing is written.
+    assert!(
+        second_compilation.written_artifact_paths.is_empty(),
+        "{:?}",
+        second_compilation.written_artifact_paths
+    );
+    assert_eq!(
+        second_compilation.total_artifacts_unchanged,
+        first_compilation.total_artifacts_written
+    );
+
+    // Only the artifacts of the edited client field are written (and passed to the
+    // post_emit_command) again.
+    let profile_route_artifacts = config.artifact_directory.join("Query/ProfileRoute");
+    assert!(!third_compilation.written_artifact_paths.is_empty());
+    assert!(
+        third_compilation
+            .written_artifact_paths
+            .iter()
+            .all(|path| path.starts_with(&profile_route_artifacts)),
+        "{:?}",
+        third_compilation.written_artifact_paths
+    );
+
+    // The post_emit_command only ran once on the artifacts of HomeRoute.
+    assert_eq!(home_route_reader.matches("// formatted").count(), 1);
+    Ok(())
+}
@@ -0,0 +1,115 @@
+use std::{error::Error, fs, path::PathBuf};
+
+use isograph_compiler::{create_config, handle_compile_command};
+use tests::{project_directory, DEFAULT_CONFIG};
+
+static FIXTURE_DIRECTORY: &str = concat!(
+    env!("CARGO_MANIFEST_DIR"),
+    "/tests/fixtures/reserved_names_project"
+);
+
+static FIXTURE_FILES: [&str; 3] = [
+    "schema.graphql",
+    "src/UserResolver.tsx",
+    "src/DefaultRoute.tsx",
+];
+
+/// Copies the fixture project, whose client fields, fields and aliases are named
+/// default, resolver and artifact, into a fresh directory, and returns the path to
+/// its config.
+fn copy_fixture_project(name: &str) -> Result<PathBuf, Box<dyn Error>> {
+    tests::copy_fixture_project(
+        name,
+        FIXTURE_DIRECTORY,
+        &FIXTURE_FILES.map(|fixture_file| (fixture_file, fixture_file)),
+        DEFAULT_CONFIG,
+    )
+}
+
+/// The number of declarations of the identifier in the artifact, whether by import
+/// or by const.
+fn declaration_count(artifact: &str, identifier: &str) -> usize {
+    artifact.matches(&format!("as {identifier} }}")).count()
+        + artifact.matches(&format!("import {identifier} ")).count()
+        + artifact.matches(&format!("const {identifier}:")).count()
+        + artifact.matches(&format!("const {identifier} =")).count()
+}
+
+#[test]
+fn test_names_of_generated_identifiers_are_legal_field_names() -> Result<(), Box<dyn Error>> {
+    let config = create_config(copy_fixture_project("reserved_names")?);
+    handle_compile_command(&config, &[]).item?;
+    let read_artifact = |path: &str| fs::read_to_string(config.artifact_directory.join(path));
+    let user_resolver = read_artifact("User/resolver/reader.ts");
+    let default_route = read_artifact("Query/default/reader.ts");
+    let entrypoint = read_artifact("Query/default/entrypoint.ts");
+    fs::remove_dir_all(project_directory(&config))?;
+    let (user_resolver, default_route, entrypoint) = (user_resolver?, default_route?, entrypoint?);
+
+    // The field and alias are only ever property keys (which may be reserved words
+    // or clash with identifiers) and strings, never identifiers.
+    assert!(
+        user_resolver.contains("  default: (string | null),\n"),
+        "{user_resolver}"
+    );
+    assert!(
+        user_resolver.contains("  artifact: (string | null),\n"),
+        "{user_resolver}"
+    );
+    assert!(
+        user_resolver.contains("fieldName: \"default\""),
+        "{user_resolver}"
+    );
+    assert!(
+        user_resolver.contains("alias: \"artifact\""),
+        "{user_resolver}"
+    );
+
+    // The function that is exported as resolver is imported under the name of the
+    // generated identifier, which is declared once.
+    assert!(
+        user_resolver.contains("import { resolver as resolver } from '"),
+        "{user_resolver}"
+    );
+    assert_eq!(
+        declaration_count(&user_resolver, "resolver"),
+        1,
+        "{user_resolver}"
+    );
+    assert_eq!(
+        declaration_count(&user_resolver, "artifact"),
+        1,
+        "{user_resolver}"
+    );
+
+    // Selected client fields are imported as Type__field, and the types of client
+    // fields are named Type__field__param and Type__field__outputType, so their names
+    // never clash with generated identifiers, nor are used as identifiers by
+    // themselves.
+    assert!(
+        default_route.contains("import User__resolver from '../../User/resolver/reader';"),
+        "{default_route}"
+    );
+    assert!(
+        default_route.contains("export type Query__default__param = "),
+        "{default_route}"
+    );
+    assert_eq!(
+        declaration_count(&default_route, "resolver"),
+        1,
+        "{default_route}"
+    );
+    assert_eq!(
+        declaration_count(&default_route, "default"),
+        0,
+        "{default_route}"
+    );
+    assert!(
+        entrypoint.contains(
+            "import type {Query__default__param, Query__default__outputType} from './reader';"
+        ),
+        "{entrypoint}"
+    );
+    assert_eq!(declaration_count(&entrypoint, "default"), 0, "{entrypoint}");
+    Ok(())
+}
@@ -0,0 +1,74 @@
+use std::{error::Error, fs, path::PathBuf};
+
+use isograph_compiler::{create_config, handle_compile_command};
+use tests::project_directory;
+
+static FIXTURE_DIRECTORY: &str = concat!(
+    env!("CARGO_MANIFEST_DIR"),
+    "/tests/fixtures/schema_stitching_project"
+);
+
+/// Copies the schema, the given stitched schema and the source file of the fixture
+/// project into a fresh directory, and returns the path to its config.
+fn copy_fixture_project(name: &str, stitched_schema: &str) -> Result<PathBuf, Box<dyn Error>> {
+    tests::copy_fixture_project(
+        name,
+        FIXTURE_DIRECTORY,
+        &[
+            ("schema.graphql", "schema.graphql"),
+            (stitched_schema, "stitched_schema.graphql"),
+            ("UserProfile.tsx", "src/UserProfile.tsx"),
+        ],
+        r#"{
+  "project_root": "./src",
+  "artifact_directory": "./",
+  "schema": "./schema.graphql",
+  "stitched_schemas": [{ "path": "./stitched_schema.graphql" }]
+}"#,
+    )
+}
+
+#[test]
+fn test_stitched_schema_is_merged_into_schema() -> Result<(), Box<dyn Error>> {
+    let config = create_config(copy_fixture_project(
+        "schema_stitching_merge",
+        "reviews.graphql",
+    )?);
+    let result = handle_compile_command(&config, &[]).item;
+    let entrypoint = fs::read_to_string(
+        config
+            .artifact_directory
+            .join("Query/UserProfile/entrypoint.ts"),
+    );
+    fs::remove_dir_all(project_directory(&config))?;
+    result?;
+    let entrypoint = entrypoint?;
+
+    // The root type of the stitched schema is merged into Query, its User type is
+    // merged into User, and its Review type is added to the schema.
+    assert!(entrypoint.contains("top_reviews {"), "{entrypoint}");
+    assert_eq!(entrypoint.matches("reviews {").count(), 2, "{entrypoint}");
+    assert!(entrypoint.contains("body,"), "{entrypoint}");
+    Ok(())
+}
+
+#[test]
+fn test_conflicting_field_definition_is_rejected() -> Result<(), Box<dyn Error>> {
+    let config = create_config(copy_fixture_project(
+        "schema_stitching_conflict",
+        "conflicting_reviews.graphql",
+    )?);
+    let result = handle_compile_command(&config, &[]).item;
+    fs::remove_dir_all(project_directory(&config))?;
+    let error = match result {
+        Ok(_) => panic!("Expected the stitched schema to conflict with the schema"),
+        Err(error) => error.to_string(),
+    };
+
+    // Both schemas define User.name, so the types cannot be merged.
+    assert!(
+        error.contains("Duplicate field named \"name\" on type \"User\""),
+        "{error}"
+    );
+    Ok(())
+}
@@ -0,0 +1,108 @@
+use std::{
+    error::Error,
+    fs,
+    path::{Path, PathBuf},
+};
+
+use isograph_compiler::{create_config, handle_compile_command};
+use tests::project_directory;
+
+static FIXTURE_DIRECTORY: &str = concat!(
+    env!("CARGO_MANIFEST_DIR"),
+    "/tests/fixtures/shared_query_text_project"
+);
+
+/// Copies the fixture project into a fresh directory, and returns the path to its
+/// config.
+fn copy_fixture_project(name: &str) -> Result<PathBuf, Box<dyn Error>> {
+    // The artifacts of ProfileRoute are namespaced, i.e. they are generated in
+    // design_system.Query/ProfileRoute/
+    tests::copy_fixture_project(
+        name,
+        FIXTURE_DIRECTORY,
+        &[
+            ("schema.graphql", "schema.graphql"),
+            ("src/UserCard.tsx", "src/UserCard.tsx"),
+            ("src/HomeRoute.tsx", "src/HomeRoute.tsx"),
+            (
+                "src/design-system/ProfileRoute.tsx",
+                "src/design-system/ProfileRoute.tsx",
+            ),
+        ],
+        r#"{
+  "project_root": "./src",
+  "artifact_directory": "./",
+  "schema": "./schema.graphql",
+  "packages": {
+    "design_system": "./src/design-system"
+  }
+}"#,
+    )
+}
+
+/// Returns the paths of the files in directory whose name starts with prefix.
+fn find_files(directory: &Path, prefix: &str, files: &mut Vec<PathBuf>) {
+    for entry in fs::read_dir(directory).expect("Expected artifact directory to be readable") {
+        let path = entry.expect("Expected directory entry").path();
+        if path.is_dir() {
+            find_files(&path, prefix, files);
+        } else if path
+            .file_name()
+            .and_then(|file_name| file_name.to_str())
+            .map(|file_name| file_name.starts_with(prefix))
+            == Some(true)
+        {
+            files.push(path);
+        }
+    }
+}
+
+#[test]
+fn test_entrypoints_import_shared_query_text_module() -> Result<(), Box<dyn Error>> {
+    let config = create_config(copy_fixture_project("shared_query_texts")?);
+    let project_directory = project_directory(&config);
+
+    handle_compile_command(&config, &[]).item?;
+
+    // Both entrypoints reach UserCard, which selects __refetch, so each has a refetch
+    // artifact, and their query texts are identical.
+    let mut refetch_artifacts = vec![];
+    find_files(
+        &config.artifact_directory,
+        "__refetch",
+        &mut refetch_artifacts,
+    );
+    refetch_artifacts.sort();
+    assert_eq!(refetch_artifacts.len(), 2, "{refetch_artifacts:?}");
+
+    let mut shared_query_text_modules = vec![];
+    find_files(
+        &config.artifact_directory.join("__queryText"),
+        "",
+        &mut shared_query_text_modules,
+    );
+    assert_eq!(
+        shared_query_text_modules.len(),
+        1,
+        "{shared_query_text_modules:?}"
+    );
+    let shared_query_text_module = shared_query_text_modules[0].canonicalize()?;
+
+    for refetch_artifact in &refetch_artifacts {
+        let contents = fs::read_to_string(refetch_artifact)?;
+        let import_path = contents
+            .lines()
+            .find_map(|line| line.strip_prefix("import queryText from '"))
+            .and_then(|line| line.strip_suffix("';"))
+            .unwrap_or_else(|| panic!("Expected {refetch_artifact:?} to import its query text"));
+        let imported_module = refetch_artifact
+            .parent()
+            .expect("Expected artifact to be in a directory")
+            .join(format!("{import_path}.ts"))
+            .canonicalize()?;
+        assert_eq!(imported_module, shared_query_text_module);
+    }
+
+    fs::remove_dir_all(&project_directory)?;
+    Ok(())
+}
@@ -0,0 +1,87 @@
+use std::{error::Error, fs, path::PathBuf};
+
+use isograph_compiler::{create_config, handle_compile_command};
+use tests::{compile_error, project_directory, DEFAULT_CONFIG};
+
+static FIXTURE_DIRECTORY: &str = concat!(
+    env!("CARGO_MANIFEST_DIR"),
+    "/tests/fixtures/skip_include_project"
+);
+
+/// Copies the schema and the source file in the given directory of the fixture project
+/// into a fresh directory, and returns the path to its config.
+fn copy_fixture_project(name: &str, source_directory: &str) -> Result<PathBuf, Box<dyn Error>> {
+    let source_file = format!("{source_directory}/UserProfile.tsx");
+    tests::copy_fixture_project(
+        name,
+        FIXTURE_DIRECTORY,
+        &[
+            ("schema.graphql", "schema.graphql"),
+            (&source_file, "src/UserProfile.tsx"),
+        ],
+        DEFAULT_CONFIG,
+    )
+}
+
+#[test]
+fn test_selections_with_literal_conditions_are_pruned() -> Result<(), Box<dyn Error>> {
+    let config = create_config(copy_fixture_project(
+        "skip_include_literal_conditions",
+        "literal_conditions",
+    )?);
+    handle_compile_command(&config, &[]).item?;
+
+    let entrypoint = fs::read_to_string(
+        config
+            .artifact_directory
+            .join("Query/UserProfile/entrypoint.ts"),
+    )?;
+    let reader = fs::read_to_string(
+        config
+            .artifact_directory
+            .join("Query/UserProfile/reader.ts"),
+    )?;
+    fs::remove_dir_all(project_directory(&config))?;
+
+    for artifact in [&entrypoint, &reader] {
+        assert!(artifact.contains("name"), "{artifact}");
+        assert!(artifact.contains("email"), "{artifact}");
+        assert!(!artifact.contains("phone_number"), "{artifact}");
+        assert!(!artifact.contains("avatar_url"), "{artifact}");
+        // The directives are applied at compile time, so they are not sent to the server.
+        assert!(!artifact.contains("@include"), "{artifact}");
+        assert!(!artifact.contains("@skip"), "{artifact}");
+    }
+    Ok(())
+}
+
+#[test]
+fn test_variable_conditions_are_rejected() -> Result<(), Box<dyn Error>> {
+    let error = compile_error(copy_fixture_project(
+        "skip_include_variable_condition",
+        "variable_condition",
+    )?)?;
+    assert!(
+        error.contains(
+            "the field `User.avatar_url` is selected with @skip, but the `if` argument of \
+            @skip must be a literal boolean"
+        ),
+        "{error}"
+    );
+    Ok(())
+}
+
+#[test]
+fn test_unknown_selection_directives_are_rejected() -> Result<(), Box<dyn Error>> {
+    let error = compile_error(copy_fixture_project(
+        "skip_include_unknown_directive",
+        "unknown_directive",
+    )?)?;
+    assert!(
+        error.contains(
+            "the field `User.name` is selected with @uppercase, but that directive does not exist"
+        ),
+        "{error}"
+    );
+    Ok(())
+}
@@ -0,0 +1,196 @@
+use common_lang_types::TextSource;
+use graphql_schema_parser::{parse_schema, parse_schema_extensions};
+use intern::string_key::Intern;
+use isograph_schema::validate_directives_and_default_values;
+use std::error::Error;
+
+static DEFINITIONS: &str = "
+directive @cacheControl(maxAge: Int!, scope: CacheScope = PUBLIC) on FIELD_DEFINITION | OBJECT
+directive @tag(name: String!) repeatable on FIELD_DEFINITION
+directive @filter(input: FilterInput) on FIELD_DEFINITION
+
+enum CacheScope {
+  PUBLIC
+  PRIVATE
+}
+
+input FilterInput {
+  query: String!
+  limit: Int
+}
+";
+
+fn text_source() -> TextSource {
+    TextSource {
+        path: "dummy".intern().into(),
+        span: None,
+    }
+}
+
+/// Validates the directives in the given type definitions and schema extension,
+/// which can use the directives, enum and input object in DEFINITIONS, and returns
+/// the error message, if any.
+fn validate(type_definitions: &str, extension: &str) -> Result<Option<String>, Box<dyn Error>> {
+    let type_system_document =
+        parse_schema(&format!("{DEFINITIONS}{type_definitions}"), text_source())
+            .map_err(|e| e.item)?;
+    let type_extension_document =
+        parse_schema_extensions(extension, text_source()).map_err(|e| e.item)?;
+    Ok(
+        validate_directives_and_default_values(&type_system_document, &[type_extension_document])
+            .err()
+            .map(|error| error.item.to_string()),
+    )
+}
+
+fn validation_error(type_definitions: &str) -> Result<String, Box<dyn Error>> {
+    Ok(validate(type_definitions, "")?
+        .unwrap_or_else(|| panic!("Expected {type_definitions} to be invalid")))
+}
+
+#[test]
+fn test_valid_directives_are_accepted() -> Result<(), Box<dyn Error>> {
+    let error = validate(
+        r#"
+type Query @cacheControl(maxAge: 10, scope: PRIVATE) {
+  me: User @tag(name: "a") @tag(name: "b") @filter(input: { query: "x", limit: 1 })
+  users(first: Int = 10, filter: FilterInput = { query: "y" }): [User] @deprecated
+}
+
+type User {
+  id: ID! @internal
+}
+"#,
+        "extend type User @cacheControl(maxAge: 1)",
+    )?;
+    assert_eq!(error, None);
+    Ok(())
+}
+
+#[test]
+fn test_unknown_directive_is_rejected() -> Result<(), Box<dyn Error>> {
+    let error = validation_error("type Query { me: String @unknown }")?;
+    assert!(
+        error.contains("The directive `@unknown` is not defined."),
+        "{error}"
+    );
+    Ok(())
+}
+
+#[test]
+fn test_unknown_directive_in_extension_is_rejected() -> Result<(), Box<dyn Error>> {
+    let error = validate("type Query { me: String }", "extend type Query @unknown")?
+        .expect("Expected the extension to be invalid");
+    assert!(
+        error.contains("The directive `@unknown` is not defined."),
+        "{error}"
+    );
+    Ok(())
+}
+
+#[test]
+fn test_directive_at_wrong_location_is_rejected() -> Result<(), Box<dyn Error>> {
+    let error = validation_error(r#"type Query @tag(name: "a") { me: String }"#)?;
+    assert!(
+        error.contains("The directive `@tag` cannot be used at location OBJECT."),
+        "{error}"
+    );
+    Ok(())
+}
+
+#[test]
+fn test_unknown_directive_argument_is_rejected() -> Result<(), Box<dyn Error>> {
+    let error = validation_error("type Query { me: String @cacheControl(maxAge: 1, max_age: 2) }")?;
+    assert!(
+        error
+            .contains("The directive `@cacheControl` does not accept an argument named `max_age`."),
+        "{error}"
+    );
+    Ok(())
+}
+
+#[test]
+fn test_missing_directive_argument_is_rejected() -> Result<(), Box<dyn Error>> {
+    let error = validation_error("type Query { me: String @cacheControl }")?;
+    assert!(
+        error.contains("The directive `@cacheControl` requires an argument named `maxAge`."),
+        "{error}"
+    );
+    Ok(())
+}
+
+#[test]
+fn test_repeated_non_repeatable_directive_is_rejected() -> Result<(), Box<dyn Error>> {
+    let error = validation_error(
+        "type Query { me: String @cacheControl(maxAge: 1) @cacheControl(maxAge: 2) }",
+    )?;
+    assert!(
+        error.contains("The directive `@cacheControl` is not repeatable"),
+        "{error}"
+    );
+    Ok(())
+}
+
+#[test]
+fn test_argument_of_wrong_type_is_rejected() -> Result<(), Box<dyn Error>> {
+    let error = validation_error(r#"type Query { me: String @cacheControl(maxAge: "ten") }"#)?;
+    assert!(
+        error.contains("Expected a value of type `Int!`."),
+        "{error}"
+    );
+    Ok(())
+}
+
+#[test]
+fn test_default_value_of_wrong_type_is_rejected() -> Result<(), Box<dyn Error>> {
+    let error = validation_error(r#"type Query { users(first: Int = "ten"): [String] }"#)?;
+    assert!(error.contains("Expected a value of type `Int`."), "{error}");
+    Ok(())
+}
+
+#[test]
+fn test_unknown_enum_value_is_rejected() -> Result<(), Box<dyn Error>> {
+    let error =
+        validation_error("type Query { me: String @cacheControl(maxAge: 1, scope: PRIVTE) }")?;
+    assert!(
+        error
+            .contains("The enum `CacheScope` has no value named `PRIVTE`. Did you mean `PRIVATE`?"),
+        "{error}"
+    );
+    Ok(())
+}
+
+#[test]
+fn test_unknown_input_object_field_is_rejected() -> Result<(), Box<dyn Error>> {
+    let error =
+        validation_error(r#"type Query { me: String @filter(input: { query: "x", limt: 1 }) }"#)?;
+    assert!(
+        error.contains("The input object `FilterInput` has no field named `limt`."),
+        "{error}"
+    );
+    Ok(())
+}
+
+#[test]
+fn test_duplicate_input_object_field_is_rejected() -> Result<(), Box<dyn Error>> {
+    let error = validation_error(
+        r#"type Query { me: String @filter(input: { query: "x", query: "y" }) }"#,
+    )?;
+    assert!(
+        error.contains(
+            "The field `query` of the input object `FilterInput` is specified more than once."
+        ),
+        "{error}"
+    );
+    Ok(())
+}
+
+#[test]
+fn test_missing_input_object_field_is_rejected() -> Result<(), Box<dyn Error>> {
+    let error = validation_error("type Query { me: String @filter(input: { limit: 1 }) }")?;
+    assert!(
+        error.contains("The input object `FilterInput` requires a field named `query`."),
+        "{error}"
+    );
+    Ok(())
+}
@@ -20,7 +20,16 @@ macro_rules! u32_newtype {
 
         impl From<usize> for $named {
             fn from(other: usize) -> Self {
-                Self(other as u32)
+                // Truncating would silently produce an id that points at another item.
+                // Callers that may encounter large indexes should use try_from_usize.
+                Self::try_from_usize(other).unwrap_or_else(|| {
+                    panic!(
+                        "Expected {} to fit in a {}. This indicates that the schema \
+                        is too large, or a bug in Isograph.",
+                        other,
+                        stringify!($named)
+                    )
+                })
             }
         }
 
@@ -36,6 +45,11 @@ macro_rules! u32_newtype {
             pub fn as_usize(&self) -> usize {
                 self.0 as usize
             }
+
+            /// Returns None if other does not fit in a u32.
+            pub fn try_from_usize(other: usize) -> Option<Self> {
+                u32::try_from(other).ok().map(Self)
+            }
         }
     };
 }
@@ -17,7 +17,7 @@ export const IsStarred = iso(`
 });
 
 export const RepositoryDetail = iso(`
-  field Query.RepositoryDetail @component {
+  field Query.RepositoryDetail($repositoryName: String!, $repositoryOwner: String!, $first: Int!) @component {
     repository(name: $repositoryName, owner: $repositoryOwner) {
       IsStarred
       nameWithOwner
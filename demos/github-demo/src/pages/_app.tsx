@@ -6,7 +6,10 @@ import {
   IsographEnvironmentProvider,
 } from '@isograph/react';
 
-function makeNetworkRequest<T>(queryText: string, variables: any): Promise<T> {
+function makeNetworkRequest<T>(
+  queryText: string | null,
+  variables: any,
+): Promise<T> {
   let promise = fetch('https://api.github.com/graphql', {
     method: 'POST',
     headers: {
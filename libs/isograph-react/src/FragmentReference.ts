@@ -5,6 +5,8 @@ import { ReaderArtifact } from './reader';
 // TODO type this better
 export type Variable = any;
 
+export type Variables = { [index: string]: Variable };
+
 export type FragmentReference<
   TReadFromStore extends Object,
   TResolverResult,
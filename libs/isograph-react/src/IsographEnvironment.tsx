@@ -32,9 +32,17 @@ export type MissingFieldHandler = (
   variables: { [index: string]: any } | null,
 ) => Link | undefined;
 
+// If the query is persisted, queryText is null and the hash of the query
+// text should be sent instead.
+//
+// The promise resolves with a single JSON response. If the query uses @defer
+// or @stream, the network function must collect the subsequent payloads of a
+// multipart response into its incremental array before resolving; payloads
+// are not normalized as they arrive.
 export type IsographNetworkFunction = (
-  queryText: string,
+  queryText: string | null,
   variables: object,
+  persistedQueryHash?: string,
 ) => Promise<any>;
 
 export type Link = {
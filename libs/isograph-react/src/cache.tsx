@@ -9,11 +9,13 @@ import {
   ArgumentValue,
   IsographEntrypoint,
   NormalizationAst,
+  NormalizationDefer,
   NormalizationLinkedField,
   NormalizationScalarField,
   ReaderLinkedField,
   ReaderScalarField,
   RefetchQueryArtifactWrapper,
+  assertLink,
 } from './index';
 import {
   DataId,
@@ -83,7 +85,9 @@ export function getOrCreateCacheForArtifact<T>(
   artifact: IsographEntrypoint<any, T>,
   variables: object,
 ): ParentCache<PromiseWrapper<T>> {
-  const cacheKey = artifact.queryText + JSON.stringify(stableCopy(variables));
+  const cacheKey =
+    (artifact.queryText ?? artifact.persistedQueryHash) +
+    JSON.stringify(stableCopy(variables));
   const factory: Factory<PromiseWrapper<T>> = () =>
     makeNetworkRequest<T>(environment, artifact, variables);
   return getOrCreateCache<PromiseWrapper<T>>(environment, cacheKey, factory);
@@ -114,7 +118,11 @@ export function makeNetworkRequest<T>(
   };
   // This should be an observable, not a promise
   const promise = environment
-    .networkFunction(artifact.queryText, variables)
+    .networkFunction(
+      artifact.queryText,
+      variables,
+      artifact.persistedQueryHash,
+    )
     .then((networkResponse) => {
       if (typeof window !== 'undefined' && window.__LOG) {
         console.log('network response', artifact, artifact);
@@ -128,6 +136,15 @@ export function makeNetworkRequest<T>(
           variables,
           artifact.nestedRefetchQueries,
         );
+        for (const incrementalResult of networkResponse.incremental ?? []) {
+          normalizeIncrementalResult(
+            environment,
+            artifact.normalizationAst,
+            incrementalResult,
+            variables,
+            artifact.nestedRefetchQueries,
+          );
+        }
         const retainedQuery = {
           normalizationAst: artifact.normalizationAst,
           variables,
@@ -178,6 +195,21 @@ type NetworkResponseObject = {
   id?: DataId;
 };
 
+// The subsequent payloads of @defer and @stream, which are read from the
+// incremental array of the (single) response that the network function
+// resolves with. For @stream, the path ends with the index of the first item.
+type IncrementalResult =
+  | {
+      label?: string;
+      path: (string | number)[];
+      data: NetworkResponseObject;
+    }
+  | {
+      label?: string;
+      path: (string | number)[];
+      items: NetworkResponseObject[];
+    };
+
 function normalizeData(
   environment: IsographEnvironment,
   normalizationAst: NormalizationAst,
@@ -212,6 +244,144 @@ function normalizeData(
   return encounteredIds;
 }
 
+function normalizeIncrementalResult(
+  environment: IsographEnvironment,
+  normalizationAst: NormalizationAst,
+  incrementalResult: IncrementalResult,
+  variables: Object,
+  nestedRefetchQueries: RefetchQueryArtifactWrapper[],
+) {
+  const isStream = 'items' in incrementalResult;
+  const pathToRecord = isStream
+    ? incrementalResult.path.slice(0, -2)
+    : incrementalResult.path;
+
+  let ast = normalizationAst;
+  let recordId: DataId = ROOT_ID;
+  for (let i = 0; i < pathToRecord.length; i++) {
+    const linkedField = findLinkedField(ast, String(pathToRecord[i]));
+    if (linkedField == null) {
+      throw new Error(
+        'Unexpected path in incremental result. ' +
+          'This is indicative of a bug in Isograph.',
+      );
+    }
+    let value =
+      environment.store[recordId]?.[
+        getParentRecordKey(linkedField, variables as any)
+      ];
+    if (Array.isArray(value)) {
+      i++;
+      value = value[pathToRecord[i] as number];
+    }
+    const link = value === undefined ? null : assertLink(value);
+    if (link == null) {
+      // The record the payload belongs to is not in the store (anymore).
+      return;
+    }
+    ast = linkedField.selections;
+    recordId = link.__link;
+  }
+
+  const record = environment.store[recordId];
+  if (record == null) {
+    return;
+  }
+
+  const encounteredIds = new Set<DataId>();
+  if ('items' in incrementalResult) {
+    const path = incrementalResult.path;
+    const linkedField = findLinkedField(ast, String(path[path.length - 2]));
+    const parentRecordKey =
+      linkedField && getParentRecordKey(linkedField, variables as any);
+    const links = parentRecordKey && record[parentRecordKey];
+    if (
+      linkedField == null ||
+      parentRecordKey == null ||
+      !Array.isArray(links)
+    ) {
+      return;
+    }
+    const firstIndex = path[path.length - 1] as number;
+    // The array in the store is replaced rather than mutated, so that data that
+    // was previously read out of the store (and that subscribers compare with)
+    // is not changed underneath them.
+    const newLinks = links.slice();
+    incrementalResult.items.forEach((item, i) => {
+      newLinks[firstIndex + i] = {
+        __link: normalizeNetworkResponseObject(
+          environment,
+          linkedField,
+          item,
+          recordId,
+          variables as any,
+          firstIndex + i,
+          nestedRefetchQueries,
+          encounteredIds,
+        ),
+      };
+    });
+    record[parentRecordKey] = newLinks;
+  } else {
+    const deferNode = findDefer(ast, incrementalResult.label);
+    if (deferNode == null) {
+      return;
+    }
+    normalizeDataIntoRecord(
+      environment,
+      deferNode.selections,
+      incrementalResult.data,
+      record,
+      recordId,
+      variables as any,
+      nestedRefetchQueries,
+      encounteredIds,
+    );
+  }
+  callSubscriptions(environment);
+}
+
+function findLinkedField(
+  normalizationAst: NormalizationAst,
+  networkResponseKey: string,
+): NormalizationLinkedField | null {
+  for (const normalizationNode of normalizationAst) {
+    if (normalizationNode.kind === 'Linked') {
+      if (getNetworkResponseKey(normalizationNode) === networkResponseKey) {
+        return normalizationNode;
+      }
+    } else if (normalizationNode.kind !== 'Scalar') {
+      const linkedField = findLinkedField(
+        normalizationNode.selections,
+        networkResponseKey,
+      );
+      if (linkedField != null) {
+        return linkedField;
+      }
+    }
+  }
+  return null;
+}
+
+function findDefer(
+  normalizationAst: NormalizationAst,
+  label: string | undefined,
+): NormalizationDefer | null {
+  for (const normalizationNode of normalizationAst) {
+    if (normalizationNode.kind === 'Defer') {
+      if (normalizationNode.label === label) {
+        return normalizationNode;
+      }
+    } else if (normalizationNode.kind === 'InlineFragment') {
+      const deferNode = findDefer(normalizationNode.selections, label);
+      if (deferNode != null) {
+        return deferNode;
+      }
+    }
+  }
+  return null;
+}
+
 export function subscribe(
   environment: IsographEnvironment,
   callback: () => void,
@@ -271,10 +441,59 @@ function normalizeDataIntoRecord(
         );
         break;
       }
+      case 'InlineFragment': {
+        // Inline fragments are normalized into the same record as their parent.
+        if (
+          networkResponseParentRecord['__typename'] === normalizationNode.type
+        ) {
+          normalizeDataIntoRecord(
+            environment,
+            normalizationNode.selections,
+            networkResponseParentRecord,
+            targetParentRecord,
+            targetParentRecordId,
+            variables,
+            nestedRefetchQueries,
+            mutableEncounteredIds,
+          );
+        }
+        break;
+      }
+      case 'Defer': {
+        // Deferred selections are normalized into the same record as their parent,
+        // if they were delivered in this payload.
+        if (
+          isDeferredDataPresent(normalizationNode, networkResponseParentRecord)
+        ) {
+          normalizeDataIntoRecord(
+            environment,
+            normalizationNode.selections,
+            networkResponseParentRecord,
+            targetParentRecord,
+            targetParentRecordId,
+            variables,
+            nestedRefetchQueries,
+            mutableEncounteredIds,
+          );
+        }
+        break;
+      }
     }
   }
 }
 
+function isDeferredDataPresent(
+  normalizationNode: NormalizationDefer,
+  networkResponseParentRecord: NetworkResponseObject,
+): boolean {
+  return normalizationNode.selections.every(
+    (selection) =>
+      (selection.kind !== 'Scalar' && selection.kind !== 'Linked') ||
+      networkResponseParentRecord[getNetworkResponseKey(selection)] !==
+        undefined,
+  );
+}
+
 function normalizeScalarField(
   astNode: NormalizationScalarField,
   networkResponseParentRecord: NetworkResponseObject,
@@ -479,6 +698,9 @@ function getStoreKeyChunkForArgument(
 function getNetworkResponseKey(
   astNode: NormalizationLinkedField | NormalizationScalarField,
 ): string {
+  if (astNode.alias != null) {
+    return astNode.alias;
+  }
   let networkResponseKey = astNode.fieldName;
   const fieldParameters = astNode.arguments;
   if (fieldParameters != null) {
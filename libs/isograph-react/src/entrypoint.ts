@@ -1,40 +1,77 @@
 import { Arguments } from './index';
 import { ReaderArtifact } from './reader';
+import { Variables } from './FragmentReference';
 
 // This type should be treated as an opaque type.
 export type IsographEntrypoint<
   TReadFromStore extends Object,
   TResolverResult,
+  TVariables extends Variables = Variables,
 > = {
   kind: 'Entrypoint';
-  queryText: string;
+  operationKind: OperationKind;
+  // A stable hash of the query text, e.g. for logging, caching or
+  // server allow-lists
+  queryId: string;
+  // null if the query is persisted, in which case the server looks up the
+  // query text by persistedQueryHash
+  queryText: string | null;
+  persistedQueryHash?: string;
   normalizationAst: NormalizationAst;
   readerArtifact: ReaderArtifact<TReadFromStore, TResolverResult>;
   nestedRefetchQueries: RefetchQueryArtifactWrapper[];
+  // Never present at runtime. Generated entrypoints declare the type of their
+  // variables, so that they can be checked when the entrypoint is fetched.
+  __variables?: TVariables;
 };
 
+export type OperationKind = 'Query' | 'Mutation' | 'Subscription';
+
 export type NormalizationAstNode =
   | NormalizationScalarField
-  | NormalizationLinkedField;
+  | NormalizationLinkedField
+  | NormalizationInlineFragment
+  | NormalizationDefer;
 export type NormalizationAst = NormalizationAstNode[];
 
 export type NormalizationScalarField = {
   kind: 'Scalar';
   fieldName: string;
+  // Only present if the alias in the network response cannot be derived
+  // from the field name and arguments.
+  alias?: string;
   arguments: Arguments | null;
 };
 
 export type NormalizationLinkedField = {
   kind: 'Linked';
   fieldName: string;
+  alias?: string;
   arguments: Arguments | null;
   selections: NormalizationAst;
 };
 
+export type NormalizationInlineFragment = {
+  kind: 'InlineFragment';
+  type: string;
+  selections: NormalizationAst;
+};
+
+// Selections that the server delivers in a subsequent payload, identified by
+// the label. They are normalized into the same record as their parent.
+export type NormalizationDefer = {
+  kind: 'Defer';
+  label: string;
+  selections: NormalizationAst;
+};
+
 // This is more like an entrypoint, but one specifically for a refetch query/mutation
 export type RefetchQueryArtifact = {
   kind: 'RefetchQuery';
-  queryText: string;
+  operationKind: OperationKind;
+  queryId: string;
+  queryText: string | null;
+  persistedQueryHash?: string;
   normalizationAst: NormalizationAst;
 };
 
@@ -112,6 +112,16 @@ function recordReachableIdsFromRecord(
           }
         }
 
+        continue;
+      case 'InlineFragment':
+      case 'Defer':
+        recordReachableIdsFromRecord(
+          store,
+          currentRecord,
+          mutableRetainedIds,
+          selection.selections,
+          variables,
+        );
         continue;
       case 'Scalar':
         continue;
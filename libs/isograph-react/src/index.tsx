@@ -4,7 +4,7 @@ import { type PromiseWrapper } from './PromiseWrapper';
 import { DataTypeValue, Link, ROOT_ID } from './IsographEnvironment';
 import { useIsographEnvironment } from './IsographEnvironmentProvider';
 import { IsographEntrypoint, assertIsEntrypoint } from './entrypoint';
-import { FragmentReference, Variable } from './FragmentReference';
+import { FragmentReference, Variables } from './FragmentReference';
 
 export {
   retainQuery,
@@ -38,9 +38,11 @@ export {
   type ReaderArtifact,
   ReaderAst,
   ReaderAstNode,
+  ReaderInlineResolverField,
   ReaderLinkedField,
   ReaderMutationField,
   ReaderRefetchField,
+  ReaderRefinementField,
   ReaderResolverField,
   ReaderResolverVariant,
   ReaderScalarField,
@@ -48,16 +50,19 @@ export {
 export {
   NormalizationAst,
   NormalizationAstNode,
+  NormalizationDefer,
+  NormalizationInlineFragment,
   NormalizationLinkedField,
   NormalizationScalarField,
   IsographEntrypoint,
+  OperationKind,
   assertIsEntrypoint,
   RefetchQueryArtifact,
   RefetchQueryArtifactWrapper,
 } from './entrypoint';
 export { read, readButDoNotEvaluate } from './read';
 export { useResult } from './useResult';
-export { type FragmentReference } from './FragmentReference';
+export { type FragmentReference, type Variables } from './FragmentReference';
 
 export type ExtractSecondParam<T extends (arg1: any, arg2: any) => any> =
   T extends (arg1: any, arg2: infer P) => any ? P : never;
@@ -79,6 +84,10 @@ export type ExtractReadFromStore<Type> =
   Type extends IsographEntrypoint<infer X, any> ? X : never;
 export type ExtractResolverResult<Type> =
   Type extends IsographEntrypoint<any, infer X> ? X : never;
+export type ExtractVariables<Type> =
+  Type extends IsographEntrypoint<any, any, infer X extends Variables>
+    ? X
+    : Variables;
 // Note: we cannot write TEntrypoint extends IsographEntrypoint<any, any, any>, or else
 // if we do not explicitly pass a type, the read out type will be any.
 // We cannot write TEntrypoint extends IsographEntrypoint<never, never, never>, or else
@@ -90,7 +99,7 @@ export function useLazyReference<TEntrypoint>(
     // iso(`...`). At runtime, we confirm that the passed-in `iso` literal is actually
     // an entrypoint.
     | ((_: any) => any),
-  variables: { [key: string]: Variable },
+  variables: ExtractVariables<TEntrypoint>,
 ): {
   queryReference: FragmentReference<
     ExtractReadFromStore<TEntrypoint>,
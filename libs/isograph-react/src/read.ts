@@ -1,7 +1,7 @@
 import { getParentRecordKey, onNextChange } from './cache';
 import { getOrCreateCachedComponent } from './componentCache';
 import { RefetchQueryArtifactWrapper } from './entrypoint';
-import { assertLink, FragmentReference, ReaderAst } from './index';
+import { Arguments, assertLink, FragmentReference, ReaderAst } from './index';
 import {
   DataId,
   defaultMissingFieldHandler,
@@ -24,6 +24,7 @@ export function read<TReadFromStore extends Object, TResolverResult>(
     if (data.kind === 'MissingData') {
       throw onNextChange(environment);
     } else {
+      fragmentReference.readerArtifact.assertReadData?.(data.data);
       // @ts-expect-error This not properly typed yet
       return fragmentReference.readerArtifact.resolver(data.data);
     }
@@ -59,6 +60,7 @@ export function readButDoNotEvaluate<TReadFromStore extends Object>(
   if (response.kind === 'MissingData') {
     throw onNextChange(environment);
   } else {
+    reference.readerArtifact.assertReadData?.(response.data);
     return response.data;
   }
 }
@@ -203,6 +205,35 @@ function readData<TReadFromStore>(
         target[field.alias ?? field.fieldName] = data.data;
         break;
       }
+      case 'Refinement': {
+        const typename = storeRecord['__typename'];
+        if (typename === undefined) {
+          return {
+            kind: 'MissingData',
+            reason: 'No value for __typename on root ' + root,
+          };
+        }
+        if (typename !== field.concreteType) {
+          target[field.alias ?? field.fieldName] = null;
+          break;
+        }
+        const data = readData(
+          environment,
+          field.selections,
+          root,
+          variables,
+          nestedRefetchQueries,
+        );
+        if (data.kind === 'MissingData') {
+          return {
+            kind: 'MissingData',
+            reason: 'Missing data for ' + field.fieldName + ' on root ' + root,
+            nestedReason: data,
+          };
+        }
+        target[field.alias ?? field.fieldName] = data.data;
+        break;
+      }
       case 'RefetchField': {
         const data = readData(
           environment,
@@ -288,13 +319,16 @@ function readData<TReadFromStore>(
           (index) => nestedRefetchQueries[index],
         );
 
+        // Arguments passed to the resolver become the variables of its reader AST.
+        const resolverVariables = applyArguments(field.arguments, variables);
+
         const variant = field.readerArtifact.variant;
         if (variant.kind === 'Eager') {
           const data = readData(
             environment,
             field.readerArtifact.readerAst,
             root,
-            variables,
+            resolverVariables,
             resolverRefetchQueries,
           );
           if (data.kind === 'MissingData') {
@@ -304,6 +338,7 @@ function readData<TReadFromStore>(
               nestedReason: data,
             };
           } else {
+            field.readerArtifact.assertReadData?.(data.data);
             // @ts-expect-error
             target[field.alias] = field.readerArtifact.resolver(data.data);
           }
@@ -313,17 +348,52 @@ function readData<TReadFromStore>(
             root,
             variant.componentName,
             field.readerArtifact,
-            variables,
+            resolverVariables,
             resolverRefetchQueries,
           );
         }
         break;
       }
+      case 'InlineResolver': {
+        const data = readData(
+          environment,
+          field.selections,
+          root,
+          applyArguments(field.arguments, variables),
+          nestedRefetchQueries,
+        );
+        if (data.kind === 'MissingData') {
+          return {
+            kind: 'MissingData',
+            reason: 'Missing data for ' + field.alias + ' on root ' + root,
+            nestedReason: data,
+          };
+        }
+        target[field.alias] = data.data;
+        break;
+      }
     }
   }
   return { kind: 'Success', data: target as any };
 }
 
+function applyArguments(
+  args: Arguments | null,
+  variables: { [index: string]: string },
+): { [index: string]: string } {
+  if (args == null) {
+    return variables;
+  }
+  const result = { ...variables };
+  for (const [argumentName, argumentValue] of args) {
+    result[argumentName] =
+      argumentValue.kind === 'Literal'
+        ? argumentValue.value
+        : variables[argumentValue.name];
+  }
+  return result;
+}
+
 function filterVariables(
   variables: { [index: string]: string },
   allowedVariables: string[],
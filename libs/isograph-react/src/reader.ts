@@ -1,5 +1,6 @@
 // TODO this should probably be at least three distinct types, for @component,
 
+import { RefetchQueryArtifactWrapper } from './entrypoint';
 import { Arguments } from './index';
 
 // non-@component and refetch resolvers
@@ -8,12 +9,21 @@ export type ReaderArtifact<TReadFromStore extends Object, TResolverResult> = {
   readerAst: ReaderAst<TReadFromStore>;
   resolver: (data: TReadFromStore, runtimeProps: any) => TResolverResult;
   variant: ReaderResolverVariant;
+  // Only present if the compiler is configured to generate dev assertions.
+  // Throws if the read data lacks any field selected by the reader AST.
+  assertReadData?: (data: any) => void;
+  // Only present if the compiler is configured to generate refetch queries, and
+  // the client field is on a type with an id field. Its allowedVariables start
+  // with "id", which is the id of the record from which the data was read.
+  refetchQuery?: RefetchQueryArtifactWrapper;
 };
 
 export type ReaderAstNode =
   | ReaderScalarField
   | ReaderLinkedField
+  | ReaderRefinementField
   | ReaderResolverField
+  | ReaderInlineResolverField
   | ReaderRefetchField
   | ReaderMutationField;
 
@@ -34,6 +44,16 @@ export type ReaderLinkedField = {
   arguments: Arguments | null;
 };
 
+// Narrows the current record to concreteType. Reads selections from the same
+// record if its __typename matches, and is null otherwise.
+export type ReaderRefinementField = {
+  kind: 'Refinement';
+  fieldName: string;
+  alias: string | null;
+  concreteType: string;
+  selections: ReaderAst<unknown>;
+};
+
 export type ReaderResolverVariant =
   | { kind: 'Eager' }
   // componentName is the component's cacheKey for getRefReaderByName
@@ -48,6 +68,15 @@ export type ReaderResolverField = {
   usedRefetchQueries: number[];
 };
 
+// An eager resolver without an associated js function, whose selections are
+// inlined into the parent reader AST. It evaluates to the data it reads.
+export type ReaderInlineResolverField = {
+  kind: 'InlineResolver';
+  alias: string;
+  arguments: Arguments | null;
+  selections: ReaderAst<unknown>;
+};
+
 export type ReaderRefetchField = {
   kind: 'RefetchField';
   alias: string;
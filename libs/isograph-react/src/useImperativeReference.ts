@@ -7,6 +7,7 @@ import {
   type FragmentReference,
   ExtractReadFromStore,
   ExtractResolverResult,
+  ExtractVariables,
   ROOT_ID,
   useIsographEnvironment,
   makeNetworkRequest,
@@ -23,7 +24,7 @@ export function useImperativeReference<
         ExtractResolverResult<TEntrypoint>
       >
     | UnassignedState;
-  loadQueryReference: (variables: { [index: string]: string }) => void;
+  loadQueryReference: (variables: ExtractVariables<TEntrypoint>) => void;
 } {
   const { state, setState } =
     useUpdatableDisposableState<
@@ -35,7 +36,7 @@ export function useImperativeReference<
   const environment = useIsographEnvironment();
   return {
     queryReference: state,
-    loadQueryReference: (variables: { [index: string]: string }) => {
+    loadQueryReference: (variables: ExtractVariables<TEntrypoint>) => {
       const [_networkRequest, disposeNetworkRequest] = makeNetworkRequest(
         environment,
         entrypoint,